@@ -28,7 +28,6 @@ fn benchmark_regex(c: &mut Criterion) {
                 // many captures to stress the regex parser
                 patterns: vec![r#"^(?P<addr>\d+\.\d+\.\d+\.\d+) (?P<user>\S+) (?P<auth>\S+) \[(?P<date>\d+/[A-Za-z]+/\d+:\d+:\d+:\d+ [+-]\d{4})\] "(?P<method>[A-Z]+) (?P<uri>[^"]+) HTTP/\d\.\d" (?P<code>\d+) (?P<size>\d+) "(?P<referrer>[^"]+)" "(?P<browser>[^"]+)""#.into()],
                 field: None,
-                drop_failed: true,
                 ..Default::default()
             }
             .build(&GlobalOptions::default())
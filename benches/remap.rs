@@ -131,8 +131,7 @@ fn benchmark_remap(c: &mut Criterion) {
             field: Some("foo".to_string()),
             target_field: Some("bar".to_owned()),
             drop_field: false,
-            drop_invalid: false,
-            overwrite_target: None,
+            ..Default::default()
         }));
 
         let event = {
@@ -294,7 +294,8 @@ fn benchmark_complex(c: &mut Criterion) {
                     "sample",
                     &["parser"],
                     transforms::sample::SampleConfig {
-                        rate: sample_rate,
+                        rate: Some(sample_rate),
+                        ratio: None,
                         key_field: None,
                         exclude: None,
                     },
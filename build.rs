@@ -1,9 +1,39 @@
 fn main() {
     println!("cargo:rerun-if-changed=proto/event.proto");
+    println!("cargo:rerun-if-changed=proto/vector.proto");
+    println!("cargo:rerun-if-changed=proto/loki.proto");
+    println!("cargo:rerun-if-changed=proto/opentelemetry");
     let mut prost_build = prost_build::Config::new();
     prost_build.btree_map(&["."]);
     prost_build
         .compile_protos(&["proto/event.proto"], &["proto/"])
         .unwrap();
+
+    prost_build::Config::new()
+        .compile_protos(&["proto/loki.proto"], &["proto/"])
+        .unwrap();
+
+    let mut prost_build = prost_build::Config::new();
+    prost_build
+        .btree_map(&["."])
+        .extern_path(".event.proto", "crate::event::proto");
+    tonic_build::configure()
+        .compile_with_config(prost_build, &["proto/vector.proto"], &["proto/"])
+        .unwrap();
+
+    tonic_build::configure()
+        .build_client(false)
+        .compile(
+            &[
+                "proto/opentelemetry/proto/common/v1/common.proto",
+                "proto/opentelemetry/proto/resource/v1/resource.proto",
+                "proto/opentelemetry/proto/logs/v1/logs.proto",
+                "proto/opentelemetry/proto/metrics/v1/metrics.proto",
+                "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+            ],
+            &["proto/"],
+        )
+        .unwrap();
     built::write_built_file().expect("Failed to acquire build-time information");
 }
@@ -50,6 +50,76 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		profiling: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether the `profile` query is enabled. It samples which components are
+				using CPU time, and returns the result in a format that can be rendered
+				as a flamegraph. Tracking this adds a small overhead to every component,
+				so it's disabled by default.
+				"""
+		}
+		subscription_buffer_size: {
+			common:   false
+			required: false
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+			description: """
+				The number of results of each subscription buffered for a client that
+				can't keep up. Once the buffer is full, results are dropped according to
+				`subscription_drop_policy`, so that a slow client can't balloon Vector's
+				memory.
+				"""
+		}
+		subscription_drop_policy: {
+			common:   false
+			required: false
+			type: string: {
+				default: "drop_oldest"
+				enum: {
+					drop_oldest: "Drop the oldest buffered result to make room for a new one."
+					drop_newest: "Drop new results until the client catches up."
+				}
+				syntax: "literal"
+			}
+			description: """
+				What to drop once a subscription's buffer is full. The number of dropped
+				results is given to the client in the `extensions.dropped` field of the
+				next result of the subscription it receives, and reported by the
+				`api_subscription_dropped_total` internal metric.
+				"""
+		}
+		keepalive_secs: {
+			common:   false
+			required: false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+			description: """
+				How often subscription websockets are pinged. Connections that haven't
+				heard back from their client in two intervals are closed, which stops
+				their subscriptions. Set to `0` to disable pings.
+				"""
+		}
+		max_connections: {
+			common:   false
+			required: false
+			type: uint: {
+				default: null
+				examples: [100]
+				unit: null
+			}
+			description: """
+				The maximum number of subscription websockets open at once. Further
+				connections are refused with a `503 Service Unavailable` response. By
+				default, the number of connections isn't limited.
+				"""
+		}
 	}
 
 	endpoints: {
@@ -58,6 +128,32 @@ api: {
 				description: """
 					Main endpoint for receiving and processing
 					GraphQL queries.
+
+					The `updateSinkRequestLimits` mutation tunes the
+					`request.rate_limit_num` of a running sink, and caps the
+					concurrency of its requests, without a reload. This helps
+					back off a struggling backend during an incident. Tuned
+					limits apply until a new configuration changes the sink.
+
+					The `fileSourceCheckpoints` query lists the files
+					watched by a running `file` source, with the offset
+					each has been read up to, its size, and the number of
+					bytes yet to be read. This helps debug files that
+					aren't being read, without inspecting checkpoints on
+					disk.
+
+					The `byName` subscription streams every internal metric
+					whose name matches a glob, such as `*_errors_total`, or a
+					regular expression if `regex` is set. Matched metrics
+					include their tags, so that newly added or
+					component-specific metrics can be watched without a
+					dedicated type in the schema.
+
+					The schema supports [Apollo Federation](\(urls.apollo_federation)),
+					so that it can be composed into a federated gateway.
+					The gateway fetches the schema from the `_service { sdl }`
+					query, and resolves sources, transforms and sinks by
+					their `name` key.
 					"""
 				responses: {
 					"200": {
@@ -85,6 +181,22 @@ api: {
 				}
 			}
 		}
+		"/metrics": {
+			GET: {
+				description: """
+					Vector's internal metrics, as reported by the
+					[`internal_metrics`](\(urls.vector_internal_metrics_source))
+					source, in the Prometheus text exposition format,
+					for scraping without configuring a
+					`prometheus_exporter` sink.
+					"""
+				responses: {
+					"200": {
+						description: "The current value of every internal metric."
+					}
+				}
+			}
+		}
 		"/playground": {
 			GET: {
 				description: """
@@ -98,9 +98,13 @@ cli: {
 			_short:      "v"
 			description: "Enable more detailed logging. Repeat to reduce further. Overrides `--verbose`"
 		}
+		"strict-env-vars": {
+			description: "Fail to load configuration referencing unset environment variables, instead of replacing them with an empty string"
+			env_var:     "VECTOR_STRICT_ENV_VARS"
+		}
 		"watch-config": {
 			_short:      "w"
-			description: "Watch for changes in the configuration file, and reload accordingly"
+			description: "Watch for changes in the configuration files and directories, and reload accordingly"
 			env_var:     "VECTOR_WATCH_CONFIG"
 		}
 	}
@@ -155,6 +159,18 @@ cli: {
 			type:    "string"
 			env_var: "VECTOR_CONFIG_YAML"
 		}
+		"config-dir": {
+			_short: "C"
+			description: """
+				Read configuration from the files in one or more directories,
+				such as `/etc/vector/conf.d`. The format of each file is
+				determined from its extension (.toml, .yaml, .json), and files
+				with other extensions are ignored. Components from all files
+				are merged, and components sharing a name are an error.
+				"""
+			type:    "string"
+			env_var: "VECTOR_CONFIG_DIR"
+		}
 		"threads": {
 			_short: "t"
 			description: """
@@ -205,6 +221,58 @@ cli: {
 			description: "Prints this message or the help of the given subcommand(s)"
 		}
 
+		"graph": {
+			description: """
+				Print the topology of the target config in the DOT language of
+				[Graphviz](https://graphviz.org), then exit. Sources, transforms,
+				and sinks are drawn with distinct shapes and colors, with an
+				edge from each component to the components taking it as input.
+				For example: `vector graph --config vector.toml | dot -Tsvg > graph.svg`
+				"""
+
+			flags: _default_flags
+
+			options: {
+				"config": {
+					_short: "c"
+					description: """
+						Any number of Vector config files to graph. If none are specified
+						the default config path `/etc/vector/vector.toml` will be targeted
+						"""
+					type: "string"
+				}
+				"config-toml": {
+					description: """
+						Any number of Vector config files to graph.
+						TOML file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-json": {
+					description: """
+						Any number of Vector config files to graph.
+						JSON file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-yaml": {
+					description: """
+						Any number of Vector config files to graph.
+						YAML file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-dir": {
+					_short: "C"
+					description: """
+						Any number of directories of Vector config files to graph.
+						The format of each file is determined from its extension.
+						"""
+					type: "string"
+				}
+			}
+		}
+
 		"list": {
 			description: "List available components, then exit"
 
@@ -223,6 +291,89 @@ cli: {
 			}
 		}
 
+		"replay": {
+			description: """
+				Replay files previously written by the `file` or `aws_s3` sinks through a
+				Vector config, then exit. The config's own sources are replaced by an
+				internal `replay` source reading the replayed files. The command is only
+				available in builds with the `replay` feature, which the default builds
+				enable.
+				"""
+
+			flags: _default_flags
+
+			options: {
+				"into": {
+					_short: "i"
+					description: """
+						Components (transforms or sinks) to send replayed events into.
+						Components that aren't downstream of these are not started. If none
+						are specified, replayed events are sent into every sink.
+						"""
+					type: "string"
+				}
+				"encoding": {
+					_short: "e"
+					description: """
+						The encoding the archived files were written with. Files encoded as
+						`csv` or `avro` can't be replayed.
+						"""
+					default: "ndjson"
+					enum: {
+						ndjson: "Each line is a JSON encoded event."
+						text:   "Each line is the raw message of an event."
+					}
+				}
+				"compression": {
+					description: "The compression of the archived files"
+					default:     "auto"
+					enum: {
+						auto: "Detect gzip or zstd compression from the file contents."
+						gzip: "Files are gzip compressed."
+						zstd: "Files are zstd compressed."
+						none: "Files are not compressed."
+					}
+				}
+				"config": {
+					_short: "c"
+					description: """
+						Read configuration from one or more files. Wildcard paths are
+						supported. If zero files are specified the default config path
+						`/etc/vector/vector.toml` will be targeted.
+						"""
+					type: "string"
+				}
+				"config-toml": {
+					description: """
+						Read configuration from one or more files. Wildcard paths are
+						supported. TOML file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-json": {
+					description: """
+						Read configuration from one or more files. Wildcard paths are
+						supported. JSON file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-yaml": {
+					description: """
+						Read configuration from one or more files. Wildcard paths are
+						supported. YAML file format is assumed.
+						"""
+					type: "string"
+				}
+			}
+
+			args: {
+				files: {
+					description: "Archived files to replay, in order"
+					type:        "list"
+				}
+			}
+		}
+
 		"test": {
 			description: """
 				Run Vector config unit tests, then exit. This command is experimental and
@@ -295,18 +446,35 @@ cli: {
 					description: "The URL for the GraphQL endpoint of the running Vector instance"
 					type:        "string"
 				}
+				"token": {
+					description: """
+						A token sent as a bearer token to authenticate with the API, such as
+						when it's exposed behind an authenticating proxy. Dropped connections
+						are reconnected with the same token.
+						"""
+					type:    "string"
+					env_var: "VECTOR_API_TOKEN"
+				}
 			}
 		}
 
 		"validate": {
-			description: "Validate the target config, then exit"
+			description: """
+				Validate the target config, then exit. Unless `--no-environment` is
+				passed, components are also built, the addresses they listen on are
+				bound, the data directory is written to, and the health checks of
+				sinks are run, with the result of each check reported. Addresses
+				already in use, such as by a running Vector, are reported as
+				warnings.
+				"""
 
 			flags: _default_flags & {
 				"no-environment": {
 					_short: "ne"
 					description: """
 						Disables environment checks. That includes component
-						checks and health checks
+						checks, checks that the addresses components listen on
+						are free, and health checks
 						"""
 				}
 				"deny-warnings": {
@@ -337,6 +505,14 @@ cli: {
 						"""
 					type: "string"
 				}
+				"config-dir": {
+					_short: "C"
+					description: """
+						Any number of directories of Vector config files to validate.
+						The format of each file is determined from its extension.
+						"""
+					type: "string"
+				}
 			}
 
 			args: {
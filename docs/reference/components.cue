@@ -169,6 +169,7 @@ components: {
 			generate?: #FeaturesGenerate
 			multiline: #FeaturesMultiline
 			encoding?: #FeaturesEncoding
+			codecs?:   #FeaturesCodecs
 			receive?:  #FeaturesReceive
 		}
 
@@ -263,6 +264,15 @@ components: {
 		enabled: bool
 	}
 
+	#FeaturesCodecs: {
+		enabled: bool
+
+		// `default_framing` is the framing method used when the `framing`
+		// option isn't set. Sources that receive pre-framed data, such as
+		// lines of files, leave it unset and have no `framing` option.
+		default_framing?: "bytes" | "newline_delimited"
+	}
+
 	#FeaturesParse: {
 		format: {
 			name:     string
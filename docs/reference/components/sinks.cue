@@ -10,6 +10,13 @@ components: sinks: [Name=string]: {
 	features: _
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Whether sources that support acknowledgements, such as the `kafka` and `splunk_hec` sources, wait for this sink to accept their events before acknowledging them. Events are accepted once the sink acks them, or once they're written to a disk buffer."
+			required:    false
+			type: bool: default: false
+		}
+
 		if features.send != _|_ && features.send.batch != _|_ {
 			if features.send.batch.enabled {
 				batch: {
@@ -79,7 +86,7 @@ components: sinks: [Name=string]: {
 						max_size: {
 							description:   "The maximum size of the buffer on the disk."
 							required:      true
-							relevant_when: "type = \"disk\""
+							relevant_when: "type = \"disk\" or type = \"disk_v2\""
 							type: uint: {
 								examples: [104900000]
 								unit: "bytes"
@@ -92,8 +99,9 @@ components: sinks: [Name=string]: {
 							type: string: {
 								default: "memory"
 								enum: {
-									memory: "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
-									disk:   "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									memory:  "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
+									disk:    "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									disk_v2: "Stores the sink's buffer on disk in append-only segment files, with a checksum for every event. It avoids the compaction stalls of `disk`, and starts up quickly with a large buffer. Events buffered with `disk` aren't carried over when switching to `disk_v2`."
 								}
 								syntax: "literal"
 							}
@@ -132,6 +140,9 @@ components: sinks: [Name=string]: {
 							if list.Contains(features.send.compression.algorithms, "gzip") {
 								gzip: "[Gzip](\(urls.gzip)) standard DEFLATE compression."
 							}
+							if list.Contains(features.send.compression.algorithms, "zstd") {
+								zstd: "[Zstandard](\(urls.zstd)) compression."
+							}
 						}
 						syntax: "literal"
 					}
@@ -195,6 +206,99 @@ components: sinks: [Name=string]: {
 									syntax: "literal"
 								}
 							}
+
+							if features.send.encoding.codec.enabled {
+								if list.Contains(features.send.encoding.codec.enum, "csv") {
+									csv: {
+										common:        false
+										description:   "Options of the `csv` codec, which writes the given fields of each event as a line of delimited values."
+										relevant_when: "codec = `csv`"
+										required:      false
+										type: object: options: {
+											fields: {
+												description: "The fields to write, in order. Missing fields are left empty."
+												required:    true
+												type: array: items: type: string: {
+													examples: ["timestamp", "host", "message"]
+													syntax: "field_path"
+												}
+											}
+											delimiter: {
+												common:      false
+												description: "The character separating values."
+												required:    false
+												type: string: {
+													default: ","
+													examples: ["\t", ";"]
+													syntax: "literal"
+												}
+											}
+											header: {
+												common:      false
+												description: "Whether to start each file, or request, with a line of the field names. Not supported by sinks that compress batches as they're built, such as `aws_s3`."
+												required:    false
+												type: bool: default: false
+											}
+										}
+									}
+								}
+
+								if list.Contains(features.send.encoding.codec.enum, "avro") {
+									schema: {
+										common:        false
+										description:   "The Avro schema, as JSON, that events are encoded with. Events that don't match the schema are dropped. Files and batches are written as [Avro container files](\(urls.avro_container_files)), while messages are written as bare Avro datums."
+										relevant_when: "codec = `avro`"
+										required:      false
+										type: string: {
+											default: null
+											examples: [#"{"type": "record", "name": "log", "fields": [{"name": "message", "type": "string"}]}"#]
+											syntax: "literal"
+										}
+									}
+								}
+
+								if list.Contains(features.send.encoding.codec.enum, "protobuf") {
+									protobuf: {
+										common:        false
+										description:   "Options of the `protobuf` codec, which encodes each event as a [Protocol Buffers](\(urls.protobuf)) message. Fields of the event are mapped to the fields of the message with the same name, nested messages are encoded from maps, and repeated fields from arrays. Events that don't match the message type are dropped."
+										relevant_when: "codec = `protobuf`"
+										required:      false
+										type: object: options: {
+											descriptor_set_path: {
+												description: "The path of a compiled descriptor set holding the message type, as written by `protoc --include_imports --descriptor_set_out`."
+												required:    true
+												type: string: {
+													examples: ["/etc/vector/events.desc"]
+													syntax: "literal"
+												}
+											}
+											message_type: {
+												description: "The full name of the message type that events are encoded as."
+												required:    true
+												type: string: {
+													examples: ["package.Event"]
+													syntax: "literal"
+												}
+											}
+										}
+									}
+								}
+
+								// Sinks supporting the `csv` codec also support templated text.
+								if list.Contains(features.send.encoding.codec.enum, "csv") {
+									template: {
+										common:        false
+										description:   "A template to render each event as a line of text, instead of writing its message. Events the template can't be rendered for are dropped."
+										relevant_when: "codec = `text`"
+										required:      false
+										type: string: {
+											default: null
+											examples: ["{{ timestamp }} [{{ level }}] {{ message }}"]
+											syntax: "template"
+										}
+									}
+								}
+							}
 						}
 					}
 				}
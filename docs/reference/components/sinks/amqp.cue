@@ -0,0 +1,99 @@
+package metadata
+
+components: sinks: amqp: {
+	title: "AMQP"
+
+	description: """
+		Publishes messages to an exchange of an [AMQP 0-9-1](\(urls.amqp)) broker, such as
+		[RabbitMQ](\(urls.rabbitmq)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			to: components._amqp.features.send.to
+		}
+	}
+
+	support: components._amqp.support
+
+	configuration: {
+		connection_string: components._amqp.configuration.connection_string
+		exchange: {
+			common:      true
+			description: "The exchange to publish messages to. The default exchange routes each message to the queue named by its routing key."
+			required:    false
+			warnings: []
+			type: string: {
+				default: ""
+				examples: ["logs"]
+				syntax: "literal"
+			}
+		}
+		routing_key: {
+			common:      true
+			description: "The routing key to publish messages with."
+			required:    false
+			warnings: []
+			type: string: {
+				default: ""
+				examples: ["logs.{{ host }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: components._amqp.how_it_works & {
+		publisher_confirms: {
+			title: "Publisher confirms"
+			body: """
+				Messages are published with publisher confirms enabled, so that an event
+				only counts as sent once the broker has taken responsibility for its
+				message. Up to 128 messages await their confirmation at once. Messages
+				that the broker rejects, or whose connection is lost before they're
+				confirmed, are published again up to 5 times, after which their events
+				are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:       components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+}
@@ -26,7 +26,7 @@ components: sinks: clickhouse: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -94,15 +94,35 @@ components: sinks: clickhouse: {
 			password_example: "${CLICKHOUSE_PASSWORD}"
 			username_example: "${CLICKHOUSE_USERNAME}"
 		}}
+		async_insert: {
+			common:      false
+			description: "Enables [asynchronous inserts][urls.clickhouse_async_insert], which the server buffers and writes to the table in larger blocks. Requires Clickhouse `>= 21.11`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		columns: {
+			common:      false
+			description: "The columns that rows are inserted into. Required by the `tsv` `format`, whose values are written in this order."
+			required:    false
+			warnings: []
+			type: array: {
+				default: null
+				items: type: string: {
+					examples: ["timestamp", "host", "message"]
+					syntax: "literal"
+				}
+			}
+		}
 		database: {
 			common:      true
-			description: "The database that contains the stable that data will be inserted into."
+			description: "The database that contains the table that data will be inserted into."
 			required:    false
 			warnings: []
 			type: string: {
-				default: null
-				examples: ["mydatabase"]
-				syntax: "literal"
+				default: "default"
+				examples: ["mydatabase", "{{ environment }}"]
+				syntax: "template"
 			}
 		}
 		endpoint: {
@@ -113,13 +133,34 @@ components: sinks: clickhouse: {
 				syntax: "literal"
 			}
 		}
+		format: {
+			common:      false
+			description: "The [input format][urls.clickhouse_formats] rows are inserted in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "json_each_row"
+				enum: {
+					json_each_row: "Each event is a `JSONEachRow` JSON object."
+					tsv:           "Each event is a `TabSeparated` row of the values of `columns`. Missing values are inserted as `NULL`."
+				}
+				syntax: "literal"
+			}
+		}
+		skip_unknown_fields: {
+			common:      false
+			description: "Ignores the fields of events that aren't columns of the table, instead of rejecting the whole batch."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		table: {
 			description: "The table that data will be inserted into."
 			required:    true
 			warnings: []
 			type: string: {
-				examples: ["mytable"]
-				syntax: "literal"
+				examples: ["mytable", "{{ service }}_logs"]
+				syntax: "template"
 			}
 		}
 	}
@@ -128,4 +169,17 @@ components: sinks: clickhouse: {
 		logs:    true
 		metrics: null
 	}
+
+	how_it_works: {
+		retries: {
+			title: "Retries"
+			body: """
+				Requests that fail because of their data or the table's schema, such as type
+				mismatches, unknown tables, or syntax errors, are not retried, as they would fail
+				the same way again. Other errors, including temporary ones such as exceeding the
+				memory limit or having too many parts, are retried. The errors are told apart by
+				their [Clickhouse error code][urls.clickhouse_error_codes].
+				"""
+		}
+	}
 }
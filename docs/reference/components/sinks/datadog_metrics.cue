@@ -66,6 +66,42 @@ components: sinks: datadog_metrics: {
 				syntax: "literal"
 			}
 		}
+		interval_tag: {
+			common:      false
+			description: "The tag whose value, in seconds, is sent as the interval of each metric, instead of the time since the last request. The tag itself isn't sent."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["interval"]
+				syntax: "literal"
+			}
+		}
+		series_api_version: {
+			common:      false
+			description: "The version of the [metrics API][urls.datadog_metrics_endpoints] that series are sent to."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "v1"
+				enum: {
+					v1: "The `/api/v1/series` endpoint."
+					v2: "The `/api/v2/series` endpoint, which also accepts the source of each series."
+				}
+				syntax: "literal"
+			}
+		}
+		source_tag: {
+			common:      false
+			description: "The tag whose value is sent as the source of each series, with the `v2` `series_api_version`. The tag itself isn't sent."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["source"]
+				syntax: "literal"
+			}
+		}
 	}
 
 	input: {
@@ -74,9 +110,25 @@ components: sinks: datadog_metrics: {
 			counter:      true
 			distribution: true
 			gauge:        true
-			histogram:    false
+			histogram:    true
 			set:          false
-			summary:      false
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		histograms_and_summaries: {
+			title: "Histograms and summaries"
+			body: """
+				Distributions of sampled values are sent as [Datadog distributions][urls.datadog_distribution],
+				as are aggregated histograms. Datadog needs the values of a distribution, so those of a
+				histogram are approximated by spreading the observations counted in each bucket evenly over
+				its range. Histograms counting more than 1000 observations are approximated with 1000 values,
+				each bucket keeping its share of them, so the counts of their distributions are scaled down.
+
+				Aggregated summaries can't be turned into distributions, and are sent as the `.count` and
+				`.sum` gauges, along with a `.quantile` gauge tagged with each `quantile`.
+				"""
 		}
 	}
 }
@@ -81,7 +81,7 @@ components: sinks: elasticsearch: {
 		}
 		requirements: [
 			#"""
-				Elasticsearch's Data streams feature requires Vector to be configured with the `create` `bulk_action`. *This is not enabled by default.*
+				Elasticsearch's Data streams feature requires Vector to be configured with the `data_stream` `mode`, or the `create` `bulk_action`. *This is not enabled by default.*
 				"""#,
 		]
 		warnings: []
@@ -154,13 +154,64 @@ components: sinks: elasticsearch: {
 		}
 		bulk_action: {
 			common:      false
-			description: "Action to use when making requests to the [Elasticsearch Bulk API](elasticsearch_bulk). Supports `index` and `create`."
+			description: "Action to use when making requests to the [Elasticsearch Bulk API][urls.elasticsearch_bulk]. Supports `index`, `create` and `update`. Events whose action doesn't render to one of these are dropped. `update` requires `id_key`, and events updated without an id are dropped. Ignored in the `data_stream` `mode`, which always uses `create`."
 			required:    false
 			warnings: []
 			type: string: {
 				default: "index"
-				examples: ["index", "create"]
-				syntax: "literal"
+				examples: ["index", "create", "{{ action }}"]
+				syntax: "template"
+			}
+		}
+		data_stream: {
+			common:      false
+			description: "The data stream events are created in, named `<type>-<dataset>-<namespace>`, when `mode` is `data_stream`."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					dataset: {
+						common:      true
+						description: "The data stream's dataset."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "generic"
+							examples: ["nginx", "{{ service }}"]
+							syntax: "template"
+						}
+					}
+					namespace: {
+						common:      true
+						description: "The data stream's namespace."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "default"
+							examples: ["production", "{{ environment }}"]
+							syntax: "template"
+						}
+					}
+					sync_fields: {
+						common:      false
+						description: "Sets the `data_stream.type`, `data_stream.dataset` and `data_stream.namespace` fields of each event to the names of its data stream."
+						required:    false
+						warnings: []
+						type: bool: default: true
+					}
+					type: {
+						common:      true
+						description: "The data stream's type."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "logs"
+							examples: ["logs", "metrics"]
+							syntax: "template"
+						}
+					}
+				}
 			}
 		}
 		doc_type: {
@@ -204,6 +255,20 @@ components: sinks: elasticsearch: {
 				syntax: "template"
 			}
 		}
+		mode: {
+			common:      false
+			description: "How events are written to Elasticsearch."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "normal"
+				enum: {
+					normal:      "Events are written to `index` with `bulk_action`."
+					data_stream: "Events are created in the [data stream][urls.elasticsearch_data_streams] named by `data_stream`."
+				}
+				syntax: "literal"
+			}
+		}
 		pipeline: {
 			common:      true
 			description: "Name of the pipeline to apply."
@@ -240,7 +305,8 @@ components: sinks: elasticsearch: {
 				[`_bulk` API endpoint][urls.elasticsearch_bulk]. By default, all events are
 				inserted via the `index` action which will update documents if an existing
 				one has the same `id`. If `bulk_action` is configured with `create`, Elasticsearch
-				will _not_ replace an existing document and instead return a conflict error.
+				will _not_ replace an existing document and instead return a conflict error. With
+				`update`, events are merged into the existing document, which is created if missing.
 				"""
 		}
 
@@ -248,8 +314,12 @@ components: sinks: elasticsearch: {
 			title: "Data streams"
 			body: """
 				By default, Vector will use the `index` action with Elasticsearch's Bulk API.
-				To use [Data streams][urls.elasticsearch_data_streams], `bulk_action` must be configured
-				with the `create` option.
+				To use [Data streams][urls.elasticsearch_data_streams], set `mode` to `data_stream`.
+				Events are then created in the data stream named `<type>-<dataset>-<namespace>` from the
+				`data_stream` options, with the `create` action that data streams require. Events without
+				an `@timestamp` field have their timestamp moved to it, as data streams require it too.
+
+				Setting `bulk_action` to `create` also works with data streams, when `index` names one.
 				"""
 		}
 
@@ -70,6 +70,23 @@ components: sinks: influxdb_logs: {
 				syntax: "literal"
 			}
 		}
+		precision: {
+			common:      false
+			description: "The precision of the timestamps that are written."
+			groups: ["v1", "v2"]
+			required: false
+			warnings: []
+			type: string: {
+				default: "ns"
+				enum: {
+					s:  "Seconds."
+					ms: "Milliseconds."
+					us: "Microseconds."
+					ns: "Nanoseconds."
+				}
+				syntax: "literal"
+			}
+		}
 	}
 
 	input: {
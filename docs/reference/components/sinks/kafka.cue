@@ -34,7 +34,7 @@ components: sinks: kafka: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "json", "protobuf", "text"]
 				}
 			}
 			request: enabled: false
@@ -53,6 +53,55 @@ components: sinks: kafka: {
 
 	configuration: {
 		bootstrap_servers: components._kafka.configuration.bootstrap_servers
+		compression_level: {
+			common:      false
+			description: "The compression level passed to librdkafka as `compression.level`. The range of valid levels depends on the `compression` algorithm. If unset, the algorithm's default level is used."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [1, 6]
+				unit: null
+			}
+		}
+		headers: {
+			common:      false
+			description: "Record headers to attach to each message, with their values rendered from the event."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						"source":  "vector"
+						"user_id": "{{ user_id }}"
+					},
+				]
+				options: {
+					"*": {
+						common:      false
+						description: "Any record header. Headers whose value can't be rendered are left out."
+						required:    false
+						warnings: []
+						type: string: {
+							default: null
+							examples: ["vector", "{{ user_id }}"]
+							syntax: "template"
+						}
+					}
+				}
+			}
+		}
+		key: {
+			common:      false
+			description: "The message key, rendered from the event. Can't be used together with `key_field`. If the key can't be rendered, a blank value will be used."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ user_id }}", "{{ region }}-{{ user_id }}"]
+				syntax: "template"
+			}
+		}
 		key_field: {
 			description: "The log field name or tags key to use for the topic key. If unspecified, the key will be randomly generated. If the field does not exist on the log or in tags, a blank value will be used."
 			required:    true
@@ -149,7 +198,16 @@ components: sinks: kafka: {
 		}
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		group_membership: {
+			title: "Consumer groups"
+			body:  """
+				The sink only produces messages, so it never joins a consumer group, and the
+				static membership and rebalance options of the `kafka` source don't apply
+				to it.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
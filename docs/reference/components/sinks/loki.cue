@@ -92,6 +92,20 @@ components: sinks: loki: {
 			password_example: "${LOKI_PASSWORD}"
 			username_example: "${LOKI_USERNAME}"
 		}}
+		compression: {
+			common:      false
+			description: "The compression of push requests, which also picks their payload format."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "none"
+				enum: {
+					none:   "Uncompressed JSON payloads."
+					snappy: "[Snappy](\(urls.snappy)) compressed protobuf payloads, which are much smaller."
+				}
+				syntax: "literal"
+			}
+		}
 		labels: {
 			description: "A set of labels that will be attached to each batch of events. These values are also templateable to allow events to provide dynamic label values.Note: If the set of label values has high cardinality this can cause drastic performance issues with Loki. To ensure this does not happen one should try to reduce the amount of unique label values."
 			required:    true
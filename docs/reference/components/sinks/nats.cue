@@ -26,7 +26,13 @@ components: sinks: nats: {
 				}
 			}
 			request: enabled: false
-			tls: enabled:     false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
 			to: {
 				service: services.nats
 
@@ -34,7 +40,7 @@ components: sinks: nats: {
 					socket: {
 						direction: "outgoing"
 						protocols: ["tcp"]
-						ssl: "disabled"
+						ssl: "optional"
 					}
 				}
 			}
@@ -58,6 +64,17 @@ components: sinks: nats: {
 	}
 
 	configuration: {
+		credentials_file: {
+			common:      false
+			description: "The path to a NATS credentials file, holding the user JWT and NKey seed to authenticate with."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["/etc/nats/user.creds"]
+				syntax: "literal"
+			}
+		}
 		url: {
 			description: "The NATS URL to connect to. The url _must_ take the form of `nats://server:port`."
 			required:    true
@@ -0,0 +1,149 @@
+package metadata
+
+components: sinks: redis: {
+	title: "Redis"
+
+	description: """
+		Pushes events to a [Redis](\(urls.redis)) list, or publishes them to a Redis
+		channel, as Logstash's Redis output does.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: {
+				service: services.redis
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		data_type: {
+			common:      true
+			description: "Whether events are pushed to a list or published to a channel."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "list"
+				enum: {
+					list:    "Push each event to the list named by `key`."
+					channel: "Publish each event to the channel named by `key`."
+				}
+				syntax: "literal"
+			}
+		}
+		key: {
+			description: "The list or channel to send events to."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector", "logs.{{ host }}"]
+				syntax: "template"
+			}
+		}
+		list_method: {
+			common:      false
+			description: "The command that pushes events to a list."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "rpush"
+				enum: {
+					lpush: "Push events to the head of the list."
+					rpush: "Push events to the tail of the list."
+				}
+				syntax: "literal"
+			}
+		}
+		pipeline_size: {
+			common:      false
+			description: "The most events sent to Redis in one pipeline. Events that are ready are sent together, up to this many."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 100
+				unit:    "events"
+			}
+		}
+		url: {
+			description: "The URL of the Redis server, including the database to use."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["redis://127.0.0.1:6379/0", "redis://:password@redis.example.com:6379/1"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		reconnection: {
+			title: "Reconnection"
+			body: """
+				When Redis can't be reached, or the connection fails while a pipeline is sent,
+				Vector reconnects with exponential backoff and sends the whole pipeline again.
+				Events may then be delivered more than once. Pipelines that Redis answers with an
+				error, such as when the key holds a value of another type, aren't sent again, and
+				their events are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:       components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+}
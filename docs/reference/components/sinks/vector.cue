@@ -20,6 +20,13 @@ components: sinks: vector: {
 		buffer: enabled:      true
 		healthcheck: enabled: true
 		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10000000
+				max_events:   1000
+				timeout_secs: 1
+			}
 			compression: enabled: false
 			encoding: {
 				enabled: true
@@ -27,7 +34,16 @@ components: sinks: vector: {
 			}
 			send_buffer_bytes: enabled: true
 			keepalive: enabled:         true
-			request: enabled:           false
+			request: {
+				enabled:                    true
+				concurrency:                5
+				rate_limit_duration_secs:   1
+				rate_limit_num:             9223372036854775807
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    3600
+				timeout_secs:               60
+				headers:                    false
+			}
 			tls: {
 				enabled:                true
 				can_enable:             true
@@ -41,7 +57,7 @@ components: sinks: vector: {
 				interface: {
 					socket: {
 						direction: "outgoing"
-						protocols: ["tcp"]
+						protocols: ["tcp", "http"]
 						ssl: "optional"
 					}
 				}
@@ -87,6 +103,55 @@ components: sinks: vector: {
 				syntax: "literal"
 			}
 		}
+		version: components.sources.vector.configuration.version
+		spillover: {
+			common:      false
+			description: "Spills events to a local disk queue while the downstream Vector is unreachable, rather than holding them up in the buffer. The queue is drained, oldest events first, once the downstream Vector is reachable again."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					data_dir: {
+						common:      false
+						description: "The directory the queue is written to. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["/var/lib/vector"]
+							syntax: "file_system_path"
+						}
+					}
+					max_size: {
+						common:      true
+						description: "The maximum size of the queue on disk. Once reached, the oldest spilled events are dropped to make room for new ones."
+						required:    false
+						type: uint: {
+							default: 1_073_741_824
+							unit:    "bytes"
+						}
+					}
+					max_segment_size: {
+						common:      false
+						description: "The size of the batches of events, before compression, that are written to the queue as a single segment."
+						required:    false
+						type: uint: {
+							default: 4_194_304
+							unit:    "bytes"
+						}
+					}
+					compression_level: {
+						common:      false
+						description: "The zstd compression level of the segments."
+						required:    false
+						type: uint: {
+							default: 3
+							unit:    null
+						}
+					}
+				}
+			}
+		}
 	}
 
 	how_it_works: components.sources.vector.how_it_works
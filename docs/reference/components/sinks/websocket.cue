@@ -0,0 +1,126 @@
+package metadata
+
+components: sinks: websocket: {
+	title: "WebSocket"
+
+	description: """
+		Streams events over a persistent [WebSocket](\(urls.websocket)) connection, such as
+		one feeding a live dashboard.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: {
+				service: services.websocket
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		auth: configuration._http_auth & {_args: {
+			password_example: "${WEBSOCKET_PASSWORD}"
+			username_example: "${WEBSOCKET_USERNAME}"
+		}}
+		ping_interval_secs: {
+			common:      false
+			description: "Ping the server at this interval, and reconnect when it doesn't answer with a pong within `ping_timeout_secs`. The server isn't pinged when this isn't set."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [30]
+				unit: "seconds"
+			}
+		}
+		ping_timeout_secs: {
+			common:      false
+			description: "How long past a ping to wait for the server's pong, before reconnecting."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		uri: {
+			description: "The WebSocket endpoint to connect to. `wss://` endpoints are connected to over TLS."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["ws://127.0.0.1:8080/events", "wss://dashboard.example.com/events"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		reconnection: {
+			title: "Reconnection"
+			body: """
+				Each event is sent as a text message. When the connection fails or is closed,
+				Vector reconnects with exponential backoff and sends the message it was
+				sending again. Messages already handed to a connection that then fails may
+				be lost, as WebSocket has no acknowledgements.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total:   components.sources.internal_metrics.output.metrics.connection_errors_total
+		connection_shutdown_total: components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		processed_bytes_total:     components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:    components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:         components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+}
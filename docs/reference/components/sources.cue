@@ -93,6 +93,55 @@ components: sources: [Name=string]: {
 			}
 		}
 
+		if features.codecs != _|_ {
+			if features.codecs.enabled {
+				decoding: {
+					common:      false
+					description: "Configures how each frame of the received data is decoded into an event."
+					required:    false
+					type: object: options: {
+						codec: {
+							description: "The codec used to decode each frame. Frames that can't be decoded are dropped."
+							required:    false
+							common:      true
+							type: string: {
+								default: "bytes"
+								enum: {
+									bytes:  "The frame is used as the `message` of the event."
+									json:   "The frame is parsed as a JSON object, whose fields become the fields of the event."
+									syslog: "The frame is parsed as a Syslog message, in the RFC 3164 or RFC 5424 format."
+								}
+								syntax: "literal"
+							}
+						}
+					}
+				}
+
+				if features.codecs.default_framing != _|_ {
+					framing: {
+						common:      false
+						description: "Configures how the received data is split into frames. By default, \(features.codecs.default_framing) framing is used."
+						required:    false
+						type: object: options: {
+							method: {
+								description: "The method used to split the received data into frames."
+								required:    true
+								type: string: {
+									enum: {
+										bytes:             "The received data is a single frame."
+										newline_delimited: "Frames are separated by newlines."
+										length_delimited:  "Frames are prefixed with their length, as a 4 byte big-endian integer."
+										octet_counting:    "Frames are prefixed with their length in ASCII digits and a space, as described in [RFC 6587](\(urls.rfc_6587)). Frames without this prefix are separated by newlines."
+									}
+									syntax: "literal"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		if features.collect != _|_ {
 			if features.collect.tls != _|_ {
 				if features.collect.tls.enabled {
@@ -149,6 +198,131 @@ components: sources: [Name=string]: {
 				}}
 			}
 		}
+
+		metadata_keys: {
+			common:      false
+			description: "Controls the metadata keys added to the events of this source, such as `host` and `source_type`, so that they don't collide with fields of the same name. Options that aren't set fall back to the global `metadata_keys` option."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					host: {
+						common:      false
+						description: "How the `host` key is added to events."
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								enabled: {
+									common:      false
+									description: "Whether the key is added at all."
+									required:    false
+									type: bool: default: true
+								}
+								overwrite: {
+									common:      false
+									description: "Whether a value already at `target` is overwritten."
+									required:    false
+									type: bool: default: true
+								}
+								target: {
+									common:      false
+									description: "The path the key is added at, instead of the key set by `log_schema`."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["_vector.host"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+					source_type: {
+						common:      false
+						description: "How the `source_type` key is added to events."
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								enabled: {
+									common:      false
+									description: "Whether the key is added at all."
+									required:    false
+									type: bool: default: true
+								}
+								overwrite: {
+									common:      false
+									description: "Whether a value already at `target` is overwritten."
+									required:    false
+									type: bool: default: true
+								}
+								target: {
+									common:      false
+									description: "The path the key is added at, instead of the key set by `log_schema`."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["_vector.source_type"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+		log_schema: {
+			common:      false
+			description: "Overrides the keys of the global `log_schema` option for the events of this source. Fields are moved to these keys after `metadata_keys` is applied, unless the event already has a field at the key. Sources whose events meet at a transform or sink, directly or through other transforms, must have the same log schema, otherwise the configuration is rejected."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					host_key: {
+						common:      false
+						description: "The key the host is moved to."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["agent.hostname"]
+							syntax: "literal"
+						}
+					}
+					message_key: {
+						common:      false
+						description: "The key the message is moved to."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["msg"]
+							syntax: "literal"
+						}
+					}
+					source_type_key: {
+						common:      false
+						description: "The key the source type is moved to."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["agent.type"]
+							syntax: "literal"
+						}
+					}
+					timestamp_key: {
+						common:      false
+						description: "The key the timestamp is moved to."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["@timestamp"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: {
@@ -0,0 +1,149 @@
+package metadata
+
+components: sources: amqp: {
+	title: "AMQP"
+
+	description: """
+		Consumes messages from a queue of an [AMQP 0-9-1](\(urls.amqp)) broker, such as
+		[RabbitMQ](\(urls.rabbitmq)).
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			from: components._amqp.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._amqp.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Acknowledge a message only once its events are accepted by the sinks that have `acknowledgements` enabled, and return it to the queue otherwise. If this is `false`, messages are acknowledged once they're read."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		connection_string: components._amqp.configuration.connection_string
+		consumer: {
+			common:      false
+			description: "The consumer tag to identify this consumer to the broker with."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "vector"
+				syntax:  "literal"
+			}
+		}
+		exchange_key: {
+			common:      false
+			description: "The log field name to store the exchange each message was published to in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "exchange"
+				syntax:  "literal"
+			}
+		}
+		prefetch_count: {
+			common:      false
+			description: "The number of messages the broker sends ahead, before waiting for some of them to be acknowledged. If unset, the broker doesn't limit it."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [100]
+				unit: null
+			}
+		}
+		queue: {
+			description: "The name of the queue to consume from."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		routing_key_field: {
+			common:      false
+			description: "The log field name to store the routing key of each message in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "routing"
+				syntax:  "literal"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual AMQP message."
+		fields: {
+			exchange: {
+				description: "The exchange the message was published to. This is empty for the default exchange."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+					syntax: "literal"
+				}
+			}
+			message: {
+				description: "The body of the message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			routing: {
+				description: "The routing key the message was published with."
+				required:    true
+				type: string: {
+					examples: ["logs.web"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The `timestamp` property of the message, or the current time if it's missing."
+			}
+		}
+	}
+
+	how_it_works: components._amqp.how_it_works
+
+	telemetry: metrics: {
+		connection_errors_total:  components.sources.internal_metrics.output.metrics.connection_errors_total
+		consumer_errors_total:    components.sources.internal_metrics.output.metrics.consumer_errors_total
+		events_failed_total:      components.sources.internal_metrics.output.metrics.events_failed_total
+		processed_bytes_total:    components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:   components.sources.internal_metrics.output.metrics.processed_events_total
+		reconnect_attempts_total: components.sources.internal_metrics.output.metrics.reconnect_attempts_total
+	}
+}
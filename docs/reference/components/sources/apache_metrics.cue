@@ -151,6 +151,13 @@ components: sources: apache_metrics: {
 			default_namespace: "apache"
 			tags:              _apache_metrics_tags
 		}
+		requests_per_second: {
+			description:       "The average number of requests per second served by the Apache server since it started."
+			relevant_when:     "`ExtendedStatus On`"
+			type:              "gauge"
+			default_namespace: "apache"
+			tags:              _apache_metrics_tags
+		}
 		scoreboard: {
 			description:       "The amount of times various Apache server tasks have been run."
 			type:              "gauge"
@@ -163,6 +170,13 @@ components: sources: apache_metrics: {
 				}
 			}
 		}
+		sent_bytes_per_second: {
+			description:       "The average number of bytes per second sent by the Apache server since it started."
+			relevant_when:     "`ExtendedStatus On`"
+			type:              "gauge"
+			default_namespace: "apache"
+			tags:              _apache_metrics_tags
+		}
 		sent_bytes_total: {
 			description:       "The amount of bytes sent by the Apache server."
 			relevant_when:     "`ExtendedStatus On`"
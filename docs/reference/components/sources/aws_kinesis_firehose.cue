@@ -91,6 +91,20 @@ components: sources: aws_kinesis_firehose: {
 				syntax: "literal"
 			}
 		}
+		record_compression: {
+			common:      false
+			description: "The compression of the data of each record. Firehose only passes records on as they were put, so they are gzipped when coming from CloudWatch Logs subscriptions, and usually not otherwise."
+			required:    false
+			type: string: {
+				default: "auto"
+				enum: {
+					auto: "Records are decompressed if they start with the gzip magic bytes."
+					gzip: "Records are gzipped."
+					none: "Records are uncompressed."
+				}
+				syntax: "literal"
+			}
+		}
 	}
 
 	output: logs: {
@@ -47,6 +47,13 @@ components: sources: aws_s3: components._aws & {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Delete a message only once the events of each of its objects are accepted by the sinks that have `acknowledgements` enabled. The objects of a message are then processed one at a time, and messages with objects that aren't delivered are received again once their visibility timeout expires."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		strategy: {
 			common:      false
 			description: "The strategy to use to consume objects from AWS S3."
@@ -225,7 +232,6 @@ components: sources: aws_s3: components._aws & {
 							"(?P<redirect_url>[^"]+)"[ ]
 							"(?P<error_reason>[^"]+)"'
 					field = "message"
-					drop_failed = false
 
 					types.received_bytes = "int"
 					types.request_processing_time = "float"
@@ -238,7 +244,6 @@ components: sources: aws_s3: components._aws & {
 					inputs = ["elasticloadbalancing_fields_parsed"]
 					regex = '^(?P<url_scheme>[\\w]+)://(?P<url_hostname>[^\\s:/?#]+)(?::(?P<request_port>[\\d-]+))?-?(?:/(?P<url_path>[^\\s?#]*))?(?P<request_url_query>\\?[^\\s#]+)?'
 					field = "request_url"
-					drop_failed = false
 				```
 				"""
 		}
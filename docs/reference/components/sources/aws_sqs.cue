@@ -0,0 +1,158 @@
+package metadata
+
+components: sources: aws_sqs: components._aws & {
+	title: "AWS SQS"
+
+	features: {
+		multiline: enabled: false
+		collect: {
+			tls: enabled:        false
+			checkpoint: enabled: false
+			from: service:       services.aws_sqs
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Delete a message only once its event is accepted by the sinks that have `acknowledgements` enabled. Messages that aren't delivered are received again once their visibility timeout expires."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		delete_message: {
+			common:      true
+			description: "Whether to delete the message once Vector processes it. It can be useful to set this to `false` to debug or during initial Vector setup."
+			required:    false
+			warnings: []
+			type: bool: default: true
+		}
+		poll_secs: {
+			common:      true
+			description: "How long each receive request waits for messages to arrive, with [long polling](\(urls.aws_sqs_long_polling)). This can't be more than 20 seconds."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		queue_url: {
+			description: "The URL of the SQS queue to receive messages from."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["https://sqs.us-east-2.amazonaws.com/123456789012/MyQueue"]
+				syntax: "literal"
+			}
+		}
+		visibility_timeout_secs: {
+			common:      false
+			description: "The visibility timeout to use for messages in seconds. This controls how long a message is left unavailable when Vector receives it. If Vector does not delete the message before the timeout expires, it will be made available for another consumer; this can happen if, for example, the `vector` process crashes."
+			required:    false
+			warnings: ["Should be set higher than the length of time it takes to deliver an event, when `acknowledgements` is enabled, to avoid that message being reprocessed."]
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "A message received from the queue."
+		fields: {
+			"*": {
+				description: "The message attributes of the message, each as a field. `Number` attributes are parsed as numbers, and `Binary` attributes are kept as bytes."
+				required:    false
+				type: "*": {}
+			}
+			message: {
+				description: "The body of the message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			message_id: {
+				description: "The ID SQS assigned to the message."
+				required:    true
+				type: string: {
+					examples: ["5fea7756-0ea4-451a-a703-a558b933e274"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the message was sent to the queue. Defaults the current timestamp if this information is missing."
+			}
+		}
+	}
+
+	how_it_works: {
+		deletion: {
+			title: "Message deletion"
+			body: """
+				Messages are received in batches of up to 10, and each message becomes an
+				event. Once a batch is sent on, its messages are deleted from the queue,
+				unless `delete_message` is `false`. With `acknowledgements` enabled, each
+				message is only deleted once its event is accepted by the sinks, so that
+				messages that can't be delivered stay on the queue to be received again.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform:  "aws"
+			_service:  "sqs"
+			_docs_tag: "AWSSimpleQueueService"
+
+			policies: [
+				{
+					_action: "ReceiveMessage"
+				},
+				{
+					_action:       "DeleteMessage"
+					required_when: "[`delete_message`](#delete_message) is set to `true`"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		events_failed_total:              components.sources.internal_metrics.output.metrics.events_failed_total
+		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:           components.sources.internal_metrics.output.metrics.processed_events_total
+		sqs_message_delete_failed_total:  components.sources.internal_metrics.output.metrics.sqs_message_delete_failed_total
+		sqs_message_receive_failed_total: components.sources.internal_metrics.output.metrics.sqs_message_receive_failed_total
+	}
+}
@@ -253,7 +253,8 @@ components: sources: docker_logs: {
 		retry_backoff_secs: {
 			common: false
 			description: """
-				The amount of time to wait before retrying after an error.
+				The amount of time to wait before retrying after an error. The wait
+				doubles with each consecutive error, up to 30 seconds, and is jittered.
 				"""
 			required: false
 			type: uint: {
@@ -398,5 +399,6 @@ components: sources: docker_logs: {
 		logging_driver_errors_total:           components.sources.internal_metrics.output.metrics.logging_driver_errors_total
 		processed_bytes_total:                 components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:                components.sources.internal_metrics.output.metrics.processed_events_total
+		reconnect_attempts_total:              components.sources.internal_metrics.output.metrics.reconnect_attempts_total
 	}
 }
@@ -0,0 +1,223 @@
+package metadata
+
+components: sources: exec: {
+	title: "Exec"
+
+	description: """
+		Runs a command and collects the lines it writes to its standard output and
+		standard error, either by running it on an interval or by keeping a
+		long-lived process running.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.host
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		command: {
+			description: "The command to run, followed by its arguments. The command isn't run in a shell, so to use shell features, run the shell itself, such as `[\"sh\", \"-c\", \"...\"]`."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: [["echo", "Hello World!"], ["ls", "-la"]]
+				syntax: "literal"
+			}
+		}
+		include_stderr: {
+			common:      false
+			description: "Whether to collect the lines written to standard error as well."
+			required:    false
+			warnings: []
+			type: bool: default: true
+		}
+		max_length: {
+			common:      false
+			description: "The maximum bytes size of a line before the rest of it is discarded."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 102400
+				unit:    "bytes"
+			}
+		}
+		mode: {
+			description: "How the command is run."
+			required:    true
+			warnings: []
+			type: string: {
+				enum: {
+					scheduled: "The command is run on an interval, and its output is sent on once it exits."
+					streaming: "The command is expected to keep running, and its output is sent on as it's written."
+				}
+				syntax: "literal"
+			}
+		}
+		scheduled: {
+			common:      true
+			description: "The options for the `scheduled` mode."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					exec_interval_secs: {
+						common:      true
+						description: "How often to run the command."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+		streaming: {
+			common:      true
+			description: "The options for the `streaming` mode."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					respawn_interval_secs: {
+						common:      false
+						description: "How long to wait before running the command again after it exits."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 5
+							unit:    "seconds"
+						}
+					}
+					respawn_on_exit: {
+						common:      true
+						description: "Whether to run the command again after it exits."
+						required:    false
+						warnings: []
+						type: bool: default: true
+					}
+				}
+			}
+		}
+		working_directory: {
+			common:      false
+			description: "The directory to run the command in. Defaults to the working directory of Vector."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "A line written by the command."
+		fields: {
+			command: {
+				description: "The command that was run, followed by its arguments."
+				required:    true
+				type: array: items: type: string: {
+					examples: ["echo", "Hello World!"]
+					syntax: "literal"
+				}
+			}
+			exit_code: {
+				description: "The exit code of the command. This is only set in the `scheduled` mode, and is missing if the command was killed by a signal."
+				required:    false
+				type: uint: {
+					examples: [0, 1]
+					unit: null
+				}
+			}
+			host:    fields._local_host
+			message: fields._raw_line
+			pid: {
+				description: "The process ID of the command."
+				required:    true
+				type: uint: {
+					examples: [60085]
+					unit: null
+				}
+			}
+			stream: {
+				description: "The stream the line was written to."
+				required:    true
+				type: string: {
+					enum: {
+						stderr: "The standard error stream."
+						stdout: "The standard output stream."
+					}
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		modes: {
+			title: "Scheduled and streaming modes"
+			body: """
+				In the `scheduled` mode, the command is run every `exec_interval_secs`,
+				and its output is held until it exits, so that each event can carry the
+				`exit_code` of the command. It suits commands that print a report and
+				exit. A run that takes longer than the interval delays the next one.
+
+				In the `streaming` mode, the command is started once and each line is
+				sent on as soon as it's written. When the command exits it's started
+				again after `respawn_interval_secs`, unless `respawn_on_exit` is `false`.
+
+				In both modes the command is killed when Vector shuts down.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		command_executed_total:             components.sources.internal_metrics.output.metrics.command_executed_total
+		command_execution_duration_seconds: components.sources.internal_metrics.output.metrics.command_execution_duration_seconds
+		processed_bytes_total:              components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:             components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total:            components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
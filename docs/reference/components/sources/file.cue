@@ -27,6 +27,7 @@ components: sources: file: {
 		}
 		multiline: enabled: true
 		encoding: enabled:  true
+		codecs: enabled:    true
 	}
 
 	support: {
@@ -50,6 +51,13 @@ components: sources: file: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Checkpoint a file only up to the lines whose events are accepted by the sinks that have `acknowledgements` enabled. A file isn't checkpointed any further once the event of one of its lines isn't delivered, so that it's read again from there after a restart. Multi-line messages are checkpointed where they start."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		exclude: {
 			common:      false
 			description: "Array of file patterns to exclude. [Globbing](#globbing) is supported.*Takes precedence over the [`include` option](#include).*"
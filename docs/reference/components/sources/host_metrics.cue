@@ -72,9 +72,10 @@ components: sources: host_metrics: {
 			common:      true
 			required:    false
 			type: array: {
-				default: ["cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				default: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
 				items: type: string: {
 					enum: {
+						cgroups:    "Metrics of the cgroup Vector is running in (Linux only)."
 						cpu:        "Metrics related to CPU utilization."
 						disk:       "Metrics related to disk I/O utilization."
 						filesystem: "Metrics related to filesystem space utilization."
@@ -336,6 +337,32 @@ components: sources: host_metrics: {
 			}
 		}
 
+		// Host cgroups
+		cgroup_cpu_usage_seconds_total:     _host & _cgroups_counter & _cgroups_linux & {description: "The number of CPU seconds used by the cgroup."}
+		cgroup_cpu_user_seconds_total:      _host & _cgroups_counter & _cgroups_v2 & {description: "The number of CPU seconds used by the cgroup in user mode."}
+		cgroup_cpu_system_seconds_total:    _host & _cgroups_counter & _cgroups_v2 & {description: "The number of CPU seconds used by the cgroup in system mode."}
+		cgroup_cpu_periods_total:           _host & _cgroups_counter & _cgroups_linux & {description: "The number of CPU bandwidth enforcement periods that have elapsed."}
+		cgroup_cpu_throttled_periods_total: _host & _cgroups_counter & _cgroups_linux & {description: "The number of enforcement periods in which the cgroup was throttled."}
+		cgroup_cpu_throttled_seconds_total: _host & _cgroups_counter & _cgroups_linux & {description: "The number of seconds the cgroup was throttled for."}
+		cgroup_memory_current_bytes:        _host & _cgroups_gauge & _cgroups_linux & {description:   "The number of bytes of memory used by the cgroup."}
+		cgroup_memory_limit_bytes:          _host & _cgroups_gauge & _cgroups_linux & {description:   "The memory limit of the cgroup, in bytes. Not reported if the cgroup is unlimited."}
+		cgroup_pressure_stall_ratio:        _host & _cgroups_v2 & {
+			description: "The share of time in which tasks of the cgroup were stalled waiting for a resource, averaged over a window."
+			type:        "gauge"
+			tags:        _cgroups_pressure_tags & {
+				window: {
+					description: "The window the share is averaged over."
+					required:    true
+					examples: ["10s", "60s", "300s"]
+				}
+			}
+		}
+		cgroup_pressure_stall_seconds_total: _host & _cgroups_v2 & {
+			description: "The number of seconds in which tasks of the cgroup were stalled waiting for a resource."
+			type:        "counter"
+			tags:        _cgroups_pressure_tags
+		}
+
 		// Host CPU
 		host_cpu_seconds_total: _host & {
 			description: "The number of CPU seconds accumulated in different operating modes."
@@ -410,6 +437,40 @@ components: sources: host_metrics: {
 			default_namespace: "host"
 		}
 
+		_cgroups_counter: {
+			type: "counter"
+			tags: _host_metrics_tags & {
+				collector: examples: ["cgroups"]
+			}
+		}
+		_cgroups_gauge: {
+			type: "gauge"
+			tags: _host_metrics_tags & {
+				collector: examples: ["cgroups"]
+			}
+		}
+		_cgroups_linux: {relevant_when: "OS is Linux"}
+		_cgroups_v2: {relevant_when: "OS is Linux, with cgroups v2"}
+		_cgroups_pressure_tags: _host_metrics_tags & {
+			collector: examples: ["cgroups"]
+			resource: {
+				description: "The resource tasks were waiting for."
+				required:    true
+				enum: {
+					cpu:    "CPU time."
+					memory: "Memory."
+					io:     "Disk and other I/O."
+				}
+			}
+			type: {
+				description: "Whether some or all of the tasks of the cgroup were stalled."
+				required:    true
+				enum: {
+					some: "At least one task was stalled."
+					full: "All tasks were stalled at once."
+				}
+			}
+		}
 		_disk_device: {
 			description: "The disk device name."
 			required:    true
@@ -16,6 +16,10 @@ components: sources: http: {
 
 	features: {
 		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
 		receive: {
 			from: {
 				service: services.http
@@ -70,7 +74,7 @@ components: sources: http: {
 		}
 		encoding: {
 			common:      true
-			description: "The expected encoding of received data. Note that for `json` and `ndjson` encodings, the fields of the JSON objects are output as separate fields."
+			description: "The expected encoding of received data. Note that for `json` and `ndjson` encodings, the fields of the JSON objects are output as separate fields. When the `framing` option is set, each frame is decoded with this encoding. Ignored when the `decoding` option is set."
 			required:    false
 			type: string: {
 				default: "text"
@@ -78,6 +82,7 @@ components: sources: http: {
 					text:   "Newline-delimited text, with each line forming a message."
 					ndjson: "Newline-delimited JSON objects, where each line must contain a JSON object."
 					json:   "Array of JSON objects, which must be a JSON array containing JSON objects."
+					avro:   "Avro records decoded with the schema of `schema_validation`, which must be set. The body is either an Avro object container file, or a single datum, in the Confluent wire format for the `confluent` registry. Can't be used with the `decoding` option."
 				}
 				syntax: "literal"
 			}
@@ -138,6 +143,94 @@ components: sources: http: {
 				syntax: "literal"
 			}
 		}
+		schema_validation: {
+			common:      false
+			description: "Validates incoming events against an Avro schema fetched from a schema registry when the source starts. Events are validated once the `headers`, `query_parameters` and `path_key` fields are added, and fields that aren't part of the schema are ignored. With the `avro` encoding, events are decoded with the schema instead, and bodies that can't be decoded are rejected. Reload the configuration to pick up a new version of the schema."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					registry: {
+						description: "The registry to fetch the schema from."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								type: {
+									description: "The type of registry."
+									required:    true
+									warnings: []
+									type: string: {
+										enum: {
+											confluent: "A [Confluent Schema Registry](\(urls.confluent_schema_registry)), serving the schemas of `subject`. Only `AVRO` schemas are supported."
+											http:      "A plain HTTP endpoint serving the Avro schema itself."
+										}
+										syntax: "literal"
+									}
+								}
+								url: {
+									description: "The URL of the registry, or of the schema for the `http` registry."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["http://schema-registry:8081", "https://schemas.example.com/request.avsc"]
+										syntax: "literal"
+									}
+								}
+								subject: {
+									description:   "The subject to fetch the schema of."
+									relevant_when: "type = \"confluent\""
+									required:      true
+									warnings: []
+									type: string: {
+										examples: ["requests-value"]
+										syntax: "literal"
+									}
+								}
+								version: {
+									common:        false
+									description:   "The version of the subject's schema to fetch."
+									relevant_when: "type = \"confluent\""
+									required:      false
+									warnings: []
+									type: string: {
+										default: "latest"
+										examples: ["3"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+					on_failure: {
+						common:      true
+						description: "What to do with events that don't conform to the schema."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "tag"
+							enum: {
+								tag:  "Keep the event, with the validation error in `error_field`."
+								drop: "Drop the event."
+							}
+							syntax: "literal"
+						}
+					}
+					error_field: {
+						common:      false
+						description: "The field that the validation error of non-conforming events is stored in."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "schema_error"
+							syntax:  "literal"
+						}
+					}
+				}
+			}
+		}
 	}
 	output: logs: {
 		text: {
@@ -263,7 +356,7 @@ components: sources: http: {
 			title: "Decompression"
 			body: """
 				Received body is decompressed according to `Content-Encoding` header.
-				Supported algorithms are `gzip`, `deflate`, and `snappy`.
+				Supported algorithms are `gzip`, `deflate`, `snappy`, and `zstd`.
 				"""
 		}
 	}
@@ -45,6 +45,54 @@ components: sources: internal_metrics: {
 		platform_name: null
 	}
 
+	configuration: {
+		namespace: {
+			description: "The namespace of metrics. Disabled if empty."
+			common:      false
+			required:    false
+			type: string: {
+				default: "vector"
+				syntax:  "literal"
+			}
+		}
+		scrape_interval_secs: {
+			description: "The interval between metric gathering, in seconds."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 2
+				unit:    "seconds"
+			}
+		}
+		tags: {
+			description: "Tags added to every metric, so that the metrics of several Vector instances can be told apart downstream."
+			common:      false
+			required:    false
+			type: object: options: {
+				host_key: {
+					description: "The tag the hostname of the machine Vector is running on is added under. Not added if unset."
+					common:      false
+					required:    false
+					type: string: {
+						default: null
+						examples: ["host"]
+						syntax: "literal"
+					}
+				}
+				pid_key: {
+					description: "The tag the process ID of Vector is added under. Not added if unset."
+					common:      false
+					required:    false
+					type: string: {
+						default: null
+						examples: ["pid"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
+
 	output: metrics: {
 		// Default internal metrics tags
 		_internal_metrics_tags: {
@@ -166,6 +214,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		buffer_byte_size: {
+			description:       "The number of bytes currently in the sink's disk buffer."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		buffer_events: {
+			description:       "The number of events currently in the sink's buffer."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		buffer_max_size: {
+			description:       "The maximum size of the sink's buffer, in events for memory buffers and in bytes for disk buffers."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints."
 			type:              "counter"
@@ -198,6 +264,34 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		command_executed_total: {
+			description:       "The total number of times a command has been executed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				exit_code: {
+					description: "The exit code of the command, or `unknown` if it was killed by a signal."
+					required:    true
+				}
+			}
+		}
+		command_execution_duration_seconds: {
+			description:       "The duration spent executing a command."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				exit_code: {
+					description: "The exit code of the command, or `unknown` if it was killed by a signal."
+					required:    true
+				}
+			}
+		}
+		component_allocated_bytes: {
+			description:       "The number of bytes currently allocated by this component. Only reported when Vector is built with the `allocation-tracing` feature."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
 		communication_errors_total: {
 			description:       "The total number of errors stemming from communication with the Docker daemon."
 			type:              "counter"
@@ -218,12 +312,45 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		consumer_assigned_partitions: {
+			description:       "The number of partitions currently assigned to a Kafka consumer."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		consumer_errors_total: {
+			description:       "The total number of errors consuming messages from a queue, or acknowledging them."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		consumer_offset_updates_failed_total: {
 			description:       "The total number of failures to update a Kafka consumer offset."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		consumer_rebalance_errors_total: {
+			description:       "The total number of failed rebalances of a Kafka consumer group."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		consumer_rebalances_total: {
+			description:       "The total number of times a Kafka consumer group was rebalanced."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				rebalance_type: {
+					description: "Whether partitions were assigned to or revoked from the consumer."
+					required:    true
+					enum: {
+						assign: "Partitions were assigned to the consumer."
+						revoke: "Partitions were revoked from the consumer."
+					}
+				}
+			}
+		}
 		container_processed_events_total: {
 			description:       "The total number of container events processed."
 			type:              "counter"
@@ -266,6 +393,35 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		database_reload_errors_total: {
+			description:       "The total number of errors reloading a changed database file."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		database_reloads_total: {
+			description:       "The total number of times a changed database file has been reloaded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		decompress_errors_total: {
+			description:       "The total number of payloads that couldn't be decompressed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		enrichment_table_loads_total: {
+			description:       "The total number of times an enrichment table has been loaded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+			}
+		}
 		encode_errors_total: {
 			description:       "The total number of errors encountered when encoding an event."
 			type:              "counter"
@@ -296,6 +452,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		events_recorded_total: {
+			description:       "The number of events recorded by the aggregate transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		processed_events_total: {
 			description:       "The total number of events processed by this component."
 			type:              "counter"
@@ -360,6 +522,12 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		flushes_total: {
+			description:       "The number of flushes done by the aggregate transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		glob_errors_total: {
 			description:       "The total number of errors encountered when globbing paths."
 			type:              "counter"
@@ -413,6 +581,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		lookup_misses_total: {
+			description:       "The total number of events without a matching row in the enrichment table."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		memory_used_bytes: {
 			description:       "The total memory currently being used by Vector (in bytes)."
 			type:              "gauge"
@@ -463,6 +637,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		reconnect_attempts_total: {
+			description:       "The total number of times a source waited to reconnect, or to restart what it reads from, after a failure."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		request_errors_total: {
 			description:       "The total number of requests errors for this component."
 			type:              "counter"
@@ -709,6 +889,7 @@ components: sources: internal_metrics: {
 			description: "The type of the error"
 			required:    true
 			enum: {
+				"command_failed":              "The command couldn't be run."
 				"field_missing":               "The event field was missing."
 				"invalid_metric":              "The metric was invalid."
 				"mapping_failed":              "The mapping failed."
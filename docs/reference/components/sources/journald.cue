@@ -68,26 +68,39 @@ components: sources: journald: {
 		}
 		exclude_units: {
 			common:      true
-			description: "The list of unit names to exclude from monitoring. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name."
+			description: "The list of unit names or glob patterns to exclude from monitoring. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name."
 			required:    false
 			warnings: []
 			type: array: {
 				default: []
 				items: type: string: {
-					examples: ["badservice", "sysinit.target"]
+					examples: ["badservice", "sysinit.target", "user@*"]
+					syntax: "literal"
+				}
+			}
+		}
+		include_facilities: {
+			common:      false
+			description: "The list of syslog facilities, by name or number, to monitor. If empty or not present, all facilities are accepted. Requires `journalctl` from systemd 245 or later."
+			required:    false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["auth", "authpriv", "4"]
 					syntax: "literal"
 				}
 			}
 		}
 		include_units: {
 			common:      true
-			description: "The list of unit names to monitor. If empty or not present, all units are accepted. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name."
+			description: "The list of unit names or glob patterns to monitor. If empty or not present, all units are accepted. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name. These are passed to `journalctl`, so entries of other units aren't read at all."
 			required:    false
 			warnings: []
 			type: array: {
 				default: []
 				items: type: string: {
-					examples: ["ntpd", "sysinit.target"]
+					examples: ["ntpd", "sysinit.target", "docker-*.scope"]
 					syntax: "literal"
 				}
 			}
@@ -203,5 +216,6 @@ components: sources: journald: {
 		invalid_record_bytes_total: components.sources.internal_metrics.output.metrics.invalid_record_bytes_total
 		processed_bytes_total:      components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:     components.sources.internal_metrics.output.metrics.processed_events_total
+		reconnect_attempts_total:   components.sources.internal_metrics.output.metrics.reconnect_attempts_total
 	}
 }
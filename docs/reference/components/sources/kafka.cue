@@ -16,6 +16,10 @@ components: sources: kafka: {
 			from: components._kafka.features.collect.from
 		}
 		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
 	}
 
 	classes: {
@@ -34,6 +38,13 @@ components: sources: kafka: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Store the offset of a message only once its event is accepted by the sinks that have `acknowledgements` enabled. The offsets of a partition are stored in order, so a message whose event wasn't delivered stops the offsets of its partition from advancing until the source restarts, and its messages are then consumed again."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		auto_offset_reset: {
 			common:      false
 			description: "If offsets for consumer group do not exist, set them using this strategy. [librdkafka documentation][urls.librdkafka_config] for `auto.offset.reset` option for explanation."
@@ -77,6 +88,17 @@ components: sources: kafka: {
 				syntax: "literal"
 			}
 		}
+		group_instance_id: {
+			common:      false
+			description: "A unique, stable identifier of this consumer within the consumer group, making it a static member. See [static membership](#static-membership) for details."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector-0", "${HOSTNAME}"]
+				syntax: "literal"
+			}
+		}
 		key_field: {
 			common:      true
 			description: "The log field name to use for the Kafka message key. If unspecified, the key would not be added to the log event. If the message has null key, then this field would not be added to the log event."
@@ -238,11 +260,37 @@ components: sources: kafka: {
 	}
 
 	telemetry: metrics: {
+		consumer_assigned_partitions:         components.sources.internal_metrics.output.metrics.consumer_assigned_partitions
 		consumer_offset_updates_failed_total: components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
+		consumer_rebalance_errors_total:      components.sources.internal_metrics.output.metrics.consumer_rebalance_errors_total
+		consumer_rebalances_total:            components.sources.internal_metrics.output.metrics.consumer_rebalances_total
 		events_failed_total:                  components.sources.internal_metrics.output.metrics.events_failed_total
 		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
+		reconnect_attempts_total:             components.sources.internal_metrics.output.metrics.reconnect_attempts_total
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		static_membership: {
+			title: "Static membership"
+			body:  """
+				By default, every time a consumer leaves or joins its group, the group is
+				rebalanced and consumption of all of its partitions pauses until the
+				partitions are reassigned. Setting `group_instance_id` makes the consumer a
+				static member of the group: when it's restarted, for example on a deploy,
+				it gets its previous partitions back without a rebalance, as long as it
+				rejoins within the `session_timeout_ms`.
+
+				Each instance of Vector in the group needs its own `group_instance_id`, which
+				must stay the same across restarts, such as a pod name in a `StatefulSet`.
+				Static membership requires Kafka brokers version 2.3 or later.
+
+				Partitions are always assigned with the default eager strategies, `range`
+				and `roundrobin`, so a rebalance still pauses consumption of all of the
+				group's partitions. The incremental `cooperative-sticky` strategy, which only
+				moves the partitions that change owner, isn't supported: it requires
+				`librdkafka` 1.6 or later, while Vector bundles `librdkafka` 1.5.
+				"""
+		}
+	}
 }
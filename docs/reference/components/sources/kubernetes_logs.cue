@@ -58,7 +58,7 @@ components: sources: kubernetes_logs: {
 	configuration: {
 		annotation_fields: {
 			common:      false
-			description: "Configuration for how the events are annotated with Pod metadata."
+			description: "Configuration for how the events are annotated with Pod metadata. Metadata whose event field is set to an empty string isn't added to events."
 			required:    false
 			type: object: {
 				examples: []
@@ -81,6 +81,16 @@ components: sources: kubernetes_logs: {
 							syntax:  "literal"
 						}
 					}
+					pod_annotations: {
+						common:      false
+						description: "Event field for Pod annotations. Annotations are only added when this is set, since they can be large, such as the configuration `kubectl apply` keeps in them."
+						required:    false
+						type: string: {
+							default: ""
+							examples: ["kubernetes.pod_annotations"]
+							syntax: "literal"
+						}
+					}
 					pod_ip: {
 						common:      false
 						description: "Event field for Pod IPv4 Address."
@@ -135,6 +145,15 @@ components: sources: kubernetes_logs: {
 							syntax:  "literal"
 						}
 					}
+					pod_owner: {
+						common:      false
+						description: "Event field for the Pod owner, as `<kind>/<name>`."
+						required:    false
+						type: string: {
+							default: "kubernetes.pod_owner"
+							syntax:  "literal"
+						}
+					}
 					pod_uid: {
 						common:      false
 						description: "Event field for Pod uid."
@@ -245,6 +264,15 @@ components: sources: kubernetes_logs: {
 					syntax:  "literal"
 				}
 			}
+			"kubernetes.pod_annotations": {
+				description: "Pod annotations, when `annotation_fields.pod_annotations` is set to this field."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"myannotation": "myvalue"}]
+					options: {}
+				}
+			}
 			"kubernetes.pod_ip": {
 				description: "Pod IPv4 address."
 				required:    false
@@ -304,6 +332,16 @@ components: sources: kubernetes_logs: {
 					syntax:  "literal"
 				}
 			}
+			"kubernetes.pod_owner": {
+				description: "The controller that manages the Pod, such as a `ReplicaSet`."
+				required:    false
+				common:      false
+				type: string: {
+					examples: ["ReplicaSet/coredns-f9fd979d6"]
+					default: null
+					syntax:  "literal"
+				}
+			}
 			"kubernetes.pod_uid": {
 				description: "Pod uid."
 				required:    false
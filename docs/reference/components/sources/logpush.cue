@@ -0,0 +1,168 @@
+package metadata
+
+components: sources: logpush: {
+	_port: 443
+
+	title: "Logpush"
+
+	description: """
+		Receives batches of logs pushed over HTTPS by log delivery services, such as
+		[Cloudflare Logpush](\(urls.cloudflare_logpush)) and
+		[Fastly HTTPS logging](\(urls.fastly_https_logging)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.http
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: sources.http.configuration.address
+		auth:    sources.http.configuration.auth
+		fastly_service_ids: {
+			common:      false
+			description: "The IDs of the Fastly services allowed to deliver to this source, or `*` to allow any service. Fastly checks the list at `/.well-known/fastly/logging/challenge` before delivering logs. The challenge isn't answered unless this is set."
+			required:    false
+			type: array: {
+				default: null
+				items: type: string: {
+					examples: ["SU1Z0isxPaozGVKXdv0eY", "*"]
+					syntax: "literal"
+				}
+			}
+		}
+		headers: {
+			common:      false
+			description: "Headers that each request must have, with their expected values. Cloudflare Logpush can be configured to send such a header as a shared secret. Requests missing one are rejected with a `401 Unauthorized` response."
+			required:    false
+			type: object: {
+				examples: [{"X-Logpush-Token": "${LOGPUSH_TOKEN}"}]
+				options: {}
+			}
+		}
+		path: sources.http.configuration.path
+		signature: {
+			common:      false
+			description: "Verifies that each request is signed with a shared secret. Requests without a valid signature are rejected with a `401 Unauthorized` response."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					header: {
+						common:      true
+						description: "The header holding the hex-encoded HMAC-SHA256 of the request body, optionally prefixed by `sha256=`. The signature is of the body as sent, before it's decompressed."
+						required:    false
+						type: string: {
+							default: "X-Signature"
+							syntax:  "literal"
+						}
+					}
+					secret: {
+						description: "The secret the signature is keyed with."
+						required:    true
+						type: string: {
+							examples: ["${LOGPUSH_SECRET}"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "An individual line from a batch of lines received through an HTTP POST request."
+		fields: {
+			"*": {
+				description: "The fields of the line, if it's a JSON object."
+				required:    false
+				type: "*": {}
+			}
+			message: {
+				description: "The line, if it isn't a JSON object."
+				required:    false
+				type: string: {
+					examples: ["Hello world"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		batches: {
+			title: "Batches"
+			body: """
+				Each request holds a batch of newline-delimited lines, and each line becomes
+				an event. Lines holding a JSON object have their fields added to the event,
+				and other lines are kept as the `message`, so that a single malformed line
+				doesn't get the whole batch redelivered. Batches are decompressed according
+				to the `Content-Encoding` header, and gzipped batches are also detected when
+				the header is missing.
+				"""
+		}
+		status_codes: {
+			title: "Status codes"
+			body: """
+				Accepted batches are answered with a `200 OK` response. Requests that fail
+				verification are answered with a `401 Unauthorized` response, and batches
+				that can't be decompressed with a `422 Unprocessable Entity` response. Any
+				other response, such as when Vector is shutting down, leaves the batch to be
+				retried by the delivery service.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		http_bad_requests_total: components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		parse_errors_total:      components.sources.internal_metrics.output.metrics.parse_errors_total
+	}
+}
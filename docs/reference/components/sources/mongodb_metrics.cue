@@ -125,6 +125,16 @@ components: sources: mongodb_metrics: {
 				required:    true
 				examples: [_values.local_host]
 			}
+			replica_set: {
+				description: "The name of the replica set the MongoDB server belongs to."
+				required:    false
+				examples: ["rs0"]
+			}
+			replica_set_role: {
+				description: "The current role of the MongoDB server in its replica set."
+				required:    false
+				examples: ["primary", "secondary", "arbiter", "other"]
+			}
 		}
 
 		assets_total: {
@@ -0,0 +1,127 @@
+package metadata
+
+components: sources: nats: {
+	title: "NATS"
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			from: {
+				service: services.nats
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		credentials_file: components.sinks.nats.configuration.credentials_file
+		name:             components.sinks.nats.configuration.name
+		queue: {
+			common:      false
+			description: "The NATS queue group to join. The messages of a subject are shared between the subscribers of a queue group, instead of each subscriber receiving all of them."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		subject: {
+			description: "The NATS subject to subscribe to. Wildcards can be used to subscribe to several subjects."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["foo", "time.us.east", "time.*.east", "time.>", ">"]
+				syntax: "literal"
+			}
+		}
+		subject_key: {
+			common:      false
+			description: "The log field name to store the subject of each message in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "subject"
+				syntax:  "literal"
+			}
+		}
+		url: components.sinks.nats.configuration.url
+	}
+
+	output: logs: record: {
+		description: "An individual NATS message."
+		fields: {
+			message: {
+				description: "The payload of the message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			subject: {
+				description: "The subject the message was published to."
+				required:    true
+				type: string: {
+					examples: ["time.us.east"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	telemetry: metrics: {
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
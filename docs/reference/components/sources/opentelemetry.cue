@@ -0,0 +1,227 @@
+package metadata
+
+components: sources: opentelemetry: {
+	_grpc_port: 4317
+	_http_port: 4318
+
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs and metrics exported with the
+		[OpenTelemetry protocol (OTLP)](\(urls.opentelemetry_protocol)), over either gRPC
+		or HTTP, such as from OpenTelemetry collectors and SDKs.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _grpc_port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		grpc: {
+			common:      true
+			description: "Configures the listener for OTLP/gRPC, which serves the logs and metrics export services."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: "The address to listen for gRPC connections on. It _must_ include a port."
+						required:    true
+						type: string: {
+							examples: ["0.0.0.0:\(_grpc_port)"]
+							syntax: "literal"
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_enable:      true
+						enabled_default: false
+					}}
+				}
+			}
+		}
+		http: {
+			common:      true
+			description: "Configures the listener for OTLP/HTTP, which accepts protobuf encoded requests as `POST`s to `/v1/logs` and `/v1/metrics`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: "The address to listen for HTTP connections on. It _must_ include a port."
+						required:    true
+						type: string: {
+							examples: ["0.0.0.0:\(_http_port)"]
+							syntax: "literal"
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_enable:      true
+						enabled_default: false
+					}}
+				}
+			}
+		}
+	}
+
+	output: {
+		logs: record: {
+			description: "A log record exported with OTLP."
+			fields: {
+				attributes: {
+					description: "The attributes of the record."
+					required:    false
+					type: object: {
+						examples: [{"http.method": "GET"}]
+						options: {}
+					}
+				}
+				flags: {
+					description: "The trace flags of the record."
+					required:    false
+					type: uint: {
+						examples: [1]
+						unit: null
+					}
+				}
+				message: {
+					description: "The body of the record."
+					required:    true
+					type: "*": {}
+				}
+				resources: {
+					description: "The attributes of the resource the record comes from."
+					required:    false
+					type: object: {
+						examples: [{"service.name": "checkout"}]
+						options: {}
+					}
+				}
+				scope: {
+					description: "The instrumentation scope the record was emitted by."
+					required:    false
+					type: object: {
+						examples: [{"name": "io.opentelemetry.example", "version": "1.0.0"}]
+						options: {}
+					}
+				}
+				severity_number: {
+					description: "The numerical severity of the record."
+					required:    false
+					type: uint: {
+						examples: [9]
+						unit: null
+					}
+				}
+				severity_text: {
+					description: "The severity of the record, as named by its source."
+					required:    false
+					type: string: {
+						examples: ["INFO"]
+						syntax: "literal"
+					}
+				}
+				span_id: {
+					description: "The hex-encoded ID of the span the record is part of."
+					required:    false
+					type: string: {
+						examples: ["a7d25b5ef1e9f28c"]
+						syntax: "literal"
+					}
+				}
+				timestamp: {
+					description: "The time the event occurred, or else the time it was observed, or else the time it was received."
+					required:    true
+					type: timestamp: {}
+				}
+				trace_id: {
+					description: "The hex-encoded ID of the trace the record is part of."
+					required:    false
+					type: string: {
+						examples: ["5b8efff798038103d269b633813fc60c"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
+	}
+
+	how_it_works: {
+		metric_types: {
+			title: "Metric type interpretation"
+			body: """
+				Each data point of a metric becomes a metric event, tagged with the
+				attributes of the data point, the attributes of its resource prefixed
+				with `resource.`, and the name and version of its instrumentation scope.
+				Gauges become gauges, and monotonic sums become counters, which are
+				incremental for delta sums and absolute for cumulative sums. Sums that
+				aren't monotonic become gauges. Histograms and summaries become aggregated
+				histograms and summaries.
+				"""
+		}
+		acknowledgement: {
+			title: "Acknowledgement"
+			body: """
+				Export requests are only answered once their events are accepted by
+				Vector, so that exporters retry requests that weren't delivered.
+				Requests with bodies that aren't protobuf encoded, such as the JSON
+				encoding of OTLP/HTTP, are rejected with a `415 Unsupported Media Type`
+				response.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		http_bad_requests_total: components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -72,6 +72,125 @@ components: sources: prometheus_scrape: {
 				}
 			}
 		}
+		endpoint_tag: {
+			common:      false
+			description: "The tag name added to each metric holding the URL of the endpoint it was scraped from. Not added if unset."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["endpoint"]
+				syntax: "literal"
+			}
+		}
+		honor_labels: {
+			common:      false
+			description: "Keep the scraped value of labels that conflict with `instance_tag` or `endpoint_tag`. Otherwise, the scraped value is moved to a label prefixed with `exported_`, as Prometheus does."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		instance_tag: {
+			common:      false
+			description: "The tag name added to each metric holding the `host:port` of the endpoint it was scraped from. Not added if unset."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["instance"]
+				syntax: "literal"
+			}
+		}
+		metric_relabel_configs: {
+			common: false
+			description: """
+				Rules rewriting or dropping scraped metrics, in the format of Prometheus'
+				[`metric_relabel_configs`][urls.prometheus_metric_relabel_configs]. They are applied in order, after
+				`instance_tag` and `endpoint_tag`. The metric name is available as the `__name__` label.
+				"""
+			required: false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						action: {
+							common:      true
+							description: "What to do with metrics matching `regex`."
+							required:    false
+							warnings: []
+							type: string: {
+								default: "replace"
+								enum: {
+									replace:   "Set `target_label` to `replacement`, with the groups of `regex` expanded. An empty value removes the label."
+									keep:      "Drop the metrics whose source labels don't match `regex`."
+									drop:      "Drop the metrics whose source labels match `regex`."
+									labeldrop: "Remove the labels whose names match `regex`."
+									labelkeep: "Remove the labels whose names don't match `regex`."
+								}
+								syntax: "literal"
+							}
+						}
+						regex: {
+							common:      true
+							description: "The regular expression matched against the joined values of `source_labels`, or against label names. It must match the whole value."
+							required:    false
+							warnings: []
+							type: string: {
+								default: "(.*)"
+								examples: ["5..", "go_.*"]
+								syntax: "regex"
+							}
+						}
+						replacement: {
+							common:      false
+							description: "The value `target_label` is set to, in which `$1` and `${name}` refer to the groups of `regex`."
+							required:    false
+							warnings: []
+							type: string: {
+								default: "$1"
+								syntax:  "literal"
+							}
+						}
+						separator: {
+							common:      false
+							description: "The separator between the values of `source_labels`."
+							required:    false
+							warnings: []
+							type: string: {
+								default: ";"
+								syntax:  "literal"
+							}
+						}
+						source_labels: {
+							common:      true
+							description: "The labels whose values are joined and matched against `regex`. Missing labels have an empty value."
+							required:    false
+							warnings: []
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["__name__", "code"]
+									syntax: "literal"
+								}
+							}
+						}
+						target_label: {
+							common:      true
+							description: "The label set by the `replace` action, which requires it."
+							required:    false
+							warnings: []
+							type: string: {
+								default: null
+								examples: ["__name__", "status"]
+								syntax: "literal"
+							}
+						}
+					}
+				}
+			}
+		}
 		scrape_interval_secs: {
 			common:      true
 			description: "The interval between scrapes, in seconds."
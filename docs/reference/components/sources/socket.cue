@@ -16,6 +16,10 @@ components: sources: socket: {
 
 	features: {
 		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		receive: {
 			from: {
 				service: services.socket_client
@@ -71,6 +75,23 @@ components: sources: socket: {
 				syntax: "literal"
 			}
 		}
+		decompression: {
+			common:        false
+			description:   "How received payloads are compressed. With `tcp`, the whole stream of each connection is decompressed before it's split into lines. With `udp`, each datagram is decompressed on its own, and datagrams that fail to decompress are dropped."
+			relevant_when: "mode = `tcp` or `udp`"
+			required:      false
+			warnings: []
+			type: string: {
+				default: "none"
+				enum: {
+					none: "Payloads aren't compressed."
+					gzip: "Payloads are compressed with gzip."
+					zstd: "Payloads are compressed with zstd."
+					auto: "Payloads are decompressed if they start with the magic bytes of gzip or zstd, and are passed through otherwise."
+				}
+				syntax: "literal"
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -84,7 +105,7 @@ components: sources: socket: {
 		}
 		max_length: {
 			common:      true
-			description: "The maximum bytes size of incoming messages before they are discarded."
+			description: "The maximum bytes size of incoming messages before they are discarded. With `unix_datagram`, this is also the size of the buffer datagrams are received in, and longer datagrams are truncated."
 			required:    false
 			warnings: []
 			type: uint: {
@@ -108,7 +129,7 @@ components: sources: socket: {
 		}
 		path: {
 			description:   "The unix socket path. *This should be an absolute path*."
-			relevant_when: "mode = `unix`"
+			relevant_when: "mode = `unix_datagram` or `unix_stream`"
 			required:      true
 			warnings: []
 			type: string: {
@@ -116,6 +137,18 @@ components: sources: socket: {
 				syntax: "literal"
 			}
 		}
+		socket_file_mode: {
+			common:        false
+			description:   "The permissions of the socket file, set once it's bound. When not set, they follow the process umask."
+			relevant_when: "mode = `unix_datagram` or `unix_stream`"
+			required:      false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [0o660, 0o666]
+				unit: null
+			}
+		}
 		shutdown_timeout_secs: {
 			common:        false
 			description:   "The timeout before a connection is forcefully closed during shutdown."
@@ -165,5 +198,7 @@ components: sources: socket: {
 		connection_failed_total:      components.sources.internal_metrics.output.metrics.connection_failed_total
 		connection_send_errors_total: components.sources.internal_metrics.output.metrics.connection_send_errors_total
 		connection_shutdown_total:    components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		decompress_errors_total:      components.sources.internal_metrics.output.metrics.decompress_errors_total
+		reconnect_attempts_total:     components.sources.internal_metrics.output.metrics.reconnect_attempts_total
 	}
 }
@@ -62,6 +62,13 @@ components: sources: splunk_hec: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Respond to a request only once the events it carried are accepted by the sinks that have `acknowledgements` enabled. Requests whose events aren't delivered get a `500` response, so that clients send them again."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		address: {
 			common:      true
 			description: "The address to accept connections on."
@@ -72,6 +79,44 @@ components: sources: splunk_hec: {
 				syntax:  "literal"
 			}
 		}
+		indexer_acknowledgements: {
+			common:      false
+			description: "Splunk HEC [indexer acknowledgements][urls.splunk_hec_indexer_acknowledgements], for clients such as forwarders that are configured with `useACK=true`."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					enabled: {
+						common:      true
+						description: "Answer requests with an `ackId` right away, and report through the `/services/collector/ack` endpoint whether the events of each request were accepted by the sinks that have `acknowledgements` enabled. Requests have to carry a channel in the `X-Splunk-Request-Channel` header."
+						required:    false
+						warnings: []
+						type: bool: default: false
+					}
+					max_idle_secs: {
+						common:      false
+						description: "Channels that aren't used for this long are forgotten, along with their ack ids."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 300
+							unit:    "seconds"
+						}
+					}
+					max_pending_acks_per_channel: {
+						common:      false
+						description: "The number of ack ids a channel can have until they're queried. Further requests on the channel get a `503` response."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 1000000
+							unit:    null
+						}
+					}
+				}
+			}
+		}
 		token: {
 			common:      true
 			description: "If supplied, incoming requests must supply this token in the `Authorization` header, just as a client would if it was communicating with the Splunk HEC endpoint directly. If _not_ supplied, the `Authorization` header will be ignored and requests will not be authenticated."
@@ -98,6 +143,21 @@ components: sources: splunk_hec: {
 		}
 	}
 
+	how_it_works: {
+		indexer_acknowledgements: {
+			title: "Indexer acknowledgements"
+			body: """
+				With `indexer_acknowledgements.enabled`, each request to the `event` and `raw`
+				endpoints is answered with an `ackId` that's unique within its channel, as soon as
+				its events are read. Clients then post `{"acks": [0, 1]}` to
+				`/services/collector/ack` on the same channel, and get `{"acks": {"0": true, "1": false}}`
+				back. An ack id is `true` once the events of its request are accepted by the sinks,
+				and is forgotten after it has been reported so. Ack ids of requests whose events
+				aren't delivered stay `false`, so that clients send those requests again.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		http_request_errors_total: components.sources.internal_metrics.output.metrics.http_request_errors_total
 		requests_received_total:   components.sources.internal_metrics.output.metrics.requests_received_total
@@ -127,6 +127,18 @@ components: sources: statsd: {
 				[metric][docs.data-model.metric] data model page for more info.
 				"""
 		}
+
+		multiple_values: {
+			title: "Multiple Values"
+			body: """
+				A line can carry several values of the same metric separated by
+				`:`, as in `page.load:120:135|ms`, the way DogStatsD clients
+				pack them. Counter values are summed, timer, histogram and
+				distribution values become samples of a single metric, set
+				values are all added to the set, and gauge values are applied in
+				order.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
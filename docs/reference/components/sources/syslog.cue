@@ -48,6 +48,17 @@ components: sources: syslog: {
 
 	configuration: sources.socket.configuration & {
 		"type": "type": string: enum: syslog: "The type of this component."
+		socket_file_mode: {
+			common:        false
+			description:   "The permissions of the socket file. The default lets any local user log, as a syslog daemon would."
+			relevant_when: "mode = `unix_datagram`"
+			required:      false
+			warnings: []
+			type: uint: {
+				default: 0o666
+				unit:    null
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -182,6 +193,46 @@ components: sources: syslog: {
 				"""
 		}
 
+		octet_counting: {
+			title: "Octet Counting"
+			body: """
+				With `mode` set to `tcp` or `unix`, messages framed with octet counting, as
+				described in [RFC 6587][urls.syslog_6587], are detected by their leading length
+				and read whole, even if they contain new lines. A frame longer than
+				`max_length` closes the connection, as its end can't be found.
+				"""
+		}
+
+		client_verification: {
+			title: "Verifying clients"
+			body: """
+				With `mode` set to `tcp`, to accept only clients presenting a certificate
+				signed by a trusted authority, enable `tls` with `tls.verify_certificate` set
+				and `tls.ca_file` pointing to that authority.
+				"""
+		}
+
+		dev_log: {
+			title: "Replacing the local syslog daemon"
+			body: """
+				With `mode` set to `unix_datagram`, Vector listens on `/dev/log` (or `path`)
+				the way a local syslog daemon does, so that it receives the messages of
+				applications using the libc `syslog` API. Each datagram is taken as a single
+				message, even if it spans multiple lines.
+
+				A socket file left behind by a stopped daemon or a previous run is removed
+				before binding, but Vector refuses to take over a socket that another
+				process is still listening on, so the existing daemon must be stopped first.
+				Any other file at the path, such as the `/dev/log` symlink systemd creates,
+				is never removed, and the source fails to start instead.
+
+				The socket is created in place, rather than moved there, so on SELinux
+				systems it's labelled by the policy's file type transitions, `devlog_t` for
+				`/dev/log`, and its permissions are then set with `socket_file_mode`.
+				Vector must run in a domain allowed to create it.
+				"""
+		}
+
 		parsing: {
 			title: "Parsing"
 			body:  """
@@ -27,7 +27,7 @@ components: sources: vector: {
 				interface: socket: {
 					direction: "incoming"
 					port:      _port
-					protocols: ["tcp"]
+					protocols: ["tcp", "http"]
 					ssl: "optional"
 				}
 			}
@@ -72,6 +72,20 @@ components: sources: vector: {
 				syntax: "literal"
 			}
 		}
+		version: {
+			common:      false
+			description: "The version of the protocol spoken with the other Vector instance, which must be the same on both ends. Version 2 only supports the `address`, `tls` and, in the sink, `batch` and `request` options."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "1"
+				enum: {
+					"1": "Events are sent one at a time over TCP, without acknowledgement."
+					"2": "Batches of events are sent over [gRPC](\(urls.grpc)), and acknowledged once the receiving Vector has accepted them."
+				}
+				syntax: "literal"
+			}
+		}
 		shutdown_timeout_secs: {
 			common:      false
 			description: "The timeout before a connection is forcefully closed during shutdown."
@@ -116,15 +130,19 @@ components: sources: vector: {
 			title: "Communication Protocol"
 			body: """
 				Upstream Vector instances forward data to downstream Vector
-				instances via the TCP protocol.
+				instances via the TCP protocol. With `version = "2"`, batches
+				of events are sent as requests of a [gRPC](\(urls.grpc))
+				service instead, which are multiplexed over HTTP/2 connections.
 				"""
 		}
 		message_acknowledgement: {
 			title: "Message Acknowledgement"
 			body: """
-				Currently, Vector does not perform any application level message
-				acknowledgement. While rare, this means the individual message
-				could be lost.
+				With `version = "1"`, Vector does not perform any application
+				level message acknowledgement. While rare, this means the
+				individual message could be lost. With `version = "2"`, each
+				batch is acknowledged once the downstream Vector has accepted
+				its events, and retried otherwise.
 				"""
 		}
 
@@ -132,5 +150,6 @@ components: sources: vector: {
 
 	telemetry: metrics: {
 		protobuf_decode_errors_total: components.sources.internal_metrics.output.metrics.protobuf_decode_errors_total
+		reconnect_attempts_total:     components.sources.internal_metrics.output.metrics.reconnect_attempts_total
 	}
 }
@@ -0,0 +1,86 @@
+package metadata
+
+components: transforms: aggregate: {
+	title: "Aggregate"
+
+	description: """
+		Aggregates metrics over a window of time, and flushes the merged values
+		at the end of each window, reducing the rate of metrics reaching
+		downstream sinks.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		interval_ms: {
+			common:      true
+			description: "The interval over which metrics are aggregated. At the end of each interval, the aggregated metrics are flushed."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		aggregation: {
+			title: "Aggregation"
+			body: """
+				Metrics are grouped by their series, that is their name, namespace, and
+				tags. Incremental metrics of a series are merged together: counters are
+				summed, histograms and summaries have their buckets and counts added, sets
+				are joined, and distributions have their samples collected. Absolute
+				metrics, such as most gauges, replace the metric recorded for their
+				series, so that only the last value is flushed. A metric whose type
+				differs from the one recorded for its series replaces it.
+
+				The metrics recorded are flushed when the input ends, so that nothing is
+				lost on shutdown.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_recorded_total: components.sources.internal_metrics.output.metrics.events_recorded_total
+		flushes_total:         components.sources.internal_metrics.output.metrics.flushes_total
+	}
+}
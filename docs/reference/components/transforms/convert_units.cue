@@ -0,0 +1,144 @@
+package metadata
+
+components: transforms: convert_units: {
+	title: "Convert Units"
+
+	description: """
+		Converts the values of metrics between units, such as bytes to mebibytes or
+		milliseconds to seconds, or rescales them by a factor, so that metrics from
+		heterogeneous exporters can be normalized before reaching a shared backend.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		rules: {
+			description: """
+				The conversions to apply. Only the first rule whose `metric` pattern matches the name of a metric
+				is applied to it. Metrics that don't match any rule are passed through unchanged.
+				"""
+			required: true
+			warnings: []
+			type: array: items: type: object: {
+				examples: []
+				options: {
+					metric: {
+						description: "A glob pattern matched against the name of metrics, such as `*_milliseconds`."
+						required:    true
+						warnings: []
+						type: string: {
+							examples: ["*_milliseconds", "node_memory_*_bytes"]
+							syntax: "literal"
+						}
+					}
+					from: {
+						common:      true
+						description: "The unit the values of matching metrics are in. Requires `to`."
+						required:    false
+						warnings: []
+						type: string: {
+							default: null
+							enum: {
+								bits:         "Bits."
+								bytes:        "Bytes."
+								kilobytes:    "Kilobytes, 1000 bytes."
+								megabytes:    "Megabytes, 1000 kilobytes."
+								gigabytes:    "Gigabytes, 1000 megabytes."
+								kibibytes:    "Kibibytes, 1024 bytes."
+								mebibytes:    "Mebibytes, 1024 kibibytes."
+								gibibytes:    "Gibibytes, 1024 mebibytes."
+								nanoseconds:  "Nanoseconds."
+								microseconds: "Microseconds."
+								milliseconds: "Milliseconds."
+								seconds:      "Seconds."
+								minutes:      "Minutes."
+								hours:        "Hours."
+							}
+							syntax: "literal"
+						}
+					}
+					to: {
+						common:      true
+						description: "The unit to convert the values of matching metrics to. It must measure the same thing as `from`, information or time. Takes the same values as `from`."
+						required:    false
+						warnings: []
+						type: string: {
+							default: null
+							examples: ["seconds", "mebibytes"]
+							syntax: "literal"
+						}
+					}
+					scale: {
+						common:      false
+						description: "A factor to multiply the values of matching metrics by, instead of converting between `from` and `to`."
+						required:    false
+						warnings: []
+						type: float: {
+							default: null
+							examples: [0.001, 100.0]
+						}
+					}
+					rename: {
+						common:      false
+						description: "A new name for matching metrics, so that their name reflects their new unit."
+						required:    false
+						warnings: []
+						type: string: {
+							default: null
+							examples: ["request_duration_seconds"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		scaled_values: {
+			title: "Scaled Values"
+			body: """
+				The values of counters and gauges, the samples of distributions, the bucket limits
+				and sums of histograms, and the quantile values and sums of summaries are scaled.
+				Counts of observations are left unchanged, as are sets, which have no values to scale.
+				"""
+		}
+	}
+}
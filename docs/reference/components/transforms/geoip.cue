@@ -46,7 +46,8 @@ components: transforms: geoip: {
 			description: """
 				Path to the [MaxMind GeoIP2](\(urls.maxmind_geoip2)) or [GeoLite2 binary city
 				database](\(urls.maxmind_geolite2_city)) file (`GeoLite2-City.mmdb`). Other
-				databases, such as the the country database, are not supported.
+				databases, such as the the country database, are not supported. Changes to
+				the file are picked up without restarting Vector.
 				"""
 			required:    true
 			type: string: {
@@ -102,6 +103,17 @@ components: transforms: geoip: {
 				format](\(urls.maxmind_db_file_format)).
 				"""
 		}
+		database_reloading: {
+			title: "Database reloading"
+			body:  """
+				The database file is checked for changes every 10 seconds, and opened again
+				when its modification time has changed, so that updated databases, such as
+				those downloaded by `geoipupdate`, are used without restarting Vector. If the
+				new file can't be read, the previous database keeps being used, and the file
+				is tried again at the next check. The file is opened in the background, so
+				events keep being enriched with the previous database meanwhile.
+				"""
+		}
 	}
 
 	output: logs: line: {
@@ -278,6 +290,8 @@ components: transforms: geoip: {
 	}
 
 	telemetry: metrics: {
-		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+		database_reload_errors_total: components.sources.internal_metrics.output.metrics.database_reload_errors_total
+		database_reloads_total:       components.sources.internal_metrics.output.metrics.database_reloads_total
+		processing_errors_total:      components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
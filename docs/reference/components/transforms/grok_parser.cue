@@ -63,6 +63,17 @@ components: transforms: grok_parser: {
 			warnings: []
 			type: bool: default: true
 		}
+		error_field: {
+			common:      false
+			description: "The field of events sent to the `rejected` output holding the reason they failed to parse."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "error"
+				examples: ["error", "parse_error"]
+				syntax: "literal"
+			}
+		}
 		field: {
 			common:      true
 			description: "The log field to execute the `pattern` against. Must be a `string` value."
@@ -93,6 +104,17 @@ components: transforms: grok_parser: {
 	}
 
 	how_it_works: {
+		failed_parsing: {
+			title: "Failed Parsing"
+			body: """
+				If the `field` is missing, or its value does not match the `pattern`, the event
+				is passed on unchanged. When another component uses the
+				`<transform_name>.rejected` output as an input, the original event is sent to
+				that output instead, with the reason in the `error_field` field, so that these
+				events can be handled separately.
+				"""
+		}
+
 		available_patterns: {
 			title: "Available Patterns"
 			body:  support.notices[0]
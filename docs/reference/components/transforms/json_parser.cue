@@ -57,10 +57,22 @@ components: transforms: json_parser: {
 			type: bool: default: true
 		}
 		drop_invalid: {
-			description: "If `true` events with invalid JSON will be dropped, otherwise the event will be kept and passed through."
-			required:    true
+			common:      true
+			description: "If `true` events with invalid JSON will be dropped, otherwise the event will be kept and passed through. Only used when the transform's `rejected` output isn't used."
+			required:    false
 			warnings: []
-			type: bool: {}
+			type: bool: default: false
+		}
+		error_field: {
+			common:      false
+			description: "The field of events sent to the `rejected` output holding the reason they failed to parse."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "error"
+				examples: ["error", "parse_error"]
+				syntax: "literal"
+			}
 		}
 		field: {
 			common:      true
@@ -102,11 +114,17 @@ components: transforms: json_parser: {
 		invalid_json: {
 			title: "Invalid JSON"
 			body: """
-				If the value for the specified `field` is not valid JSON you can control keeping
-				or discarding the event with the `drop_invalid` option. Setting it to `true` will
-				discard the event and drop it entirely. Setting it to `false` will keep the
-				event and pass it through. Note that passing through the event could cause
-				problems and violate assumptions about the structure of your event.
+				If the value for the specified `field` is missing, is not valid JSON, or is not
+				a JSON object, you can control keeping or discarding the event with the
+				`drop_invalid` option. Setting it to `true` will discard the event and drop it
+				entirely. Setting it to `false` will keep the event and pass it through. Note
+				that passing through the event could cause problems and violate assumptions
+				about the structure of your event.
+
+				Alternatively, when another component uses the `<transform_name>.rejected`
+				output as an input, the event is not passed on. Instead, the original event is
+				sent to that output, with the reason in the `error_field` field, so that these
+				events can be handled separately. `drop_invalid` has no effect then.
 				"""
 		}
 
@@ -62,6 +62,17 @@ components: transforms: key_value_parser: {
 			type: bool: default: true
 		}
 
+		error_field: {
+			common:      false
+			description: "The field of events sent to the `rejected` output holding the reason they failed to parse."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "error"
+				examples: ["error", "parse_error"]
+				syntax: "literal"
+			}
+		}
 		field: {
 			common:      true
 			description: "The log field containing key/value pairs to parse. Must be a `string` value."
@@ -168,6 +179,16 @@ components: transforms: key_value_parser: {
 				splitting of the message into pairs. The `field_split` option allows you to define the character(s) which split the key from the value.
 				"""
 		}
+
+		failed_parsing: {
+			title: "Failed Parsing"
+			body: """
+				If the `field` is missing, or the `target_field` already exists and `overwrite_target` is `false`, the
+				event is passed on unchanged. When another component uses the `<transform_name>.rejected` output as an
+				input, the original event is sent to that output instead, with the reason in the `error_field` field, so
+				that these events can be handled separately.
+				"""
+		}
 	}
 
 	examples: [
@@ -0,0 +1,109 @@
+package metadata
+
+components: transforms: lookup: {
+	title: "Lookup"
+
+	description: """
+		Looks events up in an [enrichment table](\(urls.vector_configuration)#enrichment_tables),
+		and adds the columns of the matching row to them, such as the team owning
+		the user ID of an event.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		enrich: {
+			from: service: {
+				name:     "Enrichment tables"
+				url:      urls.vector_configuration
+				versions: null
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		table: {
+			description: "The name of the enrichment table to look events up in. It must be configured in `enrichment_tables`."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["teams"]
+				syntax: "literal"
+			}
+		}
+		match_fields: {
+			description: "Maps columns of the table to the fields of events whose values they must equal. The first row matching all of them is used."
+			required:    true
+			warnings: []
+			type: object: {
+				examples: [{user_id: "user.id"}]
+				options: {
+					"*": {
+						description: "The field of events whose value the column must equal."
+						required:    true
+						warnings: []
+						type: string: {
+							examples: ["user.id"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+		target: {
+			common:      true
+			description: "The field the columns of the matching row are inserted under. They're inserted at the root of the event if it's not set."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["user", "team_info"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		reloading: {
+			title: "Reloading tables"
+			body: """
+				Enrichment tables are read again when Vector's configuration is
+				reloaded, and the transform uses their new rows without being
+				rebuilt. The new rows are only used once the reload succeeds; if it
+				fails, the previous rows are kept. Events are passed through unchanged when the fields to match
+				are missing, or when no row matches them.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		lookup_misses_total:     components.sources.internal_metrics.output.metrics.lookup_misses_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
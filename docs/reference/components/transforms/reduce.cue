@@ -123,6 +123,7 @@ components: transforms: reduce: {
 								concat:         "Concatenate each string value (delimited with a space)."
 								concat_newline: "Concatenate each string value (delimited with a newline)."
 								discard:        "Discard all but the first value found."
+								retain:         "Discard all but the last value found."
 								sum:            "Sum all numeric values."
 								max:            "The maximum of all numeric values."
 								min:            "The minimum of all numeric values."
@@ -51,7 +51,7 @@ components: transforms: regex_parser: {
 	configuration: {
 		drop_failed: {
 			common:      true
-			description: "If the event should be dropped if parsing fails."
+			description: "If the event should be dropped if parsing fails. Only used when the transform's `rejected` output isn't used."
 			required:    false
 			warnings: []
 			type: bool: default: false
@@ -63,6 +63,17 @@ components: transforms: regex_parser: {
 			warnings: []
 			type: bool: default: true
 		}
+		error_field: {
+			common:      false
+			description: "The field of events sent to the `rejected` output holding the reason they failed to parse."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "error"
+				examples: ["error", "parse_error"]
+				syntax: "literal"
+			}
+		}
 		field: {
 			common:      true
 			description: "The log field to parse."
@@ -143,7 +154,9 @@ components: transforms: regex_parser: {
 		failed_parsing: {
 			title: "Failed Parsing"
 			body: """
-				By default, if the input message text does not match any of the configured regular expression patterns, this transform will log an error message but leave the log event unchanged. If you instead wish to have this transform drop the event, set `drop_failed = true`.
+				By default, if the `field` is missing, or its value does not match any of the configured regular expression patterns, this transform will log an error message but leave the log event unchanged. If you instead wish to have this transform drop the event, set `drop_failed = true`.
+
+				When another component uses the `<transform_name>.rejected` output as an input, the original event is sent to that output instead, with the reason in the `error_field` field, so that these events can be handled separately. `drop_failed` has no effect then.
 				"""
 		}
 		flags: {
@@ -40,6 +40,7 @@ components: transforms: route: {
 			description: """
 				A table of route identifiers to logical conditions representing the filter of the route. Each route
 				can then be referenced as an input by other components with the name `<transform_name>.<route_id>`.
+				The `_unmatched` identifier is reserved for the output of events matching no route.
 				"""
 			required: true
 			warnings: []
@@ -89,6 +90,18 @@ components: transforms: route: {
 		},
 	]
 
+	how_it_works: {
+		unmatched_events: {
+			title: "Unmatched events"
+			body: """
+				Events that match none of the routes are sent to the `<transform_name>._unmatched`
+				output, so that they can be handled instead of being dropped. The output is only
+				built when another component consumes it, as it checks every event against all
+				the conditions again.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
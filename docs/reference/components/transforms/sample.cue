@@ -55,7 +55,9 @@ components: transforms: sample: {
 		exclude: {
 			common: true
 			description: """
-				The set of logical conditions to exclude events from sampling.
+				The set of logical conditions to exclude events from sampling. The
+				`check_fields` predicates it previously took without a `type`, such as
+				`exclude."status.eq" = "error"`, are still accepted.
 				"""
 			required: false
 			warnings: []
@@ -68,17 +70,34 @@ components: transforms: sample: {
 			}
 		}
 		rate: {
+			common: true
 			description: """
 				The rate at which events will be forwarded, expressed as 1/N. For example,
 				`rate = 10` means 1 out of every 10 events will be forwarded and the rest will be dropped.
+				Exactly one of `rate` and `ratio` must be set.
 				"""
-			required: true
+			required: false
 			warnings: []
 			type: uint: {
+				default: null
 				examples: [10]
 				unit: null
 			}
 		}
+		ratio: {
+			common: false
+			description: """
+				The fraction of events which will be forwarded, between `0` and `1`. For example,
+				`ratio = 0.25` means 1 out of every 4 events will be forwarded and the rest will be dropped.
+				Exactly one of `rate` and `ratio` must be set.
+				"""
+			required: false
+			warnings: []
+			type: float: {
+				default: null
+				examples: [0.25]
+			}
+		}
 	}
 
 	input: {
@@ -86,6 +105,20 @@ components: transforms: sample: {
 		metrics: null
 	}
 
+	output: logs: line: {
+		description: "The sampled events, unless they were excluded from sampling."
+		fields: {
+			sample_rate: {
+				description: "The configured `rate` or `ratio` the event was sampled at."
+				required:    true
+				type: string: {
+					examples: ["10", "0.25"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
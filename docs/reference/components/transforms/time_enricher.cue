@@ -0,0 +1,174 @@
+package metadata
+
+components: transforms: time_enricher: {
+	title: "Time Enricher"
+
+	description: """
+		Derives calendar fields, such as the hour, weekday and whether the event
+		falls within business hours, from an event's timestamp.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		business_hours: {
+			common:      true
+			description: """
+				When set, an `is_business_hours` field is added to the output, based on the
+				configured days, hours and holidays in the configured `timezone`.
+				"""
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					start_hour: {
+						common:      true
+						description: "The hour of the day, inclusive, at which business hours begin."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 9
+							examples: [8, 9]
+							unit: null
+						}
+					}
+					end_hour: {
+						common:      true
+						description: "The hour of the day, exclusive, at which business hours end."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 17
+							examples: [17, 18]
+							unit: null
+						}
+					}
+					days: {
+						common:      true
+						description: "The days of the week that are considered business days."
+						required:    false
+						warnings: []
+						type: array: {
+							default: ["Mon", "Tue", "Wed", "Thu", "Fri"]
+							items: type: string: {
+								examples: ["Mon", "Saturday"]
+								syntax: "literal"
+							}
+						}
+					}
+					holidays: {
+						common:      false
+						description: "Dates, in `YYYY-MM-DD` format, that are never considered business hours."
+						required:    false
+						warnings: []
+						type: array: {
+							default: []
+							items: type: string: {
+								examples: ["2021-12-25"]
+								syntax: "literal"
+							}
+						}
+					}
+				}
+			}
+		}
+		field: {
+			common:      true
+			description: "The timestamp field to derive calendar fields from."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "timestamp"
+				examples: ["timestamp", "parent.child"]
+				syntax: "literal"
+			}
+		}
+		target: {
+			common:      true
+			description: "The field to insert the derived calendar fields into. See [output](#output) for more info."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "time"
+				examples: ["time", "parent.child"]
+				syntax: "literal"
+			}
+		}
+		timezone: configuration._timezone
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	examples: [
+		{
+			title: "Business hours"
+			configuration: {
+				timezone: "America/New_York"
+				business_hours: {
+					start_hour: 9
+					end_hour:   17
+				}
+			}
+			input: log: {
+				timestamp: "2021-01-04T15:30:00Z"
+			}
+			output: log: {
+				timestamp: "2021-01-04T15:30:00Z"
+				time: {
+					year:              2021
+					month:             1
+					day:               4
+					day_of_year:       4
+					week:              1
+					hour:              10
+					minute:            30
+					weekday:           "monday"
+					is_business_hours: true
+				}
+			}
+		},
+	]
+
+	how_it_works: {
+		missing_timestamps: {
+			title: "Missing Timestamps"
+			body:  """
+				Events whose `field` is missing, or isn't a timestamp, are passed through
+				unchanged and a `processing_errors_total` metric is emitted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
@@ -0,0 +1,110 @@
+package metadata
+
+components: transforms: traffic_split: {
+	title: "Traffic Split"
+
+	description: """
+		Splits a stream of events across multiple sub-streams by percentage, for
+		example to send a small share of traffic to an experimental sink.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		route: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		key_field: {
+			common: false
+			description: """
+				The name of the log field whose value will be hashed to pick the output of each event.
+
+				Events with the same value are always sent to the same output. If left unspecified, or if the event
+				doesn't have `key_field`, the whole event is hashed instead, so events are spread across outputs in
+				proportion to their percentages, and identical events are sent to the same output.
+				"""
+			required: false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["user_id"]
+				syntax: "literal"
+			}
+		}
+		outputs: {
+			description: """
+				A table of output identifiers to the percentage of events sent to that output. The percentages must
+				add up to 100. Each output can then be referenced as an input by other components with the name
+				`<transform_name>.<output_id>`.
+				"""
+			required: true
+			warnings: []
+			type: object: {
+				options: {
+					"*": {
+						description: """
+							The percentage of events, between 0 and 100, that are sent to this output.
+							"""
+						required: true
+						warnings: []
+						type: float: {
+							examples: [95.0, 5.0]
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	examples: [
+		{
+			title: "Canary a new sink"
+			configuration: {
+				key_field: "user_id"
+				outputs: {
+					stable: 95.0
+					canary: 5.0
+				}
+			}
+			input: log: {
+				user_id: "4a8f2c"
+				message: "Login succeeded"
+			}
+			output: log: {
+				user_id: "4a8f2c"
+				message: "Login succeeded"
+			}
+		},
+	]
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
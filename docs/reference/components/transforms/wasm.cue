@@ -68,9 +68,9 @@ components: transforms: wasm: {
 				syntax: "file_system_path"
 			}
 		}
-		heap_max_size: {
+		heap_memory_size: {
 			common:      false
-			description: "The maximum size of the heap of this module, in bytes. (This includes the module itself, default is 10 MB.)"
+			description: "The maximum size of the heap of this module, in bytes. (This includes the module itself, default is 10 MB.) A module growing its heap beyond it fails to process the event."
 			required:    false
 			warnings: []
 			type: uint: {
@@ -91,6 +91,16 @@ components: transforms: wasm: {
 				syntax: "file_system_path"
 			}
 		}
+		options: {
+			common:      false
+			description: "Options passed to the module when it's initialized, which it can read through the host calls of the WASM ABI."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [{field: "message", drop_empty: true}]
+				options: {}
+			}
+		}
 	}
 
 	input: {
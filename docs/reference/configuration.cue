@@ -23,6 +23,18 @@ configuration: {
 			}
 		}
 
+		expire_metrics_secs: {
+			common:      false
+			description: "How long internal metrics that haven't been updated are kept, such as the metrics of components removed by reloading the configuration. Expired metrics are no longer reported by the `internal_metrics` source, the API or `vector top`. Metrics are kept forever if this option isn't set."
+			required:    false
+			warnings: []
+			type: float: {
+				default: null
+				examples: [60.0]
+				unit: "seconds"
+			}
+		}
+
 		healthchecks: {
 			common: false
 			description: """
@@ -58,6 +70,416 @@ configuration: {
 							default: false
 						}
 					}
+
+					interval_secs: {
+						common: false
+						description: """
+							How often sinks' health checks are re-run after startup. The latest
+							results are reported by the API. Health checks are only run on
+							startup by default.
+							"""
+						required: false
+						warnings: []
+						type: uint: {
+							default: 0
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+
+		control: {
+			common: false
+			description: """
+				Registers Vector with a remote control plane, for visibility of a fleet
+				of instances from one place. On startup, and then on every heartbeat,
+				Vector posts a JSON report with its hostname, version, uptime, a
+				fingerprint of its running configuration, and totals of its processed
+				events, processed bytes and errors. Changes to these options are picked
+				up by a restart rather than a reload.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					endpoint: {
+						common:      true
+						description: "The URL reports are posted to. Reporting is disabled unless it's set, and an endpoint that isn't an absolute URL fails loading the configuration."
+						required:    false
+						warnings: []
+						type: string: {
+							default: null
+							examples: ["https://control.example.com/api/v1/agents"]
+							syntax: "literal"
+						}
+					}
+
+					token: {
+						common:      true
+						description: "A token sent as a bearer token in the `Authorization` header of reports."
+						required:    false
+						warnings: []
+						type: string: {
+							default: null
+							examples: ["${CONTROL_PLANE_TOKEN}"]
+							syntax: "literal"
+						}
+					}
+
+					heartbeat_secs: {
+						common:      false
+						description: "How often a report is posted to the control plane."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 30
+							unit:    "seconds"
+						}
+					}
+
+					retry_attempts: {
+						common: false
+						description: """
+							How many times a report is attempted, with an exponential backoff
+							between attempts, before it's given up on until the next heartbeat.
+							"""
+						required: false
+						warnings: []
+						type: uint: {
+							default: 5
+							unit:    null
+						}
+					}
+
+					tls: {
+						common: false
+						description: """
+							Configures the TLS options for connections to the control plane,
+							such as `ca_file`, `crt_file`, `key_file` and `verify_certificate`.
+							Takes the same options as the `tls` option of the `http` sink.
+							"""
+						required: false
+						warnings: []
+						type: object: {
+							examples: []
+							options: {}
+						}
+					}
+				}
+			}
+		}
+
+		secret: {
+			common: false
+			description: """
+				Backends, keyed by name, that secrets are retrieved from when the
+				configuration is loaded. A secret is referenced in any option as
+				`SECRET[<backend>.<key>]` in a string, and replaced with its value
+				once the configuration is parsed, so that credentials don't need to
+				be written in the configuration.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						vault: {
+							type: "exec"
+							command: ["/usr/local/bin/vault-secrets"]
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "A secret backend, named by its key."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								type: {
+									description: "The kind of backend."
+									required:    true
+									warnings: []
+									type: string: {
+										enum: {
+											exec: """
+												Runs `command`, writing `{"version": "1.0", "secrets": ["<key>", ...]}`
+												to its standard input. It must write
+												`{"<key>": {"value": "<value>", "error": null}, ...}` to its standard
+												output, with an error message instead of a value for the secrets it
+												can't retrieve.
+												"""
+											file:        "Reads `path`, a JSON object mapping keys to values."
+											environment: "Reads the environment variables named by the keys."
+										}
+										syntax: "literal"
+									}
+								}
+								command: {
+									common:      false
+									description: "The program to run and its arguments, for the `exec` backend."
+									required:    false
+									warnings: []
+									type: array: {
+										default: null
+										items: type: string: {
+											examples: ["/usr/local/bin/vault-secrets", "--role=vector"]
+											syntax: "literal"
+										}
+									}
+								}
+								timeout_secs: {
+									common:      false
+									description: "The time the command is given to answer before it's killed, for the `exec` backend."
+									required:    false
+									warnings: []
+									type: uint: {
+										default: 5
+										unit:    "seconds"
+									}
+								}
+								path: {
+									common:      false
+									description: "The file secrets are read from, for the `file` backend."
+									required:    false
+									warnings: []
+									type: string: {
+										default: null
+										examples: ["/run/secrets/vector.json"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		enrichment_tables: {
+			common: false
+			description: """
+				Tables of reference data, keyed by name, that the `lookup` transform
+				joins events against, such as a mapping of user IDs to teams. Tables are
+				read into memory when Vector starts, and read again on every reload,
+				so that their new rows are used without rebuilding the components
+				using them.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						teams: {
+							type: "file"
+							path: "/etc/vector/teams.csv"
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "A table, named by its key."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								type: {
+									description: "The kind of table."
+									required:    true
+									warnings: []
+									type: string: {
+										enum: {
+											file: "Rows are read from a file."
+										}
+										syntax: "literal"
+									}
+								}
+								path: {
+									description: "The file the rows of the table are read from."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["/etc/vector/teams.csv"]
+										syntax: "literal"
+									}
+								}
+								encoding: {
+									common:      false
+									description: "How the file is decoded."
+									required:    false
+									warnings: []
+									type: object: {
+										examples: []
+										options: {
+											type: {
+												common:      false
+												description: "The format of the file."
+												required:    false
+												warnings: []
+												type: string: {
+													default: "csv"
+													enum: {
+														csv: "Comma-separated values, with quoted values as in RFC 4180."
+													}
+													syntax: "literal"
+												}
+											}
+											delimiter: {
+												common:      false
+												description: "The character separating the values of a row."
+												required:    false
+												warnings: []
+												type: string: {
+													default: ","
+													examples: [",", ";", "\t"]
+													syntax: "literal"
+												}
+											}
+											include_headers: {
+												common:      false
+												description: "Whether the first line of the file names the columns. Otherwise columns are named by their position, starting at `0`."
+												required:    false
+												warnings: []
+												type: bool: default: true
+											}
+										}
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		metadata_keys: {
+			common:      false
+			description: "Controls the metadata keys that sources add to their events, such as `host` and `source_type`, so that they don't collide with fields of the same name. Sources add the key at `target`, or leave it out if the key isn't `enabled`, without touching the fields of the event at the key set by `log_schema`. Each source can override these options with its own `metadata_keys` option."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					host: {
+						common:      false
+						description: "How the `host` key is added to events."
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								enabled: {
+									common:      false
+									description: "Whether the key is added at all."
+									required:    false
+									type: bool: default: true
+								}
+								overwrite: {
+									common:      false
+									description: "Whether a value already at `target` is overwritten."
+									required:    false
+									type: bool: default: true
+								}
+								target: {
+									common:      false
+									description: "The path the key is added at, instead of the key set by `log_schema`."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["_vector.host"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+					source_type: {
+						common:      false
+						description: "How the `source_type` key is added to events."
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								enabled: {
+									common:      false
+									description: "Whether the key is added at all."
+									required:    false
+									type: bool: default: true
+								}
+								overwrite: {
+									common:      false
+									description: "Whether a value already at `target` is overwritten."
+									required:    false
+									type: bool: default: true
+								}
+								target: {
+									common:      false
+									description: "The path the key is added at, instead of the key set by `log_schema`."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["_vector.source_type"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		pipelines: {
+			common: false
+			description: """
+				Ordered lists of transforms, keyed by name, that are defined once and
+				chained together without wiring the inputs of every transform. Each
+				transform of a pipeline is named `<pipeline>.<name>`, which identifies
+				it in logs and metrics, and takes the previous one as its input. Other
+				components take the output of the pipeline by naming the pipeline in
+				their `inputs`.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					"*": {
+						description: "A pipeline, named by its key."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								inputs: {
+									description: "The names of the components the first transform of the pipeline takes events from."
+									required:    true
+									warnings: []
+									type: array: items: type: string: {
+										examples: ["my-source-or-transform-id", "prefix-*"]
+										syntax: "literal"
+									}
+								}
+								transforms: {
+									description: "The transforms of the pipeline, in order. Each takes a `name`, unique within the pipeline, and the options of a transform, including its `type`."
+									required:    true
+									warnings: []
+									type: array: items: type: object: {
+										examples: [
+											{
+												name:  "parse"
+												type:  "json_parser"
+												field: "message"
+											},
+										]
+										options: {}
+									}
+								}
+							}
+						}
+					}
 				}
 			}
 		}
@@ -103,6 +525,25 @@ configuration: {
 						```
 						"""
 				},
+				{
+					title: "Required values"
+					body: """
+						Variables that must be set can be marked via the `:?` syntax, failing to load the
+						configuration with the given message when they are not:
+
+						```toml
+						option = "${ENV_VAR:?the ENV_VAR variable must be set}"
+						```
+						"""
+				},
+				{
+					title: "Strict mode"
+					body: """
+						By default, a variable that isn't set and has no default value is replaced with an
+						empty string, and a warning is logged. Starting Vector with `--strict-env-vars`
+						fails loading the configuration instead.
+						"""
+				},
 				{
 					title: "Escaping"
 					body: """
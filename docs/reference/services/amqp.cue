@@ -0,0 +1,10 @@
+package metadata
+
+services: amqp: {
+	name:     "AMQP"
+	thing:    "an \(name) broker"
+	url:      urls.amqp
+	versions: "0-9-1"
+
+	description: "[AMQP](\(urls.amqp)) is an open standard protocol for passing messages between applications, through queues and exchanges held by a broker such as [RabbitMQ](\(urls.rabbitmq))."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: opentelemetry: {
+	name:     "OpenTelemetry"
+	thing:    "an \(name) collector or SDK"
+	url:      urls.opentelemetry
+	versions: null
+
+	description: "[OpenTelemetry](\(urls.opentelemetry)) is a collection of tools, APIs, and SDKs to instrument, generate, collect, and export telemetry data. It is a Cloud Native Computing Foundation project."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: redis: {
+	name:     "Redis"
+	thing:    "a \(name) server"
+	url:      urls.redis
+	versions: null
+
+	description: "[Redis](\(urls.redis)) is an in-memory data structure store, often used as a buffer between log shippers such as Logstash."
+}
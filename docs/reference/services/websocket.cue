@@ -0,0 +1,10 @@
+package metadata
+
+services: websocket: {
+	name:     "WebSocket"
+	thing:    "a \(name) server"
+	url:      urls.websocket
+	versions: null
+
+	description: "[WebSocket](\(urls.websocket)) is a protocol for full-duplex communication over a single TCP connection, commonly used to push live data to browsers."
+}
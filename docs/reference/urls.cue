@@ -4,6 +4,8 @@ urls: {
 	affine_type_system:                                       "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:                        "/blog/adaptive-request-concurrency/"
 	amazon_linux:                                             "https://aws.amazon.com/amazon-linux-ami/"
+	amqp:                                                     "https://www.amqp.org/"
+	amqp_uri:                                                 "https://www.rabbitmq.com/uri-spec.html"
 	ansi_escape_codes:                                        "\(wikipedia)/wiki/ANSI_escape_code"
 	apache:                                                   "https://httpd.apache.org"
 	apache_common:                                            "\(apache)/docs/1.3/logs.html#common"
@@ -12,8 +14,10 @@ urls: {
 	apache_extended_status:                                   "\(apache)/docs/current/mod/core.html#extendedstatus"
 	apache_install:                                           "\(apache)/docs/current/install.html"
 	apache_mod_status:                                        "http://httpd.apache.org/docs/current/mod/mod_status.html"
+	apollo_federation:                                        "https://www.apollographql.com/docs/federation/"
 	apt:                                                      "\(wikipedia)/wiki/APT_(software)"
 	arm:                                                      "\(wikipedia)/wiki/ARM_architecture"
+	avro_container_files:                                     "https://avro.apache.org/docs/current/spec.html#Object+Container+Files"
 	aws_access_keys:                                          "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
 	aws_arm_g2_announcement:                                  "https://aws.amazon.com/about-aws/whats-new/2019/12/announcing-new-amazon-ec2-m6g-c6g-and-r6g-instances-powered-by-next-generation-arm-based-aws-graviton2-processors/"
 	aws_athena:                                               "https://aws.amazon.com/athena/"
@@ -77,6 +81,7 @@ urls: {
 	aws_sqs:                                                  "https://aws.amazon.com/sqs/"
 	aws_sqs_api:                                              "\(aws_docs)/AWSSimpleQueueService/latest/APIReference/Welcome.html"
 	aws_sqs_create:                                           "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
+	aws_sqs_long_polling:                                     "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-short-and-long-polling.html"
 	aws_vpc_flow_logs:                                        "\(aws_docs)/vpc/latest/userguide/flow-logs.html"
 	azure_monitor:                                            "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:                             "https://docs.microsoft.com/en-us/rest/api/monitor/"
@@ -92,10 +97,15 @@ urls: {
 	chrono_time_formats:                                      "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	cgroups_limit_resources:                                  "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                               "https://clickhouse.yandex/"
+	clickhouse_async_insert:                                  "https://clickhouse.com/docs/en/operations/settings/settings/#async-insert"
+	clickhouse_error_codes:                                   "https://github.com/ClickHouse/ClickHouse/blob/master/src/Common/ErrorCodes.cpp"
+	clickhouse_formats:                                       "https://clickhouse.com/docs/en/interfaces/formats/"
 	clickhouse_http:                                          "https://clickhouse.yandex/docs/en/interfaces/http/"
+	cloudflare_logpush:                                       "https://developers.cloudflare.com/logs/get-started/enable-destinations/http"
 	cloudsmith:                                               "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                                           "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                                           "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
+	confluent_schema_registry:                                "https://docs.confluent.io/platform/current/schema-registry/index.html"
 	console:                                                  "\(wikipedia)/wiki/System_console"
 	conventional_commits:                                     "https://www.conventionalcommits.org"
 	contributing:                                             "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -155,6 +165,7 @@ urls: {
 	exit_codes:                                               "https://docs.rs/exitcode/latest/exitcode/#constants"
 	externally_tagged_representation:                         "https://serde.rs/enum-representations.html#externally-tagged"
 	fail_safe:                                                "https://en.wikipedia.org/wiki/Fail-safe"
+	fastly_https_logging:                                     "https://docs.fastly.com/en/guides/log-streaming-https"
 	ffi:                                                      "https://en.wikipedia.org/wiki/Foreign_function_interface"
 	file:                                                     "\(wikipedia)/wiki/Computer_file"
 	file_system:                                              "\(wikipedia)/wiki/File_system"
@@ -188,6 +199,7 @@ urls: {
 	grok:                                                     "https://grokdebug.herokuapp.com/"
 	grok_debugger:                                            "https://grokdebug.herokuapp.com/"
 	grok_patterns:                                            "\(github)/daschl/grok/tree/master/patterns"
+	grpc:                                                     "https://grpc.io/"
 	gzip:                                                     "https://www.gzip.org/"
 	haproxy:                                                  "https://www.haproxy.org/"
 	helm:                                                     "https://helm.sh/"
@@ -306,6 +318,8 @@ urls: {
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
 	openssl:                                                  "https://www.openssl.org/"
+	opentelemetry:                                            "https://opentelemetry.io/"
+	opentelemetry_protocol:                                   "https://opentelemetry.io/docs/specs/otlp/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
 	papertrail_syslog:                                        "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
@@ -320,6 +334,7 @@ urls: {
 	prometheus_high_cardinality:                              "https://prometheus.io/docs/practices/naming/#labels"
 	prometheus_histogram:                                     "https://prometheus.io/docs/concepts/metric_types/#histogram"
 	prometheus_histograms_guide:                              "https://prometheus.io/docs/practices/histograms/"
+	prometheus_metric_relabel_configs:                        "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#metric_relabel_configs"
 	prometheus_summary:                                       "https://prometheus.io/docs/concepts/metric_types/#summary"
 	prometheus_text_based_exposition_format:                  "\(github)/prometheus/docs/blob/master/content/docs/instrumenting/exposition_formats.md#text-based-format"
 	prometheus_metric_naming:                                 "https://prometheus.io/docs/practices/naming/#metric-names"
@@ -328,7 +343,9 @@ urls: {
 	protobuf:                                                 "https://developers.google.com/protocol-buffers"
 	pulsar:                                                   "https://pulsar.apache.org/"
 	pulsar_protocol:                                          "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	rabbitmq:                                                 "https://www.rabbitmq.com/"
 	raspbian:                                                 "https://www.raspbian.org/"
+	redis:                                                    "https://redis.io/"
 	rdkafka:                                                  "\(github)/edenhill/librdkafka"
 	regex:                                                    "\(wikipedia)/wiki/Regular_expression"
 	regex_grouping_and_flags:                                 "https://docs.rs/regex/latest/regex/#grouping-and-flags"
@@ -338,6 +355,7 @@ urls: {
 	rfc_2822:                                                 "https://tools.ietf.org/html/rfc2822#section-3.3"
 	rfc_3339:                                                 "https://tools.ietf.org/html/rfc3339"
 	rfc_4180:                                                 "https://tools.ietf.org/html/rfc4180"
+	rfc_6587:                                                 "https://tools.ietf.org/html/rfc6587#section-3.4.1"
 	rhel:                                                     "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rlua:                                                     "\(github)/kyren/rlua"
 	rpm:                                                      "https://rpm.org/"
@@ -369,6 +387,7 @@ urls: {
 	splunk:                                                   "https://www.splunk.com"
 	splunk_hec:                                               "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_event_endpoint:                                "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fevent"
+	splunk_hec_indexer_acknowledgements:                      "https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/AboutHECIDXAck"
 	splunk_hec_indexed_fields:                                "https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/IFXandHEC"
 	splunk_hec_protocol:                                      "https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/HECRESTendpoints"
 	splunk_hec_raw_endpoint:                                  "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fraw"
@@ -441,6 +460,7 @@ urls: {
 	vector_homebrew_source_files:                             "\(github)/timberio/homebrew-brew/blob/master/Formula/vector.rb"
 	vector_http_source:                                       "\(vector_website)/docs/reference/sources/http/"
 	vector_initd_service:                                     "\(vector_repo)/blob/master/distribution/init.d/vector"
+	vector_internal_metrics_source:                           "\(vector_website)/docs/reference/sources/internal_metrics/"
 	vector_installer:                                         "https://sh.vector.dev"
 	vector_issues:                                            "\(vector_repo)/issues"
 	vector_journald_source:                                   "\(vector_website)/docs/reference/sources/journald/"
@@ -505,6 +525,7 @@ urls: {
 	vote_feature:                                             "\(vector_repo)/issues?q=is%3Aissue+is%3Aopen+sort%3Areactions-%2B1-desc+label%3A%22Type%3A+New+Feature%22"
 	wasm:                                                     "https://webassembly.org/"
 	wasm_languages:                                           "\(github)/appcypher/awesome-wasm-langs"
+	websocket:                                                "https://tools.ietf.org/html/rfc6455"
 	wikipedia:                                                "https://en.wikipedia.org"
 	windows:                                                  "https://www.microsoft.com/en-us/windows"
 	windows_installer:                                        "\(wikipedia)/wiki/Windows_Installer"
@@ -46,6 +46,14 @@ pub struct CheckpointsView {
     checkpoints: DashMap<FileFingerprint, FilePosition>,
     modified_times: DashMap<FileFingerprint, DateTime<Utc>>,
     removed_times: DashMap<FileFingerprint, DateTime<Utc>>,
+    watched: DashMap<FileFingerprint, WatchedFile>,
+}
+
+/// A file currently being watched, and the position it has been read up to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchedFile {
+    pub path: PathBuf,
+    pub position: FilePosition,
 }
 
 impl CheckpointsView {
@@ -53,6 +61,52 @@ impl CheckpointsView {
         self.checkpoints.insert(fng, pos);
         self.modified_times.insert(fng, Utc::now());
         self.removed_times.remove(&fng);
+        if let Some(mut watched) = self.watched.get_mut(&fng) {
+            watched.position = pos;
+        }
+    }
+
+    /// Update the checkpoint of a file once the lines read up to `pos` are
+    /// acknowledged, which may be after the file stopped being watched.
+    pub fn acknowledge(&self, fng: FileFingerprint, pos: FilePosition) {
+        self.checkpoints.insert(fng, pos);
+        self.modified_times.insert(fng, Utc::now());
+        match self.watched.get_mut(&fng) {
+            Some(mut watched) => watched.position = pos,
+            // Let the checkpoints of removed files still expire.
+            None => {
+                self.removed_times.entry(fng).or_insert_with(Utc::now);
+            }
+        }
+    }
+
+    /// Track a newly watched file, which starts to be read from `pos`.
+    pub fn watch(&self, fng: FileFingerprint, path: PathBuf, pos: FilePosition) {
+        self.watched.insert(
+            fng,
+            WatchedFile {
+                path,
+                position: pos,
+            },
+        );
+    }
+
+    /// Track the new path of a watched file that has been renamed.
+    pub fn set_path(&self, fng: FileFingerprint, path: PathBuf) {
+        if let Some(mut watched) = self.watched.get_mut(&fng) {
+            watched.path = path;
+        }
+    }
+
+    /// The files currently being watched, sorted by path.
+    pub fn watched_files(&self) -> Vec<WatchedFile> {
+        let mut files = self
+            .watched
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
     }
 
     pub fn get(&self, fng: FileFingerprint) -> Option<FilePosition> {
@@ -61,6 +115,7 @@ impl CheckpointsView {
 
     pub fn set_dead(&self, fng: FileFingerprint) {
         self.removed_times.insert(fng, Utc::now());
+        self.watched.remove(&fng);
     }
 
     pub fn update_key(&self, old: FileFingerprint, new: FileFingerprint) {
@@ -75,6 +130,10 @@ impl CheckpointsView {
         if let Some((_, value)) = self.removed_times.remove(&old) {
             self.removed_times.insert(new, value);
         }
+
+        if let Some((_, value)) = self.watched.remove(&old) {
+            self.watched.insert(new, value);
+        }
     }
 
     pub fn contains_bytes_checksums(&self) -> bool {
@@ -164,6 +223,12 @@ impl CheckpointsView {
 
 impl Checkpointer {
     pub fn new(data_dir: &Path) -> Checkpointer {
+        Self::with_view(data_dir, Arc::default())
+    }
+
+    /// Create a checkpointer that keeps its checkpoints in `view`, which may be shared to
+    /// inspect them from elsewhere.
+    pub fn with_view(data_dir: &Path, view: Arc<CheckpointsView>) -> Checkpointer {
         let directory = data_dir.join("checkpoints");
         let glob_string = directory.join("*").to_string_lossy().into_owned();
         let tmp_file_path = data_dir.join(TMP_FILE_NAME);
@@ -174,7 +239,7 @@ impl Checkpointer {
             glob_string,
             tmp_file_path,
             stable_file_path,
-            checkpoints: Arc::new(CheckpointsView::default()),
+            checkpoints: view,
         }
     }
 
@@ -365,7 +430,8 @@ impl Checkpointer {
 #[cfg(test)]
 mod test {
     use super::{
-        Checkpoint, Checkpointer, FileFingerprint, FilePosition, STABLE_FILE_NAME, TMP_FILE_NAME,
+        Checkpoint, Checkpointer, CheckpointsView, FileFingerprint, FilePosition, WatchedFile,
+        STABLE_FILE_NAME, TMP_FILE_NAME,
     };
     use chrono::{Duration, Utc};
     use tempfile::tempdir;
@@ -620,4 +686,33 @@ mod test {
         assert_eq!(Some(1234), chkptr.get_checkpoint(new));
         assert_eq!(None, chkptr.get_checkpoint(old));
     }
+
+    #[test]
+    fn test_checkpoints_view_watched_files() {
+        let view = CheckpointsView::default();
+        let first = FileFingerprint::DevInode(1, 2);
+        let second = FileFingerprint::DevInode(1, 3);
+
+        view.watch(second, "/var/log/b.log".into(), 0);
+        view.watch(first, "/var/log/a.log".into(), 10);
+        view.update(second, 20);
+        view.set_path(first, "/var/log/a.log.1".into());
+
+        assert_eq!(
+            view.watched_files(),
+            vec![
+                WatchedFile {
+                    path: "/var/log/a.log.1".into(),
+                    position: 10,
+                },
+                WatchedFile {
+                    path: "/var/log/b.log".into(),
+                    position: 20,
+                },
+            ]
+        );
+
+        view.set_dead(first);
+        assert_eq!(view.watched_files().len(), 1);
+    }
 }
@@ -2,7 +2,7 @@ use crate::{
     checkpointer::{Checkpointer, CheckpointsView},
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
-    FileSourceInternalEvents, ReadFrom,
+    FilePosition, FileSourceInternalEvents, ReadFrom,
 };
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
@@ -50,6 +50,20 @@ where
     pub remove_after: Option<Duration>,
     pub emitter: E,
     pub handle: tokio::runtime::Handle,
+    pub checkpoints: Arc<CheckpointsView>,
+    /// Leaves the checkpoints alone when lines are read, for them to be
+    /// updated once the lines are acknowledged instead.
+    pub acknowledgements: bool,
+}
+
+/// A line read from a file.
+#[derive(Clone, Debug)]
+pub struct Line {
+    pub text: Bytes,
+    pub filename: String,
+    pub file_id: FileFingerprint,
+    /// The position in the file right after the line.
+    pub offset: FilePosition,
 }
 
 /// `FileServer` as Source
@@ -74,10 +88,10 @@ where
         self,
         mut chans: C,
         shutdown: S,
-    ) -> Result<Shutdown, <C as Sink<Vec<(Bytes, String)>>>::Error>
+    ) -> Result<Shutdown, <C as Sink<Vec<Line>>>::Error>
     where
-        C: Sink<Vec<(Bytes, String)>> + Unpin,
-        <C as Sink<Vec<(Bytes, String)>>>::Error: std::error::Error,
+        C: Sink<Vec<Line>> + Unpin,
+        <C as Sink<Vec<Line>>>::Error: std::error::Error,
         S: Future + Unpin + Send + 'static,
         <S as Future>::Output: Clone + Send + Sync,
     {
@@ -88,7 +102,8 @@ where
         let mut backoff_cap: usize = 1;
         let mut lines = Vec::new();
 
-        let mut checkpointer = Checkpointer::new(&self.data_dir);
+        let mut checkpointer =
+            Checkpointer::with_view(&self.data_dir, Arc::clone(&self.checkpoints));
         checkpointer.read_checkpoints(self.ignore_before);
 
         let mut known_small_files = HashSet::new();
@@ -225,6 +240,7 @@ where
                                         old_path = ?watcher.path
                                     );
                                     watcher.update_path(path).ok(); // ok if this fails: might fix next cycle
+                                    checkpoints.set_path(file_id, watcher.path.clone());
                                 } else {
                                     info!(
                                         message = "More than one file has the same fingerprint.",
@@ -243,6 +259,7 @@ where
                                                 old_modified_time = ?old_modified_time,
                                             );
                                             watcher.update_path(path).ok(); // ok if this fails: might fix next cycle
+                                            checkpoints.set_path(file_id, watcher.path.clone());
                                         }
                                     }
                                 }
@@ -282,10 +299,12 @@ where
 
                     bytes_read += sz;
 
-                    lines.push((
-                        line,
-                        watcher.path.to_str().expect("not a valid path").to_owned(),
-                    ));
+                    lines.push(Line {
+                        text: line,
+                        filename: watcher.path.to_str().expect("not a valid path").to_owned(),
+                        file_id,
+                        offset: watcher.get_file_position(),
+                    });
 
                     if bytes_read > self.max_read_bytes {
                         maxed_out_reading_single_file = true;
@@ -296,7 +315,9 @@ where
 
                 if bytes_read > 0 {
                     global_bytes_read = global_bytes_read.saturating_add(bytes_read);
-                    checkpoints.update(file_id, watcher.get_file_position());
+                    if !self.acknowledgements {
+                        checkpoints.update(file_id, watcher.get_file_position());
+                    }
                 } else {
                     // Should the file be removed
                     if let Some(grace_period) = self.remove_after {
@@ -427,6 +448,7 @@ where
                     self.emitter.emit_file_added(&path);
                 }
                 watcher.set_file_findable(true);
+                checkpoints.watch(file_id, path, watcher.get_file_position());
                 fp_map.insert(file_id, watcher);
             }
             Err(error) => self.emitter.emit_file_watch_failed(&path, error),
@@ -10,12 +10,19 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    token: Option<String>,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self { url, token: None }
+    }
+
+    /// Returns a client that sends `token` as a bearer token in the `Authorization` header
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
     }
 
     /// Issue a GraphQL query using Reqwest, serializing the response to the associated
@@ -24,10 +31,12 @@ impl Client {
         &self,
         request_body: &graphql_client::QueryBody<T::Variables>,
     ) -> QueryResult<T> {
-        let client = reqwest::Client::new();
+        let mut request = reqwest::Client::new().post(self.url.clone());
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
 
-        client
-            .post(self.url.clone())
+        request
             .json(request_body)
             .send()
             .await
@@ -8,14 +8,20 @@
 //! 4. Extension methods for each client, for executing queries/subscriptions, and returning
 //! deserialized JSON responses
 //!
+//! Both clients can authenticate with a bearer token, and the subscription client reconnects
+//! dropped connections with a backoff, restarting its subscriptions. This makes it suitable
+//! for external tooling that consumes Vector's subscriptions.
+//!
 
 #![deny(missing_debug_implementations, missing_copy_implementations)]
 
 mod client;
 /// GraphQL queries
 pub mod gql;
+mod options;
 mod subscription;
 pub mod test;
 
 pub use client::*;
+pub use options::*;
 pub use subscription::*;
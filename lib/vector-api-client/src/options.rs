@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+/// Options for connecting a `SubscriptionClient` to the API server
+#[derive(Debug, Clone)]
+pub struct ConnectOptions {
+    /// Token sent as a bearer token in the `Authorization` header of the WebSocket handshake
+    pub token: Option<String>,
+
+    /// Delay before the first attempt to reconnect a dropped connection. The delay doubles
+    /// on every failed attempt, up to `max_backoff`
+    pub initial_backoff: Duration,
+
+    /// Longest delay between attempts to reconnect
+    pub max_backoff: Duration,
+
+    /// Number of failed attempts to reconnect before giving up, which ends the streams of
+    /// all subscriptions. `None` keeps trying forever, and `Some(0)` disables reconnecting
+    pub max_reconnect_attempts: Option<usize>,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            token: None,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_reconnect_attempts: None,
+        }
+    }
+}
+
+impl ConnectOptions {
+    /// Returns options that send `token` to authenticate with the API server
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Returns the delays between attempts to reconnect, ending once attempts run out
+    pub(crate) fn backoff(&self) -> impl Iterator<Item = Duration> {
+        let max_backoff = self.max_backoff;
+        let delays = std::iter::successors(Some(self.initial_backoff), move |delay| {
+            Some((*delay * 2).min(max_backoff))
+        });
+
+        delays.take(self.max_reconnect_attempts.unwrap_or(usize::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let options = ConnectOptions {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            max_reconnect_attempts: Some(5),
+            ..ConnectOptions::default()
+        };
+
+        let delays = options.backoff().map(|d| d.as_secs()).collect::<Vec<_>>();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+    }
+
+    #[test]
+    fn backoff_can_be_disabled() {
+        let options = ConnectOptions {
+            max_reconnect_attempts: Some(0),
+            ..ConnectOptions::default()
+        };
+
+        assert_eq!(options.backoff().count(), 0);
+    }
+}
@@ -1,16 +1,27 @@
+use crate::ConnectOptions;
 use futures::SinkExt;
 use graphql_client::GraphQLQuery;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::{
+    collections::HashMap,
     pin::Pin,
     sync::{Arc, Mutex, Weak},
 };
 use tokio::{
     stream::{Stream, StreamExt},
-    sync::{broadcast, mpsc, oneshot},
+    sync::{broadcast, mpsc, mpsc::error::TryRecvError, oneshot},
+    time::delay_for,
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        client::IntoClientRequest,
+        handshake::client::Request,
+        http::header::{HeaderValue, AUTHORIZATION},
+        Error, Message,
+    },
 };
-use tokio_tungstenite::{connect_async, tungstenite::Message};
 use url::Url;
 use uuid::Uuid;
 use weak_table::WeakValueHashMap;
@@ -216,37 +227,200 @@ impl SubscriptionClient {
 
 /// Connect to a new WebSocket GraphQL server endpoint, and return a `SubscriptionClient`.
 /// This method will a) connect to a ws(s):// endpoint, and perform the initial handshake, and b)
-/// set up channel forwarding to expose just the returned `Payload`s to the client.
-pub async fn connect_subscription_client(
-    url: Url,
-) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
-    let (ws, _) = connect_async(url).await?;
-    let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
+/// set up channel forwarding to expose just the returned `Payload`s to the client. Dropped
+/// connections are reconnected with the default `ConnectOptions`.
+pub async fn connect_subscription_client(url: Url) -> Result<SubscriptionClient, Error> {
+    connect_subscription_client_with_options(url, ConnectOptions::default()).await
+}
 
-    let (send_tx, mut send_rx) = mpsc::unbounded_channel::<Payload>();
+/// Connect to a new WebSocket GraphQL server endpoint with the given `options`, and return a
+/// `SubscriptionClient`. Only the first connection's error is returned; once connected, a
+/// dropped connection is reconnected with a backoff, and the subscriptions that are still
+/// alive are restarted on the new connection.
+pub async fn connect_subscription_client_with_options(
+    url: Url,
+    options: ConnectOptions,
+) -> Result<SubscriptionClient, Error> {
+    let (send_tx, send_rx) = mpsc::unbounded_channel::<Payload>();
     let (recv_tx, recv_rx) = mpsc::unbounded_channel::<Payload>();
+    let (connected_tx, connected_rx) = oneshot::channel();
 
-    // Forwarded received messages back upstream to the GraphQL server
-    tokio::spawn(async move {
-        loop {
-            if let Some(p) = send_rx.next().await {
-                let _ = ws_tx
-                    .send(Message::Text(serde_json::to_string(&p).unwrap()))
-                    .await;
-            }
+    tokio::spawn(run_connection(url, options, send_rx, recv_tx, connected_tx));
+    connected_rx.await.unwrap_or(Err(Error::ConnectionClosed))?;
+
+    Ok(SubscriptionClient::new(send_tx, recv_rx))
+}
+
+/// Returns the WebSocket handshake request, authenticated by the token in `options`
+fn request(url: &Url, options: &ConnectOptions) -> Result<Request, Error> {
+    let mut request = url.as_str().into_client_request()?;
+    if let Some(token) = &options.token {
+        let value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|error| Error::HttpFormat(error.into()))?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+    Ok(request)
+}
+
+/// Keeps track of the subscriptions started by `payload`, so they can be restarted after
+/// reconnecting
+fn track(started: &mut HashMap<Uuid, Payload>, payload: &Payload) {
+    match payload.payload_type.as_str() {
+        "start" => {
+            started.insert(payload.id, payload.clone());
+        }
+        "stop" => {
+            started.remove(&payload.id);
         }
-    });
-
-    // Forward received messages to the receiver channel
-    tokio::spawn(async move {
-        loop {
-            if let Some(Ok(Message::Text(m))) = ws_rx.next().await {
-                if let Ok(p) = serde_json::from_str::<Payload>(&m) {
-                    let _ = recv_tx.send(p);
+        _ => {}
+    }
+}
+
+/// Keeps track of the subscriptions the server has ended with a `payload` it sent, so
+/// they aren't restarted after reconnecting. Returns whether `payload` belongs to a started
+/// subscription; payloads of unknown or already ended subscriptions are ignored.
+fn track_received(started: &mut HashMap<Uuid, Payload>, payload: &Payload) -> bool {
+    match payload.payload_type.as_str() {
+        "complete" | "error" => started.remove(&payload.id).is_some(),
+        _ => started.contains_key(&payload.id),
+    }
+}
+
+fn message(payload: &Payload) -> Message {
+    Message::Text(serde_json::to_string(payload).unwrap())
+}
+
+/// Owns the WebSocket connection, forwarding `Payload`s in both directions and reconnecting
+/// whenever the connection drops. Returns once the client is dropped, or attempts to
+/// reconnect run out.
+async fn run_connection(
+    url: Url,
+    options: ConnectOptions,
+    mut send_rx: mpsc::UnboundedReceiver<Payload>,
+    recv_tx: mpsc::UnboundedSender<Payload>,
+    connected_tx: oneshot::Sender<Result<(), Error>>,
+) {
+    let mut connected_tx = Some(connected_tx);
+    let mut started = HashMap::new();
+    let mut backoff = options.backoff();
+
+    loop {
+        let result = match request(&url, &options) {
+            Ok(request) => connect_async(request).await.map(|(ws, _)| ws),
+            Err(error) => Err(error),
+        };
+
+        match (result, connected_tx.take()) {
+            (Ok(ws), connected_tx) => {
+                if let Some(connected_tx) = connected_tx {
+                    let _ = connected_tx.send(Ok(()));
+                }
+                backoff = options.backoff();
+
+                let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
+
+                // Catch up on payloads sent while disconnected, and (re)start the
+                // subscriptions that are still alive
+                loop {
+                    match send_rx.try_recv() {
+                        Ok(p) => track(&mut started, &p),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Closed) => return,
+                    }
+                }
+                for p in started.values() {
+                    let _ = ws_tx.send(message(p)).await;
+                }
+
+                loop {
+                    tokio::select! {
+                        // Forward payloads upstream to the GraphQL server
+                        p = send_rx.next() => match p {
+                            Some(p) => {
+                                track(&mut started, &p);
+                                if ws_tx.send(message(&p)).await.is_err() {
+                                    break;
+                                }
+                            }
+                            // The client has been dropped
+                            None => return,
+                        },
+
+                        // Forward received messages to the receiver channel
+                        m = ws_rx.next() => match m {
+                            Some(Ok(Message::Text(m))) => {
+                                if let Ok(p) = serde_json::from_str::<Payload>(&m) {
+                                    if track_received(&mut started, &p) {
+                                        let _ = recv_tx.send(p);
+                                    }
+                                }
+                            }
+                            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                            Some(Ok(_)) => {}
+                        },
+                    }
                 }
             }
+            // The first connection's error is returned to the caller
+            (Err(error), Some(connected_tx)) => {
+                let _ = connected_tx.send(Err(error));
+                return;
+            }
+            (Err(_), None) => {}
         }
-    });
 
-    Ok(SubscriptionClient::new(send_tx, recv_rx))
+        match backoff.next() {
+            Some(delay) => delay_for(delay).await,
+            None => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(id: Uuid, payload_type: &str) -> Payload {
+        Payload {
+            id,
+            payload_type: payload_type.to_owned(),
+            payload: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn completed_subscriptions_are_not_restarted() {
+        let mut started = HashMap::new();
+        let (completed, running) = (Uuid::new_v4(), Uuid::new_v4());
+        track(&mut started, &payload(completed, "start"));
+        track(&mut started, &payload(running, "start"));
+
+        assert!(track_received(&mut started, &payload(completed, "data")));
+        assert!(track_received(
+            &mut started,
+            &payload(completed, "complete")
+        ));
+        assert!(track_received(&mut started, &payload(running, "data")));
+
+        assert_eq!(started.keys().collect::<Vec<_>>(), vec![&running]);
+    }
+
+    #[test]
+    fn ignores_payloads_of_unknown_subscriptions() {
+        let mut started = HashMap::new();
+        let stopped = Uuid::new_v4();
+        track(&mut started, &payload(stopped, "start"));
+        track(&mut started, &payload(stopped, "stop"));
+
+        assert!(!track_received(&mut started, &payload(stopped, "data")));
+        assert!(!track_received(
+            &mut started,
+            &payload(Uuid::new_v4(), "data")
+        ));
+        assert!(!track_received(
+            &mut started,
+            &payload(Uuid::new_v4(), "complete")
+        ));
+        assert!(started.is_empty());
+    }
 }
@@ -0,0 +1,226 @@
+//! Attributes allocated memory to the components that allocated it.
+//!
+//! Every allocation is extended by a trailing byte holding the id of the group
+//! that was active on the allocating thread, so that it's credited back to the
+//! same group when it's freed, even if that happens in another component. Each
+//! component's tasks are wrapped in `Tracked`, which activates the component's
+//! group whenever they're polled. Group 0 holds everything else.
+
+use crate::internal_events::ComponentAllocatedBytes;
+use lazy_static::lazy_static;
+use pin_project::pin_project;
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Groups are identified by a single byte, so this is also the maximum number
+/// of tracked components, after which allocations go to the untracked group.
+const MAX_GROUPS: usize = 256;
+
+const UNTRACKED: u8 = 0;
+
+struct Group {
+    allocated: AtomicU64,
+    deallocated: AtomicU64,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_GROUP: Group = Group {
+    allocated: AtomicU64::new(0),
+    deallocated: AtomicU64::new(0),
+};
+
+static GROUPS: [Group; MAX_GROUPS] = [EMPTY_GROUP; MAX_GROUPS];
+
+lazy_static! {
+    /// The `(component_kind, component_name)` of each group, indexed by id.
+    static ref GROUP_NAMES: Mutex<Vec<(&'static str, String)>> =
+        Mutex::new(vec![("", String::new())]);
+}
+
+thread_local! {
+    static CURRENT_GROUP: Cell<u8> = Cell::new(UNTRACKED);
+}
+
+fn current_group() -> u8 {
+    CURRENT_GROUP
+        .try_with(|group| group.get())
+        .unwrap_or(UNTRACKED)
+}
+
+#[cfg(feature = "jemallocator")]
+pub type Allocator = jemallocator::Jemalloc;
+#[cfg(feature = "jemallocator")]
+pub const ALLOCATOR: Allocator = jemallocator::Jemalloc;
+
+#[cfg(not(feature = "jemallocator"))]
+pub type Allocator = std::alloc::System;
+#[cfg(not(feature = "jemallocator"))]
+pub const ALLOCATOR: Allocator = std::alloc::System;
+
+/// A global allocator that counts the bytes allocated by each group.
+pub struct Tracking<A>(pub A);
+
+fn with_trailer(layout: Layout) -> Option<(Layout, usize)> {
+    layout.extend(Layout::new::<u8>()).ok()
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Tracking<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let (wrapped, offset) = match with_trailer(layout) {
+            Some(wrapped) => wrapped,
+            None => return std::ptr::null_mut(),
+        };
+
+        let ptr = self.0.alloc(wrapped);
+        if !ptr.is_null() {
+            let group = current_group();
+            ptr.add(offset).write(group);
+            GROUPS[group as usize]
+                .allocated
+                .fetch_add(wrapped.size() as u64, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let (wrapped, offset) = match with_trailer(layout) {
+            Some(wrapped) => wrapped,
+            None => return std::ptr::null_mut(),
+        };
+
+        let ptr = self.0.alloc_zeroed(wrapped);
+        if !ptr.is_null() {
+            let group = current_group();
+            ptr.add(offset).write(group);
+            GROUPS[group as usize]
+                .allocated
+                .fetch_add(wrapped.size() as u64, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // The layout was already extended successfully when this was allocated.
+        let (wrapped, offset) = with_trailer(layout).unwrap();
+
+        let group = ptr.add(offset).read();
+        GROUPS[group as usize]
+            .deallocated
+            .fetch_add(wrapped.size() as u64, Ordering::Relaxed);
+        self.0.dealloc(ptr, wrapped);
+    }
+}
+
+/// Returns the id of the group for a component, registering it if it's new.
+pub fn register(component_kind: &'static str, component_name: &str) -> u8 {
+    let mut names = GROUP_NAMES.lock().unwrap();
+    if let Some(id) = names
+        .iter()
+        .position(|(kind, name)| *kind == component_kind && name == component_name)
+    {
+        return id as u8;
+    }
+
+    if names.len() < MAX_GROUPS {
+        names.push((component_kind, component_name.to_owned()));
+        (names.len() - 1) as u8
+    } else {
+        warn!(
+            message = "Too many components to track their allocations; counting them as untracked.",
+            %component_name,
+        );
+        UNTRACKED
+    }
+}
+
+/// Bytes currently allocated by the group `id`.
+fn allocated_bytes(id: usize) -> u64 {
+    let group = &GROUPS[id];
+    let deallocated = group.deallocated.load(Ordering::Relaxed);
+    let allocated = group.allocated.load(Ordering::Relaxed);
+    allocated.saturating_sub(deallocated)
+}
+
+/// Periodically reports the allocated bytes of every component.
+pub async fn report() {
+    let mut interval = tokio::time::interval(Duration::from_secs(2));
+    loop {
+        interval.tick().await;
+
+        let names = GROUP_NAMES.lock().unwrap().clone();
+        for (id, (component_kind, component_name)) in names.into_iter().enumerate().skip(1) {
+            emit!(ComponentAllocatedBytes {
+                component_kind,
+                component_name,
+                bytes: allocated_bytes(id),
+            });
+        }
+    }
+}
+
+/// Activates the allocation group `group` whenever the inner future is polled.
+#[pin_project]
+pub struct Tracked<F> {
+    #[pin]
+    inner: F,
+    group: u8,
+}
+
+impl<F> Tracked<F> {
+    pub fn new(inner: F, group: u8) -> Self {
+        Self { inner, group }
+    }
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let previous = CURRENT_GROUP.with(|group| group.replace(*this.group));
+        let result = this.inner.poll(cx);
+        CURRENT_GROUP.with(|group| group.set(previous));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_reuses_groups() {
+        let a = register("sink", "allocations_test_a");
+        let b = register("source", "allocations_test_a");
+        assert_ne!(a, b);
+        assert_eq!(register("sink", "allocations_test_a"), a);
+    }
+
+    #[test]
+    fn frees_are_credited_to_the_allocating_group() {
+        let tracking = Tracking(std::alloc::System);
+        let layout = Layout::new::<[u64; 4]>();
+        let group = register("transform", "allocations_test_b");
+
+        let ptr = CURRENT_GROUP.with(|current| {
+            let previous = current.replace(group);
+            let ptr = unsafe { tracking.alloc(layout) };
+            current.set(previous);
+            ptr
+        });
+        assert!(allocated_bytes(group as usize) >= layout.size() as u64);
+
+        unsafe { tracking.dealloc(ptr, layout) };
+        assert_eq!(allocated_bytes(group as usize), 0);
+    }
+}
@@ -0,0 +1,242 @@
+//! Bounded delivery for subscription streams.
+//!
+//! The results sent to a websocket client are driven by their own task into a buffer
+//! holding at most `api.subscription_buffer_size` results of each subscription, which the
+//! client reads from. When a client is too slow to keep up, results are dropped according
+//! to `api.subscription_drop_policy` rather than piling up in memory, and the number of
+//! dropped results is noted on the next result of the subscription that reaches the client.
+//! Messages that aren't the result of a subscription are never dropped.
+
+use crate::{config, config::api::DropPolicy, internal_events::ApiSubscriptionLagged};
+use async_stream::stream;
+use futures::future::{AbortHandle, Abortable};
+use lazy_static::lazy_static;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, RwLock},
+};
+use tokio::{
+    stream::{Stream, StreamExt},
+    sync::Notify,
+};
+
+#[derive(Debug, Copy, Clone)]
+struct Settings {
+    buffer_size: usize,
+    drop_policy: DropPolicy,
+}
+
+lazy_static! {
+    static ref SETTINGS: RwLock<Settings> = RwLock::new(Settings {
+        buffer_size: config::api::default_subscription_buffer_size(),
+        drop_policy: DropPolicy::default(),
+    });
+}
+
+/// Updates the buffer size and drop policy used by connections opened from now on.
+pub fn update_config(config: &config::Config) {
+    *SETTINGS.write().unwrap() = Settings {
+        buffer_size: config.api.subscription_buffer_size.max(1),
+        drop_policy: config.api.subscription_drop_policy,
+    };
+}
+
+/// An item of a bounded stream.
+pub trait Item {
+    /// The subscription the item is a result of. Only results can be dropped.
+    fn subscription(&self) -> Option<&str>;
+
+    /// Notes on a result that `dropped` results of its subscription were dropped before it.
+    fn set_dropped(&mut self, dropped: usize);
+}
+
+struct Buffer<T> {
+    items: VecDeque<T>,
+    /// The number of buffered results of each subscription.
+    buffered: HashMap<String, usize>,
+    /// The number of results of each subscription dropped since its last delivered result.
+    dropped: HashMap<String, usize>,
+    done: bool,
+}
+
+impl<T: Item> Buffer<T> {
+    fn push(&mut self, item: T, settings: Settings) {
+        let subscription = match item.subscription() {
+            Some(subscription) => subscription.to_owned(),
+            None => {
+                self.items.push_back(item);
+                return;
+            }
+        };
+
+        let buffered = self.buffered.entry(subscription.clone()).or_insert(0);
+        if *buffered < settings.buffer_size {
+            *buffered += 1;
+            self.items.push_back(item);
+            return;
+        }
+
+        *self.dropped.entry(subscription.clone()).or_insert(0) += 1;
+        match settings.drop_policy {
+            DropPolicy::DropOldest => {
+                let oldest = self
+                    .items
+                    .iter()
+                    .position(|buffered| buffered.subscription() == Some(subscription.as_str()));
+                if let Some(oldest) = oldest {
+                    self.items.remove(oldest);
+                }
+                self.items.push_back(item);
+            }
+            DropPolicy::DropNewest => {}
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let mut item = self.items.pop_front()?;
+        if let Some(subscription) = item.subscription() {
+            if let Some(buffered) = self.buffered.get_mut(subscription) {
+                *buffered -= 1;
+                if *buffered == 0 {
+                    self.buffered.remove(subscription);
+                }
+            }
+            if let Some(dropped) = self.dropped.remove(subscription) {
+                emit!(ApiSubscriptionLagged { dropped });
+                item.set_dropped(dropped);
+            }
+        }
+        Some(item)
+    }
+}
+
+struct Shared<T> {
+    buffer: Mutex<Buffer<T>>,
+    notify: Notify,
+}
+
+/// Stops driving the inner stream once the client's stream is dropped.
+struct AbortOnDrop(AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Drives `stream` in the background, buffering at most `api.subscription_buffer_size`
+/// results of each subscription for the returned stream.
+pub fn bounded<S>(stream: S) -> impl Stream<Item = S::Item>
+where
+    S: Stream + Send + 'static,
+    S::Item: Item + Send + 'static,
+{
+    let settings = *SETTINGS.read().unwrap();
+    let shared = Arc::new(Shared {
+        buffer: Mutex::new(Buffer {
+            items: VecDeque::new(),
+            buffered: HashMap::new(),
+            dropped: HashMap::new(),
+            done: false,
+        }),
+        notify: Notify::new(),
+    });
+
+    let producer = Arc::clone(&shared);
+    let (abort_handle, abort_registration) = AbortHandle::new_pair();
+    tokio::spawn(Abortable::new(
+        async move {
+            tokio::pin!(stream);
+            while let Some(item) = stream.next().await {
+                producer.buffer.lock().unwrap().push(item, settings);
+                producer.notify.notify();
+            }
+            producer.buffer.lock().unwrap().done = true;
+            producer.notify.notify();
+        },
+        abort_registration,
+    ));
+
+    stream! {
+        let _abort = AbortOnDrop(abort_handle);
+        loop {
+            let (item, done) = {
+                let mut buffer = shared.buffer.lock().unwrap();
+                (buffer.pop(), buffer.done)
+            };
+
+            match item {
+                Some(item) => yield item,
+                None if done => break,
+                None => shared.notify.notified().await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+    use tokio::time::{delay_for, Duration};
+
+    #[derive(Debug, PartialEq)]
+    struct TestItem {
+        subscription: Option<&'static str>,
+        value: usize,
+        dropped: usize,
+    }
+
+    impl Item for TestItem {
+        fn subscription(&self) -> Option<&str> {
+            self.subscription
+        }
+
+        fn set_dropped(&mut self, dropped: usize) {
+            self.dropped = dropped;
+        }
+    }
+
+    fn item(subscription: Option<&'static str>, value: usize) -> TestItem {
+        TestItem {
+            subscription,
+            value,
+            dropped: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn drops_oldest_results_of_slow_clients() {
+        let items = stream::iter(0..1000).map(|value| item(Some("1"), value));
+        let bounded = bounded(items);
+        tokio::pin!(bounded);
+
+        // Let the producer run ahead of the client.
+        delay_for(Duration::from_millis(50)).await;
+
+        let received = bounded.collect::<Vec<_>>().await;
+        let buffer_size = config::api::default_subscription_buffer_size();
+        assert_eq!(
+            received.iter().map(|item| item.value).collect::<Vec<_>>(),
+            (1000 - buffer_size..1000).collect::<Vec<_>>()
+        );
+        assert_eq!(received[0].dropped, 1000 - buffer_size);
+        assert!(received[1..].iter().all(|item| item.dropped == 0));
+    }
+
+    #[tokio::test]
+    async fn keeps_messages_that_arent_results() {
+        let buffer_size = config::api::default_subscription_buffer_size();
+        let items = stream::iter(0..buffer_size * 2)
+            .map(|value| item(Some("1"), value))
+            .chain(stream::iter(vec![item(None, 0)]));
+        let bounded = bounded(items);
+        tokio::pin!(bounded);
+
+        delay_for(Duration::from_millis(50)).await;
+
+        let received = bounded.collect::<Vec<_>>().await;
+        assert_eq!(received.len(), buffer_size + 1);
+        assert_eq!(received.last(), Some(&item(None, 0)));
+    }
+}
@@ -1,8 +1,30 @@
+use crate::{
+    event::Event,
+    metrics::{capture_metrics, get_controller},
+    sinks::prometheus::encode_text,
+};
 use serde_json::json;
-use warp::reply::json;
+use warp::reply::{json, with_header};
 use warp::{Rejection, Reply};
 
 // Health handler, responds with { ok: true }
 pub async fn health() -> Result<impl Reply, Rejection> {
     Ok(json(&json!({"ok": true})))
 }
+
+// Metrics handler, responds with the internal metrics in the Prometheus text format
+pub async fn metrics() -> Result<impl Reply, Rejection> {
+    let controller = get_controller().expect("Metrics system not initialized. Please report.");
+    let metrics = capture_metrics(controller)
+        .filter_map(|event| match event {
+            Event::Metric(metric) => Some(metric),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(with_header(
+        encode_text(&metrics, Some("vector")),
+        "content-type",
+        "text/plain; version=0.0.4",
+    ))
+}
@@ -1,6 +1,8 @@
+mod bounded;
 mod handler;
 mod schema;
 mod server;
+mod websocket;
 
-pub use schema::build_schema;
+pub use schema::{build_schema, reload::ReloadOutcome};
 pub use server::Server;
@@ -0,0 +1,61 @@
+use async_graphql::{Object, SimpleObject};
+
+/// A file watched by a file source, and how far it has been read
+#[derive(SimpleObject)]
+pub struct FileSourceCheckpoint {
+    /// Path of the file
+    path: String,
+
+    /// Offset the file has been read up to, in bytes
+    offset: i64,
+
+    /// Size of the file, in bytes. Null if the file can't be inspected
+    size: Option<i64>,
+
+    /// Number of bytes of the file yet to be read. Null if the file can't be inspected
+    lag: Option<i64>,
+}
+
+#[cfg(feature = "sources-file")]
+impl FileSourceCheckpoint {
+    async fn new(file: file_source::WatchedFile) -> Self {
+        let size = tokio::fs::metadata(&file.path)
+            .await
+            .ok()
+            .map(|metadata| metadata.len());
+
+        Self {
+            path: file.path.to_string_lossy().into_owned(),
+            offset: file.position as i64,
+            size: size.map(|size| size as i64),
+            lag: size.map(|size| size.saturating_sub(file.position) as i64),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CheckpointsQuery;
+
+#[Object]
+impl CheckpointsQuery {
+    /// Files watched by the running file source `componentName`, sorted by path
+    async fn file_source_checkpoints(
+        &self,
+        component_name: String,
+    ) -> async_graphql::Result<Vec<FileSourceCheckpoint>> {
+        #[cfg(feature = "sources-file")]
+        {
+            let checkpoints = crate::sources::file::checkpoints(&component_name)
+                .ok_or_else(|| format!("File source \"{}\" isn't running", component_name))?;
+
+            let mut files = Vec::new();
+            for file in checkpoints.watched_files() {
+                files.push(FileSourceCheckpoint::new(file).await);
+            }
+            Ok(files)
+        }
+
+        #[cfg(not(feature = "sources-file"))]
+        Err(format!("File source \"{}\" isn't running", component_name).into())
+    }
+}
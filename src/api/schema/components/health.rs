@@ -0,0 +1,69 @@
+use crate::topology::health::{self, HealthStatus};
+use async_graphql::{Enum, Object};
+use chrono::{DateTime, Utc};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ComponentHealthStatus {
+    /// No healthcheck has completed yet, or the component doesn't have one
+    Unknown,
+    Healthy,
+    Unhealthy,
+    /// Healthchecks are disabled for the component
+    Disabled,
+}
+
+impl From<HealthStatus> for ComponentHealthStatus {
+    fn from(status: HealthStatus) -> Self {
+        match status {
+            HealthStatus::Unknown => ComponentHealthStatus::Unknown,
+            HealthStatus::Healthy => ComponentHealthStatus::Healthy,
+            HealthStatus::Unhealthy => ComponentHealthStatus::Unhealthy,
+            HealthStatus::Disabled => ComponentHealthStatus::Disabled,
+        }
+    }
+}
+
+pub struct ComponentHealth {
+    name: String,
+    health: health::ComponentHealth,
+}
+
+impl ComponentHealth {
+    pub fn new(name: String) -> Self {
+        let health = health::get(&name);
+        Self { name, health }
+    }
+}
+
+#[Object]
+impl ComponentHealth {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Result of the most recent healthcheck
+    async fn status(&self) -> ComponentHealthStatus {
+        self.health.status.into()
+    }
+
+    /// Error of the most recent failed healthcheck
+    async fn last_error(&self) -> Option<&str> {
+        self.health.last_error.as_deref()
+    }
+
+    /// When the most recent healthcheck completed
+    async fn last_checked(&self) -> Option<DateTime<Utc>> {
+        self.health.last_checked
+    }
+
+    /// Smoothed ratio of time spent processing rather than waiting for input
+    async fn utilization(&self) -> Option<f64> {
+        self.health.utilization
+    }
+
+    /// Whether the component is busy enough to be holding up upstream components
+    async fn is_backpressured(&self) -> bool {
+        self.health.is_backpressured()
+    }
+}
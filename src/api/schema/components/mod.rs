@@ -1,3 +1,4 @@
+pub mod health;
 pub mod sink;
 pub mod source;
 pub mod state;
@@ -12,13 +13,16 @@ use crate::{
     config::Config,
     filter_check,
 };
-use async_graphql::{Enum, InputObject, Interface, Object, Subscription};
+use async_graphql::{validators::IntRange, Enum, InputObject, Interface, Object, Subscription};
 use lazy_static::lazy_static;
 use std::{
     cmp,
     collections::{HashMap, HashSet},
 };
-use tokio::stream::{Stream, StreamExt};
+use tokio::{
+    stream::{Stream, StreamExt},
+    time::Duration,
+};
 
 #[derive(Debug, Clone, Interface)]
 #[graphql(
@@ -207,6 +211,36 @@ impl ComponentsQuery {
     async fn component_by_name(&self, name: String) -> Option<Component> {
         component_by_name(&name)
     }
+
+    /// Resolves a configured source by name, as an entity of a federated graph
+    #[graphql(entity)]
+    async fn find_source_by_name(&self, name: String) -> async_graphql::Result<source::Source> {
+        match component_by_name(&name) {
+            Some(Component::Source(source)) => Ok(source),
+            _ => Err(format!("Source \"{}\" isn't configured", name).into()),
+        }
+    }
+
+    /// Resolves a configured transform by name, as an entity of a federated graph
+    #[graphql(entity)]
+    async fn find_transform_by_name(
+        &self,
+        name: String,
+    ) -> async_graphql::Result<transform::Transform> {
+        match component_by_name(&name) {
+            Some(Component::Transform(transform)) => Ok(transform),
+            _ => Err(format!("Transform \"{}\" isn't configured", name).into()),
+        }
+    }
+
+    /// Resolves a configured sink by name, as an entity of a federated graph
+    #[graphql(entity)]
+    async fn find_sink_by_name(&self, name: String) -> async_graphql::Result<sink::Sink> {
+        match component_by_name(&name) {
+            Some(Component::Sink(sink)) => Ok(sink),
+            _ => Err(format!("Sink \"{}\" isn't configured", name).into()),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -248,6 +282,19 @@ impl ComponentsSubscription {
                 _ => None,
             })
     }
+
+    /// Health of all components, sampled every `interval` milliseconds
+    async fn component_health(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+    ) -> impl Stream<Item = Vec<health::ComponentHealth>> {
+        tokio::time::interval(Duration::from_millis(interval as u64)).map(|_| {
+            state::get_component_names()
+                .into_iter()
+                .map(health::ComponentHealth::new)
+                .collect()
+        })
+    }
 }
 
 /// Update the 'global' configuration that will be consumed by component queries
@@ -260,8 +307,8 @@ pub fn update_config(config: &Config) {
             name.to_owned(),
             Component::Source(source::Source(source::Data {
                 name: name.to_owned(),
-                component_type: source.source_type().to_string(),
-                output_type: source.output_type(),
+                component_type: source.inner.source_type().to_string(),
+                output_type: source.inner.output_type(),
             })),
         );
     }
@@ -1,4 +1,4 @@
-use super::{source, state, transform, Component};
+use super::{health, source, state, transform, Component};
 use crate::{
     api::schema::{
         filter,
@@ -107,6 +107,39 @@ impl Sink {
             .collect()
     }
 
+    /// Sink health
+    pub async fn health(&self) -> health::ComponentHealth {
+        health::ComponentHealth::new(self.get_name().to_string())
+    }
+
+    /// Bytes currently held in the sink's buffer. Only reported by disk buffers
+    pub async fn buffer_byte_size(&self) -> Option<i64> {
+        metrics::gauge_value(
+            &metrics::by_component_name(self.get_name()),
+            "buffer_byte_size",
+        )
+        .map(|v| v as i64)
+    }
+
+    /// Events currently held in the sink's buffer
+    pub async fn buffer_event_count(&self) -> Option<i64> {
+        metrics::gauge_value(
+            &metrics::by_component_name(self.get_name()),
+            "buffer_events",
+        )
+        .map(|v| v as i64)
+    }
+
+    /// Maximum size of the sink's buffer; in events for memory buffers, and in bytes for disk
+    /// buffers
+    pub async fn buffer_max_size(&self) -> Option<i64> {
+        metrics::gauge_value(
+            &metrics::by_component_name(self.get_name()),
+            "buffer_max_size",
+        )
+        .map(|v| v as i64)
+    }
+
     /// Sink metrics
     pub async fn metrics(&self) -> metrics::SinkMetrics {
         metrics::by_component_name(self.get_name()).into_sink_metrics(self.get_component_type())
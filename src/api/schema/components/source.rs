@@ -1,4 +1,4 @@
-use super::{sink, state, transform, Component};
+use super::{health, sink, state, transform, Component};
 use crate::{
     api::schema::{
         filter,
@@ -105,6 +105,11 @@ impl Source {
         })
     }
 
+    /// Source health
+    pub async fn health(&self) -> health::ComponentHealth {
+        health::ComponentHealth::new(self.get_name().to_string())
+    }
+
     /// Source metrics
     pub async fn metrics(&self) -> metrics::SourceMetrics {
         metrics::by_component_name(&self.get_name()).into_source_metrics(&self.get_component_type())
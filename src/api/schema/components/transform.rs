@@ -1,4 +1,4 @@
-use super::{sink, source, state, Component};
+use super::{health, sink, source, state, Component};
 use crate::{
     api::schema::{
         filter,
@@ -86,6 +86,11 @@ impl Transform {
         })
     }
 
+    /// Transform health
+    pub async fn health(&self) -> health::ComponentHealth {
+        health::ComponentHealth::new(self.get_name().to_string())
+    }
+
     /// Transform metrics
     pub async fn metrics(&self) -> metrics::TransformMetrics {
         metrics::by_component_name(&self.0.name).into_transform_metrics(&self.get_component_type())
@@ -1,4 +1,20 @@
-use async_graphql::Object;
+use crate::config::{self, Config};
+use async_graphql::{Object, SimpleObject};
+use lazy_static::lazy_static;
+use std::sync::RwLock;
+
+lazy_static! {
+    static ref API_OPTIONS: RwLock<config::api::Options> = RwLock::new(Default::default());
+}
+
+/// API features enabled in the configuration
+#[derive(SimpleObject)]
+pub struct ApiFeatures {
+    /// Whether the GraphQL playground is served
+    playground: bool,
+    /// Whether components can be profiled
+    profiling: bool,
+}
 
 #[derive(Default)]
 pub struct Meta;
@@ -10,6 +26,55 @@ impl Meta {
         crate::get_version()
     }
 
+    /// Vector version number, without build details
+    async fn version(&self) -> String {
+        crate::vector_version().to_string()
+    }
+
+    /// Commit Vector was built from, if known
+    async fn git_sha(&self) -> Option<&'static str> {
+        crate::get_commit_hash()
+    }
+
+    /// Version of rustc Vector was built with
+    async fn rustc_version(&self) -> &'static str {
+        crate::built_info::RUSTC_VERSION
+    }
+
+    /// Target triple Vector was built for
+    async fn target(&self) -> &'static str {
+        crate::built_info::TARGET
+    }
+
+    /// CPU architecture Vector was built for
+    async fn arch(&self) -> &'static str {
+        crate::built_info::CFG_TARGET_ARCH
+    }
+
+    /// Paths of the loaded config files
+    async fn config_paths(&self) -> Vec<String> {
+        config::CONFIG_PATHS
+            .lock()
+            .map(|paths| {
+                paths
+                    .iter()
+                    .map(|(path, _)| path.to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// API features enabled in the configuration
+    async fn api_features(&self) -> ApiFeatures {
+        let options = *API_OPTIONS
+            .read()
+            .expect("Couldn't read API options. Please report.");
+        ApiFeatures {
+            playground: options.playground,
+            profiling: options.profiling,
+        }
+    }
+
     /// Hostname
     async fn hostname(&self) -> Option<String> {
         crate::get_hostname().ok()
@@ -25,3 +90,10 @@ impl MetaQuery {
         Meta
     }
 }
+
+/// Update the API options reported by the `meta` query
+pub fn update_config(config: &Config) {
+    *API_OPTIONS
+        .write()
+        .expect("Couldn't update API options. Please report.") = config.api;
+}
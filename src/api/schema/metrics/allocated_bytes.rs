@@ -0,0 +1,42 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+
+pub struct ComponentAllocatedBytes {
+    name: String,
+    metric: Metric,
+}
+
+impl ComponentAllocatedBytes {
+    /// Returns a new `ComponentAllocatedBytes` struct, which is a GraphQL type. The
+    /// component name is hoisted for clear field resolution in the resulting payload
+    pub fn new(metric: Metric) -> Self {
+        let name = metric.tag_value("component_name").expect(
+            "Returned a metric without a `component_name`, which shouldn't happen. Please report.",
+        );
+
+        Self { name, metric }
+    }
+}
+
+#[Object]
+impl ComponentAllocatedBytes {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Metric timestamp
+    async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.metric.data.timestamp
+    }
+
+    /// Bytes currently allocated by the component. Requires Vector to be built with the
+    /// `allocation-tracing` feature
+    async fn allocated_bytes(&self) -> f64 {
+        match self.metric.data.value {
+            MetricValue::Gauge { value } => value,
+            _ => 0.00,
+        }
+    }
+}
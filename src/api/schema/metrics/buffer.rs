@@ -0,0 +1,48 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+
+/// Returns the value of the gauge `name` among a component's metrics.
+pub fn gauge_value(metrics: &[Metric], name: &str) -> Option<f64> {
+    metrics
+        .iter()
+        .find(|m| m.name() == name)
+        .and_then(|m| match m.data.value {
+            MetricValue::Gauge { value } => Some(value),
+            _ => None,
+        })
+}
+
+pub struct ComponentBufferUsage {
+    name: String,
+    metrics: Vec<Metric>,
+}
+
+impl ComponentBufferUsage {
+    /// Returns a new `ComponentBufferUsage` from the `buffer_*` gauges of the sink `name`
+    pub fn new(name: String, metrics: Vec<Metric>) -> Self {
+        Self { name, metrics }
+    }
+}
+
+#[Object]
+impl ComponentBufferUsage {
+    /// Sink name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Bytes currently held in the buffer. Only reported by disk buffers
+    async fn byte_size(&self) -> Option<i64> {
+        gauge_value(&self.metrics, "buffer_byte_size").map(|v| v as i64)
+    }
+
+    /// Events currently held in the buffer
+    async fn event_count(&self) -> Option<i64> {
+        gauge_value(&self.metrics, "buffer_events").map(|v| v as i64)
+    }
+
+    /// Maximum size of the buffer; in events for memory buffers, and in bytes for disk buffers
+    async fn max_size(&self) -> Option<i64> {
+        gauge_value(&self.metrics, "buffer_max_size").map(|v| v as i64)
+    }
+}
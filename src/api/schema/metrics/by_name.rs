@@ -0,0 +1,161 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::{Enum, Object, SimpleObject};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+/// A metric name pattern, matched as a glob such as `*_errors_total`, or as a regular
+/// expression.
+pub enum MetricNamePattern {
+    Glob(glob::Pattern),
+    Regex(Regex),
+}
+
+impl MetricNamePattern {
+    pub fn new(pattern: &str, regex: bool) -> async_graphql::Result<Self> {
+        let result = if regex {
+            Regex::new(pattern)
+                .map(Self::Regex)
+                .map_err(|error| error.to_string())
+        } else {
+            glob::Pattern::new(pattern)
+                .map(Self::Glob)
+                .map_err(|error| error.to_string())
+        };
+
+        result.map_err(|error| format!("Invalid pattern \"{}\": {}", pattern, error).into())
+    }
+
+    pub fn matches(&self, m: &Metric) -> bool {
+        match self {
+            Self::Glob(pattern) => pattern.matches(m.name()),
+            Self::Regex(regex) => regex.is_match(m.name()),
+        }
+    }
+}
+
+#[derive(Enum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MetricValueType {
+    Counter,
+    Gauge,
+    Set,
+    Distribution,
+    AggregatedHistogram,
+    AggregatedSummary,
+}
+
+#[derive(SimpleObject)]
+pub struct MetricTag {
+    /// Tag key
+    key: String,
+
+    /// Tag value
+    value: String,
+}
+
+/// An internal metric matched by name, regardless of the metric's type
+pub struct NamedMetric(Metric);
+
+impl NamedMetric {
+    pub fn new(metric: Metric) -> Self {
+        Self(metric)
+    }
+}
+
+#[Object]
+impl NamedMetric {
+    /// Metric name
+    async fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    /// Metric namespace
+    async fn namespace(&self) -> Option<&str> {
+        self.0.namespace()
+    }
+
+    /// Metric timestamp
+    async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.data.timestamp
+    }
+
+    /// Metric tags, sorted by key
+    async fn tags(&self) -> Vec<MetricTag> {
+        self.0
+            .tags()
+            .map(|tags| {
+                tags.iter()
+                    .map(|(key, value)| MetricTag {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Metric type
+    async fn value_type(&self) -> MetricValueType {
+        match self.0.data.value {
+            MetricValue::Counter { .. } => MetricValueType::Counter,
+            MetricValue::Gauge { .. } => MetricValueType::Gauge,
+            MetricValue::Set { .. } => MetricValueType::Set,
+            MetricValue::Distribution { .. } => MetricValueType::Distribution,
+            MetricValue::AggregatedHistogram { .. } => MetricValueType::AggregatedHistogram,
+            MetricValue::AggregatedSummary { .. } => MetricValueType::AggregatedSummary,
+        }
+    }
+
+    /// Value of a counter or gauge. Null for other types of metrics
+    async fn value(&self) -> Option<f64> {
+        match self.0.data.value {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Number of values of a set, samples of a distribution, or observations of a histogram
+    /// or summary. Null for counters and gauges
+    async fn count(&self) -> Option<i64> {
+        match &self.0.data.value {
+            MetricValue::Counter { .. } | MetricValue::Gauge { .. } => None,
+            MetricValue::Set { values } => Some(values.len() as i64),
+            MetricValue::Distribution { samples, .. } => Some(samples.len() as i64),
+            MetricValue::AggregatedHistogram { count, .. }
+            | MetricValue::AggregatedSummary { count, .. } => Some(*count as i64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::MetricKind;
+
+    fn counter(name: &str) -> Metric {
+        Metric::new(
+            name,
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+    }
+
+    #[test]
+    fn matches_globs() {
+        let pattern = MetricNamePattern::new("*_errors_total", false).unwrap();
+        assert!(pattern.matches(&counter("http_request_errors_total")));
+        assert!(!pattern.matches(&counter("processed_events_total")));
+    }
+
+    #[test]
+    fn matches_regexes() {
+        let pattern = MetricNamePattern::new("^(events_in|events_out)_total$", true).unwrap();
+        assert!(pattern.matches(&counter("events_in_total")));
+        assert!(!pattern.matches(&counter("processed_events_total")));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(MetricNamePattern::new("[", false).is_err());
+        assert!(MetricNamePattern::new("(", true).is_err());
+    }
+}
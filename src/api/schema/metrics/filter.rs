@@ -1,4 +1,6 @@
-use super::{ProcessedBytesTotal, ProcessedEventsTotal};
+use super::{
+    ErrorsTotal, ProcessedBytesTotal, ProcessedEventsTotal, ReceivedEventsTotal, SentEventsTotal,
+};
 use crate::{
     event::{Event, Metric, MetricValue},
     metrics::{capture_metrics, get_controller, Controller},
@@ -31,6 +33,9 @@ fn sum_metrics<'a, I: IntoIterator<Item = &'a Metric>>(metrics: I) -> Option<Met
 pub trait MetricsFilter<'a> {
     fn processed_events_total(&self) -> Option<ProcessedEventsTotal>;
     fn processed_bytes_total(&self) -> Option<ProcessedBytesTotal>;
+    fn received_events_total(&self) -> Option<ReceivedEventsTotal>;
+    fn sent_events_total(&self) -> Option<SentEventsTotal>;
+    fn errors_total(&self) -> Option<ErrorsTotal>;
 }
 
 impl<'a> MetricsFilter<'a> for Vec<Metric> {
@@ -45,6 +50,24 @@ impl<'a> MetricsFilter<'a> for Vec<Metric> {
 
         Some(ProcessedBytesTotal::new(sum))
     }
+
+    fn received_events_total(&self) -> Option<ReceivedEventsTotal> {
+        let sum = sum_metrics(self.iter().filter(|m| m.name() == "events_in_total"))?;
+
+        Some(ReceivedEventsTotal::new(sum))
+    }
+
+    fn sent_events_total(&self) -> Option<SentEventsTotal> {
+        let sum = sum_metrics(self.iter().filter(|m| m.name() == "events_out_total"))?;
+
+        Some(SentEventsTotal::new(sum))
+    }
+
+    fn errors_total(&self) -> Option<ErrorsTotal> {
+        let sum = sum_metrics(self.iter().filter(|m| m.name().ends_with("_errors_total")))?;
+
+        Some(ErrorsTotal::new(sum))
+    }
 }
 
 impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
@@ -67,25 +90,45 @@ impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
 
         Some(ProcessedBytesTotal::new(sum))
     }
+
+    fn received_events_total(&self) -> Option<ReceivedEventsTotal> {
+        let sum = sum_metrics(
+            self.iter()
+                .filter(|m| m.name() == "events_in_total")
+                .copied(),
+        )?;
+
+        Some(ReceivedEventsTotal::new(sum))
+    }
+
+    fn sent_events_total(&self) -> Option<SentEventsTotal> {
+        let sum = sum_metrics(
+            self.iter()
+                .filter(|m| m.name() == "events_out_total")
+                .copied(),
+        )?;
+
+        Some(SentEventsTotal::new(sum))
+    }
+
+    fn errors_total(&self) -> Option<ErrorsTotal> {
+        let sum = sum_metrics(
+            self.iter()
+                .filter(|m| m.name().ends_with("_errors_total"))
+                .copied(),
+        )?;
+
+        Some(ErrorsTotal::new(sum))
+    }
 }
 
 /// Returns a stream of `Metric`s, collected at the provided millisecond interval.
 pub fn get_metrics(interval: i32) -> impl Stream<Item = Metric> {
-    let controller = get_controller().unwrap();
-    let mut interval = tokio::time::interval(Duration::from_millis(interval as u64));
-
-    stream! {
-        loop {
-            interval.tick().await;
-            for ev in capture_metrics(&controller) {
-                if let Event::Metric(m) = ev {
-                    yield m;
-                }
-            }
-        }
-    }
+    futures::StreamExt::flat_map(get_all_metrics(interval), futures::stream::iter)
 }
 
+/// Returns a stream of all `Metric`s collected at each tick of the provided millisecond
+/// interval.
 pub fn get_all_metrics(interval: i32) -> impl Stream<Item = Vec<Metric>> {
     let controller = get_controller().unwrap();
     let mut interval = tokio::time::interval(Duration::from_millis(interval as u64));
@@ -103,6 +146,46 @@ pub fn get_all_metrics(interval: i32) -> impl Stream<Item = Vec<Metric>> {
     }
 }
 
+/// Component names that a subscription is limited to, as glob patterns such as `nginx_*`.
+/// Without patterns, every component matches.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentNamePatterns(Vec<glob::Pattern>);
+
+impl ComponentNamePatterns {
+    pub fn new(patterns: Option<Vec<String>>) -> async_graphql::Result<Self> {
+        patterns
+            .unwrap_or_default()
+            .iter()
+            .map(|pattern| {
+                glob::Pattern::new(pattern)
+                    .map_err(|error| format!("Invalid pattern \"{}\": {}", pattern, error).into())
+            })
+            .collect::<async_graphql::Result<_>>()
+            .map(Self)
+    }
+
+    pub fn matches(&self, m: &Metric) -> bool {
+        self.0.is_empty()
+            || m.tags()
+                .and_then(|tags| tags.get("component_name"))
+                .map_or(false, |name| {
+                    self.0.iter().any(|pattern| pattern.matches(name))
+                })
+    }
+}
+
+/// Like `get_all_metrics`, but only returns the metrics of components matching `patterns`,
+/// so that others are filtered out before reaching the client.
+pub fn get_component_metrics(
+    interval: i32,
+    patterns: ComponentNamePatterns,
+) -> impl Stream<Item = Vec<Metric>> {
+    get_all_metrics(interval).map(move |mut m| {
+        m.retain(|m| patterns.matches(m));
+        m
+    })
+}
+
 /// Return Vec<Metric> based on a component name tag.
 pub fn by_component_name(component_name: &str) -> Vec<Metric> {
     capture_metrics(&GLOBAL_CONTROLLER)
@@ -113,7 +196,7 @@ pub fn by_component_name(component_name: &str) -> Vec<Metric> {
         .collect()
 }
 
-type MetricFilterFn = dyn Fn(&Metric) -> bool + Send + Sync;
+pub(crate) type MetricFilterFn = dyn Fn(&Metric) -> bool + Send + Sync;
 
 /// Returns a stream of `Vec<Metric>`, where `metric_name` matches the name of the metric
 /// (e.g. "processed_events_total"), and the value is derived from `MetricValue::Counter`. Uses a
@@ -122,11 +205,12 @@ type MetricFilterFn = dyn Fn(&Metric) -> bool + Send + Sync;
 /// to be notified as metrics increase without returning 'empty' or identical results.
 pub fn component_counter_metrics(
     interval: i32,
+    patterns: ComponentNamePatterns,
     filter_fn: &'static MetricFilterFn,
 ) -> impl Stream<Item = Vec<Metric>> {
     let mut cache = BTreeMap::new();
 
-    get_all_metrics(interval).map(move |m| {
+    get_component_metrics(interval, patterns).map(move |m| {
         m.into_iter()
             .filter(filter_fn)
             .filter_map(|m| match m.tag_value("component_name") {
@@ -185,11 +269,12 @@ pub fn counter_throughput(
 /// and filtered by the provided `filter_fn`, aggregated against each component.
 pub fn component_counter_throughputs(
     interval: i32,
+    patterns: ComponentNamePatterns,
     filter_fn: &'static MetricFilterFn,
 ) -> impl Stream<Item = Vec<(Metric, f64)>> {
     let mut cache = BTreeMap::new();
 
-    get_all_metrics(interval)
+    get_component_metrics(interval, patterns)
         .map(move |m| {
             m.into_iter()
                 .filter(filter_fn)
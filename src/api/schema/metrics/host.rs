@@ -1,7 +1,10 @@
 use crate::event::{Metric, MetricValue};
 use crate::sources;
 use crate::sources::host_metrics::HostMetricsConfig;
-use async_graphql::Object;
+use async_graphql::{Enum, Object};
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use tokio::{stream::Stream, time::Duration};
 
 pub struct MemoryMetrics(Vec<Metric>);
 
@@ -257,6 +260,105 @@ impl DiskMetrics {
     }
 }
 
+pub struct PressureStallMetrics(Vec<Metric>);
+
+#[Object]
+/// Share of time that tasks were stalled waiting for a resource
+impl PressureStallMetrics {
+    /// Share of the last 10 seconds in which some tasks were stalled
+    async fn some_avg10(&self) -> f64 {
+        filter_pressure_stall(&self.0, "some", "10s")
+    }
+
+    /// Share of the last 60 seconds in which some tasks were stalled
+    async fn some_avg60(&self) -> f64 {
+        filter_pressure_stall(&self.0, "some", "60s")
+    }
+
+    /// Share of the last 300 seconds in which some tasks were stalled
+    async fn some_avg300(&self) -> f64 {
+        filter_pressure_stall(&self.0, "some", "300s")
+    }
+
+    /// Share of the last 10 seconds in which all tasks were stalled
+    async fn full_avg10(&self) -> f64 {
+        filter_pressure_stall(&self.0, "full", "10s")
+    }
+
+    /// Share of the last 60 seconds in which all tasks were stalled
+    async fn full_avg60(&self) -> f64 {
+        filter_pressure_stall(&self.0, "full", "60s")
+    }
+
+    /// Share of the last 300 seconds in which all tasks were stalled
+    async fn full_avg300(&self) -> f64 {
+        filter_pressure_stall(&self.0, "full", "300s")
+    }
+}
+
+pub struct CgroupMetrics(Vec<Metric>);
+
+impl CgroupMetrics {
+    fn pressure(&self, resource: &str) -> Option<PressureStallMetrics> {
+        let metrics = self
+            .0
+            .iter()
+            .filter(|m| m.tag_matches("resource", resource))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if metrics.is_empty() {
+            None
+        } else {
+            Some(PressureStallMetrics(metrics))
+        }
+    }
+}
+
+#[Object]
+/// Metrics of the cgroup Vector is running in
+impl CgroupMetrics {
+    /// CPU seconds total
+    async fn cpu_usage_seconds_total(&self) -> f64 {
+        filter_host_metric(&self.0, "cgroup_cpu_usage_seconds_total")
+    }
+
+    /// Total CPU enforcement periods in which the cgroup was throttled
+    async fn cpu_throttled_periods_total(&self) -> f64 {
+        filter_host_metric(&self.0, "cgroup_cpu_throttled_periods_total")
+    }
+
+    /// Total seconds the cgroup was throttled for
+    async fn cpu_throttled_seconds_total(&self) -> f64 {
+        filter_host_metric(&self.0, "cgroup_cpu_throttled_seconds_total")
+    }
+
+    /// Used memory bytes
+    async fn memory_current_bytes(&self) -> f64 {
+        filter_host_metric(&self.0, "cgroup_memory_current_bytes")
+    }
+
+    /// Memory limit bytes, if the cgroup is limited
+    async fn memory_limit_bytes(&self) -> Option<f64> {
+        find_host_metric(&self.0, "cgroup_memory_limit_bytes")
+    }
+
+    /// CPU pressure stall information (cgroup v2 only)
+    async fn cpu_pressure(&self) -> Option<PressureStallMetrics> {
+        self.pressure("cpu")
+    }
+
+    /// Memory pressure stall information (cgroup v2 only)
+    async fn memory_pressure(&self) -> Option<PressureStallMetrics> {
+        self.pressure("memory")
+    }
+
+    /// I/O pressure stall information (cgroup v2 only)
+    async fn io_pressure(&self) -> Option<PressureStallMetrics> {
+        self.pressure("io")
+    }
+}
+
 pub struct HostMetrics(HostMetricsConfig);
 
 impl HostMetrics {
@@ -307,17 +409,174 @@ impl HostMetrics {
     async fn disk(&self) -> DiskMetrics {
         DiskMetrics(self.0.disk_metrics().await)
     }
+
+    /// Metrics of the cgroup Vector is running in, if any (Linux only)
+    async fn cgroups(&self) -> Option<CgroupMetrics> {
+        let metrics = self.0.cgroups_metrics().await;
+        if metrics.is_empty() {
+            None
+        } else {
+            Some(CgroupMetrics(metrics))
+        }
+    }
+}
+
+/// Host metrics collector, selectable on the `hostMetrics` subscription
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HostMetricsCollector {
+    /// CPU metrics
+    Cpu,
+    /// Memory and swap metrics
+    Memory,
+    /// Disk metrics
+    Disk,
+    /// Network metrics
+    Network,
+    /// Filesystem metrics
+    Filesystem,
+}
+
+impl HostMetricsCollector {
+    /// All collectors, used when a subscriber doesn't select any
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Cpu,
+            Self::Memory,
+            Self::Disk,
+            Self::Network,
+            Self::Filesystem,
+        ]
+    }
+}
+
+/// A sample of host metrics, taken from the selected collectors only
+#[derive(Default)]
+pub struct HostMetricsSample {
+    timestamp: Option<DateTime<Utc>>,
+    cpu: Option<Vec<Metric>>,
+    memory: Option<Vec<Metric>>,
+    swap: Option<Vec<Metric>>,
+    disk: Option<Vec<Metric>>,
+    network: Option<Vec<Metric>>,
+    filesystem: Option<Vec<Metric>>,
+}
+
+impl HostMetricsSample {
+    async fn collect(config: &HostMetricsConfig, collectors: &[HostMetricsCollector]) -> Self {
+        let mut sample = Self {
+            timestamp: Some(Utc::now()),
+            ..Self::default()
+        };
+
+        for collector in collectors {
+            match collector {
+                HostMetricsCollector::Cpu => sample.cpu = Some(config.cpu_metrics().await),
+                HostMetricsCollector::Memory => {
+                    sample.memory = Some(config.memory_metrics().await);
+                    sample.swap = Some(config.swap_metrics().await);
+                }
+                HostMetricsCollector::Disk => sample.disk = Some(config.disk_metrics().await),
+                HostMetricsCollector::Network => {
+                    sample.network = Some(config.network_metrics().await);
+                }
+                HostMetricsCollector::Filesystem => {
+                    sample.filesystem = Some(config.filesystem_metrics().await);
+                }
+            }
+        }
+
+        sample
+    }
+}
+
+#[Object]
+/// Host metrics sampled by the `hostMetrics` subscription. Metrics of collectors that
+/// weren't selected are null
+impl HostMetricsSample {
+    /// Time the sample was taken
+    async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.timestamp
+    }
+
+    /// CPU metrics
+    async fn cpu(&self) -> Option<CPUMetrics> {
+        self.cpu.clone().map(CPUMetrics)
+    }
+
+    /// Memory metrics
+    async fn memory(&self) -> Option<MemoryMetrics> {
+        self.memory.clone().map(MemoryMetrics)
+    }
+
+    /// Swap metrics
+    async fn swap(&self) -> Option<SwapMetrics> {
+        self.swap.clone().map(SwapMetrics)
+    }
+
+    /// Disk metrics
+    async fn disk(&self) -> Option<DiskMetrics> {
+        self.disk.clone().map(DiskMetrics)
+    }
+
+    /// Network metrics
+    async fn network(&self) -> Option<NetworkMetrics> {
+        self.network.clone().map(NetworkMetrics)
+    }
+
+    /// Filesystem metrics
+    async fn filesystem(&self) -> Option<FileSystemMetrics> {
+        self.filesystem.clone().map(FileSystemMetrics)
+    }
+}
+
+/// Returns a stream of host metrics samples from `collectors`, taken every `interval`
+/// milliseconds.
+pub fn get_host_metrics(
+    interval: i32,
+    collectors: Vec<HostMetricsCollector>,
+) -> impl Stream<Item = HostMetricsSample> {
+    let config = HostMetricsConfig::default();
+    let mut interval = tokio::time::interval(Duration::from_millis(interval as u64));
+
+    stream! {
+        loop {
+            interval.tick().await;
+            yield HostMetricsSample::collect(&config, &collectors).await;
+        }
+    }
+}
+
+/// Returns the inner `value` of a metric
+fn metric_value(metric: &Metric) -> f64 {
+    match metric.data.value {
+        MetricValue::Gauge { value } => value,
+        MetricValue::Counter { value } => value,
+        _ => 0.00,
+    }
+}
+
+/// Finds a host metric by name, returning its inner `value`
+fn find_host_metric(metrics: &[Metric], name: &str) -> Option<f64> {
+    metrics
+        .iter()
+        .find(|m| matches!(m.namespace(), Some(n) if n == "host") && m.name() == name)
+        .map(metric_value)
 }
 
 /// Filters a Vec<Metric> by name, returning the inner `value` or 0.00 if not found
 fn filter_host_metric(metrics: &[Metric], name: &str) -> f64 {
+    find_host_metric(metrics, name).unwrap_or_else(|| 0.00)
+}
+
+/// Filters pressure stall metrics by type and window, returning the ratio or 0.00 if not found
+fn filter_pressure_stall(metrics: &[Metric], r#type: &str, window: &str) -> f64 {
     metrics
         .iter()
-        .find(|m| matches!(m.namespace(), Some(n) if n == "host") && m.name() == name)
-        .map(|m| match m.data.value {
-            MetricValue::Gauge { value } => value,
-            MetricValue::Counter { value } => value,
-            _ => 0.00,
+        .find(|m| {
+            m.name() == "cgroup_pressure_stall_ratio"
+                && m.tag_matches("type", r#type)
+                && m.tag_matches("window", window)
         })
+        .map(metric_value)
         .unwrap_or_else(|| 0.00)
 }
@@ -1,28 +1,41 @@
+mod allocated_bytes;
+mod buffer;
+mod by_name;
 mod errors;
 pub mod filter;
 mod host;
 mod processed_bytes;
 mod processed_events;
+mod received_events;
+mod sent_events;
 mod sink;
 pub mod source;
+mod top;
 mod transform;
 mod uptime;
 
 use async_graphql::{validators::IntRange, Interface, Object, Subscription};
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use tokio::stream::{Stream, StreamExt};
 
+pub use allocated_bytes::ComponentAllocatedBytes;
+pub use buffer::{gauge_value, ComponentBufferUsage};
+pub use by_name::{MetricNamePattern, NamedMetric};
 pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use filter::*;
-pub use host::HostMetrics;
+pub use host::{HostMetrics, HostMetricsCollector, HostMetricsSample};
 pub use processed_bytes::{
     ComponentProcessedBytesThroughput, ComponentProcessedBytesTotal, ProcessedBytesTotal,
 };
 pub use processed_events::{
     ComponentProcessedEventsThroughput, ComponentProcessedEventsTotal, ProcessedEventsTotal,
 };
+pub use received_events::{ComponentReceivedEventsTotal, ReceivedEventsTotal};
+pub use sent_events::{ComponentSentEventsTotal, SentEventsTotal};
 pub use sink::{IntoSinkMetrics, SinkMetrics};
 pub use source::{IntoSourceMetrics, SourceMetrics};
+pub use top::{TopComponent, TopComponentsSortBy};
 pub use transform::{IntoTransformMetrics, TransformMetrics};
 pub use uptime::Uptime;
 
@@ -32,6 +45,8 @@ pub enum MetricType {
     Uptime(Uptime),
     ProcessedEventsTotal(ProcessedEventsTotal),
     ProcessedBytesTotal(ProcessedBytesTotal),
+    ReceivedEventsTotal(ReceivedEventsTotal),
+    SentEventsTotal(SentEventsTotal),
 }
 
 #[derive(Default)]
@@ -61,6 +76,19 @@ impl MetricsSubscription {
         })
     }
 
+    /// Host metrics from the selected `collectors`, or all of them if none are given,
+    /// sampled every `interval`.
+    async fn host_metrics(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        collectors: Option<Vec<HostMetricsCollector>>,
+    ) -> impl Stream<Item = HostMetricsSample> {
+        let collectors = collectors
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(HostMetricsCollector::all);
+        host::get_host_metrics(interval, collectors)
+    }
+
     /// Event processing metrics.
     async fn processed_events_total(
         &self,
@@ -85,31 +113,70 @@ impl MetricsSubscription {
     async fn component_processed_events_throughputs(
         &self,
         #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
-    ) -> impl Stream<Item = Vec<ComponentProcessedEventsThroughput>> {
-        component_counter_throughputs(interval, &|m| m.name() == "processed_events_total").map(
-            |m| {
-                m.into_iter()
-                    .map(|(m, throughput)| {
-                        ComponentProcessedEventsThroughput::new(
-                            m.tag_value("component_name").unwrap(),
-                            throughput as i64,
-                        )
-                    })
-                    .collect()
-            },
-        )
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentProcessedEventsThroughput>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(component_counter_throughputs(interval, patterns, &|m| {
+            m.name() == "processed_events_total"
+        })
+        .map(|m| {
+            m.into_iter()
+                .map(|(m, throughput)| {
+                    ComponentProcessedEventsThroughput::new(
+                        m.tag_value("component_name").unwrap(),
+                        throughput as i64,
+                    )
+                })
+                .collect()
+        }))
     }
 
     /// Component event processing metrics over `interval`.
     async fn component_processed_events_totals(
         &self,
         #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
-    ) -> impl Stream<Item = Vec<ComponentProcessedEventsTotal>> {
-        component_counter_metrics(interval, &|m| m.name() == "processed_events_total").map(|m| {
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentProcessedEventsTotal>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(component_counter_metrics(interval, patterns, &|m| {
+            m.name() == "processed_events_total"
+        })
+        .map(|m| {
             m.into_iter()
                 .map(ComponentProcessedEventsTotal::new)
                 .collect()
-        })
+        }))
+    }
+
+    /// Component metrics of events received from upstream components over `interval`.
+    async fn component_received_events_totals(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentReceivedEventsTotal>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(
+            component_counter_metrics(interval, patterns, &|m| m.name() == "events_in_total").map(
+                |m| {
+                    m.into_iter()
+                        .map(ComponentReceivedEventsTotal::new)
+                        .collect()
+                },
+            ),
+        )
+    }
+
+    /// Component metrics of events sent to downstream components over `interval`.
+    async fn component_sent_events_totals(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentSentEventsTotal>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(
+            component_counter_metrics(interval, patterns, &|m| m.name() == "events_out_total")
+                .map(|m| m.into_iter().map(ComponentSentEventsTotal::new).collect()),
+        )
     }
 
     /// Byte processing metrics.
@@ -136,20 +203,30 @@ impl MetricsSubscription {
     async fn component_processed_bytes_totals(
         &self,
         #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
-    ) -> impl Stream<Item = Vec<ComponentProcessedBytesTotal>> {
-        component_counter_metrics(interval, &|m| m.name() == "processed_bytes_total").map(|m| {
-            m.into_iter()
-                .map(ComponentProcessedBytesTotal::new)
-                .collect()
-        })
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentProcessedBytesTotal>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(
+            component_counter_metrics(interval, patterns, &|m| m.name() == "processed_bytes_total")
+                .map(|m| {
+                    m.into_iter()
+                        .map(ComponentProcessedBytesTotal::new)
+                        .collect()
+                }),
+        )
     }
 
     /// Component byte processing throughput over `interval`
     async fn component_processed_bytes_throughputs(
         &self,
         #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
-    ) -> impl Stream<Item = Vec<ComponentProcessedBytesThroughput>> {
-        component_counter_throughputs(interval, &|m| m.name() == "processed_bytes_total").map(|m| {
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentProcessedBytesThroughput>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(component_counter_throughputs(interval, patterns, &|m| {
+            m.name() == "processed_bytes_total"
+        })
+        .map(|m| {
             m.into_iter()
                 .map(|(m, throughput)| {
                     ComponentProcessedBytesThroughput::new(
@@ -158,7 +235,24 @@ impl MetricsSubscription {
                     )
                 })
                 .collect()
-        })
+        }))
+    }
+
+    /// The `limit` components with the highest throughput of events or bytes per second,
+    /// busiest first, sampled over `interval`.
+    async fn top_components(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        patterns: Option<Vec<String>>,
+        #[graphql(default = 10, validator(IntRange(min = "1", max = "1_000")))] limit: i32,
+        #[graphql(default_with = "TopComponentsSortBy::ProcessedEvents")]
+        sort_by: TopComponentsSortBy,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<TopComponent>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(
+            component_counter_throughputs(interval, patterns, sort_by.filter_fn())
+                .map(move |m| top::top_components(m, interval, limit as usize)),
+        )
     }
 
     /// Total error metrics.
@@ -175,9 +269,64 @@ impl MetricsSubscription {
     async fn component_errors_totals(
         &self,
         #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
-    ) -> impl Stream<Item = Vec<ComponentErrorsTotal>> {
-        component_counter_metrics(interval, &|m| m.name().ends_with("_errors_total"))
-            .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentErrorsTotal>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(
+            component_counter_metrics(interval, patterns, &|m| m.name().ends_with("_errors_total"))
+                .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect()),
+        )
+    }
+
+    /// Buffer usage of each sink over `interval`.
+    async fn component_buffer_usage(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentBufferUsage>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(get_component_metrics(interval, patterns).map(|m| {
+            m.into_iter()
+                .filter(|m| m.name().starts_with("buffer_"))
+                .filter_map(|m| m.tag_value("component_name").map(|name| (name, m)))
+                .fold(BTreeMap::new(), |mut map, (name, m)| {
+                    map.entry(name).or_insert_with(Vec::new).push(m);
+                    map
+                })
+                .into_iter()
+                .map(|(name, metrics)| ComponentBufferUsage::new(name, metrics))
+                .collect()
+        }))
+    }
+
+    /// Bytes currently allocated by each component, over `interval`.
+    async fn component_allocated_bytes(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        patterns: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<ComponentAllocatedBytes>>> {
+        let patterns = ComponentNamePatterns::new(patterns)?;
+        Ok(get_component_metrics(interval, patterns).map(|m| {
+            m.into_iter()
+                .filter(|m| m.name() == "component_allocated_bytes")
+                .map(ComponentAllocatedBytes::new)
+                .collect()
+        }))
+    }
+
+    /// Every internal metric whose name matches `pattern` over `interval`, including metrics
+    /// without a dedicated type. The pattern is a glob such as `*_errors_total`, or a regular
+    /// expression if `regex` is set.
+    async fn by_name(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        pattern: String,
+        #[graphql(default)] regex: bool,
+    ) -> async_graphql::Result<impl Stream<Item = NamedMetric>> {
+        let pattern = MetricNamePattern::new(&pattern, regex)?;
+        Ok(get_metrics(interval)
+            .filter(move |m| pattern.matches(m))
+            .map(NamedMetric::new))
     }
 
     /// All metrics.
@@ -189,6 +338,8 @@ impl MetricsSubscription {
             "uptime_seconds" => Some(MetricType::Uptime(m.into())),
             "processed_events_total" => Some(MetricType::ProcessedEventsTotal(m.into())),
             "processed_bytes_total" => Some(MetricType::ProcessedBytesTotal(m.into())),
+            "events_in_total" => Some(MetricType::ReceivedEventsTotal(m.into())),
+            "events_out_total" => Some(MetricType::SentEventsTotal(m.into())),
             _ => None,
         })
     }
@@ -0,0 +1,71 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+
+pub struct SentEventsTotal(Metric);
+
+impl SentEventsTotal {
+    pub fn new(m: Metric) -> Self {
+        Self(m)
+    }
+
+    pub fn get_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.data.timestamp
+    }
+
+    pub fn get_sent_events_total(&self) -> f64 {
+        match self.0.data.value {
+            MetricValue::Counter { value } => value,
+            _ => 0.00,
+        }
+    }
+}
+
+#[Object]
+impl SentEventsTotal {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.get_timestamp()
+    }
+
+    /// Total number of events sent
+    pub async fn sent_events_total(&self) -> f64 {
+        self.get_sent_events_total()
+    }
+}
+
+impl From<Metric> for SentEventsTotal {
+    fn from(m: Metric) -> Self {
+        Self(m)
+    }
+}
+
+pub struct ComponentSentEventsTotal {
+    name: String,
+    metric: Metric,
+}
+
+impl ComponentSentEventsTotal {
+    /// Returns a new `ComponentSentEventsTotal` struct, which is a GraphQL type. The
+    /// component name is hoisted for clear field resolution in the resulting payload
+    pub fn new(metric: Metric) -> Self {
+        let name = metric.tag_value("component_name").expect(
+            "Returned a metric without a `component_name`, which shouldn't happen. Please report.",
+        );
+
+        Self { name, metric }
+    }
+}
+
+#[Object]
+impl ComponentSentEventsTotal {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Events sent total metric
+    async fn metric(&self) -> SentEventsTotal {
+        SentEventsTotal::new(self.metric.clone())
+    }
+}
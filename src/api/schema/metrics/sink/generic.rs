@@ -24,4 +24,9 @@ impl GenericSinkMetrics {
     pub async fn processed_bytes_total(&self) -> Option<metrics::ProcessedBytesTotal> {
         self.0.processed_bytes_total()
     }
+
+    /// Errors encountered by the current sink
+    pub async fn errors_total(&self) -> Option<metrics::ErrorsTotal> {
+        self.0.errors_total()
+    }
 }
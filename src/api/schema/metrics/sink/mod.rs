@@ -1,13 +1,14 @@
 mod generic;
 
-use super::{ProcessedBytesTotal, ProcessedEventsTotal};
+use super::{ErrorsTotal, ProcessedBytesTotal, ProcessedEventsTotal};
 use crate::event::Metric;
 use async_graphql::Interface;
 
 #[derive(Debug, Clone, Interface)]
 #[graphql(
     field(name = "processed_events_total", type = "Option<ProcessedEventsTotal>"),
-    field(name = "processed_bytes_total", type = "Option<ProcessedBytesTotal>")
+    field(name = "processed_bytes_total", type = "Option<ProcessedBytesTotal>"),
+    field(name = "errors_total", type = "Option<ErrorsTotal>")
 )]
 pub enum SinkMetrics {
     GenericSinkMetrics(generic::GenericSinkMetrics),
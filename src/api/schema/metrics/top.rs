@@ -0,0 +1,107 @@
+use super::MetricFilterFn;
+use crate::event::Metric;
+use async_graphql::{Enum, SimpleObject};
+use std::cmp::Ordering;
+
+/// Throughput to rank components by
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TopComponentsSortBy {
+    /// Events processed per second
+    ProcessedEvents,
+    /// Bytes processed per second
+    ProcessedBytes,
+}
+
+impl TopComponentsSortBy {
+    pub fn filter_fn(self) -> &'static MetricFilterFn {
+        match self {
+            Self::ProcessedEvents => &is_processed_events_total,
+            Self::ProcessedBytes => &is_processed_bytes_total,
+        }
+    }
+}
+
+fn is_processed_events_total(m: &Metric) -> bool {
+    m.name() == "processed_events_total"
+}
+
+fn is_processed_bytes_total(m: &Metric) -> bool {
+    m.name() == "processed_bytes_total"
+}
+
+#[derive(SimpleObject, Debug, Clone, PartialEq)]
+pub struct TopComponent {
+    /// Component name
+    name: String,
+
+    /// Throughput per second, of events or bytes as ranked by
+    throughput: f64,
+}
+
+/// The `limit` components with the highest throughputs, busiest first, from the
+/// throughputs of all components sampled over `interval` milliseconds.
+pub fn top_components(
+    throughputs: Vec<(Metric, f64)>,
+    interval: i32,
+    limit: usize,
+) -> Vec<TopComponent> {
+    let per_second = 1000.0 / interval as f64;
+    let mut components = throughputs
+        .into_iter()
+        .filter_map(|(m, throughput)| {
+            Some(TopComponent {
+                name: m.tag_value("component_name")?,
+                throughput: throughput * per_second,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    components.sort_by(|a, b| {
+        b.throughput
+            .partial_cmp(&a.throughput)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    components.truncate(limit);
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{MetricKind, MetricValue};
+
+    fn throughput(name: &str, value: f64) -> (Metric, f64) {
+        let metric = Metric::new(
+            "processed_events_total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value },
+        )
+        .with_tags(Some(
+            vec![("component_name".to_owned(), name.to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+        (metric, value)
+    }
+
+    #[test]
+    fn ranks_busiest_first() {
+        let throughputs = vec![
+            throughput("quiet", 1.0),
+            throughput("busy", 50.0),
+            throughput("tied_b", 10.0),
+            throughput("tied_a", 10.0),
+        ];
+
+        let top = top_components(throughputs, 500, 3);
+        let top = top
+            .iter()
+            .map(|c| (c.name.as_str(), c.throughput))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            top,
+            vec![("busy", 100.0), ("tied_a", 20.0), ("tied_b", 20.0)]
+        );
+    }
+}
@@ -1,13 +1,18 @@
 mod generic;
 
-use super::{ProcessedBytesTotal, ProcessedEventsTotal};
+use super::{
+    ErrorsTotal, ProcessedBytesTotal, ProcessedEventsTotal, ReceivedEventsTotal, SentEventsTotal,
+};
 use crate::event::Metric;
 use async_graphql::Interface;
 
 #[derive(Debug, Clone, Interface)]
 #[graphql(
     field(name = "processed_events_total", type = "Option<ProcessedEventsTotal>"),
-    field(name = "processed_bytes_total", type = "Option<ProcessedBytesTotal>")
+    field(name = "processed_bytes_total", type = "Option<ProcessedBytesTotal>"),
+    field(name = "received_events_total", type = "Option<ReceivedEventsTotal>"),
+    field(name = "sent_events_total", type = "Option<SentEventsTotal>"),
+    field(name = "errors_total", type = "Option<ErrorsTotal>")
 )]
 pub enum TransformMetrics {
     GenericTransformMetrics(generic::GenericTransformMetrics),
@@ -1,29 +1,65 @@
+mod checkpoints;
 pub mod components;
 pub mod filter;
 mod health;
-mod meta;
+pub mod meta;
 mod metrics;
+mod profile;
 mod relay;
+pub mod reload;
 pub mod sort;
+mod tuning;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
 
 #[derive(MergedObject, Default)]
 pub struct Query(
     health::HealthQuery,
     components::ComponentsQuery,
     metrics::MetricsQuery,
+    checkpoints::CheckpointsQuery,
     meta::MetaQuery,
+    profile::ProfileQuery,
 );
 
+#[derive(MergedObject, Default)]
+pub struct Mutation(tuning::TuningMutation);
+
 #[derive(MergedSubscription, Default)]
 pub struct Subscription(
     health::HealthSubscription,
     metrics::MetricsSubscription,
     components::ComponentsSubscription,
+    reload::ReloadSubscription,
 );
 
-/// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+/// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types. Federation
+/// is enabled, so that a gateway can compose the schema from its `_service { sdl }`, and resolve
+/// components by their `name` key
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
+    .enable_federation()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn federation_sdl() {
+        let response = build_schema()
+            .finish()
+            .execute("{ _service { sdl } }")
+            .await;
+        assert!(response.errors.is_empty());
+
+        let json = serde_json::to_value(&response.data).unwrap();
+        let sdl = json["_service"]["sdl"].as_str().unwrap();
+        assert!(sdl.contains(r#"type Source @key(fields: "name")"#));
+        assert!(sdl.contains(r#"type Sink @key(fields: "name")"#));
+    }
 }
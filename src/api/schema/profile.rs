@@ -0,0 +1,63 @@
+use crate::profiling;
+use async_graphql::{validators::IntRange, Object, SimpleObject};
+use std::time::Duration;
+
+#[derive(SimpleObject)]
+pub struct ProfileStack {
+    /// Frames of the stack, outermost first
+    frames: Vec<String>,
+
+    /// Number of times the stack was sampled
+    samples: i64,
+}
+
+pub struct Profile(profiling::Profile);
+
+#[Object]
+impl Profile {
+    /// Duration of the profile, in milliseconds
+    async fn duration(&self) -> i64 {
+        self.0.duration.as_millis() as i64
+    }
+
+    /// Number of samples taken per second
+    async fn frequency(&self) -> i32 {
+        self.0.frequency as i32
+    }
+
+    /// Sampled stacks of component spans
+    async fn stacks(&self) -> Vec<ProfileStack> {
+        self.0
+            .samples
+            .iter()
+            .map(|(frames, samples)| ProfileStack {
+                frames: frames.clone(),
+                samples: *samples as i64,
+            })
+            .collect()
+    }
+
+    /// Sampled stacks in the folded format, as consumed by flamegraph tools
+    async fn folded(&self) -> String {
+        self.0.folded()
+    }
+}
+
+#[derive(Default)]
+pub struct ProfileQuery;
+
+#[Object]
+impl ProfileQuery {
+    /// Samples which components are using CPU time for `duration` milliseconds. Requires
+    /// `api.profiling` to be enabled
+    async fn profile(
+        &self,
+        #[graphql(default = 5000, validator(IntRange(min = "100", max = "60_000")))] duration: i32,
+        #[graphql(default = 99, validator(IntRange(min = "1", max = "1000")))] frequency: i32,
+    ) -> async_graphql::Result<Profile> {
+        let profile =
+            profiling::profile(Duration::from_millis(duration as u64), frequency as u32).await?;
+
+        Ok(Profile(profile))
+    }
+}
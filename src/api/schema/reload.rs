@@ -0,0 +1,104 @@
+use crate::config::{ConfigDiff, Difference};
+use async_graphql::{Enum, SimpleObject, Subscription};
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use tokio::stream::{Stream, StreamExt};
+
+/// Outcome of an attempt to reload the configuration
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReloadOutcome {
+    /// The new configuration is running
+    Success,
+    /// The new configuration couldn't be loaded, so nothing was changed
+    FailedValidation,
+    /// The new configuration changes global options, which can't be changed by a
+    /// reload, so nothing was changed
+    Rejected,
+    /// The new configuration failed to start, and the previous one was restored
+    RolledBack,
+    /// Neither the new nor the previous configuration could be started, and
+    /// Vector is shutting down
+    RecoveryFailed,
+}
+
+/// Components of each kind that a reload added, removed or changed
+#[derive(SimpleObject, Debug, Clone, Default)]
+pub struct ComponentsDiff {
+    sources: Vec<String>,
+    transforms: Vec<String>,
+    sinks: Vec<String>,
+}
+
+impl ComponentsDiff {
+    fn new(diff: &ConfigDiff, names: impl Fn(&Difference) -> Vec<String>) -> Self {
+        Self {
+            sources: names(&diff.sources),
+            transforms: names(&diff.transforms),
+            sinks: names(&diff.sinks),
+        }
+    }
+}
+
+fn sorted<'a>(names: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut names = names.cloned().collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct ConfigEvent {
+    /// Outcome of the reload
+    outcome: ReloadOutcome,
+    /// Time the reload finished
+    timestamp: DateTime<Utc>,
+    /// Components added by the new configuration, whether or not it was applied
+    added: ComponentsDiff,
+    /// Components removed by the new configuration, whether or not it was applied
+    removed: ComponentsDiff,
+    /// Components changed by the new configuration, whether or not it was applied
+    changed: ComponentsDiff,
+}
+
+impl ConfigEvent {
+    /// The diff is `None` when the new configuration couldn't be loaded.
+    pub fn new(outcome: ReloadOutcome, diff: Option<&ConfigDiff>) -> Self {
+        let components = |names: fn(&Difference) -> Vec<String>| {
+            diff.map(|diff| ComponentsDiff::new(diff, names))
+                .unwrap_or_default()
+        };
+
+        Self {
+            outcome,
+            timestamp: Utc::now(),
+            added: components(|d| sorted(d.to_add.iter())),
+            removed: components(|d| sorted(d.to_remove.iter())),
+            changed: components(|d| sorted(d.to_change.iter())),
+        }
+    }
+}
+
+lazy_static! {
+    static ref CONFIG_EVENTS: tokio::sync::broadcast::Sender<ConfigEvent> = {
+        let (tx, _) = tokio::sync::broadcast::channel(10);
+        tx
+    };
+}
+
+#[derive(Debug, Default)]
+pub struct ReloadSubscription;
+
+#[Subscription]
+impl ReloadSubscription {
+    /// Subscribes to the outcome of every attempt to reload the configuration
+    async fn config_events(&self) -> impl Stream<Item = ConfigEvent> {
+        CONFIG_EVENTS
+            .subscribe()
+            .into_stream()
+            .filter_map(|event| event.ok())
+    }
+}
+
+/// Publish the outcome of a reload to `configEvents` subscribers
+pub fn publish(event: ConfigEvent) {
+    let _ = CONFIG_EVENTS.send(event);
+}
@@ -0,0 +1,66 @@
+use crate::sinks::util::request_limits::{self, RequestLimits};
+use async_graphql::{MaybeUndefined, Object, SimpleObject};
+
+/// Request limits of a running sink
+#[derive(SimpleObject)]
+pub struct SinkRequestLimits {
+    /// Sink name
+    name: String,
+
+    /// Number of requests allowed per `request.rate_limit_duration_secs`
+    rate_limit_num: i64,
+
+    /// Cap on the number of concurrent requests, if any
+    max_concurrency: Option<i64>,
+}
+
+impl SinkRequestLimits {
+    fn new(name: String, limits: &RequestLimits) -> Self {
+        Self {
+            name,
+            rate_limit_num: limits.rate_limit_num() as i64,
+            max_concurrency: limits.max_concurrency().map(|max| max as i64),
+        }
+    }
+}
+
+fn positive(value: MaybeUndefined<i64>, arg: &str) -> async_graphql::Result<Option<Option<u64>>> {
+    match value {
+        MaybeUndefined::Undefined => Ok(None),
+        MaybeUndefined::Null => Ok(Some(None)),
+        MaybeUndefined::Value(value) if value > 0 => Ok(Some(Some(value as u64))),
+        MaybeUndefined::Value(_) => Err(format!("`{}` must be positive", arg).into()),
+    }
+}
+
+#[derive(Default)]
+pub struct TuningMutation;
+
+#[Object]
+impl TuningMutation {
+    /// Tunes the request limits of a running sink without a reload, until the
+    /// sink is changed by a new configuration. Omitted limits are left as they
+    /// are, and `null` restores the configured rate limit or lifts the cap on
+    /// concurrency
+    async fn update_sink_request_limits(
+        &self,
+        name: String,
+        rate_limit_num: MaybeUndefined<i64>,
+        max_concurrency: MaybeUndefined<i64>,
+    ) -> async_graphql::Result<SinkRequestLimits> {
+        let rate_limit_num = positive(rate_limit_num, "rateLimitNum")?;
+        let max_concurrency = positive(max_concurrency, "maxConcurrency")?;
+
+        let limits = request_limits::find(&name)
+            .ok_or_else(|| format!("Sink \"{}\" isn't running, or has no request limits", name))?;
+
+        if let Some(num) = rate_limit_num {
+            limits.set_rate_limit_num(num);
+        }
+        if let Some(max) = max_concurrency {
+            limits.set_max_concurrency(max.map(|max| max as usize));
+        }
+
+        Ok(SinkRequestLimits::new(name, &limits))
+    }
+}
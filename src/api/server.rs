@@ -1,11 +1,11 @@
-use super::{handler, schema};
+use super::{bounded, handler, schema, websocket};
 use crate::config;
 use async_graphql::{
     http::{playground_source, GraphQLPlaygroundConfig},
     Request, Schema,
 };
-use async_graphql_warp::{graphql_subscription, Response as GQLResponse};
-use std::{convert::Infallible, net::SocketAddr};
+use async_graphql_warp::Response as GQLResponse;
+use std::{convert::Infallible, net::SocketAddr, time::Duration};
 use tokio::sync::oneshot;
 use warp::filters::BoxedFilter;
 use warp::{http::Response, Filter, Reply};
@@ -19,7 +19,7 @@ impl Server {
     /// Start the API server. This creates the routes and spawns a Warp server. The server is
     /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing
     pub fn start(config: &config::Config) -> Self {
-        let routes = make_routes(config.api.playground);
+        let routes = make_routes(&config.api);
 
         let (_shutdown, rx) = oneshot::channel();
         let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
@@ -31,6 +31,9 @@ impl Server {
 
         // Update component schema with the config before starting the server
         schema::components::update_config(config);
+        schema::meta::update_config(config);
+        bounded::update_config(config);
+        crate::profiling::set_enabled(config.api.profiling);
 
         // Spawn the server in the background
         tokio::spawn(server);
@@ -47,11 +50,24 @@ impl Server {
     /// directly involve `self`, it provides a neater API to expose an internal implementation
     /// detail than exposing the function of the sub-mod directly
     pub fn update_config(&self, config: &config::Config) {
-        schema::components::update_config(config)
+        schema::components::update_config(config);
+        schema::meta::update_config(config);
+        bounded::update_config(config);
+        crate::profiling::set_enabled(config.api.profiling);
+    }
+
+    /// Publish the outcome of a config reload, along with the components it affected. The
+    /// diff is `None` if the new config couldn't be loaded
+    pub fn config_reloaded(
+        &self,
+        outcome: schema::reload::ReloadOutcome,
+        diff: Option<&config::ConfigDiff>,
+    ) {
+        schema::reload::publish(schema::reload::ConfigEvent::new(outcome, diff));
     }
 }
 
-fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
+fn make_routes(options: &config::api::Options) -> BoxedFilter<(impl Reply,)> {
     // Build the GraphQL schema
     let schema = schema::build_schema().finish();
 
@@ -60,11 +76,19 @@ fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
     // Health
     let health = warp::path("health").and_then(handler::health);
 
+    // Prometheus scrape endpoint
+    let metrics = warp::path("metrics").and_then(handler::metrics);
+
     // 404
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
-    // GraphQL query and subscription handler
-    let graphql_handler = warp::path("graphql").and(graphql_subscription(schema.clone()).or(
+    // GraphQL query and subscription handler. A `keepalive_secs` of 0 disables pings
+    let keepalive = Some(options.keepalive_secs)
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs);
+    let subscription =
+        websocket::graphql_subscription(schema.clone(), keepalive, options.max_connections);
+    let graphql_handler = warp::path("graphql").and(subscription.or(
         async_graphql_warp::graphql(schema).and_then(
             |(schema, request): (Schema<_, _, _>, Request)| async move {
                 Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
@@ -73,7 +97,7 @@ fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
     ));
 
     // GraphQL playground
-    let graphql_playground = if playground {
+    let graphql_playground = if options.playground {
         warp::path("playground")
             .map(move || {
                 Response::builder()
@@ -88,6 +112,7 @@ fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
     };
 
     health
+        .or(metrics)
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
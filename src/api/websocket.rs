@@ -0,0 +1,156 @@
+use super::{
+    bounded::{bounded, Item},
+    schema::{Mutation, Query, Subscription},
+};
+use crate::internal_events::{ApiConnectionRefused, ApiConnectionTimedOut};
+use async_graphql::{http::WebSocket as GraphQLWebSocket, Schema};
+use futures::{future, stream, StreamExt};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use warp::{
+    filters::BoxedFilter,
+    http::StatusCode,
+    ws::{Message, WebSocket, Ws},
+    Filter, Reply,
+};
+
+type ApiSchema = Schema<Query, Mutation, Subscription>;
+
+/// A message for the client, along with the subscription it's a result of, if any.
+struct Response {
+    text: String,
+    subscription: Option<String>,
+}
+
+impl Response {
+    fn new(text: String) -> Self {
+        let subscription = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .filter(|message| message["type"] == "data")
+            .and_then(|message| message["id"].as_str().map(Into::into));
+        Self { text, subscription }
+    }
+}
+
+impl Item for Response {
+    fn subscription(&self) -> Option<&str> {
+        self.subscription.as_deref()
+    }
+
+    /// Tells the client about the dropped results in the `extensions` of the result.
+    fn set_dropped(&mut self, dropped: usize) {
+        if let Ok(mut message) = serde_json::from_str::<serde_json::Value>(&self.text) {
+            message["payload"]["extensions"]["dropped"] = dropped.into();
+            self.text = message.to_string();
+        }
+    }
+}
+
+/// Counts an open connection, until dropped.
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl ConnectionGuard {
+    /// Returns `None` if `max_connections` are already open.
+    fn acquire(connections: &Arc<AtomicUsize>, max_connections: Option<usize>) -> Option<Self> {
+        let count = connections.fetch_add(1, Ordering::SeqCst);
+        let guard = Self(Arc::clone(connections));
+        match max_connections {
+            Some(max) if count >= max => None,
+            _ => Some(guard),
+        }
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// GraphQL subscriptions over websockets. Connections beyond `max_connections` are refused.
+/// With a `keepalive`, clients are pinged at that interval, and connections that haven't
+/// heard back from their client in two intervals are torn down, along with their
+/// subscriptions.
+pub fn graphql_subscription(
+    schema: ApiSchema,
+    keepalive: Option<Duration>,
+    max_connections: Option<usize>,
+) -> BoxedFilter<(Box<dyn Reply>,)> {
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    warp::ws()
+        .map(move |ws: Ws| -> Box<dyn Reply> {
+            let guard = match ConnectionGuard::acquire(&connections, max_connections) {
+                Some(guard) => guard,
+                None => {
+                    emit!(ApiConnectionRefused {
+                        max_connections: max_connections.unwrap_or_default(),
+                    });
+                    return Box::new(warp::reply::with_status(
+                        "Too many connections.",
+                        StatusCode::SERVICE_UNAVAILABLE,
+                    ));
+                }
+            };
+
+            let schema = schema.clone();
+            Box::new(warp::reply::with_header(
+                ws.on_upgrade(move |websocket| serve(websocket, schema, keepalive, guard)),
+                "Sec-WebSocket-Protocol",
+                "graphql-ws",
+            ))
+        })
+        .boxed()
+}
+
+async fn serve(
+    websocket: WebSocket,
+    schema: ApiSchema,
+    keepalive: Option<Duration>,
+    _guard: ConnectionGuard,
+) {
+    let (tx, rx) = websocket.split();
+
+    let last_seen = Arc::new(Mutex::new(Instant::now()));
+    let incoming = {
+        let last_seen = Arc::clone(&last_seen);
+        rx.take_while(|message| future::ready(message.is_ok()))
+            .map(Result::unwrap)
+            .inspect(move |_| *last_seen.lock().unwrap() = Instant::now())
+            .filter(|message| future::ready(message.is_text() || message.is_binary()))
+            .map(Message::into_bytes)
+    };
+    // Results are computed ahead of a slow client, which only gets the latest of them.
+    let responses = bounded(GraphQLWebSocket::new(schema, incoming).map(Response::new))
+        .map(|response| Message::text(response.text));
+
+    let outgoing = match keepalive {
+        Some(keepalive) => {
+            let pings = tokio::time::interval(keepalive).map(move |_| {
+                if last_seen.lock().unwrap().elapsed() > keepalive * 2 {
+                    emit!(ApiConnectionTimedOut { keepalive });
+                    None
+                } else {
+                    Some(Message::ping(Vec::new()))
+                }
+            });
+
+            // Ends once either the client goes away, or stops responding.
+            stream::select(
+                responses.map(Some).chain(stream::once(future::ready(None))),
+                pings,
+            )
+            .take_while(|message| future::ready(message.is_some()))
+            .map(|message| Ok(message.unwrap()))
+            .boxed()
+        }
+        None => responses.map(Ok).boxed(),
+    };
+
+    let _ = outgoing.forward(tx).await;
+}
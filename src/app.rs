@@ -2,15 +2,19 @@ use crate::cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubComm
 use crate::signal::SignalTo;
 use crate::topology::RunningTopology;
 use crate::{
-    config, generate, heartbeat, list, metrics, signal, topology, trace, unit_test, validate,
+    config, control, generate, graph, heartbeat, list, metrics, signal, topology, trace, unit_test,
+    validate,
 };
 use std::cmp::max;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use futures::StreamExt;
 use tokio::sync::mpsc;
 
+#[cfg(feature = "replay")]
+use crate::replay;
 #[cfg(feature = "sources-host_metrics")]
 use crate::sources::host_metrics;
 #[cfg(feature = "api-client")]
@@ -103,6 +107,8 @@ impl Application {
                 .expect("Unable to create async runtime")
         };
 
+        config::set_strict_env_vars(root_opts.strict_env_vars);
+
         let config = {
             let config_paths = root_opts.config_paths_with_formats();
             let watch_config = root_opts.watch_config;
@@ -113,7 +119,10 @@ impl Application {
                     let code = match s {
                         SubCommand::Validate(v) => validate::validate(&v, color).await,
                         SubCommand::List(l) => list::cmd(&l),
+                        SubCommand::Graph(g) => graph::cmd(&g),
                         SubCommand::Test(t) => unit_test::cmd(&t).await,
+                        #[cfg(feature = "replay")]
+                        SubCommand::Replay(r) => replay::cmd(&r).await,
                         SubCommand::Generate(g) => generate::cmd(&g),
                         #[cfg(feature = "api-client")]
                         SubCommand::Top(t) => top::cmd(&t).await,
@@ -131,15 +140,17 @@ impl Application {
                 #[cfg(feature = "sources-host_metrics")]
                 host_metrics::init_roots();
 
+                let watched_paths = config_paths.clone();
                 let config_paths = config::process_paths(&config_paths).ok_or(exitcode::CONFIG)?;
 
                 if watch_config {
-                    // Start listening for config changes immediately.
-                    config::watcher::spawn_thread(config_paths.iter().map(|(path, _)| path), None)
-                        .map_err(|error| {
-                            error!(message = "Unable to start config watcher.", %error);
-                            exitcode::CONFIG
-                        })?;
+                    // Start listening for config changes immediately. Directories are watched
+                    // instead of the files in them, so that files added to them are loaded.
+                    let watched_paths = config::watched_paths(&watched_paths, &config_paths);
+                    config::watcher::spawn_thread(&watched_paths, None).map_err(|error| {
+                        error!(message = "Unable to start config watcher.", %error);
+                        exitcode::CONFIG
+                    })?;
                 }
 
                 info!(
@@ -157,6 +168,15 @@ impl Application {
                 }
                 config.healthchecks.set_require_healthy(require_healthy);
 
+                // Global options can't change on reload, so the expiry is only set once.
+                if let Ok(controller) = metrics::get_controller() {
+                    let idle_timeout = config
+                        .global
+                        .expire_metrics_secs
+                        .map(Duration::from_secs_f64);
+                    controller.set_expiry(idle_timeout);
+                }
+
                 let diff = config::ConfigDiff::initial(&config);
                 let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
                     .await
@@ -205,6 +225,8 @@ impl Application {
         rt.block_on(async move {
             emit!(VectorStarted);
             tokio::spawn(heartbeat::heartbeat());
+            #[cfg(feature = "allocation-tracing")]
+            tokio::spawn(crate::allocations::report());
 
             #[cfg(feature = "api")]
             // assigned to prevent the API terminating when falling out of scope
@@ -220,6 +242,12 @@ impl Application {
                 None
             };
 
+            // assigned to prevent reporting stopping when falling out of scope
+            let control_plane = control::ControlPlane::start(topology.config()).unwrap_or_else(|error| {
+                error!(message = "Failed to start reporting to the control plane.", %error);
+                None
+            });
+
             let signals = signal::signals();
             tokio::pin!(signals);
             let mut sources_finished = topology.sources_finished();
@@ -230,26 +258,57 @@ impl Application {
                     if signal == SignalTo::Reload {
                         // Reload paths
                         config_paths = config::process_paths(&opts.config_paths_with_formats()).unwrap_or(config_paths);
-                        // Reload config
-                        let new_config = config::load_from_paths(&config_paths).map_err(handle_config_errors).ok();
+                        // Reload config, off the runtime as secrets may be retrieved by running commands.
+                        let paths = config_paths.clone();
+                        let new_config = tokio::task::spawn_blocking(move || config::load_from_paths(&paths))
+                            .await
+                            .expect("Loading the config panicked.")
+                            .map_err(handle_config_errors)
+                            .ok();
 
                         if let Some(mut new_config) = new_config {
                             new_config.healthchecks.set_require_healthy(opts.require_healthy);
+                            #[cfg(feature="api")]
+                            let diff = config::ConfigDiff::new(topology.config(), &new_config);
+                            #[cfg(feature="api")]
+                            let global_changed = topology.config().global != new_config.global;
                             match topology
                                 .reload_config_and_respawn(new_config)
                                 .await
                             {
                                 Ok(true) => {
+                                    if let Some(ref control_plane) = control_plane {
+                                        control_plane.update_config(topology.config());
+                                    }
+
                                     #[cfg(feature="api")]
                                     if let Some(ref api_server) = api_server {
-                                        api_server.update_config(topology.config())
+                                        api_server.update_config(topology.config());
+                                        api_server.config_reloaded(api::ReloadOutcome::Success, Some(&diff));
                                     }
 
                                     emit!(VectorReloaded { config_paths: &config_paths })
                                 },
-                                Ok(false) => emit!(VectorReloadFailed),
+                                Ok(false) => {
+                                    #[cfg(feature="api")]
+                                    if let Some(ref api_server) = api_server {
+                                        let outcome = if global_changed {
+                                            api::ReloadOutcome::Rejected
+                                        } else {
+                                            api::ReloadOutcome::RolledBack
+                                        };
+                                        api_server.config_reloaded(outcome, Some(&diff));
+                                    }
+
+                                    emit!(VectorReloadFailed)
+                                },
                                 // Trigger graceful shutdown for what remains of the topology
                                 Err(()) => {
+                                    #[cfg(feature="api")]
+                                    if let Some(ref api_server) = api_server {
+                                        api_server.config_reloaded(api::ReloadOutcome::RecoveryFailed, Some(&diff));
+                                    }
+
                                     emit!(VectorReloadFailed);
                                     emit!(VectorRecoveryFailed);
                                     break SignalTo::Shutdown;
@@ -257,6 +316,11 @@ impl Application {
                             }
                             sources_finished = topology.sources_finished();
                         } else {
+                            #[cfg(feature="api")]
+                            if let Some(ref api_server) = api_server {
+                                api_server.config_reloaded(api::ReloadOutcome::FailedValidation, None);
+                            }
+
                             emit!(VectorConfigLoadFailed);
                         }
                     } else {
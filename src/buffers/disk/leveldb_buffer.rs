@@ -25,7 +25,7 @@ use std::{
 };
 
 use super::{DataDirOpenError, Error};
-use crate::buffers::Acker;
+use crate::buffers::{Acker, BufferUsage};
 
 /// How much of disk buffer needs to be deleted before we trigger compaction.
 /// <0,1>
@@ -56,11 +56,22 @@ pub struct Writer {
     batch_size: usize,
     max_size: usize,
     current_size: Arc<AtomicUsize>,
+    current_events: Arc<AtomicUsize>,
 }
 
 // Writebatch isn't Send, but the leveldb docs explicitly say that it's okay to share across threads
 unsafe impl Send for Writer {}
 
+impl Writer {
+    pub fn usage(&self) -> BufferUsage {
+        BufferUsage::disk(
+            Arc::clone(&self.current_events),
+            Arc::clone(&self.current_size),
+            self.max_size,
+        )
+    }
+}
+
 impl Clone for Writer {
     fn clone(&self) -> Self {
         Self {
@@ -72,6 +83,7 @@ impl Clone for Writer {
             batch_size: 0,
             max_size: self.max_size,
             current_size: Arc::clone(&self.current_size),
+            current_events: Arc::clone(&self.current_events),
         }
     }
 }
@@ -106,6 +118,7 @@ impl Sink for Writer {
         }
 
         let key = self.offset.fetch_add(1, Ordering::Relaxed);
+        self.current_events.fetch_add(1, Ordering::Relaxed);
 
         self.writebatch.put(Key(key), &value);
         self.batch_size += 1;
@@ -158,6 +171,7 @@ pub struct Reader {
     write_notifier: Arc<AtomicTask>,
     blocked_write_tasks: Arc<Mutex<Vec<Task>>>,
     current_size: Arc<AtomicUsize>,
+    current_events: Arc<AtomicUsize>,
     ack_counter: Arc<AtomicUsize>,
     uncompacted_size: usize,
     unacked_sizes: VecDeque<usize>,
@@ -249,6 +263,8 @@ impl Reader {
 
             let size_deleted = self.unacked_sizes.drain(..num_to_delete).sum();
             self.current_size.fetch_sub(size_deleted, Ordering::Relaxed);
+            self.current_events
+                .fetch_sub(num_to_delete, Ordering::Relaxed);
 
             self.uncompacted_size += size_deleted;
             if self.uncompacted_size > self.max_uncompacted_size {
@@ -286,8 +302,9 @@ impl super::DiskBuffer for Buffer {
         let mut options = Options::new();
         options.create_if_missing = true;
 
-        let db: Database<Key> =
-            Database::open(&path, options).with_context(|| DataDirOpenError {
+        let db: Database<Key> = Database::open(&path, options)
+            .map_err(crate::Error::from)
+            .with_context(|| DataDirOpenError {
                 data_dir: path.parent().expect("always a parent"),
             })?;
         let db = Arc::new(db);
@@ -303,6 +320,7 @@ impl super::DiskBuffer for Buffer {
 
         let initial_size = db.value_iter(ReadOptions::new()).map(|v| v.len()).sum();
         let current_size = Arc::new(AtomicUsize::new(initial_size));
+        let current_events = Arc::new(AtomicUsize::new(tail - head));
 
         let write_notifier = Arc::new(AtomicTask::new());
 
@@ -320,6 +338,7 @@ impl super::DiskBuffer for Buffer {
             batch_size: 0,
             max_size,
             current_size: Arc::clone(&current_size),
+            current_events: Arc::clone(&current_events),
         };
 
         let mut reader = Reader {
@@ -329,6 +348,7 @@ impl super::DiskBuffer for Buffer {
             read_offset: head,
             delete_offset: head,
             current_size,
+            current_events,
             ack_counter,
             max_uncompacted_size,
             uncompacted_size: 1,
@@ -1,12 +1,14 @@
-#![cfg(feature = "leveldb")]
-
 use crate::event::Event;
-use futures01::{Async, AsyncSink, Poll, Sink, Stream};
+#[cfg(feature = "leveldb")]
+use futures01::{Async, AsyncSink, Poll};
+use futures01::{Sink, Stream};
 use snafu::Snafu;
 use std::io;
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "leveldb")]
 pub mod leveldb_buffer;
+pub mod segment_buffer;
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -22,8 +24,10 @@ pub enum Error {
     #[snafu(display("Unable to open data_dir {:?}", data_dir))]
     DataDirOpenError {
         data_dir: PathBuf,
-        source: leveldb::database::error::Error,
+        source: crate::Error,
     },
+    #[snafu(display("Unable to open disk buffer {:?}: {}", path, source))]
+    BufferOpenError { path: PathBuf, source: io::Error },
 }
 
 pub trait DiskBuffer {
@@ -36,11 +40,20 @@ pub trait DiskBuffer {
     ) -> Result<(Self::Writer, Self::Reader, super::Acker), Error>;
 }
 
+#[cfg(feature = "leveldb")]
 #[derive(Clone)]
 pub struct Writer {
     inner: leveldb_buffer::Writer,
 }
 
+#[cfg(feature = "leveldb")]
+impl Writer {
+    pub fn usage(&self) -> super::BufferUsage {
+        self.inner.usage()
+    }
+}
+
+#[cfg(feature = "leveldb")]
 impl Sink for Writer {
     type SinkItem = Event;
     type SinkError = ();
@@ -61,6 +74,7 @@ impl Sink for Writer {
     }
 }
 
+#[cfg(feature = "leveldb")]
 pub fn open(
     data_dir: &Path,
     name: &str,
@@ -73,9 +87,32 @@ pub fn open(
     ),
     Error,
 > {
-    let path = data_dir.join(name);
+    check_data_dir(data_dir)?;
+
+    let (writer, reader, acker) = leveldb_buffer::Buffer::build(data_dir.join(name), max_size)?;
+    Ok((Writer { inner: writer }, Box::new(reader), acker))
+}
 
-    // Check data dir
+/// Opens the segmented disk buffer, selected with `type = "disk_v2"`.
+pub fn open_segments(
+    data_dir: &Path,
+    name: &str,
+    max_size: usize,
+) -> Result<
+    (
+        segment_buffer::Writer,
+        Box<dyn Stream<Item = Event, Error = ()> + Send>,
+        super::Acker,
+    ),
+    Error,
+> {
+    check_data_dir(data_dir)?;
+
+    let (writer, reader, acker) = segment_buffer::Buffer::build(data_dir.join(name), max_size)?;
+    Ok((writer, Box::new(reader), acker))
+}
+
+fn check_data_dir(data_dir: &Path) -> Result<(), Error> {
     std::fs::metadata(&data_dir)
         .map_err(|e| match e.kind() {
             io::ErrorKind::PermissionDenied => Error::DataDirNotWritable {
@@ -97,8 +134,5 @@ pub fn open(
             } else {
                 Ok(())
             }
-        })?;
-
-    let (writer, reader, acker) = leveldb_buffer::Buffer::build(path, max_size)?;
-    Ok((Writer { inner: writer }, Box::new(reader), acker))
+        })
 }
@@ -0,0 +1,792 @@
+//! An append-only disk buffer, which stores events in a directory of segment files.
+//!
+//! Every record is an encoded event, preceded by its length and its CRC32 checksum, both as
+//! little endian `u32`s. The writer appends records to the newest segment until it reaches
+//! the segment size, and then starts a new segment. The reader reads the segments in order,
+//! and deletes a segment once all of its records have been read and acked. Segments are
+//! never rewritten, so there's no compaction, and on startup only the record headers are
+//! scanned to count the buffered events. The position of the oldest record not acked yet
+//! is persisted regularly, so that acked records aren't read again after a restart.
+
+use crate::{
+    buffers::{Acker, BufferUsage},
+    event::{proto, Event},
+};
+use bytes::Bytes;
+use futures01::{
+    task::{self, AtomicTask, Task},
+    Async, AsyncSink, Poll, Sink, Stream,
+};
+use prost::Message;
+use snafu::ResultExt;
+use std::{
+    collections::VecDeque,
+    convert::TryInto,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use super::{BufferOpenError, Error};
+
+/// The largest size of a segment file. Smaller buffers use segments of an eighth of their
+/// size, so that space is released in small steps as events are acked.
+const MAX_SEGMENT_SIZE: usize = 128 * 1024 * 1024;
+
+/// Length and checksum of a record.
+const HEADER_SIZE: usize = 8;
+
+/// Number of records written before they're flushed to the segment file.
+const FLUSH_EVENTS: usize = 100;
+
+const SEGMENT_EXTENSION: &str = "seg";
+
+/// File holding the segment and offset of the oldest record not acked yet, both as little
+/// endian `u64`s.
+const ACKED_FILE: &str = "acked";
+
+/// How often the acked position is persisted, at most.
+const PERSIST_ACKED_INTERVAL: Duration = Duration::from_secs(1);
+
+fn segment_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", id, SEGMENT_EXTENSION))
+}
+
+fn segment_id(path: &Path) -> Option<u64> {
+    if path.extension()? != SEGMENT_EXTENSION {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+/// Encodes a copy of the event, so that the event keeps its finalizers until it's either
+/// written or handed back to the sender.
+fn encode_record(event: &Event) -> Vec<u8> {
+    let mut payload = vec![];
+    proto::EventWrapper::from(event.clone())
+        .encode(&mut payload)
+        .unwrap(); // This will not error when writing to a Vec
+
+    let mut record = Vec::with_capacity(HEADER_SIZE + payload.len());
+    record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    record.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+    record.extend_from_slice(&payload);
+    record
+}
+
+fn decode_event(payload: Vec<u8>) -> Option<Event> {
+    match proto::EventWrapper::decode(Bytes::from(payload)) {
+        Ok(event) => Some(Event::from(event)),
+        Err(error) => {
+            error!(message = "Error deserializing proto.", %error);
+            None
+        }
+    }
+}
+
+/// Walks the record headers of a segment from `offset`, and returns the number of complete
+/// records and their total size.
+fn scan_segment(path: &Path, offset: u64) -> io::Result<(usize, usize)> {
+    let len = (fs::metadata(path)?.len()).saturating_sub(offset) as usize;
+    let mut file = BufReader::new(File::open(path)?);
+    file.seek(SeekFrom::Start(offset))?;
+    let (mut events, mut size) = (0, 0);
+
+    let mut header = [0; HEADER_SIZE];
+    while size + HEADER_SIZE <= len {
+        file.read_exact(&mut header)?;
+        let record_size =
+            HEADER_SIZE + u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+        if size + record_size > len {
+            break;
+        }
+        file.seek(SeekFrom::Current((record_size - HEADER_SIZE) as i64))?;
+        events += 1;
+        size += record_size;
+    }
+
+    Ok((events, size))
+}
+
+struct Segment {
+    id: u64,
+    /// Number and total size of the records of the segment that weren't read yet.
+    unread_events: usize,
+    unread_size: usize,
+}
+
+struct Segments {
+    /// The segments on disk, oldest first. Records are appended to the last one.
+    list: VecDeque<Segment>,
+    file: BufWriter<File>,
+    /// Bytes written to the last segment.
+    size: usize,
+}
+
+impl Segments {
+    fn get_mut(&mut self, id: u64) -> Option<&mut Segment> {
+        self.list.iter_mut().find(|segment| segment.id == id)
+    }
+}
+
+struct Shared {
+    dir: PathBuf,
+    segments: Mutex<Segments>,
+    write_notifier: Arc<AtomicTask>,
+    blocked_write_tasks: Mutex<Vec<Task>>,
+    max_size: usize,
+    max_segment_size: usize,
+    current_size: Arc<AtomicUsize>,
+    current_events: Arc<AtomicUsize>,
+}
+
+pub struct Writer {
+    shared: Arc<Shared>,
+    unflushed: usize,
+}
+
+impl Writer {
+    pub fn usage(&self) -> BufferUsage {
+        BufferUsage::disk(
+            Arc::clone(&self.shared.current_events),
+            Arc::clone(&self.shared.current_size),
+            self.shared.max_size,
+        )
+    }
+
+    fn write_record(&mut self, record: &[u8]) -> io::Result<()> {
+        let mut segments = self.shared.segments.lock().unwrap();
+
+        if segments.size > 0 && segments.size + record.len() > self.shared.max_segment_size {
+            // The reader moves on to the next segment once it's listed, so the current one
+            // has to be complete by then.
+            segments.file.flush()?;
+            let id = segments.list.back().expect("there's always a segment").id + 1;
+            segments.file = BufWriter::new(create_segment(&self.shared.dir, id)?);
+            segments.list.push_back(Segment {
+                id,
+                unread_events: 0,
+                unread_size: 0,
+            });
+            segments.size = 0;
+        }
+
+        segments.file.write_all(record)?;
+        segments.size += record.len();
+        let segment = segments.list.back_mut().expect("there's always a segment");
+        segment.unread_events += 1;
+        segment.unread_size += record.len();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.shared.segments.lock().unwrap().file.flush()?;
+        self.unflushed = 0;
+        self.shared.write_notifier.notify();
+        Ok(())
+    }
+}
+
+impl Clone for Writer {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            unflushed: 0,
+        }
+    }
+}
+
+impl Sink for Writer {
+    type SinkItem = Event;
+    type SinkError = ();
+
+    fn start_send(
+        &mut self,
+        event: Self::SinkItem,
+    ) -> Result<AsyncSink<Self::SinkItem>, Self::SinkError> {
+        let record = encode_record(&event);
+        let record_size = record.len();
+
+        if self
+            .shared
+            .current_size
+            .fetch_add(record_size, Ordering::Relaxed)
+            + record_size
+            > self.shared.max_size
+        {
+            self.shared
+                .blocked_write_tasks
+                .lock()
+                .unwrap()
+                .push(task::current());
+
+            self.shared
+                .current_size
+                .fetch_sub(record_size, Ordering::Relaxed);
+
+            self.poll_complete()?;
+
+            return Ok(AsyncSink::NotReady(event));
+        }
+
+        if let Err(error) = self.write_record(&record) {
+            error!(message = "Error writing to disk buffer.", %error);
+            self.shared
+                .current_size
+                .fetch_sub(record_size, Ordering::Relaxed);
+            return Err(());
+        }
+        self.shared.current_events.fetch_add(1, Ordering::Relaxed);
+
+        self.unflushed += 1;
+        if self.unflushed >= FLUSH_EVENTS {
+            self.poll_complete()?;
+        }
+
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Result<Async<()>, Self::SinkError> {
+        if self.unflushed > 0 {
+            self.flush().map_err(|error| {
+                error!(message = "Error flushing disk buffer.", %error);
+            })?;
+        }
+
+        Ok(Async::Ready(()))
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        if self.unflushed > 0 {
+            if let Err(error) = self.flush() {
+                error!(message = "Error flushing disk buffer.", %error);
+            }
+        }
+
+        // We need to wake up the reader so it can return None if there are no more writers
+        self.shared.write_notifier.notify();
+    }
+}
+
+enum ReadResult {
+    Record(Vec<u8>),
+    /// The rest of the record hasn't been written yet, or was cut off by a crash.
+    Incomplete,
+    Corrupted,
+}
+
+pub struct Reader {
+    shared: Arc<Shared>,
+    read_segment: u64,
+    file: Option<BufReader<File>>,
+    /// Position of the next record in the segment being read.
+    read_offset: u64,
+    ack_counter: Arc<AtomicUsize>,
+    /// Segment, offset and size of the records read but not acked yet, in order.
+    unacked: VecDeque<(u64, u64, usize)>,
+    /// The acked position last persisted, and when.
+    persisted: ((u64, u64), Instant),
+}
+
+impl Stream for Reader {
+    type Item = Event;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        self.delete_acked();
+
+        // If there's no record to read, we return NotReady and rely on Writer
+        // using write_notifier to wake this task up after the next flush.
+        self.shared.write_notifier.register();
+
+        loop {
+            let mut result = self.try_read_record()?;
+            if let ReadResult::Incomplete = result {
+                if self.is_sealed() {
+                    // Segments stop growing once a later one is listed, so whatever is
+                    // left of them has to be read before moving on.
+                    result = self.try_read_record()?;
+                    if let ReadResult::Incomplete = result {
+                        self.next_segment();
+                        continue;
+                    }
+                } else if Arc::strong_count(&self.shared) == 1 {
+                    // There are no writers left
+                    return Ok(Async::Ready(None));
+                } else {
+                    return Ok(Async::NotReady);
+                }
+            }
+
+            match result {
+                ReadResult::Record(payload) => {
+                    let size = HEADER_SIZE + payload.len();
+                    self.unacked
+                        .push_back((self.read_segment, self.read_offset, size));
+                    self.read_offset += size as u64;
+                    if let Some(segment) = self
+                        .shared
+                        .segments
+                        .lock()
+                        .unwrap()
+                        .get_mut(self.read_segment)
+                    {
+                        segment.unread_events -= 1;
+                        segment.unread_size -= size;
+                    }
+
+                    if let Some(event) = decode_event(payload) {
+                        return Ok(Async::Ready(Some(event)));
+                    }
+                    debug_assert!(false);
+                }
+                ReadResult::Corrupted => {
+                    error!(
+                        message = "Skipping the rest of a corrupted disk buffer segment.",
+                        segment = self.read_segment
+                    );
+                    if !self.next_segment() {
+                        return Ok(Async::NotReady);
+                    }
+                }
+                ReadResult::Incomplete => unreachable!(),
+            }
+        }
+    }
+}
+
+impl Reader {
+    /// Reads the next record. Errors reading the segment end the stream, leaving the
+    /// records on disk to be read once the buffer is opened again.
+    fn try_read_record(&mut self) -> Result<ReadResult, ()> {
+        self.read_record().map_err(|error| {
+            error!(
+                message = "Error reading disk buffer.",
+                segment = self.read_segment,
+                %error
+            );
+        })
+    }
+
+    fn read_record(&mut self) -> io::Result<ReadResult> {
+        if self.file.is_none() {
+            let path = segment_path(&self.shared.dir, self.read_segment);
+            let mut file = BufReader::new(File::open(path)?);
+            file.seek(SeekFrom::Start(self.read_offset))?;
+            self.file = Some(file);
+        }
+        let file = self.file.as_mut().unwrap();
+
+        let mut header = [0; HEADER_SIZE];
+        if !read_full(file, &mut header)? {
+            file.seek(SeekFrom::Start(self.read_offset))?;
+            return Ok(ReadResult::Incomplete);
+        }
+        let len = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+        let crc = u32::from_le_bytes(header[4..].try_into().unwrap());
+        if len > self.shared.max_size {
+            return Ok(ReadResult::Corrupted);
+        }
+
+        let mut payload = vec![0; len];
+        if !read_full(file, &mut payload)? {
+            file.seek(SeekFrom::Start(self.read_offset))?;
+            return Ok(ReadResult::Incomplete);
+        }
+        if crc32fast::hash(&payload) != crc {
+            return Ok(ReadResult::Corrupted);
+        }
+
+        Ok(ReadResult::Record(payload))
+    }
+
+    fn is_sealed(&self) -> bool {
+        let segments = self.shared.segments.lock().unwrap();
+        segments.list.back().map(|segment| segment.id) != Some(self.read_segment)
+    }
+
+    /// Moves on to the segment after the one being read, dropping the records left unread
+    /// in it. Returns `false` if the writer is still appending to the segment being read.
+    fn next_segment(&mut self) -> bool {
+        let mut segments = self.shared.segments.lock().unwrap();
+        let next = segments
+            .list
+            .iter()
+            .map(|segment| segment.id)
+            .find(|&id| id > self.read_segment);
+        match next {
+            Some(id) => {
+                if let Some(segment) = segments.get_mut(self.read_segment) {
+                    self.shared
+                        .current_events
+                        .fetch_sub(segment.unread_events, Ordering::Relaxed);
+                    self.shared
+                        .current_size
+                        .fetch_sub(segment.unread_size, Ordering::Relaxed);
+                    segment.unread_events = 0;
+                    segment.unread_size = 0;
+                }
+                self.read_segment = id;
+                self.read_offset = 0;
+                self.file = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn delete_acked(&mut self) {
+        let num_to_delete = self.ack_counter.swap(0, Ordering::Relaxed);
+
+        if num_to_delete > 0 {
+            assert!(
+                num_to_delete <= self.unacked.len(),
+                "Tried to ack beyond read offset"
+            );
+
+            let size_deleted = self
+                .unacked
+                .drain(..num_to_delete)
+                .map(|(_, _, size)| size)
+                .sum();
+            self.shared
+                .current_size
+                .fetch_sub(size_deleted, Ordering::Relaxed);
+            self.shared
+                .current_events
+                .fetch_sub(num_to_delete, Ordering::Relaxed);
+        }
+
+        if self.persisted.1.elapsed() >= PERSIST_ACKED_INTERVAL {
+            self.persist_acked();
+        }
+
+        // Segments before the oldest one that still has unacked records are done with.
+        let (oldest_needed, _) = self.acked_position();
+        let mut segments = self.shared.segments.lock().unwrap();
+        while segments.list.len() > 1 && segments.list[0].id < oldest_needed {
+            let id = segments.list.pop_front().unwrap().id;
+            if let Err(error) = fs::remove_file(segment_path(&self.shared.dir, id)) {
+                error!(message = "Error deleting disk buffer segment.", segment = id, %error);
+            }
+        }
+        drop(segments);
+
+        for task in self.shared.blocked_write_tasks.lock().unwrap().drain(..) {
+            task.notify();
+        }
+    }
+
+    /// The segment and offset of the oldest record not acked yet.
+    fn acked_position(&self) -> (u64, u64) {
+        self.unacked
+            .front()
+            .map(|&(segment, offset, _)| (segment, offset))
+            .unwrap_or((self.read_segment, self.read_offset))
+    }
+
+    fn persist_acked(&mut self) {
+        let position = self.acked_position();
+        if position != self.persisted.0 {
+            if let Err(error) = write_acked(&self.shared.dir, position) {
+                error!(message = "Error persisting disk buffer acked position.", %error);
+            }
+        }
+        self.persisted = (position, Instant::now());
+    }
+}
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        self.persist_acked();
+    }
+}
+
+fn write_acked(dir: &Path, (segment, offset): (u64, u64)) -> io::Result<()> {
+    let mut bytes = [0; 16];
+    bytes[..8].copy_from_slice(&segment.to_le_bytes());
+    bytes[8..].copy_from_slice(&offset.to_le_bytes());
+    // The position is replaced at once, so that it's never found half written.
+    let tmp_path = dir.join(format!("{}.tmp", ACKED_FILE));
+    fs::write(&tmp_path, bytes)?;
+    fs::rename(tmp_path, dir.join(ACKED_FILE))
+}
+
+fn read_acked(dir: &Path) -> io::Result<Option<(u64, u64)>> {
+    match fs::read(dir.join(ACKED_FILE)) {
+        Ok(bytes) if bytes.len() == 16 => Ok(Some((
+            u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            u64::from_le_bytes(bytes[8..].try_into().unwrap()),
+        ))),
+        Ok(_) => Ok(None),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Reads exactly `buf.len()` bytes, or returns `false` if the end of the file comes first.
+fn read_full(file: &mut impl Read, buf: &mut [u8]) -> io::Result<bool> {
+    match file.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+fn create_segment(dir: &Path, id: u64) -> io::Result<File> {
+    OpenOptions::new()
+        .create_new(true)
+        .append(true)
+        .open(segment_path(dir, id))
+}
+
+pub struct Buffer;
+
+impl super::DiskBuffer for Buffer {
+    type Writer = Writer;
+    type Reader = Reader;
+
+    fn build(path: PathBuf, max_size: usize) -> Result<(Self::Writer, Self::Reader, Acker), Error> {
+        let max_segment_size = (max_size / 8).min(MAX_SEGMENT_SIZE);
+
+        let (mut list, read_offset, current_events, current_size) =
+            recover(&path).context(BufferOpenError { path: &path })?;
+
+        // Records are always appended to a new segment, so that a record cut off by a
+        // crash is never followed by another one.
+        let next_id = list.back().map_or(0, |segment| segment.id + 1);
+        let file = create_segment(&path, next_id).context(BufferOpenError { path: &path })?;
+        let (read_segment, read_offset) = match list.front() {
+            Some(segment) => (segment.id, read_offset),
+            None => (next_id, 0),
+        };
+        list.push_back(Segment {
+            id: next_id,
+            unread_events: 0,
+            unread_size: 0,
+        });
+
+        let write_notifier = Arc::new(AtomicTask::new());
+        let ack_counter = Arc::new(AtomicUsize::new(0));
+        let acker = Acker::Disk(Arc::clone(&ack_counter), Arc::clone(&write_notifier));
+
+        let shared = Arc::new(Shared {
+            dir: path,
+            segments: Mutex::new(Segments {
+                list,
+                file: BufWriter::new(file),
+                size: 0,
+            }),
+            write_notifier,
+            blocked_write_tasks: Mutex::new(Vec::new()),
+            max_size,
+            max_segment_size,
+            current_size: Arc::new(AtomicUsize::new(current_size)),
+            current_events: Arc::new(AtomicUsize::new(current_events)),
+        });
+
+        let writer = Writer {
+            shared: Arc::clone(&shared),
+            unflushed: 0,
+        };
+        let reader = Reader {
+            shared,
+            read_segment,
+            file: None,
+            read_offset,
+            ack_counter,
+            unacked: VecDeque::new(),
+            persisted: ((read_segment, read_offset), Instant::now()),
+        };
+
+        Ok((writer, reader, acker))
+    }
+}
+
+/// Lists the segments left by a previous run, and counts the events they hold past the
+/// persisted acked position. Returns the segments, the offset to start reading the first
+/// one at, and the number and total size of the events.
+fn recover(path: &Path) -> io::Result<(VecDeque<Segment>, u64, usize, usize)> {
+    fs::create_dir_all(path)?;
+
+    let mut ids = Vec::new();
+    for entry in fs::read_dir(path)? {
+        if let Some(id) = segment_id(&entry?.path()) {
+            ids.push(id);
+        }
+    }
+    ids.sort_unstable();
+
+    // Segments before the acked one were done with, but may not have been deleted yet.
+    let (acked_segment, acked_offset) = read_acked(path)?.unwrap_or((0, 0));
+    let mut list = VecDeque::new();
+    for id in ids {
+        let segment_path = segment_path(path, id);
+        if id < acked_segment {
+            fs::remove_file(segment_path)?;
+            continue;
+        }
+        let offset = if id == acked_segment { acked_offset } else { 0 };
+        let (unread_events, unread_size) = scan_segment(&segment_path, offset)?;
+        list.push_back(Segment {
+            id,
+            unread_events,
+            unread_size,
+        });
+    }
+
+    let read_offset = match list.front() {
+        Some(segment) if segment.id == acked_segment => acked_offset,
+        _ => 0,
+    };
+    let events = list.iter().map(|segment| segment.unread_events).sum();
+    let size = list.iter().map(|segment| segment.unread_size).sum();
+
+    Ok((list, read_offset, events, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffers::disk::DiskBuffer,
+        event::{BatchNotifier, EventStatus},
+        test_util::temp_dir,
+    };
+    use futures::FutureExt;
+    use futures01::{future, stream, Future};
+
+    fn build(path: &Path, max_size: usize) -> (Writer, Reader, Acker) {
+        Buffer::build(path.to_path_buf(), max_size).unwrap()
+    }
+
+    fn events(range: std::ops::Range<usize>) -> Vec<Event> {
+        range
+            .map(|i| {
+                let mut event = Event::new_empty_log();
+                event.as_mut_log().insert("message", format!("event {}", i));
+                event
+            })
+            .collect()
+    }
+
+    fn write(writer: Writer, events: Vec<Event>) -> Writer {
+        writer
+            .send_all(stream::iter_ok::<_, ()>(events))
+            .wait()
+            .unwrap()
+            .0
+    }
+
+    /// Polls the reader from within a task, so that it can register for wakeups.
+    fn poll(reader: &mut Reader) -> Async<Option<Event>> {
+        future::lazy(|| reader.poll()).wait().unwrap()
+    }
+
+    fn read(reader: &mut Reader, count: usize) -> Vec<Event> {
+        (0..count)
+            .map(|_| match poll(reader) {
+                Async::Ready(Some(event)) => event,
+                other => panic!("Expected an event, got {:?}.", other),
+            })
+            .collect()
+    }
+
+    fn segment_count(path: &Path) -> usize {
+        fs::read_dir(path)
+            .unwrap()
+            .filter(|entry| segment_id(&entry.as_ref().unwrap().path()).is_some())
+            .count()
+    }
+
+    #[test]
+    fn reads_events_in_order_across_segments() {
+        let path = temp_dir();
+        let (writer, mut reader, acker) = build(&path, 10_000);
+
+        let writer = write(writer, events(0..100));
+        assert!(segment_count(&path) > 1);
+        assert_eq!(read(&mut reader, 100), events(0..100));
+
+        acker.ack(100);
+        drop(writer);
+        assert_eq!(poll(&mut reader), Async::Ready(None));
+        assert_eq!(segment_count(&path), 1);
+        assert_eq!(reader.shared.current_events.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn recovers_unacked_events() {
+        let path = temp_dir();
+        {
+            let (writer, mut reader, acker) = build(&path, 5_000);
+            let _writer = write(writer, events(0..100));
+            read(&mut reader, 50);
+            acker.ack(50);
+            assert_eq!(
+                poll(&mut reader),
+                Async::Ready(Some(events(50..51).remove(0)))
+            );
+        }
+
+        // The event read but not acked is read again, the acked ones aren't.
+        let (_writer, mut reader, _acker) = build(&path, 5_000);
+        assert_eq!(reader.shared.current_events.load(Ordering::Relaxed), 50);
+        assert_eq!(read(&mut reader, 50), events(50..100));
+    }
+
+    #[test]
+    fn keeps_finalizers_of_events_not_accepted_when_full() {
+        let path = temp_dir();
+        let (mut writer, _reader, _acker) = build(&path, 1);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let event = events(0..1).remove(0).with_batch_notifier(&batch);
+        drop(batch);
+
+        let mut event = match future::lazy(|| writer.start_send(event)).wait() {
+            Ok(AsyncSink::NotReady(event)) => event,
+            other => panic!("Expected the event back, got {:?}.", other),
+        };
+
+        // The batch isn't notified until the returned event is done with.
+        assert!((&mut receiver).now_or_never().is_none());
+        event.take_finalizers().update_status(EventStatus::Errored);
+        drop(event);
+        assert_eq!(receiver.now_or_never(), Some(EventStatus::Errored));
+    }
+
+    #[test]
+    fn skips_corrupted_segments() {
+        let path = temp_dir();
+        {
+            let (writer, _reader, _acker) = build(&path, 1_000_000);
+            let _writer = write(writer, events(0..10));
+        }
+
+        // Flip a byte of the first record's payload.
+        let first = segment_path(&path, 0);
+        let mut bytes = fs::read(&first).unwrap();
+        bytes[HEADER_SIZE] ^= 0xff;
+        fs::write(&first, bytes).unwrap();
+
+        let (writer, mut reader, acker) = build(&path, 1_000_000);
+        assert_eq!(reader.shared.current_events.load(Ordering::Relaxed), 10);
+        let writer = write(writer, events(10..11));
+        assert_eq!(read(&mut reader, 1), events(10..11));
+
+        // The events skipped with the corrupted segment are no longer buffered.
+        acker.ack(1);
+        drop(writer);
+        assert_eq!(poll(&mut reader), Async::Ready(None));
+        assert_eq!(reader.shared.current_events.load(Ordering::Relaxed), 0);
+        assert_eq!(reader.shared.current_size.load(Ordering::Relaxed), 0);
+    }
+}
@@ -1,24 +1,29 @@
-use crate::{config::Resource, internal_events::EventOut, Event};
-#[cfg(feature = "leveldb")]
+use crate::{
+    config::Resource,
+    event::{Event, EventFinalizers, EventStatus},
+    internal_events::EventOut,
+};
 use futures::compat::{Sink01CompatExt, Stream01CompatExt};
-use futures::{channel::mpsc, Sink, SinkExt, Stream};
+use futures::{channel::mpsc, future, Sink, SinkExt, Stream};
 use futures01::task::AtomicTask;
 use pin_project::pin_project;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::VecDeque,
     path::PathBuf,
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     task::{Context, Poll},
 };
-#[cfg(feature = "leveldb")]
 use tokio::stream::StreamExt;
 
-#[cfg(feature = "leveldb")]
 pub mod disk;
+mod usage;
+
+pub use usage::BufferUsage;
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
@@ -36,6 +41,11 @@ pub enum BufferConfig {
         #[serde(default)]
         when_full: WhenFull,
     },
+    DiskV2 {
+        max_size: usize,
+        #[serde(default)]
+        when_full: WhenFull,
+    },
 }
 
 impl Default for BufferConfig {
@@ -62,18 +72,33 @@ impl Default for WhenFull {
 
 #[derive(Clone)]
 pub enum BufferInputCloner {
-    Memory(mpsc::Sender<Event>, WhenFull),
+    /// Sinks' memory buffers track their usage, while transforms' input channels don't.
+    /// The last field tells whether events keep their finalizers. Sinks that don't
+    /// acknowledge events release them as soon as the events are buffered.
+    Memory(mpsc::Sender<Event>, WhenFull, Option<BufferUsage>, bool),
     #[cfg(feature = "leveldb")]
     Disk(disk::Writer, WhenFull),
+    DiskV2(disk::segment_buffer::Writer, WhenFull),
 }
 
 impl BufferInputCloner {
     pub fn get(&self) -> Box<dyn Sink<Event, Error = ()> + Send> {
         match self {
-            BufferInputCloner::Memory(tx, when_full) => {
+            BufferInputCloner::Memory(tx, when_full, usage, keep_finalizers) => {
+                let usage = usage.clone();
+                let keep_finalizers = *keep_finalizers;
                 let inner = tx
                     .clone()
-                    .sink_map_err(|error| error!(message = "Sender error.", %error));
+                    .sink_map_err(|error| error!(message = "Sender error.", %error))
+                    .with(move |mut event: Event| {
+                        if let Some(usage) = &usage {
+                            usage.increment();
+                        }
+                        if !keep_finalizers {
+                            drop(event.take_finalizers());
+                        }
+                        future::ready(Ok::<_, ()>(event))
+                    });
                 if when_full == &WhenFull::DropNewest {
                     Box::new(DropWhenFull::new(inner))
                 } else {
@@ -90,6 +115,15 @@ impl BufferInputCloner {
                     Box::new(inner)
                 }
             }
+
+            BufferInputCloner::DiskV2(writer, when_full) => {
+                let inner = writer.clone().sink_compat();
+                if when_full == &WhenFull::DropNewest {
+                    Box::new(DropWhenFull::new(inner))
+                } else {
+                    Box::new(inner)
+                }
+            }
         }
     }
 }
@@ -100,11 +134,14 @@ impl BufferConfig {
         500
     }
 
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
+    /// Builds the buffer of a sink. With `acknowledgements`, the finalizers of events are
+    /// held until the sink acks the events, so that sources learn once their events are
+    /// delivered. Disk buffers release the finalizers once the events are written to disk.
     pub fn build(
         &self,
         data_dir: &Option<PathBuf>,
         sink_name: &str,
+        acknowledgements: bool,
     ) -> Result<
         (
             BufferInputCloner,
@@ -119,9 +156,25 @@ impl BufferConfig {
                 when_full,
             } => {
                 let (tx, rx) = mpsc::channel(*max_events);
-                let tx = BufferInputCloner::Memory(tx, *when_full);
-                let rx = Box::new(rx);
-                Ok((tx, rx, Acker::Null))
+                let usage = BufferUsage::memory(*max_events);
+                let tx = BufferInputCloner::Memory(
+                    tx,
+                    *when_full,
+                    Some(usage.clone()),
+                    acknowledgements,
+                );
+                let rx = Box::new(usage.instrument(rx));
+                if acknowledgements {
+                    let pending = Arc::new(Mutex::new(VecDeque::new()));
+                    let acker = Acker::Finalizers(Arc::clone(&pending));
+                    let rx = Box::new(futures::StreamExt::map(rx, move |mut event| {
+                        pending.lock().unwrap().push_back(event.take_finalizers());
+                        event
+                    }));
+                    Ok((tx, rx, acker))
+                } else {
+                    Ok((tx, rx, Acker::Null))
+                }
             }
 
             #[cfg(feature = "leveldb")]
@@ -136,24 +189,49 @@ impl BufferConfig {
 
                 let (tx, rx, acker) = disk::open(&data_dir, buffer_dir.as_ref(), *max_size)
                     .map_err(|error| error.to_string())?;
+                let usage = tx.usage();
                 let tx = BufferInputCloner::Disk(tx, *when_full);
                 let rx = Box::new(
                     rx.compat()
                         .take_while(|event| event.is_ok())
                         .map(|event| event.unwrap()),
                 );
+                let rx = Box::new(usage.instrument(rx));
+                Ok((tx, rx, acker))
+            }
+
+            BufferConfig::DiskV2 {
+                max_size,
+                when_full,
+            } => {
+                let data_dir = data_dir
+                    .as_ref()
+                    .ok_or_else(|| "Must set data_dir to use on-disk buffering.".to_string())?;
+                let buffer_dir = format!("{}_buffer_v2", sink_name);
+
+                let (tx, rx, acker) =
+                    disk::open_segments(&data_dir, buffer_dir.as_ref(), *max_size)
+                        .map_err(|error| error.to_string())?;
+                let usage = tx.usage();
+                let tx = BufferInputCloner::DiskV2(tx, *when_full);
+                let rx = Box::new(
+                    rx.compat()
+                        .take_while(|event| event.is_ok())
+                        .map(|event| event.unwrap()),
+                );
+                let rx = Box::new(usage.instrument(rx));
                 Ok((tx, rx, acker))
             }
         }
     }
 
     /// Resources that the sink is using.
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
     pub fn resources(&self, sink_name: &str) -> Vec<Resource> {
         match self {
             BufferConfig::Memory { .. } => Vec::new(),
             #[cfg(feature = "leveldb")]
             BufferConfig::Disk { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
+            BufferConfig::DiskV2 { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
         }
     }
 }
@@ -161,6 +239,9 @@ impl BufferConfig {
 #[derive(Debug, Clone)]
 pub enum Acker {
     Disk(Arc<AtomicUsize>, Arc<AtomicTask>),
+    /// The finalizers of the events a sink has taken from its buffer, in order, which are
+    /// released once the sink acks the events.
+    Finalizers(Arc<Mutex<VecDeque<EventFinalizers>>>),
     Null,
 }
 
@@ -172,10 +253,24 @@ impl Acker {
     // This is primary used by the on-disk buffer to know which events are okay to
     // delete from disk.
     pub fn ack(&self, num: usize) {
+        self.ack_with_status(num, EventStatus::Delivered)
+    }
+
+    /// Acks the next `num` events like `ack`, telling their sources whether the events
+    /// were delivered. Events in disk buffers were already reported as delivered once
+    /// they were written to disk.
+    pub fn ack_with_status(&self, num: usize, status: EventStatus) {
         // Only ack items if the amount to ack is larger than zero.
         if num > 0 {
             match self {
                 Acker::Null => {}
+                Acker::Finalizers(pending) => {
+                    let mut pending = pending.lock().unwrap();
+                    let num = num.min(pending.len());
+                    for finalizers in pending.drain(..num) {
+                        finalizers.update_status(status);
+                    }
+                }
                 Acker::Disk(counter, notifier) => {
                     counter.fetch_add(num, Ordering::Relaxed);
                     notifier.notify();
@@ -207,7 +302,7 @@ impl<S> DropWhenFull<S> {
     }
 }
 
-impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
+impl<S: Sink<Event> + Unpin> Sink<Event> for DropWhenFull<S> {
     type Error = S::Error;
 
     fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
@@ -225,12 +320,14 @@ impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
         }
     }
 
-    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+    fn start_send(self: Pin<&mut Self>, mut item: Event) -> Result<(), Self::Error> {
         if self.drop {
             debug!(
                 message = "Shedding load; dropping event.",
                 internal_log_rate_secs = 10
             );
+            // The source is told the event won't ever be delivered.
+            item.take_finalizers().update_status(EventStatus::Failed);
             Ok(())
         } else {
             self.project().inner.start_send(item)
@@ -249,10 +346,14 @@ impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
 #[cfg(test)]
 mod test {
     use super::{Acker, BufferConfig, DropWhenFull, WhenFull};
-    use crate::sink::BoundedSink;
-    use futures::{future, Sink, Stream};
+    use crate::{
+        event::{BatchNotifier, Event, EventStatus},
+        sink::BoundedSink,
+    };
+    use futures::{future, FutureExt, Sink, SinkExt, Stream, StreamExt};
     use futures01::task::AtomicTask;
     use std::{
+        pin::Pin,
         sync::{atomic::AtomicUsize, Arc},
         task::Poll,
     };
@@ -263,28 +364,55 @@ mod test {
     async fn drop_when_full() {
         future::lazy(|cx| {
             let (tx, rx) = mpsc::channel(3);
+            let events = (1..=4)
+                .map(|n| Event::from(n.to_string()))
+                .collect::<Vec<_>>();
 
             let mut tx = Box::pin(DropWhenFull::new(BoundedSink::new(tx)));
 
-            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
-            assert_eq!(tx.as_mut().start_send(1), Ok(()));
-            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
-            assert_eq!(tx.as_mut().start_send(2), Ok(()));
-            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
-            assert_eq!(tx.as_mut().start_send(3), Ok(()));
-            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
-            assert_eq!(tx.as_mut().start_send(4), Ok(()));
+            for event in &events {
+                assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+                assert_eq!(tx.as_mut().start_send(event.clone()), Ok(()));
+            }
 
             let mut rx = Box::pin(rx);
 
-            assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(Some(1)));
-            assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(Some(2)));
-            assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(Some(3)));
+            assert_eq!(
+                rx.as_mut().poll_next(cx),
+                Poll::Ready(Some(events[0].clone()))
+            );
+            assert_eq!(
+                rx.as_mut().poll_next(cx),
+                Poll::Ready(Some(events[1].clone()))
+            );
+            assert_eq!(
+                rx.as_mut().poll_next(cx),
+                Poll::Ready(Some(events[2].clone()))
+            );
             assert_eq!(rx.as_mut().poll_next(cx), Poll::Pending);
         })
         .await;
     }
 
+    #[tokio::test]
+    async fn drop_when_full_fails_dropped_events() {
+        let (tx, _rx) = mpsc::channel(1);
+        let mut tx = Box::pin(DropWhenFull::new(BoundedSink::new(tx)));
+
+        let (first, first_receiver) = BatchNotifier::new_with_receiver();
+        let (second, second_receiver) = BatchNotifier::new_with_receiver();
+        tx.send(Event::from("first").with_batch_notifier(&first))
+            .await
+            .unwrap();
+        tx.send(Event::from("second").with_batch_notifier(&second))
+            .await
+            .unwrap();
+        drop((first, second));
+
+        assert_eq!(second_receiver.await, EventStatus::Failed);
+        assert!(first_receiver.now_or_never().is_none());
+    }
+
     #[test]
     fn ack_with_none() {
         let counter = Arc::new(AtomicUsize::new(0));
@@ -302,6 +430,56 @@ mod test {
         assert!(mock.is_notified());
     }
 
+    #[tokio::test]
+    async fn memory_buffer_holds_finalizers_until_acked() {
+        let buffer = BufferConfig::default();
+        let (tx, rx, acker) = buffer.build(&None, "sink", true).unwrap();
+        let (mut tx, mut rx) = (Pin::from(tx.get()), Pin::from(rx));
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from("message").with_batch_notifier(&batch);
+        drop(batch);
+        tx.send(event).await.unwrap();
+
+        drop(rx.next().await.unwrap());
+        assert!((&mut receiver).now_or_never().is_none());
+
+        acker.ack(1);
+        assert_eq!(receiver.await, EventStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn memory_buffer_reports_the_status_of_acked_events() {
+        let buffer = BufferConfig::default();
+        let (tx, rx, acker) = buffer.build(&None, "sink", true).unwrap();
+        let (mut tx, mut rx) = (Pin::from(tx.get()), Pin::from(rx));
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        tx.send(Event::from("message").with_batch_notifier(&batch))
+            .await
+            .unwrap();
+        drop(batch);
+
+        drop(rx.next().await.unwrap());
+        acker.ack_with_status(1, EventStatus::Errored);
+        assert_eq!(receiver.await, EventStatus::Errored);
+    }
+
+    #[tokio::test]
+    async fn memory_buffer_releases_finalizers_without_acknowledgements() {
+        let buffer = BufferConfig::default();
+        let (tx, _rx, _acker) = buffer.build(&None, "sink", false).unwrap();
+        let mut tx = Pin::from(tx.get());
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        tx.send(Event::from("message").with_batch_notifier(&batch))
+            .await
+            .unwrap();
+        drop(batch);
+
+        assert_eq!(receiver.await, EventStatus::Delivered);
+    }
+
     #[test]
     fn config_default_values() {
         fn check(source: &str, config: BufferConfig) {
@@ -352,5 +530,16 @@ mod test {
                 when_full: WhenFull::Block,
             },
         );
+
+        check(
+            r#"
+          type = "disk_v2"
+          max_size = 1024
+          "#,
+            BufferConfig::DiskV2 {
+                max_size: 1024,
+                when_full: WhenFull::Block,
+            },
+        );
     }
 }
@@ -0,0 +1,123 @@
+use crate::{internal_events::BufferUsageReport, Event};
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Tracks how much of a sink's buffer is in use.
+#[derive(Clone, Debug)]
+pub enum BufferUsage {
+    /// Events are counted as they're sent into, and read out of, the buffer.
+    Memory {
+        events: Arc<AtomicUsize>,
+        max_events: usize,
+    },
+    /// Events stay in a disk buffer until they're acknowledged, so the buffer
+    /// itself keeps these counts up to date.
+    Disk {
+        events: Arc<AtomicUsize>,
+        byte_size: Arc<AtomicUsize>,
+        max_size: usize,
+    },
+}
+
+impl BufferUsage {
+    pub fn memory(max_events: usize) -> Self {
+        BufferUsage::Memory {
+            events: Arc::new(AtomicUsize::new(0)),
+            max_events,
+        }
+    }
+
+    pub fn disk(events: Arc<AtomicUsize>, byte_size: Arc<AtomicUsize>, max_size: usize) -> Self {
+        BufferUsage::Disk {
+            events,
+            byte_size,
+            max_size,
+        }
+    }
+
+    /// Counts an event sent into a memory buffer.
+    pub fn increment(&self) {
+        if let BufferUsage::Memory { events, .. } = self {
+            events.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn decrement(&self) {
+        if let BufferUsage::Memory { events, .. } = self {
+            events.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    fn report(&self) {
+        match self {
+            BufferUsage::Memory { events, max_events } => emit!(BufferUsageReport {
+                events: events.load(Ordering::Relaxed),
+                byte_size: None,
+                max_size: *max_events,
+            }),
+            BufferUsage::Disk {
+                events,
+                byte_size,
+                max_size,
+            } => emit!(BufferUsageReport {
+                events: events.load(Ordering::Relaxed),
+                byte_size: Some(byte_size.load(Ordering::Relaxed)),
+                max_size: *max_size,
+            }),
+        }
+    }
+
+    /// Wraps the reading end of a buffer, so that its usage is reported from the
+    /// task consuming it, and so attributed to that sink.
+    pub fn instrument(self, inner: impl Stream<Item = Event> + Unpin) -> impl Stream<Item = Event> {
+        stream! {
+            let mut inner = inner;
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            loop {
+                let value = tokio::select! {
+                    value = inner.next() => value,
+                    _ = interval.tick() => {
+                        self.report();
+                        continue
+                    }
+                };
+                if let Some(value) = value {
+                    self.decrement();
+                    yield value
+                } else {
+                    break
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    #[tokio::test]
+    async fn memory_usage_counts_unread_events() {
+        let usage = BufferUsage::memory(10);
+        for _ in 0..3 {
+            usage.increment();
+        }
+
+        let events = stream::iter(vec![Event::from("one"), Event::from("two")]);
+        let read = usage.clone().instrument(events).collect::<Vec<_>>().await;
+        assert_eq!(read.len(), 2);
+
+        match usage {
+            BufferUsage::Memory { events, .. } => assert_eq!(events.load(Ordering::Relaxed), 1),
+            BufferUsage::Disk { .. } => unreachable!(),
+        }
+    }
+}
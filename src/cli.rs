@@ -1,7 +1,9 @@
-use crate::{config, generate, get_version, list, unit_test, validate};
+use crate::{config, generate, get_version, graph, list, unit_test, validate};
 use std::path::PathBuf;
 use structopt::{clap::AppSettings, StructOpt};
 
+#[cfg(feature = "replay")]
+use crate::replay;
 #[cfg(feature = "api-client")]
 use crate::top;
 
@@ -33,6 +35,7 @@ impl Opts {
         let (quiet_level, verbose_level) = match self.sub_command {
             Some(SubCommand::Validate(_))
             | Some(SubCommand::Generate(_))
+            | Some(SubCommand::Graph(_))
             | Some(SubCommand::List(_)) => {
                 if self.root.verbose == 0 {
                     (self.root.quiet + 1, self.root.verbose)
@@ -80,6 +83,12 @@ pub struct RootOpts {
     #[structopt(name = "config-yaml", long, env = "VECTOR_CONFIG_YAML")]
     pub config_paths_yaml: Vec<PathBuf>,
 
+    /// Read configuration from the files in one or more directories, such as
+    /// `/etc/vector/conf.d`. File format is detected from the file name, and
+    /// files with other extensions are ignored.
+    #[structopt(name = "config-dir", short = "C", long, env = "VECTOR_CONFIG_DIR")]
+    pub config_dirs: Vec<PathBuf>,
+
     /// Exit on startup if any sinks fail healthchecks
     #[structopt(short, long, env = "VECTOR_REQUIRE_HEALTHY")]
     pub require_healthy: Option<bool>,
@@ -110,6 +119,11 @@ pub struct RootOpts {
     #[structopt(long, default_value = "auto", possible_values = &["auto", "always", "never"])]
     pub color: Color,
 
+    /// Fail to load configuration referencing unset environment variables,
+    /// instead of replacing them with an empty string.
+    #[structopt(long, env = "VECTOR_STRICT_ENV_VARS")]
+    pub strict_env_vars: bool,
+
     /// Watch for changes in configuration file, and reload accordingly.
     #[structopt(short, long, env = "VECTOR_WATCH_CONFIG")]
     pub watch_config: bool,
@@ -123,6 +137,7 @@ impl RootOpts {
             (&self.config_paths_toml, Some(config::Format::TOML)),
             (&self.config_paths_json, Some(config::Format::JSON)),
             (&self.config_paths_yaml, Some(config::Format::YAML)),
+            (&self.config_dirs, None),
         ])
     }
 }
@@ -139,10 +154,17 @@ pub enum SubCommand {
     /// List available components, then exit.
     List(list::Opts),
 
+    /// Print the topology of the target config in the DOT language of Graphviz, then exit.
+    Graph(graph::Opts),
+
     /// Run Vector config unit tests, then exit. This command is experimental and therefore subject to change.
     /// For guidance on how to write unit tests check out: https://vector.dev/docs/setup/guides/unit-testing/
     Test(unit_test::Opts),
 
+    /// Replay files written by the `file` or `aws_s3` sinks through a Vector config, then exit.
+    #[cfg(feature = "replay")]
+    Replay(replay::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -0,0 +1,163 @@
+use crate::{
+    config::log_schema,
+    event::{Event, Value},
+};
+use bytes::Bytes;
+use chrono::{Datelike, Utc};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use snafu::{ResultExt, Snafu};
+use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
+
+#[derive(Debug, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("Invalid JSON: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("Expected a JSON object, got {}", kind))]
+    NotAnObject { kind: &'static str },
+    #[snafu(display("Invalid UTF-8: {}", source))]
+    InvalidUtf8 { source: std::str::Utf8Error },
+}
+
+/// How each frame received by a source is turned into an event.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Derivative)]
+#[serde(tag = "codec", rename_all = "snake_case", deny_unknown_fields)]
+#[derivative(Default)]
+pub enum DecodingConfig {
+    /// The frame is the `message` of the event.
+    #[derivative(Default)]
+    Bytes,
+    /// The frame is a JSON object, whose fields are the fields of the event.
+    Json,
+    /// The frame is a syslog message, parsed into the fields of the event.
+    Syslog,
+}
+
+impl DecodingConfig {
+    pub fn decode(self, frame: Bytes) -> Result<Event, DecodeError> {
+        match self {
+            Self::Bytes => Ok(Event::from(frame)),
+            Self::Json => match serde_json::from_slice(&frame).context(InvalidJson)? {
+                JsonValue::Object(fields) => {
+                    let mut event = Event::new_empty_log();
+                    let log = event.as_mut_log();
+                    for (key, value) in fields {
+                        log.insert_flat(key, value);
+                    }
+                    log.try_insert(log_schema().timestamp_key(), Utc::now());
+                    Ok(event)
+                }
+                value => NotAnObject {
+                    kind: json_kind(&value),
+                }
+                .fail(),
+            },
+            Self::Syslog => {
+                let line = std::str::from_utf8(&frame).context(InvalidUtf8)?;
+                let parsed = syslog_loose::parse_message_with_year(line.trim(), resolve_year);
+
+                let mut event = Event::from(parsed.msg);
+                let timestamp = parsed.timestamp.map(Into::into).unwrap_or_else(Utc::now);
+                event
+                    .as_mut_log()
+                    .insert(log_schema().timestamp_key(), timestamp);
+                insert_fields_from_syslog(&mut event, parsed);
+                Ok(event)
+            }
+        }
+    }
+}
+
+fn json_kind(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Object(_) => "an object",
+        JsonValue::Array(_) => "an array",
+        JsonValue::String(_) => "a string",
+        JsonValue::Number(_) => "a number",
+        JsonValue::Bool(_) => "a boolean",
+        JsonValue::Null => "null",
+    }
+}
+
+/// Function used to resolve the year for syslog messages that don't include the year.
+/// If the current month is January, and the syslog message is for December, it will take the previous year.
+/// Otherwise, take the current year.
+pub(crate) fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
+    let now = Utc::now();
+    if now.month() == 1 && month == 12 {
+        now.year() - 1
+    } else {
+        now.year()
+    }
+}
+
+pub(crate) fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
+    let log = event.as_mut_log();
+
+    if let Some(host) = parsed.hostname {
+        log.insert("hostname", host.to_string());
+    }
+    if let Some(severity) = parsed.severity {
+        log.insert("severity", severity.as_str().to_owned());
+    }
+    if let Some(facility) = parsed.facility {
+        log.insert("facility", facility.as_str().to_owned());
+    }
+    if let Protocol::RFC5424(version) = parsed.protocol {
+        log.insert("version", version as i64);
+    }
+    if let Some(app_name) = parsed.appname {
+        log.insert("appname", app_name.to_owned());
+    }
+    if let Some(msg_id) = parsed.msgid {
+        log.insert("msgid", msg_id.to_owned());
+    }
+    if let Some(procid) = parsed.procid {
+        let value: Value = match procid {
+            ProcId::PID(pid) => pid.into(),
+            ProcId::Name(name) => name.to_string().into(),
+        };
+        log.insert("procid", value);
+    }
+
+    for element in parsed.structured_data.into_iter() {
+        for (name, value) in element.params.into_iter() {
+            let key = format!("{}.{}", element.id, name);
+            log.insert(key, value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_json_objects() {
+        let event = DecodingConfig::Json
+            .decode(Bytes::from(r#"{"message": "hello", "status": 200}"#))
+            .unwrap();
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log["status"], 200.into());
+        assert!(log.contains(log_schema().timestamp_key()));
+
+        assert!(DecodingConfig::Json.decode(Bytes::from("[1]")).is_err());
+        assert!(DecodingConfig::Json.decode(Bytes::from("{")).is_err());
+    }
+
+    #[test]
+    fn decodes_syslog_messages() {
+        let event = DecodingConfig::Syslog
+            .decode(Bytes::from(
+                "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - hello",
+            ))
+            .unwrap();
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log["hostname"], "mymachine.example.com".into());
+        assert_eq!(log["appname"], "su".into());
+        assert_eq!(log["severity"], "crit".into());
+    }
+}
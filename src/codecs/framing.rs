@@ -0,0 +1,278 @@
+use bytes::{Buf, Bytes, BytesMut};
+use codec::BytesDelimitedCodec;
+use serde::{Deserialize, Serialize};
+use std::io;
+use tokio_util::codec::{Decoder, LengthDelimitedCodec};
+
+/// How the bytes received by a source are split into frames.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case", deny_unknown_fields)]
+pub enum FramingConfig {
+    /// The whole input is a single frame, such as a datagram or a request body.
+    Bytes,
+    /// Frames are separated by newlines.
+    NewlineDelimited,
+    /// Frames are prefixed with their length, as a 4 byte big-endian integer.
+    LengthDelimited,
+    /// Frames are prefixed with their length in ASCII digits and a space, as in
+    /// https://tools.ietf.org/html/rfc6587#section-3.4.1. Frames without a length
+    /// are separated by newlines.
+    OctetCounting,
+}
+
+impl FramingConfig {
+    /// Builds the framer, which skips or rejects frames longer than `max_length`.
+    pub fn build(self, max_length: usize) -> Framer {
+        match self {
+            Self::Bytes => Framer::Bytes,
+            Self::NewlineDelimited => Framer::NewlineDelimited {
+                codec: BytesDelimitedCodec::new_with_max_length(b'\n', max_length),
+                strip_carriage_returns: false,
+            },
+            Self::LengthDelimited => Framer::LengthDelimited(
+                LengthDelimitedCodec::builder()
+                    .max_frame_length(max_length)
+                    .new_codec(),
+            ),
+            Self::OctetCounting => Framer::OctetCounting(OctetCountingDecoder::new(max_length)),
+        }
+    }
+}
+
+/// Splits bytes into frames, as set by a `FramingConfig`.
+#[derive(Debug, Clone)]
+pub enum Framer {
+    Bytes,
+    NewlineDelimited {
+        codec: BytesDelimitedCodec,
+        strip_carriage_returns: bool,
+    },
+    LengthDelimited(LengthDelimitedCodec),
+    OctetCounting(OctetCountingDecoder),
+}
+
+impl Framer {
+    /// Strips the `\r` of newline delimited frames ending with `\r\n`, as lines of text do.
+    pub fn strip_carriage_returns(mut self) -> Self {
+        if let Self::NewlineDelimited {
+            strip_carriage_returns,
+            ..
+        } = &mut self
+        {
+            *strip_carriage_returns = true;
+        }
+        self
+    }
+}
+
+fn strip_carriage_return(frame: Option<Bytes>, strip: bool) -> Option<Bytes> {
+    frame.map(|mut frame| {
+        if strip && frame.ends_with(b"\r") {
+            frame.truncate(frame.len() - 1);
+        }
+        frame
+    })
+}
+
+impl Decoder for Framer {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self {
+            // The frame only ends with the input.
+            Self::Bytes => Ok(None),
+            Self::NewlineDelimited {
+                codec,
+                strip_carriage_returns,
+            } => Ok(strip_carriage_return(
+                codec.decode(src)?,
+                *strip_carriage_returns,
+            )),
+            Self::LengthDelimited(decoder) => Ok(decoder.decode(src)?.map(BytesMut::freeze)),
+            Self::OctetCounting(decoder) => decoder.decode(src),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self {
+            Self::Bytes if src.is_empty() => Ok(None),
+            Self::Bytes => Ok(Some(src.split().freeze())),
+            Self::NewlineDelimited {
+                codec,
+                strip_carriage_returns,
+            } => Ok(strip_carriage_return(
+                codec.decode_eof(src)?,
+                *strip_carriage_returns,
+            )),
+            Self::LengthDelimited(decoder) => Ok(decoder.decode_eof(src)?.map(BytesMut::freeze)),
+            Self::OctetCounting(decoder) => decoder.decode_eof(src),
+        }
+    }
+}
+
+/// Decodes frames prefixed with their length, as in
+/// https://tools.ietf.org/html/rfc6587#section-3.4.1, and falls back to newlines for frames
+/// that don't start with a length.
+#[derive(Debug, Clone)]
+pub struct OctetCountingDecoder {
+    newlines: BytesDelimitedCodec,
+    max_length: usize,
+}
+
+impl OctetCountingDecoder {
+    pub fn new(max_length: usize) -> Self {
+        Self {
+            newlines: BytesDelimitedCodec::new_with_max_length(b'\n', max_length),
+            max_length,
+        }
+    }
+
+    /// Returns `None` if the frame doesn't start with a length, that is digits followed by
+    /// a space, such as a newline delimited frame starting with a date.
+    fn octet_decode(&self, src: &mut BytesMut, eof: bool) -> Option<io::Result<Option<Bytes>>> {
+        // A non zero digit starts the length.
+        if !matches!(src.first(), Some(b'1'..=b'9')) {
+            return None;
+        }
+
+        let digits = src.iter().take_while(|b| b.is_ascii_digit()).count();
+        let result = match src.get(digits) {
+            Some(b' ') => std::str::from_utf8(&src[..digits])
+                .ok()
+                .and_then(|len| len.parse::<usize>().ok())
+                .filter(|&len| len <= self.max_length)
+                .ok_or_else(|| invalid_data("Frame length limit exceeded."))
+                .and_then(|len| {
+                    let from = digits + 1;
+                    if src.len() < from + len {
+                        return match eof {
+                            true => Err(invalid_data("Frame is incomplete.")),
+                            false => Ok(None),
+                        };
+                    }
+                    src.advance(from);
+                    Ok(Some(src.split_to(len).freeze()))
+                }),
+            Some(_) => return None,
+            // The length may not be complete yet.
+            None if !eof && digits <= self.max_length.to_string().len() => Ok(None),
+            None => return None,
+        };
+        Some(result)
+    }
+}
+
+impl Decoder for OctetCountingDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.octet_decode(src, false) {
+            Some(result) => result,
+            None => self.newlines.decode(src),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.octet_decode(src, true) {
+            Some(result) => result,
+            None => self.newlines.decode_eof(src),
+        }
+    }
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(framing: FramingConfig, input: &[u8]) -> Vec<Bytes> {
+        let mut framer = framing.build(64);
+        let mut src = BytesMut::new();
+        let mut frames = Vec::new();
+
+        // Feed the input in small chunks, as it would arrive over a connection.
+        for chunk in input.chunks(3) {
+            src.extend_from_slice(chunk);
+            while let Some(frame) = framer.decode(&mut src).unwrap() {
+                frames.push(frame);
+            }
+        }
+        while let Some(frame) = framer.decode_eof(&mut src).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn splits_frames() {
+        assert_eq!(
+            frames(FramingConfig::Bytes, b"first\nsecond"),
+            vec![Bytes::from("first\nsecond")]
+        );
+        assert_eq!(
+            frames(FramingConfig::NewlineDelimited, b"first\nsecond"),
+            vec![Bytes::from("first"), Bytes::from("second")]
+        );
+        assert_eq!(
+            frames(
+                FramingConfig::LengthDelimited,
+                b"\0\0\0\x06first\n\0\0\0\x06second"
+            ),
+            vec![Bytes::from("first\n"), Bytes::from("second")]
+        );
+        assert_eq!(
+            frames(FramingConfig::OctetCounting, b"6 first\n7 second\nthird\n"),
+            vec![
+                Bytes::from("first\n"),
+                Bytes::from("second\n"),
+                Bytes::from("third")
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_newlines_for_frames_starting_with_digits() {
+        assert_eq!(
+            frames(
+                FramingConfig::OctetCounting,
+                b"2021-03-04 first\n5 third404\n"
+            ),
+            vec![
+                Bytes::from("2021-03-04 first"),
+                Bytes::from("third"),
+                Bytes::from("404")
+            ]
+        );
+    }
+
+    #[test]
+    fn strips_carriage_returns_of_lines() {
+        let mut framer = FramingConfig::NewlineDelimited
+            .build(64)
+            .strip_carriage_returns();
+        let mut src = BytesMut::from("first\r\nsecond\r");
+        assert_eq!(framer.decode(&mut src).unwrap(), Some(Bytes::from("first")));
+        assert_eq!(
+            framer.decode_eof(&mut src).unwrap(),
+            Some(Bytes::from("second"))
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_octet_counted_frames() {
+        let mut framer = FramingConfig::OctetCounting.build(64);
+        let mut src = BytesMut::from("65 ");
+        assert!(framer.decode(&mut src).is_err());
+    }
+
+    #[test]
+    fn deserializes_config() {
+        let framing: FramingConfig = toml::from_str(r#"method = "octet_counting""#).unwrap();
+        assert_eq!(framing, FramingConfig::OctetCounting);
+    }
+}
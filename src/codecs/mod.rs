@@ -0,0 +1,89 @@
+//! Decoding of the bytes received by sources into events.
+//!
+//! The bytes are first split into frames, as set by a source's `framing` option, and each
+//! frame is then decoded into an event, as set by its `decoding` option.
+
+mod decoding;
+mod framing;
+
+pub(crate) use decoding::{insert_fields_from_syslog, resolve_year};
+pub use decoding::{DecodeError, DecodingConfig};
+pub use framing::{Framer, FramingConfig, OctetCountingDecoder};
+
+use crate::{emit, event::Event, internal_events::DecoderDeserializeFailed};
+use bytes::BytesMut;
+use std::io;
+
+/// Splits the bytes received by a source into frames, and decodes each frame into an event,
+/// along with the size of the frame. Frames that can't be decoded are skipped.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    framer: Framer,
+    decoding: DecodingConfig,
+}
+
+impl Decoder {
+    pub fn new(framer: Framer, decoding: DecodingConfig) -> Self {
+        Self { framer, decoding }
+    }
+
+    fn decode_frames(
+        &mut self,
+        src: &mut BytesMut,
+        eof: bool,
+    ) -> Result<Option<(Event, usize)>, io::Error> {
+        loop {
+            let frame = if eof {
+                tokio_util::codec::Decoder::decode_eof(&mut self.framer, src)?
+            } else {
+                tokio_util::codec::Decoder::decode(&mut self.framer, src)?
+            };
+
+            let frame = match frame {
+                Some(frame) => frame,
+                None => return Ok(None),
+            };
+            let byte_size = frame.len();
+            match self.decoding.decode(frame) {
+                Ok(event) => return Ok(Some((event, byte_size))),
+                Err(error) => emit!(DecoderDeserializeFailed { error: &error }),
+            }
+        }
+    }
+}
+
+impl tokio_util::codec::Decoder for Decoder {
+    type Item = (Event, usize);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_frames(src, false)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_frames(src, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::log_schema;
+    use tokio_util::codec::Decoder as _;
+
+    #[test]
+    fn skips_frames_that_fail_to_decode() {
+        let mut decoder = Decoder::new(
+            FramingConfig::NewlineDelimited.build(1024),
+            DecodingConfig::Json,
+        );
+        let mut src =
+            BytesMut::from("{\"message\": \"first\"}\nnot json\n{\"message\": \"third\"}");
+
+        let mut messages = Vec::new();
+        while let Some((event, _)) = decoder.decode_eof(&mut src).unwrap() {
+            messages.push(event.as_log()[log_schema().message_key()].to_string_lossy());
+        }
+        assert_eq!(messages, vec!["first", "third"]);
+    }
+}
@@ -12,6 +12,35 @@ pub struct Options {
 
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    #[serde(default)]
+    pub profiling: bool,
+
+    #[serde(default = "default_subscription_buffer_size")]
+    pub subscription_buffer_size: usize,
+
+    #[serde(default)]
+    pub subscription_drop_policy: DropPolicy,
+
+    #[serde(default = "default_keepalive_secs")]
+    pub keepalive_secs: u64,
+
+    #[serde(default)]
+    pub max_connections: Option<usize>,
+}
+
+/// What to drop once a subscriber falls `subscription_buffer_size` items behind
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum DropPolicy {
+    DropOldest,
+    DropNewest,
+}
+
+impl Default for DropPolicy {
+    fn default() -> Self {
+        Self::DropOldest
+    }
 }
 
 impl Default for Options {
@@ -20,6 +49,11 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            profiling: false,
+            subscription_buffer_size: default_subscription_buffer_size(),
+            subscription_drop_policy: DropPolicy::default(),
+            keepalive_secs: default_keepalive_secs(),
+            max_connections: None,
         }
     }
 }
@@ -39,6 +73,14 @@ fn default_playground() -> bool {
     true
 }
 
+pub fn default_subscription_buffer_size() -> usize {
+    100
+}
+
+fn default_keepalive_secs() -> u64 {
+    30
+}
+
 impl Options {
     pub fn merge(&mut self, other: Self) -> Result<(), String> {
         // Merge options
@@ -64,6 +106,26 @@ impl Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            profiling: self.profiling | other.profiling,
+            // Prefer non default subscription settings
+            subscription_buffer_size: if other.subscription_buffer_size
+                == default_subscription_buffer_size()
+            {
+                self.subscription_buffer_size
+            } else {
+                other.subscription_buffer_size
+            },
+            subscription_drop_policy: if other.subscription_drop_policy == DropPolicy::default() {
+                self.subscription_drop_policy
+            } else {
+                other.subscription_drop_policy
+            },
+            keepalive_secs: if other.keepalive_secs == default_keepalive_secs() {
+                self.keepalive_secs
+            } else {
+                other.keepalive_secs
+            },
+            max_connections: other.max_connections.or(self.max_connections),
         };
 
         *self = options;
@@ -77,6 +139,8 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        profiling: false,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -87,6 +151,8 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            profiling: false,
+            ..Options::default()
         }
     );
 }
@@ -98,6 +164,8 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        profiling: false,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -108,6 +176,8 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            profiling: false,
+            ..Options::default()
         }
     );
 }
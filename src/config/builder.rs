@@ -1,9 +1,12 @@
 #[cfg(feature = "api")]
 use super::api;
+use super::secret::SecretBackend;
 use super::{
-    compiler, default_data_dir, Config, GlobalOptions, HealthcheckOptions, SinkConfig, SinkOuter,
-    SourceConfig, TestDefinition, TransformConfig, TransformOuter,
+    compiler, control, default_data_dir, Config, GlobalOptions, HealthcheckOptions, PipelineConfig,
+    SinkConfig, SinkOuter, SourceConfig, SourceOuter, TestDefinition, TransformConfig,
+    TransformOuter,
 };
+use crate::enrichment_tables::EnrichmentTableConfig;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
@@ -16,14 +19,24 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub api: api::Options,
     #[serde(default)]
+    pub control: control::Options,
+    #[serde(default)]
     pub healthchecks: HealthcheckOptions,
+    /// The secrets referenced by the config have been replaced with their
+    /// values when it was loaded, so the backends are kept only to be validated.
+    #[serde(default, skip_serializing)]
+    pub secret: IndexMap<String, SecretBackend>,
+    #[serde(default)]
+    pub enrichment_tables: IndexMap<String, EnrichmentTableConfig>,
     #[serde(default)]
-    pub sources: IndexMap<String, Box<dyn SourceConfig>>,
+    pub sources: IndexMap<String, SourceOuter>,
     #[serde(default)]
     pub sinks: IndexMap<String, SinkOuter>,
     #[serde(default)]
     pub transforms: IndexMap<String, TransformOuter>,
     #[serde(default)]
+    pub pipelines: IndexMap<String, PipelineConfig>,
+    #[serde(default)]
     pub tests: Vec<TestDefinition>,
 }
 
@@ -45,10 +58,15 @@ impl From<Config> for ConfigBuilder {
             global: c.global,
             #[cfg(feature = "api")]
             api: c.api,
+            control: c.control,
             healthchecks: c.healthchecks,
+            secret: IndexMap::new(),
+            enrichment_tables: c.enrichment_tables,
             sources: c.sources,
             sinks: c.sinks,
             transforms: c.transforms,
+            // Pipelines have been expanded into transforms.
+            pipelines: IndexMap::new(),
             tests: c.tests,
         }
     }
@@ -70,7 +88,8 @@ impl ConfigBuilder {
     }
 
     pub fn add_source<S: SourceConfig + 'static, T: Into<String>>(&mut self, name: T, source: S) {
-        self.sources.insert(name.into(), Box::new(source));
+        self.sources
+            .insert(name.into(), SourceOuter::new(Box::new(source)));
     }
 
     pub fn add_sink<S: SinkConfig + 'static, T: Into<String>>(
@@ -108,6 +127,10 @@ impl ConfigBuilder {
             errors.push(error);
         }
 
+        if let Err(error) = self.control.merge(with.control) {
+            errors.push(error);
+        }
+
         if self.global.data_dir.is_none() || self.global.data_dir == default_data_dir() {
             self.global.data_dir = with.global.data_dir;
         } else if with.global.data_dir != default_data_dir()
@@ -124,8 +147,28 @@ impl ConfigBuilder {
             errors.extend(merge_errors);
         }
 
+        if let Err(error) = self.global.metadata_keys.merge(with.global.metadata_keys) {
+            errors.push(error);
+        }
+
+        match (
+            self.global.expire_metrics_secs,
+            with.global.expire_metrics_secs,
+        ) {
+            (Some(current), Some(other)) if current != other => {
+                errors.push("conflicting values for 'expire_metrics_secs' found".to_owned());
+            }
+            (None, other) => self.global.expire_metrics_secs = other,
+            _ => {}
+        }
+
         self.healthchecks.merge(with.healthchecks);
 
+        with.enrichment_tables.keys().for_each(|k| {
+            if self.enrichment_tables.contains_key(k) {
+                errors.push(format!("duplicate enrichment table name found: {}", k));
+            }
+        });
         with.sources.keys().for_each(|k| {
             if self.sources.contains_key(k) {
                 errors.push(format!("duplicate source name found: {}", k));
@@ -141,6 +184,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform name found: {}", k));
             }
         });
+        with.pipelines.keys().for_each(|k| {
+            if self.pipelines.contains_key(k) {
+                errors.push(format!("duplicate pipeline name found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -150,9 +198,11 @@ impl ConfigBuilder {
             return Err(errors);
         }
 
+        self.enrichment_tables.extend(with.enrichment_tables);
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
+        self.pipelines.extend(with.pipelines);
         self.tests.extend(with.tests);
 
         Ok(())
@@ -1,12 +1,18 @@
 use super::{builder::ConfigBuilder, validation, Config, TransformOuter};
+use crate::transforms::util::{rejected::REJECTED_OUTPUT, UNMATCHED_OUTPUT};
 use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
 
 pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<String>> {
     let mut errors = Vec::new();
 
+    expand_pipelines(&mut builder)?;
+
     expand_wildcards(&mut builder);
 
-    let expansions = expand_macros(&mut builder)?;
+    let mut expansions = expand_macros(&mut builder)?;
+
+    prune_unconsumed_outputs(&mut builder, &mut expansions);
 
     let warnings = validation::warnings(&builder);
 
@@ -22,13 +28,23 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(type_errors);
     }
 
+    if let Err(table_errors) = validation::check_enrichment_tables(&builder) {
+        errors.extend(table_errors);
+    }
+
+    if let Err(schema_errors) = validation::check_log_schemas(&builder) {
+        errors.extend(schema_errors);
+    }
+
     if errors.is_empty() {
         Ok((
             Config {
                 global: builder.global,
                 #[cfg(feature = "api")]
                 api: builder.api,
+                control: builder.control,
                 healthchecks: builder.healthchecks,
+                enrichment_tables: builder.enrichment_tables,
                 sources: builder.sources,
                 sinks: builder.sinks,
                 transforms: builder.transforms,
@@ -42,6 +58,65 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
     }
 }
 
+/// Expands pipelines into their transforms, named `<pipeline>.<transform>` so that their
+/// metrics are told apart, each taking the previous one as its input. Components taking a
+/// pipeline as their input are given its last transform instead.
+pub(super) fn expand_pipelines(config: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    let mut outputs = HashMap::new();
+
+    for (name, pipeline) in std::mem::take(&mut config.pipelines) {
+        if config.sources.contains_key(&name) || config.transforms.contains_key(&name) {
+            errors.push(format!(
+                "pipeline '{}' has the same name as a component",
+                name
+            ));
+            continue;
+        }
+        if pipeline.transforms.is_empty() {
+            errors.push(format!("pipeline '{}' has no transforms", name));
+            continue;
+        }
+
+        let mut inputs = pipeline.inputs;
+        for transform in pipeline.transforms {
+            let full_name = format!("{}.{}", name, transform.name);
+            if config.transforms.contains_key(&full_name) {
+                errors.push(format!("duplicate transform name found: {}", full_name));
+            }
+            config.transforms.insert(
+                full_name.clone(),
+                TransformOuter {
+                    inputs,
+                    inner: transform.inner,
+                },
+            );
+            inputs = vec![full_name];
+        }
+        outputs.insert(name, inputs.remove(0));
+    }
+
+    let resolve = |inputs: &mut Vec<String>| {
+        for input in inputs.iter_mut() {
+            if let Some(output) = outputs.get(input) {
+                *input = output.clone();
+            }
+        }
+    };
+    for transform in config.transforms.values_mut() {
+        resolve(&mut transform.inputs);
+    }
+    for sink in config.sinks.values_mut() {
+        resolve(&mut sink.inputs);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// Some component configs can act like macros and expand themselves into multiple replacement
 /// configs. Performs those expansions and records the relevant metadata.
 pub(super) fn expand_macros(
@@ -51,6 +126,14 @@ pub(super) fn expand_macros(
     let mut expansions = IndexMap::new();
     let mut errors = Vec::new();
 
+    let consumed = config
+        .transforms
+        .values()
+        .flat_map(|transform| transform.inputs.iter())
+        .chain(config.sinks.values().flat_map(|sink| sink.inputs.iter()))
+        .cloned()
+        .collect::<HashSet<_>>();
+
     while let Some((k, mut t)) = config.transforms.pop() {
         if let Some(expanded) = match t.inner.expand() {
             Ok(e) => e,
@@ -59,13 +142,31 @@ pub(super) fn expand_macros(
                 continue;
             }
         } {
+            // Parsers only need to route the events they fail to parse when something uses
+            // their rejected output.
+            if expanded.contains_key(REJECTED_OUTPUT)
+                && !consumed.contains(&format!("{}.{}", k, REJECTED_OUTPUT))
+            {
+                expanded_transforms.insert(k, t);
+                continue;
+            }
+
             let mut children = Vec::new();
             for (name, child) in expanded {
-                let full_name = format!("{}.{}", k, name);
+                // A child without a name takes the place of its parent.
+                let full_name = if name.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", k, name)
+                };
+                let inputs = match child.expanded_input() {
+                    Some(sibling) => vec![format!("{}.{}", k, sibling)],
+                    None => t.inputs.clone(),
+                };
                 expanded_transforms.insert(
                     full_name.clone(),
                     TransformOuter {
-                        inputs: t.inputs.clone(),
+                        inputs,
                         inner: child,
                     },
                 );
@@ -85,6 +186,40 @@ pub(super) fn expand_macros(
     }
 }
 
+/// Outputs that process every event a second time, like the unmatched output of routes, are
+/// only kept when something consumes them.
+const OPTIONAL_OUTPUTS: &[&str] = &[UNMATCHED_OUTPUT];
+
+fn prune_unconsumed_outputs(
+    config: &mut ConfigBuilder,
+    expansions: &mut IndexMap<String, Vec<String>>,
+) {
+    let is_consumed = |name: &String| {
+        config
+            .transforms
+            .values()
+            .any(|transform| transform.inputs.contains(name))
+            || config.sinks.values().any(|sink| sink.inputs.contains(name))
+    };
+
+    let unused = expansions
+        .keys()
+        .flat_map(|parent| {
+            OPTIONAL_OUTPUTS
+                .iter()
+                .map(move |output| format!("{}.{}", parent, output))
+        })
+        .filter(|name| config.transforms.contains_key(name) && !is_consumed(name))
+        .collect::<Vec<_>>();
+
+    for name in unused {
+        config.transforms.remove(&name);
+        for children in expansions.values_mut() {
+            children.retain(|child| *child != name);
+        }
+    }
+}
+
 /// Expand trailing `*` wildcards in input lists
 fn expand_wildcards(config: &mut ConfigBuilder) {
     let candidates = config
@@ -123,7 +258,10 @@ fn expand_wildcards_inner(inputs: &mut Vec<String>, name: &str, candidates: &[St
 mod test {
     use super::*;
     use crate::{
-        config::{DataType, GlobalOptions, SinkConfig, SinkContext, SourceConfig, TransformConfig},
+        config::{
+            DataType, GlobalOptions, PipelineConfig, PipelineTransform, SinkConfig, SinkContext,
+            SourceConfig, TransformConfig,
+        },
         shutdown::ShutdownSignal,
         sinks::{Healthcheck, VectorSink},
         sources::Source,
@@ -139,7 +277,16 @@ mod test {
     #[derive(Debug, Clone, Serialize, Deserialize)]
     struct MockTransformConfig;
 
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockParserConfig;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockParserOutputConfig;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockRouteConfig;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     struct MockSinkConfig;
 
     #[async_trait]
@@ -184,6 +331,85 @@ mod test {
         }
     }
 
+    #[async_trait]
+    #[typetag::serde(name = "mock_parser")]
+    impl TransformConfig for MockParserConfig {
+        async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+            unimplemented!()
+        }
+
+        fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+            let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
+            map.insert("_parse".into(), Box::new(MockTransformConfig));
+            map.insert("".into(), Box::new(MockParserOutputConfig));
+            map.insert(REJECTED_OUTPUT.into(), Box::new(MockParserOutputConfig));
+            Ok(Some(map))
+        }
+
+        fn transform_type(&self) -> &'static str {
+            "mock_parser"
+        }
+
+        fn input_type(&self) -> DataType {
+            DataType::Any
+        }
+
+        fn output_type(&self) -> DataType {
+            DataType::Any
+        }
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_parser_output")]
+    impl TransformConfig for MockParserOutputConfig {
+        async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+            unimplemented!()
+        }
+
+        fn expanded_input(&self) -> Option<&str> {
+            Some("_parse")
+        }
+
+        fn transform_type(&self) -> &'static str {
+            "mock_parser_output"
+        }
+
+        fn input_type(&self) -> DataType {
+            DataType::Any
+        }
+
+        fn output_type(&self) -> DataType {
+            DataType::Any
+        }
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_route")]
+    impl TransformConfig for MockRouteConfig {
+        async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+            unimplemented!()
+        }
+
+        fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+            let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
+            map.insert("errors".into(), Box::new(MockTransformConfig));
+            map.insert(UNMATCHED_OUTPUT.into(), Box::new(MockTransformConfig));
+            Ok(Some(map))
+        }
+
+        fn transform_type(&self) -> &'static str {
+            "mock_route"
+        }
+
+        fn input_type(&self) -> DataType {
+            DataType::Any
+        }
+
+        fn output_type(&self) -> DataType {
+            DataType::Any
+        }
+    }
+
     #[async_trait]
     #[typetag::serde(name = "mock")]
     impl SinkConfig for MockSinkConfig {
@@ -219,4 +445,86 @@ mod test {
             vec!["foo1", "foo2", "bar", "foos"]
         );
     }
+
+    #[test]
+    fn parsers_are_expanded_only_when_rejected_output_is_consumed() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockSourceConfig);
+        builder.add_transform("wired", &["in"], MockParserConfig);
+        builder.add_transform("unwired", &["in"], MockParserConfig);
+        builder.add_sink("out", &["wired", "unwired"], MockSinkConfig);
+        builder.add_sink("errors", &["wired.rejected"], MockSinkConfig);
+
+        let config = builder.build().expect("build should succeed");
+
+        assert_eq!(config.transforms["wired._parse"].inputs, vec!["in"]);
+        assert_eq!(config.transforms["wired"].inputs, vec!["wired._parse"]);
+        assert_eq!(
+            config.transforms["wired.rejected"].inputs,
+            vec!["wired._parse"]
+        );
+        assert_eq!(config.transforms["unwired"].inputs, vec!["in"]);
+        assert!(!config.transforms.contains_key("unwired._parse"));
+        assert!(!config.transforms.contains_key("unwired.rejected"));
+        assert_eq!(config.get_inputs("wired"), vec!["wired._parse"]);
+        assert_eq!(config.get_inputs("unwired"), vec!["unwired"]);
+    }
+
+    #[test]
+    fn pipelines_are_expanded() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockSourceConfig);
+        let transform = |name: &str| PipelineTransform {
+            name: name.into(),
+            inner: Box::new(MockTransformConfig),
+        };
+        builder.pipelines.insert(
+            "normalize".into(),
+            PipelineConfig {
+                inputs: vec!["in".into()],
+                transforms: vec![transform("parse"), transform("tag")],
+            },
+        );
+        builder.add_sink("out", &["normalize"], MockSinkConfig);
+
+        let config = builder.build().expect("build should succeed");
+
+        assert_eq!(config.transforms["normalize.parse"].inputs, vec!["in"]);
+        assert_eq!(
+            config.transforms["normalize.tag"].inputs,
+            vec!["normalize.parse"]
+        );
+        assert_eq!(config.sinks["out"].inputs, vec!["normalize.tag"]);
+    }
+
+    #[test]
+    fn pipelines_require_transforms() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockSourceConfig);
+        builder.pipelines.insert(
+            "empty".into(),
+            PipelineConfig {
+                inputs: vec!["in".into()],
+                transforms: vec![],
+            },
+        );
+
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn unmatched_outputs_are_kept_only_when_consumed() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockSourceConfig);
+        builder.add_transform("wired", &["in"], MockRouteConfig);
+        builder.add_transform("unwired", &["in"], MockRouteConfig);
+        builder.add_sink("out", &["wired.errors", "unwired.errors"], MockSinkConfig);
+        builder.add_sink("rest", &["wired._unmatched"], MockSinkConfig);
+
+        let config = builder.build().expect("build should succeed");
+
+        assert!(config.transforms.contains_key("wired._unmatched"));
+        assert!(!config.transforms.contains_key("unwired._unmatched"));
+        assert_eq!(config.get_inputs("unwired"), vec!["unwired.errors"]);
+    }
 }
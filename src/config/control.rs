@@ -0,0 +1,144 @@
+use crate::tls::TlsConfig;
+use http::Uri;
+use serde::{Deserialize, Serialize};
+
+/// Registration of this instance with a remote control plane, which receives its
+/// heartbeats, config fingerprint and a summary of its internal metrics.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    pub endpoint: Option<String>,
+
+    pub token: Option<String>,
+
+    #[serde(default = "default_heartbeat_secs")]
+    pub heartbeat_secs: u64,
+
+    #[serde(default = "default_retry_attempts")]
+    pub retry_attempts: usize,
+
+    pub tls: Option<TlsConfig>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            token: None,
+            heartbeat_secs: default_heartbeat_secs(),
+            retry_attempts: default_retry_attempts(),
+            tls: None,
+        }
+    }
+}
+
+fn default_heartbeat_secs() -> u64 {
+    30
+}
+
+fn default_retry_attempts() -> usize {
+    5
+}
+
+impl Options {
+    pub fn enabled(&self) -> bool {
+        self.endpoint.is_some()
+    }
+
+    /// Checks that `endpoint` is an absolute URI, so that an invalid one fails loading
+    /// the config rather than every report.
+    pub fn validate(&self) -> Result<(), String> {
+        let endpoint = match &self.endpoint {
+            Some(endpoint) => endpoint,
+            None => return Ok(()),
+        };
+        match endpoint.parse::<Uri>() {
+            Ok(uri) if uri.scheme().is_some() && uri.authority().is_some() => Ok(()),
+            Ok(_) => Err(format!(
+                "Invalid `control` endpoint {:?}: must be an absolute URI.",
+                endpoint
+            )),
+            Err(error) => Err(format!(
+                "Invalid `control` endpoint {:?}: {}.",
+                endpoint, error
+            )),
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) -> Result<(), String> {
+        let endpoint = match (self.endpoint.take(), other.endpoint) {
+            (Some(a), Some(b)) if a != b => {
+                return Err(format!("Conflicting `control` endpoint: {}, {} .", a, b))
+            }
+            (a, b) => a.or(b),
+        };
+
+        let options = Options {
+            endpoint,
+            token: other.token.or_else(|| self.token.take()),
+            // Prefer non default settings
+            heartbeat_secs: if other.heartbeat_secs == default_heartbeat_secs() {
+                self.heartbeat_secs
+            } else {
+                other.heartbeat_secs
+            },
+            retry_attempts: if other.retry_attempts == default_retry_attempts() {
+                self.retry_attempts
+            } else {
+                other.retry_attempts
+            },
+            tls: other.tls.or_else(|| self.tls.take()),
+        };
+
+        *self = options;
+        Ok(())
+    }
+}
+
+#[test]
+fn merge_prefers_non_default() {
+    let mut a = Options {
+        endpoint: Some("https://control.example.com".to_owned()),
+        heartbeat_secs: 10,
+        ..Options::default()
+    };
+
+    a.merge(Options {
+        token: Some("secret".to_owned()),
+        ..Options::default()
+    })
+    .unwrap();
+
+    assert_eq!(a.endpoint.as_deref(), Some("https://control.example.com"));
+    assert_eq!(a.token.as_deref(), Some("secret"));
+    assert_eq!(a.heartbeat_secs, 10);
+    assert_eq!(a.retry_attempts, default_retry_attempts());
+}
+
+#[test]
+fn merge_conflicting_endpoints() {
+    let mut a = Options {
+        endpoint: Some("https://a.example.com".to_owned()),
+        ..Options::default()
+    };
+
+    let b = Options {
+        endpoint: Some("https://b.example.com".to_owned()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn validate_endpoint() {
+    let options = |endpoint: &str| Options {
+        endpoint: Some(endpoint.to_owned()),
+        ..Options::default()
+    };
+
+    assert!(Options::default().validate().is_ok());
+    assert!(options("https://control.example.com/v1").validate().is_ok());
+    assert!(options("control.example.com").validate().is_err());
+    assert!(options("https://control example.com").validate().is_err());
+}
@@ -1,13 +1,20 @@
-use super::{builder::ConfigBuilder, format, vars, Config, Format, FormatHint};
+use super::{builder::ConfigBuilder, secret, vars, Config, Format, FormatHint};
 use glob::glob;
+use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fs::File,
     path::{Path, PathBuf},
-    sync::Mutex,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
 };
 
+/// Whether referencing an unset environment variable in a config fails loading it.
+static STRICT_ENV_VARS: AtomicBool = AtomicBool::new(false);
+
 lazy_static! {
     pub static ref DEFAULT_UNIX_CONFIG_PATHS: Vec<(PathBuf, FormatHint)> =
         vec![("/etc/vector/vector.toml".into(), Some(Format::TOML))];
@@ -20,6 +27,10 @@ lazy_static! {
     pub static ref CONFIG_PATHS: Mutex<Vec<(PathBuf, FormatHint)>> = Mutex::default();
 }
 
+pub fn set_strict_env_vars(strict: bool) {
+    STRICT_ENV_VARS.store(strict, Ordering::Relaxed);
+}
+
 /// Merge the paths coming from different cli flags with different formats into
 /// a unified list of paths with formats.
 pub fn merge_path_lists(path_lists: Vec<(&[PathBuf], FormatHint)>) -> Vec<(PathBuf, FormatHint)> {
@@ -30,7 +41,8 @@ pub fn merge_path_lists(path_lists: Vec<(&[PathBuf], FormatHint)>) -> Vec<(PathB
 }
 
 /// Expand a list of paths (potentially containing glob patterns) into real
-/// config paths, replacing it with the default paths when empty.
+/// config paths, replacing it with the default paths when empty. Directories
+/// are expanded into the config files directly inside them.
 pub fn process_paths(config_paths: &[(PathBuf, FormatHint)]) -> Option<Vec<(PathBuf, FormatHint)>> {
     let default_paths = if cfg!(unix) {
         DEFAULT_UNIX_CONFIG_PATHS.clone()
@@ -64,7 +76,17 @@ pub fn process_paths(config_paths: &[(PathBuf, FormatHint)]) -> Option<Vec<(Path
         }
 
         for path in matches {
-            paths.push((path, *format));
+            if path.is_dir() {
+                match config_files_in_dir(&path, *format) {
+                    Ok(files) => paths.extend(files),
+                    Err(error) => {
+                        error!(message = "Failed to read config directory.", path = ?path, %error);
+                        return None;
+                    }
+                }
+            } else {
+                paths.push((path, *format));
+            }
         }
     }
 
@@ -76,6 +98,56 @@ pub fn process_paths(config_paths: &[(PathBuf, FormatHint)]) -> Option<Vec<(Path
     Some(paths)
 }
 
+/// The paths to watch for config changes, given the config paths and the
+/// config `files` they were processed into. The directories among the config
+/// paths are watched instead of the files in them, so that files being added
+/// or removed are noticed too.
+pub fn watched_paths(
+    config_paths: &[(PathBuf, FormatHint)],
+    files: &[(PathBuf, FormatHint)],
+) -> Vec<PathBuf> {
+    let dirs = config_paths
+        .iter()
+        .map(|(path, _)| path)
+        .filter(|path| path.is_dir())
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut paths = files
+        .iter()
+        .map(|(path, _)| path)
+        .filter(|path| {
+            path.parent()
+                .map_or(true, |parent| !dirs.iter().any(|dir| dir == parent))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    paths.extend(dirs);
+    paths
+}
+
+/// The files directly inside `dir` whose extension names a config format,
+/// sorted by file name. With a format hint, only the files of that format are
+/// kept.
+fn config_files_in_dir(
+    dir: &Path,
+    format: FormatHint,
+) -> std::io::Result<Vec<(PathBuf, FormatHint)>> {
+    let mut files = Vec::new();
+    for entry in dir.read_dir()? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Ok(file_format) = Format::from_path(&path) {
+            if format.map_or(true, |format| format == file_format) {
+                files.push((path, Some(file_format)));
+            }
+        }
+    }
+    files.sort_by(|(a, _), (b, _)| a.file_name().cmp(&b.file_name()));
+    Ok(files)
+}
+
 pub fn load_from_paths(config_paths: &[(PathBuf, FormatHint)]) -> Result<Config, Vec<String>> {
     let (builder, load_warnings) = load_builder_from_paths(config_paths)?;
     let (config, build_warnings) = builder.build_with_warnings()?;
@@ -95,7 +167,8 @@ pub fn load_builder_from_paths(
 
     for (path, format) in config_paths {
         if let Some(file) = open_config(&path) {
-            inputs.push((file, format.or_else(move || Format::from_path(&path).ok())));
+            let format = format.or_else(|| Format::from_path(&path).ok());
+            inputs.push((Some(path.as_path()), file, format));
         } else {
             errors.push(format!("Config file not found in path: {:?}.", path));
         };
@@ -109,7 +182,8 @@ pub fn load_builder_from_paths(
 }
 
 pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<String>> {
-    let (builder, load_warnings) = load_from_inputs(std::iter::once((input.as_bytes(), format)))?;
+    let (builder, load_warnings) =
+        load_from_inputs(std::iter::once((None, input.as_bytes(), format)))?;
     let (config, build_warnings) = builder.build_with_warnings()?;
 
     for warning in load_warnings.into_iter().chain(build_warnings) {
@@ -119,20 +193,60 @@ pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<Stri
     Ok(config)
 }
 
-fn load_from_inputs(
-    inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint)>,
+/// Loads and merges the inputs, prefixing errors with the path of the input
+/// they were found in. Conflicts between inputs, such as components having
+/// the same name, are reported in the later input.
+fn load_from_inputs<'a>(
+    inputs: impl IntoIterator<Item = (Option<&'a Path>, impl std::io::Read, FormatHint)>,
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
     let mut config = Config::builder();
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
-    for (input, format) in inputs {
-        if let Err(errs) = load(input, format).and_then(|(n, mut warn)| {
+    // Secret backends can be configured in any input, so all of them are read
+    // before the secrets they reference are retrieved.
+    let mut contents = Vec::new();
+    let mut backends = IndexMap::new();
+    for (path, input, format) in inputs {
+        let loaded = load(input).and_then(|(content, mut warn)| {
             warnings.append(&mut warn);
-            config.append(n)
-        }) {
-            // TODO: add back paths
-            errors.extend(errs.iter().map(|e| e.to_string()));
+            secret::backends(&content, format).map(|defined| (content, defined))
+        });
+        match loaded {
+            Ok((content, defined)) => {
+                for (name, backend) in defined {
+                    if backends.insert(name.clone(), backend).is_some() {
+                        let error = format!("duplicate secret backend name found: {}", name);
+                        errors.push(in_path(path, error));
+                    }
+                }
+                contents.push((path, content, format));
+            }
+            Err(errs) => errors.extend(errs.into_iter().map(|error| in_path(path, error))),
+        }
+    }
+
+    let mut keys = IndexMap::<_, BTreeSet<_>>::new();
+    for (path, content, _) in &contents {
+        for (backend, key) in secret::references(content) {
+            if backends.contains_key(&backend) {
+                keys.entry(backend).or_default().insert(key);
+            } else {
+                let error = format!("unknown secret backend: {}", backend);
+                errors.push(in_path(*path, error));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let secrets = secret::retrieve(&backends, &keys)?;
+
+    for (path, content, format) in contents {
+        if let Err(errs) =
+            secret::deserialize(&content, format, &secrets).and_then(|n| config.append(n))
+        {
+            errors.extend(errs.into_iter().map(|error| in_path(path, error)));
         }
     }
 
@@ -143,6 +257,13 @@ fn load_from_inputs(
     }
 }
 
+fn in_path(path: Option<&Path>, error: String) -> String {
+    match path {
+        Some(path) => format!("{}: {}", path.display(), error),
+        None => error,
+    }
+}
+
 fn open_config(path: &Path) -> Option<File> {
     match File::open(path) {
         Ok(f) => Some(f),
@@ -158,10 +279,10 @@ fn open_config(path: &Path) -> Option<File> {
     }
 }
 
-fn load(
-    mut input: impl std::io::Read,
-    format: FormatHint,
-) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+/// Reads the input, interpolating environment variables in it.
+///
+/// Ok((content, warnings)) or Err(errors)
+fn load(mut input: impl std::io::Read) -> Result<(String, Vec<String>), Vec<String>> {
     let mut source_string = String::new();
     input
         .read_to_string(&mut source_string)
@@ -173,7 +294,142 @@ fn load(
             vars.insert("HOSTNAME".into(), hostname);
         }
     }
-    let (with_vars, warnings) = vars::interpolate(&source_string, &vars);
+    vars::interpolate(
+        &source_string,
+        &vars,
+        STRICT_ENV_VARS.load(Ordering::Relaxed),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn expands_config_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in &["c.json", "README.md", "b.yaml", "a.toml"] {
+            fs::write(dir.path().join(file), "").unwrap();
+        }
+        fs::create_dir(dir.path().join("nested.toml")).unwrap();
+
+        assert_eq!(
+            config_files_in_dir(dir.path(), None).unwrap(),
+            vec![
+                (dir.path().join("a.toml"), Some(Format::TOML)),
+                (dir.path().join("b.yaml"), Some(Format::YAML)),
+                (dir.path().join("c.json"), Some(Format::JSON)),
+            ]
+        );
+
+        let paths = process_paths(&[(dir.path().into(), None)]).unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                (dir.path().join("a.toml"), Some(Format::TOML)),
+                (dir.path().join("b.yaml"), Some(Format::YAML)),
+                (dir.path().join("c.json"), Some(Format::JSON)),
+            ]
+        );
+
+        let paths = process_paths(&[(dir.path().into(), Some(Format::YAML))]).unwrap();
+        assert_eq!(paths, vec![(dir.path().join("b.yaml"), Some(Format::YAML))]);
+    }
 
-    format::deserialize(&with_vars, format).map(|builder| (builder, warnings))
+    #[test]
+    fn watches_config_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.toml"), "").unwrap();
+        let other_dir = tempfile::tempdir().unwrap();
+        let other = other_dir.path().join("b.toml");
+        fs::write(&other, "").unwrap();
+
+        let config_paths = vec![(dir.path().into(), None), (other.clone(), None)];
+        let files = process_paths(&config_paths).unwrap();
+        assert_eq!(
+            watched_paths(&config_paths, &files),
+            vec![other, dir.path().to_path_buf()]
+        );
+    }
+
+    #[test]
+    fn merges_files_and_names_conflicting_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = r#"
+            [sources.in]
+            type = "stdin"
+        "#;
+        let sink = r#"
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+        "#;
+        fs::write(dir.path().join("source.toml"), source).unwrap();
+        fs::write(dir.path().join("sink.toml"), sink).unwrap();
+
+        let paths = process_paths(&[(dir.path().into(), None)]).unwrap();
+        let (builder, _) = load_builder_from_paths(&paths).unwrap();
+        assert!(builder.sources.contains_key("in"));
+        assert!(builder.sinks.contains_key("out"));
+
+        let duplicate = dir.path().join("z_duplicate.toml");
+        fs::write(&duplicate, source).unwrap();
+
+        let paths = process_paths(&[(dir.path().into(), None)]).unwrap();
+        let errors = load_builder_from_paths(&paths).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![format!(
+                "{}: duplicate source name found: in",
+                duplicate.display()
+            )]
+        );
+    }
+
+    #[test]
+    fn resolves_secrets_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = dir.path().join("secrets.json");
+        fs::write(&secrets, r#"{"data_dir": "/var/lib/vector-test"}"#).unwrap();
+        let backend = format!(
+            r#"
+            [secret.files]
+            type = "file"
+            path = {:?}
+            "#,
+            secrets
+        );
+        fs::write(dir.path().join("backend.toml"), backend).unwrap();
+        fs::write(
+            dir.path().join("global.toml"),
+            "data_dir = \"SECRET[files.data_dir]\"",
+        )
+        .unwrap();
+
+        let paths = process_paths(&[(dir.path().into(), None)]).unwrap();
+        let (builder, _) = load_builder_from_paths(&paths).unwrap();
+        assert_eq!(
+            builder.global.data_dir,
+            Some(PathBuf::from("/var/lib/vector-test"))
+        );
+
+        let unknown = dir.path().join("unknown.toml");
+        fs::write(
+            &unknown,
+            "[healthchecks]\nenabled = \"SECRET[vault.enabled]\"",
+        )
+        .unwrap();
+
+        let paths = process_paths(&[(dir.path().into(), None)]).unwrap();
+        let errors = load_builder_from_paths(&paths).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![format!(
+                "{}: unknown secret backend: vault",
+                unknown.display()
+            )]
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use super::{load_builder_from_paths, FormatHint};
+use crate::event::Event;
 use getset::{Getters, Setters};
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
@@ -139,6 +140,50 @@ impl LogSchema {
     }
 }
 
+/// Keys of the `log_schema` overridden for the events of a single source. The
+/// source adds its fields under the global keys, and they are then moved to the
+/// overridden ones.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogSchemaOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_type_key: Option<String>,
+}
+
+impl LogSchemaOverrides {
+    /// Moves the fields of `event` from the keys of the global schema to the
+    /// overridden ones. A field the event already has at an overridden key is
+    /// kept, and the field of the global key is left where it is.
+    pub fn apply(&self, mut event: Event) -> Event {
+        if let Event::Log(log) = &mut event {
+            let schema = log_schema();
+            let keys = [
+                (schema.message_key(), &self.message_key),
+                (schema.timestamp_key(), &self.timestamp_key),
+                (schema.host_key(), &self.host_key),
+                (schema.source_type_key(), &self.source_type_key),
+            ];
+            for (key, target) in &keys {
+                if let Some(target) = target {
+                    if log.contains(target.as_str()) {
+                        continue;
+                    }
+                    if let Some(value) = log.remove(key) {
+                        log.insert(target.as_str(), value);
+                    }
+                }
+            }
+        }
+        event
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -151,4 +196,43 @@ mod test {
         "#;
         let _ = toml::from_str::<LogSchema>(toml).unwrap();
     }
+
+    #[test]
+    fn overrides_move_keys() {
+        let overrides = toml::from_str::<LogSchemaOverrides>(
+            r#"
+            message_key = "msg"
+            host_key = "agent.hostname"
+        "#,
+        )
+        .unwrap();
+
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("host", "vector-0");
+        event.as_mut_log().insert("source_type", "socket");
+
+        let event = overrides.apply(event);
+        let log = event.as_log();
+        assert_eq!(log["msg"], "hello".into());
+        assert_eq!(log["agent.hostname"], "vector-0".into());
+        assert_eq!(log["source_type"], "socket".into());
+        assert!(log.get("message").is_none());
+        assert!(log.get("host").is_none());
+    }
+
+    #[test]
+    fn overrides_keep_existing_fields() {
+        let overrides = LogSchemaOverrides {
+            message_key: Some("msg".to_owned()),
+            ..Default::default()
+        };
+
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("msg", "already there");
+
+        let event = overrides.apply(event);
+        let log = event.as_log();
+        assert_eq!(log["msg"], "already there".into());
+        assert_eq!(log["message"], "hello".into());
+    }
 }
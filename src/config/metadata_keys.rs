@@ -0,0 +1,173 @@
+use crate::event::{LogEvent, Value};
+use serde::{Deserialize, Serialize};
+
+/// How a metadata key, such as `host`, is added to the events of a source. Options that
+/// aren't set fall back to the global `metadata_keys`, and then to the defaults, which add
+/// the key where the source would without a policy.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyPolicy {
+    /// Whether the key is added at all. Defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// The path the key is added at, instead of the key set by `log_schema`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Whether a value already at `target` is overwritten. Defaults to `true`, except for
+    /// sources that keep the key when the payload already sets it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overwrite: Option<bool>,
+}
+
+impl KeyPolicy {
+    fn or(&self, fallback: &Self) -> Self {
+        Self {
+            enabled: self.enabled.or(fallback.enabled),
+            target: self.target.clone().or_else(|| fallback.target.clone()),
+            overwrite: self.overwrite.or(fallback.overwrite),
+        }
+    }
+
+    /// Adds `value` to `log` following the policy, where the source would
+    /// otherwise add it at `key`.
+    pub fn insert(&self, log: &mut LogEvent, key: &str, value: impl Into<Value>) {
+        self.insert_with(log, key, value, true)
+    }
+
+    /// Like `insert`, but a value already at the target is kept unless the
+    /// policy sets `overwrite`.
+    pub fn try_insert(&self, log: &mut LogEvent, key: &str, value: impl Into<Value>) {
+        self.insert_with(log, key, value, false)
+    }
+
+    fn insert_with(&self, log: &mut LogEvent, key: &str, value: impl Into<Value>, overwrite: bool) {
+        if !self.enabled.unwrap_or(true) {
+            return;
+        }
+
+        let target = self.target.as_deref().unwrap_or(key);
+        let value = value.into();
+        if self.overwrite.unwrap_or(overwrite) {
+            log.insert(target, value);
+        } else {
+            log.try_insert(target, value);
+        }
+    }
+}
+
+/// Policies for the metadata keys that sources add to their events, so that they don't
+/// collide with fields of the same name.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetadataKeys {
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub host: KeyPolicy,
+
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub source_type: KeyPolicy,
+}
+
+impl MetadataKeys {
+    /// Returns the policies of a source, falling back to the `global` policies for the
+    /// options the source doesn't set.
+    pub fn or(&self, global: &Self) -> Self {
+        Self {
+            host: self.host.or(&global.host),
+            source_type: self.source_type.or(&global.source_type),
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) -> Result<(), String> {
+        if *self == Self::default() {
+            *self = other;
+        } else if other != Self::default() && *self != other {
+            // If two configs both set 'metadata_keys' and have conflicting values
+            // we consider this an error.
+            return Err("conflicting values for 'metadata_keys' found".to_owned());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+
+    fn event() -> LogEvent {
+        let mut event = Event::from("message");
+        let log = event.as_mut_log();
+        log.insert("host", "payload");
+        log.insert("_vector.host", "existing");
+        event.into_log()
+    }
+
+    #[test]
+    fn defaults_insert_at_key() {
+        let mut log = event();
+        KeyPolicy::default().insert(&mut log, "source_type", "socket");
+        KeyPolicy::default().insert(&mut log, "host", "vector-0");
+        assert_eq!(log["source_type"], "socket".into());
+        assert_eq!(log["host"], "vector-0".into());
+
+        let mut log = event();
+        KeyPolicy::default().try_insert(&mut log, "host", "vector-0");
+        assert_eq!(log["host"], "payload".into());
+    }
+
+    #[test]
+    fn disables_keys() {
+        let policy = KeyPolicy {
+            enabled: Some(false),
+            ..KeyPolicy::default()
+        };
+
+        let mut log = event();
+        policy.insert(&mut log, "source_type", "socket");
+        assert!(!log.contains("source_type"));
+    }
+
+    #[test]
+    fn inserts_at_target() {
+        let policy = |overwrite| KeyPolicy {
+            target: Some("_vector.host".to_owned()),
+            overwrite: Some(overwrite),
+            ..KeyPolicy::default()
+        };
+
+        // Fields of the payload at the key aren't touched.
+        let mut log = event();
+        policy(true).insert(&mut log, "host", "vector-0");
+        assert_eq!(log["host"], "payload".into());
+        assert_eq!(log["_vector.host"], "vector-0".into());
+
+        let mut log = event();
+        policy(false).insert(&mut log, "host", "vector-0");
+        assert_eq!(log["_vector.host"], "existing".into());
+    }
+
+    #[test]
+    fn source_options_override_global_options() {
+        let global = MetadataKeys {
+            host: KeyPolicy {
+                enabled: Some(false),
+                target: Some("agent".to_owned()),
+                ..KeyPolicy::default()
+            },
+            ..MetadataKeys::default()
+        };
+        let source = MetadataKeys {
+            host: KeyPolicy {
+                enabled: Some(true),
+                ..KeyPolicy::default()
+            },
+            ..MetadataKeys::default()
+        };
+
+        let keys = source.or(&global);
+        assert_eq!(keys.host.enabled, Some(true));
+        assert_eq!(keys.host.target, Some("agent".to_owned()));
+    }
+}
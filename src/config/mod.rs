@@ -1,6 +1,7 @@
 use crate::{
     buffers::Acker,
     conditions,
+    enrichment_tables::EnrichmentTableConfig,
     event::Metric,
     shutdown::ShutdownSignal,
     sinks::{self, util::UriSerde},
@@ -23,23 +24,27 @@ pub mod api;
 mod builder;
 mod compiler;
 pub mod component;
+pub mod control;
 mod diff;
 pub mod format;
 mod loading;
 mod log_schema;
+mod metadata_keys;
+pub mod secret;
 mod unit_test;
 mod validation;
 mod vars;
 pub mod watcher;
 
 pub use builder::ConfigBuilder;
-pub use diff::ConfigDiff;
+pub use diff::{ConfigDiff, Difference};
 pub use format::{Format, FormatHint};
 pub use loading::{
     load_builder_from_paths, load_from_paths, load_from_str, merge_path_lists, process_paths,
-    CONFIG_PATHS,
+    set_strict_env_vars, watched_paths, CONFIG_PATHS,
 };
-pub use log_schema::{init_log_schema, log_schema, LogSchema};
+pub use log_schema::{init_log_schema, log_schema, LogSchema, LogSchemaOverrides};
+pub use metadata_keys::{KeyPolicy, MetadataKeys};
 pub use unit_test::build_unit_tests_main as build_unit_tests;
 pub use validation::warnings;
 
@@ -48,8 +53,10 @@ pub struct Config {
     pub global: GlobalOptions,
     #[cfg(feature = "api")]
     pub api: api::Options,
+    pub control: control::Options,
     pub healthchecks: HealthcheckOptions,
-    pub sources: IndexMap<String, Box<dyn SourceConfig>>,
+    pub enrichment_tables: IndexMap<String, EnrichmentTableConfig>,
+    pub sources: IndexMap<String, SourceOuter>,
     pub sinks: IndexMap<String, SinkOuter>,
     pub transforms: IndexMap<String, TransformOuter>,
     tests: Vec<TestDefinition>,
@@ -65,6 +72,12 @@ pub struct GlobalOptions {
     pub log_schema: LogSchema,
     #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
     pub timezone: TimeZone,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub metadata_keys: MetadataKeys,
+    /// How long internal metrics that haven't been updated are kept, such as
+    /// those of removed components. They are kept forever if unset.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub expire_metrics_secs: Option<f64>,
 }
 
 pub fn default_data_dir() -> Option<PathBuf> {
@@ -142,6 +155,9 @@ impl GlobalOptions {
 pub struct HealthcheckOptions {
     pub enabled: bool,
     pub require_healthy: bool,
+    /// How often sink healthchecks are re-run after startup. Zero, the default, disables
+    /// re-runs.
+    pub interval_secs: u64,
 }
 
 impl HealthcheckOptions {
@@ -154,6 +170,10 @@ impl HealthcheckOptions {
     fn merge(&mut self, other: Self) {
         self.enabled &= other.enabled;
         self.require_healthy |= other.require_healthy;
+        self.interval_secs = match (self.interval_secs, other.interval_secs) {
+            (0, interval) | (interval, 0) => interval,
+            (a, b) => std::cmp::min(a, b),
+        };
     }
 }
 
@@ -162,6 +182,7 @@ impl Default for HealthcheckOptions {
         Self {
             enabled: true,
             require_healthy: false,
+            interval_secs: 0,
         }
     }
 }
@@ -214,6 +235,36 @@ pub type SourceDescription = ComponentDescription<Box<dyn SourceConfig>>;
 
 inventory::collect!(SourceDescription);
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SourceOuter {
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub metadata_keys: MetadataKeys,
+
+    /// Keys of the global `log_schema` overridden for the events of this source.
+    /// They are applied after `metadata_keys`.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub log_schema: LogSchemaOverrides,
+
+    #[serde(flatten)]
+    pub inner: Box<dyn SourceConfig>,
+}
+
+impl SourceOuter {
+    pub fn new(inner: Box<dyn SourceConfig>) -> Self {
+        SourceOuter {
+            metadata_keys: MetadataKeys::default(),
+            log_schema: LogSchemaOverrides::default(),
+            inner,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SinkOuter {
     pub inputs: Vec<String>,
@@ -229,6 +280,10 @@ pub struct SinkOuter {
     #[serde(default)]
     pub buffer: crate::buffers::BufferConfig,
 
+    /// Whether sources wait for the sink to accept their events before acknowledging them.
+    #[serde(default)]
+    pub acknowledgements: bool,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -236,6 +291,7 @@ pub struct SinkOuter {
 impl SinkOuter {
     pub fn new(inputs: Vec<String>, inner: Box<dyn SinkConfig>) -> Self {
         SinkOuter {
+            acknowledgements: false,
             buffer: Default::default(),
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
@@ -300,7 +356,7 @@ impl From<UriSerde> for SinkHealthcheckOptions {
 
 #[async_trait]
 #[typetag::serde(tag = "type")]
-pub trait SinkConfig: core::fmt::Debug + Send + Sync {
+pub trait SinkConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
     async fn build(
         &self,
         cx: SinkContext,
@@ -316,8 +372,11 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
     }
 }
 
+dyn_clone::clone_trait_object!(SinkConfig);
+
 #[derive(Debug, Clone)]
 pub struct SinkContext {
+    pub(super) name: String,
     pub(super) acker: Acker,
     pub(super) healthcheck: SinkHealthcheckOptions,
     pub(super) globals: GlobalOptions,
@@ -327,12 +386,17 @@ impl SinkContext {
     #[cfg(test)]
     pub fn new_test() -> Self {
         Self {
+            name: "test".to_owned(),
             acker: Acker::Null,
             healthcheck: SinkHealthcheckOptions::default(),
             globals: GlobalOptions::default(),
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn acker(&self) -> Acker {
         self.acker.clone()
     }
@@ -353,6 +417,22 @@ pub struct TransformOuter {
     pub inner: Box<dyn TransformConfig>,
 }
 
+/// An ordered list of transforms, each taking the previous one as its input. The
+/// output of a pipeline is referenced by the name of the pipeline.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    pub inputs: Vec<String>,
+    pub transforms: Vec<PipelineTransform>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PipelineTransform {
+    pub name: String,
+    #[serde(flatten)]
+    pub inner: Box<dyn TransformConfig>,
+}
+
 #[async_trait]
 #[typetag::serde(tag = "type")]
 pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
@@ -364,12 +444,24 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
 
     fn transform_type(&self) -> &'static str;
 
+    /// The names of the enrichment tables the transform looks events up in.
+    fn enrichment_tables(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
     /// Allows a transform configuration to expand itself into multiple "child"
     /// transformations to replace it. This allows a transform to act as a macro
-    /// for various patterns.
+    /// for various patterns. Children are named `<name>.<child>`, except for a
+    /// child with an empty name, which keeps the name of the transform.
     fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
         Ok(None)
     }
+
+    /// For a child of an expanded transform, the name of the sibling whose output it takes
+    /// as its input, instead of the inputs of its parent.
+    fn expanded_input(&self) -> Option<&str> {
+        None
+    }
 }
 
 dyn_clone::clone_trait_object!(TransformConfig);
@@ -517,10 +609,22 @@ impl Config {
     /// components it was expanded to as part of the macro process. Does not check that the
     /// identifier is otherwise valid.
     pub fn get_inputs(&self, identifier: &str) -> Vec<String> {
-        self.expansions
-            .get(identifier)
-            .cloned()
-            .unwrap_or_else(|| vec![String::from(identifier)])
+        match self.expansions.get(identifier) {
+            // Children taking the output of a sibling get the events through it.
+            Some(children) => children
+                .iter()
+                .filter(|child| {
+                    self.transforms.get(*child).map_or(true, |transform| {
+                        !transform
+                            .inputs
+                            .iter()
+                            .any(|input| children.contains(input))
+                    })
+                })
+                .cloned()
+                .collect(),
+            None => vec![String::from(identifier)],
+        }
     }
 }
 
@@ -613,6 +717,36 @@ mod test {
         assert_eq!("then", config.global.log_schema.timestamp_key().to_string());
     }
 
+    #[test]
+    fn metadata_keys() {
+        let config = load_from_str(
+            indoc! {r#"
+                [metadata_keys.host]
+                  target = "_vector.host"
+
+                [sources.in]
+                  type = "file"
+                  include = ["/var/log/messages"]
+
+                  [sources.in.metadata_keys.source_type]
+                    enabled = false
+
+                [sinks.out]
+                  type = "console"
+                  inputs = ["in"]
+                  encoding = "json"
+            "#},
+            Some(Format::TOML),
+        )
+        .unwrap();
+
+        let keys = config.sources["in"]
+            .metadata_keys
+            .or(&config.global.metadata_keys);
+        assert_eq!(keys.host.target, Some("_vector.host".to_owned()));
+        assert_eq!(keys.source_type.enabled, Some(false));
+    }
+
     #[test]
     fn config_append() {
         let mut config: ConfigBuilder = format::deserialize(
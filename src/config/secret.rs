@@ -0,0 +1,461 @@
+//! Secrets referenced from config values as `SECRET[backend.key]`. They are
+//! retrieved from the backends configured in the `secret` section when the
+//! configuration is loaded, so that credentials don't need to be written in it.
+
+use super::{format, Format, FormatHint};
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+lazy_static! {
+    static ref SECRET_REFERENCE: Regex =
+        Regex::new(r"SECRET\[([[:word:]]+)\.([[:word:].\-/]+)\]").unwrap();
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SecretBackend {
+    /// Runs a command, which is sent the keys to retrieve on its standard
+    /// input, and answers with their values on its standard output.
+    Exec {
+        command: Vec<String>,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+    /// Reads a JSON file mapping keys to their values.
+    File { path: PathBuf },
+    /// Reads the environment variables named by the keys.
+    Environment,
+}
+
+impl SecretBackend {
+    fn retrieve(&self, keys: &BTreeSet<String>) -> Result<HashMap<String, String>, String> {
+        match self {
+            Self::Exec {
+                command,
+                timeout_secs,
+            } => exec(command, keys, Duration::from_secs(*timeout_secs)),
+            Self::File { path } => {
+                let file = File::open(path)
+                    .map_err(|error| format!("could not read {:?}: {}", path, error))?;
+                serde_json::from_reader(file)
+                    .map_err(|error| format!("could not parse {:?}: {}", path, error))
+            }
+            Self::Environment => Ok(keys
+                .iter()
+                .filter_map(|key| std::env::var(key).ok().map(|value| (key.clone(), value)))
+                .collect()),
+        }
+    }
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+#[derive(Deserialize)]
+struct ExecSecret {
+    value: Option<String>,
+    error: Option<String>,
+}
+
+/// Sends `{"version": "1.0", "secrets": [<keys>]}` to the command, which
+/// answers with `{<key>: {"value": <value>, "error": <error>}}`. The command
+/// is killed if it doesn't exit within `timeout`.
+fn exec(
+    command: &[String],
+    keys: &BTreeSet<String>,
+    timeout: Duration,
+) -> Result<HashMap<String, String>, String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| "`command` must not be empty".to_owned())?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("could not run {:?}: {}", program, error))?;
+
+    let request = serde_json::json!({ "version": "1.0", "secrets": keys });
+    // Dropping the input closes it, ending the request.
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(request.to_string().as_bytes())
+        .map_err(|error| format!("could not write to {:?}: {}", program, error))?;
+
+    // The output is read while waiting, so that the command doesn't block on
+    // writing it.
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let output = thread::spawn(move || {
+        let mut output = Vec::new();
+        stdout.read_to_end(&mut output).map(|_| output)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "{:?} timed out after {} seconds",
+                    program,
+                    timeout.as_secs()
+                ));
+            }
+            Err(error) => return Err(format!("could not run {:?}: {}", program, error)),
+        }
+    };
+    if !status.success() {
+        return Err(format!("{:?} failed with {}", program, status));
+    }
+    let output = output
+        .join()
+        .expect("reading the output doesn't panic")
+        .map_err(|error| format!("could not read the output of {:?}: {}", program, error))?;
+    let response: HashMap<String, ExecSecret> = serde_json::from_slice(&output)
+        .map_err(|error| format!("could not parse the output of {:?}: {}", program, error))?;
+
+    response
+        .into_iter()
+        .map(|(key, secret)| match secret.error {
+            Some(error) => Err(format!("could not retrieve secret {:?}: {}", key, error)),
+            None => Ok((key, secret.value.unwrap_or_default())),
+        })
+        .collect()
+}
+
+/// The secret backends configured in `content`.
+pub fn backends(
+    content: &str,
+    format: FormatHint,
+) -> Result<IndexMap<String, SecretBackend>, Vec<String>> {
+    #[derive(Deserialize)]
+    struct Backends {
+        #[serde(default)]
+        secret: IndexMap<String, SecretBackend>,
+    }
+
+    format::deserialize::<Backends>(content, format).map(|backends| backends.secret)
+}
+
+/// The backends and keys of the secrets referenced in `content`.
+pub fn references(content: &str) -> Vec<(String, String)> {
+    SECRET_REFERENCE
+        .captures_iter(content)
+        .map(|caps| (caps[1].to_owned(), caps[2].to_owned()))
+        .collect()
+}
+
+/// Retrieves the given keys from their backends, as a map of backend names to
+/// the secrets retrieved from them.
+pub fn retrieve(
+    backends: &IndexMap<String, SecretBackend>,
+    keys: &IndexMap<String, BTreeSet<String>>,
+) -> Result<HashMap<String, HashMap<String, String>>, Vec<String>> {
+    let mut secrets = HashMap::new();
+    let mut errors = Vec::new();
+    for (name, keys) in keys {
+        match backends[name].retrieve(keys) {
+            Ok(mut retrieved) => {
+                for key in keys {
+                    if !retrieved.contains_key(key) {
+                        errors.push(format!(
+                            "Secret backend \"{}\": secret {:?} not found",
+                            name, key
+                        ));
+                    }
+                }
+                retrieved.retain(|key, _| keys.contains(key));
+                secrets.insert(name.clone(), retrieved);
+            }
+            Err(error) => errors.push(format!("Secret backend \"{}\": {}", name, error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(secrets)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Deserializes `content`, replacing the secrets referenced in its string
+/// values with their values. The values are replaced once the content is
+/// parsed, so that they are kept as they are whatever characters they contain.
+pub fn deserialize<T: DeserializeOwned>(
+    content: &str,
+    format: FormatHint,
+    secrets: &HashMap<String, HashMap<String, String>>,
+) -> Result<T, Vec<String>> {
+    if !SECRET_REFERENCE.is_match(content) {
+        return format::deserialize(content, format);
+    }
+
+    let mut replace = |value: &mut String| *value = interpolate(value, secrets);
+    match format.unwrap_or_default() {
+        Format::TOML => {
+            let mut value: toml::Value = format::deserialize(content, format)?;
+            visit_toml(&mut value, &mut replace);
+            value.try_into().map_err(|e| vec![e.to_string()])
+        }
+        Format::YAML => {
+            let mut value: serde_yaml::Value = format::deserialize(content, format)?;
+            visit_yaml(&mut value, &mut replace);
+            serde_yaml::from_value(value).map_err(|e| vec![e.to_string()])
+        }
+        Format::JSON => {
+            let mut value: serde_json::Value = format::deserialize(content, format)?;
+            visit_json(&mut value, &mut replace);
+            serde_json::from_value(value).map_err(|e| vec![e.to_string()])
+        }
+    }
+}
+
+fn visit_toml(value: &mut toml::Value, f: &mut dyn FnMut(&mut String)) {
+    match value {
+        toml::Value::String(string) => f(string),
+        toml::Value::Array(values) => {
+            for value in values {
+                visit_toml(value, f);
+            }
+        }
+        toml::Value::Table(table) => {
+            for (_, value) in table.iter_mut() {
+                visit_toml(value, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn visit_yaml(value: &mut serde_yaml::Value, f: &mut dyn FnMut(&mut String)) {
+    match value {
+        serde_yaml::Value::String(string) => f(string),
+        serde_yaml::Value::Sequence(values) => {
+            for value in values {
+                visit_yaml(value, f);
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            for (_, value) in mapping.iter_mut() {
+                visit_yaml(value, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn visit_json(value: &mut serde_json::Value, f: &mut dyn FnMut(&mut String)) {
+    match value {
+        serde_json::Value::String(string) => f(string),
+        serde_json::Value::Array(values) => {
+            for value in values {
+                visit_json(value, f);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (_, value) in map.iter_mut() {
+                visit_json(value, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces the secrets referenced in `value` with their values.
+fn interpolate(value: &str, secrets: &HashMap<String, HashMap<String, String>>) -> String {
+    SECRET_REFERENCE
+        .replace_all(value, |caps: &Captures<'_>| {
+            secrets
+                .get(&caps[1])
+                .and_then(|secrets| secrets.get(&caps[2]))
+                .cloned()
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Format;
+
+    fn keys(backend: &str, keys: &[&str]) -> IndexMap<String, BTreeSet<String>> {
+        let mut map = IndexMap::new();
+        map.insert(
+            backend.to_owned(),
+            keys.iter().map(|key| key.to_string()).collect(),
+        );
+        map
+    }
+
+    #[test]
+    fn finds_and_replaces_references() {
+        let content = r#"
+            token = "SECRET[vault.datadog_api_key]"
+            password = "prefix-SECRET[files.db/password]"
+        "#;
+
+        assert_eq!(
+            references(content),
+            vec![
+                ("vault".into(), "datadog_api_key".into()),
+                ("files".into(), "db/password".into()),
+            ]
+        );
+
+        let mut secrets = HashMap::new();
+        secrets.insert(
+            "vault".to_owned(),
+            vec![("datadog_api_key".to_owned(), "abc".to_owned())]
+                .into_iter()
+                .collect(),
+        );
+        secrets.insert(
+            "files".to_owned(),
+            vec![("db/password".to_owned(), "d\"e\nf".to_owned())]
+                .into_iter()
+                .collect(),
+        );
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Options {
+            token: String,
+            password: String,
+        }
+        let expected = Options {
+            token: "abc".into(),
+            password: "prefix-d\"e\nf".into(),
+        };
+        assert_eq!(
+            deserialize::<Options>(content, Some(Format::TOML), &secrets).unwrap(),
+            expected
+        );
+        assert_eq!(
+            deserialize::<Options>(
+                "token: SECRET[vault.datadog_api_key]\npassword: prefix-SECRET[files.db/password]",
+                Some(Format::YAML),
+                &secrets
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            deserialize::<Options>(
+                r#"{"token": "SECRET[vault.datadog_api_key]", "password": "prefix-SECRET[files.db/password]"}"#,
+                Some(Format::JSON),
+                &secrets
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn parses_backends() {
+        let backends = backends(
+            r#"
+            [secret.vault]
+            type = "exec"
+            command = ["/usr/bin/vault-secrets"]
+
+            [secret.env]
+            type = "environment"
+
+            [sources.in]
+            type = "stdin"
+            "#,
+            Some(Format::TOML),
+        )
+        .unwrap();
+
+        assert_eq!(backends.keys().collect::<Vec<_>>(), vec!["vault", "env"]);
+    }
+
+    #[test]
+    fn retrieves_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.json");
+        std::fs::write(&path, r#"{"user": "admin", "password": "hunter2"}"#).unwrap();
+
+        let mut backends = IndexMap::new();
+        backends.insert("file".to_owned(), SecretBackend::File { path });
+
+        let secrets = retrieve(&backends, &keys("file", &["password"])).unwrap();
+        assert_eq!(secrets["file"].len(), 1);
+        assert_eq!(secrets["file"]["password"], "hunter2");
+
+        assert_eq!(
+            retrieve(&backends, &keys("file", &["token"])).unwrap_err(),
+            vec![r#"Secret backend "file": secret "token" not found"#.to_owned()]
+        );
+    }
+
+    #[test]
+    fn retrieves_from_environment() {
+        std::env::set_var("VECTOR_TEST_SECRET", "hunter2");
+
+        let mut backends = IndexMap::new();
+        backends.insert("env".to_owned(), SecretBackend::Environment);
+
+        let secrets = retrieve(&backends, &keys("env", &["VECTOR_TEST_SECRET"])).unwrap();
+        assert_eq!(secrets["env"]["VECTOR_TEST_SECRET"], "hunter2");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn retrieves_from_exec() {
+        let script = r#"
+            read request
+            echo "$request" | grep -q '"secrets":\["password"\]' || exit 1
+            echo '{"password": {"value": "hunter2", "error": null}}'
+        "#;
+        let mut backends = IndexMap::new();
+        backends.insert(
+            "exec".to_owned(),
+            SecretBackend::Exec {
+                command: vec!["sh".into(), "-c".into(), script.into()],
+                timeout_secs: 5,
+            },
+        );
+
+        let secrets = retrieve(&backends, &keys("exec", &["password"])).unwrap();
+        assert_eq!(secrets["exec"]["password"], "hunter2");
+
+        assert!(retrieve(&backends, &keys("exec", &["token"])).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_times_out() {
+        let mut backends = IndexMap::new();
+        backends.insert(
+            "exec".to_owned(),
+            SecretBackend::Exec {
+                command: vec!["sleep".into(), "10".into()],
+                timeout_secs: 1,
+            },
+        );
+
+        assert_eq!(
+            retrieve(&backends, &keys("exec", &["password"])).unwrap_err(),
+            vec![r#"Secret backend "exec": "sleep" timed out after 1 seconds"#.to_owned()]
+        );
+    }
+}
@@ -22,6 +22,7 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
     let mut tests = vec![];
     let mut errors = vec![];
 
+    super::compiler::expand_pipelines(&mut builder)?;
     let expansions = super::compiler::expand_macros(&mut builder)?;
 
     // Don't let this escape since it's not validated
@@ -29,7 +30,9 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
         global: builder.global,
         #[cfg(feature = "api")]
         api: builder.api,
+        control: builder.control,
         healthchecks: builder.healthchecks,
+        enrichment_tables: builder.enrichment_tables,
         sources: builder.sources,
         sinks: builder.sinks,
         transforms: builder.transforms,
@@ -37,8 +40,15 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
         expansions,
     };
 
+    // Tests don't run alongside a topology, so their tables are never committed.
+    let enrichment_tables =
+        crate::enrichment_tables::EnrichmentTables::default().stage(&config.enrichment_tables)?;
+
     for test in &config.tests {
-        match build_unit_test(test, &config).await {
+        match enrichment_tables
+            .building(build_unit_test(test, &config))
+            .await
+        {
             Ok(t) => tests.push(t),
             Err(errs) => {
                 let mut test_err = errs.join("\n");
@@ -1260,6 +1270,52 @@ mod tests {
         assert_eq!(tests[0].run().1, Vec::<String>::new());
     }
 
+    #[tokio::test]
+    async fn test_metric_output_conditions() {
+        let config: ConfigBuilder = toml::from_str(indoc! { r#"
+            [transforms.foo]
+              inputs = ["ignored"]
+              type = "filter"
+              condition = '.name == "requests"'
+
+            [[tests]]
+              name = "metric is passed"
+
+              [tests.input]
+                insert_at = "foo"
+                type = "metric"
+                [tests.input.metric]
+                  kind = "incremental"
+                  name = "requests"
+                  [tests.input.metric.tags]
+                    host = "localhost"
+                  [tests.input.metric.counter]
+                    value = 100.0
+
+              [[tests.outputs]]
+                extract_from = "foo"
+                conditions = ['.name == "requests" && .tags.host == "localhost" && .value == 100.0']
+
+            [[tests]]
+              name = "metric is dropped"
+              no_outputs_from = ["foo"]
+
+              [tests.input]
+                insert_at = "foo"
+                type = "metric"
+                [tests.input.metric]
+                  kind = "incremental"
+                  name = "errors"
+                  [tests.input.metric.counter]
+                    value = 1.0
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+        assert_eq!(tests[1].run().1, Vec::<String>::new());
+    }
+
     #[tokio::test]
     async fn test_success_over_gap() {
         let config: ConfigBuilder = toml::from_str(indoc! { r#"
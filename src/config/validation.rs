@@ -1,5 +1,5 @@
 use super::{builder::ConfigBuilder, DataType, Resource};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let mut errors = vec![];
@@ -12,6 +12,14 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         errors.push("No sinks defined in the config.".to_owned());
     }
 
+    if matches!(config.global.expire_metrics_secs, Some(secs) if !(secs > 0.0)) {
+        errors.push("The `expire_metrics_secs` option must be positive.".to_owned());
+    }
+
+    if let Err(error) = config.control.validate() {
+        errors.push(error);
+    }
+
     // Helper for below
     fn tagged<'a>(
         tag: &'static str,
@@ -77,7 +85,7 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let source_resources = config
         .sources
         .iter()
-        .map(|(name, config)| (name, config.resources()));
+        .map(|(name, config)| (name, config.inner.resources()));
     let sink_resources = config
         .sinks
         .iter()
@@ -100,6 +108,32 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+pub fn check_enrichment_tables(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let errors = config
+        .transforms
+        .iter()
+        .flat_map(|(name, transform)| {
+            transform
+                .inner
+                .enrichment_tables()
+                .into_iter()
+                .filter(|table| !config.enrichment_tables.contains_key(*table))
+                .map(move |table| {
+                    format!(
+                        "Transform \"{}\" looks events up in unknown enrichment table \"{}\"",
+                        name, table
+                    )
+                })
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub fn warnings(config: &ConfigBuilder) -> Vec<String> {
     let mut warnings = vec![];
 
@@ -129,6 +163,84 @@ pub fn warnings(config: &ConfigBuilder) -> Vec<String> {
     warnings
 }
 
+/// Components reading the events of sources with different log schemas would have to
+/// find their fields under different keys, so the sources meeting at a transform or sink,
+/// directly or through other transforms, must share their `log_schema`. They are only
+/// reported at the component where they first meet.
+pub fn check_log_schemas(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+
+    let consumers = config
+        .transforms
+        .iter()
+        .map(|(name, transform)| ("transform", name, &transform.inputs))
+        .chain(
+            config
+                .sinks
+                .iter()
+                .map(|(name, sink)| ("sink", name, &sink.inputs)),
+        );
+    for (consumer_type, name, inputs) in consumers {
+        let upstream = inputs
+            .iter()
+            .map(|input| upstream_sources(config, input))
+            .collect::<Vec<_>>();
+        if upstream
+            .iter()
+            .any(|sources| mismatched_log_schemas(config, sources).is_some())
+        {
+            continue;
+        }
+
+        let sources = upstream.into_iter().flatten().collect();
+        if let Some((first, other)) = mismatched_log_schemas(config, &sources) {
+            errors.push(format!(
+                "{} {:?} receives events of sources with different log schemas: {:?} and {:?}",
+                capitalize(consumer_type),
+                name,
+                first,
+                other
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The sources whose events reach `input`, through any number of transforms.
+fn upstream_sources<'a>(config: &'a ConfigBuilder, input: &'a String) -> BTreeSet<&'a String> {
+    let mut sources = BTreeSet::new();
+    let mut visited = HashSet::new();
+    let mut pending = vec![input];
+    while let Some(name) = pending.pop() {
+        if let Some((name, _)) = config.sources.get_key_value(name) {
+            sources.insert(name);
+        } else if let Some(transform) = config.transforms.get(name) {
+            if visited.insert(name) {
+                pending.extend(&transform.inputs);
+            }
+        }
+    }
+    sources
+}
+
+/// Two of `sources` that don't share their log schema, if any.
+fn mismatched_log_schemas<'a>(
+    config: &ConfigBuilder,
+    sources: &BTreeSet<&'a String>,
+) -> Option<(&'a String, &'a String)> {
+    let mut sources = sources.iter().copied();
+    let first = sources.next()?;
+    let log_schema = &config.sources[first].log_schema;
+    sources
+        .find(|source| config.sources[*source].log_schema != *log_schema)
+        .map(|other| (first, other))
+}
+
 pub fn typecheck(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     Graph::from(config).typecheck()
 }
@@ -256,7 +368,7 @@ impl From<&ConfigBuilder> for Graph {
 
         // TODO: validate that node names are unique across sources/transforms/sinks?
         for (name, config) in config.sources.iter() {
-            graph.add_source(name, config.output_type());
+            graph.add_source(name, config.inner.output_type());
         }
 
         for (name, config) in config.transforms.iter() {
@@ -486,4 +598,103 @@ mod test {
 
         assert_eq!(Ok(()), graph.typecheck());
     }
+
+    #[test]
+    fn rejects_inputs_with_different_log_schemas() {
+        let config: ConfigBuilder = toml::from_str(
+            r#"
+            [sources.in1]
+            type = "stdin"
+            log_schema.message_key = "msg"
+
+            [sources.in2]
+            type = "stdin"
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in1", "in2"]
+            encoding = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            check_log_schemas(&config),
+            Err(vec![
+                r#"Sink "out" receives events of sources with different log schemas: "in1" and "in2""#
+                    .to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_sources_with_different_log_schemas_meeting_downstream() {
+        let config: ConfigBuilder = toml::from_str(
+            r#"
+            [sources.in1]
+            type = "stdin"
+            log_schema.message_key = "msg"
+
+            [sources.in2]
+            type = "stdin"
+
+            [transforms.add1]
+            type = "add_fields"
+            inputs = ["in1"]
+            fields.source = "in1"
+
+            [transforms.add2]
+            type = "add_fields"
+            inputs = ["in2"]
+            fields.source = "in2"
+
+            [transforms.add]
+            type = "add_fields"
+            inputs = ["add1", "add2"]
+            fields.merged = true
+
+            [sinks.out]
+            type = "console"
+            inputs = ["add"]
+            encoding = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            check_log_schemas(&config),
+            Err(vec![
+                r#"Transform "add" receives events of sources with different log schemas: "in1" and "in2""#
+                    .to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn allows_sources_with_the_same_log_schema_to_meet() {
+        let config: ConfigBuilder = toml::from_str(
+            r#"
+            [sources.in1]
+            type = "stdin"
+            log_schema.message_key = "msg"
+
+            [sources.in2]
+            type = "stdin"
+            log_schema.message_key = "msg"
+
+            [transforms.add]
+            type = "add_fields"
+            inputs = ["in1"]
+            fields.source = "in1"
+
+            [sinks.out]
+            type = "console"
+            inputs = ["add", "in2"]
+            encoding = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(check_log_schemas(&config), Ok(()));
+    }
 }
@@ -1,10 +1,21 @@
 use regex::{Captures, Regex};
 use std::collections::HashMap;
 
-/// (result, warnings)
-pub fn interpolate(input: &str, vars: &HashMap<String, String>) -> (String, Vec<String>) {
+/// Replaces the variables referenced in `input` with their values.
+///
+/// An unset variable is replaced with the default given by `${VAR:-default}`,
+/// fails with the message given by `${VAR:?message}`, and otherwise is
+/// replaced with an empty string, or fails if `strict` is set.
+///
+/// Ok((result, warnings)) or Err(errors)
+pub fn interpolate(
+    input: &str,
+    vars: &HashMap<String, String>,
+    strict: bool,
+) -> Result<(String, Vec<String>), Vec<String>> {
     let mut warnings = Vec::new();
-    let re = Regex::new(r"\$\$|\$(\w+)|\$\{(\w+)(?::-([^}]+)?)?\}").unwrap();
+    let mut errors = Vec::new();
+    let re = Regex::new(r"\$\$|\$(\w+)|\$\{(\w+)(?:(:-|:\?)([^}]*))?\}").unwrap();
     let interpolated = re
         .replace_all(input, |caps: &Captures<'_>| {
             caps.get(1)
@@ -12,22 +23,45 @@ pub fn interpolate(input: &str, vars: &HashMap<String, String>) -> (String, Vec<
                 .map(|m| m.as_str())
                 .map(|name| {
                     vars.get(name).map(|val| val.as_str()).unwrap_or_else(|| {
-                        caps.get(3).map(|m| m.as_str()).unwrap_or_else(|| {
-                            warnings.push(format!("Unknown env var in config. name = {:?}", name));
-                            ""
-                        })
+                        let operand = caps.get(4).map_or("", |m| m.as_str());
+                        match caps.get(3).map(|m| m.as_str()) {
+                            Some(":-") => operand,
+                            Some(_) => {
+                                errors.push(format!(
+                                    "Missing env var in config. name = {:?}, message = {:?}",
+                                    name, operand
+                                ));
+                                ""
+                            }
+                            None if strict => {
+                                errors
+                                    .push(format!("Unknown env var in config. name = {:?}", name));
+                                ""
+                            }
+                            None => {
+                                warnings
+                                    .push(format!("Unknown env var in config. name = {:?}", name));
+                                ""
+                            }
+                        }
                     })
                 })
                 .unwrap_or("$")
                 .to_string()
         })
         .into_owned();
-    (interpolated, warnings)
+    if errors.is_empty() {
+        Ok((interpolated, warnings))
+    } else {
+        Err(errors)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::interpolate;
+    use std::collections::HashMap;
+
     #[test]
     fn interpolation() {
         let vars = vec![
@@ -36,6 +70,9 @@ mod test {
         ]
         .into_iter()
         .collect();
+        let interpolate = |input: &str, vars: &HashMap<String, String>| {
+            super::interpolate(input, vars, false).unwrap()
+        };
 
         assert_eq!("dogs", interpolate("$FOO", &vars).0);
         assert_eq!("dogs", interpolate("${FOO}", &vars).0);
@@ -56,5 +93,36 @@ mod test {
         );
         assert_eq!("${:-cats}", interpolate("${:-cats}", &vars).0);
         assert_eq!("", interpolate("${NOT:-}", &vars).0);
+        assert_eq!("dogs", interpolate("${FOO:?not set}", &vars).0);
+    }
+
+    #[test]
+    fn required_variables() {
+        let vars = vec![("FOO".into(), "dogs".into())].into_iter().collect();
+
+        assert_eq!(
+            interpolate("${NOT:?must be set}", &vars, false).unwrap_err(),
+            vec![r#"Missing env var in config. name = "NOT", message = "must be set""#.to_owned()]
+        );
+        assert!(interpolate("${NOT:?}", &vars, false).is_err());
+    }
+
+    #[test]
+    fn strict_mode() {
+        let vars = vec![("FOO".into(), "dogs".into())].into_iter().collect();
+
+        let (result, warnings) = interpolate("x${NOT}", &vars, false).unwrap();
+        assert_eq!(result, "x");
+        assert_eq!(warnings.len(), 1);
+
+        assert_eq!(
+            interpolate("$FOO ${NOT} $OTHER", &vars, true).unwrap_err(),
+            vec![
+                r#"Unknown env var in config. name = "NOT""#.to_owned(),
+                r#"Unknown env var in config. name = "OTHER""#.to_owned(),
+            ]
+        );
+        // Defaults still apply in strict mode.
+        assert_eq!(interpolate("${NOT:-cats}", &vars, true).unwrap().0, "cats");
     }
 }
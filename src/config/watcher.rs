@@ -121,9 +121,15 @@ fn add_paths(watcher: &mut RecommendedWatcher, config_paths: &[PathBuf]) -> Resu
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::{temp_file, trace_init};
+    use crate::{
+        config::watched_paths,
+        test_util::{temp_file, trace_init},
+    };
     use std::time::Duration;
-    use std::{fs::File, io::Write};
+    use std::{
+        fs::{self, File},
+        io::Write,
+    };
     #[cfg(unix)]
     use tokio::signal::unix::{signal, SignalKind};
 
@@ -169,4 +175,35 @@ mod tests {
             panic!("Test timed out");
         }
     }
+
+    #[cfg(not(target_os = "macos"))] // https://github.com/timberio/vector/issues/5000
+    #[tokio::test]
+    async fn dir_file_removed() {
+        trace_init();
+
+        let delay = Duration::from_secs(3);
+        let dir = tempfile::tempdir().unwrap();
+        let removed = dir.path().join("removed.toml");
+        let kept = dir.path().join("kept.toml");
+        File::create(&removed).unwrap();
+        let mut file = File::create(&kept).unwrap();
+
+        let config_paths = [(dir.path().to_path_buf(), None)];
+        let files = [(kept, None), (removed.clone(), None)];
+        let mut signal = signal(SignalKind::hangup()).expect("Signal handlers should not panic.");
+        let _ = spawn_thread(&watched_paths(&config_paths, &files), delay).unwrap();
+
+        fs::remove_file(&removed).unwrap();
+        if tokio::time::timeout(delay * 5, signal.recv())
+            .await
+            .is_err()
+        {
+            panic!("Test timed out");
+        }
+
+        // The directory is still watched once the file is gone.
+        if !test(&mut file, delay * 5).await {
+            panic!("Test timed out");
+        }
+    }
 }
@@ -0,0 +1,288 @@
+//! Registration of this instance with a remote control plane. Once started, reports are
+//! posted to the control plane's endpoint on every heartbeat, carrying the fingerprint of
+//! the running config and a summary of the internal metrics, so that a fleet of instances
+//! can be kept track of from one place.
+
+use crate::{
+    config::Config,
+    event::{metric::MetricValue, Event},
+    http::HttpClient,
+    internal_events::{ControlPlaneReportFailed, ControlPlaneReported},
+    metrics::{capture_metrics, get_controller},
+    sinks::util::retries::ExponentialBackoff,
+    tls::MaybeTlsSettings,
+};
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use snafu::{ResultExt, Snafu};
+use std::time::{Duration, Instant};
+use tokio::{
+    sync::{oneshot, watch},
+    time::{delay_for, interval},
+};
+
+#[derive(Debug, Snafu)]
+enum ControlPlaneError {
+    #[snafu(display("Invalid control plane endpoint: {}", source))]
+    InvalidEndpoint { source: http::uri::InvalidUri },
+    #[snafu(display("Unexpected status from control plane: {}", status))]
+    UnexpectedStatus { status: StatusCode },
+}
+
+#[derive(Debug, Serialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ReportKind {
+    /// The first report of an instance, sent when it starts.
+    Register,
+    Heartbeat,
+}
+
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    kind: ReportKind,
+    hostname: &'a str,
+    version: &'a str,
+    uptime_seconds: u64,
+    config_fingerprint: &'a str,
+    metrics: MetricsSummary,
+}
+
+/// The totals of the internal metrics that describe the throughput and health of an instance.
+#[derive(Debug, Default, Serialize, PartialEq)]
+struct MetricsSummary {
+    processed_events_total: f64,
+    processed_bytes_total: f64,
+    errors_total: f64,
+}
+
+impl MetricsSummary {
+    fn capture() -> Self {
+        get_controller()
+            .map(|controller| Self::from_metrics(capture_metrics(controller)))
+            .unwrap_or_default()
+    }
+
+    fn from_metrics(metrics: impl Iterator<Item = Event>) -> Self {
+        let mut summary = Self::default();
+        for event in metrics {
+            let metric = event.into_metric();
+            let value = match metric.data.value {
+                MetricValue::Counter { value } => value,
+                _ => continue,
+            };
+            match metric.name() {
+                "processed_events_total" => summary.processed_events_total += value,
+                "processed_bytes_total" => summary.processed_bytes_total += value,
+                name if name.ends_with("_errors_total") => summary.errors_total += value,
+                _ => {}
+            }
+        }
+        summary
+    }
+}
+
+/// Computes a fingerprint of the components and global options of a config, which changes
+/// whenever any of them do.
+pub fn fingerprint(config: &Config) -> String {
+    let json = serde_json::to_vec(&(
+        &config.global,
+        &config.sources,
+        &config.transforms,
+        &config.sinks,
+    ))
+    .expect("Config should serialize");
+    format!("{:x}", Sha256::digest(&json))
+}
+
+pub struct ControlPlane {
+    _shutdown: oneshot::Sender<()>,
+    fingerprint: watch::Sender<String>,
+}
+
+impl ControlPlane {
+    /// Start reporting to the control plane, if the config has one. Reporting stops when
+    /// Self falls out of scope by way of the oneshot sender closing. Changes to the `control`
+    /// options themselves are only picked up by a restart.
+    pub fn start(config: &Config) -> crate::Result<Option<Self>> {
+        let options = &config.control;
+        let endpoint = match &options.endpoint {
+            Some(endpoint) => endpoint.parse::<Uri>().context(InvalidEndpoint)?,
+            None => return Ok(None),
+        };
+
+        let tls = MaybeTlsSettings::from_config(&options.tls, false)?;
+        let client = HttpClient::new(tls)?;
+
+        let (_shutdown, shutdown) = oneshot::channel();
+        let (fingerprint, fingerprints) = watch::channel(self::fingerprint(config));
+
+        let reporter = Reporter {
+            client,
+            endpoint,
+            token: options.token.clone(),
+            retry_attempts: options.retry_attempts,
+            hostname: crate::get_hostname().unwrap_or_default(),
+            version: crate::get_version(),
+            since: Instant::now(),
+        };
+        let heartbeat = Duration::from_secs(options.heartbeat_secs.max(1));
+
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = reporter.run(heartbeat, fingerprints) => {},
+                _ = shutdown => {},
+            }
+        });
+
+        Ok(Some(Self {
+            _shutdown,
+            fingerprint,
+        }))
+    }
+
+    /// Update the fingerprint reported for the running config.
+    pub fn update_config(&self, config: &Config) {
+        let _ = self.fingerprint.broadcast(fingerprint(config));
+    }
+}
+
+struct Reporter {
+    client: HttpClient,
+    endpoint: Uri,
+    token: Option<String>,
+    retry_attempts: usize,
+    hostname: String,
+    version: String,
+    since: Instant,
+}
+
+impl Reporter {
+    async fn run(self, heartbeat: Duration, fingerprints: watch::Receiver<String>) {
+        let mut kind = ReportKind::Register;
+        let mut interval = interval(heartbeat);
+        loop {
+            interval.tick().await;
+
+            let fingerprint = fingerprints.borrow().clone();
+            let report = Report {
+                kind,
+                hostname: &self.hostname,
+                version: &self.version,
+                uptime_seconds: self.since.elapsed().as_secs(),
+                config_fingerprint: &fingerprint,
+                metrics: MetricsSummary::capture(),
+            };
+
+            // Keep registering until the control plane has heard of this instance.
+            if self.report_with_retries(&report).await {
+                kind = ReportKind::Heartbeat;
+            }
+        }
+    }
+
+    async fn report_with_retries(&self, report: &Report<'_>) -> bool {
+        let body = serde_json::to_vec(report).expect("Report should serialize");
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(30));
+
+        for attempt in 1..=self.retry_attempts.max(1) {
+            match self.report(body.clone()).await {
+                Ok(()) => {
+                    emit!(ControlPlaneReported {
+                        endpoint: &self.endpoint
+                    });
+                    return true;
+                }
+                Err(error) => {
+                    emit!(ControlPlaneReportFailed {
+                        error,
+                        attempt,
+                        retry_attempts: self.retry_attempts,
+                    });
+                    if attempt < self.retry_attempts {
+                        delay_for(backoff.next().unwrap()).await;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    async fn report(&self, body: Vec<u8>) -> crate::Result<()> {
+        let mut request = Request::post(&self.endpoint).header("Content-Type", "application/json");
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        let request = request.body(Body::from(body))?;
+
+        let response = self.client.send(request).await?;
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            status => Err(ControlPlaneError::UnexpectedStatus { status }.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{Metric, MetricKind};
+
+    fn counter(name: &str, value: f64) -> Event {
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+        )
+        .into()
+    }
+
+    #[cfg(all(feature = "sinks-blackhole", feature = "sources-stdin"))]
+    fn config(print_amount: usize) -> Config {
+        use crate::{
+            config::ConfigBuilder, sinks::blackhole::BlackholeConfig, sources::stdin::StdinConfig,
+        };
+
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", StdinConfig::default());
+        builder.add_sink(
+            "out",
+            &["in"],
+            BlackholeConfig {
+                print_amount,
+                rate: None,
+            },
+        );
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn summarizes_metrics() {
+        let metrics = vec![
+            counter("processed_events_total", 10.0),
+            counter("processed_events_total", 5.0),
+            counter("processed_bytes_total", 100.0),
+            counter("http_request_errors_total", 2.0),
+            counter("events_in_total", 7.0),
+        ];
+
+        assert_eq!(
+            MetricsSummary::from_metrics(metrics.into_iter()),
+            MetricsSummary {
+                processed_events_total: 15.0,
+                processed_bytes_total: 100.0,
+                errors_total: 2.0,
+            }
+        );
+    }
+
+    #[cfg(all(feature = "sinks-blackhole", feature = "sources-stdin"))]
+    #[test]
+    fn fingerprint_tracks_config_changes() {
+        assert_eq!(fingerprint(&config(1)), fingerprint(&config(1)));
+        assert_ne!(fingerprint(&config(1)), fingerprint(&config(2)));
+    }
+}
@@ -0,0 +1,347 @@
+//! Entry points for running Vector's pipeline engine inside another Rust program.
+//!
+//! A topology is configured with a [`ConfigBuilder`], which takes the same components as
+//! configuration files, and started with [`EmbeddedTopology::start`]. The program feeds
+//! events into the topology through an [`EmbeddedSourceConfig`], and receives the events
+//! that come out of it through an [`EmbeddedSinkConfig`]. Topologies have to be started
+//! from within a Tokio runtime.
+//!
+//! ```no_run
+//! use vector::{
+//!     config::ConfigBuilder,
+//!     embed::{EmbeddedSinkConfig, EmbeddedSourceConfig, EmbeddedTopology},
+//!     Event,
+//! };
+//!
+//! # async fn run() -> Result<(), Vec<String>> {
+//! let (mut tx, source) = EmbeddedSourceConfig::new(100);
+//! let (sink, mut rx) = EmbeddedSinkConfig::new(100);
+//!
+//! let mut builder = ConfigBuilder::default();
+//! builder.add_source("in", source);
+//! builder.add_sink("out", &["in"], sink);
+//!
+//! let topology = EmbeddedTopology::start(builder.build()?).await?;
+//! tx.send(Event::from("message")).await.unwrap();
+//! let event = rx.recv().await;
+//! topology.stop().await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Embedded sources and sinks can't be set in configuration files, and a topology is only
+//! told that they changed on reload when they're new instances, with channels of their own.
+//!
+//! Components written outside of this crate implement [`SourceConfig`], [`TransformConfig`]
+//! or [`SinkConfig`], and are registered under their name with `typetag`, like the
+//! components of this crate.
+
+use crate::{
+    config::{Config, ConfigDiff},
+    shutdown::ShutdownSignal,
+    sinks::util::StreamSink,
+    topology::{self, RunningTopology},
+};
+use async_trait::async_trait;
+use futures::{future, stream::BoxStream, FutureExt, SinkExt, StreamExt};
+use lazy_static::lazy_static;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
+use tokio::sync::mpsc;
+
+pub use crate::{
+    buffers::Acker,
+    config::{DataType, GlobalOptions, SinkConfig, SinkContext, SourceConfig, TransformConfig},
+    sinks::{Healthcheck, VectorSink},
+    sources::Source,
+    transforms::{FunctionTransform, TaskTransform, Transform},
+    Event, Pipeline,
+};
+
+/// A running topology, which keeps running until it's stopped.
+pub struct EmbeddedTopology {
+    topology: RunningTopology,
+    crashed: mpsc::UnboundedReceiver<()>,
+}
+
+impl EmbeddedTopology {
+    /// Builds the components of `config` and starts running them. Fails with the errors of
+    /// the components that couldn't be built, or if a sink fails its healthcheck while
+    /// `healthchecks.require_healthy` is set.
+    pub async fn start(config: Config) -> Result<Self, Vec<String>> {
+        let diff = ConfigDiff::initial(&config);
+        let pieces = topology::builder::build_pieces(&config, &diff, HashMap::new()).await?;
+
+        topology::start_validated(config, diff, pieces)
+            .await
+            .map(|(topology, crashed)| Self { topology, crashed })
+            .ok_or_else(|| vec!["Sinks failed their healthchecks.".to_owned()])
+    }
+
+    /// Replaces the running components with those of `config`, keeping those that didn't
+    /// change. Returns `false` if the new components couldn't be started, in which case
+    /// the previous components keep running.
+    pub async fn reload(&mut self, config: Config) -> bool {
+        self.topology
+            .reload_config_and_respawn(config)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Resolves once one of the components has crashed, after which the topology should
+    /// be stopped.
+    pub async fn crashed(&mut self) {
+        self.crashed.recv().await;
+    }
+
+    /// Stops the sources, and resolves once the events they received have flowed through
+    /// the transforms and sinks.
+    pub async fn stop(self) {
+        self.topology.stop().await
+    }
+}
+
+type Registry<T> = Mutex<HashMap<u64, Weak<T>>>;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    /// The channels of the embedded components, by the id their configs are serialized with.
+    /// Configs are compared and cloned by serializing them, so the id tells instances apart,
+    /// and a config can only be deserialized back into the instance it was serialized from.
+    static ref SOURCES: Registry<Mutex<Option<mpsc::Receiver<Event>>>> = Default::default();
+    static ref SINKS: Registry<mpsc::Sender<Event>> = Default::default();
+}
+
+fn register<T>(registry: &Registry<T>, channel: &Arc<T>) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut registry = registry.lock().unwrap();
+    registry.retain(|_, channel| channel.strong_count() > 0);
+    registry.insert(id, Arc::downgrade(channel));
+    id
+}
+
+fn deserialize_registered<'de, D, T>(
+    deserializer: D,
+    registry: &Registry<T>,
+    config: &str,
+) -> Result<(u64, Arc<T>), D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Registered {
+        id: Option<u64>,
+    }
+
+    let Registered { id } = Registered::deserialize(deserializer)?;
+    id.and_then(|id| Some((id, registry.lock().unwrap().get(&id)?.upgrade()?)))
+        .ok_or_else(|| {
+            de::Error::custom(format!(
+                "`embedded` components can't be configured, only created with `{}::new`.",
+                config
+            ))
+        })
+}
+
+/// A source that emits the events sent to its channel, until the channel is closed or the
+/// topology is stopped.
+#[derive(Clone, Debug, Serialize)]
+pub struct EmbeddedSourceConfig {
+    id: u64,
+    #[serde(skip)]
+    receiver: Arc<Mutex<Option<mpsc::Receiver<Event>>>>,
+}
+
+impl EmbeddedSourceConfig {
+    /// Returns the sender of the source's channel, which holds up to `buffer` events.
+    pub fn new(buffer: usize) -> (mpsc::Sender<Event>, Self) {
+        let (tx, rx) = mpsc::channel(buffer);
+        let receiver = Arc::new(Mutex::new(Some(rx)));
+        let id = register(&SOURCES, &receiver);
+        (tx, Self { id, receiver })
+    }
+}
+
+impl<'de> Deserialize<'de> for EmbeddedSourceConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_registered(deserializer, &SOURCES, "EmbeddedSourceConfig")
+            .map(|(id, receiver)| Self { id, receiver })
+    }
+}
+
+#[async_trait]
+#[typetag::serde(name = "embedded")]
+impl SourceConfig for EmbeddedSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<Source> {
+        let receiver = self
+            .receiver
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("The `embedded` source can only be built once.")?;
+
+        let out = out.sink_map_err(|error| error!(message = "Error sending event.", %error));
+        Ok(Box::pin(async move {
+            receiver.take_until(shutdown).map(Ok).forward(out).await
+        }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "embedded"
+    }
+}
+
+/// A sink that sends the events it receives to its channel.
+#[derive(Clone, Debug, Serialize)]
+pub struct EmbeddedSinkConfig {
+    id: u64,
+    #[serde(skip)]
+    sender: Arc<mpsc::Sender<Event>>,
+}
+
+impl EmbeddedSinkConfig {
+    /// Returns the receiver of the sink's channel, which holds up to `buffer` events.
+    pub fn new(buffer: usize) -> (Self, mpsc::Receiver<Event>) {
+        let (tx, rx) = mpsc::channel(buffer);
+        let sender = Arc::new(tx);
+        let id = register(&SINKS, &sender);
+        (Self { id, sender }, rx)
+    }
+}
+
+impl<'de> Deserialize<'de> for EmbeddedSinkConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_registered(deserializer, &SINKS, "EmbeddedSinkConfig")
+            .map(|(id, sender)| Self { id, sender })
+    }
+}
+
+#[async_trait]
+#[typetag::serde(name = "embedded")]
+impl SinkConfig for EmbeddedSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = EmbeddedSink {
+            sender: mpsc::Sender::clone(&self.sender),
+            acker: cx.acker(),
+        };
+
+        Ok((VectorSink::Stream(Box::new(sink)), future::ok(()).boxed()))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "embedded"
+    }
+}
+
+struct EmbeddedSink {
+    sender: mpsc::Sender<Event>,
+    acker: Acker,
+}
+
+#[async_trait]
+impl StreamSink for EmbeddedSink {
+    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        while let Some(event) = input.next().await {
+            if self.sender.send(event).await.is_err() {
+                error!(message = "Receiver of the embedded sink was dropped.");
+                return Err(());
+            }
+            self.acker.ack(1);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{log_schema, ConfigBuilder};
+
+    #[tokio::test]
+    async fn events_flow_through_topology() {
+        let (mut tx, source) = EmbeddedSourceConfig::new(1);
+        let (sink, mut rx) = EmbeddedSinkConfig::new(1);
+
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", source);
+        builder.add_sink("out", &["in"], sink);
+
+        let topology = EmbeddedTopology::start(builder.build().unwrap())
+            .await
+            .unwrap();
+
+        tx.send(Event::from("message")).await.unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "message".into());
+
+        topology.stop().await;
+    }
+
+    #[tokio::test]
+    async fn reloads_new_instances_only() {
+        let (mut first_tx, first) = EmbeddedSourceConfig::new(1);
+        let (mut second_tx, second) = EmbeddedSourceConfig::new(1);
+        let (sink, mut rx) = EmbeddedSinkConfig::new(1);
+
+        let config = |source: &EmbeddedSourceConfig| {
+            let mut builder = ConfigBuilder::default();
+            builder.add_source("in", source.clone());
+            builder.add_sink("out", &["in"], sink.clone());
+            builder.build().unwrap()
+        };
+
+        let mut topology = EmbeddedTopology::start(config(&first)).await.unwrap();
+
+        // The source is kept, rather than built again from its drained channel.
+        assert!(topology.reload(config(&first)).await);
+        first_tx.send(Event::from("first")).await.unwrap();
+        assert_eq!(
+            rx.recv().await.unwrap().as_log()[log_schema().message_key()],
+            "first".into()
+        );
+
+        assert!(topology.reload(config(&second)).await);
+        second_tx.send(Event::from("second")).await.unwrap();
+        assert_eq!(
+            rx.recv().await.unwrap().as_log()[log_schema().message_key()],
+            "second".into()
+        );
+
+        topology.stop().await;
+    }
+
+    #[test]
+    fn components_cant_be_configured() {
+        let config = r#"
+            [sources.in]
+            type = "embedded"
+
+            [sinks.out]
+            type = "embedded"
+            inputs = ["in"]
+        "#;
+
+        assert!(crate::config::load_from_str(config, None).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use super::Table;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::path::PathBuf;
+
+#[derive(Debug, Snafu)]
+enum FileError {
+    #[snafu(display("could not read {:?}: {}", path, source))]
+    ReadFile { path: PathBuf, source: csv::Error },
+    #[snafu(display("delimiter {:?} must be a single ASCII character", delimiter))]
+    InvalidDelimiter { delimiter: char },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    /// The file the rows of the table are read from.
+    pub path: PathBuf,
+    #[serde(default)]
+    pub encoding: Encoding,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Encoding {
+    Csv {
+        #[serde(default = "default_delimiter")]
+        delimiter: char,
+        /// Whether the first line of the file names the columns. Otherwise
+        /// columns are named by their position, starting at `0`.
+        #[serde(default = "crate::serde::default_true")]
+        include_headers: bool,
+    },
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Self::Csv {
+            delimiter: default_delimiter(),
+            include_headers: true,
+        }
+    }
+}
+
+impl FileConfig {
+    pub(super) fn build(&self) -> crate::Result<Table> {
+        let Encoding::Csv {
+            delimiter,
+            include_headers,
+        } = self.encoding;
+
+        if !delimiter.is_ascii() {
+            return Err(FileError::InvalidDelimiter { delimiter }.into());
+        }
+
+        let path = &self.path;
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter as u8)
+            .has_headers(include_headers)
+            .from_path(path)
+            .context(ReadFile { path })?;
+
+        let headers = if include_headers {
+            let headers = reader.headers().context(ReadFile { path })?;
+            Some(headers.iter().map(Into::into).collect::<Vec<String>>())
+        } else {
+            None
+        };
+        let rows = reader
+            .records()
+            .map(|record| record.map(|record| record.iter().map(Into::into).collect()))
+            .collect::<Result<Vec<Vec<String>>, _>>()
+            .context(ReadFile { path })?;
+        let columns = headers.unwrap_or_else(|| {
+            let count = rows.first().map_or(0, Vec::len);
+            (0..count).map(|position| position.to_string()).collect()
+        });
+
+        Ok(Table::new(columns, rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn build(contents: &str, encoding: &str) -> crate::Result<Table> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("table.csv");
+        fs::write(&path, contents).unwrap();
+
+        let config = toml::from_str::<FileConfig>(&format!(
+            "path = {:?}\n{}",
+            path.to_str().unwrap(),
+            encoding
+        ))
+        .unwrap();
+        config.build()
+    }
+
+    #[test]
+    fn reads_csv_with_headers() {
+        let table = build("id,team\n1,platform\n2,\"billing, eu\"\n", "").unwrap();
+
+        assert_eq!(table.columns(), &["id".to_string(), "team".to_string()]);
+        let row = table.find(&[("id", "2")]).unwrap().unwrap();
+        assert_eq!(row["team"], "billing, eu");
+    }
+
+    #[test]
+    fn reads_csv_without_headers() {
+        let table = build(
+            "1;platform\n2;billing\n",
+            "encoding.type = \"csv\"\nencoding.delimiter = \";\"\nencoding.include_headers = false",
+        )
+        .unwrap();
+
+        assert_eq!(table.columns(), &["0".to_string(), "1".to_string()]);
+        let row = table.find(&[("0", "1")]).unwrap().unwrap();
+        assert_eq!(row["1"], "platform");
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert!(build("id,team\n1,platform,extra\n", "").is_err());
+    }
+}
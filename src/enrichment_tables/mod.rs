@@ -0,0 +1,308 @@
+//! Tables of reference data that transforms join events against, such as a
+//! mapping of user IDs to teams. Tables are loaded every time the topology is
+//! built, so reloading the configuration also reloads their contents. Each
+//! topology has its own tables.
+
+use crate::internal_events::EnrichmentTableLoaded;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::{Arc, Mutex, RwLock},
+};
+
+mod file;
+
+pub use file::{Encoding, FileConfig};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EnrichmentTableConfig {
+    File(FileConfig),
+}
+
+impl EnrichmentTableConfig {
+    fn build(&self) -> crate::Result<Table> {
+        match self {
+            Self::File(config) => config.build(),
+        }
+    }
+}
+
+/// A row of a table, mapping the names of its columns to its values.
+pub type Row = BTreeMap<String, String>;
+
+#[derive(Debug)]
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+    /// Maps the values of a set of columns to the first row having them. An
+    /// index is built the first time rows are looked up by its columns.
+    indexes: RwLock<HashMap<Vec<usize>, HashMap<Vec<String>, usize>>>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        Self {
+            columns,
+            rows,
+            indexes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Finds the first row whose columns have the given values.
+    pub fn find(&self, conditions: &[(&str, &str)]) -> Result<Option<Row>, String> {
+        let columns = conditions
+            .iter()
+            .map(|(column, _)| {
+                self.columns
+                    .iter()
+                    .position(|name| name == column)
+                    .ok_or_else(|| format!("no column named {:?}", column))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let key = conditions
+            .iter()
+            .map(|(_, value)| value.to_string())
+            .collect::<Vec<_>>();
+
+        if let Some(index) = self.indexes.read().unwrap().get(&columns) {
+            return Ok(index.get(&key).map(|&row| self.row(row)));
+        }
+
+        let index = self.build_index(&columns);
+        let row = index.get(&key).map(|&row| self.row(row));
+        self.indexes.write().unwrap().insert(columns, index);
+        Ok(row)
+    }
+
+    fn build_index(&self, columns: &[usize]) -> HashMap<Vec<String>, usize> {
+        let mut index = HashMap::with_capacity(self.rows.len());
+        for (position, row) in self.rows.iter().enumerate() {
+            let key = columns.iter().map(|&column| row[column].clone()).collect();
+            index.entry(key).or_insert(position);
+        }
+        index
+    }
+
+    fn row(&self, position: usize) -> Row {
+        self.columns
+            .iter()
+            .cloned()
+            .zip(self.rows[position].iter().cloned())
+            .collect()
+    }
+}
+
+/// A loaded table, whose contents are replaced when the table is loaded again.
+#[derive(Clone, Debug)]
+pub struct TableHandle(Arc<RwLock<Arc<Table>>>);
+
+impl TableHandle {
+    pub fn new(table: Table) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(table))))
+    }
+
+    /// The current contents of the table.
+    pub fn get(&self) -> Arc<Table> {
+        Arc::clone(&self.0.read().unwrap())
+    }
+}
+
+tokio::task_local! {
+    static BUILDING: StagedTables;
+}
+
+/// The enrichment tables of a topology, by name.
+#[derive(Clone, Debug, Default)]
+pub struct EnrichmentTables(Arc<Mutex<HashMap<String, TableHandle>>>);
+
+impl EnrichmentTables {
+    /// Loads the configured tables, without replacing the contents of the
+    /// tables in use until the staged tables are committed. Nothing is staged
+    /// if any table fails to load.
+    pub fn stage(
+        &self,
+        configs: &IndexMap<String, EnrichmentTableConfig>,
+    ) -> Result<StagedTables, Vec<String>> {
+        let mut loaded = Vec::new();
+        let mut errors = Vec::new();
+        for (name, config) in configs {
+            match config.build() {
+                Ok(table) => loaded.push((name, table)),
+                Err(error) => errors.push(format!("Enrichment table \"{}\": {}", name, error)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let handles = self.0.lock().expect("Enrichment tables mutex is poisoned");
+        let tables = loaded
+            .into_iter()
+            .map(|(name, table)| {
+                let table = Arc::new(table);
+                let handle = handles
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_else(|| TableHandle(Arc::new(RwLock::new(Arc::clone(&table)))));
+                (name.clone(), (handle, table))
+            })
+            .collect();
+
+        Ok(StagedTables {
+            registry: self.clone(),
+            tables,
+        })
+    }
+}
+
+/// Tables loaded while building a topology. Components being built look
+/// them up, but running components keep seeing the previous contents of the
+/// tables until they're committed.
+#[derive(Clone, Debug, Default)]
+pub struct StagedTables {
+    registry: EnrichmentTables,
+    tables: HashMap<String, (TableHandle, Arc<Table>)>,
+}
+
+impl StagedTables {
+    /// The tables of the topology the tables are staged for.
+    pub fn registry(&self) -> &EnrichmentTables {
+        &self.registry
+    }
+
+    /// Builds components with `future`, letting them look up the staged tables.
+    pub async fn building<F: Future>(&self, future: F) -> F::Output {
+        BUILDING.scope(self.clone(), future).await
+    }
+
+    /// Replaces the contents of the tables of the topology with the staged
+    /// ones, and drops the tables that are no longer configured.
+    pub fn commit(&self) {
+        let mut handles = self
+            .registry
+            .0
+            .lock()
+            .expect("Enrichment tables mutex is poisoned");
+        handles.retain(|name, _| self.tables.contains_key(name));
+        for (name, (handle, table)) in &self.tables {
+            emit!(EnrichmentTableLoaded {
+                name,
+                rows: table.len(),
+            });
+            *handle.0.write().unwrap() = Arc::clone(table);
+            handles.insert(name.clone(), handle.clone());
+        }
+    }
+}
+
+/// The staged table with the given name, for a component being built. Along
+/// with its handle, returns the contents the table will have once committed.
+pub fn get(name: &str) -> Option<(TableHandle, Arc<Table>)> {
+    BUILDING
+        .try_with(|staged| staged.tables.get(name).cloned())
+        .ok()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        Table::new(
+            vec!["id".into(), "region".into(), "team".into()],
+            vec![
+                vec!["1".into(), "eu".into(), "platform".into()],
+                vec!["2".into(), "us".into(), "billing".into()],
+                vec!["2".into(), "eu".into(), "search".into()],
+            ],
+        )
+    }
+
+    #[test]
+    fn finds_rows() {
+        let table = table();
+
+        let row = table.find(&[("id", "2")]).unwrap().unwrap();
+        assert_eq!(row["team"], "billing");
+
+        let row = table.find(&[("id", "2"), ("region", "eu")]).unwrap();
+        assert_eq!(row.unwrap()["team"], "search");
+
+        // The index built by the first lookup is used by the next ones.
+        assert_eq!(table.find(&[("id", "3")]).unwrap(), None);
+        assert_eq!(table.indexes.read().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        assert!(table().find(&[("user", "1")]).is_err());
+    }
+
+    fn configs(path: &std::path::Path) -> IndexMap<String, EnrichmentTableConfig> {
+        let mut configs = IndexMap::new();
+        configs.insert(
+            "teams".to_owned(),
+            EnrichmentTableConfig::File(FileConfig {
+                path: path.into(),
+                encoding: Encoding::default(),
+            }),
+        );
+        configs
+    }
+
+    #[tokio::test]
+    async fn commits_staged_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("teams.csv");
+        std::fs::write(&path, "id,team\n1,platform\n").unwrap();
+
+        let tables = EnrichmentTables::default();
+        let staged = tables.stage(&configs(&path)).unwrap();
+        let (handle, _) = staged.building(async { get("teams") }).await.unwrap();
+        assert!(get("teams").is_none());
+        staged.commit();
+
+        std::fs::write(&path, "id,team\n1,platform\n2,billing\n").unwrap();
+        let staged = tables.stage(&configs(&path)).unwrap();
+        let (restaged, table) = staged.building(async { get("teams") }).await.unwrap();
+        assert_eq!(table.len(), 2);
+        // Running components keep the previous contents until the tables are committed.
+        assert_eq!(handle.get().len(), 1);
+        staged.commit();
+        assert_eq!(handle.get().len(), 2);
+        assert!(Arc::ptr_eq(&handle.0, &restaged.0));
+
+        // Other topologies don't see the tables.
+        let other = EnrichmentTables::default().stage(&IndexMap::new()).unwrap();
+        assert!(other.building(async { get("teams") }).await.is_none());
+        other.commit();
+        assert_eq!(tables.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn replaces_contents_of_handles() {
+        let handle = TableHandle::new(table());
+        let replaced = handle.get();
+
+        *handle.0.write().unwrap() = Arc::new(Table::new(vec!["id".into()], vec![]));
+
+        assert_eq!(replaced.len(), 3);
+        assert!(handle.get().is_empty());
+    }
+}
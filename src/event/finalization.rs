@@ -0,0 +1,158 @@
+use futures::FutureExt;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::oneshot;
+
+/// The delivery status of events. When the status of a batch is updated more than once,
+/// the worst status is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum EventStatus {
+    /// All the events were accepted by the sinks, or dropped on purpose, as by a filter.
+    Delivered = 0,
+    /// Some of the events couldn't be delivered, but sending them again may succeed.
+    Errored = 1,
+    /// Some of the events were rejected by the sinks, and won't ever be delivered.
+    Failed = 2,
+}
+
+impl EventStatus {
+    fn from_u8(status: u8) -> Self {
+        match status {
+            0 => Self::Delivered,
+            1 => Self::Errored,
+            _ => Self::Failed,
+        }
+    }
+}
+
+/// Notifies a source once all the events it received in one batch have been delivered.
+///
+/// Events of the batch, and all their copies, hold a reference to the notifier. Once the
+/// last of them is dropped the status of the batch is sent to its `BatchStatusReceiver`.
+pub struct BatchNotifier {
+    status: AtomicU8,
+    notifier: Mutex<Option<oneshot::Sender<EventStatus>>>,
+}
+
+impl BatchNotifier {
+    pub fn new_with_receiver() -> (Arc<Self>, BatchStatusReceiver) {
+        let (tx, rx) = oneshot::channel();
+        let notifier = Self {
+            status: AtomicU8::new(EventStatus::Delivered as u8),
+            notifier: Mutex::new(Some(tx)),
+        };
+        (Arc::new(notifier), BatchStatusReceiver(rx))
+    }
+
+    pub fn update_status(&self, status: EventStatus) {
+        self.status.fetch_max(status as u8, Ordering::Relaxed);
+    }
+
+    pub fn status(&self) -> EventStatus {
+        EventStatus::from_u8(self.status.load(Ordering::Relaxed))
+    }
+}
+
+impl Drop for BatchNotifier {
+    fn drop(&mut self) {
+        if let Some(tx) = self.notifier.lock().unwrap().take() {
+            // The source may have stopped waiting for the batch.
+            let _ = tx.send(self.status());
+        }
+    }
+}
+
+impl fmt::Debug for BatchNotifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchNotifier")
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+/// Resolves to the status of a batch once all of its events have been delivered.
+pub struct BatchStatusReceiver(oneshot::Receiver<EventStatus>);
+
+impl Future for BatchStatusReceiver {
+    type Output = EventStatus;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0
+            .poll_unpin(cx)
+            .map(|result| result.unwrap_or(EventStatus::Failed))
+    }
+}
+
+/// The batches an event belongs to. An event can belong to several batches once it has
+/// been merged with other events.
+#[derive(Clone, Debug, Default)]
+pub struct EventFinalizers(Vec<Arc<BatchNotifier>>);
+
+impl EventFinalizers {
+    pub fn new(batch: Arc<BatchNotifier>) -> Self {
+        Self(vec![batch])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+
+    /// Updates the status of all the batches of the event. The batches are notified once
+    /// the finalizers are dropped.
+    pub fn update_status(&self, status: EventStatus) {
+        for batch in &self.0 {
+            batch.update_status(status);
+        }
+    }
+}
+
+// Finalizers don't take part in comparing events.
+impl PartialEq for EventFinalizers {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn notifies_once_all_finalizers_are_dropped() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let first = EventFinalizers::new(batch);
+        let second = first.clone();
+
+        drop(first);
+        assert!((&mut receiver).now_or_never().is_none());
+
+        drop(second);
+        assert_eq!(receiver.await, EventStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn keeps_the_worst_status() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let first = EventFinalizers::new(Arc::clone(&batch));
+        let second = EventFinalizers::new(batch);
+
+        first.update_status(EventStatus::Failed);
+        second.update_status(EventStatus::Errored);
+        drop((first, second));
+
+        assert_eq!(receiver.await, EventStatus::Failed);
+    }
+}
@@ -0,0 +1,18 @@
+/// Data carried along with an event through the topology, which isn't part of the event
+/// itself, so it's neither seen by the components nor sent by the sinks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventMetadata {
+    /// The reason a parser failed to parse the event, between the parse stage of an
+    /// expanded parser and its outputs.
+    rejection: Option<String>,
+}
+
+impl EventMetadata {
+    pub fn set_rejection(&mut self, reason: String) {
+        self.rejection = Some(reason);
+    }
+
+    pub fn take_rejection(&mut self) -> Option<String> {
+        self.rejection.take()
+    }
+}
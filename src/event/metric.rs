@@ -702,8 +702,88 @@ impl Display for Metric {
 
 const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .kind, .tags";
 
-/// We can get the `type` of the metric in Remap, but can't set  it.
-const VALID_METRIC_PATHS_GET: &str = ".name, .namespace, .timestamp, .kind, .tags, .type";
+/// We can get the `type` and `value` of the metric in Remap, but can't set them.
+const VALID_METRIC_PATHS_GET: &str = ".name, .namespace, .timestamp, .kind, .tags, .type, .value";
+
+/// The value of a metric in Remap: a number for counters and gauges, and an
+/// object of the fields of the value for the other types.
+fn value_to_vrl(value: &MetricValue) -> vrl::Value {
+    match value {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => (*value).into(),
+        MetricValue::Set { values } => values.iter().cloned().map(vrl::Value::from).collect(),
+        MetricValue::Distribution { samples, statistic } => {
+            let samples = samples
+                .iter()
+                .map(|sample| {
+                    vec![
+                        ("value".to_owned(), sample.value.into()),
+                        ("rate".to_owned(), sample.rate.into()),
+                    ]
+                    .into_iter()
+                    .collect::<vrl::Value>()
+                })
+                .collect();
+            let statistic = match statistic {
+                StatisticKind::Histogram => "histogram",
+                StatisticKind::Summary => "summary",
+            };
+            vec![
+                ("samples".to_owned(), samples),
+                ("statistic".to_owned(), statistic.into()),
+            ]
+            .into_iter()
+            .collect()
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            let buckets = buckets
+                .iter()
+                .map(|bucket| {
+                    vec![
+                        ("upper_limit".to_owned(), bucket.upper_limit.into()),
+                        ("count".to_owned(), bucket.count.into()),
+                    ]
+                    .into_iter()
+                    .collect::<vrl::Value>()
+                })
+                .collect();
+            vec![
+                ("buckets".to_owned(), buckets),
+                ("count".to_owned(), (*count).into()),
+                ("sum".to_owned(), (*sum).into()),
+            ]
+            .into_iter()
+            .collect()
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => {
+            let quantiles = quantiles
+                .iter()
+                .map(|quantile| {
+                    vec![
+                        ("upper_limit".to_owned(), quantile.upper_limit.into()),
+                        ("value".to_owned(), quantile.value.into()),
+                    ]
+                    .into_iter()
+                    .collect::<vrl::Value>()
+                })
+                .collect();
+            vec![
+                ("quantiles".to_owned(), quantiles),
+                ("count".to_owned(), (*count).into()),
+                ("sum".to_owned(), (*sum).into()),
+            ]
+            .into_iter()
+            .collect()
+        }
+    }
+}
 
 #[derive(Debug, Snafu)]
 enum MetricPathError<'a> {
@@ -817,6 +897,10 @@ impl Target for Metric {
             [Segment::Field(type_)] if type_.as_str() == "type" => {
                 Ok(Some(self.data.value.clone().into()))
             }
+            [Segment::Field(value), rest @ ..] if value.as_str() == "value" => {
+                let path = vrl::Path::new_unchecked(rest.to_vec());
+                Ok(value_to_vrl(&self.data.value).get_by_path(&path).cloned())
+            }
             _ => Err(MetricPathError::InvalidPath {
                 path: &path.to_string(),
                 expected: VALID_METRIC_PATHS_GET,
@@ -1227,6 +1311,44 @@ mod test {
         }
     }
 
+    #[test]
+    fn object_metric_value() {
+        let counter = Metric::new(
+            "name",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.23 },
+        );
+        assert_eq!(
+            Ok(Some(Value::from(1.23))),
+            counter.get(&Path::from_str("value").unwrap())
+        );
+
+        let histogram = Metric::new(
+            "name",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![Bucket {
+                    upper_limit: 1.0,
+                    count: 4,
+                }],
+                count: 4,
+                sum: 2.5,
+            },
+        );
+        assert_eq!(
+            Ok(Some(Value::from(2.5))),
+            histogram.get(&Path::from_str("value.sum").unwrap())
+        );
+        assert_eq!(
+            Ok(Some(Value::from(4_i64))),
+            histogram.get(&Path::from_str("value.buckets[0].count").unwrap())
+        );
+        assert_eq!(
+            Ok(None),
+            histogram.get(&Path::from_str("value.quantiles").unwrap())
+        );
+    }
+
     #[test]
     fn object_metric_invalid_paths() {
         let mut metric = Metric::new(
@@ -1242,6 +1364,7 @@ mod test {
             ".kind",
             ".tags",
             ".type",
+            ".value",
         ];
 
         let validpaths_set = vec![".name", ".namespace", ".timestamp", ".kind", ".tags"];
@@ -2,9 +2,13 @@ use self::proto::{event_wrapper::Event as EventProto, metric::Value as MetricPro
 use crate::config::log_schema;
 use bytes::Bytes;
 use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 pub mod discriminant;
+pub mod finalization;
 pub mod merge;
 pub mod merge_state;
 pub mod metric;
@@ -12,10 +16,13 @@ pub mod util;
 
 mod log_event;
 mod lookup;
+mod metadata;
 mod value;
 
+pub use finalization::{BatchNotifier, BatchStatusReceiver, EventFinalizers, EventStatus};
 pub use log_event::LogEvent;
 pub use lookup::Lookup;
+pub use metadata::EventMetadata;
 pub use metric::{Metric, MetricKind, MetricValue, StatisticKind};
 use std::convert::{TryFrom, TryInto};
 pub(crate) use util::log::PathComponent;
@@ -80,6 +87,29 @@ impl Event {
             _ => panic!("Failed type coercion, {:?} is not a metric", self),
         }
     }
+
+    /// Adds the event to the batch of `batch`, so that the source is notified once the
+    /// event is delivered. Only log events are tracked, so metrics count as delivered as
+    /// soon as they're received.
+    pub fn add_batch_notifier(&mut self, batch: Arc<BatchNotifier>) {
+        if let Event::Log(log) = self {
+            log.add_batch_notifier(batch);
+        }
+    }
+
+    pub fn with_batch_notifier(mut self, batch: &Arc<BatchNotifier>) -> Self {
+        self.add_batch_notifier(Arc::clone(batch));
+        self
+    }
+
+    /// Takes the finalizers of the event, which the caller then has to update and drop
+    /// once the event is delivered.
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        match self {
+            Event::Log(log) => log.take_finalizers(),
+            Event::Metric(_) => EventFinalizers::default(),
+        }
+    }
 }
 
 fn timestamp_to_string(timestamp: &DateTime<Utc>) -> String {
@@ -442,7 +442,6 @@ mod tests {
                 [transforms.transform0]
                 inputs = ["source0"]
                 drop_field = true
-                drop_invalid = false
                 type = "json_parser"
 
                 [sinks.sink0]
@@ -475,7 +474,6 @@ mod tests {
                 [transforms.transform0]
                 inputs = ["source0"]
                 drop_field = true
-                drop_invalid = false
                 type = "json_parser"
 
                 [sinks.sink0]
@@ -561,7 +559,6 @@ mod tests {
                 [transforms.transform1]
                 inputs = ["transform0"]
                 drop_field = true
-                drop_invalid = false
                 type = "json_parser"
 
                 [transforms.transform2]
@@ -586,7 +583,6 @@ mod tests {
                 [transforms.transform1]
                 inputs = ["transform0"]
                 drop_field = true
-                drop_invalid = false
                 type = "json_parser"
 
                 [transforms.transform2]
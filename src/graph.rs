@@ -0,0 +1,165 @@
+use crate::config::{self, Config};
+use std::{fmt::Write, path::PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Vector config files in TOML format to graph.
+    #[structopt(name = "config-toml", long)]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format to graph.
+    #[structopt(name = "config-json", long)]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format to graph.
+    #[structopt(name = "config-yaml", long)]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Directories of Vector config files to graph. Format is detected from
+    /// the file name.
+    #[structopt(name = "config-dir", short = "C", long)]
+    config_dirs: Vec<PathBuf>,
+
+    /// Any number of Vector config files to graph. If none are specified the
+    /// default config path `/etc/vector/vector.toml` will be targeted.
+    #[structopt(name = "config", short, long)]
+    paths: Vec<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<(PathBuf, config::FormatHint)> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::TOML)),
+            (&self.paths_json, Some(config::Format::JSON)),
+            (&self.paths_yaml, Some(config::Format::YAML)),
+            (&self.config_dirs, None),
+        ])
+    }
+}
+
+/// Prints the topology of the config in the DOT language of Graphviz, to be
+/// rendered with, for example, `vector graph | dot -Tsvg > graph.svg`.
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let paths = match config::process_paths(&opts.paths_with_formats()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    match config::load_from_paths(&paths) {
+        Ok(config) => {
+            print!("{}", to_dot(&config));
+            exitcode::OK
+        }
+        Err(errors) => {
+            error!("Failed to load config:\n{}.", errors.join("\n"));
+            exitcode::CONFIG
+        }
+    }
+}
+
+fn to_dot(config: &Config) -> String {
+    let mut dot = String::from("digraph {\n");
+
+    for (name, source) in &config.sources {
+        node(
+            &mut dot,
+            name,
+            source.inner.source_type(),
+            "trapezium",
+            "#a6cee3",
+        );
+    }
+    for (name, transform) in &config.transforms {
+        node(
+            &mut dot,
+            name,
+            transform.inner.transform_type(),
+            "box",
+            "#b2df8a",
+        );
+        edges(&mut dot, name, &transform.inputs);
+    }
+    for (name, sink) in &config.sinks {
+        node(
+            &mut dot,
+            name,
+            sink.inner.sink_type(),
+            "invtrapezium",
+            "#fb9a99",
+        );
+        edges(&mut dot, name, &sink.inputs);
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn node(dot: &mut String, name: &str, component_type: &str, shape: &str, color: &str) {
+    writeln!(
+        dot,
+        "  {} [label=\"{}\\n({})\" shape=\"{}\" style=\"filled\" fillcolor=\"{}\"]",
+        id(name),
+        escape(name),
+        component_type,
+        shape,
+        color
+    )
+    .expect("writing to a String never fails");
+}
+
+fn edges(dot: &mut String, name: &str, inputs: &[String]) {
+    for input in inputs {
+        writeln!(dot, "  {} -> {}", id(input), id(name)).expect("writing to a String never fails");
+    }
+}
+
+fn id(name: &str) -> String {
+    format!("\"{}\"", escape(name))
+}
+
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graphs_components_and_inputs() {
+        let config = config::load_from_str(
+            r#"
+            [sources.in]
+            type = "stdin"
+
+            [transforms."sample \"logs\""]
+            type = "sample"
+            inputs = ["in"]
+            rate = 10
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in", "sample \"logs\""]
+            encoding = "json"
+            "#,
+            Some(config::Format::TOML),
+        )
+        .unwrap();
+
+        assert_eq!(
+            to_dot(&config),
+            r##"digraph {
+  "in" [label="in\n(stdin)" shape="trapezium" style="filled" fillcolor="#a6cee3"]
+  "sample \"logs\"" [label="sample \"logs\"\n(sample)" shape="box" style="filled" fillcolor="#b2df8a"]
+  "in" -> "sample \"logs\""
+  "out" [label="out\n(console)" shape="invtrapezium" style="filled" fillcolor="#fb9a99"]
+  "in" -> "out"
+  "sample \"logs\"" -> "out"
+}
+"##
+        );
+    }
+}
@@ -11,7 +11,7 @@ use http::HeaderMap;
 use http::Request;
 use hyper::{
     body::{Body, HttpBody},
-    client::{Client, HttpConnector},
+    client::{self, Client, HttpConnector},
 };
 use hyper_openssl::HttpsConnector;
 use serde::{Deserialize, Serialize};
@@ -49,6 +49,14 @@ where
     B::Error: Into<crate::Error>,
 {
     pub fn new(tls_settings: impl Into<MaybeTlsSettings>) -> Result<HttpClient<B>, HttpError> {
+        HttpClient::new_with_builder(tls_settings, &Client::builder())
+    }
+
+    /// Builds the client with the given options, such as `http2_only` for gRPC services.
+    pub fn new_with_builder(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        client_builder: &client::Builder,
+    ) -> Result<HttpClient<B>, HttpError> {
         let mut http = HttpConnector::new_with_resolver(Resolver);
         http.enforce_http(false);
 
@@ -65,7 +73,7 @@ where
             Ok(())
         });
 
-        let client = Client::builder().build(https);
+        let client = client_builder.build(https);
 
         let version = crate::get_version();
         let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
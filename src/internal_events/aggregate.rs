@@ -0,0 +1,26 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct AggregateEventRecorded;
+
+impl InternalEvent for AggregateEventRecorded {
+    fn emit_metrics(&self) {
+        counter!("events_recorded_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AggregateFlushed {
+    pub count: usize,
+}
+
+impl InternalEvent for AggregateFlushed {
+    fn emit_logs(&self) {
+        trace!(message = "Flushed aggregated metrics.", count = %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("flushes_total", 1);
+    }
+}
@@ -0,0 +1,19 @@
+use super::InternalEvent;
+use metrics::gauge;
+
+#[derive(Debug)]
+pub struct ComponentAllocatedBytes {
+    pub component_kind: &'static str,
+    pub component_name: String,
+    pub bytes: u64,
+}
+
+impl InternalEvent for ComponentAllocatedBytes {
+    fn emit_metrics(&self) {
+        gauge!(
+            "component_allocated_bytes", self.bytes as f64,
+            "component_kind" => self.component_kind,
+            "component_name" => self.component_name.clone(),
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use super::InternalEvent;
+use crate::event::EventStatus;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct AmqpEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for AmqpEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventSent {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for AmqpEventSent {
+    fn emit_logs(&self) {
+        trace!(message = "Event sent.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpConnectionFailed<'a> {
+    pub error: &'a crate::Error,
+}
+
+impl InternalEvent for AmqpConnectionFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to connect to the AMQP server; retrying.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpConsumeFailed {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpConsumeFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to consume from the AMQP queue; reconnecting.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpAckFailed {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpAckFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to acknowledge AMQP message.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpMessageNotDelivered {
+    pub status: EventStatus,
+}
+
+impl InternalEvent for AmqpMessageNotDelivered {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event was not delivered; returning its message to the queue.",
+            status = ?self.status,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpPublishFailed {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpPublishFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to publish message; reconnecting.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpPublishNacked;
+
+impl InternalEvent for AmqpPublishNacked {
+    fn emit_logs(&self) {
+        error!(
+            message = "Server rejected published message.",
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventDropped {
+    pub attempts: usize,
+}
+
+impl InternalEvent for AmqpEventDropped {
+    fn emit_logs(&self) {
+        error!(
+            message = "Message could not be published; dropping its event.",
+            attempts = %self.attempts,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+    }
+}
@@ -1,6 +1,6 @@
 use super::InternalEvent;
 use metrics::counter;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 #[derive(Debug)]
 pub struct ApiStarted {
@@ -22,3 +22,59 @@ impl InternalEvent for ApiStarted {
         counter!("api_started_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct ApiSubscriptionLagged {
+    pub dropped: usize,
+}
+
+impl InternalEvent for ApiSubscriptionLagged {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Subscriber is lagging behind, dropped items.",
+            count = %self.dropped,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("api_subscription_dropped_total", self.dropped as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ApiConnectionRefused {
+    pub max_connections: usize,
+}
+
+impl InternalEvent for ApiConnectionRefused {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Refused API connection, too many connections are open.",
+            max_connections = %self.max_connections,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("api_connections_refused_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ApiConnectionTimedOut {
+    pub keepalive: Duration,
+}
+
+impl InternalEvent for ApiConnectionTimedOut {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Closing API connection, client stopped responding to pings.",
+            keepalive = ?self.keepalive,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("api_connections_timed_out_total", 1);
+    }
+}
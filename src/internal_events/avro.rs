@@ -0,0 +1,22 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct AvroEncodingFailed<'a> {
+    pub error: &'a avro_rs::Error,
+}
+
+impl InternalEvent for AvroEncodingFailed<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Event doesn't match the Avro schema; discarding event.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "schema_validation_failed");
+        counter!("events_discarded_total", 1);
+    }
+}
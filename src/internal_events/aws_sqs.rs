@@ -1,5 +1,8 @@
 use super::InternalEvent;
+use crate::event::EventStatus;
 use metrics::counter;
+use rusoto_core::RusotoError;
+use rusoto_sqs::ReceiveMessageError;
 
 #[derive(Debug)]
 pub struct AwsSqsEventSent<'a> {
@@ -17,3 +20,76 @@ impl InternalEvent for AwsSqsEventSent<'_> {
         counter!("processed_bytes_total", self.byte_size as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct AwsSqsEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for AwsSqsEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageReceiveFailed {
+    pub error: RusotoError<ReceiveMessageError>,
+}
+
+impl InternalEvent for AwsSqsMessageReceiveFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to receive SQS messages.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_receive_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageNotDelivered {
+    pub status: EventStatus,
+}
+
+impl InternalEvent for AwsSqsMessageNotDelivered {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event was not delivered; not deleting its message.",
+            status = ?self.status,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageDeleteFailed<'a> {
+    pub error: &'a str,
+}
+
+impl InternalEvent for AwsSqsMessageDeleteFailed<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Deletion of SQS message failed.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_delete_failed_total", 1);
+    }
+}
@@ -0,0 +1,19 @@
+use super::InternalEvent;
+use metrics::gauge;
+
+#[derive(Debug)]
+pub struct BufferUsageReport {
+    pub events: usize,
+    pub byte_size: Option<usize>,
+    pub max_size: usize,
+}
+
+impl InternalEvent for BufferUsageReport {
+    fn emit_metrics(&self) {
+        gauge!("buffer_events", self.events as f64);
+        if let Some(byte_size) = self.byte_size {
+            gauge!("buffer_byte_size", byte_size as f64);
+        }
+        gauge!("buffer_max_size", self.max_size as f64);
+    }
+}
@@ -0,0 +1,41 @@
+use super::InternalEvent;
+use crate::codecs::DecodeError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct DecoderDeserializeFailed<'a> {
+    pub error: &'a DecodeError,
+}
+
+impl InternalEvent for DecoderDeserializeFailed<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed deserializing frame.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "failed_parse");
+    }
+}
+
+#[derive(Debug)]
+pub struct DecoderFramingFailed<'a> {
+    pub error: &'a std::io::Error,
+}
+
+impl InternalEvent for DecoderFramingFailed<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed framing bytes.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "failed_framing");
+    }
+}
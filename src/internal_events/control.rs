@@ -0,0 +1,41 @@
+use super::InternalEvent;
+use http::Uri;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct ControlPlaneReported<'a> {
+    pub endpoint: &'a Uri,
+}
+
+impl<'a> InternalEvent for ControlPlaneReported<'a> {
+    fn emit_logs(&self) {
+        debug!(message = "Reported to control plane.", endpoint = %self.endpoint);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("control_plane_reports_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ControlPlaneReportFailed {
+    pub error: crate::Error,
+    pub attempt: usize,
+    pub retry_attempts: usize,
+}
+
+impl InternalEvent for ControlPlaneReportFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to report to control plane.",
+            error = %self.error,
+            attempt = %self.attempt,
+            retry_attempts = %self.retry_attempts,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("control_plane_report_errors_total", 1);
+    }
+}
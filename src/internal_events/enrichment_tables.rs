@@ -0,0 +1,22 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct EnrichmentTableLoaded<'a> {
+    pub name: &'a str,
+    pub rows: usize,
+}
+
+impl<'a> InternalEvent for EnrichmentTableLoaded<'a> {
+    fn emit_logs(&self) {
+        info!(
+            message = "Loaded enrichment table.",
+            name = %self.name,
+            rows = %self.rows,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("enrichment_table_loads_total", 1, "table" => self.name.to_owned());
+    }
+}
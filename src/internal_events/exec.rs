@@ -0,0 +1,71 @@
+use super::InternalEvent;
+use metrics::{counter, histogram};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct ExecEventReceived<'a> {
+    pub command: &'a str,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ExecEventReceived<'_> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received one event.",
+            command = %self.command,
+            byte_size = %self.byte_size,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecFailed<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to run command.",
+            command = %self.command,
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "command_failed");
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandExecuted<'a> {
+    pub command: &'a str,
+    pub exit_code: Option<i32>,
+    pub elapsed: Duration,
+}
+
+impl InternalEvent for ExecCommandExecuted<'_> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Command finished.",
+            command = %self.command,
+            exit_code = ?self.exit_code,
+            elapsed_millis = %self.elapsed.as_millis(),
+        );
+    }
+
+    fn emit_metrics(&self) {
+        let exit_code = self
+            .exit_code
+            .map_or_else(|| "unknown".to_string(), |code| code.to_string());
+        counter!("command_executed_total", 1, "exit_code" => exit_code.clone());
+        histogram!("command_execution_duration_seconds", self.elapsed, "exit_code" => exit_code);
+    }
+}
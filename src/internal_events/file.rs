@@ -18,6 +18,7 @@ impl InternalEvent for FileOpen {
 #[cfg(any(feature = "sources-file", feature = "sources-kubernetes-logs"))]
 mod source {
     use super::{FileOpen, InternalEvent};
+    use crate::event::EventStatus;
     use file_source::FileSourceInternalEvents;
     use metrics::counter;
     use std::{io::Error, path::Path, time::Duration};
@@ -49,6 +50,30 @@ mod source {
         }
     }
 
+    #[derive(Debug)]
+    pub struct FileEventNotDelivered<'a> {
+        pub file: &'a str,
+        pub status: EventStatus,
+    }
+
+    impl InternalEvent for FileEventNotDelivered<'_> {
+        fn emit_logs(&self) {
+            error!(
+                message = "Event was not delivered; not checkpointing the file any further.",
+                file = %self.file,
+                status = ?self.status,
+                internal_log_rate_secs = 10
+            );
+        }
+
+        fn emit_metrics(&self) {
+            counter!(
+                "events_failed_total", 1,
+                "file" => self.file.to_owned(),
+            );
+        }
+    }
+
     #[derive(Debug)]
     pub struct FileChecksumFailed<'a> {
         pub path: &'a Path,
@@ -38,3 +38,39 @@ impl<'a> InternalEvent for GeoipFieldDoesNotExist<'a> {
         counter!("processing_errors_total", 1, "error_type" => "type_field_does_not_exist");
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct GeoipDatabaseReloaded<'a> {
+    pub path: &'a str,
+}
+
+impl<'a> InternalEvent for GeoipDatabaseReloaded<'a> {
+    fn emit_logs(&self) {
+        info!(message = "Reloaded GeoIP database.", path = %self.path);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("database_reloads_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GeoipDatabaseReloadFailed<'a> {
+    pub path: &'a str,
+    pub error: maxminddb::MaxMindDBError,
+}
+
+impl<'a> InternalEvent for GeoipDatabaseReloadFailed<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to reload GeoIP database; keeping the previous one.",
+            path = %self.path,
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("database_reload_errors_total", 1);
+    }
+}
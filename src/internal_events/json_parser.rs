@@ -7,28 +7,17 @@ pub(crate) struct JsonParserFailedParse<'a> {
     pub field: &'a str,
     pub value: &'a str,
     pub error: Error,
-    pub drop_invalid: bool,
 }
 
 impl<'a> InternalEvent for JsonParserFailedParse<'a> {
     fn emit_logs(&self) {
-        if self.drop_invalid {
-            debug!(
-                message = "Event failed to parse as JSON.",
-                field = %self.field,
-                value = %self.value,
-                error = ?self.error,
-                internal_log_rate_secs = 30
-            )
-        } else {
-            warn!(
-                message = "Event failed to parse as JSON.",
-                field = %self.field,
-                value = %self.value,
-                error = ?self.error,
-                internal_log_rate_secs = 30
-            )
-        }
+        warn!(
+            message = "Event failed to parse as JSON.",
+            field = %self.field,
+            value = %self.value,
+            error = ?self.error,
+            internal_log_rate_secs = 30
+        )
     }
 
     fn emit_metrics(&self) {
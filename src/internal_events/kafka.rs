@@ -1,5 +1,6 @@
 use super::InternalEvent;
-use metrics::counter;
+use crate::event::EventStatus;
+use metrics::{counter, gauge};
 
 #[derive(Debug)]
 pub struct KafkaEventReceived {
@@ -47,6 +48,74 @@ impl InternalEvent for KafkaEventFailed {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaEventNotDelivered<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub status: EventStatus,
+}
+
+impl<'a> InternalEvent for KafkaEventNotDelivered<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event was not delivered; not storing further offsets of its partition.",
+            topic = %self.topic,
+            partition = %self.partition,
+            status = ?self.status,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionsAssigned {
+    pub partitions: usize,
+}
+
+impl InternalEvent for KafkaPartitionsAssigned {
+    fn emit_logs(&self) {
+        info!(message = "Consumer group rebalanced.", partitions = %self.partitions);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_rebalances_total", 1, "rebalance_type" => "assign");
+        gauge!("consumer_assigned_partitions", self.partitions as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionsRevoked;
+
+impl InternalEvent for KafkaPartitionsRevoked {
+    fn emit_logs(&self) {
+        info!(message = "Consumer group partitions revoked for rebalance.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_rebalances_total", 1, "rebalance_type" => "revoke");
+        gauge!("consumer_assigned_partitions", 0.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaRebalanceFailed<'a> {
+    pub error: &'a str,
+}
+
+impl InternalEvent for KafkaRebalanceFailed<'_> {
+    fn emit_logs(&self) {
+        error!(message = "Consumer group rebalance failed.", error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_rebalance_errors_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaKeyExtractionFailed<'a> {
     pub key_field: &'a str,
@@ -0,0 +1,21 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct LogpushLineParseError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for LogpushLineParseError {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Line isn't a JSON object, so raw message is forwarded.",
+            error = ?self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("parse_errors_total", 1);
+    }
+}
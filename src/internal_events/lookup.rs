@@ -0,0 +1,42 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub(crate) struct LookupRowNotFound<'a> {
+    pub table: &'a str,
+}
+
+impl<'a> InternalEvent for LookupRowNotFound<'a> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "No row of the enrichment table matches the event.",
+            table = %self.table,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("lookup_misses_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LookupFailed<'a> {
+    pub table: &'a str,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for LookupFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to look event up in the enrichment table.",
+            table = %self.table,
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "lookup_failed");
+    }
+}
@@ -3,11 +3,19 @@ use std::borrow::Cow;
 mod adaptive_concurrency;
 mod add_fields;
 mod add_tags;
+#[cfg(feature = "transforms-aggregate")]
+mod aggregate;
+#[cfg(feature = "allocation-tracing")]
+mod allocations;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+mod amqp;
 mod ansi_stripper;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+#[cfg(feature = "avro-rs")]
+mod avro;
 #[cfg(feature = "transforms-aws_cloudwatch_logs_subscription_parser")]
 mod aws_cloudwatch_logs_subscription_parser;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -20,15 +28,19 @@ mod aws_kinesis_firehose;
 mod aws_kinesis_streams;
 #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
 pub(crate) mod aws_s3;
-#[cfg(feature = "sinks-aws_sqs")]
+#[cfg(any(feature = "sources-aws_sqs", feature = "sinks-aws_sqs"))]
 mod aws_sqs;
 mod blackhole;
+mod buffer;
+#[cfg(feature = "codecs")]
+mod codecs;
 #[cfg(feature = "transforms-coercer")]
 mod coercer;
 #[cfg(feature = "transforms-concat")]
 mod concat;
 #[cfg(feature = "sinks-console")]
 mod console;
+mod control;
 #[cfg(feature = "sinks-datadog")]
 mod datadog_logs;
 #[cfg(feature = "transforms-dedupe")]
@@ -37,6 +49,9 @@ mod dedupe;
 mod docker_logs;
 mod elasticsearch;
 mod encoding_transcode;
+mod enrichment_tables;
+#[cfg(feature = "sources-exec")]
+mod exec;
 #[cfg(feature = "transforms-filter")]
 mod filter;
 #[cfg(feature = "sources-generator")]
@@ -65,23 +80,33 @@ mod log_to_metric;
 #[cfg(feature = "transforms-logfmt_parser")]
 mod logfmt_parser;
 mod logplex;
+#[cfg(feature = "sources-logpush")]
+mod logpush;
+#[cfg(feature = "transforms-lookup")]
+mod lookup;
 #[cfg(feature = "transforms-lua")]
 mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(feature = "sources-opentelemetry")]
+mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 mod prometheus;
+mod protobuf;
 mod pulsar;
+mod reconnect;
+#[cfg(feature = "sinks-redis")]
+mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 #[cfg(feature = "transforms-regex_parser")]
@@ -91,12 +116,18 @@ mod remap;
 mod remove_fields;
 #[cfg(feature = "transforms-rename_fields")]
 mod rename_fields;
+#[cfg(feature = "replay")]
+mod replay;
 #[cfg(feature = "transforms-route")]
 mod route;
 mod sample;
+#[cfg(feature = "sources-utils-schema-registry")]
+mod schema_registry;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
 mod socket;
+#[cfg(feature = "sinks-vector")]
+mod spillover;
 mod split;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -110,25 +141,39 @@ mod syslog;
 mod tag_cardinality_limit;
 mod tcp;
 mod template;
+#[cfg(feature = "transforms-time_enricher")]
+mod time_enricher;
 #[cfg(feature = "transforms-tokenizer")]
 mod tokenizer;
 mod topology;
+#[cfg(feature = "transforms-traffic_split")]
+mod traffic_split;
 mod udp;
 mod unix;
 mod vector;
 #[cfg(feature = "wasm")]
 mod wasm;
+#[cfg(feature = "sinks-websocket")]
+mod websocket;
 
 pub mod kubernetes;
 
 pub use self::adaptive_concurrency::*;
 pub use self::add_fields::*;
 pub use self::add_tags::*;
+#[cfg(feature = "transforms-aggregate")]
+pub use self::aggregate::*;
+#[cfg(feature = "allocation-tracing")]
+pub use self::allocations::*;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+pub use self::amqp::*;
 pub use self::ansi_stripper::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub use self::apache_metrics::*;
 #[cfg(feature = "api")]
 pub use self::api::*;
+#[cfg(feature = "avro-rs")]
+pub use self::avro::*;
 #[cfg(feature = "transforms-aws_cloudwatch_logs_subscription_parser")]
 pub(crate) use self::aws_cloudwatch_logs_subscription_parser::*;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -139,15 +184,19 @@ pub use self::aws_ecs_metrics::*;
 pub use self::aws_kinesis_firehose::*;
 #[cfg(feature = "sinks-aws_kinesis_streams")]
 pub use self::aws_kinesis_streams::*;
-#[cfg(feature = "sinks-aws_sqs")]
+#[cfg(any(feature = "sources-aws_sqs", feature = "sinks-aws_sqs"))]
 pub use self::aws_sqs::*;
 pub use self::blackhole::*;
+pub use self::buffer::*;
+#[cfg(feature = "codecs")]
+pub use self::codecs::*;
 #[cfg(feature = "transforms-coercer")]
 pub(crate) use self::coercer::*;
 #[cfg(feature = "transforms-concat")]
 pub use self::concat::*;
 #[cfg(feature = "sinks-console")]
 pub use self::console::*;
+pub use self::control::*;
 #[cfg(feature = "sinks-datadog")]
 pub use self::datadog_logs::*;
 #[cfg(feature = "transforms-dedupe")]
@@ -156,6 +205,9 @@ pub(crate) use self::dedupe::*;
 pub use self::docker_logs::*;
 pub use self::elasticsearch::*;
 pub use self::encoding_transcode::*;
+pub use self::enrichment_tables::*;
+#[cfg(feature = "sources-exec")]
+pub use self::exec::*;
 #[cfg(any(
     feature = "sources-file",
     feature = "sources-kubernetes-logs",
@@ -190,21 +242,31 @@ pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "transforms-logfmt_parser")]
 pub use self::logfmt_parser::*;
 pub use self::logplex::*;
+#[cfg(feature = "sources-logpush")]
+pub use self::logpush::*;
+#[cfg(feature = "transforms-lookup")]
+pub(crate) use self::lookup::*;
 #[cfg(feature = "transforms-lua")]
 pub use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 pub use self::open::*;
+#[cfg(feature = "sources-opentelemetry")]
+pub(crate) use self::opentelemetry::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 pub use self::process::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 pub(crate) use self::prometheus::*;
+pub use self::protobuf::*;
 pub use self::pulsar::*;
+pub use self::reconnect::*;
+#[cfg(feature = "sinks-redis")]
+pub use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
 pub(crate) use self::reduce::*;
 #[cfg(feature = "transforms-regex_parser")]
@@ -214,12 +276,18 @@ pub use self::remap::*;
 pub use self::remove_fields::*;
 #[cfg(feature = "transforms-rename_fields")]
 pub use self::rename_fields::*;
+#[cfg(feature = "replay")]
+pub use self::replay::*;
 #[cfg(feature = "transforms-route")]
 pub use self::route::*;
 pub use self::sample::*;
+#[cfg(feature = "sources-utils-schema-registry")]
+pub(crate) use self::schema_registry::*;
 #[cfg(feature = "sinks-sematext")]
 pub use self::sematext_metrics::*;
 pub(crate) use self::socket::*;
+#[cfg(feature = "sinks-vector")]
+pub use self::spillover::*;
 pub use self::split::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
@@ -233,14 +301,20 @@ pub use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 pub use self::tcp::*;
 pub use self::template::*;
+#[cfg(feature = "transforms-time_enricher")]
+pub(crate) use self::time_enricher::*;
 #[cfg(feature = "transforms-tokenizer")]
 pub(crate) use self::tokenizer::*;
 pub use self::topology::*;
+#[cfg(feature = "transforms-traffic_split")]
+pub use self::traffic_split::*;
 pub use self::udp::*;
 pub use self::unix::*;
 pub use self::vector::*;
 #[cfg(feature = "wasm")]
 pub use self::wasm::*;
+#[cfg(feature = "sinks-websocket")]
+pub use self::websocket::*;
 #[cfg(windows)]
 pub use self::windows::*;
 #[cfg(feature = "sources-mongodb_metrics")]
@@ -32,3 +32,19 @@ impl InternalEvent for NatsEventSendFail {
         counter!("send_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct NatsEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for NatsEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
@@ -0,0 +1,23 @@
+use super::InternalEvent;
+use crate::sinks::util::encoding::ProtobufEncodeError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct ProtobufEncodingFailed<'a> {
+    pub error: &'a ProtobufEncodeError,
+}
+
+impl InternalEvent for ProtobufEncodingFailed<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Event doesn't match the Protobuf message type; discarding event.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "schema_validation_failed");
+        counter!("events_discarded_total", 1);
+    }
+}
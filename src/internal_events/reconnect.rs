@@ -0,0 +1,24 @@
+use super::InternalEvent;
+use metrics::counter;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct SourceReconnecting {
+    pub attempt: u32,
+    pub delay: Duration,
+}
+
+impl InternalEvent for SourceReconnecting {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Retrying after failure.",
+            attempt = %self.attempt,
+            delay_ms = %self.delay.as_millis(),
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("reconnect_attempts_total", 1);
+    }
+}
@@ -0,0 +1,79 @@
+use super::InternalEvent;
+use metrics::counter;
+use redis::RedisError;
+
+#[derive(Debug)]
+pub struct RedisEventSent {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for RedisEventSent {
+    fn emit_logs(&self) {
+        trace!(message = "Event sent.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisConnectionFailed {
+    pub error: RedisError,
+}
+
+impl InternalEvent for RedisConnectionFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to connect to the Redis server; retrying.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisSendFailed {
+    pub error: RedisError,
+}
+
+impl InternalEvent for RedisSendFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to send events to Redis; reconnecting.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisEventsRejected {
+    pub error: RedisError,
+    pub count: usize,
+}
+
+impl InternalEvent for RedisEventsRejected {
+    fn emit_logs(&self) {
+        error!(
+            message = "Redis rejected events; dropping them.",
+            error = %self.error,
+            count = %self.count,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+        counter!("events_discarded_total", self.count as u64);
+    }
+}
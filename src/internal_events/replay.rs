@@ -0,0 +1,58 @@
+use super::InternalEvent;
+use metrics::counter;
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct ReplayEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ReplayEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayFailedOpen<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
+impl<'a> InternalEvent for ReplayFailedOpen<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to read archived file.",
+            path = ?self.path,
+            error = ?self.error,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "read_failed");
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayFailedDecode {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for ReplayFailedDecode {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Unable to decode archived event.",
+            error = ?self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "failed_parse");
+    }
+}
@@ -0,0 +1,34 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct SchemaValidationFetched<'a> {
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for SchemaValidationFetched<'a> {
+    fn emit_logs(&self) {
+        info!(message = "Fetched schema for validating events.", url = %self.url);
+    }
+}
+
+#[derive(Debug)]
+pub struct SchemaValidationFailed<'a> {
+    pub error: &'a str,
+    pub dropped: bool,
+}
+
+impl<'a> InternalEvent for SchemaValidationFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Event does not conform to schema.",
+            error = %self.error,
+            dropped = %self.dropped,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("schema_validation_failed_total", 1);
+    }
+}
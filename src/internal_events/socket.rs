@@ -68,3 +68,24 @@ impl InternalEvent for SocketReceiveError {
         counter!("connection_errors_total", 1, "mode" => self.mode.as_str());
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct SocketDecompressError {
+    pub mode: SocketMode,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for SocketDecompressError {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed decompressing payload, dropping it.",
+            error = ?self.error,
+            mode = %self.mode.as_str(),
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("decompress_errors_total", 1, "mode" => self.mode.as_str());
+    }
+}
@@ -0,0 +1,80 @@
+use super::InternalEvent;
+use metrics::{counter, gauge};
+
+#[derive(Debug)]
+pub struct SpilloverSegmentWritten {
+    pub frames: usize,
+    pub byte_size: u64,
+    pub spillover_size: u64,
+}
+
+impl InternalEvent for SpilloverSegmentWritten {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Spilled events to disk.",
+            count = %self.frames,
+            byte_size = %self.byte_size,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("spillover_events_total", self.frames as u64);
+        counter!("spillover_bytes_total", self.byte_size);
+        gauge!("spillover_size_bytes", self.spillover_size as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SpilloverSegmentDrained {
+    pub frames: usize,
+    pub spillover_size: u64,
+}
+
+impl InternalEvent for SpilloverSegmentDrained {
+    fn emit_logs(&self) {
+        debug!(message = "Sent spilled events.", count = %self.frames);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("spillover_drained_events_total", self.frames as u64);
+        gauge!("spillover_size_bytes", self.spillover_size as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SpilloverSegmentDropped {
+    pub frames: usize,
+}
+
+impl InternalEvent for SpilloverSegmentDropped {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Spillover is full, dropping the oldest spilled events.",
+            count = %self.frames,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("spillover_dropped_events_total", self.frames as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SpilloverError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for SpilloverError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Spillover failed.",
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("spillover_errors_total", 1);
+    }
+}
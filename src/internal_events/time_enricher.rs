@@ -0,0 +1,40 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub(crate) struct TimeEnricherFieldMissing<'a> {
+    pub field: &'a str,
+}
+
+impl<'a> InternalEvent for TimeEnricherFieldMissing<'a> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Field does not exist.",
+            field = %self.field,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "field_missing");
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TimeEnricherInvalidTimestamp<'a> {
+    pub field: &'a str,
+}
+
+impl<'a> InternalEvent for TimeEnricherInvalidTimestamp<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Field is not a timestamp.",
+            field = %self.field,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "invalid_timestamp");
+    }
+}
@@ -0,0 +1,11 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct TrafficSplitEventDiscarded;
+
+impl InternalEvent for TrafficSplitEventDiscarded {
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+    }
+}
@@ -0,0 +1,73 @@
+use super::InternalEvent;
+use metrics::counter;
+use tokio_tungstenite::tungstenite::error::Error as WsError;
+
+#[derive(Debug)]
+pub struct WebSocketEventSent {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for WebSocketEventSent {
+    fn emit_logs(&self) {
+        trace!(message = "Event sent.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketConnectionFailed {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for WebSocketConnectionFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to connect to the WebSocket server; retrying.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketConnectionShutdown;
+
+impl InternalEvent for WebSocketConnectionShutdown {
+    fn emit_logs(&self) {
+        warn!(
+            message = "WebSocket server didn't answer pings in time; closing the connection.",
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_shutdown_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketSendFailed {
+    pub error: WsError,
+}
+
+impl InternalEvent for WebSocketSendFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "WebSocket connection failed; reconnecting.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
@@ -20,28 +20,44 @@ extern crate pest_derive;
 #[cfg(feature = "vrl-cli")]
 extern crate vrl_cli;
 
-#[cfg(feature = "jemallocator")]
+#[cfg(all(feature = "jemallocator", not(feature = "allocation-tracing")))]
 #[global_allocator]
 static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 
+#[cfg(feature = "allocation-tracing")]
+#[global_allocator]
+static ALLOC: allocations::Tracking<allocations::Allocator> =
+    allocations::Tracking(allocations::ALLOCATOR);
+
 #[macro_use]
 pub mod config;
 pub mod cli;
+#[cfg(feature = "codecs")]
+pub mod codecs;
 pub mod conditions;
+pub mod control;
 pub mod dns;
+#[cfg(feature = "embed")]
+pub mod embed;
 pub mod event;
 pub mod expiring_hash_map;
 pub mod generate;
+pub mod graph;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 #[macro_use]
 pub mod internal_events;
+#[cfg(feature = "allocation-tracing")]
+pub mod allocations;
+#[cfg(feature = "lapin")]
+pub mod amqp;
 #[cfg(feature = "api")]
 pub mod api;
 pub mod app;
 pub mod async_read;
 pub mod buffers;
 pub mod encoding_transcode;
+pub mod enrichment_tables;
 pub mod heartbeat;
 pub mod http;
 #[cfg(feature = "rdkafka")]
@@ -51,7 +67,18 @@ pub mod line_agg;
 pub mod list;
 pub mod mapping;
 pub mod metrics;
+#[cfg(feature = "async-nats")]
+pub mod nats;
 pub(crate) mod pipeline;
+pub mod profiling;
+#[cfg(any(
+    feature = "sources-opentelemetry",
+    feature = "sources-vector",
+    feature = "sinks-vector"
+))]
+pub mod proto;
+#[cfg(feature = "replay")]
+pub mod replay;
 #[cfg(feature = "rusoto_core")]
 pub mod rusoto;
 pub mod serde;
@@ -101,7 +128,7 @@ pub fn vector_version() -> impl std::fmt::Display {
 
 pub fn get_version() -> String {
     let pkg_version = vector_version();
-    let commit_hash = built_info::GIT_VERSION.and_then(|v| v.split('-').last());
+    let commit_hash = get_commit_hash();
     let built_date = chrono::DateTime::parse_from_rfc2822(built_info::BUILT_TIME_UTC)
         .unwrap()
         .format("%Y-%m-%d");
@@ -113,6 +140,11 @@ pub fn get_version() -> String {
     format!("{} ({})", pkg_version, built_string)
 }
 
+/// The commit Vector was built from, if known.
+pub fn get_commit_hash() -> Option<&'static str> {
+    built_info::GIT_VERSION.and_then(|v| v.split('-').last())
+}
+
 #[allow(unused)]
 mod built_info {
     include!(concat!(env!("OUT_DIR"), "/built.rs"));
@@ -5,11 +5,13 @@ use metrics_tracing_context::{LabelFilter, TracingContextLayer};
 use metrics_util::layers::Layer;
 use metrics_util::{CompositeKey, Handle, MetricKind};
 use once_cell::sync::OnceCell;
+use std::collections::HashMap;
 use std::hash::Hash;
 use std::sync::{
-    atomic::{AtomicU64, Ordering},
-    Arc,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc, Mutex,
 };
+use std::time::{Duration, Instant};
 
 static CONTROLLER: OnceCell<Controller> = OnceCell::new();
 
@@ -59,9 +61,7 @@ pub fn init() -> crate::Result<()> {
     );
 
     // Initialize the controller.
-    let controller = Controller {
-        registry: Arc::clone(&registry),
-    };
+    let controller = Controller::new(Arc::clone(&registry));
     // Register the controller globally.
     CONTROLLER
         .set(controller)
@@ -106,7 +106,15 @@ where
     K: Eq + Hash + Clone + 'static,
     H: 'static,
 {
-    pub map: DashMap<K, H>,
+    pub map: DashMap<K, Tracked<H>>,
+}
+
+/// A handle, along with whether it was used since the registry was last swept
+/// for idle metrics.
+#[derive(Debug)]
+struct Tracked<H> {
+    handle: H,
+    touched: AtomicBool,
 }
 
 impl<K, H> VectorRegistry<K, H>
@@ -125,8 +133,12 @@ where
         I: FnOnce() -> H,
         O: FnOnce(&H) -> V,
     {
-        let valref = self.map.entry(key).or_insert_with(init);
-        op(valref.value())
+        let valref = self.map.entry(key).or_insert_with(|| Tracked {
+            handle: init(),
+            touched: AtomicBool::new(true),
+        });
+        valref.touched.store(true, Ordering::Relaxed);
+        op(&valref.handle)
     }
 }
 
@@ -217,6 +229,61 @@ impl LabelFilter for VectorLabelFilter {
 /// Controller allows capturing metric snapshots.
 pub struct Controller {
     registry: Arc<VectorRegistry<CompositeKey, Handle>>,
+    expiry: Mutex<Option<Expiry>>,
+}
+
+/// When the metrics that haven't been updated for `idle_timeout` are dropped
+/// from the registry, such as those of removed components.
+struct Expiry {
+    idle_timeout: Duration,
+    /// The last time each metric was seen updated by a sweep.
+    last_touched: HashMap<CompositeKey, Instant>,
+}
+
+impl Controller {
+    fn new(registry: Arc<VectorRegistry<CompositeKey, Handle>>) -> Self {
+        Self {
+            registry,
+            expiry: Mutex::new(None),
+        }
+    }
+
+    /// Drops the metrics that haven't been updated for `idle_timeout` from
+    /// the registry, or keeps them forever if it's `None`. Metrics are checked
+    /// whenever they are captured.
+    pub fn set_expiry(&self, idle_timeout: Option<Duration>) {
+        *self.expiry.lock().unwrap() = idle_timeout.map(|idle_timeout| Expiry {
+            idle_timeout,
+            last_touched: HashMap::new(),
+        });
+    }
+
+    fn expire(&self) {
+        let mut expiry = self.expiry.lock().unwrap();
+        let expiry = match expiry.as_mut() {
+            Some(expiry) => expiry,
+            None => return,
+        };
+
+        let now = Instant::now();
+        let idle_timeout = expiry.idle_timeout;
+        let last_touched = &mut expiry.last_touched;
+        // The shard of the registry being swept is locked, so metrics can't be
+        // updated between the check and their removal.
+        self.registry.map.retain(|key, tracked| {
+            if tracked.touched.swap(false, Ordering::Relaxed) {
+                last_touched.insert(key.clone(), now);
+                return true;
+            }
+            let touched = *last_touched.entry(key.clone()).or_insert(now);
+            if *key == CARDINALITY_KEY || now.duration_since(touched) < idle_timeout {
+                true
+            } else {
+                last_touched.remove(key);
+                false
+            }
+        });
+    }
 }
 
 /// Get a handle to the globally registered controller, if it's initialized.
@@ -227,17 +294,21 @@ pub fn get_controller() -> crate::Result<&'static Controller> {
 }
 
 fn snapshot(controller: &Controller) -> Vec<Event> {
+    controller.expire();
     controller
         .registry
         .map
         .iter()
-        .map(|valref| Metric::from_metric_kv(valref.key().key(), valref.value()).into())
+        .map(|valref| Metric::from_metric_kv(valref.key().key(), &valref.value().handle).into())
         .collect()
 }
 
 /// Clear all metrics from the registry.
 pub fn reset(controller: &Controller) {
-    controller.registry.map.clear()
+    controller.registry.map.clear();
+    if let Some(expiry) = controller.expiry.lock().unwrap().as_mut() {
+        expiry.last_touched.clear();
+    }
 }
 
 /// Take a snapshot of all gathered metrics and expose them as metric
@@ -248,7 +319,8 @@ pub fn capture_metrics(controller: &Controller) -> impl Iterator<Item = Event> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{event::Event, test_util::trace_init};
+    use super::*;
+    use crate::test_util::trace_init;
     use metrics::counter;
     use tracing::{span, Level};
 
@@ -338,4 +410,33 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn expires_idle_metrics() {
+        let registry = Arc::new(VectorRegistry {
+            map: DashMap::new(),
+        });
+        let controller = Controller::new(Arc::clone(&registry));
+        controller.set_expiry(Some(Duration::from_millis(100)));
+
+        let increment = |name: &'static str| {
+            let key = CompositeKey::new(MetricKind::COUNTER, Key::Owned(KeyData::from_name(name)));
+            registry.op(key, |handle| handle.increment_counter(1), Handle::counter);
+        };
+        let names = || {
+            let mut names = capture_metrics(&controller)
+                .map(|event| event.into_metric().name().to_owned())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        increment("idle_total");
+        increment("active_total");
+        assert_eq!(names(), vec!["active_total", "idle_total"]);
+
+        std::thread::sleep(Duration::from_millis(200));
+        increment("active_total");
+        assert_eq!(names(), vec!["active_total"]);
+    }
 }
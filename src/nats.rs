@@ -0,0 +1,54 @@
+use crate::tls::TlsConfig;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::path::PathBuf;
+
+#[derive(Debug, Snafu)]
+enum NatsError {
+    #[snafu(display("TLS option {:?} isn't supported by NATS", option))]
+    UnsupportedTlsOption { option: &'static str },
+    #[snafu(display("TLS option `crt_file` requires `key_file`, and the other way around"))]
+    IncompleteClientCertificate,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct NatsAuthConfig {
+    /// A credentials file, as generated for NATS 2.0 accounts, holding a user JWT and NKey seed.
+    pub credentials_file: Option<PathBuf>,
+    pub tls: Option<TlsConfig>,
+}
+
+impl NatsAuthConfig {
+    pub(crate) fn apply(&self, options: async_nats::Options) -> crate::Result<async_nats::Options> {
+        let mut options = match &self.credentials_file {
+            Some(path) => options.with_credentials(path),
+            None => options,
+        };
+
+        let tls = match &self.tls {
+            Some(tls) if tls.enabled.unwrap_or(false) => &tls.options,
+            _ => return Ok(options),
+        };
+        // The server is always verified, and keys can't be encrypted.
+        let unsupported = [
+            ("key_pass", tls.key_pass.is_some()),
+            ("verify_certificate", tls.verify_certificate == Some(false)),
+            ("verify_hostname", tls.verify_hostname == Some(false)),
+        ];
+        if let Some(&(option, _)) = unsupported.iter().find(|(_, set)| *set) {
+            return Err(NatsError::UnsupportedTlsOption { option }.into());
+        }
+
+        options = options.tls_required(true);
+        if let Some(path) = &tls.ca_file {
+            options = options.add_root_certificate(path);
+        }
+        match (&tls.crt_file, &tls.key_file) {
+            (Some(crt_file), Some(key_file)) => options = options.client_cert(crt_file, key_file),
+            (None, None) => (),
+            _ => return Err(NatsError::IncompleteClientCertificate.into()),
+        }
+
+        Ok(options)
+    }
+}
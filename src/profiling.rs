@@ -0,0 +1,256 @@
+//! A sampling profiler that attributes CPU time to components.
+//!
+//! Every thread keeps a stack of the component spans it has currently entered.
+//! While a profile is being taken, a sampling thread periodically reads those
+//! stacks and counts how often each one is seen, which approximates the share
+//! of CPU time each component is using. Results are in the "folded" stack
+//! format understood by flamegraph tools.
+
+use lazy_static::lazy_static;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// The root frame of every sampled stack.
+const ROOT_FRAME: &str = "vector";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+type Stack = Arc<Mutex<Vec<(span::Id, String)>>>;
+
+lazy_static! {
+    static ref STACKS: Mutex<Vec<Weak<Mutex<Vec<(span::Id, String)>>>>> = Mutex::new(Vec::new());
+    static ref PROFILE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::new(());
+}
+
+thread_local! {
+    static STACK: Stack = {
+        let stack = Stack::default();
+        STACKS.lock().unwrap().push(Arc::downgrade(&stack));
+        stack
+    };
+}
+
+/// Profiling is opt-in, as tracking entered spans isn't free.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The frame of a component span, stored in the span's extensions.
+struct ComponentFrame(String);
+
+#[derive(Default)]
+struct ComponentVisitor {
+    kind: Option<String>,
+    name: Option<String>,
+}
+
+impl Visit for ComponentVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "component_kind" => self.kind = Some(value.to_owned()),
+            "component_name" => self.name = Some(value.to_owned()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "component_kind" => self.kind = Some(format!("{:?}", value)),
+            "component_name" => self.name = Some(format!("{:?}", value)),
+            _ => {}
+        }
+    }
+}
+
+/// Tracks which component spans each thread is currently in.
+#[derive(Default)]
+pub struct ProfilingLayer;
+
+impl<S> Layer<S> for ProfilingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut visitor = ComponentVisitor::default();
+        attrs.record(&mut visitor);
+
+        if let (Some(kind), Some(name)) = (visitor.kind, visitor.name) {
+            if let Some(span) = ctx.span(id) {
+                let frame = ComponentFrame(format!("{}:{}", kind, name));
+                span.extensions_mut().insert(frame);
+            }
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if !is_enabled() {
+            return;
+        }
+
+        if let Some(span) = ctx.span(id) {
+            if let Some(frame) = span.extensions().get::<ComponentFrame>() {
+                let frame = frame.0.clone();
+                STACK.with(|stack| stack.lock().unwrap().push((id.clone(), frame)));
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let is_component = ctx.span(id).map_or(false, |span| {
+            span.extensions().get::<ComponentFrame>().is_some()
+        });
+        if !is_component {
+            return;
+        }
+
+        // Profiling may have been disabled since the span was entered, so
+        // this only checks whether the span is on the stack.
+        let _ = STACK.try_with(|stack| {
+            let mut stack = stack.lock().unwrap();
+            if stack.last().map_or(false, |(top, _)| top == id) {
+                stack.pop();
+            }
+        });
+    }
+}
+
+/// How many times each stack of frames was sampled.
+#[derive(Debug, Default)]
+pub struct Profile {
+    pub duration: Duration,
+    pub frequency: u32,
+    pub samples: BTreeMap<Vec<String>, u64>,
+}
+
+impl Profile {
+    /// Renders the profile in the folded stack format, one `frame;frame count`
+    /// line per stack.
+    pub fn folded(&self) -> String {
+        self.samples
+            .iter()
+            .map(|(frames, count)| format!("{} {}\n", frames.join(";"), count))
+            .collect()
+    }
+}
+
+fn frames(stack: &[(span::Id, String)]) -> Option<Vec<String>> {
+    if stack.is_empty() {
+        None
+    } else {
+        let frames = std::iter::once(ROOT_FRAME.to_owned())
+            .chain(stack.iter().map(|(_, frame)| frame.clone()))
+            .collect();
+        Some(frames)
+    }
+}
+
+fn sample(samples: &mut BTreeMap<Vec<String>, u64>) {
+    let mut stacks = STACKS.lock().unwrap();
+    stacks.retain(|stack| stack.strong_count() > 0);
+
+    for stack in stacks.iter().filter_map(Weak::upgrade) {
+        if let Some(frames) = frames(&stack.lock().unwrap()) {
+            *samples.entry(frames).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Samples the stacks of all threads `frequency` times a second, for `duration`.
+/// Only one profile is taken at a time, later callers wait for earlier ones to
+/// finish.
+pub async fn profile(duration: Duration, frequency: u32) -> Result<Profile, String> {
+    if !is_enabled() {
+        return Err(
+            "Profiling is disabled. Enable it by setting `api.profiling` to `true`.".into(),
+        );
+    }
+    if frequency == 0 {
+        return Err("Sampling frequency must be greater than 0.".into());
+    }
+
+    let _guard = PROFILE_LOCK.lock().await;
+
+    tokio::task::spawn_blocking(move || {
+        let period = Duration::from_secs(1) / frequency;
+        let start = Instant::now();
+        let mut samples = BTreeMap::new();
+
+        while start.elapsed() < duration {
+            sample(&mut samples);
+            std::thread::sleep(period);
+        }
+
+        Profile {
+            duration,
+            frequency,
+            samples,
+        }
+    })
+    .await
+    .map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn current_frames() -> Option<Vec<String>> {
+        STACK.with(|stack| frames(&stack.lock().unwrap()))
+    }
+
+    #[test]
+    fn tracks_entered_component_spans() {
+        set_enabled(true);
+
+        let subscriber = tracing_subscriber::registry::Registry::default().with(ProfilingLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = error_span!(
+                "sink",
+                component_kind = "sink",
+                component_name = %"out",
+                component_type = %"blackhole",
+            );
+
+            {
+                let _enter = span.enter();
+                let inner = info_span!("request");
+                let _inner = inner.enter();
+                assert_eq!(
+                    current_frames(),
+                    Some(vec!["vector".to_owned(), "sink:out".to_owned()])
+                );
+            }
+            assert_eq!(current_frames(), None);
+        });
+    }
+
+    #[test]
+    fn folded_output() {
+        let mut samples = BTreeMap::new();
+        samples.insert(vec!["vector".to_owned(), "sink:out".to_owned()], 3);
+        samples.insert(vec!["vector".to_owned(), "source:in".to_owned()], 1);
+
+        let profile = Profile {
+            samples,
+            ..Default::default()
+        };
+        assert_eq!(profile.folded(), "vector;sink:out 3\nvector;source:in 1\n");
+    }
+}
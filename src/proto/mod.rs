@@ -0,0 +1,64 @@
+//! The gRPC protocols spoken by sources and sinks.
+
+#[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
+use serde::{Deserialize, Serialize};
+
+/// The gRPC service of version 2 of the `vector` sink and source, which carries batches of
+/// events encoded as in `proto/event.proto`.
+#[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
+pub mod vector {
+    tonic::include_proto!("vector");
+}
+
+/// The OpenTelemetry protocol (OTLP), as vendored in `proto/opentelemetry`.
+#[cfg(feature = "sources-opentelemetry")]
+pub mod opentelemetry {
+    pub mod proto {
+        pub mod common {
+            pub mod v1 {
+                tonic::include_proto!("opentelemetry.proto.common.v1");
+            }
+        }
+        pub mod resource {
+            pub mod v1 {
+                tonic::include_proto!("opentelemetry.proto.resource.v1");
+            }
+        }
+        pub mod logs {
+            pub mod v1 {
+                tonic::include_proto!("opentelemetry.proto.logs.v1");
+            }
+        }
+        pub mod metrics {
+            pub mod v1 {
+                tonic::include_proto!("opentelemetry.proto.metrics.v1");
+            }
+        }
+        pub mod collector {
+            pub mod logs {
+                pub mod v1 {
+                    tonic::include_proto!("opentelemetry.proto.collector.logs.v1");
+                }
+            }
+            pub mod metrics {
+                pub mod v1 {
+                    tonic::include_proto!("opentelemetry.proto.collector.metrics.v1");
+                }
+            }
+        }
+    }
+}
+
+/// The version of the protocol of the `vector` sink and source, which must match on both ends.
+#[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Derivative)]
+#[derivative(Default)]
+pub enum VectorVersion {
+    /// Length delimited events over TCP.
+    #[serde(rename = "1")]
+    #[derivative(Default)]
+    V1,
+    /// Batches of events over gRPC, which are acknowledged once accepted by the source.
+    #[serde(rename = "2")]
+    V2,
+}
@@ -0,0 +1,543 @@
+use crate::{
+    cli::handle_config_errors,
+    config::{self, log_schema, ConfigBuilder, DataType, GlobalOptions, SourceConfig},
+    event::{Event, Value},
+    internal_events::{ReplayEventReceived, ReplayFailedDecode, ReplayFailedOpen},
+    shutdown::ShutdownSignal,
+    sources, topology, Pipeline,
+};
+use chrono::{DateTime, Utc};
+use flate2::read::MultiGzDecoder;
+use futures::{executor, FutureExt, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    thread,
+};
+use structopt::StructOpt;
+use tokio::sync::mpsc::channel;
+
+/// Name of the source injected into the loaded config to emit replayed events.
+const REPLAY_SOURCE_NAME: &str = "_replay";
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Components (transforms or sinks) of the loaded config to send replayed events into.
+    /// Components that aren't downstream of these are not started. If none are specified,
+    /// replayed events are sent into every sink.
+    #[structopt(short, long, use_delimiter(true))]
+    into: Vec<String>,
+
+    /// The encoding the archived files were written with by the `file` or `aws_s3` sinks,
+    /// either `ndjson` or `text`. Files encoded as `csv` or `avro` can't be replayed.
+    #[structopt(short, long, default_value = "ndjson")]
+    encoding: Encoding,
+
+    /// The compression of the archived files. `auto` detects gzip and zstd from the file
+    /// contents.
+    #[structopt(
+        long,
+        default_value = "auto",
+        possible_values = &["auto", "gzip", "zstd", "none"]
+    )]
+    compression: Compression,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[structopt(name = "config", short, long)]
+    paths: Vec<PathBuf>,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// TOML file format is expected.
+    #[structopt(name = "config-toml", long)]
+    paths_toml: Vec<PathBuf>,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// JSON file format is expected.
+    #[structopt(name = "config-json", long)]
+    paths_json: Vec<PathBuf>,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// YAML file format is expected.
+    #[structopt(name = "config-yaml", long)]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Archived files to replay, in order.
+    #[structopt(required = true)]
+    files: Vec<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<(PathBuf, config::FormatHint)> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::TOML)),
+            (&self.paths_json, Some(config::Format::JSON)),
+            (&self.paths_yaml, Some(config::Format::YAML)),
+        ])
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Ndjson,
+    Text,
+}
+
+impl std::str::FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ndjson" => Ok(Encoding::Ndjson),
+            "text" => Ok(Encoding::Text),
+            "csv" => Err(
+                "`csv` files can't be replayed, as their rows don't hold the names of the \
+                fields, expected `ndjson` or `text`"
+                    .to_owned(),
+            ),
+            "avro" => Err("`avro` files can't be replayed, expected `ndjson` or `text`".to_owned()),
+            s => Err(format!(
+                "{} is not a valid option, expected `ndjson` or `text`",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    Auto,
+    Gzip,
+    Zstd,
+    None,
+}
+
+impl std::str::FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Compression::Auto),
+            "gzip" => Ok(Compression::Gzip),
+            "zstd" => Ok(Compression::Zstd),
+            "none" => Ok(Compression::None),
+            s => Err(format!(
+                "{} is not a valid option, expected `auto`, `gzip`, `zstd` or `none`",
+                s
+            )),
+        }
+    }
+}
+
+/// Reads archived files and replays them through the loaded config, then exits once
+/// every replayed event has been processed.
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let paths = match config::process_paths(&opts.paths_with_formats()) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    if let Err(errors) = config::init_log_schema(&paths, true) {
+        return handle_config_errors(errors);
+    }
+
+    let mut builder = match config::load_builder_from_paths(&paths) {
+        Ok((builder, _warnings)) => builder,
+        Err(errors) => return handle_config_errors(errors),
+    };
+
+    if let Err(errors) = rewire(&mut builder, &opts.into) {
+        return handle_config_errors(errors);
+    }
+
+    builder.add_source(
+        REPLAY_SOURCE_NAME,
+        ReplayConfig {
+            files: opts.files.clone(),
+            encoding: opts.encoding,
+            compression: opts.compression,
+        },
+    );
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => return handle_config_errors(errors),
+    };
+
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+
+    let (topology, _graceful_crash) = match topology::start_validated(config, diff, pieces).await {
+        Some(started) => started,
+        None => return exitcode::CONFIG,
+    };
+
+    topology.sources_finished().await;
+    topology.stop().await;
+
+    info!(message = "Finished replaying files.", files = ?opts.files);
+
+    exitcode::OK
+}
+
+/// Removes the config's own sources, connects the replay source to `into` (or to every
+/// sink when `into` is empty), and drops any component left without inputs.
+fn rewire(builder: &mut ConfigBuilder, into: &[String]) -> Result<(), Vec<String>> {
+    let errors = into
+        .iter()
+        .filter(|name| {
+            !builder.transforms.contains_key(*name) && !builder.sinks.contains_key(*name)
+        })
+        .map(|name| format!("Replay target \"{}\" is not a transform or sink.", name))
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let targets = if into.is_empty() {
+        builder.sinks.keys().cloned().collect::<HashSet<_>>()
+    } else {
+        into.iter().cloned().collect::<HashSet<_>>()
+    };
+
+    builder.sources.clear();
+    builder.tests.clear();
+
+    for (name, transform) in builder.transforms.iter_mut() {
+        if targets.contains(name) {
+            transform.inputs = vec![REPLAY_SOURCE_NAME.to_string()];
+        }
+    }
+    for (name, sink) in builder.sinks.iter_mut() {
+        if targets.contains(name) {
+            sink.inputs = vec![REPLAY_SOURCE_NAME.to_string()];
+        }
+    }
+
+    // Removing a transform can leave its outputs without inputs, so repeat until nothing
+    // else is dropped.
+    loop {
+        let available = builder
+            .transforms
+            .keys()
+            .cloned()
+            .chain(std::iter::once(REPLAY_SOURCE_NAME.to_string()))
+            .collect::<HashSet<_>>();
+
+        for transform in builder.transforms.values_mut() {
+            transform.inputs.retain(|input| available.contains(input));
+        }
+        for sink in builder.sinks.values_mut() {
+            sink.inputs.retain(|input| available.contains(input));
+        }
+
+        let before = builder.transforms.len() + builder.sinks.len();
+        builder.transforms.retain(|_, t| !t.inputs.is_empty());
+        builder.sinks.retain(|_, s| !s.inputs.is_empty());
+
+        if builder.transforms.len() + builder.sinks.len() == before {
+            break;
+        }
+    }
+
+    if builder.sinks.is_empty() {
+        Err(vec![
+            "No sinks are downstream of the replay targets.".to_owned()
+        ])
+    } else {
+        Ok(())
+    }
+}
+
+/// The source the replayed events are emitted by. It's only added by the `replay` command,
+/// and isn't listed among the sources, but like every source it's registered under its
+/// `type`, so `type = "replay"` in a config builds it as well.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct ReplayConfig {
+    files: Vec<PathBuf>,
+    encoding: Encoding,
+    compression: Compression,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "replay")]
+impl SourceConfig for ReplayConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<sources::Source> {
+        Ok(replay_source(self.clone(), shutdown, out))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "replay"
+    }
+}
+
+fn replay_source(config: ReplayConfig, shutdown: ShutdownSignal, out: Pipeline) -> sources::Source {
+    let (mut sender, receiver) = channel(1024);
+
+    // Files are read on a background thread, since the decoders are blocking.
+    thread::spawn(move || {
+        for path in &config.files {
+            let reader = match open(path, config.compression) {
+                Ok(reader) => reader,
+                Err(error) => {
+                    emit!(ReplayFailedOpen { path, error });
+                    continue;
+                }
+            };
+
+            for line in reader.lines() {
+                let event = match line {
+                    Ok(line) => match decode(&line, config.encoding) {
+                        Some(event) => event,
+                        None => continue,
+                    },
+                    Err(error) => {
+                        emit!(ReplayFailedOpen { path, error });
+                        break;
+                    }
+                };
+
+                if executor::block_on(sender.send(event)).is_err() {
+                    // receiver has closed so we should shutdown
+                    return;
+                }
+            }
+        }
+    });
+
+    Box::pin(async move {
+        let mut out =
+            out.sink_map_err(|error| error!(message = "Unable to send event to out.", %error));
+
+        let res = receiver
+            .take_until(shutdown)
+            .map(Ok)
+            .forward(&mut out)
+            .inspect(|_| info!("Finished sending."))
+            .await;
+
+        let _ = out.flush().await; // error emitted by sink_map_err
+
+        res
+    })
+}
+
+fn open(path: &Path, compression: Compression) -> io::Result<Box<dyn BufRead + Send>> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let compression = match compression {
+        Compression::Auto => {
+            let start = reader.fill_buf()?;
+            if start.starts_with(GZIP_MAGIC) {
+                Compression::Gzip
+            } else if start.starts_with(ZSTD_MAGIC) {
+                Compression::Zstd
+            } else {
+                Compression::None
+            }
+        }
+        compression => compression,
+    };
+
+    Ok(match compression {
+        Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+        Compression::Zstd => Box::new(BufReader::new(zstd::Decoder::with_buffer(reader)?)),
+        Compression::Auto | Compression::None => Box::new(reader),
+    })
+}
+
+fn decode(line: &str, encoding: Encoding) -> Option<Event> {
+    if line.is_empty() {
+        return None;
+    }
+
+    emit!(ReplayEventReceived {
+        byte_size: line.len()
+    });
+
+    match encoding {
+        Encoding::Text => Some(Event::from(line)),
+        Encoding::Ndjson => {
+            let mut event = serde_json::from_str::<serde_json::Value>(line)
+                .map_err(crate::Error::from)
+                .and_then(Event::try_from)
+                .map_err(|error| emit!(ReplayFailedDecode { error }))
+                .ok()?;
+
+            // Sinks write timestamps as RFC 3339 strings, so restore them as timestamps.
+            let log = event.as_mut_log();
+            let timestamp = log
+                .get(log_schema().timestamp_key())
+                .and_then(|value| match value {
+                    Value::Bytes(bytes) => std::str::from_utf8(bytes).ok(),
+                    _ => None,
+                })
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|ts| ts.with_timezone(&Utc));
+            if let Some(timestamp) = timestamp {
+                log.insert(log_schema().timestamp_key(), timestamp);
+            }
+
+            Some(event)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_file;
+    use flate2::{write::GzEncoder, Compression as GzCompression};
+    use std::io::Write;
+
+    fn load(config: &str) -> ConfigBuilder {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn rewire_prunes_unrelated_components() {
+        let mut builder = load(
+            r#"
+            [sources.in]
+            type = "stdin"
+
+            [transforms.parse]
+            type = "json_parser"
+            inputs = ["in"]
+
+            [transforms.other]
+            type = "json_parser"
+            inputs = ["in"]
+
+            [sinks.archive]
+            type = "blackhole"
+            inputs = ["parse"]
+
+            [sinks.unrelated]
+            type = "blackhole"
+            inputs = ["other"]
+            "#,
+        );
+
+        rewire(&mut builder, &["parse".to_string()]).unwrap();
+
+        assert!(builder.sources.is_empty());
+        assert_eq!(builder.transforms["parse"].inputs, vec![REPLAY_SOURCE_NAME]);
+        assert!(!builder.transforms.contains_key("other"));
+        assert!(builder.sinks.contains_key("archive"));
+        assert!(!builder.sinks.contains_key("unrelated"));
+    }
+
+    #[test]
+    fn rewire_defaults_to_all_sinks() {
+        let mut builder = load(
+            r#"
+            [sources.in]
+            type = "stdin"
+
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["in"]
+            "#,
+        );
+
+        rewire(&mut builder, &[]).unwrap();
+
+        assert_eq!(builder.sinks["out"].inputs, vec![REPLAY_SOURCE_NAME]);
+    }
+
+    #[test]
+    fn rewire_rejects_unknown_target() {
+        let mut builder = load(
+            r#"
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["in"]
+            "#,
+        );
+
+        assert!(rewire(&mut builder, &["missing".to_string()]).is_err());
+    }
+
+    #[test]
+    fn decode_ndjson_restores_timestamp() {
+        let event = decode(
+            r#"{"message":"hello","timestamp":"2021-01-01T00:00:00Z"}"#,
+            Encoding::Ndjson,
+        )
+        .unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert!(matches!(
+            log[log_schema().timestamp_key()],
+            Value::Timestamp(_)
+        ));
+    }
+
+    #[test]
+    fn open_detects_gzip() {
+        let path = temp_file();
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), GzCompression::fast());
+        encoder.write_all(b"line 1\nline 2\n").unwrap();
+        encoder.finish().unwrap();
+
+        let lines = open(&path, Compression::Auto)
+            .unwrap()
+            .lines()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(lines, vec!["line 1", "line 2"]);
+    }
+
+    #[test]
+    fn open_detects_zstd() {
+        let path = temp_file();
+        let compressed = zstd::encode_all(&b"line 1\nline 2\n"[..], 3).unwrap();
+        std::fs::write(&path, compressed).unwrap();
+
+        let lines = open(&path, Compression::Auto)
+            .unwrap()
+            .lines()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(lines, vec!["line 1", "line 2"]);
+    }
+
+    #[test]
+    fn rejects_encodings_that_cant_be_replayed() {
+        for encoding in &["csv", "avro"] {
+            let error = encoding.parse::<Encoding>().unwrap_err();
+            assert!(error.contains("can't be replayed"), "{}", error);
+        }
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     rusoto::{self, AWSAuthentication, RegionOrEndpoint},
     serde::to_string,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{AvroEncoder, EncodingConfig, EncodingConfiguration},
         retries::RetryLogic,
         sink::Response,
         BatchConfig, BatchSettings, Buffer, Compression, Concurrency, PartitionBatchSink,
@@ -130,6 +130,8 @@ lazy_static! {
 pub enum Encoding {
     Text,
     Ndjson,
+    Csv,
+    Avro,
 }
 
 inventory::submit! {
@@ -192,6 +194,18 @@ enum HealthcheckError {
 impl S3SinkConfig {
     pub fn new(&self, client: S3Client, cx: SinkContext) -> crate::Result<super::VectorSink> {
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
+        if let Encoding::Csv = self.encoding.codec() {
+            // Objects are compressed as they're batched, so they can't be
+            // started with a header.
+            if self.encoding.csv_options()?.header() {
+                return Err("`csv.header` isn't supported by the `aws_s3` sink.".into());
+            }
+        }
+        // Each object is an Avro container file, which starts with the schema.
+        let avro = match self.encoding.codec() {
+            Encoding::Avro => Some(self.encoding.avro_encoder()?),
+            _ => None,
+        };
         let encoding = self.encoding.clone();
 
         let compression = self.compression;
@@ -229,10 +243,16 @@ impl S3SinkConfig {
             .settings(request, S3RetryLogic)
             .service(s3);
 
-        let buffer = PartitionBuffer::new(Buffer::new(batch.size, self.compression));
+        let mut buffer = Buffer::new(batch.size, self.compression);
+        if let Some(avro) = &avro {
+            buffer = buffer.with_header(avro.header());
+        }
+        let buffer = PartitionBuffer::new(buffer);
 
         let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key_prefix, &encoding)).map(Ok))
+            .with_flat_map(move |e| {
+                stream::iter(encode_event(e, &key_prefix, &encoding, avro.as_ref())).map(Ok)
+            })
             .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
 
         Ok(super::VectorSink::Sink(Box::new(sink)))
@@ -395,6 +415,7 @@ fn encode_event(
     mut event: Event,
     key_prefix: &Template,
     encoding: &EncodingConfig<Encoding>,
+    avro: Option<&AvroEncoder>,
 ) -> Option<PartitionInnerBuffer<Vec<u8>, Bytes>> {
     let key = key_prefix
         .render_string(&event)
@@ -409,6 +430,14 @@ fn encode_event(
 
     encoding.apply_rules(&mut event);
 
+    if let Encoding::Text = encoding.codec() {
+        if let Some(line) = encoding.render_template(&event) {
+            let mut bytes = line?.into_bytes();
+            bytes.push(b'\n');
+            return Some(PartitionInnerBuffer::new(bytes, key.into()));
+        }
+    }
+
     let log = event.into_log();
     let bytes = match encoding.codec() {
         Encoding::Ndjson => serde_json::to_vec(&log)
@@ -425,6 +454,12 @@ fn encode_event(
             bytes.push(b'\n');
             bytes
         }
+        Encoding::Csv => {
+            let mut bytes = encoding.csv().as_ref()?.encode(&log).into_bytes();
+            bytes.push(b'\n');
+            bytes
+        }
+        Encoding::Avro => avro?.encode_block(&log)?,
     };
 
     Some(PartitionInnerBuffer::new(bytes, key.into()))
@@ -447,6 +482,7 @@ mod tests {
             message.clone().into(),
             &batch_time_format,
             &Encoding::Text.into(),
+            None,
         )
         .unwrap();
 
@@ -455,6 +491,25 @@ mod tests {
         assert_eq!(&bytes[..], encoded_message.as_bytes());
     }
 
+    #[test]
+    fn s3_encode_event_template() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("level", "info");
+
+        let batch_time_format = Template::try_from("date=%F").unwrap();
+        let encoding: EncodingConfig<Encoding> = toml::from_str(
+            r#"
+            codec = "text"
+            template = "[{{ level }}] {{ message }}"
+            "#,
+        )
+        .unwrap();
+        let bytes = encode_event(event, &batch_time_format, &encoding, None).unwrap();
+
+        let (bytes, _) = bytes.into_parts();
+        assert_eq!(&bytes[..], b"[info] hello world\n");
+    }
+
     #[test]
     fn s3_encode_event_ndjson() {
         let message = "hello world".to_string();
@@ -462,7 +517,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let bytes = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let bytes =
+            encode_event(event, &batch_time_format, &Encoding::Ndjson.into(), None).unwrap();
 
         let (bytes, _) = bytes.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -471,6 +527,38 @@ mod tests {
         assert_eq!(map["key"], "value".to_string());
     }
 
+    #[test]
+    fn s3_encode_event_avro() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("status", 200);
+
+        let key_prefix = Template::try_from("date=%F").unwrap();
+        let encoding: EncodingConfig<Encoding> = toml::from_str(
+            r#"
+            codec = "avro"
+            schema = '{"type": "record", "name": "log", "fields": [{"name": "status", "type": "long"}]}'
+            "#,
+        )
+        .unwrap();
+        let avro = encoding.avro_encoder().unwrap();
+
+        let mut object = avro.header();
+        let bytes = encode_event(event, &key_prefix, &encoding, Some(&avro)).unwrap();
+        object.extend(bytes.into_parts().0);
+
+        let records = avro_rs::Reader::new(&object[..])
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            records,
+            vec![avro_rs::types::Value::Record(vec![(
+                "status".to_owned(),
+                avro_rs::types::Value::Long(200)
+            )])]
+        );
+    }
+
     #[test]
     fn s3_encode_event_with_removed_key() {
         let message = "hello world".to_string();
@@ -485,9 +573,12 @@ mod tests {
             only_fields: None,
             except_fields: Some(vec!["key".into()]),
             timestamp_format: None,
+            csv: None,
+            protobuf: None,
+            template: None,
         };
 
-        let bytes = encode_event(event, &key_prefix, &encoding_config).unwrap();
+        let bytes = encode_event(event, &key_prefix, &encoding_config, None).unwrap();
 
         let (bytes, _) = bytes.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
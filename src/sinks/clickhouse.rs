@@ -1,13 +1,16 @@
 use crate::{
     config::{DataType, SinkConfig, SinkContext, SinkDescription},
-    event::Event,
+    event::{Event, Value},
     http::{Auth, HttpClient, HttpError, MaybeAuth},
+    internal_events::TemplateRenderingFailed,
     sinks::util::{
         encoding::{EncodingConfigWithDefault, EncodingConfiguration},
-        http::{BatchedHttpSink, HttpRetryLogic, HttpSink},
+        http::{HttpRetryLogic, HttpSink, PartitionHttpSink},
         retries::{RetryAction, RetryLogic},
-        BatchConfig, BatchSettings, Buffer, Compression, TowerRequestConfig, UriSerde,
+        BatchConfig, BatchSettings, Buffer, Compression, PartitionBuffer, PartitionInnerBuffer,
+        TowerRequestConfig, UriSerde,
     },
+    template::{Template, TemplateParseError},
     tls::{TlsOptions, TlsSettings},
 };
 use bytes::Bytes;
@@ -16,7 +19,18 @@ use http::{Request, StatusCode, Uri};
 use hyper::Body;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use snafu::ResultExt;
+use snafu::{ResultExt, Snafu};
+use std::convert::TryFrom;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid database template: {}", source))]
+    DatabaseTemplate { source: TemplateParseError },
+    #[snafu(display("invalid table template: {}", source))]
+    TableTemplate { source: TemplateParseError },
+    #[snafu(display("the `tsv` format requires `columns`"))]
+    MissingColumns,
+}
 
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
@@ -26,6 +40,16 @@ pub struct ClickhouseConfig {
     pub endpoint: UriSerde,
     pub table: String,
     pub database: Option<String>,
+    #[serde(default)]
+    pub format: Format,
+    /// The columns rows are inserted into, in the order of `tsv` rows.
+    pub columns: Option<Vec<String>>,
+    /// Ignores the fields of events that aren't columns of the table.
+    #[serde(default)]
+    pub skip_unknown_fields: bool,
+    /// Lets the server buffer inserts, and write them to the table in larger blocks.
+    #[serde(default)]
+    pub async_insert: bool,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(
@@ -61,6 +85,24 @@ pub enum Encoding {
     Default,
 }
 
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Format {
+    #[derivative(Default)]
+    JsonEachRow,
+    Tsv,
+}
+
+impl Format {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Format::JsonEachRow => "JSONEachRow",
+            Format::Tsv => "TabSeparated",
+        }
+    }
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "clickhouse")]
 impl SinkConfig for ClickhouseConfig {
@@ -81,9 +123,9 @@ impl SinkConfig for ClickhouseConfig {
             ..self.clone()
         };
 
-        let sink = BatchedHttpSink::with_retry_logic(
-            config.clone(),
-            Buffer::new(batch.size, self.compression),
+        let sink = PartitionHttpSink::with_retry_logic(
+            ClickhouseSink::new(config.clone())?,
+            PartitionBuffer::new(Buffer::new(batch.size, self.compression)),
             ClickhouseRetryLogic::default(),
             request,
             batch.timeout,
@@ -106,32 +148,107 @@ impl SinkConfig for ClickhouseConfig {
     }
 }
 
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct PartitionKey {
+    database: String,
+    table: String,
+}
+
+struct ClickhouseSink {
+    endpoint: Uri,
+    database: Template,
+    table: Template,
+    format: Format,
+    columns: Vec<String>,
+    skip_unknown_fields: bool,
+    async_insert: bool,
+    compression: Compression,
+    encoding: EncodingConfigWithDefault<Encoding>,
+    auth: Option<Auth>,
+}
+
+impl ClickhouseSink {
+    fn new(config: ClickhouseConfig) -> crate::Result<Self> {
+        let columns = config.columns.unwrap_or_default();
+        if config.format == Format::Tsv && columns.is_empty() {
+            return Err(BuildError::MissingColumns.into());
+        }
+        let database = config.database.as_deref().unwrap_or("default");
+
+        Ok(ClickhouseSink {
+            endpoint: config.endpoint.uri,
+            database: Template::try_from(database).context(DatabaseTemplate)?,
+            table: Template::try_from(config.table).context(TableTemplate)?,
+            format: config.format,
+            columns,
+            skip_unknown_fields: config.skip_unknown_fields,
+            async_insert: config.async_insert,
+            compression: config.compression,
+            encoding: config.encoding,
+            auth: config.auth,
+        })
+    }
+
+    fn render(&self, template: &Template, event: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingFailed {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+
+    fn uri(&self, key: &PartitionKey) -> crate::Result<Uri> {
+        let query = insert_query(&key.database, &key.table, &self.columns, self.format);
+
+        let mut settings = Vec::new();
+        if self.skip_unknown_fields {
+            settings.push(("input_format_skip_unknown_fields", "1"));
+        }
+        if self.async_insert {
+            settings.push(("async_insert", "1"));
+        }
+
+        set_uri_query(&self.endpoint, &query, &settings)
+    }
+}
+
 #[async_trait::async_trait]
-impl HttpSink for ClickhouseConfig {
-    type Input = Vec<u8>;
-    type Output = Vec<u8>;
+impl HttpSink for ClickhouseSink {
+    type Input = PartitionInnerBuffer<Vec<u8>, PartitionKey>;
+    type Output = PartitionInnerBuffer<Vec<u8>, PartitionKey>;
 
     fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
+        let key = PartitionKey {
+            database: self.render(&self.database, &event, "database")?,
+            table: self.render(&self.table, &event, "table")?,
+        };
+
         self.encoding.apply_rules(&mut event);
 
-        let mut body =
-            serde_json::to_vec(&event.as_log().all_fields()).expect("Events should be valid json!");
+        let mut body = match self.format {
+            Format::JsonEachRow => serde_json::to_vec(&event.as_log().all_fields())
+                .expect("Events should be valid json!"),
+            Format::Tsv => encode_tsv_row(&event, &self.columns).into_bytes(),
+        };
         body.push(b'\n');
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, key))
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
-        let database = if let Some(database) = &self.database {
-            database.as_str()
-        } else {
-            "default"
-        };
-
-        let uri =
-            set_uri_query(&self.endpoint.uri, database, &self.table).expect("Unable to encode uri");
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
+        let (events, key) = output.into_parts();
+        let uri = self.uri(&key)?;
 
-        let mut builder = Request::post(&uri).header("Content-Type", "application/x-ndjson");
+        let content_type = match self.format {
+            Format::JsonEachRow => "application/x-ndjson",
+            Format::Tsv => "text/tab-separated-values",
+        };
+        let mut builder = Request::post(&uri).header("Content-Type", content_type);
 
         if let Some(ce) = self.compression.content_encoding() {
             builder = builder.header("Content-Encoding", ce);
@@ -164,18 +281,59 @@ async fn healthcheck(client: HttpClient, config: ClickhouseConfig) -> crate::Res
     }
 }
 
-fn set_uri_query(uri: &Uri, database: &str, table: &str) -> crate::Result<Uri> {
-    let query = url::form_urlencoded::Serializer::new(String::new())
-        .append_pair(
-            "query",
-            format!(
-                "INSERT INTO \"{}\".\"{}\" FORMAT JSONEachRow",
-                database,
-                table.replace("\"", "\\\"")
-            )
-            .as_str(),
-        )
-        .finish();
+/// Encodes the values of the columns as a `TabSeparated` row, with missing values as `NULL`.
+fn encode_tsv_row(event: &Event, columns: &[String]) -> String {
+    let log = event.as_log();
+    columns
+        .iter()
+        .map(|column| match log.get(column) {
+            None | Some(Value::Null) => "\\N".to_owned(),
+            Some(value @ Value::Map(_)) | Some(value @ Value::Array(_)) => {
+                escape_tsv(&serde_json::to_string(value).unwrap())
+            }
+            Some(value) => escape_tsv(&value.to_string_lossy()),
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+fn escape_tsv(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace("\"", "\\\""))
+}
+
+fn insert_query(database: &str, table: &str, columns: &[String], format: Format) -> String {
+    let mut query = format!(
+        "INSERT INTO {}.{}",
+        quote_identifier(database),
+        quote_identifier(table)
+    );
+    if !columns.is_empty() {
+        let columns = columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>();
+        query.push_str(&format!(" ({})", columns.join(", ")));
+    }
+    query.push_str(" FORMAT ");
+    query.push_str(format.as_str());
+    query
+}
+
+fn set_uri_query(uri: &Uri, query: &str, settings: &[(&str, &str)]) -> crate::Result<Uri> {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    serializer.append_pair("query", query);
+    for (name, value) in settings {
+        serializer.append_pair(name, value);
+    }
+    let query = serializer.finish();
 
     let mut uri = uri.to_string();
     if !uri.ends_with('/') {
@@ -189,6 +347,47 @@ fn set_uri_query(uri: &Uri, database: &str, table: &str) -> crate::Result<Uri> {
         .map_err(Into::into)
 }
 
+/// The ClickHouse error codes of requests that fail the same way however often they're sent.
+///
+/// Error code definitions: https://github.com/ClickHouse/ClickHouse/blob/master/src/Common/ErrorCodes.cpp
+const PERMANENT_ERRORS: &[(u32, &str)] = &[
+    (6, "cannot parse text"),
+    (16, "no such column in table"),
+    (26, "cannot parse quoted string"),
+    (27, "cannot parse input"),
+    (38, "cannot parse date"),
+    (41, "cannot parse datetime"),
+    (53, "type mismatch"),
+    (60, "unknown table"),
+    (62, "syntax error"),
+    (70, "cannot convert type"),
+    (72, "cannot parse number"),
+    (81, "unknown database"),
+    (117, "incorrect data"),
+    (497, "access denied"),
+    (516, "authentication failed"),
+];
+
+/// The ClickHouse error code of a failed request, from its header or the start of its body.
+fn error_code(response: &http::Response<Bytes>) -> Option<u32> {
+    if let Some(code) = response
+        .headers()
+        .get("X-ClickHouse-Exception-Code")
+        .and_then(|code| code.to_str().ok())
+        .and_then(|code| code.parse().ok())
+    {
+        return Some(code);
+    }
+
+    let body = response.body();
+    if !body.starts_with(b"Code: ") {
+        return None;
+    }
+    let body = &body[b"Code: ".len()..];
+    let digits = body.iter().take_while(|c| c.is_ascii_digit()).count();
+    std::str::from_utf8(&body[..digits]).ok()?.parse().ok()
+}
+
 #[derive(Debug, Default, Clone)]
 struct ClickhouseRetryLogic {
     inner: HttpRetryLogic,
@@ -203,25 +402,17 @@ impl RetryLogic for ClickhouseRetryLogic {
     }
 
     fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        if let Some(code) = error_code(response) {
+            if let Some((_, reason)) = PERMANENT_ERRORS.iter().find(|(c, _)| *c == code) {
+                return RetryAction::DontRetry((*reason).into());
+            }
+        }
+
         match response.status() {
+            // Clickhouse returns 500's for most errors, including ones that are temporary
+            // such as too many parts or exceeding memory limits.
             StatusCode::INTERNAL_SERVER_ERROR => {
-                let body = response.body();
-
-                // Currently, clickhouse returns 500's incorrect data and type mismatch errors.
-                // This attempts to check if the body starts with `Code: {code_num}` and to not
-                // retry those errors.
-                //
-                // Reference: https://github.com/timberio/vector/pull/693#issuecomment-517332654
-                // Error code definitions: https://github.com/ClickHouse/ClickHouse/blob/master/dbms/src/Common/ErrorCodes.cpp
-                //
-                // Fix already merged: https://github.com/ClickHouse/ClickHouse/pull/6271
-                if body.starts_with(b"Code: 117") {
-                    RetryAction::DontRetry("incorrect data".into())
-                } else if body.starts_with(b"Code: 53") {
-                    RetryAction::DontRetry("type mismatch".into())
-                } else {
-                    RetryAction::Retry(String::from_utf8_lossy(body).to_string())
-                }
+                RetryAction::Retry(String::from_utf8_lossy(response.body()).to_string())
             }
             _ => self.inner.should_retry_response(response),
         }
@@ -241,16 +432,16 @@ mod tests {
     fn encode_valid() {
         let uri = set_uri_query(
             &"http://localhost:80".parse().unwrap(),
-            "my_database",
-            "my_table",
+            &insert_query("my_database", "my_table", &[], Format::JsonEachRow),
+            &[],
         )
         .unwrap();
         assert_eq!(uri, "http://localhost:80/?query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
 
         let uri = set_uri_query(
             &"http://localhost:80".parse().unwrap(),
-            "my_database",
-            "my_\"table\"",
+            &insert_query("my_database", "my_\"table\"", &[], Format::JsonEachRow),
+            &[],
         )
         .unwrap();
         assert_eq!(uri, "http://localhost:80/?query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
@@ -258,7 +449,88 @@ mod tests {
 
     #[test]
     fn encode_invalid() {
-        set_uri_query(&"localhost:80".parse().unwrap(), "my_database", "my_table").unwrap_err();
+        set_uri_query(&"localhost:80".parse().unwrap(), "SELECT 1", &[]).unwrap_err();
+    }
+
+    #[test]
+    fn encode_tsv_inserts() {
+        let config: ClickhouseConfig = toml::from_str(
+            r#"
+            endpoint = "http://localhost:8123"
+            database = "logs"
+            table = "{{ service }}"
+            format = "tsv"
+            columns = ["host", "message", "tags"]
+            skip_unknown_fields = true
+            async_insert = true"#,
+        )
+        .unwrap();
+        let sink = ClickhouseSink::new(config).unwrap();
+
+        let mut event = Event::from("line\twith\\tabs\n");
+        event.as_mut_log().insert("service", "nginx");
+        event.as_mut_log().insert("tags", vec!["a", "b"]);
+        let (body, key) = sink.encode_event(event).unwrap().into_parts();
+
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "\\N\tline\\twith\\\\tabs\\n\t[\"a\",\"b\"]\n"
+        );
+        assert_eq!(sink.uri(&key).unwrap(), "http://localhost:8123/?query=INSERT+INTO+%22logs%22.%22nginx%22+%28%22host%22%2C+%22message%22%2C+%22tags%22%29+FORMAT+TabSeparated&input_format_skip_unknown_fields=1&async_insert=1");
+    }
+
+    #[test]
+    fn tsv_requires_columns() {
+        let config: ClickhouseConfig = toml::from_str(
+            r#"
+            endpoint = "http://localhost:8123"
+            table = "logs"
+            format = "tsv""#,
+        )
+        .unwrap();
+        assert!(ClickhouseSink::new(config).is_err());
+    }
+
+    #[test]
+    fn classifies_error_codes() {
+        let response = |status, body: &'static str| {
+            http::Response::builder()
+                .status(status)
+                .body(Bytes::from(body))
+                .unwrap()
+        };
+        let logic = ClickhouseRetryLogic::default();
+
+        assert!(matches!(
+            logic.should_retry_response(&response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Code: 117, e.displayText() = DB::Exception: Unknown field found"
+            )),
+            RetryAction::DontRetry(_)
+        ));
+        assert!(matches!(
+            logic.should_retry_response(&response(
+                StatusCode::NOT_FOUND,
+                "Code: 60, e.displayText() = DB::Exception: Table default.logs doesn't exist"
+            )),
+            RetryAction::DontRetry(_)
+        ));
+        assert!(matches!(
+            logic.should_retry_response(&response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Code: 252, e.displayText() = DB::Exception: Too many parts"
+            )),
+            RetryAction::Retry(_)
+        ));
+
+        let mut memory_limit = response(StatusCode::INTERNAL_SERVER_ERROR, "");
+        memory_limit
+            .headers_mut()
+            .insert("X-ClickHouse-Exception-Code", "241".parse().unwrap());
+        assert!(matches!(
+            logic.should_retry_response(&memory_limit),
+            RetryAction::Retry(_)
+        ));
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::{
     buffers::Acker,
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    event::Event,
+    event::{Event, EventStatus},
     internal_events::{ConsoleEventProcessed, ConsoleFieldNotFound},
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 
 use tokio::io::{self, AsyncWriteExt};
 
-#[derive(Debug, Derivative, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize)]
 #[derivative(Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Target {
@@ -27,7 +27,7 @@ pub enum Target {
     Stderr,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct ConsoleSinkConfig {
     #[serde(default)]
@@ -40,6 +40,7 @@ pub struct ConsoleSinkConfig {
 pub enum Encoding {
     Text,
     Json,
+    Csv,
 }
 
 inventory::submit! {
@@ -63,6 +64,9 @@ impl SinkConfig for ConsoleSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if let Encoding::Csv = self.encoding.codec() {
+            self.encoding.csv_options()?;
+        }
         let encoding = self.encoding.clone();
 
         let output: Box<dyn io::AsyncWrite + Send + Sync + Unpin> = match self.target {
@@ -93,6 +97,12 @@ impl SinkConfig for ConsoleSinkConfig {
 
 fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option<String> {
     encoding.apply_rules(&mut event);
+    if let Encoding::Text = encoding.codec() {
+        if let Some(line) = encoding.render_template(&event) {
+            return line;
+        }
+    }
+
     match event {
         Event::Log(log) => match encoding.codec() {
             Encoding::Json => serde_json::to_string(&log)
@@ -112,6 +122,7 @@ fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option
                     }
                 }
             }
+            Encoding::Csv => encoding.csv().as_ref().map(|csv| csv.encode(&log)),
         },
         Event::Metric(metric) => match encoding.codec() {
             Encoding::Json => serde_json::to_string(&metric)
@@ -119,7 +130,7 @@ fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option
                     error!(message = "Error encoding json.", %error);
                 })
                 .ok(),
-            Encoding::Text => Some(format!("{}", metric)),
+            Encoding::Text | Encoding::Csv => Some(format!("{}", metric)),
         },
     }
 }
@@ -133,14 +144,24 @@ struct WriterSink {
 #[async_trait]
 impl StreamSink for WriterSink {
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        if let (Encoding::Csv, Some(csv)) = (self.encoding.codec(), self.encoding.csv()) {
+            if csv.header() {
+                let header = format!("{}\n", csv.encode_header());
+                if let Err(error) = self.output.write_all(header.as_bytes()).await {
+                    error!(message = "Error writing to output. Stopping sink.", %error);
+                    return Err(());
+                }
+            }
+        }
+
         while let Some(event) = input.next().await {
-            self.acker.ack(1);
             if let Some(mut buf) = encode_event(event, &self.encoding) {
                 buf.push('\n');
                 if let Err(error) = self.output.write_all(buf.as_bytes()).await {
                     // Error when writing to stdout/stderr is likely irrecoverable,
                     // so stop the sink.
                     error!(message = "Error writing to output. Stopping sink.", %error);
+                    self.acker.ack_with_status(1, EventStatus::Errored);
                     return Err(());
                 }
 
@@ -148,6 +169,7 @@ impl StreamSink for WriterSink {
                     byte_size: buf.len(),
                 });
             }
+            self.acker.ack(1);
         }
         Ok(())
     }
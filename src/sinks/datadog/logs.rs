@@ -154,6 +154,8 @@ impl DatadogLogsConfig {
                     encoder.finish()?,
                 )
             }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(_) => unreachable!("zstd compression is rejected by `build`"),
         };
 
         request
@@ -167,6 +169,11 @@ impl DatadogLogsConfig {
 #[typetag::serde(name = "datadog_logs")]
 impl SinkConfig for DatadogLogsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        #[cfg(feature = "zstd")]
+        if let Some(Compression::Zstd(_)) = self.compression {
+            return Err("zstd compression isn't supported by the Datadog logs API".into());
+        }
+
         // Create a different sink depending on which encoding we have chosen.
         // Json and Text have different batching strategies and so each needs to be
         // handled differently.
@@ -1,6 +1,6 @@
 use crate::{
     config::{DataType, SinkConfig, SinkContext, SinkDescription},
-    event::metric::{Metric, MetricKind, MetricValue, Sample, StatisticKind},
+    event::metric::{Bucket, Metric, MetricKind, MetricValue, Sample, StatisticKind},
     http::HttpClient,
     sinks::{
         util::{
@@ -49,11 +49,26 @@ pub struct DatadogConfig {
     pub region: Option<super::Region>,
     pub api_key: String,
     #[serde(default)]
+    pub series_api_version: SeriesApiVersion,
+    /// The tag whose value is sent as the source of each series, with the v2 series API.
+    pub source_tag: Option<String>,
+    /// The tag whose value, in seconds, is sent as the interval of each metric.
+    pub interval_tag: Option<String>,
+    #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
     pub request: TowerRequestConfig,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum SeriesApiVersion {
+    #[derivative(Default)]
+    V1,
+    V2,
+}
+
 struct DatadogSink {
     config: DatadogConfig,
     /// Endpoint -> (uri_path, last_sent_timestamp)
@@ -100,6 +115,50 @@ struct DatadogMetric {
     interval: Option<i64>,
     points: Vec<DatadogPoint<f64>>,
     tags: Option<Vec<String>>,
+    /// Only sent with the v2 series API.
+    #[serde(skip)]
+    source_type_name: Option<String>,
+}
+
+// https://docs.datadoghq.com/api/latest/metrics/#submit-metrics
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct DatadogSeriesV2 {
+    metric: String,
+    r#type: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interval: Option<i64>,
+    points: Vec<DatadogPointV2>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_type_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct DatadogPointV2 {
+    timestamp: i64,
+    value: f64,
+}
+
+impl From<DatadogMetric> for DatadogSeriesV2 {
+    fn from(metric: DatadogMetric) -> Self {
+        Self {
+            metric: metric.metric,
+            r#type: match metric.r#type {
+                DatadogMetricType::Count => 1,
+                DatadogMetricType::Rate => 2,
+                DatadogMetricType::Gauge => 3,
+            },
+            interval: metric.interval,
+            points: metric
+                .points
+                .into_iter()
+                .map(|DatadogPoint(timestamp, value)| DatadogPointV2 { timestamp, value })
+                .collect(),
+            tags: metric.tags,
+            source_type_name: metric.source_type_name,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -131,9 +190,13 @@ enum DatadogEndpoint {
 }
 
 impl DatadogEndpoint {
-    fn build_uri(host: &str) -> crate::Result<Vec<(Self, Uri)>> {
+    fn build_uri(host: &str, version: SeriesApiVersion) -> crate::Result<Vec<(Self, Uri)>> {
+        let series = match version {
+            SeriesApiVersion::V1 => "/api/v1/series",
+            SeriesApiVersion::V2 => "/api/v2/series",
+        };
         Ok(vec![
-            (DatadogEndpoint::Series, build_uri(host, "/api/v1/series")?),
+            (DatadogEndpoint::Series, build_uri(host, series)?),
             (
                 DatadogEndpoint::Distribution,
                 build_uri(host, "/api/v1/distribution_points")?,
@@ -146,7 +209,8 @@ impl DatadogEndpoint {
             MetricValue::Distribution {
                 statistic: StatisticKind::Summary,
                 ..
-            } => Self::Distribution,
+            }
+            | MetricValue::AggregatedHistogram { .. } => Self::Distribution,
             _ => Self::Series,
         }
     }
@@ -171,7 +235,7 @@ impl SinkConfig for DatadogConfig {
             .parse_config(self.batch)?;
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
 
-        let uri = DatadogEndpoint::build_uri(&self.get_endpoint())?;
+        let uri = DatadogEndpoint::build_uri(&self.get_endpoint(), self.series_api_version)?;
         let timestamp = Utc::now().timestamp();
 
         let sink = DatadogSink {
@@ -228,16 +292,40 @@ impl DatadogSink {
 
         let body = match endpoint {
             DatadogEndpoint::Series => {
-                let input =
+                let mut input =
                     encode_events(events, self.config.default_namespace.as_deref(), interval);
-                serde_json::to_vec(&input).unwrap()
+                for series in &mut input.series {
+                    if let Some(interval) = self.take_interval(&mut series.tags) {
+                        series.interval = Some(interval);
+                    }
+                    if let Some(source_tag) = &self.config.source_tag {
+                        series.source_type_name = take_tag(&mut series.tags, source_tag);
+                    }
+                }
+
+                match self.config.series_api_version {
+                    SeriesApiVersion::V1 => serde_json::to_vec(&input).unwrap(),
+                    SeriesApiVersion::V2 => serde_json::to_vec(&DatadogRequest {
+                        series: input
+                            .series
+                            .into_iter()
+                            .map(DatadogSeriesV2::from)
+                            .collect(),
+                    })
+                    .unwrap(),
+                }
             }
             DatadogEndpoint::Distribution => {
-                let input = encode_distribution_events(
+                let mut input = encode_distribution_events(
                     events,
                     self.config.default_namespace.as_deref(),
                     interval,
                 );
+                for series in &mut input.series {
+                    if let Some(interval) = self.take_interval(&mut series.tags) {
+                        series.interval = Some(interval);
+                    }
+                }
                 serde_json::to_vec(&input).unwrap()
             }
         };
@@ -250,6 +338,22 @@ impl DatadogSink {
     }
 }
 
+impl DatadogSink {
+    /// Takes the interval of a metric out of its tags, if it has the configured interval tag.
+    fn take_interval(&self, tags: &mut Option<Vec<String>>) -> Option<i64> {
+        let interval_tag = self.config.interval_tag.as_deref()?;
+        take_tag(tags, interval_tag)?.parse().ok()
+    }
+}
+
+/// Removes the first tag with the name from the encoded tags, returning its value.
+fn take_tag(tags: &mut Option<Vec<String>>, name: &str) -> Option<String> {
+    let prefix = format!("{}:", name);
+    let tags = tags.as_mut()?;
+    let index = tags.iter().position(|tag| tag.starts_with(&prefix))?;
+    Some(tags.remove(index).split_off(prefix.len()))
+}
+
 fn build_uri(host: &str, endpoint: &'static str) -> crate::Result<Uri> {
     let uri = format!("{}{}", host, endpoint)
         .parse::<Uri>()
@@ -346,7 +450,9 @@ struct DatadogMetricNormalize;
 impl MetricNormalize for DatadogMetricNormalize {
     fn apply_state(state: &mut MetricSet, metric: Metric) -> Option<Metric> {
         match &metric.data.value {
-            MetricValue::Gauge { .. } => state.make_absolute(metric),
+            MetricValue::Gauge { .. } | MetricValue::AggregatedSummary { .. } => {
+                state.make_absolute(metric)
+            }
             _ => state.make_incremental(metric),
         }
     }
@@ -373,6 +479,7 @@ fn encode_events(
                     interval: Some(interval),
                     points: vec![DatadogPoint(ts, value)],
                     tags,
+                    source_type_name: None,
                 }]),
                 MetricValue::Distribution {
                     samples,
@@ -387,6 +494,7 @@ fn encode_events(
                                 interval: Some(interval),
                                 points: vec![DatadogPoint(ts, s.min)],
                                 tags: tags.clone(),
+                                source_type_name: None,
                             },
                             DatadogMetric {
                                 metric: format!("{}.avg", &fullname),
@@ -394,6 +502,7 @@ fn encode_events(
                                 interval: Some(interval),
                                 points: vec![DatadogPoint(ts, s.avg)],
                                 tags: tags.clone(),
+                                source_type_name: None,
                             },
                             DatadogMetric {
                                 metric: format!("{}.count", &fullname),
@@ -401,6 +510,7 @@ fn encode_events(
                                 interval: Some(interval),
                                 points: vec![DatadogPoint(ts, s.count)],
                                 tags: tags.clone(),
+                                source_type_name: None,
                             },
                             DatadogMetric {
                                 metric: format!("{}.median", &fullname),
@@ -408,6 +518,7 @@ fn encode_events(
                                 interval: Some(interval),
                                 points: vec![DatadogPoint(ts, s.median)],
                                 tags: tags.clone(),
+                                source_type_name: None,
                             },
                             DatadogMetric {
                                 metric: format!("{}.max", &fullname),
@@ -415,6 +526,7 @@ fn encode_events(
                                 interval: Some(interval),
                                 points: vec![DatadogPoint(ts, s.max)],
                                 tags: tags.clone(),
+                                source_type_name: None,
                             },
                         ];
                         for (q, v) in s.quantiles {
@@ -424,6 +536,7 @@ fn encode_events(
                                 interval: Some(interval),
                                 points: vec![DatadogPoint(ts, v)],
                                 tags: tags.clone(),
+                                source_type_name: None,
                             })
                         }
                         Some(result)
@@ -437,6 +550,7 @@ fn encode_events(
                     interval: None,
                     points: vec![DatadogPoint(ts, values.len() as f64)],
                     tags,
+                    source_type_name: None,
                 }]),
                 MetricValue::Gauge { value } => Some(vec![DatadogMetric {
                     metric: fullname,
@@ -444,7 +558,41 @@ fn encode_events(
                     interval: None,
                     points: vec![DatadogPoint(ts, value)],
                     tags,
+                    source_type_name: None,
                 }]),
+                // DatadogMetricNormalize keeps these absolute, so the counts and sums are totals.
+                MetricValue::AggregatedSummary {
+                    quantiles,
+                    count,
+                    sum,
+                } => {
+                    let gauge = |metric, value, tags| DatadogMetric {
+                        metric,
+                        r#type: DatadogMetricType::Gauge,
+                        interval: None,
+                        points: vec![DatadogPoint(ts, value)],
+                        tags,
+                        source_type_name: None,
+                    };
+                    let mut result = vec![
+                        gauge(
+                            format!("{}.count", &fullname),
+                            f64::from(count),
+                            tags.clone(),
+                        ),
+                        gauge(format!("{}.sum", &fullname), sum, tags.clone()),
+                    ];
+                    for quantile in quantiles {
+                        let mut tags = tags.clone().unwrap_or_default();
+                        tags.push(format!("quantile:{}", quantile.upper_limit));
+                        result.push(gauge(
+                            format!("{}.quantile", &fullname),
+                            quantile.value,
+                            Some(tags),
+                        ));
+                    }
+                    Some(result)
+                }
                 _ => None,
             }
         })
@@ -490,6 +638,20 @@ fn encode_distribution_events(
                             })
                         }
                     }
+                    MetricValue::AggregatedHistogram { buckets, .. } => {
+                        let samples = bucket_samples(&buckets);
+
+                        if samples.is_empty() {
+                            None
+                        } else {
+                            Some(DatadogDistributionMetric {
+                                metric: fullname,
+                                interval: Some(interval),
+                                points: vec![DatadogPoint(ts, samples)],
+                                tags,
+                            })
+                        }
+                    }
                     _ => None,
                 },
                 _ => None,
@@ -500,6 +662,41 @@ fn encode_distribution_events(
     DatadogRequest { series }
 }
 
+/// The number of values a histogram is approximated with, at most. Histograms counting more
+/// observations than that are scaled down.
+const MAX_HISTOGRAM_SAMPLES: u32 = 1000;
+
+/// Approximates the observations counted into histogram buckets, spreading the observations
+/// of each bucket evenly over its range. Those of the unbounded last bucket are taken to be
+/// at its lower bound. Every non-empty bucket keeps at least one value when scaled down.
+fn bucket_samples(buckets: &[Bucket]) -> Vec<f64> {
+    let total: u64 = buckets.iter().map(|bucket| u64::from(bucket.count)).sum();
+    let scale = if total > u64::from(MAX_HISTOGRAM_SAMPLES) {
+        f64::from(MAX_HISTOGRAM_SAMPLES) / total as f64
+    } else {
+        1.0
+    };
+
+    let mut samples = Vec::with_capacity(total.min(u64::from(MAX_HISTOGRAM_SAMPLES)) as usize);
+    let mut lower = 0.0;
+    for bucket in buckets {
+        let upper = if bucket.upper_limit.is_finite() {
+            bucket.upper_limit
+        } else {
+            lower
+        };
+        let count = match bucket.count {
+            0 => 0,
+            count => ((f64::from(count) * scale).round() as u32).max(1),
+        };
+        samples.extend(
+            (0..count).map(|i| lower + (upper - lower) * (f64::from(i) + 0.5) / f64::from(count)),
+        );
+        lower = upper;
+    }
+    samples
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -538,7 +735,8 @@ mod tests {
         .unwrap();
 
         let timestamp = Utc::now().timestamp();
-        let uri = DatadogEndpoint::build_uri(&sink.get_endpoint()).unwrap();
+        let uri =
+            DatadogEndpoint::build_uri(&sink.get_endpoint(), sink.series_api_version).unwrap();
         let sink = DatadogSink {
             config: sink,
             endpoint_data: uri
@@ -582,6 +780,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encode_series_v2_with_source_and_interval_tags() {
+        let (config, _cx) = load_sink::<DatadogConfig>(
+            r#"
+            api_key = "test"
+            series_api_version = "v2"
+            source_tag = "source"
+            interval_tag = "interval"
+        "#,
+        )
+        .unwrap();
+
+        let uri =
+            DatadogEndpoint::build_uri(&config.get_endpoint(), config.series_api_version).unwrap();
+        let sink = DatadogSink {
+            config,
+            endpoint_data: uri
+                .into_iter()
+                .map(|(endpoint, uri)| (endpoint, (uri, AtomicI64::new(Utc::now().timestamp()))))
+                .collect(),
+        };
+
+        let mut tags = tags();
+        tags.insert("source".into(), "nginx".into());
+        tags.insert("interval".into(), "10".into());
+        let events = vec![Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        )
+        .with_tags(Some(tags))
+        .with_timestamp(Some(ts()))];
+        let req = sink
+            .build_request(PartitionInnerBuffer::new(events, DatadogEndpoint::Series))
+            .unwrap();
+
+        assert_eq!(
+            req.uri(),
+            &Uri::from_static("https://api.datadoghq.com/api/v2/series")
+        );
+        assert_eq!(
+            std::str::from_utf8(req.body()).unwrap(),
+            r#"{"series":[{"metric":"requests","type":1,"interval":10,"points":[{"timestamp":1542182950,"value":2.0}],"tags":["empty_tag:","normal_tag:value","true_tag:true"],"source_type_name":"nginx"}]}"#
+        );
+    }
+
     #[test]
     fn test_encode_tags() {
         assert_eq!(
@@ -784,4 +1028,62 @@ mod tests {
             r#"{"series":[{"metric":"requests","interval":60,"points":[[1542182950,[1.0,1.0,1.0,2.0,2.0,2.0,3.0,3.0]]],"tags":null}]}"#
         );
     }
+
+    #[test]
+    fn encode_aggregated_summary() {
+        let events = vec![Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedSummary {
+                quantiles: crate::quantiles![0.5 => 1.5, 0.99 => 4.0],
+                count: 10,
+                sum: 20.0,
+            },
+        )
+        .with_timestamp(Some(ts()))];
+        let input = encode_events(events, None, 60);
+        let json = serde_json::to_string(&input).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"series":[{"metric":"latency.count","type":"gauge","interval":null,"points":[[1542182950,10.0]],"tags":null},{"metric":"latency.sum","type":"gauge","interval":null,"points":[[1542182950,20.0]],"tags":null},{"metric":"latency.quantile","type":"gauge","interval":null,"points":[[1542182950,1.5]],"tags":["quantile:0.5"]},{"metric":"latency.quantile","type":"gauge","interval":null,"points":[[1542182950,4.0]],"tags":["quantile:0.99"]}]}"#
+        );
+    }
+
+    #[test]
+    fn encode_aggregated_histogram_distribution() {
+        let events = vec![Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::AggregatedHistogram {
+                buckets: crate::buckets![1.0 => 2, 2.0 => 1, f64::INFINITY => 1],
+                count: 4,
+                sum: 5.0,
+            },
+        )
+        .with_timestamp(Some(ts()))];
+        assert_eq!(
+            DatadogEndpoint::from_metric(&events[0].clone().into()),
+            DatadogEndpoint::Distribution
+        );
+
+        let input = encode_distribution_events(events, None, 60);
+        let json = serde_json::to_string(&input).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"series":[{"metric":"latency","interval":60,"points":[[1542182950,[0.25,0.75,1.5,2.0]]],"tags":null}]}"#
+        );
+    }
+
+    #[test]
+    fn bucket_samples_are_capped() {
+        let samples = bucket_samples(&crate::buckets![
+            1.0 => 1_000_000, 2.0 => 3_000_000, 3.0 => 1, f64::INFINITY => 0
+        ]);
+
+        assert_eq!(samples.len(), 1001);
+        assert_eq!(samples.iter().filter(|&&value| value < 1.0).count(), 250);
+        assert_eq!(samples.iter().filter(|&&value| value > 2.0).count(), 1);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    config::{DataType, SinkConfig, SinkContext, SinkDescription},
+    config::{log_schema, DataType, SinkConfig, SinkContext, SinkDescription},
     emit,
     event::Event,
     http::{Auth, HttpClient, HttpError, MaybeAuth},
@@ -63,8 +63,55 @@ pub struct ElasticSearchConfig {
 
     pub aws: Option<RegionOrEndpoint>,
     pub tls: Option<TlsOptions>,
+    /// The bulk action of each event, `index`, `create` or `update`. Templated.
+    pub bulk_action: Option<String>,
     #[serde(default)]
-    pub bulk_action: BulkAction,
+    pub mode: ElasticSearchMode,
+    pub data_stream: Option<DataStreamConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum ElasticSearchMode {
+    #[derivative(Default)]
+    Normal,
+    /// Events are created in the data stream named by `data_stream`, instead of `index`.
+    DataStream,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
+#[serde(deny_unknown_fields)]
+#[derivative(Default)]
+pub struct DataStreamConfig {
+    #[serde(rename = "type", default = "DataStreamConfig::default_type")]
+    #[derivative(Default(value = "DataStreamConfig::default_type()"))]
+    pub dtype: String,
+    #[serde(default = "DataStreamConfig::default_dataset")]
+    #[derivative(Default(value = "DataStreamConfig::default_dataset()"))]
+    pub dataset: String,
+    #[serde(default = "DataStreamConfig::default_namespace")]
+    #[derivative(Default(value = "DataStreamConfig::default_namespace()"))]
+    pub namespace: String,
+    /// Sets the `data_stream.type`, `data_stream.dataset` and `data_stream.namespace` fields of
+    /// events to the names of their data stream.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub sync_fields: bool,
+}
+
+impl DataStreamConfig {
+    fn default_type() -> String {
+        "logs".into()
+    }
+
+    fn default_dataset() -> String {
+        "generic".into()
+    }
+
+    fn default_namespace() -> String {
+        "default".into()
+    }
 }
 
 lazy_static! {
@@ -88,13 +135,11 @@ pub enum ElasticSearchAuth {
     Aws(AWSAuthentication),
 }
 
-#[derive(Derivative, Deserialize, Serialize, Clone, Debug)]
-#[serde(deny_unknown_fields, rename_all = "snake_case")]
-#[derivative(Default)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum BulkAction {
-    #[derivative(Default)]
     Index,
     Create,
+    Update,
 }
 
 impl BulkAction {
@@ -102,6 +147,7 @@ impl BulkAction {
         match *self {
             BulkAction::Index => "index",
             BulkAction::Create => "create",
+            BulkAction::Update => "update",
         }
     }
 
@@ -109,10 +155,33 @@ impl BulkAction {
         match *self {
             BulkAction::Index => "/index",
             BulkAction::Create => "/create",
+            BulkAction::Update => "/update",
         }
     }
 }
 
+impl TryFrom<&str> for BulkAction {
+    type Error = String;
+
+    fn try_from(action: &str) -> Result<Self, Self::Error> {
+        match action {
+            "index" => Ok(BulkAction::Index),
+            "create" => Ok(BulkAction::Create),
+            "update" => Ok(BulkAction::Update),
+            _ => Err(format!("Invalid bulk action: {:?}", action)),
+        }
+    }
+}
+
+/// The templates naming the data stream of each event, `<type>-<dataset>-<namespace>`.
+#[derive(Debug)]
+struct DataStreamTemplates {
+    dtype: Template,
+    dataset: Template,
+    namespace: Template,
+    sync_fields: bool,
+}
+
 inventory::submit! {
     SinkDescription::new::<ElasticSearchConfig>("elasticsearch")
 }
@@ -175,7 +244,8 @@ pub struct ElasticSearchCommon {
     compression: Compression,
     region: Region,
     query_params: HashMap<String, String>,
-    bulk_action: BulkAction,
+    bulk_action: Template,
+    data_stream: Option<DataStreamTemplates>,
 }
 
 #[derive(Debug, Snafu)]
@@ -188,6 +258,17 @@ enum ParseError {
     AWSCredentialsGenerateFailed { source: CredentialsError },
     #[snafu(display("Index template parse error: {}", source))]
     IndexTemplate { source: TemplateParseError },
+    #[snafu(display("Bulk action template parse error: {}", source))]
+    BulkActionTemplate { source: TemplateParseError },
+    #[snafu(display("Invalid bulk action: {:?}", action))]
+    InvalidBulkAction { action: String },
+    #[snafu(display("The \"update\" bulk action requires `id_key` to be set"))]
+    UpdateWithoutIdKey,
+    #[snafu(display("Data stream {} template parse error: {}", part, source))]
+    DataStreamTemplate {
+        part: &'static str,
+        source: TemplateParseError,
+    },
 }
 
 #[async_trait::async_trait]
@@ -196,38 +277,49 @@ impl HttpSink for ElasticSearchCommon {
     type Output = Vec<u8>;
 
     fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
-        let index = self
-            .index
-            .render_string(&event)
-            .map_err(|error| {
-                emit!(TemplateRenderingFailed {
-                    error,
-                    field: Some("index"),
-                    drop_event: true,
-                });
-            })
-            .ok()?;
+        let (index, bulk_action) = match &self.data_stream {
+            Some(data_stream) => (
+                data_stream.index(&mut event)?,
+                // Data streams only accept `create`.
+                BulkAction::Create,
+            ),
+            None => (
+                render_template(&self.index, &event, "index")?,
+                self.bulk_action(&event)?,
+            ),
+        };
 
         let mut action = json!({
-            self.bulk_action.as_str(): {
+            bulk_action.as_str(): {
                 "_index": index,
                 "_type": self.doc_type,
             }
         });
-        maybe_set_id(
-            self.config.id_key.as_ref(),
-            action
-                .pointer_mut(self.bulk_action.as_json_pointer())
-                .unwrap(),
-            &mut event,
-        );
+        let doc = action.pointer_mut(bulk_action.as_json_pointer()).unwrap();
+        maybe_set_id(self.config.id_key.as_ref(), doc, &mut event);
+        if bulk_action == BulkAction::Update && doc.get("_id").is_none() {
+            error!(
+                message = "Dropping event without an id for the update bulk action.",
+                id_key = ?self.config.id_key,
+                internal_log_rate_secs = 30
+            );
+            return None;
+        }
 
         let mut body = serde_json::to_vec(&action).unwrap();
         body.push(b'\n');
 
         self.config.encoding.apply_rules(&mut event);
 
-        serde_json::to_writer(&mut body, &event.into_log()).unwrap();
+        if bulk_action == BulkAction::Update {
+            let doc = json!({
+                "doc": event.into_log(),
+                "doc_as_upsert": true,
+            });
+            serde_json::to_writer(&mut body, &doc).unwrap();
+        } else {
+            serde_json::to_writer(&mut body, &event.into_log()).unwrap();
+        }
         body.push(b'\n');
 
         emit!(ElasticSearchEventEncoded {
@@ -288,6 +380,43 @@ impl HttpSink for ElasticSearchCommon {
     }
 }
 
+fn render_template(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingFailed {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+impl DataStreamTemplates {
+    fn index(&self, event: &mut Event) -> Option<String> {
+        let dtype = render_template(&self.dtype, event, "data_stream.type")?;
+        let dataset = render_template(&self.dataset, event, "data_stream.dataset")?;
+        let namespace = render_template(&self.namespace, event, "data_stream.namespace")?;
+        let index = format!("{}-{}-{}", dtype, dataset, namespace);
+
+        let log = event.as_mut_log();
+        if self.sync_fields {
+            log.insert("data_stream.type", dtype);
+            log.insert("data_stream.dataset", dataset);
+            log.insert("data_stream.namespace", namespace);
+        }
+        // Data streams require every document to have an `@timestamp`.
+        if !log.contains("@timestamp") {
+            if let Some(timestamp) = log.remove(log_schema().timestamp_key()) {
+                log.insert("@timestamp", timestamp);
+            }
+        }
+
+        Some(index)
+    }
+}
+
 #[derive(Clone)]
 struct ElasticSearchRetryLogic;
 
@@ -301,6 +430,8 @@ enum ESResultItem {
     Index(ESIndexResult),
     #[serde(rename = "create")]
     Create(ESIndexResult),
+    #[serde(rename = "update")]
+    Update(ESIndexResult),
 }
 #[derive(Deserialize, Debug)]
 struct ESIndexResult {
@@ -318,6 +449,7 @@ impl ESResultItem {
         match self {
             ESResultItem::Index(r) => r,
             ESResultItem::Create(r) => r,
+            ESResultItem::Update(r) => r,
         }
     }
 }
@@ -414,7 +546,38 @@ impl ElasticSearchCommon {
         let index = Template::try_from(index).context(IndexTemplate)?;
 
         let doc_type = config.doc_type.clone().unwrap_or_else(|| "_doc".into());
-        let bulk_action = config.bulk_action.clone();
+        let bulk_action = config.bulk_action.as_deref().unwrap_or("index");
+        let bulk_action = Template::try_from(bulk_action).context(BulkActionTemplate)?;
+        // A bulk action that isn't templated can be checked right away.
+        if !bulk_action.is_dynamic() && BulkAction::try_from(bulk_action.get_ref()).is_err() {
+            return Err(ParseError::InvalidBulkAction {
+                action: bulk_action.get_ref().to_owned(),
+            }
+            .into());
+        }
+        // Elasticsearch rejects updates of documents that aren't given an id.
+        if matches!(config.mode, ElasticSearchMode::Normal)
+            && config.id_key.is_none()
+            && bulk_action.get_ref() == "update"
+        {
+            return Err(ParseError::UpdateWithoutIdKey.into());
+        }
+
+        let data_stream = match config.mode {
+            ElasticSearchMode::Normal => None,
+            ElasticSearchMode::DataStream => {
+                let data_stream = config.data_stream.clone().unwrap_or_default();
+                let template = |part, value: String| {
+                    Template::try_from(value).context(DataStreamTemplate { part })
+                };
+                Some(DataStreamTemplates {
+                    dtype: template("type", data_stream.dtype)?,
+                    dataset: template("dataset", data_stream.dataset)?,
+                    namespace: template("namespace", data_stream.namespace)?,
+                    sync_fields: data_stream.sync_fields,
+                })
+            }
+        };
 
         let request = config.request.tower.unwrap_with(&REQUEST_DEFAULTS);
 
@@ -450,9 +613,23 @@ impl ElasticSearchCommon {
             region,
             query_params,
             bulk_action,
+            data_stream,
         })
     }
 
+    fn bulk_action(&self, event: &Event) -> Option<BulkAction> {
+        let action = render_template(&self.bulk_action, event, "bulk_action")?;
+        BulkAction::try_from(action.as_str())
+            .map_err(|error| {
+                error!(
+                    message = "Dropping event with an invalid bulk action.",
+                    %error,
+                    internal_log_rate_secs = 30
+                );
+            })
+            .ok()
+    }
+
     fn signed_request(&self, method: &str, uri: &Uri, use_params: bool) -> SignedRequest {
         let mut request = SignedRequest::new(method, "es", &self.region, uri.path());
         request.set_hostname(uri.host().map(|host| host.into()));
@@ -600,7 +777,7 @@ mod tests {
         use chrono::{TimeZone, Utc};
 
         let config = ElasticSearchConfig {
-            bulk_action: BulkAction::Create,
+            bulk_action: Some("create".into()),
             index: Some(String::from("vector")),
             endpoint: String::from("https://example.com"),
             ..Default::default()
@@ -619,6 +796,85 @@ mod tests {
         assert_eq!(std::str::from_utf8(&encoded).unwrap(), &expected[..]);
     }
 
+    #[test]
+    fn renders_bulk_action_template() {
+        let config = ElasticSearchConfig {
+            bulk_action: Some("{{ action }}".into()),
+            id_key: Some("id".into()),
+            index: Some(String::from("vector")),
+            endpoint: String::from("https://example.com"),
+            ..Default::default()
+        };
+        let es = ElasticSearchCommon::parse_config(&config).unwrap();
+
+        let mut event = Event::new_empty_log();
+        event.as_mut_log().insert("action", "update");
+        event.as_mut_log().insert("id", "42");
+        let encoded = es.encode_event(event).unwrap();
+        let expected = r#"{"update":{"_index":"vector","_type":"_doc","_id":"42"}}
+{"doc":{"action":"update"},"doc_as_upsert":true}
+"#;
+        assert_eq!(std::str::from_utf8(&encoded).unwrap(), &expected[..]);
+
+        // Updates of events without an id would be rejected by Elasticsearch.
+        let mut event = Event::new_empty_log();
+        event.as_mut_log().insert("action", "update");
+        assert!(es.encode_event(event).is_none());
+
+        let mut event = Event::new_empty_log();
+        event.as_mut_log().insert("action", "delete");
+        assert!(es.encode_event(event).is_none());
+    }
+
+    #[test]
+    fn rejects_update_without_id_key() {
+        let config = ElasticSearchConfig {
+            bulk_action: Some("update".into()),
+            endpoint: String::from("https://example.com"),
+            ..Default::default()
+        };
+        assert!(ElasticSearchCommon::parse_config(&config).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_bulk_action() {
+        let config = ElasticSearchConfig {
+            bulk_action: Some("delete".into()),
+            endpoint: String::from("https://example.com"),
+            ..Default::default()
+        };
+        assert!(ElasticSearchCommon::parse_config(&config).is_err());
+    }
+
+    #[test]
+    fn creates_events_in_data_streams() {
+        use crate::config::log_schema;
+        use chrono::{TimeZone, Utc};
+
+        let config = ElasticSearchConfig {
+            endpoint: String::from("https://example.com"),
+            mode: ElasticSearchMode::DataStream,
+            data_stream: Some(DataStreamConfig {
+                dataset: "{{ service }}".into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let es = ElasticSearchCommon::parse_config(&config).unwrap();
+
+        let mut event = Event::from("hello there");
+        event.as_mut_log().insert("service", "nginx");
+        event.as_mut_log().insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2020, 12, 1).and_hms(1, 2, 3),
+        );
+        let encoded = es.encode_event(event).unwrap();
+        let expected = r#"{"create":{"_index":"logs-nginx-default","_type":"_doc"}}
+{"@timestamp":"2020-12-01T01:02:03Z","data_stream":{"dataset":"nginx","namespace":"default","type":"logs"},"message":"hello there","service":"nginx"}
+"#;
+        assert_eq!(std::str::from_utf8(&encoded).unwrap(), &expected[..]);
+    }
+
     #[test]
     fn handles_error_response() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
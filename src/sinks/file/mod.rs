@@ -2,16 +2,16 @@ use crate::expiring_hash_map::ExpiringHashMap;
 use crate::{
     buffers::Acker,
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    event::Event,
+    event::{Event, EventStatus},
     internal_events::FileOpen,
     internal_events::TemplateRenderingFailed,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{AvroEncoder, EncodingConfig, EncodingConfiguration},
         StreamSink,
     },
     template::Template,
 };
-use async_compression::tokio_02::write::GzipEncoder;
+use async_compression::tokio_02::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{
@@ -30,7 +30,7 @@ mod bytes_path;
 use bytes_path::BytesPath;
 use std::convert::TryFrom;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct FileSinkConfig {
     pub path: Template,
@@ -64,12 +64,15 @@ impl GenerateConfig for FileSinkConfig {
 pub enum Encoding {
     Text,
     Ndjson,
+    Csv,
+    Avro,
 }
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum Compression {
     Gzip,
+    Zstd,
     None,
 }
 
@@ -82,6 +85,7 @@ impl Default for Compression {
 enum OutFile {
     Regular(File),
     Gzip(GzipEncoder<File>),
+    Zstd(ZstdEncoder<File>),
 }
 
 impl OutFile {
@@ -89,6 +93,7 @@ impl OutFile {
         match compression {
             Compression::None => OutFile::Regular(file),
             Compression::Gzip => OutFile::Gzip(GzipEncoder::new(file)),
+            Compression::Zstd => OutFile::Zstd(ZstdEncoder::new(file)),
         }
     }
 
@@ -96,6 +101,7 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.sync_all().await,
             OutFile::Gzip(gzip) => gzip.get_mut().sync_all().await,
+            OutFile::Zstd(zstd) => zstd.get_mut().sync_all().await,
         }
     }
 
@@ -103,6 +109,7 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.shutdown().await,
             OutFile::Gzip(gzip) => gzip.shutdown().await,
+            OutFile::Zstd(zstd) => zstd.shutdown().await,
         }
     }
 
@@ -110,6 +117,7 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.write_all(src).await,
             OutFile::Gzip(gzip) => gzip.write_all(src).await,
+            OutFile::Zstd(zstd) => zstd.write_all(src).await,
         }
     }
 
@@ -128,6 +136,16 @@ impl SinkConfig for FileSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        match self.encoding.codec() {
+            Encoding::Csv => {
+                self.encoding.csv_options()?;
+            }
+            Encoding::Avro => {
+                self.encoding.avro_encoder()?;
+            }
+            _ => (),
+        }
+
         let sink = FileSink::new(&self, cx.acker());
         Ok((
             super::VectorSink::Stream(Box::new(sink)),
@@ -149,6 +167,7 @@ pub struct FileSink {
     acker: Acker,
     path: Template,
     encoding: EncodingConfig<Encoding>,
+    avro: Option<AvroEncoder>,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
@@ -160,6 +179,11 @@ impl FileSink {
             acker,
             path: config.path.clone(),
             encoding: config.encoding.clone(),
+            // Validated in `build`.
+            avro: match config.encoding.codec() {
+                Encoding::Avro => config.encoding.avro_encoder().ok(),
+                _ => None,
+            },
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
@@ -196,8 +220,8 @@ impl FileSink {
                 event = input.next() => {
                     match event {
                         Some(event) => {
-                            self.process_event(event).await;
-                            self.acker.ack(1);
+                            let status = self.process_event(event).await;
+                            self.acker.ack_with_status(1, status);
                         },
                         None => {
                             // If we got `None` - terminate the processing.
@@ -249,7 +273,8 @@ impl FileSink {
         Ok(())
     }
 
-    async fn process_event(&mut self, event: Event) {
+    /// Writes the event to its file, returning whether it was delivered.
+    async fn process_event(&mut self, event: Event) -> EventStatus {
         let path = match self.partition_event(&event) {
             Some(path) => path,
             None => {
@@ -257,7 +282,7 @@ impl FileSink {
                 // file.
                 // This is already logged at `partition_event`, so
                 // here we just skip the event.
-                return;
+                return EventStatus::Failed;
             }
         };
 
@@ -276,11 +301,24 @@ impl FileSink {
                     // Maybe other events will work though! Just log
                     // the error and skip this event.
                     error!(message = "Unable to open the file.", path = ?path, %error);
-                    return;
+                    return EventStatus::Errored;
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
+            let is_empty = file
+                .metadata()
+                .await
+                .map(|metadata| metadata.len() == 0)
+                .unwrap_or(false);
+            let mut outfile = OutFile::new(file, self.compression);
+
+            if is_empty {
+                if let Err(error) =
+                    write_header(&mut outfile, &self.encoding, self.avro.as_ref()).await
+                {
+                    error!(message = "Failed to write header.", path = ?path, %error);
+                }
+            }
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
             emit!(FileOpen {
@@ -290,8 +328,12 @@ impl FileSink {
         };
 
         trace!(message = "Writing an event to file.", path = ?path);
-        if let Err(error) = write_event_to_file(file, event, &self.encoding).await {
-            error!(message = "Failed to write file.", path = ?path, %error);
+        match write_event_to_file(file, event, &self.encoding, self.avro.as_ref()).await {
+            Ok(()) => EventStatus::Delivered,
+            Err(error) => {
+                error!(message = "Failed to write file.", path = ?path, %error);
+                EventStatus::Errored
+            }
         }
     }
 }
@@ -312,15 +354,49 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
-pub fn encode_event(encoding: &EncodingConfig<Encoding>, mut event: Event) -> Vec<u8> {
+pub fn encode_event(
+    encoding: &EncodingConfig<Encoding>,
+    avro: Option<&AvroEncoder>,
+    mut event: Event,
+) -> Option<Vec<u8>> {
     encoding.apply_rules(&mut event);
-    let log = event.into_log();
     match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log).expect("Unable to encode event as JSON."),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy().into_bytes())
-            .unwrap_or_default(),
+        Encoding::Ndjson => {
+            Some(serde_json::to_vec(event.as_log()).expect("Unable to encode event as JSON."))
+        }
+        Encoding::Text => match encoding.render_template(&event) {
+            Some(line) => line.map(String::into_bytes),
+            None => Some(
+                event
+                    .as_log()
+                    .get(log_schema().message_key())
+                    .map(|v| v.to_string_lossy().into_bytes())
+                    .unwrap_or_default(),
+            ),
+        },
+        Encoding::Csv => encoding
+            .csv()
+            .as_ref()
+            .map(|csv| csv.encode(event.as_log()).into_bytes()),
+        Encoding::Avro => avro?.encode_block(event.as_log()),
+    }
+}
+
+/// Starts a new file with the CSV header, if one is configured, or with the
+/// header of an Avro container file.
+async fn write_header(
+    file: &mut OutFile,
+    encoding: &EncodingConfig<Encoding>,
+    avro: Option<&AvroEncoder>,
+) -> Result<(), std::io::Error> {
+    match (encoding.codec(), encoding.csv(), avro) {
+        (Encoding::Csv, Some(csv), _) if csv.header() => {
+            let mut buf = csv.encode_header().into_bytes();
+            buf.push(b'\n');
+            file.write_all(&buf[..]).await
+        }
+        (Encoding::Avro, _, Some(avro)) => file.write_all(&avro.header()).await,
+        _ => Ok(()),
     }
 }
 
@@ -328,10 +404,16 @@ async fn write_event_to_file(
     file: &mut OutFile,
     event: Event,
     encoding: &EncodingConfig<Encoding>,
+    avro: Option<&AvroEncoder>,
 ) -> Result<(), std::io::Error> {
-    let mut buf = encode_event(encoding, event);
-    buf.push(b'\n');
-    file.write_all(&buf[..]).await
+    if let Some(mut buf) = encode_event(encoding, avro, event) {
+        // Avro blocks are delimited by the container file itself.
+        if *encoding.codec() != Encoding::Avro {
+            buf.push(b'\n');
+        }
+        file.write_all(&buf[..]).await?;
+    }
+    Ok(())
 }
 
 #[async_trait]
@@ -382,6 +464,89 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn csv_with_header() {
+        trace_init();
+
+        let template = temp_file();
+
+        let encoding: EncodingConfig<Encoding> = toml::from_str(
+            r#"
+            codec = "csv"
+            csv.fields = ["status", "message"]
+            csv.header = true
+            "#,
+        )
+        .unwrap();
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding,
+            compression: Compression::None,
+        };
+
+        let mut sink = FileSink::new(&config, Acker::Null);
+        let events = vec!["ok", "not, ok"].into_iter().map(|message| {
+            let mut event = Event::from(message);
+            event.as_mut_log().insert("status", 200);
+            event
+        });
+        sink.run(Box::pin(stream::iter(events))).await.unwrap();
+
+        assert_eq!(
+            lines_from_file(template),
+            vec!["status,message", "200,ok", r#"200,"not, ok""#]
+        );
+    }
+
+    #[tokio::test]
+    async fn avro_container_file() {
+        trace_init();
+
+        let template = temp_file();
+
+        let encoding: EncodingConfig<Encoding> = toml::from_str(
+            r#"
+            codec = "avro"
+            schema = '{"type": "record", "name": "log", "fields": [{"name": "status", "type": "long"}]}'
+            "#,
+        )
+        .unwrap();
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding,
+            compression: Compression::None,
+        };
+
+        // The event without a status doesn't match the schema, and is dropped.
+        let mut sink = FileSink::new(&config, Acker::Null);
+        let events = vec![Some(200), None, Some(404)].into_iter().map(|status| {
+            let mut event = Event::from("message");
+            if let Some(status) = status {
+                event.as_mut_log().insert("status", status);
+            }
+            event
+        });
+        sink.run(Box::pin(stream::iter(events))).await.unwrap();
+
+        let file = std::fs::read(template).unwrap();
+        let statuses = avro_rs::Reader::new(&file[..])
+            .unwrap()
+            .map(|record| match record.unwrap() {
+                avro_rs::types::Value::Record(fields) => fields[0].1.clone(),
+                value => panic!("Expected a record, got {:?}", value),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                avro_rs::types::Value::Long(200),
+                avro_rs::types::Value::Long(404)
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn single_partition_gzip() {
         trace_init();
@@ -407,6 +572,30 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "zstd")]
+    #[tokio::test]
+    async fn single_partition_zstd() {
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: Encoding::Text.into(),
+            compression: Compression::Zstd,
+        };
+
+        let mut sink = FileSink::new(&config, Acker::Null);
+        let (input, _) = random_lines_with_stream(100, 64);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+
+        let output = crate::test_util::lines_from_zstd_file(template);
+        assert_eq!(input, output);
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         trace_init();
@@ -51,7 +51,7 @@ enum GcsError {
     BucketNotFound { bucket: String },
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct GcsSinkConfig {
     bucket: String,
@@ -4,7 +4,7 @@ use crate::{
     http::{Auth, HttpClient, MaybeAuth},
     internal_events::{HTTPEventEncoded, HTTPEventMissingMessage},
     sinks::util::{
-        buffer::compression::GZIP_DEFAULT,
+        buffer::compression::{GZIP_DEFAULT, ZSTD_DEFAULT},
         encoding::{EncodingConfig, EncodingConfiguration},
         http::{BatchedHttpSink, HttpSink, RequestConfig},
         BatchConfig, BatchSettings, Buffer, Compression, Concurrency, TowerRequestConfig, UriSerde,
@@ -95,6 +95,7 @@ pub enum Encoding {
     Text,
     Ndjson,
     Json,
+    Csv,
 }
 
 inventory::submit! {
@@ -136,6 +137,9 @@ impl SinkConfig for HttpSinkConfig {
 
         config.request.add_old_option(config.headers.take());
         validate_headers(&config.request.headers, &config.auth)?;
+        if let Encoding::Csv = config.encoding.codec() {
+            config.encoding.csv_options()?;
+        }
 
         let batch = BatchSettings::default()
             .bytes(bytesize::mib(10u64))
@@ -174,11 +178,19 @@ impl HttpSink for HttpSinkConfig {
 
     fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
         self.encoding.apply_rules(&mut event);
+        let template_line = match self.encoding.codec() {
+            Encoding::Text => self.encoding.render_template(&event),
+            _ => None,
+        };
         let event = event.into_log();
 
         let body = match &self.encoding.codec() {
             Encoding::Text => {
-                if let Some(v) = event.get(crate::config::log_schema().message_key()) {
+                if let Some(line) = template_line {
+                    let mut b = line?.into_bytes();
+                    b.push(b'\n');
+                    b
+                } else if let Some(v) = event.get(crate::config::log_schema().message_key()) {
                     let mut b = v.to_string_lossy().into_bytes();
                     b.push(b'\n');
                     b
@@ -203,6 +215,12 @@ impl HttpSink for HttpSinkConfig {
                 b.push(b',');
                 b
             }
+
+            Encoding::Csv => {
+                let mut b = self.encoding.csv().as_ref()?.encode(&event).into_bytes();
+                b.push(b'\n');
+                b
+            }
         };
 
         emit!(HTTPEventEncoded {
@@ -228,6 +246,15 @@ impl HttpSink for HttpSinkConfig {
                 body.push(b']');
                 "application/json"
             }
+            Encoding::Csv => {
+                if let Some(csv) = self.encoding.csv().as_ref().filter(|csv| csv.header()) {
+                    let mut header = csv.encode_header().into_bytes();
+                    header.push(b'\n');
+                    header.append(&mut body);
+                    body = header;
+                }
+                "text/csv"
+            }
         };
 
         let mut builder = Request::builder()
@@ -244,6 +271,12 @@ impl HttpSink for HttpSinkConfig {
                 w.write_all(&body).expect("Writing to Vec can't fail");
                 body = w.finish().expect("Writing to Vec can't fail");
             }
+            Compression::Zstd(level) => {
+                builder = builder.header("Content-Encoding", "zstd");
+
+                let level = level.unwrap_or(ZSTD_DEFAULT) as i32;
+                body = zstd::encode_all(&body[..], level).expect("Writing to Vec can't fail");
+            }
             Compression::None => {}
         }
 
@@ -330,6 +363,26 @@ mod tests {
         assert_eq!(bytes, Vec::from(&"hello world\n"[..]));
     }
 
+    #[test]
+    fn http_encode_event_csv() {
+        let encoding: EncodingConfig<Encoding> = toml::from_str(
+            r#"
+            codec = "csv"
+            csv.fields = ["host", "message"]
+            csv.delimiter = ";"
+            "#,
+        )
+        .unwrap();
+        let mut event = Event::from("hello; world");
+        event.as_mut_log().insert("host", "example.com");
+
+        let mut config = default_config(Encoding::Csv);
+        config.encoding = encoding;
+        let bytes = config.encode_event(event).unwrap();
+
+        assert_eq!(bytes, Vec::from(&"example.com;\"hello; world\"\n"[..]));
+    }
+
     #[test]
     fn http_encode_event_json() {
         let encoding = EncodingConfig::from(Encoding::Ndjson);
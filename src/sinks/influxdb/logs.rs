@@ -4,8 +4,8 @@ use crate::{
     http::HttpClient,
     sinks::{
         influxdb::{
-            encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
-            InfluxDB1Settings, InfluxDB2Settings, ProtocolVersion,
+            healthcheck, influx_line_protocol, influxdb_settings, Field, InfluxDB1Settings,
+            InfluxDB2Settings, Precision, ProtocolVersion,
         },
         util::{
             encode_namespace,
@@ -31,6 +31,8 @@ pub struct InfluxDBLogsConfig {
     pub endpoint: String,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub precision: Precision,
     #[serde(flatten)]
     pub influxdb1_settings: Option<InfluxDB1Settings>,
     #[serde(flatten)]
@@ -54,6 +56,7 @@ struct InfluxDBLogsSink {
     protocol_version: ProtocolVersion,
     namespace: String,
     tags: HashSet<String>,
+    precision: Precision,
     encoding: EncodingConfig<Encoding>,
 }
 
@@ -115,7 +118,7 @@ impl SinkConfig for InfluxDBLogsConfig {
         .unwrap();
 
         let endpoint = self.endpoint.clone();
-        let uri = settings.write_uri(endpoint).unwrap();
+        let uri = settings.write_uri(endpoint, self.precision).unwrap();
 
         let token = settings.token();
         let protocol_version = settings.protocol_version();
@@ -127,6 +130,7 @@ impl SinkConfig for InfluxDBLogsConfig {
             protocol_version,
             namespace,
             tags,
+            precision: self.precision,
             encoding: self.encoding.clone().into(),
         };
 
@@ -170,10 +174,12 @@ impl HttpSink for InfluxDBLogsSink {
         );
 
         // Timestamp
-        let timestamp = encode_timestamp(match event.remove(log_schema().timestamp_key()) {
-            Some(Value::Timestamp(ts)) => Some(ts),
-            _ => None,
-        });
+        let timestamp =
+            self.precision
+                .encode_timestamp(match event.remove(log_schema().timestamp_key()) {
+                    Some(Value::Timestamp(ts)) => Some(ts),
+                    _ => None,
+                });
 
         // Tags + Fields
         let mut tags: BTreeMap<String, String> = BTreeMap::new();
@@ -643,6 +649,7 @@ mod tests {
             protocol_version,
             namespace,
             tags,
+            precision: Precision::Ns,
             encoding: EncodingConfigWithDefault::default().into(),
         }
     }
@@ -675,6 +682,7 @@ mod integration_tests {
             namespace: ns.clone(),
             endpoint: "http://localhost:9999".to_string(),
             tags: Default::default(),
+            precision: Default::default(),
             influxdb1_settings: None,
             influxdb2_settings: Some(InfluxDB2Settings {
                 org: ORG.to_string(),
@@ -8,7 +8,7 @@ use crate::{
     sinks::{
         influxdb::{
             encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
-            InfluxDB1Settings, InfluxDB2Settings, ProtocolVersion,
+            InfluxDB1Settings, InfluxDB2Settings, Precision, ProtocolVersion,
         },
         util::{
             buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer},
@@ -129,7 +129,7 @@ impl InfluxDBSvc {
             .parse_config(config.batch)?;
         let request = config.request.unwrap_with(&REQUEST_DEFAULTS);
 
-        let uri = settings.write_uri(endpoint)?;
+        let uri = settings.write_uri(endpoint, Precision::Ns)?;
 
         let http_service = HttpBatchService::new(client, create_build_request(uri, token));
 
@@ -30,6 +30,39 @@ pub(in crate::sinks) enum ProtocolVersion {
     V2,
 }
 
+/// The precision of the timestamps that are written.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Precision {
+    S,
+    Ms,
+    Us,
+    #[derivative(Default)]
+    Ns,
+}
+
+impl Precision {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Precision::S => "s",
+            Precision::Ms => "ms",
+            Precision::Us => "us",
+            Precision::Ns => "ns",
+        }
+    }
+
+    pub(in crate::sinks) fn encode_timestamp(self, timestamp: Option<DateTime<Utc>>) -> i64 {
+        let timestamp = timestamp.unwrap_or_else(Utc::now);
+        match self {
+            Precision::S => timestamp.timestamp(),
+            Precision::Ms => timestamp.timestamp_millis(),
+            Precision::Us => timestamp.timestamp_nanos() / 1000,
+            Precision::Ns => timestamp.timestamp_nanos(),
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 enum ConfigError {
     #[snafu(display("InfluxDB v1 or v2 should be configured as endpoint."))]
@@ -62,14 +95,14 @@ pub struct InfluxDB2Settings {
 }
 
 trait InfluxDBSettings: std::fmt::Debug {
-    fn write_uri(&self, endpoint: String) -> crate::Result<Uri>;
+    fn write_uri(&self, endpoint: String, precision: Precision) -> crate::Result<Uri>;
     fn healthcheck_uri(&self, endpoint: String) -> crate::Result<Uri>;
     fn token(&self) -> String;
     fn protocol_version(&self) -> ProtocolVersion;
 }
 
 impl InfluxDBSettings for InfluxDB1Settings {
-    fn write_uri(&self, endpoint: String) -> crate::Result<Uri> {
+    fn write_uri(&self, endpoint: String, precision: Precision) -> crate::Result<Uri> {
         encode_uri(
             &endpoint,
             "write",
@@ -79,7 +112,7 @@ impl InfluxDBSettings for InfluxDB1Settings {
                 ("rp", self.retention_policy_name.clone()),
                 ("p", self.password.clone()),
                 ("u", self.username.clone()),
-                ("precision", Some("ns".to_owned())),
+                ("precision", Some(precision.as_str().to_owned())),
             ],
         )
     }
@@ -98,14 +131,14 @@ impl InfluxDBSettings for InfluxDB1Settings {
 }
 
 impl InfluxDBSettings for InfluxDB2Settings {
-    fn write_uri(&self, endpoint: String) -> crate::Result<Uri> {
+    fn write_uri(&self, endpoint: String, precision: Precision) -> crate::Result<Uri> {
         encode_uri(
             &endpoint,
             "api/v2/write",
             &[
                 ("org", Some(self.org.clone())),
                 ("bucket", Some(self.bucket.clone())),
-                ("precision", Some("ns".to_owned())),
+                ("precision", Some(precision.as_str().to_owned())),
             ],
         )
     }
@@ -272,11 +305,7 @@ fn encode_string(key: String, output: &mut String) {
 }
 
 pub(in crate::sinks) fn encode_timestamp(timestamp: Option<DateTime<Utc>>) -> i64 {
-    if let Some(ts) = timestamp {
-        ts.timestamp_nanos()
-    } else {
-        encode_timestamp(Some(Utc::now()))
-    }
+    Precision::Ns.encode_timestamp(timestamp)
 }
 
 pub(in crate::sinks) fn encode_uri(
@@ -533,7 +562,7 @@ mod tests {
         };
 
         let uri = settings
-            .write_uri("http://localhost:8086".to_owned())
+            .write_uri("http://localhost:8086".to_owned(), Precision::Ns)
             .unwrap();
         assert_eq!("http://localhost:8086/write?consistency=quorum&db=vector_db&rp=autogen&p=secret&u=writer&precision=ns", uri.to_string())
     }
@@ -547,10 +576,10 @@ mod tests {
         };
 
         let uri = settings
-            .write_uri("http://localhost:9999".to_owned())
+            .write_uri("http://localhost:9999".to_owned(), Precision::Ms)
             .unwrap();
         assert_eq!(
-            "http://localhost:9999/api/v2/write?org=my-org&bucket=my-bucket&precision=ns",
+            "http://localhost:9999/api/v2/write?org=my-org&bucket=my-bucket&precision=ms",
             uri.to_string()
         )
     }
@@ -732,6 +761,17 @@ mod tests {
         assert!(encode_timestamp(None) >= start)
     }
 
+    #[test]
+    fn test_encode_timestamp_precision() {
+        assert_eq!(Precision::S.encode_timestamp(Some(ts())), 1542182950);
+        assert_eq!(Precision::Ms.encode_timestamp(Some(ts())), 1542182950000);
+        assert_eq!(Precision::Us.encode_timestamp(Some(ts())), 1542182950000000);
+        assert_eq!(
+            Precision::Ns.encode_timestamp(Some(ts())),
+            1542182950000000011
+        );
+    }
+
     #[test]
     fn test_encode_uri_valid() {
         let uri = encode_uri(
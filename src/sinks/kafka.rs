@@ -1,11 +1,12 @@
 use crate::{
     buffers::Acker,
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::{EventStatus, LogEvent, Value},
     internal_events::TemplateRenderingFailed,
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::to_string,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{AvroEncoder, EncodingConfig, EncodingConfiguration, ProtobufEncoder},
         BatchConfig,
     },
     template::{Template, TemplateParseError},
@@ -15,16 +16,18 @@ use futures::{
     channel::oneshot::Canceled, future::BoxFuture, ready, stream::FuturesUnordered, FutureExt,
     Sink, Stream, TryFutureExt,
 };
+use indexmap::IndexMap;
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
     error::{KafkaError, RDKafkaError},
+    message::OwnedHeaders,
     producer::{DeliveryFuture, FutureProducer, FutureRecord},
     ClientConfig,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     convert::TryFrom,
     pin::Pin,
     sync::Arc,
@@ -41,6 +44,15 @@ enum BuildError {
     KafkaCreateFailed { source: KafkaError },
     #[snafu(display("invalid topic template: {}", source))]
     TopicTemplate { source: TemplateParseError },
+    #[snafu(display("invalid key template: {}", source))]
+    KeyTemplate { source: TemplateParseError },
+    #[snafu(display("invalid template for header {:?}: {}", name, source))]
+    HeaderTemplate {
+        name: String,
+        source: TemplateParseError,
+    },
+    #[snafu(display("only one of `key` and `key_field` can be set"))]
+    KeyConflict,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -48,12 +60,19 @@ pub struct KafkaSinkConfig {
     bootstrap_servers: String,
     topic: String,
     key_field: Option<String>,
+    /// A template rendering the message key, instead of taking it from `key_field`.
+    key: Option<String>,
+    /// Record headers, with templates rendering their values.
+    #[serde(default)]
+    headers: IndexMap<String, String>,
     encoding: EncodingConfig<Encoding>,
     /// These batching options will **not** override librdkafka_options values.
     #[serde(default)]
     batch: BatchConfig,
     #[serde(default)]
     compression: KafkaCompression,
+    /// The codec specific compression level, set as librdkafka's `compression.level`.
+    compression_level: Option<i32>,
     #[serde(flatten)]
     auth: KafkaAuthConfig,
     #[serde(default = "default_socket_timeout_ms")]
@@ -77,13 +96,19 @@ fn default_message_timeout_ms() -> u64 {
 pub enum Encoding {
     Text,
     Json,
+    Avro,
+    Protobuf,
 }
 
 pub struct KafkaSink {
     producer: Arc<FutureProducer>,
     topic: Template,
     key_field: Option<String>,
+    key: Option<Template>,
+    headers: Vec<(String, Template)>,
     encoding: EncodingConfig<Encoding>,
+    avro: Option<AvroEncoder>,
+    protobuf: Option<ProtobufEncoder>,
     delivery_fut: FuturesUnordered<BoxFuture<'static, (usize, Result<DeliveryFuture, KafkaError>)>>,
     in_flight: FuturesUnordered<
         BoxFuture<'static, (usize, Result<Result<(i32, i64), KafkaError>, Canceled>)>,
@@ -92,7 +117,7 @@ pub struct KafkaSink {
     acker: Acker,
     seq_head: usize,
     seq_tail: usize,
-    pending_acks: HashSet<usize>,
+    pending_acks: HashMap<usize, EventStatus>,
 }
 
 inventory::submit! {
@@ -149,6 +174,10 @@ impl KafkaSinkConfig {
             .set("socket.timeout.ms", &self.socket_timeout_ms.to_string())
             .set("message.timeout.ms", &self.message_timeout_ms.to_string());
 
+        if let Some(level) = self.compression_level {
+            client_config.set("compression.level", &level.to_string());
+        }
+
         self.auth.apply(&mut client_config)?;
 
         // All batch options are producer only.
@@ -225,19 +254,47 @@ impl KafkaSinkConfig {
 
 impl KafkaSink {
     fn new(config: KafkaSinkConfig, acker: Acker) -> crate::Result<Self> {
+        if config.key.is_some() && config.key_field.is_some() {
+            return Err(BuildError::KeyConflict.into());
+        }
+        let key = config
+            .key
+            .map(|key| Template::try_from(key).context(KeyTemplate))
+            .transpose()?;
+        let headers = config
+            .headers
+            .into_iter()
+            .map(|(name, value)| {
+                let template = Template::try_from(value).context(HeaderTemplate { name: &name })?;
+                Ok((name, template))
+            })
+            .collect::<Result<Vec<_>, BuildError>>()?;
+
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = producer_config.create().context(KafkaCreateFailed)?;
+        let avro = match config.encoding.codec() {
+            Encoding::Avro => Some(config.encoding.avro_encoder()?),
+            _ => None,
+        };
+        let protobuf = match config.encoding.codec() {
+            Encoding::Protobuf => Some(config.encoding.protobuf_encoder()?),
+            _ => None,
+        };
         Ok(KafkaSink {
             producer: Arc::new(producer),
             topic: Template::try_from(config.topic).context(TopicTemplate)?,
             key_field: config.key_field,
+            key,
+            headers,
             encoding: config.encoding,
+            avro,
+            protobuf,
             delivery_fut: FuturesUnordered::new(),
             in_flight: FuturesUnordered::new(),
             acker,
             seq_head: 0,
             seq_tail: 0,
-            pending_acks: HashSet::new(),
+            pending_acks: HashMap::new(),
         })
     }
 
@@ -260,6 +317,55 @@ impl KafkaSink {
 
         Poll::Ready(())
     }
+
+    /// Renders the key template, if there is one. Events whose key can't be rendered are sent
+    /// with a blank key, as they are when `key_field` is missing.
+    fn render_key(&self, event: &Event) -> Option<Vec<u8>> {
+        let key = self.key.as_ref()?.render_string(event).map_err(|error| {
+            emit!(TemplateRenderingFailed {
+                error,
+                field: Some("key"),
+                drop_event: false,
+            });
+        });
+        Some(key.map(String::into_bytes).unwrap_or_default())
+    }
+
+    /// Renders the record headers, leaving out those that can't be rendered.
+    fn render_headers(&self, event: &Event) -> Option<OwnedHeaders> {
+        if self.headers.is_empty() {
+            return None;
+        }
+
+        let headers =
+            self.headers.iter().fold(
+                OwnedHeaders::new(),
+                |headers, (name, template)| match template.render_string(event) {
+                    Ok(value) => headers.add(name, value.as_str()),
+                    Err(error) => {
+                        emit!(TemplateRenderingFailed {
+                            error,
+                            field: Some(name.as_str()),
+                            drop_event: false,
+                        });
+                        headers
+                    }
+                },
+            );
+        Some(headers)
+    }
+}
+
+impl KafkaSink {
+    /// Acknowledges the sent events that follow all the previously acknowledged ones.
+    fn ack_sent(&mut self, seqno: usize, status: EventStatus) {
+        self.pending_acks.insert(seqno, status);
+
+        while let Some(status) = self.pending_acks.remove(&self.seq_tail) {
+            self.acker.ack_with_status(1, status);
+            self.seq_tail += 1
+        }
+    }
 }
 
 impl Sink<Event> for KafkaSink {
@@ -293,17 +399,39 @@ impl Sink<Event> for KafkaSink {
             Event::Metric(metric) => metric.data.timestamp.as_ref(),
         }
         .map(|ts| ts.timestamp_millis());
-        let (key, body) = encode_event(item, &self.key_field, &self.encoding);
+        // Rendered before the encoding rules remove any fields.
+        let rendered_key = self.render_key(&item);
+        let headers = self.render_headers(&item);
 
         let seqno = self.seq_head;
         self.seq_head += 1;
 
+        let encoded = encode_event(
+            item,
+            &self.key_field,
+            &self.encoding,
+            self.avro.as_ref(),
+            self.protobuf.as_ref(),
+        );
+        let (key, body) = match encoded {
+            Some(encoded) => encoded,
+            None => {
+                // The event is dropped, but still acknowledged in order.
+                self.ack_sent(seqno, EventStatus::Failed);
+                return Ok(());
+            }
+        };
+        let key = rendered_key.unwrap_or(key);
+
         let producer = Arc::clone(&self.producer);
         self.delivery_fut.push(Box::pin(async move {
             let mut record = FutureRecord::to(&topic).key(&key).payload(&body[..]);
             if let Some(timestamp) = timestamp_ms {
                 record = record.timestamp(timestamp);
             }
+            if let Some(headers) = headers {
+                record = record.headers(headers);
+            }
 
             let result = loop {
                 debug!(message = "Sending event.", count = 1);
@@ -336,21 +464,18 @@ impl Sink<Event> for KafkaSink {
         while !this.in_flight.is_empty() {
             match ready!(Pin::new(&mut this.in_flight).poll_next(cx)) {
                 Some((seqno, Ok(result))) => {
-                    match result {
+                    let status = match result {
                         Ok((partition, offset)) => {
-                            trace!(message = "Produced message.", ?partition, ?offset)
+                            trace!(message = "Produced message.", ?partition, ?offset);
+                            EventStatus::Delivered
+                        }
+                        Err(error) => {
+                            error!(message = "Kafka error.", %error);
+                            EventStatus::Errored
                         }
-                        Err(error) => error!(message = "Kafka error.", %error),
                     };
 
-                    this.pending_acks.insert(seqno);
-
-                    let mut num_to_ack = 0;
-                    while this.pending_acks.remove(&this.seq_tail) {
-                        num_to_ack += 1;
-                        this.seq_tail += 1
-                    }
-                    this.acker.ack(num_to_ack);
+                    this.ack_sent(seqno, status);
                 }
                 Some((_, Err(Canceled))) => {
                     error!(message = "Request canceled.");
@@ -402,7 +527,9 @@ fn encode_event(
     mut event: Event,
     key_field: &Option<String>,
     encoding: &EncodingConfig<Encoding>,
-) -> (Vec<u8>, Vec<u8>) {
+    avro: Option<&AvroEncoder>,
+    protobuf: Option<&ProtobufEncoder>,
+) -> Option<(Vec<u8>, Vec<u8>)> {
     let key = key_field
         .as_ref()
         .and_then(|f| match &event {
@@ -423,20 +550,33 @@ fn encode_event(
                 .get(log_schema().message_key())
                 .map(|v| v.as_bytes().to_vec())
                 .unwrap_or_default(),
+            Encoding::Avro => avro?.encode_datum(&log)?,
+            Encoding::Protobuf => protobuf?.encode(&log)?,
         },
         Event::Metric(metric) => match encoding.codec() {
             Encoding::Json => serde_json::to_vec(&metric).unwrap(),
             Encoding::Text => metric.to_string().into_bytes(),
+            Encoding::Avro => avro?.encode_datum(&metric)?,
+            // Metrics are mapped to the message type as in their JSON representation.
+            Encoding::Protobuf => match Value::from(serde_json::to_value(&metric).unwrap()) {
+                Value::Map(fields) => protobuf?.encode(&LogEvent::from(fields))?,
+                _ => unreachable!("Metrics serialize to JSON objects."),
+            },
         },
     };
 
-    (key, body)
+    Some((key, body))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::event::{Metric, MetricKind, MetricValue};
+    use prost::Message;
+    use prost_types::{
+        field_descriptor_proto::Type, DescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+        FileDescriptorSet,
+    };
     use std::collections::BTreeMap;
 
     #[test]
@@ -444,6 +584,49 @@ mod tests {
         crate::test_util::test_generate_config::<KafkaSinkConfig>();
     }
 
+    #[test]
+    fn kafka_renders_key_and_headers() {
+        use rdkafka::message::Headers;
+
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9091"
+            topic = "topic-1234"
+            key = "{{ user }}-{{ region }}"
+            headers.source = "vector"
+            headers.user = "{{ user }}"
+            headers.missing = "{{ missing }}"
+            encoding.codec = "json""#,
+        )
+        .unwrap();
+        let sink = KafkaSink::new(config, Acker::Null).unwrap();
+
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("user", "alice");
+        event.as_mut_log().insert("region", "eu");
+
+        assert_eq!(sink.render_key(&event), Some(b"alice-eu".to_vec()));
+
+        let headers = sink.render_headers(&event).unwrap();
+        assert_eq!(headers.count(), 2);
+        assert_eq!(headers.get(0), Some(("source", &b"vector"[..])));
+        assert_eq!(headers.get(1), Some(("user", &b"alice"[..])));
+    }
+
+    #[test]
+    fn kafka_rejects_key_with_key_field() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9091"
+            topic = "topic-1234"
+            key = "{{ user }}"
+            key_field = "user"
+            encoding.codec = "json""#,
+        )
+        .unwrap();
+        assert!(KafkaSink::new(config, Acker::Null).is_err());
+    }
+
     #[test]
     fn kafka_encode_event_log_text() {
         crate::test_util::trace_init();
@@ -453,7 +636,10 @@ mod tests {
             message.clone().into(),
             &None,
             &EncodingConfig::from(Encoding::Text),
-        );
+            None,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(&key_bytes[..], key.as_bytes());
         assert_eq!(&bytes[..], message.as_bytes());
@@ -471,7 +657,10 @@ mod tests {
             event,
             &Some("key".into()),
             &EncodingConfig::from(Encoding::Json),
-        );
+            None,
+            None,
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -492,7 +681,10 @@ mod tests {
             metric.clone().into(),
             &None,
             &EncodingConfig::from(Encoding::Text),
-        );
+            None,
+            None,
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(metric.to_string(), String::from_utf8_lossy(&bytes));
@@ -509,7 +701,10 @@ mod tests {
             metric.clone().into(),
             &None,
             &EncodingConfig::from(Encoding::Json),
-        );
+            None,
+            None,
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(
@@ -518,6 +713,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn kafka_encode_event_log_avro() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("status", 200);
+
+        let encoding: EncodingConfig<Encoding> = toml::from_str(
+            r#"
+            codec = "avro"
+            schema = '{"type": "record", "name": "log", "fields": [{"name": "status", "type": "long"}]}'
+            "#,
+        )
+        .unwrap();
+        let avro = encoding.avro_encoder().unwrap();
+
+        let (_, bytes) = encode_event(event, &None, &encoding, Some(&avro), None).unwrap();
+        // The only field is the zigzag encoded status.
+        assert_eq!(bytes, vec![0x90, 0x03]);
+
+        let event = Event::from("no status");
+        assert!(encode_event(event, &None, &encoding, Some(&avro), None).is_none());
+    }
+
+    #[test]
+    fn kafka_encode_event_log_protobuf() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("status", 200);
+
+        let descriptor_set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                package: Some("test".into()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Log".into()),
+                    field: vec![FieldDescriptorProto {
+                        name: Some("status".into()),
+                        number: Some(1),
+                        r#type: Some(Type::Int64 as i32),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let path = crate::test_util::temp_file();
+        let mut bytes = Vec::new();
+        descriptor_set.encode(&mut bytes).unwrap();
+        std::fs::write(&path, bytes).unwrap();
+
+        let encoding: EncodingConfig<Encoding> = toml::from_str(&format!(
+            r#"
+            codec = "protobuf"
+            protobuf.descriptor_set_path = {:?}
+            protobuf.message_type = "test.Log"
+            "#,
+            path
+        ))
+        .unwrap();
+        let protobuf = encoding.protobuf_encoder().unwrap();
+
+        let (_, bytes) = encode_event(event, &None, &encoding, None, Some(&protobuf)).unwrap();
+        // The only field is the varint encoded status, with tag 1.
+        assert_eq!(bytes, vec![0x08, 0xc8, 0x01]);
+
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("status", "OK");
+        assert!(encode_event(event, &None, &encoding, None, Some(&protobuf)).is_none());
+    }
+
     #[test]
     fn kafka_encode_event_log_apply_rules() {
         crate::test_util::trace_init();
@@ -533,8 +796,14 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
+                csv: None,
+                protobuf: None,
+                template: None,
             },
-        );
+            None,
+            None,
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -569,9 +838,12 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".into(),
             topic: topic.clone(),
             key_field: None,
+            key: None,
+            headers: IndexMap::new(),
             encoding: EncodingConfig::from(Encoding::Text),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
+            compression_level: None,
             auth: KafkaAuthConfig::default(),
             socket_timeout_ms: 60000,
             message_timeout_ms: 300000,
@@ -620,8 +892,11 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".to_string(),
             topic: format!("{}-%Y%m%d", topic),
             compression: KafkaCompression::None,
+            compression_level: None,
             encoding: Encoding::Text.into(),
             key_field: None,
+            key: None,
+            headers: IndexMap::new(),
             auth: KafkaAuthConfig {
                 sasl: None,
                 tls: None,
@@ -774,9 +1049,12 @@ mod integration_test {
             bootstrap_servers: server.to_string(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
+            key: None,
+            headers: IndexMap::new(),
             encoding: EncodingConfig::from(Encoding::Text),
             batch: BatchConfig::default(),
             compression,
+            compression_level: None,
             auth: kafka_auth.clone(),
             socket_timeout_ms: 60000,
             message_timeout_ms: 300000,
@@ -1,7 +1,8 @@
 //! Loki sink
 //!
 //! This sink provides downstream support for `Loki` via
-//! the v1 http json endpoint.
+//! the v1 http push endpoint, with either json or snappy
+//! compressed protobuf payloads.
 //!
 //! https://github.com/grafana/loki/blob/master/docs/api.md
 //!
@@ -28,8 +29,13 @@ use crate::{
     tls::{TlsOptions, TlsSettings},
 };
 use futures::{FutureExt, SinkExt};
+use prost::Message;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+mod logproto {
+    include!(concat!(env!("OUT_DIR"), "/logproto.rs"));
+}
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -46,6 +52,8 @@ pub struct LokiConfig {
     remove_timestamp: bool,
     #[serde(default)]
     out_of_order_action: OutOfOrderAction,
+    #[serde(default)]
+    compression: Compression,
 
     auth: Option<Auth>,
 
@@ -67,6 +75,17 @@ pub enum OutOfOrderAction {
     RewriteTimestamp,
 }
 
+/// The payload of push requests is json when uncompressed, and protobuf when compressed with
+/// snappy.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    #[derivative(Default)]
+    None,
+    Snappy,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 enum Encoding {
@@ -158,6 +177,7 @@ struct LokiSink {
 
     remove_label_fields: bool,
     remove_timestamp: bool,
+    compression: Compression,
 
     auth: Option<Auth>,
 }
@@ -171,6 +191,7 @@ impl LokiSink {
             labels: config.labels,
             remove_label_fields: config.remove_label_fields,
             remove_timestamp: config.remove_timestamp,
+            compression: config.compression,
             auth: config.auth,
         }
     }
@@ -256,11 +277,21 @@ impl HttpSink for LokiSink {
         let (json, key) = output.into_parts();
         let tenant_id = key.tenant_id;
 
-        let body = serde_json::to_vec(&json).unwrap();
+        let (body, content_type) = match self.compression {
+            Compression::None => (serde_json::to_vec(&json).unwrap(), "application/json"),
+            Compression::Snappy => {
+                let mut body = Vec::new();
+                push_request(json)?.encode(&mut body)?;
+                let body = snap::raw::Encoder::new()
+                    .compress_vec(&body)
+                    .expect("Out of memory");
+                (body, "application/x-protobuf")
+            }
+        };
 
         let uri = format!("{}loki/api/v1/push", self.endpoint.uri);
 
-        let mut req = http::Request::post(uri).header("Content-Type", "application/json");
+        let mut req = http::Request::post(uri).header("Content-Type", content_type);
 
         if let Some(tenant_id) = tenant_id {
             req = req.header("X-Scope-OrgID", tenant_id);
@@ -276,6 +307,65 @@ impl HttpSink for LokiSink {
     }
 }
 
+#[derive(Deserialize)]
+struct JsonPushRequest {
+    streams: Vec<JsonStream>,
+}
+
+#[derive(Deserialize)]
+struct JsonStream {
+    stream: BTreeMap<String, String>,
+    values: Vec<(String, String)>,
+}
+
+/// Converts the json payload built by `LokiBuffer` to its protobuf form.
+fn push_request(json: serde_json::Value) -> crate::Result<logproto::PushRequest> {
+    let request: JsonPushRequest = serde_json::from_value(json)?;
+
+    let streams = request
+        .streams
+        .into_iter()
+        .map(|stream| {
+            let entries = stream
+                .values
+                .into_iter()
+                .map(|(timestamp, line)| {
+                    let timestamp = timestamp.parse::<i64>()?;
+                    Ok(logproto::EntryAdapter {
+                        timestamp: Some(prost_types::Timestamp {
+                            seconds: timestamp.div_euclid(1_000_000_000),
+                            nanos: timestamp.rem_euclid(1_000_000_000) as i32,
+                        }),
+                        line,
+                    })
+                })
+                .collect::<crate::Result<_>>()?;
+
+            Ok(logproto::StreamAdapter {
+                labels: format_labels(&stream.stream),
+                entries,
+            })
+        })
+        .collect::<crate::Result<_>>()?;
+
+    Ok(logproto::PushRequest { streams })
+}
+
+/// Formats labels the way Loki expects them in protobuf payloads, `{name="value", ...}`.
+fn format_labels(labels: &BTreeMap<String, String>) -> String {
+    let labels = labels
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", name, value)
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", labels.join(", "))
+}
+
 async fn healthcheck(config: LokiConfig, client: HttpClient) -> crate::Result<()> {
     let uri = format!("{}ready", config.endpoint.uri);
 
@@ -376,6 +466,55 @@ mod tests {
         assert_eq!(record.labels[0], ("bar".to_string(), "bar".to_string()));
     }
 
+    #[tokio::test]
+    async fn snappy_protobuf_payloads() {
+        let (config, _cx) = load_sink::<LokiConfig>(
+            r#"
+            endpoint = "http://localhost:3100"
+            labels = {source = "vector"}
+            encoding = "text"
+            compression = "snappy"
+        "#,
+        )
+        .unwrap();
+        let sink = LokiSink::new(config);
+
+        let json = serde_json::json!({
+            "streams": [{
+                "stream": {"source": "vector", "quote": "a \"b\""},
+                "values": [["1600000000123456789", "hello world"]],
+            }],
+        });
+        let request = sink
+            .build_request(PartitionInnerBuffer::new(
+                json,
+                PartitionKey { tenant_id: None },
+            ))
+            .await
+            .unwrap();
+        assert_eq!(request.headers()["content-type"], "application/x-protobuf");
+
+        let body = snap::raw::Decoder::new()
+            .decompress_vec(request.body())
+            .unwrap();
+        let request = logproto::PushRequest::decode(&body[..]).unwrap();
+        assert_eq!(
+            request,
+            logproto::PushRequest {
+                streams: vec![logproto::StreamAdapter {
+                    labels: r#"{quote="a \"b\"", source="vector"}"#.into(),
+                    entries: vec![logproto::EntryAdapter {
+                        timestamp: Some(prost_types::Timestamp {
+                            seconds: 1_600_000_000,
+                            nanos: 123_456_789,
+                        }),
+                        line: "hello world".into(),
+                    }],
+                }],
+            }
+        );
+    }
+
     #[tokio::test]
     async fn healthcheck_includes_auth() {
         let (mut config, _cx) = load_sink::<LokiConfig>(
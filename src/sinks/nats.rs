@@ -2,7 +2,9 @@ use crate::{
     buffers::Acker,
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     emit,
+    event::EventStatus,
     internal_events::{NatsEventSendFail, NatsEventSendSuccess, TemplateRenderingFailed},
+    nats::NatsAuthConfig,
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
         StreamSink,
@@ -33,6 +35,8 @@ pub struct NatsSinkConfig {
     name: String,
     subject: String,
     url: String,
+    #[serde(flatten)]
+    auth: NatsAuthConfig,
 }
 
 fn default_name() -> String {
@@ -85,16 +89,12 @@ impl SinkConfig for NatsSinkConfig {
 }
 
 impl NatsSinkConfig {
-    fn to_nats_options(&self) -> async_nats::Options {
-        // Set reconnect_buffer_size on the nats client to 0 bytes so that the
-        // client doesn't buffer internally (to avoid message loss).
-        async_nats::Options::new()
-            .with_name(&self.name)
-            .reconnect_buffer_size(0)
+    fn to_nats_options(&self) -> crate::Result<async_nats::Options> {
+        NatsOptions::from(self).to_nats_options()
     }
 
     async fn connect(&self) -> crate::Result<async_nats::Connection> {
-        self.to_nats_options()
+        self.to_nats_options()?
             .connect(&self.url)
             .map_err(|e| e.into())
             .await
@@ -112,6 +112,7 @@ async fn healthcheck(config: NatsSinkConfig) -> crate::Result<()> {
 #[derive(Clone)]
 struct NatsOptions {
     name: String,
+    auth: NatsAuthConfig,
 }
 
 pub struct NatsSink {
@@ -124,6 +125,9 @@ pub struct NatsSink {
 
 impl NatsSink {
     fn new(config: NatsSinkConfig, acker: Acker) -> crate::Result<Self> {
+        // Invalid authentication options are caught here rather than on connection.
+        config.to_nats_options()?;
+
         Ok(NatsSink {
             options: (&config).into(),
             encoding: config.encoding,
@@ -134,11 +138,14 @@ impl NatsSink {
     }
 }
 
-impl From<NatsOptions> for async_nats::Options {
-    fn from(options: NatsOptions) -> Self {
-        async_nats::Options::new()
-            .with_name(&options.name)
-            .reconnect_buffer_size(0)
+impl NatsOptions {
+    fn to_nats_options(&self) -> crate::Result<async_nats::Options> {
+        // Set reconnect_buffer_size on the nats client to 0 bytes so that the
+        // client doesn't buffer internally (to avoid message loss).
+        let options = async_nats::Options::new()
+            .with_name(&self.name)
+            .reconnect_buffer_size(0);
+        self.auth.apply(options)
     }
 }
 
@@ -146,6 +153,7 @@ impl From<&NatsSinkConfig> for NatsOptions {
     fn from(options: &NatsSinkConfig) -> Self {
         Self {
             name: options.name.clone(),
+            auth: options.auth.clone(),
         }
     }
 }
@@ -153,7 +161,7 @@ impl From<&NatsSinkConfig> for NatsOptions {
 #[async_trait]
 impl StreamSink for NatsSink {
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
-        let nats_options: async_nats::Options = self.options.clone().into();
+        let nats_options = self.options.to_nats_options().map_err(|_| ())?;
 
         let nc = nats_options.connect(&self.url).await.map_err(|_| ())?;
 
@@ -179,13 +187,13 @@ impl StreamSink for NatsSink {
                     emit!(NatsEventSendSuccess {
                         byte_size: message_len,
                     });
+                    self.acker.ack(1);
                 }
                 Err(error) => {
                     emit!(NatsEventSendFail { error });
+                    self.acker.ack_with_status(1, EventStatus::Errored);
                 }
             }
-
-            self.acker.ack(1);
         }
 
         Ok(())
@@ -262,6 +270,7 @@ mod integration_tests {
             name: "".to_owned(),
             subject: subject.clone(),
             url: "nats://127.0.0.1:4222".to_owned(),
+            auth: Default::default(),
         };
 
         // Establish the consumer subscription.
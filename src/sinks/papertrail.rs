@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 
 use syslog::{Facility, Formatter3164, LogFormat, Severity};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct PapertrailConfig {
     endpoint: UriSerde,
@@ -136,6 +136,9 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["magic".into()]),
                 timestamp_format: None,
+                csv: None,
+                protobuf: None,
+                template: None,
             },
         );
 
@@ -182,6 +182,22 @@ pub(super) trait MetricCollector {
     }
 }
 
+/// Encodes metrics in the text exposition format, with the default buckets and
+/// quantiles of the exporter.
+pub(crate) fn encode_text<'a>(
+    metrics: impl IntoIterator<Item = &'a Metric>,
+    default_namespace: Option<&str>,
+) -> String {
+    let buckets = super::default_histogram_buckets();
+    let quantiles = super::default_summary_quantiles();
+
+    let mut s = StringCollector::new();
+    for metric in metrics {
+        s.encode_metric(default_namespace, &buckets, &quantiles, false, metric);
+    }
+    s.finish()
+}
+
 pub(super) struct StringCollector {
     // BTreeMap ensures we get sorted output, which whilst not required is preferable
     processed: BTreeMap<String, String>,
@@ -2,6 +2,8 @@ mod collector;
 pub(crate) mod exporter;
 pub(crate) mod remote_write;
 
+pub(crate) use collector::encode_text;
+
 fn default_histogram_buckets() -> Vec<f64> {
     vec![
         0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
@@ -31,7 +31,7 @@ enum Errors {
     SetMetricInvalid,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct RemoteWriteConfig {
     pub endpoint: String,
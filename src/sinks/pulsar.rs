@@ -380,6 +380,9 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
+                csv: None,
+                protobuf: None,
+                template: None,
             },
             &None,
         )
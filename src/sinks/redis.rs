@@ -0,0 +1,418 @@
+use crate::{
+    buffers::Acker,
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    emit,
+    event::EventStatus,
+    internal_events::{
+        RedisConnectionFailed, RedisEventSent, RedisEventsRejected, RedisSendFailed,
+        TemplateRenderingFailed,
+    },
+    sinks::util::{
+        encoding::{EncodingConfig, EncodingConfiguration},
+        retries::ExponentialBackoff,
+        StreamSink,
+    },
+    template::{Template, TemplateParseError},
+    Event,
+};
+use async_trait::async_trait;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use redis::{aio::Connection, Client, RedisError};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{convert::TryFrom, time::Duration};
+use tokio::time::delay_for;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid Redis URL: {}", source))]
+    InvalidUrl { source: RedisError },
+    #[snafu(display("invalid key template: {}", source))]
+    KeyTemplate { source: TemplateParseError },
+    #[snafu(display("`pipeline_size` must be at least 1"))]
+    ZeroPipelineSize,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedisSinkConfig {
+    /// The Redis server to connect to, such as `redis://127.0.0.1:6379/0`.
+    url: String,
+    /// The list to push to, or the channel to publish to.
+    key: String,
+    #[serde(default)]
+    data_type: RedisDataType,
+    #[serde(default)]
+    list_method: ListMethod,
+    encoding: EncodingConfig<Encoding>,
+    /// The most events sent to the server in one pipeline, of those that are ready.
+    #[serde(default = "default_pipeline_size")]
+    pipeline_size: usize,
+}
+
+fn default_pipeline_size() -> usize {
+    100
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RedisDataType {
+    #[derivative(Default)]
+    List,
+    Channel,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ListMethod {
+    Lpush,
+    #[derivative(Default)]
+    Rpush,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Text,
+    Json,
+}
+
+inventory::submit! {
+    SinkDescription::new::<RedisSinkConfig>("redis")
+}
+
+impl GenerateConfig for RedisSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "redis")]
+impl SinkConfig for RedisSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = RedisSink::new(self.clone(), cx.acker())?;
+        let healthcheck = healthcheck(sink.client.clone()).boxed();
+        Ok((super::VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "redis"
+    }
+}
+
+async fn healthcheck(client: Client) -> crate::Result<()> {
+    let mut connection = client.get_async_connection().await?;
+    redis::cmd("PING")
+        .query_async::<_, String>(&mut connection)
+        .await?;
+    Ok(())
+}
+
+pub struct RedisSink {
+    client: Client,
+    key: Template,
+    data_type: RedisDataType,
+    list_method: ListMethod,
+    encoding: EncodingConfig<Encoding>,
+    pipeline_size: usize,
+    acker: Acker,
+}
+
+impl RedisSink {
+    fn new(config: RedisSinkConfig, acker: Acker) -> crate::Result<Self> {
+        if config.pipeline_size == 0 {
+            return Err(BuildError::ZeroPipelineSize.into());
+        }
+
+        Ok(RedisSink {
+            client: Client::open(config.url.as_str()).context(InvalidUrl)?,
+            key: Template::try_from(config.key).context(KeyTemplate)?,
+            data_type: config.data_type,
+            list_method: config.list_method,
+            encoding: config.encoding,
+            pipeline_size: config.pipeline_size,
+            acker,
+        })
+    }
+
+    fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+
+    /// Connects to the server, retrying until that succeeds.
+    async fn connect_backoff(&self) -> Connection {
+        let mut backoff = Self::fresh_backoff();
+        loop {
+            match self.client.get_async_connection().await {
+                Ok(connection) => return connection,
+                Err(error) => {
+                    emit!(RedisConnectionFailed { error });
+                    delay_for(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    fn command(&self) -> &'static str {
+        match self.data_type {
+            RedisDataType::Channel => "PUBLISH",
+            RedisDataType::List => match self.list_method {
+                ListMethod::Lpush => "LPUSH",
+                ListMethod::Rpush => "RPUSH",
+            },
+        }
+    }
+
+    /// The key and payload of an event, or `None` if its key can't be rendered.
+    fn encode_event(&self, event: Event) -> Option<(String, Vec<u8>)> {
+        let key = match self.key.render_string(&event) {
+            Ok(key) => key,
+            Err(error) => {
+                emit!(TemplateRenderingFailed {
+                    error,
+                    field: Some("key"),
+                    drop_event: true,
+                });
+                return None;
+            }
+        };
+        Some((key, encode_event(event, &self.encoding)))
+    }
+}
+
+#[async_trait]
+impl StreamSink for RedisSink {
+    async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut input = input.ready_chunks(self.pipeline_size);
+        let mut connection = None;
+
+        while let Some(events) = input.next().await {
+            // Events whose key can't be rendered are kept as `None`, to ack them in order.
+            let messages = events
+                .into_iter()
+                .map(|event| self.encode_event(event))
+                .collect::<Vec<_>>();
+
+            let mut pipeline = redis::pipe();
+            for (key, payload) in messages.iter().flatten() {
+                pipeline
+                    .cmd(self.command())
+                    .arg(key)
+                    .arg(&payload[..])
+                    .ignore();
+            }
+
+            let mut backoff = Self::fresh_backoff();
+            let mut status = EventStatus::Delivered;
+            // The whole pipeline is sent again when the connection fails, reconnecting in
+            // between, so events may be delivered more than once. Errors returned by the
+            // server won't go away by sending the same commands again.
+            while messages.iter().any(Option::is_some) {
+                let mut conn = match connection.take() {
+                    Some(conn) => conn,
+                    None => self.connect_backoff().await,
+                };
+
+                match pipeline.query_async::<_, ()>(&mut conn).await {
+                    Ok(()) => {
+                        for (_, payload) in messages.iter().flatten() {
+                            emit!(RedisEventSent {
+                                byte_size: payload.len(),
+                            });
+                        }
+                        connection = Some(conn);
+                        break;
+                    }
+                    // The connection is opened again for the next attempt.
+                    Err(error) if error.is_io_error() => emit!(RedisSendFailed { error }),
+                    Err(error) => {
+                        emit!(RedisEventsRejected {
+                            error,
+                            count: messages.iter().flatten().count(),
+                        });
+                        status = EventStatus::Failed;
+                        connection = Some(conn);
+                        break;
+                    }
+                }
+                delay_for(backoff.next().unwrap()).await;
+            }
+
+            for message in &messages {
+                let status = match message {
+                    Some(_) => status,
+                    None => EventStatus::Failed,
+                };
+                self.acker.ack_with_status(1, status);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Vec<u8> {
+    encoding.apply_rules(&mut event);
+
+    match encoding.codec() {
+        Encoding::Json => serde_json::to_vec(event.as_log()).unwrap(),
+        Encoding::Text => event
+            .as_log()
+            .get(log_schema().message_key())
+            .map(|v| v.as_bytes().to_vec())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Value;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisSinkConfig>();
+    }
+
+    #[test]
+    fn encodes_raw_logs() {
+        let event = Event::from("foo");
+        assert_eq!(
+            b"foo".to_vec(),
+            encode_event(event, &EncodingConfig::from(Encoding::Text))
+        );
+    }
+
+    #[test]
+    fn encodes_log_events() {
+        let mut event = Event::new_empty_log();
+        let log = event.as_mut_log();
+        log.insert("x", Value::from("23"));
+        log.insert("z", Value::from(25));
+        log.insert("a", Value::from("0"));
+
+        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        assert_eq!(encoded, br#"{"a":"0","x":"23","z":25}"#.to_vec());
+    }
+
+    #[test]
+    fn picks_commands() {
+        let sink = |config: &str| {
+            let config = toml::from_str::<RedisSinkConfig>(config).unwrap();
+            RedisSink::new(config, Acker::Null).unwrap()
+        };
+
+        let base = r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            encoding.codec = "text"
+            "#;
+        assert_eq!(sink(base).command(), "RPUSH");
+        assert_eq!(
+            sink(&format!("{}list_method = \"lpush\"", base)).command(),
+            "LPUSH"
+        );
+        assert_eq!(
+            sink(&format!("{}data_type = \"channel\"", base)).command(),
+            "PUBLISH"
+        );
+    }
+}
+
+#[cfg(feature = "redis-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{random_lines_with_stream, random_string, trace_init};
+
+    const REDIS_URL: &str = "redis://127.0.0.1:6379/0";
+
+    fn make_config(key: &str, data_type: &str) -> RedisSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            url = "{}"
+            key = "{}"
+            data_type = "{}"
+            encoding.codec = "text"
+            pipeline_size = 7"#,
+            REDIS_URL, key, data_type
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn redis_sink_pushes_to_list() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        let config = make_config(&key, "list");
+        let client = Client::open(REDIS_URL).unwrap();
+        healthcheck(client.clone()).await.unwrap();
+
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let mut sink = RedisSink::new(config, acker).unwrap();
+
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events);
+        sink.run(Box::pin(events)).await.unwrap();
+
+        let mut connection = client.get_async_connection().await.unwrap();
+        let output = redis::cmd("LRANGE")
+            .arg(&key)
+            .arg(0)
+            .arg(-1)
+            .query_async::<_, Vec<String>>(&mut connection)
+            .await
+            .unwrap();
+        assert_eq!(output, input);
+        assert_eq!(
+            ack_counter.load(std::sync::atomic::Ordering::Relaxed),
+            num_events
+        );
+    }
+
+    #[tokio::test]
+    async fn redis_sink_publishes_to_channel() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        let config = make_config(&key, "channel");
+        let client = Client::open(REDIS_URL).unwrap();
+
+        let mut pubsub = client.get_async_connection().await.unwrap().into_pubsub();
+        pubsub.subscribe(&key).await.unwrap();
+
+        let (acker, _) = Acker::new_for_testing();
+        let mut sink = RedisSink::new(config, acker).unwrap();
+
+        let num_events = 10;
+        let (input, events) = random_lines_with_stream(100, num_events);
+        sink.run(Box::pin(events)).await.unwrap();
+
+        let output = pubsub
+            .on_message()
+            .take(num_events)
+            .map(|message| message.get_payload::<String>().unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, input);
+    }
+}
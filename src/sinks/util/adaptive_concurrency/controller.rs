@@ -9,7 +9,10 @@ use crate::{
         AdaptiveConcurrencyAveragedRtt, AdaptiveConcurrencyInFlight, AdaptiveConcurrencyLimit,
         AdaptiveConcurrencyObservedRtt,
     },
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        request_limits::RequestLimits,
+        retries::{RetryAction, RetryLogic},
+    },
     stats::{Mean, EWMA},
 };
 use std::future::Future;
@@ -26,6 +29,7 @@ pub(super) struct Controller<L> {
     concurrency: Option<usize>,
     settings: AdaptiveConcurrencySettings,
     logic: L,
+    limits: Option<Arc<RequestLimits>>,
     pub(super) inner: Arc<Mutex<Inner>>,
     #[cfg(test)]
     pub(super) stats: Arc<Mutex<ControllerStatistics>>,
@@ -56,6 +60,7 @@ impl<L> Controller<L> {
         concurrency: Option<usize>,
         settings: AdaptiveConcurrencySettings,
         logic: L,
+        limits: Option<Arc<RequestLimits>>,
     ) -> Self {
         // If a `concurrency` is specified, it becomes both the
         // current limit and the maximum, effectively bypassing all the
@@ -67,6 +72,7 @@ impl<L> Controller<L> {
             concurrency,
             settings,
             logic,
+            limits,
             inner: Arc::new(Mutex::new(Inner {
                 current_limit,
                 in_flight: 0,
@@ -83,12 +89,38 @@ impl<L> Controller<L> {
 
     pub(super) fn acquire(&self) -> impl Future<Output = OwnedSemaphorePermit> + Send + 'static {
         let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
+        self.apply_max_concurrency(&mut inner);
         if inner.in_flight >= inner.current_limit {
             inner.reached_limit = true;
         }
         Arc::clone(&self.semaphore).acquire()
     }
 
+    /// The cap tuned through the API, if any.
+    fn max_concurrency(&self) -> Option<usize> {
+        self.limits
+            .as_ref()
+            .and_then(|limits| limits.max_concurrency())
+    }
+
+    /// Follow changes to the tuned cap. A fixed concurrency is lowered to
+    /// the cap and restored once it's lifted, while an adaptive limit is only
+    /// lowered, and grows back on its own.
+    fn apply_max_concurrency(&self, inner: &mut MutexGuard<Inner>) {
+        let max = self.max_concurrency();
+        let limit = match self.concurrency {
+            Some(concurrency) => max.map_or(concurrency, |max| max.min(concurrency)),
+            None => max.map_or(inner.current_limit, |max| max.min(inner.current_limit)),
+        };
+
+        if limit > inner.current_limit {
+            self.semaphore.add_permits(limit - inner.current_limit);
+        } else if limit < inner.current_limit {
+            self.semaphore.forget_permits(inner.current_limit - limit);
+        }
+        inner.current_limit = limit;
+    }
+
     pub(super) fn start_request(&self) {
         let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
 
@@ -192,7 +224,12 @@ impl<L> Controller<L> {
         // concurrency limit. Note that we only check this if we had
         // requests to go beyond the current limit to prevent
         // increasing the limit beyond what we have evidence for.
-        if inner.current_limit < super::MAX_CONCURRENCY
+        let max_limit = self
+            .max_concurrency()
+            .map_or(super::MAX_CONCURRENCY, |max| {
+                max.min(super::MAX_CONCURRENCY)
+            });
+        if inner.current_limit < max_limit
             && inner.reached_limit
             && !inner.had_back_pressure
             && current_rtt.is_some()
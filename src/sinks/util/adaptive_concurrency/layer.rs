@@ -1,5 +1,6 @@
 use super::{AdaptiveConcurrencyLimit, AdaptiveConcurrencySettings};
-use crate::sinks::util::retries::RetryLogic;
+use crate::sinks::util::{request_limits::RequestLimits, retries::RetryLogic};
+use std::sync::Arc;
 use tower::Layer;
 
 /// Enforces a limit on the concurrent number of requests the underlying
@@ -9,6 +10,7 @@ pub(crate) struct AdaptiveConcurrencyLimitLayer<L> {
     concurrency: Option<usize>,
     options: AdaptiveConcurrencySettings,
     logic: L,
+    limits: Option<Arc<RequestLimits>>,
 }
 
 impl<L> AdaptiveConcurrencyLimitLayer<L> {
@@ -18,14 +20,27 @@ impl<L> AdaptiveConcurrencyLimitLayer<L> {
             concurrency,
             options,
             logic,
+            limits: None,
         }
     }
+
+    /// Caps the concurrency to the tunable `RequestLimits::max_concurrency`.
+    pub fn with_limits(mut self, limits: Arc<RequestLimits>) -> Self {
+        self.limits = Some(limits);
+        self
+    }
 }
 
 impl<S, L: RetryLogic> Layer<S> for AdaptiveConcurrencyLimitLayer<L> {
     type Service = AdaptiveConcurrencyLimit<S, L>;
 
     fn layer(&self, service: S) -> Self::Service {
-        AdaptiveConcurrencyLimit::new(service, self.logic.clone(), self.concurrency, self.options)
+        AdaptiveConcurrencyLimit::new(
+            service,
+            self.logic.clone(),
+            self.concurrency,
+            self.options,
+            self.limits.clone(),
+        )
     }
 }
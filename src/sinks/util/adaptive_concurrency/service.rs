@@ -1,5 +1,5 @@
 use super::{controller::Controller, future::ResponseFuture, AdaptiveConcurrencySettings};
-use crate::sinks::util::retries::RetryLogic;
+use crate::sinks::util::{request_limits::RequestLimits, retries::RetryLogic};
 
 use tower::Service;
 
@@ -36,10 +36,11 @@ impl<S, L> AdaptiveConcurrencyLimit<S, L> {
         logic: L,
         concurrency: Option<usize>,
         options: AdaptiveConcurrencySettings,
+        limits: Option<Arc<RequestLimits>>,
     ) -> Self {
         AdaptiveConcurrencyLimit {
             inner,
-            controller: Arc::new(Controller::new(concurrency, options, logic)),
+            controller: Arc::new(Controller::new(concurrency, options, logic, limits)),
             state: State::Empty,
         }
     }
@@ -126,7 +126,7 @@ fn default_concurrency() -> Concurrency {
     Concurrency::Adaptive
 }
 
-#[derive(Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Serialize)]
 struct TestConfig {
     request: TowerRequestConfig,
     params: TestParams,
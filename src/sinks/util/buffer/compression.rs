@@ -7,23 +7,46 @@ pub const GZIP_FAST: usize = 1;
 pub const GZIP_DEFAULT: usize = 6;
 pub const GZIP_BEST: usize = 9;
 
+pub const ZSTD_FAST: usize = 1;
+pub const ZSTD_DEFAULT: usize = 3;
+pub const ZSTD_BEST: usize = 21;
+
 #[derive(Debug, Derivative, Copy, Clone, Eq, PartialEq)]
 #[derivative(Default)]
 pub enum Compression {
     #[derivative(Default)]
     None,
     Gzip(Option<usize>),
+    #[cfg(feature = "zstd")]
+    Zstd(Option<usize>),
 }
 
+// zstd is only available to the sinks that enable the `zstd` dependency.
+#[cfg(feature = "zstd")]
+const EXPECTED_ALGORITHM: &str = r#""none", "gzip" or "zstd""#;
+#[cfg(not(feature = "zstd"))]
+const EXPECTED_ALGORITHM: &str = r#""none" or "gzip""#;
+#[cfg(feature = "zstd")]
+const ALGORITHMS: &[&str] = &["none", "gzip", "zstd"];
+#[cfg(not(feature = "zstd"))]
+const ALGORITHMS: &[&str] = &["none", "gzip"];
+
 impl Compression {
     pub const fn gzip_default() -> Compression {
         Compression::Gzip(None)
     }
 
+    #[cfg(feature = "zstd")]
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd(None)
+    }
+
     pub fn content_encoding(&self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(_) => Some("zstd"),
         }
     }
 
@@ -31,6 +54,8 @@ impl Compression {
         match self {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
+            #[cfg(feature = "zstd")]
+            Self::Zstd(_) => "log.zst",
         }
     }
 }
@@ -39,23 +64,71 @@ impl fmt::Display for Compression {
         match *self {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.unwrap_or(GZIP_DEFAULT)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(ref level) => write!(f, "zstd({})", level.unwrap_or(ZSTD_DEFAULT)),
         }
     }
 }
 
+// AWS APIs only accept gzip compressed requests.
 #[cfg(feature = "rusoto_core")]
-impl From<Compression> for rusoto_core::encoding::ContentEncoding {
-    fn from(compression: Compression) -> Self {
+impl std::convert::TryFrom<Compression> for rusoto_core::encoding::ContentEncoding {
+    type Error = &'static str;
+
+    fn try_from(compression: Compression) -> Result<Self, Self::Error> {
         match compression {
-            Compression::None => rusoto_core::encoding::ContentEncoding::Identity,
+            Compression::None => Ok(rusoto_core::encoding::ContentEncoding::Identity),
             Compression::Gzip(level) => {
                 let level = level.unwrap_or(GZIP_DEFAULT);
-                rusoto_core::encoding::ContentEncoding::Gzip(None, level as u32)
+                Ok(rusoto_core::encoding::ContentEncoding::Gzip(
+                    None,
+                    level as u32,
+                ))
             }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(_) => Err("zstd compression isn't supported by this sink"),
         }
     }
 }
 
+fn parse_level<E: de::Error>(algorithm: &str, level: Value) -> Result<usize, E> {
+    let (max, expected) = match algorithm {
+        "zstd" => (ZSTD_BEST as u64, "an integer from 1 to 21"),
+        _ => (GZIP_BEST as u64, "0, 1, 2, 3, 4, 5, 6, 7, 8 or 9"),
+    };
+
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if value <= max && (algorithm != "zstd" || value > 0) => Ok(value as usize),
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &expected,
+            )),
+        },
+        Value::String(level) => match (algorithm, level.as_str()) {
+            ("zstd", "fast") => Ok(ZSTD_FAST),
+            ("zstd", "default") => Ok(ZSTD_DEFAULT),
+            ("zstd", "best") => Ok(ZSTD_BEST),
+            ("zstd", level) => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""fast", "best" or "default""#,
+            )),
+            (_, "none") => Ok(GZIP_NONE),
+            (_, "fast") => Ok(GZIP_FAST),
+            (_, "default") => Ok(GZIP_DEFAULT),
+            (_, "best") => Ok(GZIP_BEST),
+            (_, level) => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""none", "fast", "best" or "default""#,
+            )),
+        },
+        value => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Compression {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -77,9 +150,11 @@ impl<'de> de::Deserialize<'de> for Compression {
                 match s {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
+                    #[cfg(feature = "zstd")]
+                    "zstd" => Ok(Compression::zstd_default()),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip""#,
+                        &EXPECTED_ALGORITHM,
                     )),
                 }
             }
@@ -103,47 +178,34 @@ impl<'de> de::Deserialize<'de> for Compression {
                             if level.is_some() {
                                 return Err(de::Error::duplicate_field("level"));
                             }
-                            level = Some(match map.next_value::<Value>()? {
-                                Value::Number(level) => match level.as_u64() {
-                                    Some(value) if value <= 9 => value as usize,
-                                    Some(_) | None => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Other(&level.to_string()),
-                                            &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                                        ))
-                                    }
-                                },
-                                Value::String(level) => match level.as_str() {
-                                    "none" => GZIP_NONE,
-                                    "fast" => GZIP_FAST,
-                                    "default" => GZIP_DEFAULT,
-                                    "best" => GZIP_BEST,
-                                    level => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Str(level),
-                                            &r#""none", "fast", "best" or "default""#,
-                                        ))
-                                    }
-                                },
-                                value => {
-                                    return Err(de::Error::invalid_type(
-                                        de::Unexpected::Other(&value.to_string()),
-                                        &"integer or string",
-                                    ));
+                            // The level is checked once the algorithm is known.
+                            let value = map.next_value::<Value>()?;
+                            level = Some(match algorithm {
+                                Some(algorithm @ "gzip") | Some(algorithm @ "zstd") => {
+                                    Ok(parse_level::<A::Error>(algorithm, value)?)
                                 }
+                                _ => Err(value),
                             });
                         }
                         _ => return Err(de::Error::unknown_field(key, &["algorithm", "level"])),
                     };
                 }
 
-                match algorithm.ok_or_else(|| de::Error::missing_field("algorithm"))? {
+                let algorithm = algorithm.ok_or_else(|| de::Error::missing_field("algorithm"))?;
+                let parsed_level = |level: Option<Result<usize, Value>>| -> Result<_, A::Error> {
+                    level
+                        .map(|level| level.or_else(|value| parse_level(algorithm, value)))
+                        .transpose()
+                };
+                match algorithm {
                     "none" => match level {
                         Some(_) => Err(de::Error::unknown_field("level", &[])),
                         None => Ok(Compression::None),
                     },
-                    "gzip" => Ok(Compression::Gzip(level)),
-                    algorithm => Err(de::Error::unknown_variant(algorithm, &["none", "gzip"])),
+                    "gzip" => Ok(Compression::Gzip(parsed_level(level)?)),
+                    #[cfg(feature = "zstd")]
+                    "zstd" => Ok(Compression::Zstd(parsed_level(level)?)),
+                    algorithm => Err(de::Error::unknown_variant(algorithm, ALGORITHMS)),
                 }
             }
         }
@@ -172,6 +234,16 @@ impl ser::Serialize for Compression {
                     level => map.serialize_entry("level", &level)?,
                 };
             }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(level) => {
+                map.serialize_entry("algorithm", "zstd")?;
+                match level.unwrap_or(ZSTD_DEFAULT) {
+                    ZSTD_FAST => map.serialize_entry("level", "fast")?,
+                    ZSTD_DEFAULT => map.serialize_entry("level", "default")?,
+                    ZSTD_BEST => map.serialize_entry("level", "best")?,
+                    level => map.serialize_entry("level", &level)?,
+                };
+            }
         };
         map.end()
     }
@@ -181,6 +253,7 @@ impl ser::Serialize for Compression {
 mod test {
     use super::Compression;
 
+    #[cfg(feature = "zstd")]
     #[test]
     fn deserialization() {
         let fixtures_valid = [
@@ -195,6 +268,19 @@ mod test {
                 r#"{"algorithm": "gzip", "level": 8}"#,
                 Compression::Gzip(Some(8)),
             ),
+            (r#""zstd""#, Compression::Zstd(None)),
+            (
+                r#"{"algorithm": "zstd", "level": "best"}"#,
+                Compression::Zstd(Some(21)),
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": 15}"#,
+                Compression::Zstd(Some(15)),
+            ),
+            (
+                r#"{"level": 15, "algorithm": "zstd"}"#,
+                Compression::Zstd(Some(15)),
+            ),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -208,11 +294,11 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip" or "zstd" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected `none` or `gzip` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zstd` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -230,6 +316,14 @@ mod test {
                 r#"{"algorithm": "gzip", "level": {}}"#,
                 r#"invalid type: {}, expected integer or string at line 1 column 34"#,
             ),
+            (
+                r#"{"algorithm": "zstd", "level": 22}"#,
+                r#"invalid value: 22, expected an integer from 1 to 21 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": "none"}"#,
+                r#"invalid value: string "none", expected "fast", "best" or "default" at line 1 column 38"#,
+            ),
             (
                 r#"{"algorithm": "gzip", "level": "default", "key": 42}"#,
                 r#"unknown field `key`, expected `algorithm` or `level` at line 1 column 47"#,
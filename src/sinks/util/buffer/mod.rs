@@ -1,8 +1,9 @@
 use super::batch::{
     err_event_too_large, Batch, BatchConfig, BatchError, BatchSettings, BatchSize, PushResult,
 };
+use bytes::Bytes;
 use flate2::write::GzEncoder;
-use std::io::Write;
+use std::{fmt, io::Write};
 
 pub mod compression;
 pub mod json;
@@ -12,7 +13,7 @@ pub mod metrics;
 pub mod partition;
 pub mod vec;
 
-pub use compression::{Compression, GZIP_FAST};
+pub use compression::{Compression, GZIP_FAST, ZSTD_DEFAULT};
 pub use partition::{Partition, PartitionBuffer, PartitionInnerBuffer};
 
 #[derive(Debug)]
@@ -22,12 +23,26 @@ pub struct Buffer {
     num_bytes: usize,
     settings: BatchSize<Self>,
     compression: Compression,
+    header: Option<Bytes>,
 }
 
-#[derive(Debug)]
 pub enum InnerBuffer {
     Plain(Vec<u8>),
     Gzip(GzEncoder<Vec<u8>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, Vec<u8>>),
+}
+
+// The zstd encoder doesn't implement `Debug`.
+impl fmt::Debug for InnerBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InnerBuffer::Plain(inner) => f.debug_tuple("Plain").field(inner).finish(),
+            InnerBuffer::Gzip(inner) => f.debug_tuple("Gzip").field(inner).finish(),
+            #[cfg(feature = "zstd")]
+            InnerBuffer::Zstd(inner) => f.debug_tuple("Zstd").field(inner.get_ref()).finish(),
+        }
+    }
 }
 
 impl Buffer {
@@ -42,6 +57,14 @@ impl Buffer {
                     flate2::Compression::new(level as u32),
                 ))
             }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(level) => {
+                let level = level.unwrap_or(ZSTD_DEFAULT);
+                InnerBuffer::Zstd(
+                    zstd::Encoder::new(buffer, level as i32)
+                        .expect("This can't fail because the inner writer is a Vec"),
+                )
+            }
         };
         Self {
             inner,
@@ -49,11 +72,27 @@ impl Buffer {
             num_bytes: 0,
             settings,
             compression,
+            header: None,
         }
     }
 
+    /// Starts each batch with `header`, such as the header of a file format.
+    pub fn with_header(mut self, header: impl Into<Bytes>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
     pub fn push(&mut self, input: &[u8]) {
+        if self.num_items == 0 {
+            if let Some(header) = self.header.clone() {
+                self.write(&header);
+            }
+        }
         self.num_items += 1;
+        self.write(input);
+    }
+
+    fn write(&mut self, input: &[u8]) {
         match &mut self.inner {
             InnerBuffer::Plain(inner) => {
                 inner.extend_from_slice(input);
@@ -61,14 +100,17 @@ impl Buffer {
             InnerBuffer::Gzip(inner) => {
                 inner.write_all(input).unwrap();
             }
+            #[cfg(feature = "zstd")]
+            InnerBuffer::Zstd(inner) => {
+                inner.write_all(input).unwrap();
+            }
         }
     }
 
+    /// Whether nothing was pushed. The compressed encoders hold on to their
+    /// input until they are flushed, so their output can't tell.
     pub fn is_empty(&self) -> bool {
-        match &self.inner {
-            InnerBuffer::Plain(inner) => inner.is_empty(),
-            InnerBuffer::Gzip(inner) => inner.get_ref().is_empty(),
-        }
+        self.num_items == 0
     }
 }
 
@@ -108,7 +150,10 @@ impl Batch for Buffer {
     }
 
     fn fresh(&self) -> Self {
-        Self::new(self.settings, self.compression)
+        Self {
+            header: self.header.clone(),
+            ..Self::new(self.settings, self.compression)
+        }
     }
 
     fn finish(self) -> Self::Output {
@@ -117,6 +162,10 @@ impl Batch for Buffer {
             InnerBuffer::Gzip(inner) => inner
                 .finish()
                 .expect("This can't fail because the inner writer is a Vec"),
+            #[cfg(feature = "zstd")]
+            InnerBuffer::Zstd(inner) => inner
+                .finish()
+                .expect("This can't fail because the inner writer is a Vec"),
         }
     }
 
@@ -139,10 +188,9 @@ mod test {
     };
     use tokio::time::Duration;
 
-    #[tokio::test]
-    async fn gzip() {
-        use flate2::read::GzDecoder;
+    const LINE: &[u8] = b"It's going down, I'm yelling timber, You better move, you better dance";
 
+    async fn send_batches(compression: Compression) -> Vec<Vec<u8>> {
         let (acker, _) = Acker::new_for_testing();
         let sent_requests = Arc::new(Mutex::new(Vec::new()));
 
@@ -154,17 +202,9 @@ mod test {
         let batch_size = BatchSettings::default().bytes(100_000).events(1_000).size;
         let timeout = Duration::from_secs(0);
 
-        let buffered = BatchSink::new(
-            svc,
-            Buffer::new(batch_size, Compression::gzip_default()),
-            timeout,
-            acker,
-        );
+        let buffered = BatchSink::new(svc, Buffer::new(batch_size, compression), timeout, acker);
 
-        let input = std::iter::repeat(
-            b"It's going down, I'm yelling timber, You better move, you better dance".to_vec(),
-        )
-        .take(100_000);
+        let input = std::iter::repeat(LINE.to_vec()).take(100_000);
 
         let _ = buffered
             .sink_map_err(drop)
@@ -181,6 +221,30 @@ mod test {
 
         assert!(output.len() > 1);
         assert!(dbg!(output.iter().map(|o| o.len()).sum::<usize>()) < 80_000);
+        output
+    }
+
+    #[test]
+    fn is_empty_until_pushed() {
+        let mut compressions = vec![Compression::None, Compression::gzip_default()];
+        #[cfg(feature = "zstd")]
+        compressions.push(Compression::zstd_default());
+
+        for compression in compressions {
+            let batch_size = BatchSettings::default().bytes(1_000).events(10).size;
+            let mut buffer = Buffer::new(batch_size, compression);
+            assert!(buffer.is_empty());
+
+            buffer.push(LINE);
+            assert!(!buffer.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn gzip() {
+        use flate2::read::GzDecoder;
+
+        let output = send_batches(Compression::gzip_default()).await;
 
         let decompressed = output.into_iter().flat_map(|batch| {
             let mut decompressed = vec![];
@@ -190,10 +254,18 @@ mod test {
             decompressed
         });
 
-        assert!(decompressed.eq(std::iter::repeat(
-            b"It's going down, I'm yelling timber, You better move, you better dance".to_vec()
-        )
-        .take(100_000)
-        .flatten()));
+        assert!(decompressed.eq(std::iter::repeat(LINE.to_vec()).take(100_000).flatten()));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[tokio::test]
+    async fn zstd() {
+        let output = send_batches(Compression::zstd_default()).await;
+
+        let decompressed = output
+            .into_iter()
+            .flat_map(|batch| zstd::decode_all(batch.as_slice()).unwrap());
+
+        assert!(decompressed.eq(std::iter::repeat(LINE.to_vec()).take(100_000).flatten()));
     }
 }
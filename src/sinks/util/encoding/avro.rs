@@ -0,0 +1,148 @@
+use crate::{emit, internal_events::AvroEncodingFailed};
+use avro_rs::{types::Value as AvroValue, Schema};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+const MAGIC: &[u8] = b"Obj\x01";
+
+/// Encoder of the `avro` codec, which encodes events with the schema set by `encoding.schema`.
+///
+/// Events are either encoded as bare datums, or as blocks of an object container file, as in
+/// https://avro.apache.org/docs/current/spec.html#Object+Container+Files. Each block holds a
+/// single event, so that events can be appended to files and batches as they come.
+#[derive(Debug, Clone)]
+pub struct AvroEncoder {
+    raw_schema: String,
+    schema: Schema,
+    sync_marker: [u8; 16],
+}
+
+impl AvroEncoder {
+    pub fn new(raw_schema: &str) -> crate::Result<Self> {
+        let schema = Schema::parse_str(raw_schema)
+            .map_err(|error| format!("Invalid Avro schema in `encoding.schema`: {}", error))?;
+        // The marker is derived from the schema, so that files can be appended to across
+        // restarts.
+        let mut sync_marker = [0; 16];
+        sync_marker.copy_from_slice(&Sha256::digest(raw_schema.as_bytes())[..16]);
+
+        Ok(Self {
+            raw_schema: raw_schema.to_owned(),
+            schema,
+            sync_marker,
+        })
+    }
+
+    /// The header that starts a container file, which holds the schema.
+    pub fn header(&self) -> Vec<u8> {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "avro.schema".to_owned(),
+            AvroValue::Bytes(self.raw_schema.clone().into_bytes()),
+        );
+        metadata.insert("avro.codec".to_owned(), AvroValue::Bytes(b"null".to_vec()));
+
+        let mut header = MAGIC.to_vec();
+        header.extend(
+            avro_rs::to_avro_datum(
+                &Schema::Map(Box::new(Schema::Bytes)),
+                AvroValue::Map(metadata),
+            )
+            .expect("The metadata always matches its schema."),
+        );
+        header.extend_from_slice(&self.sync_marker);
+        header
+    }
+
+    /// Encodes the event as a datum of the schema. This is `None` if the event doesn't match
+    /// the schema, in which case it should be dropped.
+    pub fn encode_datum(&self, event: impl Serialize) -> Option<Vec<u8>> {
+        avro_rs::to_value(event)
+            .and_then(|value| value.resolve(&self.schema))
+            .and_then(|value| avro_rs::to_avro_datum(&self.schema, value))
+            .map_err(|error| emit!(AvroEncodingFailed { error: &error }))
+            .ok()
+    }
+
+    /// Encodes the event as a block of a container file, to be written after `header`.
+    pub fn encode_block(&self, event: impl Serialize) -> Option<Vec<u8>> {
+        let datum = self.encode_datum(event)?;
+
+        let mut block = encode_long(1);
+        block.extend(encode_long(datum.len() as i64));
+        block.extend(datum);
+        block.extend_from_slice(&self.sync_marker);
+        Some(block)
+    }
+}
+
+fn encode_long(n: i64) -> Vec<u8> {
+    avro_rs::to_avro_datum(&Schema::Long, AvroValue::Long(n))
+        .expect("A long always matches its schema.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use avro_rs::Reader;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "log",
+        "fields": [
+            {"name": "message", "type": "string"},
+            {"name": "status", "type": "long"}
+        ]
+    }"#;
+
+    fn event(message: &str, status: i64) -> Event {
+        let mut event = Event::new_empty_log();
+        event.as_mut_log().insert("message", message);
+        event.as_mut_log().insert("status", status);
+        event
+    }
+
+    #[test]
+    fn encodes_container_files() {
+        let encoder = AvroEncoder::new(SCHEMA).unwrap();
+
+        let mut file = encoder.header();
+        for (message, status) in &[("first", 200), ("second", 404)] {
+            let block = encoder.encode_block(event(message, *status).as_log());
+            file.extend(block.unwrap());
+        }
+
+        let records = Reader::new(&file[..])
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            records,
+            vec![
+                AvroValue::Record(vec![
+                    ("message".to_owned(), AvroValue::String("first".to_owned())),
+                    ("status".to_owned(), AvroValue::Long(200)),
+                ]),
+                AvroValue::Record(vec![
+                    ("message".to_owned(), AvroValue::String("second".to_owned())),
+                    ("status".to_owned(), AvroValue::Long(404)),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_logs_that_dont_match_the_schema() {
+        let encoder = AvroEncoder::new(SCHEMA).unwrap();
+
+        let event = Event::from("no status");
+        assert!(encoder.encode_datum(event.as_log()).is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        assert!(AvroEncoder::new(r#"{"type": "unknown"}"#).is_err());
+    }
+}
@@ -2,8 +2,10 @@ use crate::{
     event::{PathComponent, PathIter},
     serde::skip_serializing_if_default,
     sinks::util::encoding::{
-        with_default::EncodingConfigWithDefault, EncodingConfiguration, TimestampFormat,
+        with_default::EncodingConfigWithDefault, CsvOptions, EncodingConfiguration,
+        ProtobufOptions, TimestampFormat,
     },
+    template::Template,
 };
 use serde::{
     de::{self, DeserializeOwned, IntoDeserializer, MapAccess, Visitor},
@@ -31,6 +33,12 @@ pub struct EncodingConfig<E> {
     pub(crate) except_fields: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     pub(crate) timestamp_format: Option<TimestampFormat>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) csv: Option<CsvOptions>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) protobuf: Option<ProtobufOptions>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) template: Option<Template>,
 }
 
 impl<E> EncodingConfiguration<E> for EncodingConfig<E> {
@@ -50,6 +58,15 @@ impl<E> EncodingConfiguration<E> for EncodingConfig<E> {
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+    fn csv(&self) -> &Option<CsvOptions> {
+        &self.csv
+    }
+    fn protobuf(&self) -> &Option<ProtobufOptions> {
+        &self.protobuf
+    }
+    fn template(&self) -> &Option<Template> {
+        &self.template
+    }
 }
 
 impl<E> From<EncodingConfigWithDefault<E>> for EncodingConfig<E>
@@ -63,6 +80,9 @@ where
             only_fields: encoding.only_fields,
             except_fields: encoding.except_fields,
             timestamp_format: encoding.timestamp_format,
+            csv: encoding.csv,
+            protobuf: encoding.protobuf,
+            template: encoding.template,
         }
     }
 }
@@ -79,6 +99,9 @@ impl<E> EncodingConfig<E> {
             only_fields: self.only_fields,
             except_fields: self.except_fields,
             timestamp_format: self.timestamp_format,
+            csv: self.csv,
+            protobuf: self.protobuf,
+            template: self.template,
         }
     }
 }
@@ -91,6 +114,9 @@ impl<E> From<E> for EncodingConfig<E> {
             only_fields: Default::default(),
             except_fields: Default::default(),
             timestamp_format: Default::default(),
+            csv: Default::default(),
+            protobuf: Default::default(),
+            template: Default::default(),
         }
     }
 }
@@ -132,6 +158,9 @@ where
                     only_fields: Default::default(),
                     except_fields: Default::default(),
                     timestamp_format: Default::default(),
+                    csv: Default::default(),
+                    protobuf: Default::default(),
+                    template: Default::default(),
                 })
             }
 
@@ -161,6 +190,9 @@ where
             }),
             except_fields: inner.except_fields,
             timestamp_format: inner.timestamp_format,
+            csv: inner.csv,
+            protobuf: inner.protobuf,
+            template: inner.template,
         };
 
         concrete.validate().map_err(serde::de::Error::custom)?;
@@ -179,4 +211,10 @@ pub struct Inner<E> {
     except_fields: Option<Vec<String>>,
     #[serde(default)]
     timestamp_format: Option<TimestampFormat>,
+    #[serde(default)]
+    csv: Option<CsvOptions>,
+    #[serde(default)]
+    protobuf: Option<ProtobufOptions>,
+    #[serde(default)]
+    template: Option<Template>,
 }
@@ -0,0 +1,98 @@
+use crate::event::LogEvent;
+use serde::{Deserialize, Serialize};
+
+/// Options of the `csv` codec.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CsvOptions {
+    /// The fields to encode, in order. Missing fields are left empty.
+    pub(crate) fields: Vec<String>,
+    #[serde(default = "default_delimiter")]
+    pub(crate) delimiter: char,
+    /// Whether to start each file, or request, with a line of the field names.
+    #[serde(default)]
+    pub(crate) header: bool,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+impl CsvOptions {
+    pub fn header(&self) -> bool {
+        self.header
+    }
+
+    /// The line of field names, without a line terminator.
+    pub fn encode_header(&self) -> String {
+        self.encode_values(self.fields.iter().map(String::as_str))
+    }
+
+    /// The values of the fields of a log as a line, without a line terminator.
+    pub fn encode(&self, log: &LogEvent) -> String {
+        let values = self
+            .fields
+            .iter()
+            .map(|field| {
+                log.get(field)
+                    .map(|value| value.to_string_lossy())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        self.encode_values(values.iter().map(String::as_str))
+    }
+
+    fn encode_values<'a>(&self, values: impl Iterator<Item = &'a str>) -> String {
+        let mut delimiter = [0; 4];
+        let delimiter = self.delimiter.encode_utf8(&mut delimiter);
+
+        let mut line = String::new();
+        for (i, value) in values.enumerate() {
+            if i > 0 {
+                line.push_str(delimiter);
+            }
+            // Values are quoted as in RFC 4180, only when they need to be.
+            if value.contains(|c| c == self.delimiter || c == '"' || c == '\n' || c == '\r') {
+                line.push('"');
+                line.push_str(&value.replace('"', "\"\""));
+                line.push('"');
+            } else {
+                line.push_str(value);
+            }
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+
+    fn options(fields: &[&str], delimiter: char) -> CsvOptions {
+        CsvOptions {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            delimiter,
+            header: true,
+        }
+    }
+
+    #[test]
+    fn encodes_fields_in_order() {
+        let mut event = Event::from("hello, \"world\"");
+        event.as_mut_log().insert("status", 200);
+
+        let options = options(&["status", "missing", "message"], ',');
+        assert_eq!(options.encode_header(), "status,missing,message");
+        assert_eq!(options.encode(event.as_log()), r#"200,,"hello, ""world""""#);
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        let mut event = Event::from("a,b");
+        event.as_mut_log().insert("other", "c\td");
+
+        let options = options(&["message", "other"], '\t');
+        assert_eq!(options.encode(event.as_log()), "a,b\t\"c\td\"");
+    }
+}
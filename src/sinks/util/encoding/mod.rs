@@ -29,13 +29,23 @@
 //       `Encoder` that defines some `encode` function which this config then calls internally as
 //       part of it's own (yet to be written) `encode() -> Vec<u8>` function.
 
+#[cfg(feature = "avro-rs")]
+mod avro;
+#[cfg(feature = "avro-rs")]
+pub use self::avro::AvroEncoder;
 mod config;
 pub use config::EncodingConfig;
+mod csv;
+pub use self::csv::CsvOptions;
+mod protobuf;
+pub use self::protobuf::{ProtobufEncodeError, ProtobufEncoder, ProtobufOptions};
 mod with_default;
 pub use with_default::EncodingConfigWithDefault;
 
 use crate::{
     event::{PathComponent, PathIter, Value},
+    internal_events::TemplateRenderingFailed,
+    template::Template,
     Event, Result,
 };
 use serde::{Deserialize, Serialize};
@@ -51,6 +61,9 @@ pub trait EncodingConfiguration<E> {
     fn only_fields(&self) -> &Option<Vec<Vec<PathComponent>>>;
     fn except_fields(&self) -> &Option<Vec<String>>;
     fn timestamp_format(&self) -> &Option<TimestampFormat>;
+    fn csv(&self) -> &Option<CsvOptions>;
+    fn protobuf(&self) -> &Option<ProtobufOptions>;
+    fn template(&self) -> &Option<Template>;
 
     fn apply_only_fields(&self, event: &mut Event) {
         if let Some(only_fields) = &self.only_fields() {
@@ -132,9 +145,57 @@ pub trait EncodingConfiguration<E> {
                 );
             }
         }
+        if let Some(csv) = &self.csv() {
+            if csv.fields.is_empty() {
+                return Err("`csv.fields` must list at least one field.".into());
+            }
+        }
         Ok(())
     }
 
+    /// Options of the `csv` codec, which sinks should require when it's used.
+    fn csv_options(&self) -> Result<&CsvOptions> {
+        self.csv()
+            .as_ref()
+            .ok_or_else(|| "`csv.fields` must be set to use the `csv` codec.".into())
+    }
+
+    /// Encoder of the `avro` codec, which sinks should require when it's used.
+    #[cfg(feature = "avro-rs")]
+    fn avro_encoder(&self) -> Result<AvroEncoder> {
+        let schema = self
+            .schema()
+            .as_ref()
+            .ok_or("`encoding.schema` must be set to use the `avro` codec.")?;
+        AvroEncoder::new(schema)
+    }
+
+    /// Encoder of the `protobuf` codec, which sinks should require when it's used.
+    fn protobuf_encoder(&self) -> Result<ProtobufEncoder> {
+        self.protobuf()
+            .as_ref()
+            .ok_or("`encoding.protobuf` must be set to use the `protobuf` codec.")?
+            .build()
+    }
+
+    /// Renders the event as a line of text with `template`. This is `None` if
+    /// no template is set, and `Some(None)` if the event couldn't be rendered,
+    /// in which case it should be dropped.
+    fn render_template(&self, event: &Event) -> Option<Option<String>> {
+        self.template().as_ref().map(|template| {
+            template
+                .render_string(event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingFailed {
+                        error,
+                        field: Some("encoding.template"),
+                        drop_event: true,
+                    })
+                })
+                .ok()
+        })
+    }
+
     /// Apply the EncodingConfig rules to the provided event.
     ///
     /// Currently, this is idempotent.
@@ -0,0 +1,557 @@
+use crate::{
+    emit,
+    event::{LogEvent, Value},
+    internal_events::ProtobufEncodingFailed,
+};
+use bytes::BufMut;
+use chrono::{DateTime, Utc};
+use prost::{
+    encoding::{encode_key, encode_varint, WireType},
+    Message,
+};
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorSet,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::Arc,
+};
+
+const TIMESTAMP_TYPE: &str = "google.protobuf.Timestamp";
+
+/// Options of the `protobuf` codec.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ProtobufOptions {
+    /// A `FileDescriptorSet`, as written by `protoc --include_imports --descriptor_set_out`.
+    pub(crate) descriptor_set_path: PathBuf,
+    /// The full name of the message type of events, such as `package.Message`.
+    pub(crate) message_type: String,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Unable to read descriptor set {:?}: {}", path, source))]
+    ReadDescriptorSet {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid descriptor set {:?}: {}", path, source))]
+    DecodeDescriptorSet {
+        path: PathBuf,
+        source: prost::DecodeError,
+    },
+    #[snafu(display("Message type {:?} isn't in the descriptor set", message_type))]
+    UnknownMessageType { message_type: String },
+}
+
+#[derive(Debug, Snafu)]
+pub enum ProtobufEncodeError {
+    #[snafu(display("Field {:?} should be {}, got {:?}", field, expected, value))]
+    MismatchedType {
+        field: String,
+        expected: &'static str,
+        value: Value,
+    },
+    #[snafu(display("Field {:?} has no enum value {:?}", field, value))]
+    UnknownEnumValue { field: String, value: String },
+    #[snafu(display("Field {:?} is a group, which isn't supported", field))]
+    UnsupportedGroup { field: String },
+    #[snafu(display("Type {:?} isn't in the descriptor set", type_name))]
+    UnknownType { type_name: String },
+}
+
+impl ProtobufOptions {
+    pub fn build(&self) -> crate::Result<ProtobufEncoder> {
+        let path = &self.descriptor_set_path;
+        let bytes = std::fs::read(path).context(ReadDescriptorSet { path })?;
+        let set = FileDescriptorSet::decode(&bytes[..]).context(DecodeDescriptorSet { path })?;
+
+        let mut descriptors = Descriptors::default();
+        for file in set.file {
+            descriptors.index(file.package(), file.message_type, file.enum_type);
+        }
+
+        let message_type = self.message_type.trim_start_matches('.').to_owned();
+        if !descriptors.messages.contains_key(&message_type) {
+            return Err(UnknownMessageType { message_type }.build().into());
+        }
+
+        Ok(ProtobufEncoder {
+            descriptors: Arc::new(descriptors),
+            message_type,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Descriptors {
+    messages: HashMap<String, DescriptorProto>,
+    enums: HashMap<String, EnumDescriptorProto>,
+}
+
+impl Descriptors {
+    /// Indexes types by their full names, without the leading dot.
+    fn index(
+        &mut self,
+        scope: &str,
+        messages: Vec<DescriptorProto>,
+        enums: Vec<EnumDescriptorProto>,
+    ) {
+        for descriptor in enums {
+            self.enums
+                .insert(full_name(scope, descriptor.name()), descriptor);
+        }
+        for mut descriptor in messages {
+            let name = full_name(scope, descriptor.name());
+            let nested_types = std::mem::take(&mut descriptor.nested_type);
+            let enum_types = std::mem::take(&mut descriptor.enum_type);
+            self.index(&name, nested_types, enum_types);
+            self.messages.insert(name, descriptor);
+        }
+    }
+
+    fn message(&self, type_name: &str) -> Result<&DescriptorProto, ProtobufEncodeError> {
+        let type_name = type_name.trim_start_matches('.');
+        self.messages
+            .get(type_name)
+            .ok_or_else(|| ProtobufEncodeError::UnknownType {
+                type_name: type_name.to_owned(),
+            })
+    }
+
+    fn enumeration(&self, type_name: &str) -> Result<&EnumDescriptorProto, ProtobufEncodeError> {
+        let type_name = type_name.trim_start_matches('.');
+        self.enums
+            .get(type_name)
+            .ok_or_else(|| ProtobufEncodeError::UnknownType {
+                type_name: type_name.to_owned(),
+            })
+    }
+}
+
+fn full_name(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+/// Encoder of the `protobuf` codec, which maps the fields of logs to the fields, of the same
+/// name, of the message type set by `encoding.protobuf.message_type`.
+///
+/// Fields of the log that aren't in the message type are left out, and nested messages are
+/// encoded from maps. Repeated fields are encoded from arrays, and map fields from maps.
+#[derive(Debug, Clone)]
+pub struct ProtobufEncoder {
+    descriptors: Arc<Descriptors>,
+    message_type: String,
+}
+
+impl ProtobufEncoder {
+    /// Encodes the log as a message. This is `None` if the log doesn't match the message type,
+    /// in which case it should be dropped.
+    pub fn encode(&self, log: &LogEvent) -> Option<Vec<u8>> {
+        let mut buf = Vec::new();
+        self.descriptors
+            .message(&self.message_type)
+            .and_then(|message| self.encode_message(message, log.as_map(), &mut buf))
+            .map_err(|error| emit!(ProtobufEncodingFailed { error: &error }))
+            .ok()?;
+        Some(buf)
+    }
+
+    fn encode_message(
+        &self,
+        message: &DescriptorProto,
+        fields: &BTreeMap<String, Value>,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), ProtobufEncodeError> {
+        for field in &message.field {
+            let value = match fields.get(field.name()) {
+                None | Some(Value::Null) => continue,
+                Some(value) => value,
+            };
+
+            match (field.label(), value) {
+                (Label::Repeated, Value::Array(values)) => {
+                    for value in values {
+                        self.encode_field(field, value, buf)?;
+                    }
+                }
+                (Label::Repeated, Value::Map(entries)) if self.is_map_field(field) => {
+                    for (key, value) in entries {
+                        let mut entry = BTreeMap::new();
+                        entry.insert("key".to_owned(), Value::from(key.clone()));
+                        entry.insert("value".to_owned(), value.clone());
+                        self.encode_field(field, &Value::Map(entry), buf)?;
+                    }
+                }
+                (Label::Repeated, value) => return mismatched(field, "an array", value),
+                (_, value) => self.encode_field(field, value, buf)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn is_map_field(&self, field: &FieldDescriptorProto) -> bool {
+        field.r#type() == Type::Message
+            && self
+                .descriptors
+                .message(field.type_name())
+                .map(|entry| entry.options.as_ref().map_or(false, |o| o.map_entry()))
+                .unwrap_or(false)
+    }
+
+    fn encode_field(
+        &self,
+        field: &FieldDescriptorProto,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), ProtobufEncodeError> {
+        let tag = field.number() as u32;
+        match field.r#type() {
+            Type::Double => {
+                encode_key(tag, WireType::SixtyFourBit, buf);
+                buf.put_f64_le(float(field, value)?);
+            }
+            Type::Float => {
+                encode_key(tag, WireType::ThirtyTwoBit, buf);
+                buf.put_f32_le(float(field, value)? as f32);
+            }
+            Type::Int64 | Type::Int32 => {
+                encode_key(tag, WireType::Varint, buf);
+                encode_varint(integer(field, value)? as u64, buf);
+            }
+            Type::Uint64 | Type::Uint32 => {
+                encode_key(tag, WireType::Varint, buf);
+                encode_varint(unsigned(field, value)?, buf);
+            }
+            Type::Sint64 => {
+                let n = integer(field, value)?;
+                encode_key(tag, WireType::Varint, buf);
+                encode_varint(((n << 1) ^ (n >> 63)) as u64, buf);
+            }
+            Type::Sint32 => {
+                let n = integer(field, value)? as i32;
+                encode_key(tag, WireType::Varint, buf);
+                encode_varint(((n << 1) ^ (n >> 31)) as u32 as u64, buf);
+            }
+            Type::Fixed64 => {
+                encode_key(tag, WireType::SixtyFourBit, buf);
+                buf.put_u64_le(unsigned(field, value)?);
+            }
+            Type::Fixed32 => {
+                encode_key(tag, WireType::ThirtyTwoBit, buf);
+                buf.put_u32_le(unsigned(field, value)? as u32);
+            }
+            Type::Sfixed64 => {
+                encode_key(tag, WireType::SixtyFourBit, buf);
+                buf.put_i64_le(integer(field, value)?);
+            }
+            Type::Sfixed32 => {
+                encode_key(tag, WireType::ThirtyTwoBit, buf);
+                buf.put_i32_le(integer(field, value)? as i32);
+            }
+            Type::Bool => match value {
+                Value::Boolean(b) => {
+                    encode_key(tag, WireType::Varint, buf);
+                    encode_varint(*b as u64, buf);
+                }
+                value => return mismatched(field, "a boolean", value),
+            },
+            Type::Enum => {
+                let number = match value {
+                    Value::Integer(number) => *number,
+                    Value::Bytes(name) => {
+                        let name = String::from_utf8_lossy(name);
+                        self.descriptors
+                            .enumeration(field.type_name())?
+                            .value
+                            .iter()
+                            .find(|value| value.name() == name)
+                            .map(|value| value.number() as i64)
+                            .ok_or_else(|| ProtobufEncodeError::UnknownEnumValue {
+                                field: field.name().to_owned(),
+                                value: name.into_owned(),
+                            })?
+                    }
+                    value => return mismatched(field, "an enum name or number", value),
+                };
+                encode_key(tag, WireType::Varint, buf);
+                encode_varint(number as u64, buf);
+            }
+            Type::String | Type::Bytes => match value {
+                Value::Bytes(bytes) => encode_length_delimited(tag, bytes, buf),
+                Value::Timestamp(_) if field.r#type() == Type::String => {
+                    encode_length_delimited(tag, value.to_string_lossy().as_bytes(), buf)
+                }
+                value => return mismatched(field, "a string", value),
+            },
+            Type::Message => {
+                let mut nested = Vec::new();
+                match value {
+                    Value::Map(fields) => {
+                        let message = self.descriptors.message(field.type_name())?;
+                        self.encode_message(message, fields, &mut nested)?;
+                    }
+                    Value::Timestamp(ts)
+                        if field.type_name().trim_start_matches('.') == TIMESTAMP_TYPE =>
+                    {
+                        encode_timestamp(ts, &mut nested);
+                    }
+                    value => return mismatched(field, "a map", value),
+                }
+                encode_length_delimited(tag, &nested, buf);
+            }
+            Type::Group => {
+                return Err(ProtobufEncodeError::UnsupportedGroup {
+                    field: field.name().to_owned(),
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+fn encode_length_delimited(tag: u32, bytes: &[u8], buf: &mut Vec<u8>) {
+    encode_key(tag, WireType::LengthDelimited, buf);
+    encode_varint(bytes.len() as u64, buf);
+    buf.extend_from_slice(bytes);
+}
+
+fn encode_timestamp(ts: &DateTime<Utc>, buf: &mut Vec<u8>) {
+    prost_types::Timestamp {
+        seconds: ts.timestamp(),
+        nanos: ts.timestamp_subsec_nanos() as i32,
+    }
+    .encode(buf)
+    .expect("Vec grows to fit the message.");
+}
+
+fn mismatched<T>(
+    field: &FieldDescriptorProto,
+    expected: &'static str,
+    value: &Value,
+) -> Result<T, ProtobufEncodeError> {
+    Err(ProtobufEncodeError::MismatchedType {
+        field: field.name().to_owned(),
+        expected,
+        value: value.clone(),
+    })
+}
+
+fn float(field: &FieldDescriptorProto, value: &Value) -> Result<f64, ProtobufEncodeError> {
+    match value {
+        Value::Float(f) => Ok(*f),
+        Value::Integer(i) => Ok(*i as f64),
+        value => mismatched(field, "a number", value),
+    }
+}
+
+fn integer(field: &FieldDescriptorProto, value: &Value) -> Result<i64, ProtobufEncodeError> {
+    match value {
+        Value::Integer(i) => Ok(*i),
+        value => mismatched(field, "an integer", value),
+    }
+}
+
+fn unsigned(field: &FieldDescriptorProto, value: &Value) -> Result<u64, ProtobufEncodeError> {
+    match value {
+        Value::Integer(i) if *i >= 0 => Ok(*i as u64),
+        value => mismatched(field, "a non-negative integer", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use prost_types::{EnumValueDescriptorProto, FileDescriptorProto};
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Request {
+        #[prost(string, tag = "1")]
+        message: String,
+        #[prost(int64, tag = "2")]
+        status: i64,
+        #[prost(sint32, tag = "3")]
+        delta: i32,
+        #[prost(string, repeated, tag = "4")]
+        tags: Vec<String>,
+        #[prost(message, optional, tag = "5")]
+        timestamp: Option<prost_types::Timestamp>,
+        #[prost(message, optional, tag = "6")]
+        client: Option<Client>,
+        #[prost(enumeration = "Method", tag = "7")]
+        method: i32,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Client {
+        #[prost(string, tag = "1")]
+        ip: String,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, prost::Enumeration)]
+    enum Method {
+        Get = 0,
+        Post = 1,
+    }
+
+    fn field(
+        name: &str,
+        number: i32,
+        r#type: Type,
+        type_name: Option<&str>,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            type_name: type_name.map(Into::into),
+            ..Default::default()
+        }
+    }
+
+    fn descriptor_set() -> FileDescriptorSet {
+        let mut tags = field("tags", 4, Type::String, None);
+        tags.label = Some(Label::Repeated as i32);
+
+        let request = DescriptorProto {
+            name: Some("Request".to_owned()),
+            field: vec![
+                field("message", 1, Type::String, None),
+                field("status", 2, Type::Int64, None),
+                field("delta", 3, Type::Sint32, None),
+                tags,
+                field(
+                    "timestamp",
+                    5,
+                    Type::Message,
+                    Some(".google.protobuf.Timestamp"),
+                ),
+                field("client", 6, Type::Message, Some(".test.Request.Client")),
+                field("method", 7, Type::Enum, Some(".test.Method")),
+            ],
+            nested_type: vec![DescriptorProto {
+                name: Some("Client".to_owned()),
+                field: vec![field("ip", 1, Type::String, None)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let method = EnumDescriptorProto {
+            name: Some("Method".to_owned()),
+            value: vec![
+                EnumValueDescriptorProto {
+                    name: Some("GET".to_owned()),
+                    number: Some(0),
+                    ..Default::default()
+                },
+                EnumValueDescriptorProto {
+                    name: Some("POST".to_owned()),
+                    number: Some(1),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let timestamp = DescriptorProto {
+            name: Some("Timestamp".to_owned()),
+            field: vec![
+                field("seconds", 1, Type::Int64, None),
+                field("nanos", 2, Type::Int32, None),
+            ],
+            ..Default::default()
+        };
+
+        FileDescriptorSet {
+            file: vec![
+                FileDescriptorProto {
+                    package: Some("test".to_owned()),
+                    message_type: vec![request],
+                    enum_type: vec![method],
+                    ..Default::default()
+                },
+                FileDescriptorProto {
+                    package: Some("google.protobuf".to_owned()),
+                    message_type: vec![timestamp],
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    fn encoder(message_type: &str) -> crate::Result<ProtobufEncoder> {
+        let mut bytes = Vec::new();
+        descriptor_set().encode(&mut bytes).unwrap();
+        let path = crate::test_util::temp_file();
+        std::fs::write(&path, bytes).unwrap();
+
+        ProtobufOptions {
+            descriptor_set_path: path,
+            message_type: message_type.to_owned(),
+        }
+        .build()
+    }
+
+    #[test]
+    fn encodes_logs_as_messages() {
+        let encoder = encoder("test.Request").unwrap();
+        let timestamp = Utc::now();
+
+        let mut event = Event::from("hello");
+        let log = event.as_mut_log();
+        log.insert("status", 404);
+        log.insert("delta", -2);
+        log.insert("tags", vec!["a", "b"]);
+        log.insert("timestamp", timestamp);
+        log.insert("client.ip", "10.0.0.1");
+        log.insert("method", "POST");
+        log.insert("ignored", true);
+
+        let request = Request::decode(&encoder.encode(log).unwrap()[..]).unwrap();
+        assert_eq!(
+            request,
+            Request {
+                message: "hello".to_owned(),
+                status: 404,
+                delta: -2,
+                tags: vec!["a".to_owned(), "b".to_owned()],
+                timestamp: Some(prost_types::Timestamp {
+                    seconds: timestamp.timestamp(),
+                    nanos: timestamp.timestamp_subsec_nanos() as i32,
+                }),
+                client: Some(Client {
+                    ip: "10.0.0.1".to_owned(),
+                }),
+                method: Method::Post as i32,
+            }
+        );
+    }
+
+    #[test]
+    fn drops_logs_that_dont_match_the_message_type() {
+        let encoder = encoder("test.Request").unwrap();
+
+        let mut fields = BTreeMap::new();
+        fields.insert("status".to_owned(), Value::from("not a number"));
+        assert!(encoder.encode(&LogEvent::from(fields)).is_none());
+
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("method", "DELETE");
+        assert!(encoder.encode(event.as_log()).is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_message_types() {
+        assert!(encoder("test.Response").is_err());
+    }
+}
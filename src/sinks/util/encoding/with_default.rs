@@ -1,7 +1,8 @@
 use crate::{
     event::{PathComponent, PathIter},
     serde::skip_serializing_if_default,
-    sinks::util::encoding::{EncodingConfiguration, TimestampFormat},
+    sinks::util::encoding::{CsvOptions, EncodingConfiguration, ProtobufOptions, TimestampFormat},
+    template::Template,
 };
 use serde::{
     de::{self, DeserializeOwned, Deserializer, IntoDeserializer, MapAccess, Visitor},
@@ -34,6 +35,15 @@ pub struct EncodingConfigWithDefault<E: Default + PartialEq> {
     /// Format for outgoing timestamps.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     pub(crate) timestamp_format: Option<TimestampFormat>,
+    /// Options of the `csv` codec.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) csv: Option<CsvOptions>,
+    /// Options of the `protobuf` codec.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) protobuf: Option<ProtobufOptions>,
+    /// Template of each line of the `text` codec, instead of the message.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) template: Option<Template>,
 }
 
 impl<E: Default + PartialEq> EncodingConfiguration<E> for EncodingConfigWithDefault<E> {
@@ -53,6 +63,15 @@ impl<E: Default + PartialEq> EncodingConfiguration<E> for EncodingConfigWithDefa
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+    fn csv(&self) -> &Option<CsvOptions> {
+        &self.csv
+    }
+    fn protobuf(&self) -> &Option<ProtobufOptions> {
+        &self.protobuf
+    }
+    fn template(&self) -> &Option<Template> {
+        &self.template
+    }
 }
 
 impl<E> From<E> for EncodingConfigWithDefault<E>
@@ -66,6 +85,9 @@ where
             only_fields: Default::default(),
             except_fields: Default::default(),
             timestamp_format: Default::default(),
+            csv: Default::default(),
+            protobuf: Default::default(),
+            template: Default::default(),
         }
     }
 }
@@ -109,6 +131,9 @@ where
                     only_fields: Default::default(),
                     except_fields: Default::default(),
                     timestamp_format: Default::default(),
+                    csv: Default::default(),
+                    protobuf: Default::default(),
+                    template: Default::default(),
                 })
             }
 
@@ -138,6 +163,9 @@ where
             }),
             except_fields: inner.except_fields,
             timestamp_format: inner.timestamp_format,
+            csv: inner.csv,
+            protobuf: inner.protobuf,
+            template: inner.template,
         };
 
         concrete.validate().map_err(de::Error::custom)?;
@@ -157,4 +185,10 @@ pub struct InnerWithDefault<E: Default> {
     except_fields: Option<Vec<String>>,
     #[serde(default)]
     timestamp_format: Option<TimestampFormat>,
+    #[serde(default)]
+    csv: Option<CsvOptions>,
+    #[serde(default)]
+    protobuf: Option<ProtobufOptions>,
+    #[serde(default)]
+    template: Option<Template>,
 }
@@ -3,10 +3,13 @@ pub mod batch;
 pub mod buffer;
 pub mod encoding;
 pub mod http;
+pub mod request_limits;
 pub mod retries;
 pub mod service;
 pub mod sink;
 pub mod socket_bytes_sink;
+#[cfg(feature = "sinks-vector")]
+pub mod spillover;
 pub mod statistic;
 pub mod tcp;
 #[cfg(test)]
@@ -0,0 +1,253 @@
+//! Limits on the requests of a running sink that can be tuned through the API,
+//! without a reload, such as to back off a struggling backend right away.
+//!
+//! Sinks are built within `building`, which registers the limits of their
+//! request services under the sink's name. Tuned limits apply until the sink
+//! is rebuilt, which resets them to the configured values.
+
+use futures::ready;
+use lazy_static::lazy_static;
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{delay_until, Delay, Instant};
+use tower::{Layer, Service};
+
+tokio::task_local! {
+    static BUILDING_SINK: String;
+}
+
+lazy_static! {
+    static ref REGISTRY: Mutex<HashMap<String, Weak<RequestLimits>>> = Default::default();
+}
+
+/// Builds the sink `name` with `future`, registering the limits of its request
+/// services. Limits previously registered under the name are discarded.
+pub async fn building<F: Future>(name: &str, future: F) -> F::Output {
+    REGISTRY
+        .lock()
+        .expect("Request limits mutex is poisoned")
+        .remove(name);
+    BUILDING_SINK.scope(name.to_owned(), future).await
+}
+
+/// The limits of the running sink `name`, if it has request services.
+pub fn find(name: &str) -> Option<Arc<RequestLimits>> {
+    REGISTRY
+        .lock()
+        .expect("Request limits mutex is poisoned")
+        .get(name)
+        .and_then(Weak::upgrade)
+}
+
+#[derive(Debug)]
+pub struct RequestLimits {
+    configured_rate_limit_num: u64,
+    rate_limit_num: AtomicU64,
+    /// 0 when the concurrency isn't capped.
+    max_concurrency: AtomicUsize,
+}
+
+impl RequestLimits {
+    /// The limits shared by the request services of the sink being built, or
+    /// unregistered limits outside of `building`.
+    pub(super) fn for_current_sink(rate_limit_num: u64) -> Arc<Self> {
+        let new = || {
+            Arc::new(Self {
+                configured_rate_limit_num: rate_limit_num,
+                rate_limit_num: AtomicU64::new(rate_limit_num),
+                max_concurrency: AtomicUsize::new(0),
+            })
+        };
+
+        BUILDING_SINK
+            .try_with(|name| {
+                let mut registry = REGISTRY.lock().expect("Request limits mutex is poisoned");
+                registry.retain(|_, limits| limits.strong_count() > 0);
+                match registry.get(name).and_then(Weak::upgrade) {
+                    Some(limits) => limits,
+                    None => {
+                        let limits = new();
+                        registry.insert(name.clone(), Arc::downgrade(&limits));
+                        limits
+                    }
+                }
+            })
+            .unwrap_or_else(|_| new())
+    }
+
+    pub fn rate_limit_num(&self) -> u64 {
+        self.rate_limit_num.load(Ordering::Relaxed)
+    }
+
+    /// `None` restores the configured rate limit.
+    pub fn set_rate_limit_num(&self, num: Option<u64>) {
+        let num = num.unwrap_or(self.configured_rate_limit_num).max(1);
+        self.rate_limit_num.store(num, Ordering::Relaxed);
+    }
+
+    pub fn max_concurrency(&self) -> Option<usize> {
+        match self.max_concurrency.load(Ordering::Relaxed) {
+            0 => None,
+            max => Some(max),
+        }
+    }
+
+    /// Caps the concurrency, whether fixed or adaptive. `None` lifts the cap.
+    pub fn set_max_concurrency(&self, max: Option<usize>) {
+        let max = max.map_or(0, |max| max.max(1));
+        self.max_concurrency.store(max, Ordering::Relaxed);
+    }
+}
+
+/// Like `tower::limit::RateLimit`, but reads the number of requests allowed
+/// per period from `RequestLimits` at the start of each period.
+#[derive(Debug)]
+pub struct RateLimit<S> {
+    inner: S,
+    limits: Arc<RequestLimits>,
+    per: Duration,
+    state: State,
+}
+
+#[derive(Debug)]
+enum State {
+    Limited(Delay),
+    Ready { until: Instant, rem: u64 },
+}
+
+impl<S> RateLimit<S> {
+    fn new(inner: S, limits: Arc<RequestLimits>, per: Duration) -> Self {
+        let state = State::Ready {
+            until: Instant::now(),
+            rem: limits.rate_limit_num(),
+        };
+        Self {
+            inner,
+            limits,
+            per,
+            state,
+        }
+    }
+}
+
+impl<S, Request> Service<Request> for RateLimit<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let State::Limited(ref mut delay) = self.state {
+            ready!(Pin::new(delay).poll(cx));
+            self.state = State::Ready {
+                until: Instant::now() + self.per,
+                rem: self.limits.rate_limit_num(),
+            };
+        }
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        match self.state {
+            State::Ready { mut until, mut rem } => {
+                let now = Instant::now();
+                if now >= until {
+                    until = now + self.per;
+                    rem = self.limits.rate_limit_num();
+                }
+
+                self.state = if rem > 1 {
+                    State::Ready {
+                        until,
+                        rem: rem - 1,
+                    }
+                } else {
+                    State::Limited(delay_until(until))
+                };
+                self.inner.call(request)
+            }
+            State::Limited(_) => panic!("Rate limit reached; poll_ready must be called first"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limits: Arc<RequestLimits>,
+    per: Duration,
+}
+
+impl RateLimitLayer {
+    pub fn new(limits: Arc<RequestLimits>, per: Duration) -> Self {
+        Self { limits, per }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit::new(inner, Arc::clone(&self.limits), self.per)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_test::mock;
+
+    #[tokio::test]
+    async fn limits_are_shared_by_name() {
+        let (first, second) = building("tuned", async {
+            (
+                RequestLimits::for_current_sink(10),
+                RequestLimits::for_current_sink(10),
+            )
+        })
+        .await;
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Arc::ptr_eq(&first, &find("tuned").unwrap()));
+
+        first.set_rate_limit_num(Some(2));
+        assert_eq!(second.rate_limit_num(), 2);
+        first.set_rate_limit_num(None);
+        assert_eq!(second.rate_limit_num(), 10);
+
+        let rebuilt = building("tuned", async { RequestLimits::for_current_sink(5) }).await;
+        assert!(Arc::ptr_eq(&rebuilt, &find("tuned").unwrap()));
+        assert!(!Arc::ptr_eq(&first, &rebuilt));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_follows_tuning() {
+        tokio::time::pause();
+
+        let limits = RequestLimits::for_current_sink(1);
+        let layer = RateLimitLayer::new(Arc::clone(&limits), Duration::from_secs(1));
+        let (mut service, mut handle) = mock::spawn_layer::<(), (), _>(layer);
+        handle.allow(10);
+
+        assert!(service.poll_ready().is_ready());
+        let _ = service.call(());
+        assert!(service.poll_ready().is_pending());
+
+        limits.set_rate_limit_num(Some(3));
+        tokio::time::advance(Duration::from_secs(1)).await;
+        for _ in 0..3 {
+            assert!(service.poll_ready().is_ready());
+            let _ = service.call(());
+        }
+        assert!(service.poll_ready().is_pending());
+    }
+}
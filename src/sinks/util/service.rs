@@ -2,6 +2,7 @@ use super::{
     adaptive_concurrency::{
         AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
     },
+    request_limits::{RateLimit, RateLimitLayer, RequestLimits},
     retries::{FixedRetryPolicy, RetryLogic},
     sink::Response,
     Batch, BatchSink, Partition, PartitionBatchSink,
@@ -14,7 +15,6 @@ use serde::{
 use std::{fmt, hash::Hash, sync::Arc, task::Poll, time::Duration};
 use tower::{
     layer::{util::Stack, Layer},
-    limit::RateLimit,
     retry::Retry,
     timeout::Timeout,
     util::BoxService,
@@ -310,14 +310,21 @@ impl TowerRequestSettings {
         Request: Send + Clone + 'static,
     {
         let policy = self.retry_policy(retry_logic.clone());
+        let limits = RequestLimits::for_current_sink(self.rate_limit_num);
         ServiceBuilder::new()
-            .rate_limit(self.rate_limit_num, self.rate_limit_duration)
-            .retry(policy)
-            .layer(AdaptiveConcurrencyLimitLayer::new(
-                self.concurrency,
-                self.adaptive_concurrency,
-                retry_logic,
+            .layer(RateLimitLayer::new(
+                Arc::clone(&limits),
+                self.rate_limit_duration,
             ))
+            .retry(policy)
+            .layer(
+                AdaptiveConcurrencyLimitLayer::new(
+                    self.concurrency,
+                    self.adaptive_concurrency,
+                    retry_logic,
+                )
+                .with_limits(limits),
+            )
             .timeout(self.timeout)
             .service(service)
     }
@@ -36,7 +36,7 @@ use super::{
     buffer::{Partition, PartitionBuffer, PartitionInnerBuffer},
     service::{Map, ServiceBuilderExt},
 };
-use crate::{buffers::Acker, Event};
+use crate::{buffers::Acker, event::EventStatus, Event};
 use async_trait::async_trait;
 use futures::{
     future::BoxFuture,
@@ -360,11 +360,11 @@ where
 
 struct ServiceSink<S, Request> {
     service: S,
-    in_flight: FuturesUnordered<oneshot::Receiver<(usize, usize)>>,
+    in_flight: FuturesUnordered<oneshot::Receiver<(usize, usize, EventStatus)>>,
     acker: Acker,
     seq_head: usize,
     seq_tail: usize,
-    pending_acks: HashMap<usize, usize>,
+    pending_acks: HashMap<usize, (usize, EventStatus)>,
     next_request_id: usize,
     _pd: PhantomData<Request>,
 }
@@ -412,22 +412,27 @@ where
             .call(req)
             .err_into()
             .map(move |result| {
-                match result {
+                // Requests that failed after their retries may succeed when sent again, while
+                // responses that weren't successful rejected the events.
+                let status = match result {
                     Ok(response) if response.is_successful() => {
                         trace!(message = "Response successful.", ?response);
+                        EventStatus::Delivered
                     }
                     Ok(response) => {
                         error!(message = "Response wasn't successful.", ?response);
+                        EventStatus::Failed
                     }
                     Err(error) => {
                         error!(message = "Request failed.", %error);
+                        EventStatus::Errored
                     }
-                }
+                };
 
                 // If the rx end is dropped we still completed
                 // the request so this is a weird case that we can
                 // ignore for now.
-                let _ = tx.send((seqno, batch_size));
+                let _ = tx.send((seqno, batch_size, status));
             })
             .instrument(info_span!("request", %request_id))
             .boxed()
@@ -436,16 +441,14 @@ where
     fn poll_complete(&mut self, cx: &mut Context<'_>) -> Poll<()> {
         while !self.in_flight.is_empty() {
             match ready!(Pin::new(&mut self.in_flight).poll_next(cx)) {
-                Some(Ok((seqno, batch_size))) => {
-                    self.pending_acks.insert(seqno, batch_size);
+                Some(Ok((seqno, batch_size, status))) => {
+                    self.pending_acks.insert(seqno, (batch_size, status));
 
-                    let mut num_to_ack = 0;
-                    while let Some(ack_size) = self.pending_acks.remove(&self.seq_tail) {
-                        num_to_ack += ack_size;
+                    while let Some((ack_size, status)) = self.pending_acks.remove(&self.seq_tail) {
+                        trace!(message = "Acking events.", acking_num = ack_size, ?status);
+                        self.acker.ack_with_status(ack_size, status);
                         self.seq_tail += 1
                     }
-                    trace!(message = "Acking events.", acking_num = num_to_ack);
-                    self.acker.ack(num_to_ack);
                 }
                 Some(Err(_)) => panic!("ServiceSink service sender dropped."),
                 None => break,
@@ -487,6 +490,7 @@ mod tests {
     use super::*;
     use crate::{
         buffers::Acker,
+        event::{BatchNotifier, EventFinalizers},
         sinks::util::{BatchSettings, EncodedLength, VecBuffer},
         test_util::trace_init,
     };
@@ -913,6 +917,37 @@ mod tests {
         assert_eq!(ack_counter.load(Relaxed), 10);
     }
 
+    #[tokio::test]
+    async fn service_sink_reports_the_status_of_requests() {
+        let (first, first_receiver) = BatchNotifier::new_with_receiver();
+        let (second, second_receiver) = BatchNotifier::new_with_receiver();
+        let pending = vec![EventFinalizers::new(first), EventFinalizers::new(second)];
+        let acker = Acker::Finalizers(Arc::new(Mutex::new(pending.into_iter().collect())));
+
+        let svc = tower::service_fn(|req: u8| {
+            if req == 1 {
+                future::err("bad")
+            } else {
+                future::ok("good")
+            }
+        });
+        let mut sink = ServiceSink::new(svc, acker);
+
+        let mut cx = Context::from_waker(noop_waker_ref());
+        assert!(matches!(
+            sink.call(1, 1).poll_unpin(&mut cx),
+            Poll::Ready(())
+        ));
+        assert!(matches!(
+            sink.call(2, 1).poll_unpin(&mut cx),
+            Poll::Ready(())
+        ));
+        assert!(matches!(sink.poll_complete(&mut cx), Poll::Ready(())));
+
+        assert_eq!(first_receiver.await, EventStatus::Errored);
+        assert_eq!(second_receiver.await, EventStatus::Delivered);
+    }
+
     #[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
     enum Partitions {
         A,
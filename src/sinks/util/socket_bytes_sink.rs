@@ -1,5 +1,6 @@
 use crate::{
     buffers::Acker,
+    event::EventStatus,
     internal_events::{SocketEventsSent, SocketMode},
 };
 use bytes::Bytes;
@@ -28,7 +29,8 @@ pub enum ShutdownCheck {
 /// - Call `shutdown_check` on each `poll_flush`, so we can stop sending data if other side disconnected.
 /// - Flush all data on each `poll_ready` if total number of events in queue more than some limit.
 /// - Count event size on each `start_send`.
-/// - Ack all sent events on successful `poll_flush` and `poll_close` or on `Drop`.
+/// - Ack all sent events on `poll_flush` and `poll_close`, as delivered when they succeed,
+///   or as errored on `Drop`.
 #[pin_project(PinnedDrop)]
 pub struct BytesSink<T>
 where
@@ -63,15 +65,24 @@ where
         }
     }
 
-    fn ack(&mut self) {
-        if self.events_total > 0 {
-            self.acker.ack(self.events_total);
+    /// Acknowledges the events sent from now on with `acker`, rather than the
+    /// one the sink was created with.
+    pub(crate) fn set_acker(&mut self, acker: Acker) {
+        self.ack(EventStatus::Delivered);
+        self.acker = acker;
+    }
 
-            emit!(SocketEventsSent {
-                mode: self.socket_mode,
-                count: self.events_total as u64,
-                byte_size: self.bytes_total,
-            });
+    fn ack(&mut self, status: EventStatus) {
+        if self.events_total > 0 {
+            self.acker.ack_with_status(self.events_total, status);
+
+            if status == EventStatus::Delivered {
+                emit!(SocketEventsSent {
+                    mode: self.socket_mode,
+                    count: self.events_total as u64,
+                    byte_size: self.bytes_total,
+                });
+            }
 
             self.events_total = 0;
             self.bytes_total = 0;
@@ -85,7 +96,7 @@ where
     T: AsyncWrite + Unpin,
 {
     fn drop(self: Pin<&mut Self>) {
-        self.get_mut().ack()
+        self.get_mut().ack(EventStatus::Errored)
     }
 }
 
@@ -127,13 +138,20 @@ where
         }
 
         let result = ready!(self.as_mut().project().inner.poll_flush(cx));
-        self.as_mut().get_mut().ack();
+        self.as_mut().get_mut().ack(status_of(&result));
         Poll::Ready(result)
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         let result = ready!(self.as_mut().project().inner.poll_close(cx));
-        self.as_mut().get_mut().ack();
+        self.as_mut().get_mut().ack(status_of(&result));
         Poll::Ready(result)
     }
 }
+
+fn status_of<E>(result: &Result<(), E>) -> EventStatus {
+    match result {
+        Ok(()) => EventStatus::Delivered,
+        Err(_) => EventStatus::Errored,
+    }
+}
@@ -0,0 +1,464 @@
+//! A disk queue that stream sinks spill to while their destination is
+//! unreachable, and drain once it's back.
+//!
+//! Spilled frames are batched in memory into segments, which are compressed
+//! with zstd and written to their own file. Segments are named after their
+//! sequence number and the number of frames they hold, so that the queue can be
+//! resumed after a restart. The queue is bounded by the total size of its
+//! segments, and the oldest segments are dropped to make room for new ones.
+//!
+//! The queue of a directory is shared by the sinks using it, so that a sink
+//! replacing another on reload takes over its queue once the other is done
+//! with it, rather than both writing to the same directory.
+
+use crate::{
+    buffers::Acker,
+    config::GlobalOptions,
+    event::EventStatus,
+    internal_events::{
+        SpilloverError, SpilloverSegmentDrained, SpilloverSegmentDropped, SpilloverSegmentWritten,
+    },
+};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    path::PathBuf,
+    sync::{Arc, Weak},
+};
+use tokio::{
+    fs,
+    sync::{Mutex, MutexGuard},
+    time::Delay,
+};
+
+lazy_static! {
+    /// The queues in use, by directory.
+    static ref QUEUES: std::sync::Mutex<HashMap<PathBuf, Weak<Mutex<Queue>>>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SpilloverConfig {
+    pub data_dir: Option<PathBuf>,
+    #[serde(default = "default_max_size")]
+    pub max_size: u64,
+    #[serde(default = "default_max_segment_size")]
+    pub max_segment_size: usize,
+    #[serde(default = "default_compression_level")]
+    pub compression_level: i32,
+}
+
+fn default_max_size() -> u64 {
+    1024 * 1024 * 1024
+}
+
+fn default_max_segment_size() -> usize {
+    4 * 1024 * 1024
+}
+
+fn default_compression_level() -> i32 {
+    3
+}
+
+impl SpilloverConfig {
+    /// Opens the queue in `subdir` of the data directory, with the segments
+    /// left by a previous run, or shares it with the sinks already using it.
+    pub fn build(
+        &self,
+        globals: &GlobalOptions,
+        subdir: &str,
+        acker: Acker,
+    ) -> crate::Result<Spillover> {
+        let dir = globals.resolve_and_make_data_subdir(self.data_dir.as_ref(), subdir)?;
+
+        let mut queues = QUEUES.lock().unwrap();
+        queues.retain(|_, queue| queue.strong_count() > 0);
+        let queue = match queues.get(&dir).and_then(Weak::upgrade) {
+            Some(queue) => queue,
+            None => {
+                let queue = Arc::new(Mutex::new(Queue::open(dir.clone())?));
+                queues.insert(dir, Arc::downgrade(&queue));
+                queue
+            }
+        };
+
+        Ok(Spillover {
+            queue,
+            config: self.clone(),
+            acker,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    path: PathBuf,
+    seq: u64,
+    frames: usize,
+    size: u64,
+}
+
+impl Segment {
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.strip_suffix(".zst")?;
+        let mut parts = name.splitn(2, '-');
+        let seq = parts.next()?.parse().ok()?;
+        let frames = parts.next()?.parse().ok()?;
+        let size = path.metadata().ok()?.len();
+        Some(Self {
+            path,
+            seq,
+            frames,
+            size,
+        })
+    }
+}
+
+struct Queue {
+    dir: PathBuf,
+    segments: VecDeque<Segment>,
+    next_seq: u64,
+    /// Total size of the segments on disk.
+    size: u64,
+    /// Length prefixed frames of the segment being filled.
+    open: BytesMut,
+    open_frames: usize,
+}
+
+impl Queue {
+    fn open(dir: PathBuf) -> io::Result<Self> {
+        let mut segments = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if let Some(segment) = Segment::from_path(path.clone()) {
+                segments.push(segment);
+            } else if path
+                .extension()
+                .map_or(false, |extension| extension == "tmp")
+            {
+                // Leftovers of a segment that wasn't fully written.
+                if let Err(error) = std::fs::remove_file(&path) {
+                    emit!(SpilloverError { error });
+                }
+            }
+        }
+        segments.sort_by_key(|segment| segment.seq);
+
+        Ok(Self {
+            dir,
+            next_seq: segments.last().map_or(0, |segment| segment.seq + 1),
+            size: segments.iter().map(|segment| segment.size).sum(),
+            segments: segments.into(),
+            open: BytesMut::new(),
+            open_frames: 0,
+        })
+    }
+}
+
+/// A sink's handle to its queue.
+pub struct Spillover {
+    queue: Arc<Mutex<Queue>>,
+    config: SpilloverConfig,
+    acker: Acker,
+}
+
+impl Spillover {
+    /// Takes the queue for a run of the sink, once the other sinks sharing it
+    /// are done with it.
+    pub async fn lock(&self) -> Spilling<'_> {
+        Spilling {
+            queue: self.queue.lock().await,
+            config: &self.config,
+            acker: &self.acker,
+        }
+    }
+}
+
+/// The queue, while a sink runs with it.
+pub struct Spilling<'a> {
+    queue: MutexGuard<'a, Queue>,
+    config: &'a SpilloverConfig,
+    acker: &'a Acker,
+}
+
+impl<'a> Spilling<'a> {
+    pub fn is_empty(&self) -> bool {
+        self.queue.segments.is_empty() && self.queue.open_frames == 0
+    }
+
+    /// Spills the frames of `input` until `deadline`. Returns `false` once
+    /// `input` is exhausted.
+    pub async fn spill_until<St>(&mut self, input: &mut St, mut deadline: Delay) -> bool
+    where
+        St: Stream<Item = Bytes> + Unpin,
+    {
+        loop {
+            tokio::select! {
+                _ = &mut deadline => return true,
+                frame = input.next() => match frame {
+                    Some(frame) => self.spill(frame).await,
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    async fn spill(&mut self, frame: Bytes) {
+        self.queue.open.put_u32(frame.len() as u32);
+        self.queue.open.put(frame);
+        self.queue.open_frames += 1;
+
+        if self.queue.open.len() >= self.config.max_segment_size {
+            self.seal().await;
+        }
+    }
+
+    /// Writes the segment being filled, if any, making room for it by dropping
+    /// the oldest segments. Its frames are acknowledged as delivered once
+    /// written, and as failed if they couldn't be, so that they don't hold up
+    /// the sink's buffer.
+    pub async fn seal(&mut self) {
+        if self.queue.open_frames == 0 {
+            return;
+        }
+
+        let frames = std::mem::replace(&mut self.queue.open_frames, 0);
+        let open = self.queue.open.split().freeze();
+        let status = match self.write(open, frames).await {
+            Ok(true) => EventStatus::Delivered,
+            Ok(false) => EventStatus::Failed,
+            Err(error) => {
+                emit!(SpilloverError { error });
+                EventStatus::Failed
+            }
+        };
+        self.acker.ack_with_status(frames, status);
+    }
+
+    /// Writes a segment, returning whether there was room for it.
+    async fn write(&mut self, open: Bytes, frames: usize) -> io::Result<bool> {
+        let compressed = zstd::encode_all(&open[..], self.config.compression_level)?;
+        let size = compressed.len() as u64;
+
+        while self.queue.size + size > self.config.max_size {
+            match self.queue.segments.pop_front() {
+                Some(oldest) => self.drop_segment(oldest).await?,
+                None => {
+                    emit!(SpilloverSegmentDropped { frames });
+                    return Ok(false);
+                }
+            }
+        }
+
+        let queue = &mut *self.queue;
+        let seq = queue.next_seq;
+        queue.next_seq += 1;
+        let path = queue.dir.join(format!("{:020}-{}.zst", seq, frames));
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, compressed).await?;
+        fs::rename(&tmp_path, &path).await?;
+
+        queue.size += size;
+        queue.segments.push_back(Segment {
+            path,
+            seq,
+            frames,
+            size,
+        });
+        emit!(SpilloverSegmentWritten {
+            frames,
+            byte_size: size,
+            spillover_size: queue.size,
+        });
+        Ok(true)
+    }
+
+    /// Drops a segment taken off the queue. It's no longer accounted for even if
+    /// its file couldn't be removed.
+    async fn drop_segment(&mut self, segment: Segment) -> io::Result<()> {
+        self.queue.size -= segment.size;
+        emit!(SpilloverSegmentDropped {
+            frames: segment.frames
+        });
+        fs::remove_file(&segment.path).await
+    }
+
+    /// The oldest written segment, and its frames.
+    pub async fn oldest(&mut self) -> Option<(Segment, Vec<Bytes>)> {
+        while let Some(segment) = self.queue.segments.front().cloned() {
+            match read_frames(&segment).await {
+                Ok(frames) => return Some((segment, frames)),
+                Err(error) => {
+                    emit!(SpilloverError { error });
+                    self.queue.segments.pop_front();
+                    if let Err(error) = self.drop_segment(segment).await {
+                        emit!(SpilloverError { error });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Removes a segment once its frames were sent.
+    pub async fn drained(&mut self, segment: Segment) {
+        let queue = &mut *self.queue;
+        queue.segments.retain(|other| other.seq != segment.seq);
+        queue.size -= segment.size;
+        if let Err(error) = fs::remove_file(&segment.path).await {
+            emit!(SpilloverError { error });
+        }
+        emit!(SpilloverSegmentDrained {
+            frames: segment.frames,
+            spillover_size: queue.size,
+        });
+    }
+}
+
+async fn read_frames(segment: &Segment) -> io::Result<Vec<Bytes>> {
+    let compressed = fs::read(&segment.path).await?;
+    let mut data = Bytes::from(zstd::decode_all(&compressed[..])?);
+
+    let mut frames = Vec::with_capacity(segment.frames);
+    while data.remaining() >= 4 {
+        let len = data.get_u32() as usize;
+        if data.remaining() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Truncated frame in spillover segment.",
+            ));
+        }
+        frames.push(data.split_to(len));
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{BatchNotifier, EventFinalizers};
+    use futures::stream;
+    use std::time::Duration;
+    use tokio::time::delay_for;
+
+    fn config(max_size: u64, max_segment_size: usize) -> SpilloverConfig {
+        SpilloverConfig {
+            data_dir: None,
+            max_size,
+            max_segment_size,
+            compression_level: default_compression_level(),
+        }
+    }
+
+    fn globals(dir: &tempfile::TempDir) -> GlobalOptions {
+        GlobalOptions {
+            data_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    fn frames(range: std::ops::Range<usize>) -> Vec<Bytes> {
+        range.map(|i| Bytes::from(format!("frame {}", i))).collect()
+    }
+
+    async fn spill(spilling: &mut Spilling<'_>, frames: Vec<Bytes>) {
+        let mut input = stream::iter(frames);
+        let deadline = delay_for(Duration::from_secs(60));
+        assert!(!spilling.spill_until(&mut input, deadline).await);
+        spilling.seal().await;
+    }
+
+    async fn drain(spilling: &mut Spilling<'_>) -> Vec<Bytes> {
+        let mut drained = Vec::new();
+        while let Some((segment, frames)) = spilling.oldest().await {
+            drained.extend(frames);
+            spilling.drained(segment).await;
+        }
+        drained
+    }
+
+    #[tokio::test]
+    async fn drains_segments_in_order_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(1024 * 1024, 64);
+
+        let spillover = config.build(&globals(&dir), "spill", Acker::Null).unwrap();
+        let mut spilling = spillover.lock().await;
+        spill(&mut spilling, frames(0..20)).await;
+        assert!(spilling.queue.segments.len() > 1);
+        drop(spilling);
+        drop(spillover);
+
+        // A segment that wasn't fully written.
+        std::fs::write(dir.path().join("spill/00000000000000000099-1.tmp"), "").unwrap();
+
+        let spillover = config.build(&globals(&dir), "spill", Acker::Null).unwrap();
+        let mut spilling = spillover.lock().await;
+        assert_eq!(drain(&mut spilling).await, frames(0..20));
+        assert!(spilling.is_empty());
+        assert_eq!(spilling.queue.size, 0);
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("spill")).unwrap().count(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn shares_queue_across_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(1024 * 1024, 64);
+
+        let old = config.build(&globals(&dir), "spill", Acker::Null).unwrap();
+        let mut spilling = old.lock().await;
+        spill(&mut spilling, frames(0..10)).await;
+
+        // The new sink is built while the old one still runs.
+        let new = config.build(&globals(&dir), "spill", Acker::Null).unwrap();
+        spill(&mut spilling, frames(10..20)).await;
+        drop(spilling);
+        drop(old);
+
+        let mut spilling = new.lock().await;
+        spill(&mut spilling, frames(20..30)).await;
+        assert_eq!(drain(&mut spilling).await, frames(0..30));
+    }
+
+    #[tokio::test]
+    async fn acks_dropped_frames_as_failed() {
+        let dir = tempfile::tempdir().unwrap();
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let pending = vec![EventFinalizers::new(batch)];
+        let acker = Acker::Finalizers(Arc::new(std::sync::Mutex::new(pending.into())));
+        // No room for any segment.
+        let spillover = config(1, 1).build(&globals(&dir), "spill", acker).unwrap();
+        let mut spilling = spillover.lock().await;
+
+        spill(&mut spilling, frames(0..1)).await;
+        assert!(spilling.is_empty());
+        assert_eq!(receiver.await, EventStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn drops_oldest_segments_when_full() {
+        let dir = tempfile::tempdir().unwrap();
+        // Room for two segments of a single frame.
+        let segment_size = zstd::encode_all(&b"\0\0\0\x07frame 0"[..], 3)
+            .unwrap()
+            .len();
+        let max_size = 2 * segment_size as u64;
+        let spillover = config(max_size, 1)
+            .build(&globals(&dir), "spill", Acker::Null)
+            .unwrap();
+        let mut spilling = spillover.lock().await;
+
+        spill(&mut spilling, frames(0..3)).await;
+        let (_, drained) = spilling.oldest().await.unwrap();
+        assert_eq!(drained, frames(1..2));
+        assert!(spilling.queue.size <= max_size);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "sinks-vector")]
+use crate::sinks::util::spillover::{Spilling, Spillover};
 use crate::{
     buffers::Acker,
     config::SinkContext,
@@ -84,11 +86,7 @@ impl TcpSinkConfig {
         cx: SinkContext,
         encode_event: impl Fn(Event) -> Option<Bytes> + Send + Sync + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
-        let uri = self.address.parse::<http::Uri>()?;
-        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
-        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
-        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
+        let connector = self.build_connector()?;
         let sink = TcpSink::new(connector.clone(), cx.acker(), encode_event);
 
         Ok((
@@ -96,6 +94,39 @@ impl TcpSinkConfig {
             Box::pin(async move { connector.healthcheck().await }),
         ))
     }
+
+    /// Like `build`, but events are spilled to `spillover` while the
+    /// destination is unreachable, rather than held up until it's back.
+    #[cfg(feature = "sinks-vector")]
+    pub fn build_with_spillover(
+        &self,
+        cx: SinkContext,
+        spillover: Option<Spillover>,
+        encode_event: impl Fn(Event) -> Option<Bytes> + Send + Sync + 'static,
+    ) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = self.build_connector()?;
+        let mut sink = TcpSink::new(connector.clone(), cx.acker(), encode_event);
+        sink.spillover = spillover;
+
+        Ok((
+            VectorSink::Stream(Box::new(sink)),
+            Box::pin(async move { connector.healthcheck().await }),
+        ))
+    }
+
+    fn build_connector(&self) -> crate::Result<TcpConnector> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        Ok(TcpConnector::new(
+            host,
+            port,
+            self.keepalive,
+            tls,
+            self.send_buffer_bytes,
+        ))
+    }
 }
 
 #[derive(Clone)]
@@ -192,6 +223,8 @@ impl TcpConnector {
 struct TcpSink {
     connector: TcpConnector,
     acker: Acker,
+    #[cfg(feature = "sinks-vector")]
+    spillover: Option<Spillover>,
     encode_event: Arc<dyn Fn(Event) -> Option<Bytes> + Send + Sync>,
 }
 
@@ -204,18 +237,95 @@ impl TcpSink {
         Self {
             connector,
             acker,
+            #[cfg(feature = "sinks-vector")]
+            spillover: None,
             encode_event: Arc::new(encode_event),
         }
     }
 
     async fn connect(&self) -> BytesSink<MaybeTlsStream<TcpStream>> {
         let stream = self.connector.connect_backoff().await;
-        BytesSink::new(
-            stream,
-            Self::shutdown_check,
-            self.acker.clone(),
-            SocketMode::Tcp,
-        )
+        self.bytes_sink(stream, self.acker.clone())
+    }
+
+    fn bytes_sink(
+        &self,
+        stream: MaybeTlsStream<TcpStream>,
+        acker: Acker,
+    ) -> BytesSink<MaybeTlsStream<TcpStream>> {
+        BytesSink::new(stream, Self::shutdown_check, acker, SocketMode::Tcp)
+    }
+
+    /// Sends the spilled events, oldest first. They were acknowledged when
+    /// spilled, so aren't acknowledged again.
+    #[cfg(feature = "sinks-vector")]
+    async fn drain(
+        &self,
+        spillover: &mut Spilling<'_>,
+        sink: &mut BytesSink<MaybeTlsStream<TcpStream>>,
+    ) -> Result<(), std::io::Error> {
+        spillover.seal().await;
+        while let Some((segment, frames)) = spillover.oldest().await {
+            sink.send_all(&mut futures::stream::iter(frames).map(Ok))
+                .await?;
+            spillover.drained(segment).await;
+        }
+        Ok(())
+    }
+
+    /// Like `run`, but rather than waiting for the destination to be
+    /// reachable, events are spilled until the next attempt to connect.
+    #[cfg(feature = "sinks-vector")]
+    async fn run_spilling(
+        &self,
+        spillover: &mut Spilling<'_>,
+        input: &mut (impl futures::Stream<Item = Bytes> + Unpin),
+    ) {
+        let mut input = input.peekable();
+        let mut backoff = TcpConnector::fresh_backoff();
+
+        loop {
+            if spillover.is_empty() && Pin::new(&mut input).peek().await.is_none() {
+                break;
+            }
+
+            let stream = match self.connector.connect().await {
+                Ok(stream) => stream,
+                Err(error) => {
+                    emit!(TcpSocketConnectionFailed { error });
+                    let retry = delay_for(backoff.next().unwrap());
+                    if spillover.spill_until(&mut input, retry).await {
+                        continue;
+                    } else {
+                        break;
+                    }
+                }
+            };
+            emit!(TcpSocketConnectionEstablished {
+                peer_addr: stream.peer_addr().ok(),
+            });
+            backoff = TcpConnector::fresh_backoff();
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            let mut sink = self.bytes_sink(stream, Acker::Null);
+            if let Err(error) = self.drain(spillover, &mut sink).await {
+                emit!(TcpSocketError { error });
+                continue;
+            }
+
+            sink.set_acker(self.acker.clone());
+            let result = match sink.send_all_peekable(&mut input).await {
+                Ok(()) => sink.close().await,
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(()) => break,
+                Err(error) => emit_send_error(error),
+            }
+        }
+
+        // Keep what's left for the next run.
+        spillover.seal().await;
     }
 
     fn shutdown_check(stream: &mut MaybeTlsStream<TcpStream>) -> ShutdownCheck {
@@ -241,15 +351,30 @@ impl TcpSink {
     }
 }
 
+fn emit_send_error(error: std::io::Error) {
+    if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
+        emit!(TcpSocketConnectionShutdown {});
+    } else {
+        emit!(TcpSocketError { error });
+    }
+}
+
 #[async_trait]
 impl StreamSink for TcpSink {
     async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encode_event = Arc::clone(&self.encode_event);
+        let mut input = input.map(|event| encode_event(event).unwrap_or_else(Bytes::new));
+
+        #[cfg(feature = "sinks-vector")]
+        if let Some(spillover) = self.spillover.take() {
+            let mut spilling = spillover.lock().await;
+            self.run_spilling(&mut spilling, &mut input).await;
+            return Ok(());
+        }
+
         // We need [Peekable](https://docs.rs/futures/0.3.6/futures/stream/struct.Peekable.html) for initiating
         // connection only when we have something to send.
-        let encode_event = Arc::clone(&self.encode_event);
-        let mut input = input
-            .map(|event| encode_event(event).unwrap_or_else(Bytes::new))
-            .peekable();
+        let mut input = input.peekable();
 
         while Pin::new(&mut input).peek().await.is_some() {
             let mut sink = self.connect().await;
@@ -261,11 +386,7 @@ impl StreamSink for TcpSink {
             };
 
             if let Err(error) = result {
-                if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
-                    emit!(TcpSocketConnectionShutdown {});
-                } else {
-                    emit!(TcpSocketError { error });
-                }
+                emit_send_error(error);
             }
         }
 
@@ -292,4 +413,61 @@ mod test {
         let bad = TcpConnector::from_host_port(addr.ip().to_string(), addr.port());
         assert!(bad.healthcheck().await.is_err());
     }
+
+    #[cfg(feature = "sinks-vector")]
+    #[tokio::test]
+    async fn spills_while_unreachable_and_drains_in_order() {
+        use crate::{config::GlobalOptions, sinks::util::spillover::SpilloverConfig};
+        use futures::channel::mpsc;
+        use tokio_util::codec::{FramedRead, LinesCodec};
+
+        trace_init();
+
+        let addr = next_addr();
+        let dir = tempfile::tempdir().unwrap();
+        let globals = GlobalOptions {
+            data_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let spillover = SpilloverConfig {
+            data_dir: None,
+            max_size: 1024 * 1024,
+            max_segment_size: 16,
+            compression_level: 3,
+        }
+        .build(&globals, "spill", Acker::Null)
+        .unwrap();
+        let connector = TcpConnector::from_host_port(addr.ip().to_string(), addr.port());
+        let sink = TcpSink::new(connector, Acker::Null, |_| None);
+
+        let lines = (0..6).map(|i| format!("line {}", i)).collect::<Vec<_>>();
+        let (tx, mut rx) = mpsc::unbounded();
+        for line in &lines[..3] {
+            tx.unbounded_send(Bytes::from(format!("{}\n", line)))
+                .unwrap();
+        }
+
+        let run = tokio::spawn(async move {
+            let mut spilling = spillover.lock().await;
+            sink.run_spilling(&mut spilling, &mut rx).await;
+            assert!(spilling.is_empty());
+        });
+
+        // The first attempt to connect fails, so the sink spills until the next one.
+        delay_for(Duration::from_millis(100)).await;
+        let mut listener = TcpListener::bind(&addr).await.unwrap();
+        for line in &lines[3..] {
+            tx.unbounded_send(Bytes::from(format!("{}\n", line)))
+                .unwrap();
+        }
+        drop(tx);
+
+        let (socket, _) = listener.accept().await.unwrap();
+        let received = FramedRead::new(socket, LinesCodec::new())
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        run.await.unwrap();
+        assert_eq!(received, lines);
+    }
 }
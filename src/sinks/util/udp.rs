@@ -5,6 +5,7 @@ use crate::{
     buffers::Acker,
     config::SinkContext,
     dns,
+    event::EventStatus,
     internal_events::{
         SocketEventsSent, SocketMode, UdpSendIncomplete, UdpSocketConnectionEstablished,
         UdpSocketConnectionFailed, UdpSocketError,
@@ -253,20 +254,25 @@ impl StreamSink for UdpSink {
         while Pin::new(&mut input).peek().await.is_some() {
             let mut socket = self.connector.connect_backoff().await;
             while let Some(event) = input.next().await {
-                self.acker.ack(1);
-
                 let bytes = match (self.encode_event)(event) {
                     Some(bytes) => bytes,
-                    None => continue,
+                    None => {
+                        self.acker.ack(1);
+                        continue;
+                    }
                 };
 
                 match udp_send(&mut socket, &bytes).await {
-                    Ok(()) => emit!(SocketEventsSent {
-                        mode: SocketMode::Udp,
-                        count: 1,
-                        byte_size: bytes.len(),
-                    }),
+                    Ok(()) => {
+                        self.acker.ack(1);
+                        emit!(SocketEventsSent {
+                            mode: SocketMode::Udp,
+                            count: 1,
+                            byte_size: bytes.len(),
+                        });
+                    }
                     Err(error) => {
+                        self.acker.ack_with_status(1, EventStatus::Errored);
                         emit!(UdpSocketError { error });
                         break;
                     }
@@ -1,7 +1,10 @@
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::proto,
-    sinks::util::tcp::TcpSinkConfig,
+    proto::VectorVersion,
+    sinks::util::{
+        spillover::SpilloverConfig, tcp::TcpSinkConfig, BatchConfig, TowerRequestConfig,
+    },
     tcp::TcpKeepaliveConfig,
     tls::TlsConfig,
     Event,
@@ -12,14 +15,24 @@ use prost::Message;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 
-#[derive(Deserialize, Serialize, Debug, Setters)]
+mod v2;
+
+#[derive(Clone, Deserialize, Serialize, Debug, Setters)]
 #[serde(deny_unknown_fields)]
 pub struct VectorSinkConfig {
+    #[serde(default)]
+    #[set = "pub"]
+    version: VectorVersion,
     address: String,
     keepalive: Option<TcpKeepaliveConfig>,
     #[set = "pub"]
     tls: Option<TlsConfig>,
     send_buffer_bytes: Option<usize>,
+    spillover: Option<SpilloverConfig>,
+    #[serde(default)]
+    batch: BatchConfig,
+    #[serde(default)]
+    request: TowerRequestConfig,
 }
 
 impl VectorSinkConfig {
@@ -30,10 +43,14 @@ impl VectorSinkConfig {
         send_buffer_bytes: Option<usize>,
     ) -> Self {
         Self {
+            version: VectorVersion::V1,
             address,
             keepalive,
             tls,
             send_buffer_bytes,
+            spillover: None,
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
         }
     }
 
@@ -67,6 +84,10 @@ impl SinkConfig for VectorSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if self.version == VectorVersion::V2 {
+            return v2::build(&self.address, &self.tls, self.batch, self.request, cx);
+        }
+
         let sink_config = TcpSinkConfig::new(
             self.address.clone(),
             self.keepalive,
@@ -74,7 +95,16 @@ impl SinkConfig for VectorSinkConfig {
             self.send_buffer_bytes,
         );
 
-        sink_config.build(cx, |event| Some(encode_event(event)))
+        let spillover = match &self.spillover {
+            Some(spillover) => Some(spillover.build(
+                cx.globals(),
+                &format!("{}_spillover", cx.name()),
+                cx.acker(),
+            )?),
+            None => None,
+        };
+
+        sink_config.build_with_spillover(cx, spillover, |event| Some(encode_event(event)))
     }
 
     fn input_type(&self) -> DataType {
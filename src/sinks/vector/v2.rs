@@ -0,0 +1,218 @@
+use crate::{
+    config::SinkContext,
+    event::{proto as event_proto, Event},
+    http::{HttpClient, HttpError},
+    internal_events::VectorEventsSent,
+    proto::vector as proto,
+    sinks::{
+        util::{
+            retries::RetryLogic, BatchConfig, BatchSettings, EncodedLength, TowerRequestConfig,
+            VecBuffer,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, SinkExt,
+};
+use http::Uri;
+use lazy_static::lazy_static;
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use std::task::{Context, Poll};
+use tonic::{body::BoxBody, Code, Status};
+use tower::Service;
+
+type Client = proto::vector_client::VectorClient<HyperService>;
+
+lazy_static! {
+    static ref REQUEST_DEFAULTS: TowerRequestConfig = TowerRequestConfig {
+        timeout_secs: Some(60),
+        rate_limit_num: Some(u64::max_value()),
+        ..Default::default()
+    };
+}
+
+#[derive(Debug, Snafu)]
+enum VectorSinkError {
+    #[snafu(display("Invalid address {:?}: {}", address, source))]
+    InvalidAddress {
+        address: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: Status },
+    #[snafu(display("Source isn't serving"))]
+    NotServing,
+}
+
+pub(super) fn build(
+    address: &str,
+    tls: &Option<TlsConfig>,
+    batch: BatchConfig,
+    request: TowerRequestConfig,
+    cx: SinkContext,
+) -> crate::Result<(VectorSink, Healthcheck)> {
+    let tls = MaybeTlsSettings::from_config(tls, false)?;
+    let uri = with_default_scheme(address, tls.is_tls())?;
+    let client = HttpClient::new_with_builder(tls, hyper::Client::builder().http2_only(true))?;
+    let client = proto::vector_client::VectorClient::new(HyperService { uri, client });
+
+    let healthcheck = healthcheck(client.clone()).boxed();
+
+    let batch = BatchSettings::default()
+        .bytes(10_000_000)
+        .events(1000)
+        .timeout(1)
+        .parse_config(batch)?;
+    let request = request.unwrap_with(&REQUEST_DEFAULTS);
+
+    let sink = request
+        .batch_sink(
+            VectorRetryLogic,
+            VectorService { client },
+            VecBuffer::new(batch.size),
+            batch.timeout,
+            cx.acker(),
+        )
+        .sink_map_err(|error| error!(message = "Fatal vector sink error.", %error))
+        .with(|event: Event| future::ok::<_, ()>(event_proto::EventWrapper::from(event)));
+
+    Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
+}
+
+fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
+    let uri = if address.contains("://") {
+        address.parse::<Uri>()
+    } else {
+        let scheme = if tls { "https" } else { "http" };
+        format!("{}://{}", scheme, address).parse::<Uri>()
+    };
+    Ok(uri.context(InvalidAddress { address })?)
+}
+
+async fn healthcheck(mut client: Client) -> crate::Result<()> {
+    let response = client
+        .health_check(proto::HealthCheckRequest {})
+        .await
+        .context(Request)?;
+
+    match response.into_inner().status() {
+        proto::ServingStatus::Serving => Ok(()),
+        proto::ServingStatus::NotServing => Err(VectorSinkError::NotServing.into()),
+    }
+}
+
+/// Sends the gRPC requests of the client, which only have a path, to the address of the source.
+#[derive(Clone, Debug)]
+struct HyperService {
+    uri: Uri,
+    client: HttpClient<BoxBody>,
+}
+
+impl Service<http::Request<BoxBody>> for HyperService {
+    type Response = http::Response<hyper::Body>;
+    type Error = HttpError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.client.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<BoxBody>) -> Self::Future {
+        let mut parts = self.uri.clone().into_parts();
+        parts.path_and_query = request.uri().path_and_query().cloned();
+        *request.uri_mut() = Uri::from_parts(parts).expect("The parts are from valid URIs.");
+
+        self.client.call(request)
+    }
+}
+
+#[derive(Clone)]
+struct VectorService {
+    client: Client,
+}
+
+impl Service<Vec<event_proto::EventWrapper>> for VectorService {
+    type Response = ();
+    type Error = VectorSinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, events: Vec<event_proto::EventWrapper>) -> Self::Future {
+        let mut client = self.client.clone();
+        let count = events.len();
+        let byte_size = events.iter().map(Message::encoded_len).sum();
+
+        Box::pin(async move {
+            client
+                .push_events(proto::PushEventsRequest { events })
+                .await
+                .context(Request)?;
+            emit!(VectorEventsSent { count, byte_size });
+            Ok(())
+        })
+    }
+}
+
+impl EncodedLength for event_proto::EventWrapper {
+    fn encoded_length(&self) -> usize {
+        self.encoded_len()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VectorRetryLogic;
+
+impl RetryLogic for VectorRetryLogic {
+    type Error = VectorSinkError;
+    type Response = ();
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            // Failures to connect are reported as unknown errors.
+            VectorSinkError::Request { source } => matches!(
+                source.code(),
+                Code::Unknown
+                    | Code::Unavailable
+                    | Code::ResourceExhausted
+                    | Code::DeadlineExceeded
+                    | Code::Aborted
+                    | Code::Internal
+            ),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_scheme_to_addresses() {
+        assert_eq!(
+            with_default_scheme("localhost:6000", false)
+                .unwrap()
+                .to_string(),
+            "http://localhost:6000/"
+        );
+        assert_eq!(
+            with_default_scheme("localhost:6000", true)
+                .unwrap()
+                .to_string(),
+            "https://localhost:6000/"
+        );
+        assert_eq!(
+            with_default_scheme("http://localhost:6000", true)
+                .unwrap()
+                .to_string(),
+            "http://localhost:6000/"
+        );
+    }
+}
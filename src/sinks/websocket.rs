@@ -0,0 +1,281 @@
+use crate::{
+    buffers::Acker,
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    emit,
+    http::{Auth, MaybeAuth},
+    internal_events::{
+        WebSocketConnectionFailed, WebSocketConnectionShutdown, WebSocketEventSent,
+        WebSocketSendFailed,
+    },
+    sinks::util::{
+        encoding::{EncodingConfig, EncodingConfiguration},
+        retries::ExponentialBackoff,
+        StreamSink, UriSerde,
+    },
+    Event,
+};
+use async_trait::async_trait;
+use futures::{
+    stream::{BoxStream, Fuse},
+    FutureExt, Sink, SinkExt, Stream, StreamExt,
+};
+use http::Request;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::time::{delay_for, interval};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, error::Error as WsError, Message},
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketSinkConfig {
+    /// The `ws://` or `wss://` endpoint to connect to.
+    uri: UriSerde,
+    encoding: EncodingConfig<Encoding>,
+    /// Pings the server at this interval, reconnecting when it doesn't answer in time.
+    ping_interval_secs: Option<u64>,
+    #[serde(default = "default_ping_timeout_secs")]
+    ping_timeout_secs: u64,
+    auth: Option<Auth>,
+}
+
+fn default_ping_timeout_secs() -> u64 {
+    10
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Text,
+    Json,
+}
+
+inventory::submit! {
+    SinkDescription::new::<WebSocketSinkConfig>("websocket")
+}
+
+impl GenerateConfig for WebSocketSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            uri = "ws://127.0.0.1:8080/events"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket")]
+impl SinkConfig for WebSocketSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = WebSocketSink::new(self, cx.acker())?;
+        let healthcheck = healthcheck(sink.request()?).boxed();
+        Ok((super::VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "websocket"
+    }
+}
+
+async fn healthcheck(request: Request<()>) -> crate::Result<()> {
+    let (mut ws, _) = connect_async(request).await?;
+    ws.close(None).await?;
+    Ok(())
+}
+
+pub struct WebSocketSink {
+    uri: UriSerde,
+    auth: Option<Auth>,
+    encoding: EncodingConfig<Encoding>,
+    ping_interval: Option<Duration>,
+    ping_timeout: Duration,
+    acker: Acker,
+}
+
+impl WebSocketSink {
+    fn new(config: &WebSocketSinkConfig, acker: Acker) -> crate::Result<Self> {
+        Ok(WebSocketSink {
+            uri: config.uri.clone(),
+            auth: config.auth.choose_one(&config.uri.auth)?,
+            encoding: config.encoding.clone(),
+            ping_interval: config.ping_interval_secs.map(Duration::from_secs),
+            ping_timeout: Duration::from_secs(config.ping_timeout_secs),
+            acker,
+        })
+    }
+
+    fn request(&self) -> crate::Result<Request<()>> {
+        let mut request = self.uri.uri.clone().into_client_request()?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+        Ok(request)
+    }
+
+    fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+
+    /// Sends events until the input ends, or until the connection fails. A message that
+    /// couldn't be sent is left in `pending`, to be sent again over the next connection.
+    async fn send_events<S>(
+        &mut self,
+        input: &mut Fuse<BoxStream<'_, Event>>,
+        ws: &mut S,
+        pending: &mut Option<String>,
+    ) -> Result<(), WsError>
+    where
+        S: Stream<Item = Result<Message, WsError>> + Sink<Message, Error = WsError> + Unpin,
+    {
+        let mut ping = self.ping_interval.map(interval);
+        let mut last_pong = Instant::now();
+
+        loop {
+            if let Some(message) = pending.clone() {
+                let byte_size = message.len();
+                ws.send(Message::Text(message)).await?;
+                *pending = None;
+                self.acker.ack(1);
+                emit!(WebSocketEventSent { byte_size });
+            }
+
+            let ping_tick = async {
+                match &mut ping {
+                    Some(ping) => ping.tick().await,
+                    None => futures::future::pending().await,
+                }
+            };
+
+            tokio::select! {
+                event = input.next() => match event {
+                    Some(event) => *pending = Some(encode_event(event, &self.encoding)),
+                    None => {
+                        let _ = ws.close().await;
+                        return Ok(());
+                    }
+                },
+                // Reading lets the connection answer the server's pings, and see its pongs.
+                message = ws.next() => match message {
+                    Some(Ok(Message::Pong(_))) => last_pong = Instant::now(),
+                    Some(Ok(Message::Close(_))) | None => return Err(WsError::ConnectionClosed),
+                    Some(Ok(_)) => (),
+                    Some(Err(error)) => return Err(error),
+                },
+                _ = ping_tick => {
+                    if last_pong.elapsed() > self.ping_interval.unwrap() + self.ping_timeout {
+                        emit!(WebSocketConnectionShutdown);
+                        return Err(WsError::ConnectionClosed);
+                    }
+                    ws.send(Message::Ping(Vec::new())).await?;
+                },
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink for WebSocketSink {
+    async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut input = input.fuse();
+        let mut pending = None;
+        let mut backoff = Self::fresh_backoff();
+
+        loop {
+            let connected = match self.request() {
+                Ok(request) => connect_async(request).await.map_err(Into::into),
+                Err(error) => Err(error),
+            };
+            let mut ws = match connected {
+                Ok((ws, _)) => ws,
+                Err(error) => {
+                    emit!(WebSocketConnectionFailed { error });
+                    delay_for(backoff.next().unwrap()).await;
+                    continue;
+                }
+            };
+            backoff = Self::fresh_backoff();
+
+            match self.send_events(&mut input, &mut ws, &mut pending).await {
+                Ok(()) => return Ok(()),
+                // The connection is opened again, and the pending message sent over it.
+                Err(error) => emit!(WebSocketSendFailed { error }),
+            }
+            delay_for(backoff.next().unwrap()).await;
+        }
+    }
+}
+
+fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> String {
+    encoding.apply_rules(&mut event);
+
+    match encoding.codec() {
+        Encoding::Json => serde_json::to_string(event.as_log()).unwrap(),
+        Encoding::Text => event
+            .as_log()
+            .get(log_schema().message_key())
+            .map(|v| v.to_string_lossy())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{next_addr, random_lines_with_stream, trace_init};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketSinkConfig>();
+    }
+
+    #[tokio::test]
+    async fn websocket_sink_streams_events() {
+        trace_init();
+
+        let addr = next_addr();
+        let mut listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            ws.filter_map(|message| async move {
+                match message.unwrap() {
+                    Message::Text(text) => Some(text),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>()
+            .await
+        });
+
+        let config: WebSocketSinkConfig = toml::from_str(&format!(
+            r#"
+            uri = "ws://{}/"
+            encoding.codec = "text"
+            ping_interval_secs = 1"#,
+            addr
+        ))
+        .unwrap();
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let mut sink = WebSocketSink::new(&config, acker).unwrap();
+
+        let (input, events) = random_lines_with_stream(100, 10);
+        sink.run(Box::pin(events)).await.unwrap();
+
+        assert_eq!(server.await.unwrap(), input);
+        assert_eq!(ack_counter.load(std::sync::atomic::Ordering::Relaxed), 10);
+    }
+}
@@ -34,6 +34,8 @@ enum StatusFieldStatistic<'a> {
     CPUChildrenUser(f64),
     CPUChildrenSystem(f64),
     CPULoad(f64),
+    ReqPerSec(f64),
+    BytesPerSec(f64),
     IdleWorkers(u64),
     BusyWorkers(u64),
     ConnsTotal(u64),
@@ -72,6 +74,12 @@ impl<'a> StatusFieldStatistic<'a> {
                 Some(parse_numeric_value(key, value).map(StatusFieldStatistic::CPUChildrenSystem))
             }
             "CPULoad" => Some(parse_numeric_value(key, value).map(StatusFieldStatistic::CPULoad)),
+            "ReqPerSec" => {
+                Some(parse_numeric_value(key, value).map(StatusFieldStatistic::ReqPerSec))
+            }
+            "BytesPerSec" => {
+                Some(parse_numeric_value(key, value).map(StatusFieldStatistic::BytesPerSec))
+            }
             "IdleWorkers" => {
                 Some(parse_numeric_value(key, value).map(StatusFieldStatistic::IdleWorkers))
             }
@@ -271,6 +279,28 @@ fn line_to_metrics<'a>(
                 .with_timestamp(Some(now)),
             ))
                 as Box<dyn Iterator<Item = Metric>>,
+            StatusFieldStatistic::ReqPerSec(value) => Box::new(iter::once(
+                Metric::new(
+                    "requests_per_second",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                )
+                .with_namespace(namespace.map(str::to_string))
+                .with_tags(tags.cloned())
+                .with_timestamp(Some(now)),
+            ))
+                as Box<dyn Iterator<Item = Metric>>,
+            StatusFieldStatistic::BytesPerSec(value) => Box::new(iter::once(
+                Metric::new(
+                    "sent_bytes_per_second",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                )
+                .with_namespace(namespace.map(str::to_string))
+                .with_tags(tags.cloned())
+                .with_timestamp(Some(now)),
+            ))
+                as Box<dyn Iterator<Item = Metric>>,
             StatusFieldStatistic::IdleWorkers(value) => Box::new(iter::once(
                 Metric::new(
                     "workers",
@@ -825,6 +855,13 @@ Scoreboard: ____S_____I______R____I_______KK___D__C__G_L____________W___________
                 )
                 .with_namespace(Some("apache"))
                 .with_timestamp(Some(now)),
+                Metric::new(
+                    "requests_per_second",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 1.15385 },
+                )
+                .with_namespace(Some("apache"))
+                .with_timestamp(Some(now)),
                 Metric::new(
                     "scoreboard",
                     MetricKind::Absolute,
@@ -913,6 +950,13 @@ Scoreboard: ____S_____I______R____I_______KK___D__C__G_L____________W___________
                 .with_namespace(Some("apache"))
                 .with_tags(Some(btreemap! { "state" => "waiting" }))
                 .with_timestamp(Some(now)),
+                Metric::new(
+                    "sent_bytes_per_second",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 8546.46 },
+                )
+                .with_namespace(Some("apache"))
+                .with_timestamp(Some(now)),
                 Metric::new(
                     "sent_bytes_total",
                     MetricKind::Absolute,
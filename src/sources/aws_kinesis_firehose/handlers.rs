@@ -1,26 +1,35 @@
 use super::errors::{ParseRecords, RequestError};
 use super::models::{EncodedFirehoseRecord, FirehoseRequest, FirehoseResponse};
+use super::Compression;
 use crate::{config::log_schema, event::Event, Pipeline};
 use bytes::Bytes;
 use chrono::Utc;
 use flate2::read::GzDecoder;
 use futures::{SinkExt, StreamExt, TryFutureExt};
 use snafu::ResultExt;
-use std::io::Read;
+use std::io::{self, Read};
 use warp::reject;
 
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
 /// Publishes decoded events from the FirehoseRequest to the pipeline
 pub async fn firehose(
     request_id: String,
     source_arn: String,
     request: FirehoseRequest,
+    record_compression: Compression,
     mut out: Pipeline,
 ) -> Result<impl warp::Reply, reject::Rejection> {
-    let events = parse_records(request, request_id.as_str(), source_arn.as_str())
-        .with_context(|| ParseRecords {
-            request_id: request_id.clone(),
-        })
-        .map_err(reject::custom)?;
+    let events = parse_records(
+        request,
+        record_compression,
+        request_id.as_str(),
+        source_arn.as_str(),
+    )
+    .with_context(|| ParseRecords {
+        request_id: request_id.clone(),
+    })
+    .map_err(reject::custom)?;
     let mut stream = futures::stream::iter(events).map(Ok);
 
     let request_id = request_id.clone();
@@ -49,14 +58,15 @@ pub async fn firehose(
 /// Parses out events from the FirehoseRequest
 fn parse_records(
     request: FirehoseRequest,
+    record_compression: Compression,
     request_id: &str,
     source_arn: &str,
-) -> std::io::Result<Vec<Event>> {
+) -> io::Result<Vec<Event>> {
     request
         .records
         .iter()
         .map(|record| {
-            decode_record(record).map(|record| {
+            decode_record(record, record_compression).map(|record| {
                 let mut event = Event::new_empty_log();
                 let log = event.as_mut_log();
 
@@ -71,14 +81,53 @@ fn parse_records(
         .collect()
 }
 
-/// Decodes a Firehose record from its base64 gzip format
-fn decode_record(record: &EncodedFirehoseRecord) -> std::io::Result<Bytes> {
-    let mut cursor = std::io::Cursor::new(record.data.as_bytes());
-    let base64decoder = base64::read::DecoderReader::new(&mut cursor, base64::STANDARD);
+/// Decodes a Firehose record from its base64 format, and decompresses it if needed
+fn decode_record(record: &EncodedFirehoseRecord, compression: Compression) -> io::Result<Bytes> {
+    let data = base64::decode(&record.data)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
 
-    let mut gz = GzDecoder::new(base64decoder);
-    let mut buffer = Vec::new();
-    gz.read_to_end(&mut buffer)?;
+    let gzipped = match compression {
+        Compression::Auto => data.starts_with(GZIP_MAGIC),
+        Compression::None => false,
+        Compression::Gzip => true,
+    };
+    if !gzipped {
+        return Ok(Bytes::from(data));
+    }
 
+    let mut buffer = Vec::new();
+    GzDecoder::new(data.as_slice()).read_to_end(&mut buffer)?;
     Ok(Bytes::from(buffer))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{read::GzEncoder, Compression as GzipLevel};
+
+    fn record(data: &[u8]) -> EncodedFirehoseRecord {
+        EncodedFirehoseRecord {
+            data: base64::encode(data),
+        }
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        GzEncoder::new(data, GzipLevel::fast())
+            .read_to_end(&mut buffer)
+            .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn decodes_records_by_compression() {
+        let plain = record(b"hello");
+        let gzipped = record(&gzip(b"hello"));
+
+        assert_eq!(decode_record(&plain, Compression::Auto).unwrap(), "hello");
+        assert_eq!(decode_record(&gzipped, Compression::Auto).unwrap(), "hello");
+        assert_eq!(decode_record(&plain, Compression::None).unwrap(), "hello");
+        assert_eq!(decode_record(&gzipped, Compression::Gzip).unwrap(), "hello");
+        assert!(decode_record(&plain, Compression::Gzip).is_err());
+    }
+}
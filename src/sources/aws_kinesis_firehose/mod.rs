@@ -17,9 +17,24 @@ mod models;
 pub struct AwsKinesisFirehoseConfig {
     address: SocketAddr,
     access_key: Option<String>,
+    #[serde(default)]
+    record_compression: Compression,
     tls: Option<TlsConfig>,
 }
 
+/// The compression of the data of records, which is only gzipped by some producers, such as
+/// CloudWatch Logs subscriptions.
+#[derive(Derivative, Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+pub enum Compression {
+    /// Records are decompressed if they start with the gzip magic bytes.
+    #[derivative(Default)]
+    Auto,
+    None,
+    Gzip,
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "aws_kinesis_firehose")]
 impl SourceConfig for AwsKinesisFirehoseConfig {
@@ -30,7 +45,7 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
-        let svc = filters::firehose(self.access_key.clone(), out);
+        let svc = filters::firehose(self.access_key.clone(), self.record_compression, out);
 
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
         let listener = tls.bind(&self.address).await?;
@@ -70,6 +85,7 @@ impl GenerateConfig for AwsKinesisFirehoseConfig {
         toml::Value::try_from(Self {
             address: "0.0.0.0:443".parse().unwrap(),
             access_key: None,
+            record_compression: Compression::Auto,
             tls: None,
         })
         .unwrap()
@@ -98,7 +114,10 @@ mod tests {
         crate::test_util::test_generate_config::<AwsKinesisFirehoseConfig>();
     }
 
-    async fn source(access_key: Option<String>) -> (mpsc::Receiver<Event>, SocketAddr) {
+    async fn source(
+        access_key: Option<String>,
+        record_compression: super::Compression,
+    ) -> (mpsc::Receiver<Event>, SocketAddr) {
         let (sender, recv) = Pipeline::new_test();
         let address = next_addr();
         tokio::spawn(async move {
@@ -106,6 +125,7 @@ mod tests {
                 address,
                 tls: None,
                 access_key,
+                record_compression,
             }
             .build(
                 "default",
@@ -214,7 +234,7 @@ mod tests {
 }
 "#;
 
-        let (rx, addr) = source(None).await;
+        let (rx, addr) = source(None, super::Compression::Auto).await;
 
         let source_arn = "arn:aws:firehose:us-east-1:111111111111:deliverystream/test";
         let request_id = "e17265d6-97af-4938-982e-90d5614c4242";
@@ -275,7 +295,7 @@ mod tests {
 }
 "#;
 
-        let (rx, addr) = source(None).await;
+        let (rx, addr) = source(None, super::Compression::Auto).await;
 
         let source_arn = "arn:aws:firehose:us-east-1:111111111111:deliverystream/test";
         let request_id = "e17265d6-97af-4938-982e-90d5614c4242";
@@ -311,7 +331,7 @@ mod tests {
 
     #[tokio::test]
     async fn aws_kinesis_firehose_rejects_bad_access_key() {
-        let (_rx, addr) = source(Some("an access key".to_string())).await;
+        let (_rx, addr) = source(Some("an access key".to_string()), super::Compression::Auto).await;
 
         let request_id = "e17265d6-97af-4938-982e-90d5614c4242";
 
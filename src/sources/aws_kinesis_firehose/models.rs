@@ -20,7 +20,7 @@ pub struct FirehoseRequest {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EncodedFirehoseRecord {
-    /// data is base64 encoded, gzip'd, bytes
+    /// data is base64 encoded bytes, which may be gzip'd
     pub data: String,
 }
 
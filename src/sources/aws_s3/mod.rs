@@ -52,6 +52,9 @@ struct AwsS3Config {
     auth: AWSAuthentication,
 
     multiline: Option<MultilineConfig>,
+
+    /// Deletes a message only once the sinks have accepted the events of its objects.
+    acknowledgements: bool,
 }
 
 inventory::submit! {
@@ -130,6 +133,7 @@ impl AwsS3Config {
                     sqs.clone(),
                     self.compression,
                     multiline,
+                    self.acknowledgements,
                 )
                 .await
                 .context(Initialize {})
@@ -1,6 +1,6 @@
 use crate::{
     config::log_schema,
-    event::Event,
+    event::{BatchNotifier, Event, EventStatus},
     internal_events::aws_s3::source::{
         SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageProcessingFailed,
         SqsMessageProcessingSucceeded, SqsMessageReceiveFailed, SqsMessageReceiveSucceeded,
@@ -23,7 +23,7 @@ use rusoto_sqs::{
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
-use std::{future::ready, time::Duration};
+use std::{future::ready, sync::Arc, time::Duration};
 use tokio::time;
 use tokio_util::codec::FramedRead;
 
@@ -111,6 +111,12 @@ pub enum ProcessingError {
     },
     #[snafu(display("Unsupported S3 event version: {}.", version,))]
     UnsupportedS3EventVersion { version: semver::Version },
+    #[snafu(display("Events of s3://{}/{} were not delivered: {:?}", bucket, key, status))]
+    NotDelivered {
+        status: EventStatus,
+        bucket: String,
+        key: String,
+    },
 }
 
 pub(super) struct Ingestor {
@@ -126,6 +132,7 @@ pub(super) struct Ingestor {
     poll_interval: Duration,
     visibility_timeout_secs: i64,
     delete_message: bool,
+    acknowledgements: bool,
 }
 
 impl Ingestor {
@@ -136,6 +143,7 @@ impl Ingestor {
         config: Config,
         compression: super::Compression,
         multiline: Option<line_agg::Config>,
+        acknowledgements: bool,
     ) -> Result<Ingestor, IngestorNewError> {
         let visibility_timeout_secs: i64 = config.visibility_timeout_secs.into();
 
@@ -152,6 +160,7 @@ impl Ingestor {
             poll_interval: Duration::from_secs(config.poll_secs),
             visibility_timeout_secs,
             delete_message: config.delete_message,
+            acknowledgements,
         })
     }
 
@@ -351,6 +360,15 @@ impl Ingestor {
                     None => lines,
                 };
 
+                // Each object is only done with once its events are delivered, so that the
+                // message is left on the queue if any of its objects isn't.
+                let (batch, receiver) = if self.acknowledgements {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    (Some(batch), Some(receiver))
+                } else {
+                    (None, None)
+                };
+
                 let stream = lines.filter_map(|line| {
                     let mut event = Event::from(line);
 
@@ -366,6 +384,10 @@ impl Ingestor {
                         }
                     }
 
+                    if let Some(batch) = &batch {
+                        event.add_batch_notifier(Arc::clone(batch));
+                    }
+
                     ready(Some(Ok(event)))
                 });
 
@@ -376,26 +398,34 @@ impl Ingestor {
                         send_error = Some(err);
                     })
                     .ok();
+                drop(batch);
 
-                read_error
-                    .map(|error| {
-                        Err(ProcessingError::ReadObject {
-                            source: error,
+                if let Some(error) = read_error {
+                    return Err(ProcessingError::ReadObject {
+                        source: error,
+                        bucket: s3_event.s3.bucket.name.clone(),
+                        key: s3_event.s3.object.key.clone(),
+                    });
+                }
+                if let Some(error) = send_error {
+                    return Err(ProcessingError::PipelineSend {
+                        source: error,
+                        bucket: s3_event.s3.bucket.name.clone(),
+                        key: s3_event.s3.object.key.clone(),
+                    });
+                }
+
+                match receiver {
+                    None => Ok(()),
+                    Some(receiver) => match receiver.await {
+                        EventStatus::Delivered => Ok(()),
+                        status => Err(ProcessingError::NotDelivered {
+                            status,
                             bucket: s3_event.s3.bucket.name.clone(),
                             key: s3_event.s3.object.key.clone(),
-                        })
-                    })
-                    .unwrap_or_else(|| {
-                        send_error
-                            .map(|error| {
-                                Err(ProcessingError::PipelineSend {
-                                    source: error,
-                                    bucket: s3_event.s3.bucket.name.clone(),
-                                    key: s3_event.s3.object.key.clone(),
-                                })
-                            })
-                            .unwrap_or(Ok(()))
-                    })
+                        }),
+                    },
+                }
             }
             None => Ok(()),
         }
@@ -0,0 +1,449 @@
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, MetadataKeys, SourceConfig,
+        SourceDescription,
+    },
+    event::{BatchNotifier, Event, EventStatus, Value},
+    internal_events::{
+        AwsSqsEventReceived, AwsSqsMessageDeleteFailed, AwsSqsMessageNotDelivered,
+        AwsSqsMessageReceiveFailed,
+    },
+    rusoto::{self, AWSAuthentication, RegionOrEndpoint},
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::{future, stream, SinkExt, StreamExt};
+use rusoto_core::RusotoError;
+use rusoto_sqs::{
+    DeleteMessageBatchRequest, DeleteMessageBatchRequestEntry, Message, MessageAttributeValue,
+    ReceiveMessageError, ReceiveMessageRequest, Sqs, SqsClient,
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{convert::TryInto, time::Duration};
+use tokio::time::delay_for;
+
+/// The longest SQS lets a receive request wait for messages.
+const MAX_POLL_SECS: u32 = 20;
+const RECEIVE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`poll_secs` can't be more than {} seconds", MAX_POLL_SECS))]
+    PollSecsTooLong,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AwsSqsConfig {
+    queue_url: String,
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+    #[serde(default)]
+    auth: AWSAuthentication,
+    #[serde(default = "default_poll_secs")]
+    poll_secs: u32,
+    #[serde(default = "default_visibility_timeout_secs")]
+    visibility_timeout_secs: u32,
+    #[serde(default = "default_true")]
+    delete_message: bool,
+    /// Deletes a message only once the sinks have accepted its event.
+    #[serde(default)]
+    acknowledgements: bool,
+}
+
+const fn default_poll_secs() -> u32 {
+    15
+}
+
+const fn default_visibility_timeout_secs() -> u32 {
+    300
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+inventory::submit! {
+    SourceDescription::new::<AwsSqsConfig>("aws_sqs")
+}
+
+impl GenerateConfig for AwsSqsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"queue_url = "https://sqs.us-east-2.amazonaws.com/123456789012/MyQueue"
+            region = "us-east-2""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_sqs")]
+impl SourceConfig for AwsSqsConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        if self.poll_secs > MAX_POLL_SECS {
+            return Err(BuildError::PollSecsTooLong.into());
+        }
+
+        let region = (&self.region).try_into()?;
+        let client = rusoto::client()?;
+        let creds = self.auth.build(&region, None)?;
+        let source = SqsSource {
+            client: SqsClient::new_with(client, creds, region),
+            queue_url: self.queue_url.clone(),
+            poll_secs: self.poll_secs.into(),
+            visibility_timeout_secs: self.visibility_timeout_secs.into(),
+            delete_message: self.delete_message,
+            acknowledgements: self.acknowledgements,
+            metadata_keys: globals.metadata_keys.clone(),
+        };
+
+        Ok(Box::pin(source.run(out, shutdown)))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "aws_sqs"
+    }
+}
+
+struct SqsSource {
+    client: SqsClient,
+    queue_url: String,
+    poll_secs: i64,
+    visibility_timeout_secs: i64,
+    delete_message: bool,
+    acknowledgements: bool,
+    metadata_keys: MetadataKeys,
+}
+
+impl SqsSource {
+    async fn run(self, mut out: Pipeline, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        loop {
+            let messages = tokio::select! {
+                result = self.receive_messages() => result,
+                _ = &mut shutdown => break,
+            };
+            let messages = match messages {
+                Ok(messages) => messages,
+                Err(error) => {
+                    emit!(AwsSqsMessageReceiveFailed { error });
+                    delay_for(RECEIVE_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            let mut receipt_handles = Vec::new();
+            let mut receivers = Vec::new();
+            let mut events = Vec::new();
+            for message in messages {
+                emit!(AwsSqsEventReceived {
+                    byte_size: message.body.as_ref().map_or(0, String::len),
+                });
+
+                receipt_handles.push(message.receipt_handle.clone());
+                let mut event = message_to_event(message, &self.metadata_keys);
+                if self.acknowledgements {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    event.add_batch_notifier(batch);
+                    receivers.push(receiver);
+                }
+                events.push(event);
+            }
+
+            if let Err(error) = out.send_all(&mut stream::iter(events).map(Ok)).await {
+                error!(message = "Error sending to sink.", %error);
+                return Err(());
+            }
+
+            // Messages that weren't delivered are left on the queue, to be received again once
+            // their visibility timeout expires.
+            if self.acknowledgements {
+                let statuses = future::join_all(receivers).await;
+                for (receipt_handle, status) in receipt_handles.iter_mut().zip(statuses) {
+                    if status != EventStatus::Delivered {
+                        emit!(AwsSqsMessageNotDelivered { status });
+                        *receipt_handle = None;
+                    }
+                }
+            }
+
+            if self.delete_message {
+                self.delete_messages(receipt_handles.into_iter().flatten().collect())
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn receive_messages(&self) -> Result<Vec<Message>, RusotoError<ReceiveMessageError>> {
+        self.client
+            .receive_message(ReceiveMessageRequest {
+                queue_url: self.queue_url.clone(),
+                max_number_of_messages: Some(10),
+                visibility_timeout: Some(self.visibility_timeout_secs),
+                wait_time_seconds: Some(self.poll_secs),
+                attribute_names: Some(vec!["SentTimestamp".to_owned()]),
+                message_attribute_names: Some(vec!["All".to_owned()]),
+                ..Default::default()
+            })
+            .await
+            .map(|response| response.messages.unwrap_or_default())
+    }
+
+    async fn delete_messages(&self, receipt_handles: Vec<String>) {
+        if receipt_handles.is_empty() {
+            return;
+        }
+
+        let entries = receipt_handles
+            .into_iter()
+            .enumerate()
+            .map(|(index, receipt_handle)| DeleteMessageBatchRequestEntry {
+                id: index.to_string(),
+                receipt_handle,
+            })
+            .collect();
+        let result = self
+            .client
+            .delete_message_batch(DeleteMessageBatchRequest {
+                queue_url: self.queue_url.clone(),
+                entries,
+            })
+            .await;
+
+        match result {
+            Ok(result) => {
+                for entry in result.failed {
+                    emit!(AwsSqsMessageDeleteFailed {
+                        error: entry.message.as_deref().unwrap_or(&entry.code),
+                    });
+                }
+            }
+            Err(error) => emit!(AwsSqsMessageDeleteFailed {
+                error: &error.to_string(),
+            }),
+        }
+    }
+}
+
+/// The body of the message becomes the `message` of the event, and each of its message
+/// attributes becomes a field.
+fn message_to_event(message: Message, metadata_keys: &MetadataKeys) -> Event {
+    let mut event = Event::new_empty_log();
+    let log = event.as_mut_log();
+
+    for (name, attribute) in message.message_attributes.unwrap_or_default() {
+        log.insert_flat(name, attribute_to_value(attribute));
+    }
+
+    log.insert(log_schema().message_key(), message.body.unwrap_or_default());
+    let timestamp = message
+        .attributes
+        .as_ref()
+        .and_then(|attributes| attributes.get("SentTimestamp"))
+        .and_then(|millis| millis.parse::<i64>().ok())
+        .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    if let Some(message_id) = message.message_id {
+        log.insert("message_id", message_id);
+    }
+    metadata_keys
+        .source_type
+        .insert(log, log_schema().source_type_key(), "aws_sqs");
+
+    event
+}
+
+/// Numbers are parsed when they can be, and binary attributes are kept as bytes. Data types
+/// can have a custom suffix, as in `Number.float`.
+fn attribute_to_value(attribute: MessageAttributeValue) -> Value {
+    let data_type = attribute.data_type.split('.').next().unwrap_or_default();
+    match (data_type, attribute.string_value, attribute.binary_value) {
+        ("Number", Some(value), _) => value
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| value.parse::<f64>().map(Value::from))
+            .unwrap_or_else(|_| Value::from(value)),
+        (_, Some(value), _) => Value::from(value),
+        (_, None, Some(value)) => Value::from(value),
+        (_, None, None) => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsSqsConfig>();
+    }
+
+    fn attribute(data_type: &str, value: &str) -> MessageAttributeValue {
+        MessageAttributeValue {
+            data_type: data_type.to_owned(),
+            string_value: Some(value.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn converts_messages_to_events() {
+        let mut attributes = HashMap::new();
+        attributes.insert("SentTimestamp".to_owned(), "1600000000123".to_owned());
+        let mut message_attributes = HashMap::new();
+        message_attributes.insert("team".to_owned(), attribute("String", "payments"));
+        message_attributes.insert("retries".to_owned(), attribute("Number", "3"));
+        message_attributes.insert("ratio".to_owned(), attribute("Number.float", "0.5"));
+        message_attributes.insert(
+            "signature".to_owned(),
+            MessageAttributeValue {
+                data_type: "Binary".to_owned(),
+                binary_value: Some(Bytes::from_static(b"\x01\x02")),
+                ..Default::default()
+            },
+        );
+
+        let event = message_to_event(
+            Message {
+                body: Some("hello".to_owned()),
+                message_id: Some("abc".to_owned()),
+                attributes: Some(attributes),
+                message_attributes: Some(message_attributes),
+                ..Default::default()
+            },
+            &MetadataKeys::default(),
+        );
+
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp_millis(1_600_000_000_123).into()
+        );
+        assert_eq!(log["message_id"], "abc".into());
+        assert_eq!(log["team"], "payments".into());
+        assert_eq!(log["retries"], 3.into());
+        assert_eq!(log["ratio"], 0.5.into());
+        assert_eq!(log["signature"], Bytes::from_static(b"\x01\x02").into());
+        assert_eq!(log[log_schema().source_type_key()], "aws_sqs".into());
+    }
+}
+
+#[cfg(feature = "aws-sqs-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string};
+    use rusoto_core::Region;
+    use rusoto_sqs::{CreateQueueRequest, GetQueueAttributesRequest, SendMessageRequest};
+    use tokio::time::{delay_for, Duration};
+
+    const ENDPOINT: &str = "http://localhost:4566";
+
+    fn region() -> Region {
+        Region::Custom {
+            name: "localstack".into(),
+            endpoint: ENDPOINT.into(),
+        }
+    }
+
+    async fn create_queue(client: &SqsClient) -> String {
+        client
+            .create_queue(CreateQueueRequest {
+                queue_name: format!("test-{}", random_string(10).to_lowercase()),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .queue_url
+            .unwrap()
+    }
+
+    async fn queue_length(client: &SqsClient, queue_url: &str) -> String {
+        let attributes = client
+            .get_queue_attributes(GetQueueAttributesRequest {
+                attribute_names: Some(vec![
+                    "ApproximateNumberOfMessages".to_owned(),
+                    "ApproximateNumberOfMessagesNotVisible".to_owned(),
+                ]),
+                queue_url: queue_url.to_owned(),
+            })
+            .await
+            .unwrap()
+            .attributes
+            .unwrap();
+        format!(
+            "{}+{}",
+            attributes["ApproximateNumberOfMessages"],
+            attributes["ApproximateNumberOfMessagesNotVisible"]
+        )
+    }
+
+    #[tokio::test]
+    async fn receives_and_deletes_messages() {
+        let client = SqsClient::new(region());
+        let queue_url = create_queue(&client).await;
+        for index in 0..3 {
+            client
+                .send_message(SendMessageRequest {
+                    queue_url: queue_url.clone(),
+                    message_body: format!("message {}", index),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        let config = AwsSqsConfig {
+            queue_url: queue_url.clone(),
+            region: RegionOrEndpoint::with_endpoint(ENDPOINT.into()),
+            auth: Default::default(),
+            poll_secs: 1,
+            visibility_timeout_secs: 300,
+            delete_message: true,
+            acknowledgements: true,
+        };
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 3).await;
+        let mut messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        messages.sort();
+        assert_eq!(messages, vec!["message 0", "message 1", "message 2"]);
+
+        // Nothing is deleted until the events are dropped, which acknowledges them.
+        assert_eq!(queue_length(&client, &queue_url).await, "0+3");
+        drop(events);
+        delay_for(Duration::from_secs(1)).await;
+        assert_eq!(queue_length(&client, &queue_url).await, "0+0");
+    }
+}
@@ -1,6 +1,6 @@
-use super::util::MultilineConfig;
+use super::util::{Backoff, MultilineConfig, ReconnectPolicy};
 use crate::{
-    config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
+    config::{log_schema, DataType, GlobalOptions, MetadataKeys, SourceConfig, SourceDescription},
     event::merge_state::LogEventMergeState,
     event::{self, Event, LogEvent, Value},
     internal_events::{
@@ -155,12 +155,13 @@ impl SourceConfig for DockerLogsConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
         let source = DockerLogsSource::new(
             self.clone().with_empty_partial_event_marker_field_as_none(),
+            globals.metadata_keys.clone(),
             out,
             shutdown.clone(),
         )?;
@@ -262,8 +263,10 @@ impl DockerLogsSourceCore {
     }
 
     /// Returns event stream coming from docker.
+    /// Events from `since` onwards.
     fn docker_logs_event_stream(
         &self,
+        since: DateTime<Utc>,
     ) -> impl Stream<Item = Result<SystemEventsResponse, DockerError>> + Send {
         let mut filters = HashMap::new();
 
@@ -294,7 +297,7 @@ impl DockerLogsSourceCore {
         }
 
         self.docker.events(Some(EventsOptions {
-            since: Some(self.now_timestamp),
+            since: Some(since),
             until: None,
             filters,
         }))
@@ -322,16 +325,21 @@ struct DockerLogsSource {
     main_recv: mpsc::UnboundedReceiver<Result<ContainerLogInfo, ContainerId>>,
     /// It may contain shortened container id.
     hostname: Option<String>,
-    backoff_duration: Duration,
+    reconnect: ReconnectPolicy,
+    /// Of containers whose event streams are failing.
+    backoffs: HashMap<ContainerId, Backoff>,
+    events_backoff: Backoff,
 }
 
 impl DockerLogsSource {
     fn new(
         config: DockerLogsConfig,
+        metadata_keys: MetadataKeys,
         out: Pipeline,
         shutdown: ShutdownSignal,
     ) -> crate::Result<DockerLogsSource> {
-        let backoff_secs = config.retry_backoff_secs;
+        let reconnect = ReconnectPolicy::default()
+            .initial_delay(Duration::from_secs(config.retry_backoff_secs));
 
         let host_key = config.host_key.clone();
         let hostname = crate::get_hostname().ok();
@@ -340,7 +348,7 @@ impl DockerLogsSource {
         let core = DockerLogsSourceCore::new(config)?;
 
         // main event stream, with whom only newly started/restarted containers will be logged.
-        let events = core.docker_logs_event_stream();
+        let events = core.docker_logs_event_stream(core.now_timestamp);
         info!(message = "Listening to docker log events.");
 
         // Channel of communication between main future and event_stream futures
@@ -358,6 +366,7 @@ impl DockerLogsSource {
         let esb = EventStreamBuilder {
             host_key,
             hostname: hostname.clone(),
+            metadata_keys,
             core: Arc::new(core),
             out,
             main_send,
@@ -370,7 +379,9 @@ impl DockerLogsSource {
             containers: HashMap::new(),
             main_recv,
             hostname,
-            backoff_duration: Duration::from_secs(backoff_secs),
+            reconnect,
+            backoffs: HashMap::new(),
+            events_backoff: reconnect.backoff(),
         })
     }
 
@@ -439,6 +450,7 @@ impl DockerLogsSource {
                         Some(message) => {
                             match message {
                                 Ok(info) => {
+                                    self.backoffs.remove(&info.id);
                                     let state = self
                                         .containers
                                         .get_mut(&info.id)
@@ -453,8 +465,15 @@ impl DockerLogsSource {
                                         .remove(&id)
                                         .expect("Every started ContainerId has it's ContainerState");
                                     if state.is_running() {
-                                        let backoff = Some(self.backoff_duration);
-                                        self.containers.insert(id.clone(), self.esb.start(id, backoff));
+                                        let reconnect = self.reconnect;
+                                        let delay = self
+                                            .backoffs
+                                            .entry(id.clone())
+                                            .or_insert_with(|| reconnect.backoff())
+                                            .next_delay();
+                                        self.containers.insert(id.clone(), self.esb.start(id, Some(delay)));
+                                    } else {
+                                        self.backoffs.remove(&id);
                                     }
                                 }
                             }
@@ -469,6 +488,7 @@ impl DockerLogsSource {
                 value = self.events.next() => {
                     match value {
                         Some(Ok(mut event)) => {
+                            self.events_backoff.reset();
                             let action = event.action.unwrap();
                             let actor = event.actor.take().unwrap();
                             let id = actor.id.unwrap();
@@ -508,10 +528,13 @@ impl DockerLogsSource {
                         }
                         Some(Err(error)) => emit!(DockerLogsCommunicationError{error,container_id:None}),
                         None => {
-                            // TODO: this could be fixed, but should be tried with some timeoff and exponential backoff
                             error!(message = "Docker log event stream has ended unexpectedly.");
-                            info!(message = "Shutting down docker_logs source.");
-                            return;
+                            // Ask again for events from when the stream ended, so
+                            // that none are missed while reconnecting.
+                            let since = Utc::now();
+                            self.events_backoff.wait().await;
+                            info!(message = "Reconnecting to docker log events.");
+                            self.events = Box::pin(self.esb.core.docker_logs_event_stream(since));
                         }
                     };
                 }
@@ -532,6 +555,7 @@ impl DockerLogsSource {
 struct EventStreamBuilder {
     host_key: String,
     hostname: Option<String>,
+    metadata_keys: MetadataKeys,
     core: Arc<DockerLogsSourceCore>,
     /// Event stream futures send events through this
     out: Pipeline,
@@ -613,6 +637,7 @@ impl EventStreamBuilder {
                         self.core.config.partial_event_marker_field.clone(),
                         self.core.config.auto_partial_merge,
                         &mut partial_event_merge_state,
+                        &self.metadata_keys,
                     )),
                     Err(error) => {
                         // On any error, restart connection
@@ -651,8 +676,9 @@ impl EventStreamBuilder {
 
         let host_key = self.host_key.clone();
         let hostname = self.hostname.clone();
+        let metadata_keys = self.metadata_keys.clone();
         let result = events_stream
-            .map(move |event| add_hostname(event, &host_key, &hostname))
+            .map(move |event| add_hostname(event, &host_key, &hostname, &metadata_keys))
             .map(Ok)
             .forward(self.out.clone())
             .await;
@@ -677,9 +703,16 @@ impl EventStreamBuilder {
     }
 }
 
-fn add_hostname(mut event: Event, host_key: &str, hostname: &Option<String>) -> Event {
+fn add_hostname(
+    mut event: Event,
+    host_key: &str,
+    hostname: &Option<String>,
+    metadata_keys: &MetadataKeys,
+) -> Event {
     if let Some(hostname) = hostname {
-        event.as_mut_log().insert(host_key, hostname.clone());
+        metadata_keys
+            .host
+            .insert(event.as_mut_log(), host_key, hostname.clone());
     }
 
     event
@@ -796,6 +829,7 @@ impl ContainerLogInfo {
         partial_event_marker_field: Option<String>,
         auto_partial_merge: bool,
         partial_event_merge_state: &mut Option<LogEventMergeState>,
+        metadata_keys: &MetadataKeys,
     ) -> Option<Event> {
         let (stream, mut bytes_message) = match log_output {
             LogOutput::StdErr { message } => (STDERR.clone(), message),
@@ -869,7 +903,11 @@ impl ContainerLogInfo {
             let mut log_event = LogEvent::default();
 
             // Source type
-            log_event.insert(log_schema().source_type_key(), Bytes::from("docker"));
+            metadata_keys.source_type.insert(
+                &mut log_event,
+                log_schema().source_type_key(),
+                Bytes::from("docker"),
+            );
 
             // The log message.
             log_event.insert(log_schema().message_key(), bytes_message);
@@ -1098,8 +1136,13 @@ mod tests {
     #[test]
     fn exclude_self() {
         let (tx, _rx) = Pipeline::new_test();
-        let mut source =
-            DockerLogsSource::new(DockerLogsConfig::default(), tx, ShutdownSignal::noop()).unwrap();
+        let mut source = DockerLogsSource::new(
+            DockerLogsConfig::default(),
+            MetadataKeys::default(),
+            tx,
+            ShutdownSignal::noop(),
+        )
+        .unwrap();
         source.hostname = Some("451062c59603".to_owned());
         assert!(
             source.exclude_self("451062c59603a1cf0c6af3e74a31c0ae63d8275aa16a5fc78ef31b923baaffc3")
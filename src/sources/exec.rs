@@ -0,0 +1,500 @@
+use crate::{
+    codecs::{self, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, MetadataKeys, SourceConfig,
+        SourceDescription,
+    },
+    event::{Event, Value},
+    internal_events::{DecoderFramingFailed, ExecCommandExecuted, ExecEventReceived, ExecFailed},
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{stream, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{
+    path::PathBuf,
+    process::Stdio,
+    time::{Duration, Instant},
+};
+use tokio::{process::Command, time::delay_for};
+use tokio_util::codec::FramedRead;
+
+const COMMAND_KEY: &str = "command";
+const EXIT_CODE_KEY: &str = "exit_code";
+const PID_KEY: &str = "pid";
+const STREAM_KEY: &str = "stream";
+const STDOUT: &str = "stdout";
+const STDERR: &str = "stderr";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The command can't be empty"))]
+    EmptyCommand,
+    #[snafu(display("`exec_interval_secs` must be greater than zero"))]
+    ZeroInterval,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecConfig {
+    /// The program to run, followed by its arguments. It isn't run in a shell.
+    command: Vec<String>,
+    mode: Mode,
+    #[serde(default)]
+    scheduled: ScheduledConfig,
+    #[serde(default)]
+    streaming: StreamingConfig,
+    working_directory: Option<PathBuf>,
+    #[serde(default = "crate::serde::default_true")]
+    include_stderr: bool,
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+    /// Each line of output is an event, unless this is set.
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    decoding: DecodingConfig,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Mode {
+    /// The command is run every `exec_interval_secs`, and its output is sent on once it exits,
+    /// along with its exit code.
+    Scheduled,
+    /// The command is long-lived, and its output is sent on as it's written.
+    Streaming,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduledConfig {
+    #[serde(default = "default_exec_interval_secs")]
+    exec_interval_secs: u64,
+}
+
+impl Default for ScheduledConfig {
+    fn default() -> Self {
+        Self {
+            exec_interval_secs: default_exec_interval_secs(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StreamingConfig {
+    #[serde(default = "crate::serde::default_true")]
+    respawn_on_exit: bool,
+    #[serde(default = "default_respawn_interval_secs")]
+    respawn_interval_secs: u64,
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            respawn_on_exit: true,
+            respawn_interval_secs: default_respawn_interval_secs(),
+        }
+    }
+}
+
+fn default_exec_interval_secs() -> u64 {
+    60
+}
+
+fn default_respawn_interval_secs() -> u64 {
+    5
+}
+
+fn default_max_length() -> usize {
+    bytesize::kib(100u64) as usize
+}
+
+inventory::submit! {
+    SourceDescription::new::<ExecConfig>("exec")
+}
+
+impl GenerateConfig for ExecConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            command = ["echo", "Hello World!"]
+            mode = "scheduled"
+            scheduled.exec_interval_secs = 60"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exec")]
+impl SourceConfig for ExecConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        if self.command.is_empty() {
+            return Err(BuildError::EmptyCommand.into());
+        }
+        if self.mode == Mode::Scheduled && self.scheduled.exec_interval_secs == 0 {
+            return Err(BuildError::ZeroInterval.into());
+        }
+
+        let decoder = codecs::Decoder::new(
+            self.framing
+                .unwrap_or(FramingConfig::NewlineDelimited)
+                .build(self.max_length),
+            self.decoding,
+        );
+        let metadata = Metadata {
+            hostname: crate::get_hostname().ok(),
+            keys: globals.metadata_keys.clone(),
+        };
+
+        Ok(match self.mode {
+            Mode::Scheduled => Box::pin(run_scheduled(
+                self.clone(),
+                metadata,
+                decoder,
+                shutdown,
+                out,
+            )),
+            Mode::Streaming => Box::pin(run_streaming(
+                self.clone(),
+                metadata,
+                decoder,
+                shutdown,
+                out,
+            )),
+        })
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "exec"
+    }
+}
+
+/// What the source adds to the events of the command.
+struct Metadata {
+    hostname: Option<String>,
+    keys: MetadataKeys,
+}
+
+async fn run_scheduled(
+    config: ExecConfig,
+    metadata: Metadata,
+    decoder: codecs::Decoder,
+    shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    let interval = Duration::from_secs(config.scheduled.exec_interval_secs);
+    let mut ticks = tokio::time::interval(interval).take_until(shutdown.clone());
+
+    while ticks.next().await.is_some() {
+        run_command(
+            &config,
+            &metadata,
+            decoder.clone(),
+            shutdown.clone(),
+            &mut out,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn run_streaming(
+    config: ExecConfig,
+    metadata: Metadata,
+    decoder: codecs::Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    let respawn_interval = Duration::from_secs(config.streaming.respawn_interval_secs);
+
+    loop {
+        run_command(
+            &config,
+            &metadata,
+            decoder.clone(),
+            shutdown.clone(),
+            &mut out,
+        )
+        .await?;
+
+        if !config.streaming.respawn_on_exit {
+            break;
+        }
+        tokio::select! {
+            _ = delay_for(respawn_interval) => (),
+            _ = &mut shutdown => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the command once, until it exits or the source is shut down, which kills it.
+async fn run_command(
+    config: &ExecConfig,
+    metadata: &Metadata,
+    decoder: codecs::Decoder,
+    mut shutdown: ShutdownSignal,
+    out: &mut Pipeline,
+) -> Result<(), ()> {
+    let command_line = config.command.join(" ");
+    let start = Instant::now();
+
+    let mut child = match build_command(config).spawn() {
+        Ok(child) => child,
+        Err(error) => {
+            emit!(ExecFailed {
+                command: &command_line,
+                error,
+            });
+            return Ok(());
+        }
+    };
+    let pid = child.id();
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stdout = FramedRead::new(stdout, decoder.clone()).map(|frame| (STDOUT, frame));
+    let mut output = match child.stderr.take() {
+        Some(stderr) => {
+            let stderr = FramedRead::new(stderr, decoder).map(|frame| (STDERR, frame));
+            stream::select(stdout, stderr).boxed()
+        }
+        None => stdout.boxed(),
+    };
+
+    let mut events = Vec::new();
+    loop {
+        let (stream, frame) = tokio::select! {
+            next = output.next() => match next {
+                Some(next) => next,
+                None => break,
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+        let (event, byte_size) = match frame {
+            Ok(frame) => frame,
+            Err(error) => {
+                emit!(DecoderFramingFailed { error: &error });
+                continue;
+            }
+        };
+        emit!(ExecEventReceived {
+            command: &command_line,
+            byte_size,
+        });
+
+        let event = enrich_event(event, config, metadata, stream, pid);
+        match config.mode {
+            Mode::Scheduled => events.push(event),
+            Mode::Streaming => send(out, vec![event]).await?,
+        }
+    }
+
+    let exit_code = tokio::select! {
+        status = &mut child => match status {
+            Ok(status) => status.code(),
+            Err(error) => {
+                emit!(ExecFailed {
+                    command: &command_line,
+                    error,
+                });
+                None
+            }
+        },
+        _ = &mut shutdown => return Ok(()),
+    };
+    emit!(ExecCommandExecuted {
+        command: &command_line,
+        exit_code,
+        elapsed: start.elapsed(),
+    });
+
+    if let Some(exit_code) = exit_code {
+        for event in &mut events {
+            event.as_mut_log().insert(EXIT_CODE_KEY, exit_code as i64);
+        }
+    }
+    send(out, events).await
+}
+
+fn build_command(config: &ExecConfig) -> Command {
+    let mut command = Command::new(&config.command[0]);
+    command
+        .args(&config.command[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(if config.include_stderr {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .kill_on_drop(true);
+    if let Some(working_directory) = &config.working_directory {
+        command.current_dir(working_directory);
+    }
+    command
+}
+
+fn enrich_event(
+    mut event: Event,
+    config: &ExecConfig,
+    metadata: &Metadata,
+    stream: &'static str,
+    pid: u32,
+) -> Event {
+    let log = event.as_mut_log();
+    log.try_insert(log_schema().timestamp_key(), Utc::now());
+    metadata
+        .keys
+        .source_type
+        .insert(log, log_schema().source_type_key(), Bytes::from("exec"));
+    if let Some(hostname) = &metadata.hostname {
+        metadata
+            .keys
+            .host
+            .insert(log, log_schema().host_key(), hostname.clone());
+    }
+    log.insert(
+        COMMAND_KEY,
+        Value::Array(config.command.iter().cloned().map(Value::from).collect()),
+    );
+    log.insert(PID_KEY, pid as i64);
+    log.insert(STREAM_KEY, stream);
+    event
+}
+
+async fn send(out: &mut Pipeline, events: Vec<Event>) -> Result<(), ()> {
+    out.send_all(&mut stream::iter(events).map(Ok))
+        .await
+        .map_err(|error| error!(message = "Error sending to sink.", %error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::collect_ready;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExecConfig>();
+    }
+
+    fn config(command: &[&str], mode: Mode) -> ExecConfig {
+        ExecConfig {
+            command: command.iter().map(|arg| arg.to_string()).collect(),
+            mode,
+            scheduled: ScheduledConfig::default(),
+            streaming: StreamingConfig {
+                respawn_on_exit: false,
+                ..StreamingConfig::default()
+            },
+            working_directory: None,
+            include_stderr: true,
+            max_length: default_max_length(),
+            framing: None,
+            decoding: DecodingConfig::default(),
+        }
+    }
+
+    async fn run(config: ExecConfig) -> Vec<Event> {
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        source.await.unwrap();
+        collect_ready(rx).await
+    }
+
+    #[tokio::test]
+    async fn rejects_empty_command() {
+        let (tx, _rx) = Pipeline::new_test();
+        let result = config(&[], Mode::Streaming)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn streams_output_lines() {
+        let events = run(config(
+            &["sh", "-c", "echo first; echo second"],
+            Mode::Streaming,
+        ))
+        .await;
+
+        assert_eq!(events.len(), 2);
+        for (event, line) in events.iter().zip(&["first", "second"]) {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], (*line).into());
+            assert_eq!(log[STREAM_KEY], STDOUT.into());
+            assert_eq!(log[log_schema().source_type_key()], "exec".into());
+            assert!(log.contains(PID_KEY));
+            assert!(!log.contains(EXIT_CODE_KEY));
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn scheduled_output_has_exit_code() {
+        let config = config(
+            &["sh", "-c", "echo out; echo err >&2; exit 3"],
+            Mode::Scheduled,
+        );
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let source = config
+            .build("default", &GlobalOptions::default(), shutdown, tx)
+            .await
+            .unwrap();
+        let source = tokio::spawn(source);
+
+        let mut events = crate::test_util::collect_n(rx, 2).await;
+        drop(trigger);
+        source.await.unwrap().unwrap();
+
+        events.sort_by_key(|event| event.as_log()[STREAM_KEY].to_string_lossy());
+        let expected = [("err", STDERR), ("out", STDOUT)];
+        for (event, (line, stream)) in events.iter().zip(&expected) {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], (*line).into());
+            assert_eq!(log[STREAM_KEY], (*stream).into());
+            assert_eq!(log[EXIT_CODE_KEY], Value::Integer(3));
+            assert_eq!(
+                log[COMMAND_KEY],
+                Value::Array(vec![
+                    "sh".into(),
+                    "-c".into(),
+                    config.command[2].clone().into()
+                ])
+            );
+        }
+    }
+}
@@ -1,9 +1,13 @@
 use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
-    config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
+    codecs::DecodingConfig,
+    config::{log_schema, DataType, GlobalOptions, MetadataKeys, SourceConfig, SourceDescription},
     encoding_transcode::{Decoder, Encoder},
-    event::Event,
-    internal_events::{FileEventReceived, FileOpen, FileSourceInternalEventsEmitter},
+    event::{BatchNotifier, BatchStatusReceiver, Event, EventStatus},
+    internal_events::{
+        DecoderDeserializeFailed, FileEventNotDelivered, FileEventReceived, FileOpen,
+        FileSourceInternalEventsEmitter,
+    },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
     trace::{current_span, Instrument},
@@ -13,18 +17,23 @@ use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
-    FileServer, FingerprintStrategy, Fingerprinter, ReadFrom,
+    CheckpointsView, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line,
+    ReadFrom,
 };
 use futures::{
-    future::TryFutureExt,
+    channel::mpsc::{self, UnboundedReceiver},
+    future::{self, TryFutureExt},
     stream::{Stream, StreamExt},
     SinkExt,
 };
+use lazy_static::lazy_static;
 use regex::bytes::Regex;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use tokio::task::spawn_blocking;
 
@@ -88,6 +97,8 @@ pub struct FileConfig {
     pub remove_after_secs: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    pub decoding: DecodingConfig,
+    pub acknowledgements: bool,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -174,6 +185,8 @@ impl Default for FileConfig {
             remove_after_secs: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            decoding: DecodingConfig::default(),
+            acknowledgements: false,
         }
     }
 }
@@ -213,7 +226,17 @@ impl SourceConfig for FileConfig {
             }
         }
 
-        Ok(file_source(self, data_dir, shutdown, out))
+        let checkpoints = Arc::new(CheckpointsView::default());
+        register_checkpoints(name, &checkpoints);
+
+        Ok(file_source(
+            self,
+            data_dir,
+            checkpoints,
+            globals.metadata_keys.clone(),
+            shutdown,
+            out,
+        ))
     }
 
     fn output_type(&self) -> DataType {
@@ -225,9 +248,32 @@ impl SourceConfig for FileConfig {
     }
 }
 
+lazy_static! {
+    static ref CHECKPOINTS: Mutex<HashMap<String, Weak<CheckpointsView>>> = Default::default();
+}
+
+/// Registers the checkpoints of the file source `name`, so that they can be inspected
+/// through the API while the source is running.
+fn register_checkpoints(name: &str, checkpoints: &Arc<CheckpointsView>) {
+    let mut registry = CHECKPOINTS.lock().expect("Checkpoints mutex is poisoned");
+    registry.retain(|_, checkpoints| checkpoints.strong_count() > 0);
+    registry.insert(name.to_owned(), Arc::downgrade(checkpoints));
+}
+
+/// The checkpoints of the running file source `name`, if there is one.
+pub fn checkpoints(name: &str) -> Option<Arc<CheckpointsView>> {
+    CHECKPOINTS
+        .lock()
+        .expect("Checkpoints mutex is poisoned")
+        .get(name)
+        .and_then(Weak::upgrade)
+}
+
 pub fn file_source(
     config: &FileConfig,
     data_dir: PathBuf,
+    checkpoints: Arc<CheckpointsView>,
+    metadata_keys: MetadataKeys,
     shutdown: ShutdownSignal,
     mut out: Pipeline,
 ) -> super::Source {
@@ -277,6 +323,8 @@ pub fn file_source(
         remove_after: config.remove_after_secs.map(Duration::from_secs),
         emitter: FileSourceInternalEventsEmitter,
         handle: tokio::runtime::Handle::current(),
+        checkpoints: Arc::clone(&checkpoints),
+        acknowledgements: config.acknowledgements,
     };
 
     let file_key = config.file_key.clone();
@@ -291,6 +339,13 @@ pub fn file_source(
     let multiline_config = config.multiline.clone();
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
+    let decoding = config.decoding;
+    let (ack_tx, ack_rx) = if config.acknowledgements {
+        let (ack_tx, ack_rx) = mpsc::unbounded();
+        (Some(ack_tx), Some(ack_rx))
+    } else {
+        (None, None)
+    };
 
     Box::pin(async move {
         info!(message = "Starting file server.", include = ?include, exclude = ?exclude);
@@ -298,19 +353,19 @@ pub fn file_source(
         let mut encoding_decoder = encoding_charset.map(|e| Decoder::new(e));
 
         // sizing here is just a guess
-        let (tx, rx) = futures::channel::mpsc::channel::<Vec<(Bytes, String)>>(2);
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
         let rx = rx
             .map(futures::stream::iter)
             .flatten()
-            .map(move |(line, src)| {
+            .map(move |mut line: Line| {
                 // transcode each line from the file's encoding charset to utf8
-                match encoding_decoder.as_mut() {
-                    Some(d) => (d.decode_to_utf8(line), src),
-                    None => (line, src),
+                if let Some(d) = encoding_decoder.as_mut() {
+                    line.text = d.decode_to_utf8(line.text);
                 }
+                line
             });
 
-        let messages: Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin> =
+        let messages: Box<dyn Stream<Item = Message> + Send + std::marker::Unpin> =
             if let Some(ref multiline_config) = multiline_config {
                 wrap_with_line_agg(
                     rx,
@@ -325,17 +380,50 @@ pub fn file_source(
                     ),
                 )
             } else {
-                Box::new(rx)
+                Box::new(rx.map(|line| {
+                    let checkpoint = Some((line.file_id, line.offset));
+                    (line.text, line.filename, checkpoint)
+                }))
             };
 
+        if let Some(ack_rx) = ack_rx {
+            tokio::spawn(checkpoint_acknowledged(ack_rx, checkpoints).instrument(current_span()));
+        }
+
         // Once file server ends this will run until it has finished processing remaining
         // logs in the queue.
         let span = current_span();
         let span2 = span.clone();
         let mut messages = messages
-            .map(move |(msg, file): (Bytes, String)| {
+            .filter_map(move |(msg, file, checkpoint): Message| {
                 let _enter = span2.enter();
-                create_event(msg, file, &host_key, &hostname, &file_key)
+                let batch = match (&ack_tx, checkpoint) {
+                    (Some(ack_tx), Some((file_id, offset))) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        // Lines that aren't turned into events are acknowledged
+                        // as soon as the batch is dropped.
+                        let _ = ack_tx.unbounded_send((receiver, file.clone(), file_id, offset));
+                        Some(batch)
+                    }
+                    _ => None,
+                };
+                future::ready(
+                    create_event(
+                        msg,
+                        file,
+                        decoding,
+                        &host_key,
+                        &hostname,
+                        &file_key,
+                        &metadata_keys,
+                    )
+                    .map(|mut event| {
+                        if let Some(batch) = batch {
+                            event.add_batch_notifier(batch);
+                        }
+                        event
+                    }),
+                )
             })
             .map(Ok);
         tokio::spawn(async move { out.send_all(&mut messages).instrument(span).await });
@@ -378,45 +466,91 @@ fn reconcile_position_options(
     }
 }
 
+/// The text of a message, the file it was read from, and the position the file
+/// can be checkpointed at once the message is delivered.
+type Message = (Bytes, String, Option<(FileFingerprint, u64)>);
+
 fn wrap_with_line_agg(
-    rx: impl Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static,
+    rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
     config: line_agg::Config,
-) -> Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static> {
+) -> Box<dyn Stream<Item = Message> + Send + std::marker::Unpin + 'static> {
     let logic = line_agg::Logic::new(config);
-    Box::new(
-        LineAgg::new(rx.map(|(line, src)| (src, line, ())), logic)
-            .map(|(src, line, _context)| (line, src)),
-    )
+    // An aggregated message keeps the context of its first line, so the file
+    // is checkpointed where the message starts, after the previous line read.
+    let mut line_ends = HashMap::new();
+    let rx = rx.map(move |line: Line| {
+        let checkpoint = line_ends
+            .insert(line.file_id, line.offset)
+            .map(|start| (line.file_id, start));
+        (line.filename, line.text, checkpoint)
+    });
+    Box::new(LineAgg::new(rx, logic).map(|(src, line, checkpoint)| (line, src, checkpoint)))
+}
+
+/// Checkpoints files up to the messages read from them that were delivered, in
+/// the order they were read. A file isn't checkpointed any further once one of
+/// its messages isn't delivered, so that it's read again from there after a
+/// restart.
+async fn checkpoint_acknowledged(
+    mut acks: UnboundedReceiver<(BatchStatusReceiver, String, FileFingerprint, u64)>,
+    checkpoints: Arc<CheckpointsView>,
+) {
+    let mut failed = HashSet::new();
+    while let Some((receiver, file, file_id, offset)) = acks.next().await {
+        let status = receiver.await;
+        if failed.contains(&file_id) {
+            continue;
+        }
+        if status == EventStatus::Delivered {
+            checkpoints.acknowledge(file_id, offset);
+        } else {
+            emit!(FileEventNotDelivered {
+                file: &file,
+                status
+            });
+            failed.insert(file_id);
+        }
+    }
 }
 
 fn create_event(
     line: Bytes,
     file: String,
+    decoding: DecodingConfig,
     host_key: &str,
     hostname: &Option<String>,
     file_key: &Option<String>,
-) -> Event {
+    metadata_keys: &MetadataKeys,
+) -> Option<Event> {
     emit!(FileEventReceived {
         file: &file,
         byte_size: line.len(),
     });
 
-    let mut event = Event::from(line);
+    let mut event = match decoding.decode(line) {
+        Ok(event) => event,
+        Err(error) => {
+            emit!(DecoderDeserializeFailed { error: &error });
+            return None;
+        }
+    };
+
+    let log = event.as_mut_log();
 
     // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("file"));
+    metadata_keys
+        .source_type
+        .insert(log, log_schema().source_type_key(), Bytes::from("file"));
 
     if let Some(file_key) = &file_key {
-        event.as_mut_log().insert(file_key.clone(), file);
+        log.insert(file_key.clone(), file);
     }
 
     if let Some(hostname) = &hostname {
-        event.as_mut_log().insert(host_key, hostname.clone());
+        metadata_keys.host.insert(log, host_key, hostname.clone());
     }
 
-    event
+    Some(event)
 }
 
 #[cfg(test)]
@@ -564,7 +698,16 @@ mod tests {
         let hostname = Some("Some.Machine".to_string());
         let file_key = Some("file".to_string());
 
-        let event = create_event(line, file, &host_key, &hostname, &file_key);
+        let event = create_event(
+            line,
+            file,
+            DecodingConfig::Bytes,
+            &host_key,
+            &hostname,
+            &file_key,
+            &MetadataKeys::default(),
+        )
+        .unwrap();
         let log = event.into_log();
 
         assert_eq!(log["file"], "some_file.rs".into());
@@ -573,6 +716,30 @@ mod tests {
         assert_eq!(log[log_schema().source_type_key()], "file".into());
     }
 
+    #[test]
+    fn file_create_event_decodes_json() {
+        let decode = |line: &'static str| {
+            create_event(
+                Bytes::from(line),
+                "some_file.rs".to_string(),
+                DecodingConfig::Json,
+                "host",
+                &None,
+                &None,
+                &MetadataKeys::default(),
+            )
+        };
+
+        let log = decode(r#"{"message": "hello world", "level": "info"}"#)
+            .unwrap()
+            .into_log();
+        assert_eq!(log[log_schema().message_key()], "hello world".into());
+        assert_eq!(log["level"], "info".into());
+        assert_eq!(log[log_schema().source_type_key()], "file".into());
+
+        assert!(decode("hello world").is_none());
+    }
+
     #[tokio::test]
     async fn file_happy_path() {
         let n = 5;
@@ -585,7 +752,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path1 = dir.path().join("file1");
@@ -631,6 +805,56 @@ mod tests {
         assert_eq!(goodbye_i, n);
     }
 
+    #[tokio::test]
+    async fn file_acknowledgements() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+        let checkpoints = Arc::new(CheckpointsView::default());
+
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            acknowledgements: true,
+            ..test_default_file_config(&dir)
+        };
+
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Arc::clone(&checkpoints),
+            Default::default(),
+            shutdown,
+            tx,
+        );
+        tokio::spawn(source);
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        for line in &["first line", "second line", "third line"] {
+            writeln!(&mut file, "{}", line).unwrap();
+        }
+        sleep_500_millis().await;
+
+        let position = || checkpoints.watched_files()[0].position;
+        let first = rx.next().await.unwrap();
+        assert_eq!(position(), 0);
+
+        // The file is checkpointed once the event of the line is delivered.
+        drop(first);
+        sleep_500_millis().await;
+        assert_eq!(position(), "first line\n".len() as u64);
+
+        // It isn't checkpointed any further once an event wasn't delivered.
+        let mut second = rx.next().await.unwrap();
+        second.take_finalizers().update_status(EventStatus::Failed);
+        drop(second);
+        drop(rx.next().await.unwrap());
+        sleep_500_millis().await;
+        assert_eq!(position(), "first line\n".len() as u64);
+
+        drop(trigger_shutdown);
+    }
+
     #[tokio::test]
     async fn file_truncate() {
         let n = 5;
@@ -642,7 +866,14 @@ mod tests {
             include: vec![dir.path().join("*")],
             ..test_default_file_config(&dir)
         };
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -707,7 +938,14 @@ mod tests {
             include: vec![dir.path().join("*")],
             ..test_default_file_config(&dir)
         };
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -775,7 +1013,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path1 = dir.path().join("a.txt");
@@ -831,7 +1076,14 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
 
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             let path = dir.path().join("file");
@@ -865,7 +1117,14 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
 
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             let path = dir.path().join("file");
@@ -899,7 +1158,14 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
 
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             let path = dir.path().join("file");
@@ -947,7 +1213,14 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             sleep_500_millis().await;
@@ -968,7 +1241,14 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             sleep_500_millis().await;
@@ -995,7 +1275,14 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             sleep_500_millis().await;
@@ -1031,7 +1318,14 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             let mut file = File::create(&path).unwrap();
@@ -1056,7 +1350,14 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                Default::default(),
+                Default::default(),
+                shutdown,
+                tx,
+            );
             tokio::spawn(source);
 
             let mut file = File::create(&path).unwrap();
@@ -1090,7 +1391,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let before_path = dir.path().join("before");
@@ -1165,7 +1473,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1223,7 +1538,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1297,7 +1619,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1385,7 +1714,14 @@ mod tests {
 
         sleep_500_millis().await;
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1448,7 +1784,14 @@ mod tests {
 
         sleep_500_millis().await;
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1499,7 +1842,14 @@ mod tests {
 
         sleep_500_millis().await;
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1554,7 +1904,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1597,7 +1954,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1640,7 +2004,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1696,7 +2067,14 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            Default::default(),
+            Default::default(),
+            shutdown,
+            tx,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1,6 +1,6 @@
 use crate::{
     config::{
-        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        log_schema, DataType, GenerateConfig, GlobalOptions, MetadataKeys, Resource, SourceConfig,
         SourceDescription,
     },
     event::Event,
@@ -54,6 +54,7 @@ impl GenerateConfig for LogplexConfig {
 #[derive(Clone, Default)]
 struct LogplexSource {
     query_parameters: Vec<String>,
+    metadata_keys: MetadataKeys,
 }
 
 impl HttpSource for LogplexSource {
@@ -64,7 +65,7 @@ impl HttpSource for LogplexSource {
         query_parameters: HashMap<String, String>,
         _full_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        decode_message(body, header_map)
+        decode_message(body, header_map, &self.metadata_keys)
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
     }
 }
@@ -75,12 +76,13 @@ impl SourceConfig for LogplexConfig {
     async fn build(
         &self,
         _: &str,
-        _: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
+            metadata_keys: globals.metadata_keys.clone(),
         };
         source.run(
             self.address,
@@ -136,7 +138,11 @@ impl SourceConfig for LogplexCompatConfig {
     }
 }
 
-fn decode_message(body: Bytes, header_map: HeaderMap) -> Result<Vec<Event>, ErrorMessage> {
+fn decode_message(
+    body: Bytes,
+    header_map: HeaderMap,
+    metadata_keys: &MetadataKeys,
+) -> Result<Vec<Event>, ErrorMessage> {
     // Deal with headers
     let msg_count = match usize::from_str(get_header(&header_map, "Logplex-Msg-Count")?) {
         Ok(v) => v,
@@ -152,7 +158,7 @@ fn decode_message(body: Bytes, header_map: HeaderMap) -> Result<Vec<Event>, Erro
     });
 
     // Deal with body
-    let events = body_to_events(body);
+    let events = body_to_events(body, metadata_keys);
 
     if events.len() != msg_count {
         let error_msg = format!(
@@ -187,7 +193,7 @@ fn header_error_message(name: &str, msg: &str) -> ErrorMessage {
     )
 }
 
-fn body_to_events(body: Bytes) -> Vec<Event> {
+fn body_to_events(body: Bytes, metadata_keys: &MetadataKeys) -> Vec<Event> {
     let rdr = BufReader::new(body.reader());
     rdr.lines()
         .filter_map(|res| {
@@ -195,11 +201,11 @@ fn body_to_events(body: Bytes) -> Vec<Event> {
                 .ok()
         })
         .filter(|s| !s.is_empty())
-        .map(line_to_event)
+        .map(|line| line_to_event(line, metadata_keys))
         .collect()
 }
 
-fn line_to_event(line: String) -> Event {
+fn line_to_event(line: String, metadata_keys: &MetadataKeys) -> Event {
     let parts = line.splitn(8, ' ').collect::<Vec<&str>>();
 
     let mut event = if parts.len() == 8 {
@@ -216,7 +222,9 @@ fn line_to_event(line: String) -> Event {
             log.insert(log_schema().timestamp_key(), ts);
         }
 
-        log.insert(log_schema().host_key(), hostname.to_owned());
+        metadata_keys
+            .host
+            .insert(log, log_schema().host_key(), hostname.to_owned());
 
         log.insert("app_name", app_name.to_owned());
         log.insert("proc_id", proc_id.to_owned());
@@ -232,9 +240,11 @@ fn line_to_event(line: String) -> Event {
     };
 
     // Add source type
-    event
-        .as_mut_log()
-        .try_insert(log_schema().source_type_key(), Bytes::from("heroku_logs"));
+    metadata_keys.source_type.try_insert(
+        event.as_mut_log(),
+        log_schema().source_type_key(),
+        "heroku_logs",
+    );
 
     event
 }
@@ -244,7 +254,7 @@ mod tests {
     use super::{HttpSourceAuthConfig, LogplexConfig};
     use crate::shutdown::ShutdownSignal;
     use crate::{
-        config::{log_schema, GlobalOptions, SourceConfig},
+        config::{log_schema, GlobalOptions, MetadataKeys, SourceConfig},
         event::{Event, Value},
         test_util::{collect_n, next_addr, trace_init, wait_for_tcp},
         Pipeline,
@@ -355,7 +365,7 @@ mod tests {
     #[test]
     fn logplex_handles_normal_lines() {
         let body = "267 <158>1 2020-01-08T22:33:57.353034+00:00 host heroku router - foo bar baz";
-        let event = super::line_to_event(body.into());
+        let event = super::line_to_event(body.into(), &MetadataKeys::default());
         let log = event.as_log();
 
         assert_eq!(log[log_schema().message_key()], "foo bar baz".into());
@@ -373,7 +383,7 @@ mod tests {
     #[test]
     fn logplex_handles_malformed_lines() {
         let body = "what am i doing here";
-        let event = super::line_to_event(body.into());
+        let event = super::line_to_event(body.into(), &MetadataKeys::default());
         let log = event.as_log();
 
         assert_eq!(
@@ -387,7 +397,7 @@ mod tests {
     #[test]
     fn logplex_doesnt_blow_up_on_bad_framing() {
         let body = "1000000 <158>1 2020-01-08T22:33:57.353034+00:00 host heroku router - i'm not that long";
-        let event = super::line_to_event(body.into());
+        let event = super::line_to_event(body.into(), &MetadataKeys::default());
         let log = event.as_log();
 
         assert_eq!(log[log_schema().message_key()], "i'm not that long".into());
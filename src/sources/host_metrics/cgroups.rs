@@ -0,0 +1,392 @@
+//! Metrics of the cgroup Vector is running in. Inside a container these are the
+//! ones that matter, as the rest of the host metrics describe the whole node.
+
+use super::HostMetricsConfig;
+use crate::event::metric::Metric;
+use chrono::Utc;
+use shared::btreemap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const MICROSECONDS: f64 = 1.0 / 1_000_000.0;
+const NANOSECONDS: f64 = 1.0 / 1_000_000_000.0;
+
+/// cgroup v1 reports "no limit" as the largest page-aligned 64-bit value, so
+/// anything above this is treated as unlimited.
+const V1_UNLIMITED: u64 = 1 << 62;
+
+#[derive(Debug, PartialEq)]
+enum Cgroup {
+    /// The directory of the cgroup in the unified hierarchy.
+    V2(PathBuf),
+    /// The directories of the cgroup in each of the hierarchies of interest.
+    V1 {
+        cpu: PathBuf,
+        cpuacct: PathBuf,
+        memory: PathBuf,
+    },
+}
+
+fn cgroup_root() -> PathBuf {
+    std::env::var_os("SYSFS_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/sys"))
+        .join("fs/cgroup")
+}
+
+/// Finds the cgroup of this process from `/proc/self/cgroup`.
+///
+/// With a cgroup namespace, or without one in most container runtimes, the
+/// cgroup path isn't visible below the mount, whose root is then the cgroup
+/// itself.
+fn find_cgroup(root: &Path, membership: &str) -> Option<Cgroup> {
+    let resolve = |hierarchy: &Path, path: &str| {
+        let dir = hierarchy.join(path.trim_start_matches('/'));
+        if dir.is_dir() {
+            dir
+        } else {
+            hierarchy.to_owned()
+        }
+    };
+
+    if root.join("cgroup.controllers").is_file() {
+        let path = membership
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))?;
+        Some(Cgroup::V2(resolve(root, path)))
+    } else {
+        let controller = |name: &str| {
+            let path = membership.lines().find_map(|line| {
+                let mut parts = line.splitn(3, ':').skip(1);
+                let controllers = parts.next()?;
+                let path = parts.next()?;
+                controllers.split(',').any(|c| c == name).then(|| path)
+            })?;
+            Some(resolve(&root.join(name), path))
+        };
+
+        Some(Cgroup::V1 {
+            cpu: controller("cpu")?,
+            cpuacct: controller("cpuacct")?,
+            memory: controller("memory")?,
+        })
+    }
+}
+
+/// Parses flat keyed files such as `cpu.stat`.
+fn parse_keyed(contents: &str) -> impl Iterator<Item = (&str, u64)> {
+    contents.lines().filter_map(|line| {
+        let mut parts = line.split_whitespace();
+        let key = parts.next()?;
+        let value = parts.next()?.parse().ok()?;
+        Some((key, value))
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct PressureStall<'a> {
+    /// Either `some`, when at least one task was stalled, or `full`, when all
+    /// of them were.
+    kind: &'a str,
+    /// Percentages of time stalled over the last 10, 60 and 300 seconds.
+    averages: [(&'a str, f64); 3],
+    total_usec: u64,
+}
+
+/// Parses pressure stall information files such as `cpu.pressure`.
+fn parse_pressure(contents: &str) -> Vec<PressureStall<'_>> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let kind = parts.next()?;
+
+            let mut averages = [("10s", 0.0), ("60s", 0.0), ("300s", 0.0)];
+            let mut total_usec = 0;
+            for part in parts {
+                let mut pair = part.splitn(2, '=');
+                match (pair.next()?, pair.next()?) {
+                    ("avg10", value) => averages[0].1 = value.parse().ok()?,
+                    ("avg60", value) => averages[1].1 = value.parse().ok()?,
+                    ("avg300", value) => averages[2].1 = value.parse().ok()?,
+                    ("total", value) => total_usec = value.parse().ok()?,
+                    _ => {}
+                }
+            }
+
+            Some(PressureStall {
+                kind,
+                averages,
+                total_usec,
+            })
+        })
+        .collect()
+}
+
+/// Missing files, such as those of pressure stall information when the kernel
+/// doesn't support it, are skipped.
+async fn read(path: impl AsRef<Path>) -> Option<String> {
+    fs::read_to_string(path).await.ok()
+}
+
+impl HostMetricsConfig {
+    pub async fn cgroups_metrics(&self) -> Vec<Metric> {
+        let root = cgroup_root();
+        let cgroup = match read("/proc/self/cgroup").await {
+            Some(membership) => find_cgroup(&root, &membership),
+            None => None,
+        };
+
+        match cgroup {
+            Some(Cgroup::V2(dir)) => self.cgroups_v2_metrics(&dir).await,
+            Some(Cgroup::V1 {
+                cpu,
+                cpuacct,
+                memory,
+            }) => self.cgroups_v1_metrics(&cpu, &cpuacct, &memory).await,
+            None => {
+                debug!(
+                    message = "Not running in a cgroup; no cgroup metrics.",
+                    internal_log_rate_secs = 60
+                );
+                vec![]
+            }
+        }
+    }
+
+    async fn cgroups_v2_metrics(&self, dir: &Path) -> Vec<Metric> {
+        let timestamp = Utc::now();
+        let mut metrics = Vec::new();
+
+        if let Some(stat) = read(dir.join("cpu.stat")).await {
+            for (key, value) in parse_keyed(&stat) {
+                let (name, value) = match key {
+                    "usage_usec" => (
+                        "cgroup_cpu_usage_seconds_total",
+                        value as f64 * MICROSECONDS,
+                    ),
+                    "user_usec" => ("cgroup_cpu_user_seconds_total", value as f64 * MICROSECONDS),
+                    "system_usec" => (
+                        "cgroup_cpu_system_seconds_total",
+                        value as f64 * MICROSECONDS,
+                    ),
+                    "nr_periods" => ("cgroup_cpu_periods_total", value as f64),
+                    "nr_throttled" => ("cgroup_cpu_throttled_periods_total", value as f64),
+                    "throttled_usec" => (
+                        "cgroup_cpu_throttled_seconds_total",
+                        value as f64 * MICROSECONDS,
+                    ),
+                    _ => continue,
+                };
+                metrics.push(self.counter(name, timestamp, value, btreemap! {}));
+            }
+        }
+
+        if let Some(current) = read(dir.join("memory.current")).await {
+            if let Ok(current) = current.trim().parse::<u64>() {
+                metrics.push(self.gauge(
+                    "cgroup_memory_current_bytes",
+                    timestamp,
+                    current as f64,
+                    btreemap! {},
+                ));
+            }
+        }
+        if let Some(max) = read(dir.join("memory.max")).await {
+            // An unlimited cgroup reports "max".
+            if let Ok(max) = max.trim().parse::<u64>() {
+                metrics.push(self.gauge(
+                    "cgroup_memory_limit_bytes",
+                    timestamp,
+                    max as f64,
+                    btreemap! {},
+                ));
+            }
+        }
+
+        for resource in &["cpu", "memory", "io"] {
+            let pressure = match read(dir.join(format!("{}.pressure", resource))).await {
+                Some(pressure) => pressure,
+                None => continue,
+            };
+            for stall in parse_pressure(&pressure) {
+                for (window, average) in &stall.averages {
+                    metrics.push(self.gauge(
+                        "cgroup_pressure_stall_ratio",
+                        timestamp,
+                        average / 100.0,
+                        btreemap! {
+                            "resource" => *resource,
+                            "type" => stall.kind,
+                            "window" => *window,
+                        },
+                    ));
+                }
+                metrics.push(self.counter(
+                    "cgroup_pressure_stall_seconds_total",
+                    timestamp,
+                    stall.total_usec as f64 * MICROSECONDS,
+                    btreemap! {
+                        "resource" => *resource,
+                        "type" => stall.kind,
+                    },
+                ));
+            }
+        }
+
+        metrics
+    }
+
+    async fn cgroups_v1_metrics(&self, cpu: &Path, cpuacct: &Path, memory: &Path) -> Vec<Metric> {
+        let timestamp = Utc::now();
+        let mut metrics = Vec::new();
+
+        if let Some(usage) = read(cpuacct.join("cpuacct.usage")).await {
+            if let Ok(usage) = usage.trim().parse::<u64>() {
+                metrics.push(self.counter(
+                    "cgroup_cpu_usage_seconds_total",
+                    timestamp,
+                    usage as f64 * NANOSECONDS,
+                    btreemap! {},
+                ));
+            }
+        }
+
+        if let Some(stat) = read(cpu.join("cpu.stat")).await {
+            for (key, value) in parse_keyed(&stat) {
+                let (name, value) = match key {
+                    "nr_periods" => ("cgroup_cpu_periods_total", value as f64),
+                    "nr_throttled" => ("cgroup_cpu_throttled_periods_total", value as f64),
+                    "throttled_time" => (
+                        "cgroup_cpu_throttled_seconds_total",
+                        value as f64 * NANOSECONDS,
+                    ),
+                    _ => continue,
+                };
+                metrics.push(self.counter(name, timestamp, value, btreemap! {}));
+            }
+        }
+
+        if let Some(usage) = read(memory.join("memory.usage_in_bytes")).await {
+            if let Ok(usage) = usage.trim().parse::<u64>() {
+                metrics.push(self.gauge(
+                    "cgroup_memory_current_bytes",
+                    timestamp,
+                    usage as f64,
+                    btreemap! {},
+                ));
+            }
+        }
+        if let Some(limit) = read(memory.join("memory.limit_in_bytes")).await {
+            match limit.trim().parse::<u64>() {
+                Ok(limit) if limit < V1_UNLIMITED => metrics.push(self.gauge(
+                    "cgroup_memory_limit_bytes",
+                    timestamp,
+                    limit as f64,
+                    btreemap! {},
+                )),
+                _ => {}
+            }
+        }
+
+        metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_keyed_files() {
+        let stat = "usage_usec 2500000\nnr_throttled 3\nbogus\n";
+        assert_eq!(
+            parse_keyed(stat).collect::<Vec<_>>(),
+            vec![("usage_usec", 2_500_000), ("nr_throttled", 3)]
+        );
+    }
+
+    #[test]
+    fn parses_pressure_stall_information() {
+        let pressure = "some avg10=1.50 avg60=0.25 avg300=0.00 total=123456\n\
+                        full avg10=0.00 avg60=0.00 avg300=0.00 total=42\n";
+        assert_eq!(
+            parse_pressure(pressure),
+            vec![
+                PressureStall {
+                    kind: "some",
+                    averages: [("10s", 1.5), ("60s", 0.25), ("300s", 0.0)],
+                    total_usec: 123_456,
+                },
+                PressureStall {
+                    kind: "full",
+                    averages: [("10s", 0.0), ("60s", 0.0), ("300s", 0.0)],
+                    total_usec: 42,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_v2_cgroup() {
+        let root = tempdir().unwrap();
+        std::fs::write(root.path().join("cgroup.controllers"), "cpu memory io").unwrap();
+        std::fs::create_dir_all(root.path().join("system.slice/vector.service")).unwrap();
+
+        assert_eq!(
+            find_cgroup(root.path(), "0::/system.slice/vector.service\n"),
+            Some(Cgroup::V2(root.path().join("system.slice/vector.service")))
+        );
+        // Not visible below the mount, as in a container.
+        assert_eq!(
+            find_cgroup(root.path(), "0::/kubepods/pod1234/abcd\n"),
+            Some(Cgroup::V2(root.path().to_owned()))
+        );
+    }
+
+    #[test]
+    fn finds_v1_cgroup() {
+        let root = tempdir().unwrap();
+        let membership = "12:memory:/docker/abcd\n\
+                          4:cpu,cpuacct:/docker/abcd\n\
+                          1:name=systemd:/docker/abcd\n";
+
+        assert_eq!(
+            find_cgroup(root.path(), membership),
+            Some(Cgroup::V1 {
+                cpu: root.path().join("cpu"),
+                cpuacct: root.path().join("cpuacct"),
+                memory: root.path().join("memory"),
+            })
+        );
+        assert_eq!(find_cgroup(root.path(), "1:name=systemd:/\n"), None);
+    }
+
+    #[tokio::test]
+    async fn reads_v2_metrics() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("cpu.stat"),
+            "usage_usec 2000000\nnr_periods 10\nnr_throttled 2\nthrottled_usec 500000\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("memory.current"), "1048576\n").unwrap();
+        std::fs::write(dir.path().join("memory.max"), "max\n").unwrap();
+
+        let metrics = HostMetricsConfig::default()
+            .cgroups_v2_metrics(dir.path())
+            .await;
+        let names = metrics.iter().map(|m| m.name()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "cgroup_cpu_usage_seconds_total",
+                "cgroup_cpu_periods_total",
+                "cgroup_cpu_throttled_periods_total",
+                "cgroup_cpu_throttled_seconds_total",
+                "cgroup_memory_current_bytes",
+            ]
+        );
+    }
+}
@@ -37,9 +37,12 @@ use std::fmt;
 use std::path::Path;
 use tokio::time;
 
+mod cgroups;
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 enum Collector {
+    Cgroups,
     Cpu,
     Disk,
     Filesystem,
@@ -164,6 +167,9 @@ impl HostMetricsConfig {
     async fn capture_metrics(&self) -> impl Iterator<Item = Event> {
         let hostname = crate::get_hostname();
         let mut metrics = Vec::new();
+        if self.has_collector(Collector::Cgroups) {
+            metrics.extend(add_collector("cgroups", self.cgroups_metrics().await));
+        }
         if self.has_collector(Collector::Cpu) {
             metrics.extend(add_collector("cpu", self.cpu_metrics().await));
         }
@@ -1,11 +1,15 @@
 use crate::{
+    codecs::{DecodingConfig, Framer, FramingConfig},
     config::{
-        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        log_schema, DataType, GenerateConfig, GlobalOptions, MetadataKeys, Resource, SourceConfig,
         SourceDescription,
     },
     event::{Event, Value},
     shutdown::ShutdownSignal,
-    sources::util::{add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    sources::util::{
+        add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        SchemaValidationConfig, SchemaValidator,
+    },
     tls::TlsConfig,
     Pipeline,
 };
@@ -24,6 +28,8 @@ pub struct SimpleHttpConfig {
     address: SocketAddr,
     #[serde(default)]
     encoding: Encoding,
+    framing: Option<FramingConfig>,
+    decoding: Option<DecodingConfig>,
     #[serde(default)]
     headers: Vec<String>,
     #[serde(default)]
@@ -36,6 +42,7 @@ pub struct SimpleHttpConfig {
     path: String,
     #[serde(default = "default_path_key")]
     path_key: String,
+    schema_validation: Option<SchemaValidationConfig>,
 }
 
 inventory::submit! {
@@ -47,6 +54,8 @@ impl GenerateConfig for SimpleHttpConfig {
         toml::Value::try_from(Self {
             address: "0.0.0.0:8080".parse().unwrap(),
             encoding: Default::default(),
+            framing: None,
+            decoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
             tls: None,
@@ -54,6 +63,7 @@ impl GenerateConfig for SimpleHttpConfig {
             path_key: "path".to_string(),
             path: "/".to_string(),
             strict_path: true,
+            schema_validation: None,
         })
         .unwrap()
     }
@@ -70,9 +80,13 @@ pub fn default_path_key() -> String {
 #[derive(Clone)]
 struct SimpleHttpSource {
     encoding: Encoding,
+    framing: Option<FramingConfig>,
+    decoding: Option<DecodingConfig>,
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: String,
+    schema_validator: Option<SchemaValidator>,
+    metadata_keys: MetadataKeys,
 }
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative, Copy)]
@@ -83,6 +97,8 @@ pub enum Encoding {
     Text,
     Ndjson,
     Json,
+    /// Avro decoded with the schema of `schema_validation`.
+    Avro,
 }
 
 impl HttpSource for SimpleHttpSource {
@@ -93,15 +109,36 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        decode_body(body, self.encoding)
+        let schema_validator = self.schema_validator.as_ref();
+        let events = match (self.framing, self.decoding) {
+            (None, None) => decode_body(body, self.encoding, schema_validator),
+            // Each frame is decoded as set by `encoding`, unless `decoding` is set.
+            (Some(framing), None) => decode_frames(body, framing.build(usize::MAX), |frame| {
+                decode_body(frame, self.encoding, schema_validator)
+            }),
+            // The body is a single frame, unless the `framing` option is set.
+            (framing, Some(decoding)) => {
+                let framer = framing.unwrap_or(FramingConfig::Bytes).build(usize::MAX);
+                decode_frames(body, framer, |frame| decode_frame(frame, decoding))
+            }
+        };
+
+        events
             .map(|events| add_headers(events, &self.headers, header_map))
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
             .map(|events| add_path(events, self.path_key.as_str(), request_path))
+            .map(|events| match self.encoding {
+                // Avro events were decoded with the schema itself.
+                Encoding::Avro => events,
+                _ => validate(events, self.schema_validator.as_ref()),
+            })
             .map(|mut events| {
                 // Add source type
                 let key = log_schema().source_type_key();
                 for event in events.iter_mut() {
-                    event.as_mut_log().try_insert(key, Bytes::from("http"));
+                    self.metadata_keys
+                        .source_type
+                        .try_insert(event.as_mut_log(), key, "http");
                 }
                 events
             })
@@ -114,15 +151,32 @@ impl SourceConfig for SimpleHttpConfig {
     async fn build(
         &self,
         _: &str,
-        _: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
+        if self.encoding == Encoding::Avro {
+            if self.schema_validation.is_none() {
+                return Err("The `avro` encoding requires `schema_validation` to be set.".into());
+            }
+            if self.decoding.is_some() {
+                return Err("The `avro` encoding can't be used with `decoding`.".into());
+            }
+        }
+
+        let schema_validator = match &self.schema_validation {
+            Some(schema_validation) => Some(schema_validation.build().await?),
+            None => None,
+        };
         let source = SimpleHttpSource {
             encoding: self.encoding,
+            framing: self.framing,
+            decoding: self.decoding,
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
+            schema_validator,
+            metadata_keys: globals.metadata_keys.clone(),
         };
         source.run(
             self.address,
@@ -148,6 +202,16 @@ impl SourceConfig for SimpleHttpConfig {
     }
 }
 
+fn validate(events: Vec<Event>, schema_validator: Option<&SchemaValidator>) -> Vec<Event> {
+    match schema_validator {
+        Some(schema_validator) => events
+            .into_iter()
+            .filter_map(|event| schema_validator.validate(event))
+            .collect(),
+        None => events,
+    }
+}
+
 fn add_path(mut events: Vec<Event>, key: &str, path: &str) -> Vec<Event> {
     for event in events.iter_mut() {
         event
@@ -199,7 +263,11 @@ fn body_to_lines(buf: Bytes) -> impl Iterator<Item = Result<Bytes, ErrorMessage>
     })
 }
 
-fn decode_body(body: Bytes, enc: Encoding) -> Result<Vec<Event>, ErrorMessage> {
+fn decode_body(
+    body: Bytes,
+    enc: Encoding,
+    schema_validator: Option<&SchemaValidator>,
+) -> Result<Vec<Event>, ErrorMessage> {
     match enc {
         Encoding::Text => body_to_lines(body)
             .map(|r| Ok(Event::from(r?)))
@@ -216,7 +284,56 @@ fn decode_body(body: Bytes, enc: Encoding) -> Result<Vec<Event>, ErrorMessage> {
                 .map_err(|error| json_error(format!("Error parsing Json: {:?}", error)))?;
             json_parse_array_of_object(parsed_json)
         }
+        Encoding::Avro => {
+            let schema_validator = schema_validator.ok_or_else(|| {
+                ErrorMessage::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "The `avro` encoding requires `schema_validation` to be set.".to_owned(),
+                )
+            })?;
+            let mut events = schema_validator.decode_avro(&body).map_err(|error| {
+                ErrorMessage::new(StatusCode::BAD_REQUEST, format!("Bad Avro: {}", error))
+            })?;
+            let now = Utc::now();
+            for event in events.iter_mut() {
+                let log = event.as_mut_log();
+                if !log.contains(log_schema().timestamp_key()) {
+                    log.insert(log_schema().timestamp_key(), now);
+                }
+            }
+            Ok(events)
+        }
+    }
+}
+
+fn decode_frames(
+    body: Bytes,
+    mut framer: Framer,
+    decode: impl Fn(Bytes) -> Result<Vec<Event>, ErrorMessage>,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let mut body = BytesMut::from(&body[..]);
+    let mut events = Vec::new();
+    while let Some(frame) = framer
+        .decode_eof(&mut body)
+        .map_err(|error| bad_request(&error))?
+    {
+        // filter empty frames
+        if !frame.is_empty() {
+            events.extend(decode(frame)?);
+        }
     }
+    Ok(events)
+}
+
+fn decode_frame(frame: Bytes, decoding: DecodingConfig) -> Result<Vec<Event>, ErrorMessage> {
+    decoding
+        .decode(frame)
+        .map(|event| vec![event])
+        .map_err(|error| bad_request(&error))
+}
+
+fn bad_request(error: &dyn std::fmt::Display) -> ErrorMessage {
+    ErrorMessage::new(StatusCode::BAD_REQUEST, format!("Bad request: {}", error))
 }
 
 fn json_parse_object(value: JsonValue) -> Result<Event, ErrorMessage> {
@@ -271,12 +388,16 @@ fn json_value_to_type_string(value: &JsonValue) -> &'static str {
 
 #[cfg(test)]
 mod tests {
-    use super::{Encoding, SimpleHttpConfig};
+    use super::{
+        decode_body, decode_frame, decode_frames, Encoding, SimpleHttpConfig, SimpleHttpSource,
+    };
 
     use crate::shutdown::ShutdownSignal;
     use crate::{
+        codecs::{DecodingConfig, FramingConfig},
         config::{log_schema, GlobalOptions, SourceConfig},
         event::{Event, Value},
+        sources::util::{HttpSource, OnValidationFailure, SchemaValidator},
         test_util::{collect_n, next_addr, trace_init, wait_for_tcp},
         Pipeline,
     };
@@ -286,7 +407,7 @@ mod tests {
     };
     use http::HeaderMap;
     use pretty_assertions::assert_eq;
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::io::Write;
     use std::net::SocketAddr;
     use tokio::sync::mpsc;
@@ -312,6 +433,8 @@ mod tests {
             SimpleHttpConfig {
                 address,
                 encoding,
+                framing: None,
+                decoding: None,
                 headers,
                 query_parameters,
                 tls: None,
@@ -319,6 +442,7 @@ mod tests {
                 strict_path,
                 path_key,
                 path,
+                schema_validation: None,
             }
             .build(
                 "default",
@@ -563,6 +687,125 @@ mod tests {
         }
     }
 
+    #[test]
+    fn http_decode_frames() {
+        let body = "16 {\"message\": \"a\"}24 {\"message\": \"b\", \"n\": 1}";
+        let events = decode_frames(
+            body.into(),
+            FramingConfig::OctetCounting.build(usize::MAX),
+            |frame| decode_frame(frame, DecodingConfig::Json),
+        )
+        .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "a".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "b".into());
+        assert_eq!(events[1].as_log()["n"], 1.into());
+
+        let error = decode_frames(
+            "not json".into(),
+            FramingConfig::Bytes.build(usize::MAX),
+            |frame| decode_frame(frame, DecodingConfig::Json),
+        )
+        .unwrap_err();
+        assert!(error.to_string().starts_with("400"));
+    }
+
+    #[test]
+    fn http_decodes_frames_with_encoding() {
+        let body = "8 {\"a\": 1}20 [{\"a\": 2}, {\"a\": 3}]";
+        let events = decode_frames(
+            body.into(),
+            FramingConfig::OctetCounting.build(usize::MAX),
+            |frame| decode_body(frame, Encoding::Json, None),
+        )
+        .unwrap();
+
+        let values = events
+            .iter()
+            .map(|event| event.as_log()["a"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1.into(), 2.into(), 3.into()]);
+    }
+
+    fn request_schema() -> avro_rs::Schema {
+        avro_rs::Schema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "request",
+                "fields": [
+                    {"name": "status", "type": "long"},
+                    {"name": "http_path", "type": "string"}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn schema_validated_source(encoding: Encoding) -> SimpleHttpSource {
+        SimpleHttpSource {
+            encoding,
+            framing: None,
+            decoding: None,
+            headers: Vec::new(),
+            query_parameters: Vec::new(),
+            path_key: "http_path".to_owned(),
+            schema_validator: Some(SchemaValidator::new(
+                request_schema(),
+                OnValidationFailure::Drop,
+                "schema_error".to_owned(),
+            )),
+            metadata_keys: Default::default(),
+        }
+    }
+
+    #[test]
+    fn http_validates_events_with_added_fields() {
+        let source = schema_validated_source(Encoding::Json);
+        let events = source
+            .build_event(
+                r#"{"status": 200}"#.into(),
+                HeaderMap::new(),
+                HashMap::new(),
+                "/",
+            )
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["http_path"], "/".into());
+    }
+
+    #[test]
+    fn http_decodes_avro() {
+        let source = schema_validated_source(Encoding::Avro);
+        let datum = avro_rs::to_avro_datum(
+            &request_schema(),
+            avro_rs::types::Value::Record(vec![
+                ("status".to_owned(), avro_rs::types::Value::Long(200)),
+                (
+                    "http_path".to_owned(),
+                    avro_rs::types::Value::String("/replaced".to_owned()),
+                ),
+            ]),
+        )
+        .unwrap();
+
+        let events = source
+            .build_event(datum.into(), HeaderMap::new(), HashMap::new(), "/")
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["status"], 200.into());
+        assert_eq!(events[0].as_log()["http_path"], "/".into());
+        assert!(events[0]
+            .as_log()
+            .get(log_schema().timestamp_key())
+            .is_some());
+
+        let error = source
+            .build_event(b"\x01"[..].into(), HeaderMap::new(), HashMap::new(), "/")
+            .unwrap_err();
+        assert!(error.to_string().starts_with("400"));
+    }
+
     #[tokio::test]
     async fn http_headers() {
         trace_init();
@@ -672,6 +915,24 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_zstd() {
+        trace_init();
+
+        let body = zstd::encode_all("test body".as_bytes(), 3).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Encoding", "zstd".parse().unwrap());
+
+        let (rx, addr) = source(Encoding::default(), vec![], vec![], "http_path", "/", true).await;
+
+        assert_eq!(200, send_bytes(addr, body, headers).await);
+
+        let mut events = collect_n(rx, 1).await;
+        let log = events.remove(0).into_log();
+        assert_eq!(log[log_schema().message_key()], "test body".into());
+    }
+
     #[tokio::test]
     async fn http_path() {
         trace_init();
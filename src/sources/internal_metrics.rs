@@ -1,9 +1,10 @@
 use crate::{
     config::{DataType, GlobalOptions, SourceConfig, SourceDescription},
+    event::metric::Metric,
     metrics::Controller,
     metrics::{capture_metrics, get_controller},
     shutdown::ShutdownSignal,
-    Pipeline,
+    Event, Pipeline,
 };
 use futures::{stream, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,21 @@ use tokio::time;
 pub struct InternalMetricsConfig {
     #[derivative(Default(value = "2"))]
     scrape_interval_secs: u64,
+    /// The namespace of the metrics. Disabled if empty.
+    #[derivative(Default(value = "Some(\"vector\".into())"))]
+    namespace: Option<String>,
+    tags: TagsConfig,
+}
+
+/// Tags added to every metric, so that the metrics of several instances of
+/// Vector can be told apart.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct TagsConfig {
+    /// The tag the hostname is added under.
+    host_key: Option<String>,
+    /// The tag the ID of the Vector process is added under.
+    pid_key: Option<String>,
 }
 
 inventory::submit! {
@@ -39,7 +55,27 @@ impl SourceConfig for InternalMetricsConfig {
             );
         }
         let interval = time::Duration::from_secs(self.scrape_interval_secs);
-        Ok(Box::pin(run(get_controller()?, interval, out, shutdown)))
+
+        let namespace = self
+            .namespace
+            .clone()
+            .filter(|namespace| !namespace.is_empty());
+        let mut tags = Vec::new();
+        if let Some(host_key) = &self.tags.host_key {
+            tags.push((host_key.clone(), crate::get_hostname()?));
+        }
+        if let Some(pid_key) = &self.tags.pid_key {
+            tags.push((pid_key.clone(), std::process::id().to_string()));
+        }
+
+        Ok(Box::pin(run(
+            get_controller()?,
+            interval,
+            namespace,
+            tags,
+            out,
+            shutdown,
+        )))
     }
 
     fn output_type(&self) -> DataType {
@@ -54,6 +90,8 @@ impl SourceConfig for InternalMetricsConfig {
 async fn run(
     controller: &Controller,
     interval: time::Duration,
+    namespace: Option<String>,
+    tags: Vec<(String, String)>,
     out: Pipeline,
     shutdown: ShutdownSignal,
 ) -> Result<(), ()> {
@@ -62,17 +100,26 @@ async fn run(
 
     let mut interval = time::interval(interval).take_until(shutdown);
     while interval.next().await.is_some() {
-        let metrics = capture_metrics(controller);
+        let metrics = capture_metrics(controller)
+            .map(|event| Event::from(decorate(event.into_metric(), namespace.as_deref(), &tags)));
         out.send_all(&mut stream::iter(metrics).map(Ok)).await?;
     }
 
     Ok(())
 }
 
+fn decorate(metric: Metric, namespace: Option<&str>, tags: &[(String, String)]) -> Metric {
+    let mut metric = metric.with_namespace(namespace);
+    for (key, value) in tags {
+        metric.set_tag_value(key.clone(), value.clone());
+    }
+    metric
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::event::metric::{Metric, MetricValue, StatisticKind};
-    use crate::metrics::{capture_metrics, get_controller};
+    use super::*;
+    use crate::event::metric::{MetricKind, MetricValue, StatisticKind};
     use metrics::{counter, gauge, histogram};
     use std::collections::BTreeMap;
 
@@ -133,4 +180,37 @@ mod tests {
         labels.insert(String::from("host"), String::from("foo"));
         assert_eq!(Some(&labels), output["quux"].tags());
     }
+
+    #[test]
+    fn decorates_metrics() {
+        let config = toml::from_str::<InternalMetricsConfig>(
+            r#"
+            namespace = "agent"
+            tags.host_key = "hostname"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.namespace.as_deref(), Some("agent"));
+        assert_eq!(config.tags.host_key.as_deref(), Some("hostname"));
+        assert_eq!(config.tags.pid_key, None);
+
+        let metric = Metric::new(
+            "events_processed_total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_namespace(Some("vector"));
+        let tags = vec![
+            ("hostname".to_owned(), "vector-0".to_owned()),
+            ("pid".to_owned(), "42".to_owned()),
+        ];
+
+        let metric = decorate(metric, Some("agent"), &tags);
+        assert_eq!(metric.namespace(), Some("agent"));
+        assert_eq!(metric.tag_value("hostname"), Some("vector-0".to_owned()));
+        assert_eq!(metric.tag_value("pid"), Some("42".to_owned()));
+
+        let metric = decorate(metric, None, &[]);
+        assert_eq!(metric.namespace(), None);
+    }
 }
@@ -1,14 +1,16 @@
 use crate::{
-    config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
+    config::{log_schema, DataType, GlobalOptions, MetadataKeys, SourceConfig, SourceDescription},
     event::{Event, LogEvent, Value},
     internal_events::{JournaldEventReceived, JournaldInvalidRecord},
     shutdown::ShutdownSignal,
+    sources::util::{Backoff, ReconnectPolicy},
     Pipeline,
 };
 use bytes::Bytes;
 use chrono::TimeZone;
 use codec::BytesDelimitedCodec;
 use futures::{future, stream::BoxStream, SinkExt, StreamExt};
+use glob::{Pattern, PatternError};
 use lazy_static::lazy_static;
 use nix::{
     sys::signal::{kill, Signal},
@@ -32,7 +34,6 @@ use tokio::{
     fs::{File, OpenOptions},
     io::{self, AsyncReadExt, AsyncWriteExt},
     process::Command,
-    time::delay_for,
 };
 use tracing_futures::Instrument;
 
@@ -46,7 +47,7 @@ const SYSTEMD_UNIT: &str = "_SYSTEMD_UNIT";
 const SOURCE_TIMESTAMP: &str = "_SOURCE_REALTIME_TIMESTAMP";
 const RECEIVED_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
 
-const BACKOFF_DURATION: Duration = Duration::from_secs(1);
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
 
 lazy_static! {
     static ref JOURNALCTL: PathBuf = "journalctl".into();
@@ -63,6 +64,8 @@ enum BuildError {
         unit
     ))]
     DuplicatedUnit { unit: String },
+    #[snafu(display("Invalid unit pattern {:?}: {}", unit, source))]
+    InvalidUnitPattern { unit: String, source: PatternError },
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -72,6 +75,7 @@ pub struct JournaldConfig {
     pub units: Vec<String>,
     pub include_units: Vec<String>,
     pub exclude_units: Vec<String>,
+    pub include_facilities: Vec<String>,
     pub data_dir: Option<PathBuf>,
     pub batch_size: Option<usize>,
     pub journalctl_path: Option<PathBuf>,
@@ -113,12 +117,18 @@ impl SourceConfig for JournaldConfig {
             (false, _) => &self.include_units,
         };
 
-        let include_units: HashSet<String> = include_units.iter().map(|s| fixup_unit(&s)).collect();
-        let exclude_units: HashSet<String> =
-            self.exclude_units.iter().map(|s| fixup_unit(&s)).collect();
+        let include_units = include_units
+            .iter()
+            .map(|s| fixup_unit(&s))
+            .collect::<Vec<_>>();
+        let exclude_units = self
+            .exclude_units
+            .iter()
+            .map(|s| fixup_unit(&s))
+            .collect::<Vec<_>>();
         if let Some(unit) = include_units
             .iter()
-            .find(|unit| exclude_units.contains(&unit[..]))
+            .find(|unit| exclude_units.contains(unit))
         {
             let unit = unit.into();
             return Err(BuildError::DuplicatedUnit { unit }.into());
@@ -135,16 +145,18 @@ impl SourceConfig for JournaldConfig {
         let batch_size = self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
         let current_boot_only = self.current_boot_only.unwrap_or(true);
 
+        let args = journalctl_args(current_boot_only, &include_units, &self.include_facilities);
         let start: StartJournalctlFn =
-            Box::new(move |cursor| start_journalctl(&journalctl_path, current_boot_only, cursor));
+            Box::new(move |cursor| start_journalctl(&journalctl_path, &args, cursor));
 
         Ok(Box::pin(
             JournaldSource {
-                include_units,
-                exclude_units,
+                include_units: compile_units(&include_units)?,
+                exclude_units: compile_units(&exclude_units)?,
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
+                metadata_keys: globals.metadata_keys.clone(),
                 out,
             }
             .run_shutdown(shutdown, start)
@@ -162,11 +174,12 @@ impl SourceConfig for JournaldConfig {
 }
 
 struct JournaldSource {
-    include_units: HashSet<String>,
-    exclude_units: HashSet<String>,
+    include_units: HashSet<Pattern>,
+    exclude_units: HashSet<Pattern>,
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
+    metadata_keys: MetadataKeys,
     out: Pipeline,
 }
 
@@ -222,12 +235,17 @@ impl JournaldSource {
         on_stop: &'a mut Option<StopJournalctlFn>,
         start_journalctl: StartJournalctlFn,
     ) {
+        let mut backoff = ReconnectPolicy::default()
+            .initial_delay(INITIAL_BACKOFF)
+            .backoff();
         loop {
             info!("Starting journalctl.");
             match start_journalctl(&*cursor) {
                 Ok((stream, stop)) => {
                     *on_stop = Some(stop);
-                    let should_restart = self.run_stream(stream, checkpointer, cursor).await;
+                    let should_restart = self
+                        .run_stream(stream, checkpointer, cursor, &mut backoff)
+                        .await;
                     if let Some(stop) = on_stop.take() {
                         stop();
                     }
@@ -242,17 +260,20 @@ impl JournaldSource {
 
             // journalctl process should never stop,
             // so it is an error if we reach here.
-            delay_for(BACKOFF_DURATION).await;
+            backoff.wait().await;
         }
     }
 
     /// Process `journalctl` output until some error occurs.
     /// Return `true` if should restart `journalctl`.
+    ///
+    /// The backoff is reset once records are read, as `journalctl` is then known to work.
     async fn run_stream<'a>(
         &'a mut self,
         mut stream: BoxStream<'static, io::Result<Bytes>>,
         checkpointer: &'a mut Checkpointer,
         cursor: &'a mut Option<String>,
+        backoff: &'a mut Backoff,
     ) -> bool {
         loop {
             let mut saw_record = false;
@@ -298,7 +319,11 @@ impl JournaldSource {
                     byte_size: bytes.len()
                 });
 
-                match self.out.send(create_event(record)).await {
+                match self
+                    .out
+                    .send(create_event(record, &self.metadata_keys))
+                    .await
+                {
                     Ok(_) => {}
                     Err(error) => {
                         error!(message = "Could not send journald log.", %error);
@@ -309,6 +334,7 @@ impl JournaldSource {
             }
 
             if saw_record {
+                backoff.reset();
                 Self::save_checkpoint(checkpointer, &*cursor).await;
             }
         }
@@ -342,9 +368,35 @@ type StartJournalctlFn = Box<
 
 type StopJournalctlFn = Box<dyn FnOnce() + Send>;
 
+/// Arguments that make `journalctl` itself leave out the entries that aren't wanted, so they
+/// don't have to be decoded to be filtered.
+fn journalctl_args(
+    current_boot_only: bool,
+    include_units: &[String],
+    include_facilities: &[String],
+) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if current_boot_only {
+        args.push("--boot".into());
+    }
+
+    // `--unit` also matches entries about the unit, from systemd for example, which are
+    // then dropped by `filter_unit`.
+    for unit in include_units {
+        args.push(format!("--unit={}", unit));
+    }
+
+    if !include_facilities.is_empty() {
+        args.push(format!("--facility={}", include_facilities.join(",")));
+    }
+
+    args
+}
+
 fn start_journalctl(
     path: &PathBuf,
-    current_boot_only: bool,
+    args: &[String],
     cursor: &Option<String>,
 ) -> crate::Result<(BoxStream<'static, io::Result<Bytes>>, StopJournalctlFn)> {
     let mut command = Command::new(path);
@@ -353,10 +405,7 @@ fn start_journalctl(
     command.arg("--all");
     command.arg("--show-cursor");
     command.arg("--output=json");
-
-    if current_boot_only {
-        command.arg("--boot");
-    }
+    command.args(args);
 
     if let Some(cursor) = cursor {
         command.arg(format!("--after-cursor={}", cursor));
@@ -381,14 +430,16 @@ fn start_journalctl(
     Ok((stream, stop))
 }
 
-fn create_event(record: Record) -> Event {
+fn create_event(record: Record, metadata_keys: &MetadataKeys) -> Event {
     let mut log = LogEvent::from_iter(record);
     // Convert some journald-specific field names into Vector standard ones.
     if let Some(message) = log.remove(MESSAGE) {
         log.insert(log_schema().message_key(), message);
     }
     if let Some(host) = log.remove(HOSTNAME) {
-        log.insert(log_schema().host_key(), host);
+        metadata_keys
+            .host
+            .insert(&mut log, log_schema().host_key(), host);
     }
     // Translate the timestamp, and so leave both old and new names.
     if let Some(Value::Bytes(timestamp)) = log
@@ -404,21 +455,32 @@ fn create_event(record: Record) -> Event {
         }
     }
     // Add source type
-    log.try_insert(log_schema().source_type_key(), Bytes::from("journald"));
+    metadata_keys
+        .source_type
+        .try_insert(&mut log, log_schema().source_type_key(), "journald");
 
     log.into()
 }
 
 /// Map the given unit name into a valid systemd unit
 /// by appending ".service" if no extension is present.
+/// Patterns are left alone, as `journalctl` does.
 fn fixup_unit(unit: &str) -> String {
-    if unit.contains('.') {
+    if unit.contains(&['.', '*', '?', '['][..]) {
         unit.into()
     } else {
         format!("{}.service", unit)
     }
 }
 
+fn compile_units(units: &[String]) -> crate::Result<HashSet<Pattern>> {
+    units
+        .iter()
+        .map(|unit| Pattern::new(unit).context(InvalidUnitPattern { unit }))
+        .collect::<Result<_, _>>()
+        .map_err(Into::into)
+}
+
 fn decode_record(line: &[u8], remap: bool) -> Result<Record, JsonError> {
     let mut record = serde_json::from_str::<JsonValue>(&String::from_utf8_lossy(line))?;
     // journalctl will output non-ASCII values using an array
@@ -477,15 +539,18 @@ fn remap_priority(priority: &mut JsonValue) {
 /// Should the given unit name be filtered (excluded)?
 fn filter_unit(
     unit: Option<&String>,
-    includes: &HashSet<String>,
-    excludes: &HashSet<String>,
+    includes: &HashSet<Pattern>,
+    excludes: &HashSet<Pattern>,
 ) -> bool {
+    let matches = |patterns: &HashSet<Pattern>, unit: &str| {
+        patterns.iter().any(|pattern| pattern.matches(unit))
+    };
     match (unit, includes.is_empty(), excludes.is_empty()) {
         (None, empty, _) => !empty,
         (Some(_), true, true) => false,
-        (Some(unit), false, true) => !includes.contains(unit),
-        (Some(unit), true, false) => excludes.contains(unit),
-        (Some(unit), false, false) => !includes.contains(unit) || excludes.contains(unit),
+        (Some(unit), false, true) => !matches(includes, unit),
+        (Some(unit), true, false) => matches(excludes, unit),
+        (Some(unit), false, false) => !matches(includes, unit) || matches(excludes, unit),
     }
 }
 
@@ -663,8 +728,8 @@ mod tests {
                 .expect("Could not set checkpoint");
         }
 
-        let include_units: HashSet<String> = iunits.iter().map(|&s| s.into()).collect();
-        let exclude_units: HashSet<String> = xunits.iter().map(|&s| s.into()).collect();
+        let include_units = patterns(iunits);
+        let exclude_units = patterns(xunits);
 
         let source = JournaldSource {
             include_units,
@@ -672,6 +737,7 @@ mod tests {
             checkpoint_path,
             batch_size: DEFAULT_BATCH_SIZE,
             remap_priority: true,
+            metadata_keys: MetadataKeys::default(),
             out: tx,
         }
         .run_shutdown(
@@ -731,6 +797,13 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn includes_unit_patterns() {
+        let received = run_journal(&["*unit.service"], &["bad*"], None).await;
+        assert_eq!(received.len(), 1);
+        assert_eq!(message(&received[0]), Value::Bytes("unit message".into()));
+    }
+
     #[tokio::test]
     async fn handles_checkpoint() {
         let received = run_journal(&[], &[], Some("1")).await;
@@ -776,9 +849,9 @@ mod tests {
 
     #[test]
     fn filter_unit_works_correctly() {
-        let empty: HashSet<String> = vec![].into_iter().collect();
-        let includes: HashSet<String> = vec!["one", "two"].into_iter().map(Into::into).collect();
-        let excludes: HashSet<String> = vec!["foo", "bar"].into_iter().map(Into::into).collect();
+        let empty = patterns(&[]);
+        let includes = patterns(&["one", "two"]);
+        let excludes = patterns(&["foo", "bar"]);
 
         assert_eq!(filter_unit(None, &empty, &empty), false);
         assert_eq!(filter_unit(None, &includes, &empty), true);
@@ -794,6 +867,44 @@ mod tests {
         assert_eq!(filter_unit(Some(&two), &includes, &empty), true);
         assert_eq!(filter_unit(Some(&two), &empty, &excludes), true);
         assert_eq!(filter_unit(Some(&two), &includes, &excludes), true);
+        let three = String::from("three.service");
+        assert_eq!(filter_unit(Some(&three), &patterns(&["t*"]), &empty), false);
+        assert_eq!(
+            filter_unit(Some(&three), &empty, &patterns(&["*.service"])),
+            true
+        );
+    }
+
+    #[test]
+    fn fixup_unit_leaves_patterns() {
+        assert_eq!(fixup_unit("sshd"), "sshd.service");
+        assert_eq!(fixup_unit("sshd.socket"), "sshd.socket");
+        assert_eq!(fixup_unit("ssh*"), "ssh*");
+    }
+
+    #[test]
+    fn journalctl_args_filter_entries() {
+        assert_eq!(journalctl_args(false, &[], &[]), Vec::<String>::new());
+        assert_eq!(
+            journalctl_args(
+                true,
+                &["sshd.service".into(), "ssh*".into()],
+                &["auth".into(), "authpriv".into()]
+            ),
+            vec![
+                "--boot",
+                "--unit=sshd.service",
+                "--unit=ssh*",
+                "--facility=auth,authpriv"
+            ]
+        );
+    }
+
+    fn patterns(units: &[&str]) -> HashSet<Pattern> {
+        units
+            .iter()
+            .map(|unit| Pattern::new(unit).unwrap())
+            .collect()
     }
 
     fn message(event: &Event) -> Value {
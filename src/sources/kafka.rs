@@ -1,22 +1,35 @@
 use crate::{
-    config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
-    event::{Event, Value},
-    internal_events::{KafkaEventFailed, KafkaEventReceived, KafkaOffsetUpdateFailed},
+    codecs::{self, DecodingConfig, FramingConfig},
+    config::{log_schema, DataType, GlobalOptions, MetadataKeys, SourceConfig, SourceDescription},
+    event::{BatchNotifier, EventStatus, Value},
+    internal_events::{
+        DecoderFramingFailed, KafkaEventFailed, KafkaEventNotDelivered, KafkaEventReceived,
+        KafkaOffsetUpdateFailed, KafkaPartitionsAssigned, KafkaPartitionsRevoked,
+        KafkaRebalanceFailed,
+    },
     kafka::KafkaAuthConfig,
     shutdown::ShutdownSignal,
+    sources::util::ReconnectPolicy,
     Pipeline,
 };
-use bytes::Bytes;
+use bytes::BytesMut;
 use chrono::{TimeZone, Utc};
-use futures::{SinkExt, StreamExt};
+use futures::{stream, stream::FuturesUnordered, FutureExt, SinkExt, StreamExt};
 use rdkafka::{
     config::ClientConfig,
-    consumer::{Consumer, StreamConsumer},
+    consumer::{Consumer, ConsumerContext, Rebalance, StreamConsumer},
     message::Message,
+    ClientContext, Offset, TopicPartitionList,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+use tokio::time::delay_for;
+use tokio_util::codec::Decoder;
+use tracing::Span;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -32,6 +45,9 @@ pub struct KafkaSourceConfig {
     bootstrap_servers: String,
     topics: Vec<String>,
     group_id: String,
+    /// Makes this consumer a static member of the group, so that it can be
+    /// restarted within the session timeout without triggering a rebalance.
+    group_instance_id: Option<String>,
     #[serde(default = "default_auto_offset_reset")]
     auto_offset_reset: String,
     #[serde(default = "default_session_timeout_ms")]
@@ -49,6 +65,13 @@ pub struct KafkaSourceConfig {
     librdkafka_options: Option<HashMap<String, String>>,
     #[serde(flatten)]
     auth: KafkaAuthConfig,
+    /// Stores the offset of a message only once the sinks have accepted its events.
+    #[serde(default)]
+    acknowledgements: bool,
+    /// Each message is a single frame, unless this is set.
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    decoding: DecodingConfig,
 }
 
 fn default_session_timeout_ms() -> u64 {
@@ -83,11 +106,11 @@ impl SourceConfig for KafkaSourceConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
-        kafka_source(self, shutdown, out)
+        kafka_source(self, globals.metadata_keys.clone(), shutdown, out)
     }
 
     fn output_type(&self) -> DataType {
@@ -101,70 +124,97 @@ impl SourceConfig for KafkaSourceConfig {
 
 fn kafka_source(
     config: &KafkaSourceConfig,
+    metadata_keys: MetadataKeys,
     shutdown: ShutdownSignal,
-    out: Pipeline,
+    mut out: Pipeline,
 ) -> crate::Result<super::Source> {
     let key_field = config.key_field.clone();
     let topic_key = config.topic_key.clone();
     let partition_key = config.partition_key.clone();
     let offset_key = config.offset_key.clone();
-    let consumer = Arc::new(create_consumer(config)?);
+    let acknowledgements = config.acknowledgements;
+    let mut decoder = codecs::Decoder::new(
+        config
+            .framing
+            .unwrap_or(FramingConfig::Bytes)
+            .build(usize::MAX),
+        config.decoding,
+    );
+    let span = Arc::new(Mutex::new(Span::none()));
+    let consumer = create_consumer(config, Arc::clone(&span))?;
+    let mut backoff = ReconnectPolicy::default().backoff();
 
     Ok(Box::pin(async move {
-        Arc::clone(&consumer)
-            .start()
-            .take_until(shutdown.clone())
-            .then(move |message| {
-                let key_field = key_field.clone();
-                let topic_key = topic_key.clone();
-                let partition_key = partition_key.clone();
-                let offset_key = offset_key.clone();
-                let consumer = Arc::clone(&consumer);
-
-                async move {
-                    match message {
-                        Err(error) => {
-                            emit!(KafkaEventFailed { error });
-                            Err(())
-                        }
-                        Ok(msg) => {
-                            emit!(KafkaEventReceived {
-                                byte_size: msg.payload_len()
-                            });
-
-                            let payload = match msg.payload() {
-                                None => return Err(()), // skip messages with empty payload
-                                Some(payload) => payload,
+        *span.lock().unwrap() = Span::current();
+
+        let mut messages = consumer.start().take_until(shutdown);
+        // Messages are processed while the events of earlier ones are waiting to be
+        // delivered, so their acknowledgements are tracked alongside.
+        let mut acks = FuturesUnordered::new();
+        let mut pending = PendingOffsets::default();
+
+        loop {
+            tokio::select! {
+                Some((topic, partition, offset, status)) = acks.next() => {
+                    pending.complete(&consumer, &topic, partition, offset, status);
+                }
+                message = messages.next() => match message {
+                    None => break,
+                    Some(Err(error)) => {
+                        emit!(KafkaEventFailed { error });
+                        // librdkafka reconnects to the brokers by itself, but
+                        // keeps reporting errors until it does.
+                        delay_for(backoff.next_delay()).await;
+                    }
+                    Some(Ok(msg)) => {
+                        backoff.reset();
+                        emit!(KafkaEventReceived {
+                            byte_size: msg.payload_len()
+                        });
+
+                        // Extract timestamp from kafka message
+                        let timestamp = msg
+                            .timestamp()
+                            .to_millis()
+                            .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
+                            .unwrap_or_else(Utc::now);
+                        let key = msg
+                            .key()
+                            .map(|key| Value::from(String::from_utf8_lossy(key).to_string()));
+
+                        let (batch, receiver) = if acknowledgements {
+                            let (batch, receiver) = BatchNotifier::new_with_receiver();
+                            (Some(batch), Some(receiver))
+                        } else {
+                            (None, None)
+                        };
+
+                        // Messages with an empty payload have no events.
+                        let mut payload = BytesMut::from(msg.payload().unwrap_or_default());
+                        let mut events = Vec::new();
+                        loop {
+                            let mut event = match decoder.decode_eof(&mut payload) {
+                                Ok(Some((event, _))) => event,
+                                Ok(None) => break,
+                                Err(error) => {
+                                    // The rest of the message can't be framed.
+                                    emit!(DecoderFramingFailed { error: &error });
+                                    break;
+                                }
                             };
-                            let mut event = Event::new_empty_log();
                             let log = event.as_mut_log();
 
-                            log.insert(
-                                log_schema().message_key(),
-                                Value::from(Bytes::from(payload.to_owned())),
-                            );
-
-                            // Extract timestamp from kafka message
-                            let timestamp = msg
-                                .timestamp()
-                                .to_millis()
-                                .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
-                                .unwrap_or_else(Utc::now);
                             log.insert(log_schema().timestamp_key(), timestamp);
 
                             // Add source type
-                            log.insert(log_schema().source_type_key(), Bytes::from("kafka"));
-
-                            if let Some(key_field) = &key_field {
-                                match msg.key() {
-                                    None => (),
-                                    Some(key) => {
-                                        log.insert(
-                                            key_field,
-                                            Value::from(String::from_utf8_lossy(key).to_string()),
-                                        );
-                                    }
-                                }
+                            metadata_keys.source_type.insert(
+                                log,
+                                log_schema().source_type_key(),
+                                "kafka",
+                            );
+
+                            if let (Some(key_field), Some(key)) = (&key_field, &key) {
+                                log.insert(key_field, key.clone());
                             }
 
                             if let Some(topic_key) = &topic_key {
@@ -179,37 +229,152 @@ fn kafka_source(
                                 log.insert(offset_key, Value::from(msg.offset()));
                             }
 
-                            consumer.store_offset(&msg).map_err(|error| {
-                                emit!(KafkaOffsetUpdateFailed { error });
-                            })?;
-
-                            Ok(event)
+                            if let Some(batch) = &batch {
+                                event.add_batch_notifier(Arc::clone(batch));
+                            }
+                            events.push(event);
                         }
-                    }
-                }
-            })
-            // Try `forward` after removing old futures.
-            // Error: implementation of `futures_core::stream::Stream` is not general enough
-            // .forward(
-            //     out.sink_compat()
-            //         .sink_map_err(|error| error!(message = "Error sending to sink.", %error)),
-            // )
-            .for_each(|item| {
-                let mut out = out.clone();
-                async move {
-                    if let Ok(item) = item {
-                        if let Err(error) = out.send(item).await {
+                        drop(batch);
+
+                        let mut events = stream::iter(events).map(Ok);
+                        if let Err(error) = out.send_all(&mut events).await {
                             error!(message = "Error sending to sink.", %error);
+                            break;
+                        }
+
+                        match receiver {
+                            // The offset is only stored once the sinks have accepted the events.
+                            Some(receiver) => {
+                                let (topic, partition, offset) =
+                                    (msg.topic().to_owned(), msg.partition(), msg.offset());
+                                pending.add(&topic, partition, offset);
+                                acks.push(
+                                    receiver.map(move |status| (topic, partition, offset, status)),
+                                );
+                            }
+                            None => {
+                                if let Err(error) = consumer.store_offset(&msg) {
+                                    emit!(KafkaOffsetUpdateFailed { error });
+                                }
+                            }
                         }
                     }
                 }
-            })
-            .await;
+            }
+        }
+
+        // The events already sent may still be delivered, so their offsets are
+        // stored before the consumer commits them on shutdown.
+        drop(out);
+        while let Some((topic, partition, offset, status)) = acks.next().await {
+            pending.complete(&consumer, &topic, partition, offset, status);
+        }
         Ok(())
     }))
 }
 
-fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer> {
+/// Offsets of the messages whose events are waiting to be delivered. The
+/// offsets of a partition are stored in order, each once the events of all
+/// the earlier messages of the partition are delivered, so that messages
+/// not delivered yet are consumed again after a restart.
+#[derive(Default)]
+struct PendingOffsets {
+    partitions: HashMap<(String, i32), PendingPartition>,
+}
+
+#[derive(Default)]
+struct PendingPartition {
+    /// Offsets of the messages received, and whether they were acknowledged.
+    offsets: BTreeMap<i64, bool>,
+    /// Set once the events of a message weren't delivered, after which no
+    /// further offsets of the partition are stored.
+    failed: bool,
+}
+
+impl PendingOffsets {
+    fn add(&mut self, topic: &str, partition: i32, offset: i64) {
+        self.partitions
+            .entry((topic.to_owned(), partition))
+            .or_default()
+            .offsets
+            .insert(offset, false);
+    }
+
+    fn complete(
+        &mut self,
+        consumer: &StreamConsumer<KafkaSourceContext>,
+        topic: &str,
+        partition: i32,
+        offset: i64,
+        status: EventStatus,
+    ) {
+        if status != EventStatus::Delivered {
+            emit!(KafkaEventNotDelivered {
+                topic,
+                partition,
+                status
+            });
+        }
+        if let Some(offset) = self.acknowledge(topic, partition, offset, status) {
+            let mut offsets = TopicPartitionList::new();
+            // The stored offset is the one of the next message to consume.
+            offsets.add_partition_offset(topic, partition, Offset::Offset(offset + 1));
+            if let Err(error) = consumer.store_offsets(&offsets) {
+                emit!(KafkaOffsetUpdateFailed { error });
+            }
+        }
+    }
+
+    /// Records the acknowledgement of the message at `offset`, returning the
+    /// offset of the last message up to which the partition was delivered, if
+    /// it advanced.
+    fn acknowledge(
+        &mut self,
+        topic: &str,
+        partition: i32,
+        offset: i64,
+        status: EventStatus,
+    ) -> Option<i64> {
+        let pending = self.partitions.get_mut(&(topic.to_owned(), partition))?;
+        pending.failed |= status != EventStatus::Delivered;
+        pending.offsets.insert(offset, true);
+
+        let mut delivered = None;
+        while let Some((&offset, &true)) = pending.offsets.iter().next() {
+            pending.offsets.remove(&offset);
+            delivered = Some(offset);
+        }
+        delivered.filter(|_| !pending.failed)
+    }
+}
+
+/// Reports rebalances of the consumer group. These are signalled on the thread
+/// polling the consumer, so the source's span is entered explicitly.
+struct KafkaSourceContext {
+    span: Arc<Mutex<Span>>,
+}
+
+impl ClientContext for KafkaSourceContext {}
+
+impl ConsumerContext for KafkaSourceContext {
+    fn post_rebalance(&self, rebalance: &Rebalance) {
+        let span = self.span.lock().unwrap().clone();
+        let _enter = span.enter();
+
+        match rebalance {
+            Rebalance::Assign(partitions) => emit!(KafkaPartitionsAssigned {
+                partitions: partitions.count()
+            }),
+            Rebalance::Error(error) => emit!(KafkaRebalanceFailed { error }),
+            _ => emit!(KafkaPartitionsRevoked),
+        }
+    }
+}
+
+fn create_consumer(
+    config: &KafkaSourceConfig,
+    span: Arc<Mutex<Span>>,
+) -> crate::Result<StreamConsumer<KafkaSourceContext>> {
     let mut client_config = ClientConfig::new();
     client_config
         .set("group.id", &config.group_id)
@@ -227,6 +392,10 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer>
         .set("enable.auto.offset.store", "false")
         .set("client.id", "vector");
 
+    if let Some(group_instance_id) = &config.group_instance_id {
+        client_config.set("group.instance.id", group_instance_id);
+    }
+
     config.auth.apply(&mut client_config)?;
 
     if let Some(librdkafka_options) = &config.librdkafka_options {
@@ -235,7 +404,9 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer>
         }
     }
 
-    let consumer: StreamConsumer = client_config.create().context(KafkaCreateError)?;
+    let consumer: StreamConsumer<KafkaSourceContext> = client_config
+        .create_with_context(KafkaSourceContext { span })
+        .context(KafkaCreateError)?;
     let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
     consumer.subscribe(&topics).context(KafkaSubscribeError)?;
 
@@ -244,8 +415,8 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer>
 
 #[cfg(test)]
 mod test {
-    use super::{kafka_source, KafkaSourceConfig};
-    use crate::{shutdown::ShutdownSignal, Pipeline};
+    use super::{kafka_source, KafkaSourceConfig, PendingOffsets};
+    use crate::{config::MetadataKeys, event::EventStatus, shutdown::ShutdownSignal, Pipeline};
 
     #[test]
     fn generate_config() {
@@ -273,7 +444,13 @@ mod test {
     #[test]
     fn kafka_source_create_ok() {
         let config = make_config();
-        assert!(kafka_source(&config, ShutdownSignal::noop(), Pipeline::new_test().0).is_ok());
+        assert!(kafka_source(
+            &config,
+            MetadataKeys::default(),
+            ShutdownSignal::noop(),
+            Pipeline::new_test().0
+        )
+        .is_ok());
     }
 
     #[test]
@@ -282,7 +459,43 @@ mod test {
             auto_offset_reset: "incorrect-auto-offset-reset".to_string(),
             ..make_config()
         };
-        assert!(kafka_source(&config, ShutdownSignal::noop(), Pipeline::new_test().0).is_err());
+        assert!(kafka_source(
+            &config,
+            MetadataKeys::default(),
+            ShutdownSignal::noop(),
+            Pipeline::new_test().0
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn pending_offsets_advance_in_order() {
+        let mut pending = PendingOffsets::default();
+        for offset in 0..4 {
+            pending.add("topic", 0, offset);
+        }
+        pending.add("topic", 1, 0);
+
+        assert_eq!(
+            pending.acknowledge("topic", 0, 1, EventStatus::Delivered),
+            None
+        );
+        assert_eq!(
+            pending.acknowledge("topic", 1, 0, EventStatus::Delivered),
+            Some(0)
+        );
+        assert_eq!(
+            pending.acknowledge("topic", 0, 0, EventStatus::Delivered),
+            Some(1)
+        );
+        assert_eq!(
+            pending.acknowledge("topic", 0, 2, EventStatus::Failed),
+            None
+        );
+        assert_eq!(
+            pending.acknowledge("topic", 0, 3, EventStatus::Delivered),
+            None
+        );
     }
 }
 
@@ -356,7 +569,9 @@ mod integration_test {
 
         println!("Receiving event...");
         let (tx, rx) = Pipeline::new_test();
-        tokio::spawn(kafka_source(&config, ShutdownSignal::noop(), tx).unwrap());
+        tokio::spawn(
+            kafka_source(&config, MetadataKeys::default(), ShutdownSignal::noop(), tx).unwrap(),
+        );
         let events = collect_n(rx, 1).await;
 
         assert_eq!(
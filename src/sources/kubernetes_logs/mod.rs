@@ -12,14 +12,18 @@ use crate::internal_events::{
 };
 use crate::kubernetes as k8s;
 use crate::{
-    config::{DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription},
+    config::{
+        DataType, GenerateConfig, GlobalOptions, MetadataKeys, SourceConfig, SourceDescription,
+    },
     shutdown::ShutdownSignal,
     sources,
     transforms::{FunctionTransform, TaskTransform},
     Pipeline,
 };
 use bytes::Bytes;
-use file_source::{FileServer, FileServerShutdown, FingerprintStrategy, Fingerprinter, ReadFrom};
+use file_source::{
+    FileServer, FileServerShutdown, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
+};
 use k8s_openapi::api::core::v1::Pod;
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
@@ -162,6 +166,7 @@ struct Source {
     glob_minimum_cooldown: Duration,
     ingestion_timestamp_field: Option<String>,
     timezone: TimeZone,
+    metadata_keys: MetadataKeys,
 }
 
 impl Source {
@@ -206,6 +211,7 @@ impl Source {
             glob_minimum_cooldown,
             ingestion_timestamp_field: config.ingestion_timestamp_field.clone(),
             timezone,
+            metadata_keys: globals.metadata_keys.clone(),
         })
     }
 
@@ -226,6 +232,7 @@ impl Source {
             glob_minimum_cooldown,
             ingestion_timestamp_field,
             timezone,
+            metadata_keys,
         } = self;
 
         let watcher = k8s::api_watcher::ApiWatcher::new(client, Pod::watch_pod_for_all_namespaces);
@@ -311,22 +318,30 @@ impl Source {
             emitter: FileSourceInternalEventsEmitter,
             // A handle to the current tokio runtime
             handle: tokio::runtime::Handle::current(),
+            // The checkpoints aren't inspected from elsewhere.
+            checkpoints: Default::default(),
+            acknowledgements: false,
         };
 
-        let (file_source_tx, file_source_rx) =
-            futures::channel::mpsc::channel::<Vec<(Bytes, String)>>(2);
+        let (file_source_tx, file_source_rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
 
         let mut parser = parser::build(timezone);
         let partial_events_merger = Box::new(partial_events_merger::build(auto_partial_merge));
 
         let events = file_source_rx.map(futures::stream::iter);
         let events = events.flatten();
-        let events = events.map(move |(bytes, file)| {
+        let events = events.map(move |line: Line| {
+            let file = line.filename;
             emit!(KubernetesLogsEventReceived {
                 file: &file,
-                byte_size: bytes.len(),
+                byte_size: line.text.len(),
             });
-            let mut event = create_event(bytes, &file, ingestion_timestamp_field.as_deref());
+            let mut event = create_event(
+                line.text,
+                &file,
+                ingestion_timestamp_field.as_deref(),
+                &metadata_keys,
+            );
             if annotator.annotate(&mut event, &file).is_none() {
                 emit!(KubernetesLogsEventAnnotationFailed { event: &event });
             }
@@ -394,11 +409,17 @@ impl Source {
     }
 }
 
-fn create_event(line: Bytes, file: &str, ingestion_timestamp_field: Option<&str>) -> Event {
+fn create_event(
+    line: Bytes,
+    file: &str,
+    ingestion_timestamp_field: Option<&str>,
+    metadata_keys: &MetadataKeys,
+) -> Event {
     let mut event = Event::from(line);
 
     // Add source type.
-    event.as_mut_log().insert(
+    metadata_keys.source_type.insert(
+        event.as_mut_log(),
         crate::config::log_schema().source_type_key(),
         COMPONENT_NAME.to_owned(),
     );
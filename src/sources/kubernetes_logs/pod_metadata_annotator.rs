@@ -4,7 +4,7 @@
 
 use super::path_helpers::{parse_log_file_path, LogFileInfo};
 use crate::{
-    event::{LogEvent, PathComponent, PathIter},
+    event::{LogEvent, PathComponent, PathIter, Value},
     kubernetes as k8s, Event,
 };
 use evmap::ReadHandle;
@@ -13,7 +13,11 @@ use k8s_openapi::{
     apimachinery::pkg::apis::meta::v1::ObjectMeta,
 };
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
 
+/// Event fields that pod metadata is put in. Metadata whose field is set to an
+/// empty string isn't added to events. Pod annotations, which can be large, aren't
+/// added unless their field is set.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
 pub struct FieldsSpec {
@@ -23,7 +27,9 @@ pub struct FieldsSpec {
     pub pod_ip: String,
     pub pod_ips: String,
     pub pod_labels: String,
+    pub pod_annotations: String,
     pub pod_node_name: String,
+    pub pod_owner: String,
     pub container_name: String,
     pub container_id: String,
     pub container_image: String,
@@ -38,7 +44,9 @@ impl Default for FieldsSpec {
             pod_ip: "kubernetes.pod_ip".to_owned(),
             pod_ips: "kubernetes.pod_ips".to_owned(),
             pod_labels: "kubernetes.pod_labels".to_owned(),
+            pod_annotations: "".to_owned(),
             pod_node_name: "kubernetes.pod_node_name".to_owned(),
+            pod_owner: "kubernetes.pod_owner".to_owned(),
             container_name: "kubernetes.container_name".to_owned(),
             container_id: "kubernetes.container_id".to_owned(),
             container_image: "kubernetes.container_image".to_owned(),
@@ -112,7 +120,8 @@ fn annotate_from_file_info(
     fields_spec: &FieldsSpec,
     file_info: &LogFileInfo<'_>,
 ) {
-    log.insert(
+    insert_field(
+        log,
         &fields_spec.container_name,
         file_info.container_name.to_owned(),
     );
@@ -127,25 +136,57 @@ fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata
     .iter()
     {
         if let Some(val) = val {
-            log.insert(key, val.to_owned());
+            insert_field(log, key, val.to_owned());
         }
     }
 
     if let Some(labels) = &metadata.labels {
-        // Calculate and cache the prefix path.
-        let prefix_path = PathIter::new(fields_spec.pod_labels.as_ref()).collect::<Vec<_>>();
-        for (key, val) in labels.iter() {
-            let mut path = prefix_path.clone();
-            path.push(PathComponent::Key(key.clone()));
-            log.insert_path(path, val.to_owned());
-        }
+        insert_map(log, &fields_spec.pod_labels, labels);
+    }
+
+    if let Some(annotations) = &metadata.annotations {
+        insert_map(log, &fields_spec.pod_annotations, annotations);
+    }
+
+    // The owner is the controller that manages the pod, such as a `ReplicaSet`.
+    let owner = metadata.owner_references.as_ref().and_then(|owners| {
+        owners
+            .iter()
+            .find(|owner| owner.controller == Some(true))
+            .or_else(|| owners.first())
+    });
+    if let Some(owner) = owner {
+        insert_field(
+            log,
+            &fields_spec.pod_owner,
+            format!("{}/{}", owner.kind, owner.name),
+        );
+    }
+}
+
+fn insert_field(log: &mut LogEvent, key: &str, value: impl Into<Value> + fmt::Debug) {
+    if !key.is_empty() {
+        log.insert(key, value);
+    }
+}
+
+fn insert_map(log: &mut LogEvent, prefix: &str, map: &BTreeMap<String, String>) {
+    if prefix.is_empty() {
+        return;
+    }
+    // Calculate and cache the prefix path.
+    let prefix_path = PathIter::new(prefix).collect::<Vec<_>>();
+    for (key, val) in map.iter() {
+        let mut path = prefix_path.clone();
+        path.push(PathComponent::Key(key.clone()));
+        log.insert_path(path, val.to_owned());
     }
 }
 
 fn annotate_from_pod_spec(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_spec: &PodSpec) {
     for (ref key, ref val) in [(&fields_spec.pod_node_name, &pod_spec.node_name)].iter() {
         if let Some(val) = val {
-            log.insert(key, val.to_owned());
+            insert_field(log, key, val.to_owned());
         }
     }
 }
@@ -153,7 +194,7 @@ fn annotate_from_pod_spec(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_spec
 fn annotate_from_pod_status(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_status: &PodStatus) {
     for (ref key, ref val) in [(&fields_spec.pod_ip, &pod_status.pod_ip)].iter() {
         if let Some(val) = val {
-            log.insert(key, val.to_owned());
+            insert_field(log, key, val.to_owned());
         }
     }
 
@@ -163,7 +204,7 @@ fn annotate_from_pod_status(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_st
                 .iter()
                 .filter_map(|v| v.ip.clone())
                 .collect::<Vec<String>>();
-            log.insert(key, inner);
+            insert_field(log, key, inner);
         }
     }
 }
@@ -175,7 +216,7 @@ fn annotate_from_container_status(
 ) {
     for (ref key, ref val) in [(&fields_spec.container_id, &container_status.container_id)].iter() {
         if let Some(val) = val {
-            log.insert(key, val.to_owned());
+            insert_field(log, key, val.to_owned());
         }
     }
 }
@@ -183,7 +224,7 @@ fn annotate_from_container_status(
 fn annotate_from_container(log: &mut LogEvent, fields_spec: &FieldsSpec, container: &Container) {
     for (ref key, ref val) in [(&fields_spec.container_image, &container.image)].iter() {
         if let Some(val) = val {
-            log.insert(key, val.to_owned());
+            insert_field(log, key, val.to_owned());
         }
     }
 }
@@ -191,7 +232,7 @@ fn annotate_from_container(log: &mut LogEvent, fields_spec: &FieldsSpec, contain
 #[cfg(test)]
 mod tests {
     use super::*;
-    use k8s_openapi::api::core::v1::PodIP;
+    use k8s_openapi::{api::core::v1::PodIP, apimachinery::pkg::apis::meta::v1::OwnerReference};
 
     #[test]
     fn test_annotate_from_metadata() {
@@ -290,6 +331,71 @@ mod tests {
                     log
                 },
             ),
+            (
+                FieldsSpec {
+                    pod_annotations: "kubernetes.pod_annotations".to_owned(),
+                    ..Default::default()
+                },
+                ObjectMeta {
+                    name: Some("sandbox0-name".to_owned()),
+                    annotations: Some(
+                        vec![("sandbox0-annotation0".to_owned(), "val0".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    owner_references: Some(vec![
+                        OwnerReference {
+                            kind: "Node".to_owned(),
+                            name: "sandbox0-node".to_owned(),
+                            ..OwnerReference::default()
+                        },
+                        OwnerReference {
+                            kind: "ReplicaSet".to_owned(),
+                            name: "sandbox0-rs".to_owned(),
+                            controller: Some(true),
+                            ..OwnerReference::default()
+                        },
+                    ]),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert("kubernetes.pod_name", "sandbox0-name");
+                    log.insert("kubernetes.pod_annotations.sandbox0-annotation0", "val0");
+                    log.insert("kubernetes.pod_owner", "ReplicaSet/sandbox0-rs");
+                    log
+                },
+            ),
+            // Ensure metadata with an empty field name is left out.
+            (
+                FieldsSpec {
+                    pod_namespace: "".to_owned(),
+                    pod_labels: "".to_owned(),
+                    pod_owner: "".to_owned(),
+                    ..Default::default()
+                },
+                ObjectMeta {
+                    name: Some("sandbox0-name".to_owned()),
+                    namespace: Some("sandbox0-ns".to_owned()),
+                    labels: Some(
+                        vec![("sandbox0-label0".to_owned(), "val0".to_owned())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    owner_references: Some(vec![OwnerReference {
+                        kind: "ReplicaSet".to_owned(),
+                        name: "sandbox0-rs".to_owned(),
+                        controller: Some(true),
+                        ..OwnerReference::default()
+                    }]),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert("kubernetes.pod_name", "sandbox0-name");
+                    log
+                },
+            ),
         ];
 
         for (fields_spec, metadata, expected) in cases.into_iter() {
@@ -0,0 +1,493 @@
+use crate::{
+    config::{
+        log_schema, DataType, GlobalOptions, MetadataKeys, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::Event,
+    internal_events::LogpushLineParseError,
+    shutdown::ShutdownSignal,
+    sources::util::{ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    tls::TlsConfig,
+    Pipeline,
+};
+use bytes::{buf::BufExt, Bytes};
+use chrono::Utc;
+use flate2::read::MultiGzDecoder;
+use openssl::{
+    hash::MessageDigest,
+    memcmp,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, io::Read, net::SocketAddr};
+use warp::http::{HeaderMap, StatusCode};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const FASTLY_CHALLENGE_PATH: &str = "/.well-known/fastly/logging/challenge";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LogpushConfig {
+    address: SocketAddr,
+    #[serde(default = "default_path")]
+    path: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    signature: Option<SignatureConfig>,
+    #[serde(default)]
+    fastly_service_ids: Vec<String>,
+    tls: Option<TlsConfig>,
+    auth: Option<HttpSourceAuthConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SignatureConfig {
+    #[serde(default = "default_signature_header")]
+    header: String,
+    secret: String,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+fn default_signature_header() -> String {
+    "X-Signature".to_string()
+}
+
+inventory::submit! {
+    SourceDescription::new::<LogpushConfig>("logpush")
+}
+
+impl_generate_config_from_default!(LogpushConfig);
+
+impl Default for LogpushConfig {
+    fn default() -> Self {
+        Self {
+            address: "0.0.0.0:443".parse().unwrap(),
+            path: default_path(),
+            headers: HashMap::new(),
+            signature: None,
+            fastly_service_ids: Vec::new(),
+            tls: None,
+            auth: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct LogpushSource {
+    headers: HashMap<String, String>,
+    signature: Option<(String, PKey<Private>)>,
+    challenge: Option<String>,
+    metadata_keys: MetadataKeys,
+}
+
+impl LogpushSource {
+    fn new(config: &LogpushConfig, metadata_keys: MetadataKeys) -> crate::Result<Self> {
+        let signature = match &config.signature {
+            Some(signature) => Some((
+                signature.header.clone(),
+                PKey::hmac(signature.secret.as_bytes())?,
+            )),
+            None => None,
+        };
+
+        // Fastly only delivers to an endpoint that lists the SHA-256 of the
+        // service's ID, or `*` for any service, at its challenge path.
+        let challenge = if config.fastly_service_ids.is_empty() {
+            None
+        } else {
+            let lines = config
+                .fastly_service_ids
+                .iter()
+                .map(|id| match id.as_str() {
+                    "*" => "*".to_owned(),
+                    id => format!("{:x}", Sha256::digest(id.as_bytes())),
+                })
+                .collect::<Vec<_>>();
+            Some(lines.join("\n"))
+        };
+
+        Ok(Self {
+            headers: config.headers.clone(),
+            signature,
+            challenge,
+            metadata_keys,
+        })
+    }
+
+    fn verify_signature(&self, header_map: &HeaderMap, body: &Bytes) -> Result<(), ErrorMessage> {
+        let (header, key) = match &self.signature {
+            Some(signature) => signature,
+            None => return Ok(()),
+        };
+
+        let signature = header_map
+            .get(header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| unauthorized(format!("Missing signature header {:?}", header)))?;
+        let signature = signature.trim_start_matches("sha256=").to_ascii_lowercase();
+
+        let expected = Signer::new(MessageDigest::sha256(), key)
+            .and_then(|mut signer| {
+                signer.update(body)?;
+                signer.sign_to_vec()
+            })
+            .map_err(|error| {
+                ErrorMessage::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed computing signature: {}", error),
+                )
+            })?;
+        let expected = expected
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+
+        if constant_time_eq(signature.as_bytes(), expected.as_bytes()) {
+            Ok(())
+        } else {
+            Err(unauthorized("Invalid signature".to_owned()))
+        }
+    }
+}
+
+impl HttpSource for LogpushSource {
+    fn verify(&self, header_map: &HeaderMap, body: &Bytes) -> Result<(), ErrorMessage> {
+        for (name, expected) in &self.headers {
+            let value = header_map
+                .get(name.as_str())
+                .map(|value| value.as_bytes())
+                .unwrap_or_default();
+            if !constant_time_eq(value, expected.as_bytes()) {
+                return Err(unauthorized(format!("Invalid header {:?}", name)));
+            }
+        }
+
+        self.verify_signature(header_map, body)
+    }
+
+    fn challenge(&self, path: &str) -> Option<String> {
+        match path {
+            FASTLY_CHALLENGE_PATH => self.challenge.clone(),
+            _ => None,
+        }
+    }
+
+    fn build_event(
+        &self,
+        body: Bytes,
+        _header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        _full_path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        // Some services compress their batches without saying so.
+        let body = if body.starts_with(GZIP_MAGIC) {
+            let mut decompressed = Vec::new();
+            MultiGzDecoder::new(body.reader())
+                .read_to_end(&mut decompressed)
+                .map_err(|error| {
+                    ErrorMessage::new(
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        format!("Failed decompressing payload with gzip decoder: {}", error),
+                    )
+                })?;
+            Bytes::from(decompressed)
+        } else {
+            body
+        };
+
+        Ok(body_to_events(&body, &self.metadata_keys))
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "logpush")]
+impl SourceConfig for LogpushConfig {
+    async fn build(
+        &self,
+        _: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let source = LogpushSource::new(self, globals.metadata_keys.clone())?;
+        source.run(
+            self.address,
+            &self.path,
+            true,
+            &self.tls,
+            &self.auth,
+            out,
+            shutdown,
+        )
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "logpush"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+}
+
+fn unauthorized(message: String) -> ErrorMessage {
+    ErrorMessage::new(StatusCode::UNAUTHORIZED, message)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && memcmp::eq(a, b)
+}
+
+/// Splits a batch into an event per line. Lines holding a JSON object have
+/// their fields added to the event, and other lines are kept as the message,
+/// so that a single bad line doesn't get a whole batch redelivered.
+fn body_to_events(body: &[u8], metadata_keys: &MetadataKeys) -> Vec<Event> {
+    body.split(|&byte| byte == b'\n')
+        .map(|line| match line.split_last() {
+            Some((b'\r', line)) => line,
+            _ => line,
+        })
+        .filter(|line| !line.is_empty())
+        .map(|line| line_to_event(line, metadata_keys))
+        .collect()
+}
+
+fn line_to_event(line: &[u8], metadata_keys: &MetadataKeys) -> Event {
+    let mut event = match serde_json::from_slice::<JsonValue>(line) {
+        Ok(JsonValue::Object(map)) => {
+            let mut event = Event::new_empty_log();
+            let log = event.as_mut_log();
+            log.insert(log_schema().timestamp_key(), Utc::now());
+            for (key, value) in map {
+                log.insert_flat(key, value);
+            }
+            event
+        }
+        Ok(_) => Event::from(Bytes::copy_from_slice(line)),
+        Err(error) => {
+            emit!(LogpushLineParseError { error });
+            Event::from(Bytes::copy_from_slice(line))
+        }
+    };
+
+    metadata_keys.source_type.try_insert(
+        event.as_mut_log(),
+        log_schema().source_type_key(),
+        "logpush",
+    );
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::Value,
+        test_util::{collect_n, next_addr, trace_init, wait_for_tcp},
+    };
+    use flate2::{write::GzEncoder, Compression};
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+    use tokio::sync::mpsc;
+
+    const SECRET: &str = "s3cr3t";
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogpushConfig>();
+    }
+
+    async fn source(config: LogpushConfig) -> (mpsc::Receiver<Event>, SocketAddr) {
+        let (sender, recv) = Pipeline::new_test();
+        let address = next_addr();
+        tokio::spawn(async move {
+            LogpushConfig { address, ..config }
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    sender,
+                )
+                .await
+                .unwrap()
+                .await
+                .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    fn sign(body: &[u8]) -> String {
+        let key = PKey::hmac(SECRET.as_bytes()).unwrap();
+        let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+        signer.update(body).unwrap();
+        let signature = signer.sign_to_vec().unwrap();
+        signature
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    async fn send(address: SocketAddr, body: Vec<u8>, headers: &[(&str, &str)]) -> u16 {
+        let mut request = reqwest::Client::new().post(&format!("http://{}/", address));
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.body(body).send().await.unwrap().status().as_u16()
+    }
+
+    #[test]
+    fn splits_batches() {
+        let body = b"{\"ClientIP\":\"192.0.2.1\",\"EdgeResponseStatus\":200}\r\n\nnot json\n";
+        let events = body_to_events(body, &MetadataKeys::default());
+        assert_eq!(events.len(), 2);
+
+        let log = events[0].as_log();
+        assert_eq!(log["ClientIP"], "192.0.2.1".into());
+        assert_eq!(log["EdgeResponseStatus"], Value::Integer(200));
+        assert!(log.get(log_schema().message_key()).is_none());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+        assert_eq!(log[log_schema().source_type_key()], "logpush".into());
+
+        let log = events[1].as_log();
+        assert_eq!(log[log_schema().message_key()], "not json".into());
+    }
+
+    #[tokio::test]
+    async fn receives_gzipped_batches() {
+        trace_init();
+
+        let (rx, addr) = source(LogpushConfig::default()).await;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"{\"a\":1}\n{\"a\":2}\n").unwrap();
+        let body = encoder.finish().unwrap();
+
+        // With and without the header saying how the batch is compressed.
+        assert_eq!(
+            200,
+            send(addr, body.clone(), &[("Content-Encoding", "gzip")]).await
+        );
+        assert_eq!(200, send(addr, body, &[]).await);
+
+        let events = collect_n(rx, 4).await;
+        let values = events
+            .iter()
+            .map(|event| event.as_log()["a"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Integer(1),
+                Value::Integer(2)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn validates_signatures_and_headers() {
+        trace_init();
+
+        let mut headers = HashMap::new();
+        headers.insert("X-Logpush-Token".to_owned(), "token".to_owned());
+        let (mut rx, addr) = source(LogpushConfig {
+            headers,
+            signature: Some(SignatureConfig {
+                header: default_signature_header(),
+                secret: SECRET.to_owned(),
+            }),
+            ..LogpushConfig::default()
+        })
+        .await;
+
+        let body = b"{\"a\":1}\n".to_vec();
+        let signature = sign(&body);
+        let prefixed = format!("sha256={}", signature);
+
+        assert_eq!(
+            401,
+            send(addr, body.clone(), &[("X-Signature", signature.as_str())]).await
+        );
+        assert_eq!(
+            401,
+            send(addr, body.clone(), &[("X-Logpush-Token", "token")]).await
+        );
+        assert_eq!(
+            401,
+            send(
+                addr,
+                body.clone(),
+                &[
+                    ("X-Logpush-Token", "wrong"),
+                    ("X-Signature", signature.as_str())
+                ]
+            )
+            .await
+        );
+        assert_eq!(
+            401,
+            send(
+                addr,
+                b"{\"a\":2}\n".to_vec(),
+                &[
+                    ("X-Logpush-Token", "token"),
+                    ("X-Signature", signature.as_str())
+                ]
+            )
+            .await
+        );
+        assert!(rx.try_recv().is_err());
+
+        assert_eq!(
+            200,
+            send(
+                addr,
+                body,
+                &[
+                    ("X-Logpush-Token", "token"),
+                    ("X-Signature", prefixed.as_str())
+                ]
+            )
+            .await
+        );
+        let events = collect_n(rx, 1).await;
+        assert_eq!(events[0].as_log()["a"], Value::Integer(1));
+    }
+
+    #[tokio::test]
+    async fn answers_fastly_challenge() {
+        trace_init();
+
+        let (_rx, addr) = source(LogpushConfig {
+            fastly_service_ids: vec!["service".to_owned(), "*".to_owned()],
+            ..LogpushConfig::default()
+        })
+        .await;
+
+        let response = reqwest::get(&format!("http://{}{}", addr, FASTLY_CHALLENGE_PATH))
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.text().await.unwrap(),
+            format!("{:x}\n*", Sha256::digest(b"service"))
+        );
+
+        let response = reqwest::get(&format!("http://{}/other", addr))
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+    }
+}
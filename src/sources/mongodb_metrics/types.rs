@@ -18,6 +18,26 @@ pub struct CommandIsMaster {
     pub msg: Option<String>,
     pub set_name: Option<String>,
     pub hosts: Option<Vec<String>>,
+    #[serde(rename = "ismaster")]
+    pub is_master: Option<bool>,
+    pub secondary: Option<bool>,
+    pub arbiter_only: Option<bool>,
+}
+
+impl CommandIsMaster {
+    /// Role of the instance in its replica set, if it's a replica set member.
+    pub fn replica_set_role(&self) -> Option<&'static str> {
+        self.set_name.as_ref()?;
+        Some(if self.is_master == Some(true) {
+            "primary"
+        } else if self.secondary == Some(true) {
+            "secondary"
+        } else if self.arbiter_only == Some(true) {
+            "arbiter"
+        } else {
+            "other"
+        })
+    }
 }
 
 /// https://docs.mongodb.com/manual/reference/command/buildInfo/
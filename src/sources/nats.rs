@@ -0,0 +1,268 @@
+use crate::{
+    codecs::{self, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, MetadataKeys, SourceConfig,
+        SourceDescription,
+    },
+    internal_events::{DecoderFramingFailed, NatsEventReceived},
+    nats::NatsAuthConfig,
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use futures::{stream, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Decoder;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("NATS Connect Error: {}", source))]
+    Connect { source: std::io::Error },
+    #[snafu(display("NATS Subscribe Error: {}", source))]
+    Subscribe { source: std::io::Error },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsSourceConfig {
+    url: String,
+    #[serde(default = "default_name")]
+    name: String,
+    subject: String,
+    /// Subscribers of the same queue group share the messages of the subject, instead of each
+    /// receiving all of them.
+    queue: Option<String>,
+    #[serde(default = "default_subject_key")]
+    subject_key: String,
+    #[serde(flatten)]
+    auth: NatsAuthConfig,
+    /// Each message is a single frame, unless this is set.
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    decoding: DecodingConfig,
+}
+
+fn default_name() -> String {
+    String::from("vector")
+}
+
+fn default_subject_key() -> String {
+    String::from("subject")
+}
+
+inventory::submit! {
+    SourceDescription::new::<NatsSourceConfig>("nats")
+}
+
+impl GenerateConfig for NatsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            name = "vector"
+            subject = "from.vector"
+            url = "nats://127.0.0.1:4222""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "nats")]
+impl SourceConfig for NatsSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let subscription = self.subscribe().await?;
+        let decoder = codecs::Decoder::new(
+            self.framing
+                .unwrap_or(FramingConfig::Bytes)
+                .build(usize::MAX),
+            self.decoding,
+        );
+
+        Ok(Box::pin(nats_source(
+            subscription,
+            decoder,
+            self.subject_key.clone(),
+            globals.metadata_keys.clone(),
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "nats"
+    }
+}
+
+impl NatsSourceConfig {
+    async fn subscribe(&self) -> crate::Result<async_nats::Subscription> {
+        let options = async_nats::Options::new().with_name(&self.name);
+        let connection = self
+            .auth
+            .apply(options)?
+            .connect(&self.url)
+            .await
+            .context(Connect)?;
+
+        let subscription = match &self.queue {
+            Some(queue) => connection.queue_subscribe(&self.subject, queue).await,
+            None => connection.subscribe(&self.subject).await,
+        };
+        Ok(subscription.context(Subscribe)?)
+    }
+}
+
+async fn nats_source(
+    subscription: async_nats::Subscription,
+    mut decoder: codecs::Decoder,
+    subject_key: String,
+    metadata_keys: MetadataKeys,
+    mut shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    loop {
+        let message = tokio::select! {
+            message = subscription.next() => match message {
+                Some(message) => message,
+                None => break,
+            },
+            _ = &mut shutdown => break,
+        };
+        emit!(NatsEventReceived {
+            byte_size: message.data.len(),
+        });
+
+        let now = Utc::now();
+        let mut payload = BytesMut::from(&message.data[..]);
+        let mut events = Vec::new();
+        loop {
+            let mut event = match decoder.decode_eof(&mut payload) {
+                Ok(Some((event, _))) => event,
+                Ok(None) => break,
+                Err(error) => {
+                    // The rest of the message can't be framed.
+                    emit!(DecoderFramingFailed { error: &error });
+                    break;
+                }
+            };
+
+            let log = event.as_mut_log();
+            log.try_insert(log_schema().timestamp_key(), now);
+            metadata_keys.source_type.insert(
+                log,
+                log_schema().source_type_key(),
+                Bytes::from("nats"),
+            );
+            log.insert(subject_key.as_str(), message.subject.clone());
+            events.push(event);
+        }
+
+        if let Err(error) = out.send_all(&mut stream::iter(events).map(Ok)).await {
+            error!(message = "Error sending to sink.", %error);
+            return Err(());
+        }
+    }
+
+    // Messages the server already sent are dropped, as NATS doesn't redeliver them anyway.
+    let _ = subscription.unsubscribe().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NatsSourceConfig>();
+    }
+
+    #[test]
+    fn parses_credentials_and_tls() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "logs.>"
+            queue = "vector"
+            credentials_file = "/etc/nats/user.creds"
+            tls.enabled = true
+            tls.ca_file = "/etc/nats/ca.pem""#,
+        )
+        .unwrap();
+
+        assert_eq!(config.queue.as_deref(), Some("vector"));
+        assert!(config.auth.credentials_file.is_some());
+        assert!(config.auth.apply(async_nats::Options::new()).is_ok());
+    }
+
+    #[test]
+    fn rejects_unsupported_tls_options() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "logs"
+            tls.enabled = true
+            tls.verify_certificate = false"#,
+        )
+        .unwrap();
+
+        assert!(config.auth.apply(async_nats::Options::new()).is_err());
+    }
+}
+
+#[cfg(feature = "nats-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string, trace_init};
+
+    #[tokio::test]
+    async fn nats_source_receives_messages() {
+        trace_init();
+
+        let subject = format!("test-{}", random_string(10));
+        let config: NatsSourceConfig = toml::from_str(&format!(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "{}""#,
+            subject
+        ))
+        .unwrap();
+
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let publisher = async_nats::connect("nats://127.0.0.1:4222").await.unwrap();
+        for line in &["first", "second"] {
+            publisher.publish(&subject, line).await.unwrap();
+        }
+
+        let events = collect_n(rx, 2).await;
+        for (event, line) in events.iter().zip(&["first", "second"]) {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], (*line).into());
+            assert_eq!(log["subject"], subject.clone().into());
+            assert_eq!(log[log_schema().source_type_key()], "nats".into());
+        }
+    }
+}
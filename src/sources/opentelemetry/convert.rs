@@ -0,0 +1,545 @@
+//! Translation of OTLP requests into events.
+
+use crate::{
+    config::{log_schema, MetadataKeys},
+    event::{
+        metric::{Bucket, MetricTags, Quantile},
+        Event, Metric, MetricKind, MetricValue, Value,
+    },
+    proto::opentelemetry::proto::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+        logs::v1::LogRecord,
+        metrics::v1::{metric::Data, number_data_point, AggregationTemporality, NumberDataPoint},
+    },
+};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::BTreeMap;
+
+const SOURCE_TYPE: &str = "opentelemetry";
+
+/// Each log record becomes a log event, with the attributes of its resource under `resources`
+/// and its own attributes under `attributes`.
+pub(super) fn convert_logs(
+    request: ExportLogsServiceRequest,
+    metadata_keys: &MetadataKeys,
+) -> Vec<Event> {
+    let mut events = Vec::new();
+    for resource_logs in request.resource_logs {
+        let resources = resource_logs
+            .resource
+            .map(|resource| convert_attributes(resource.attributes))
+            .unwrap_or_default();
+        for scope_logs in resource_logs.scope_logs {
+            for record in scope_logs.log_records {
+                events.push(convert_log(
+                    record,
+                    &resources,
+                    scope_logs.scope.as_ref(),
+                    metadata_keys,
+                ));
+            }
+        }
+    }
+    events
+}
+
+fn convert_log(
+    record: LogRecord,
+    resources: &BTreeMap<String, Value>,
+    scope: Option<&InstrumentationScope>,
+    metadata_keys: &MetadataKeys,
+) -> Event {
+    let mut event = Event::new_empty_log();
+    let log = event.as_mut_log();
+
+    log.insert(log_schema().message_key(), convert_value(record.body));
+    let timestamp = timestamp(record.time_unix_nano)
+        .or_else(|| timestamp(record.observed_time_unix_nano))
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+
+    if !record.attributes.is_empty() {
+        log.insert_flat("attributes", convert_attributes(record.attributes));
+    }
+    if !resources.is_empty() {
+        log.insert_flat("resources", resources.clone());
+    }
+    if let Some(scope) = scope {
+        if !scope.name.is_empty() {
+            log.insert("scope.name", scope.name.clone());
+        }
+        if !scope.version.is_empty() {
+            log.insert("scope.version", scope.version.clone());
+        }
+    }
+    if !record.severity_text.is_empty() {
+        log.insert("severity_text", record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert("severity_number", record.severity_number as i64);
+    }
+    if !record.trace_id.is_empty() {
+        log.insert("trace_id", to_hex(&record.trace_id));
+    }
+    if !record.span_id.is_empty() {
+        log.insert("span_id", to_hex(&record.span_id));
+    }
+    if record.flags != 0 {
+        log.insert("flags", record.flags as i64);
+    }
+    metadata_keys.source_type.insert(
+        log,
+        log_schema().source_type_key(),
+        Bytes::from(SOURCE_TYPE),
+    );
+
+    event
+}
+
+/// Each data point becomes a metric, tagged with its attributes along with those of its
+/// resource, prefixed by `resource.`, and the name and version of its scope.
+pub(super) fn convert_metrics(request: ExportMetricsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+    for resource_metrics in request.resource_metrics {
+        let mut resource_tags = MetricTags::new();
+        if let Some(resource) = resource_metrics.resource {
+            for (key, value) in convert_attributes(resource.attributes) {
+                resource_tags.insert(format!("resource.{}", key), value.to_string_lossy());
+            }
+        }
+
+        for scope_metrics in resource_metrics.scope_metrics {
+            let mut scope_tags = resource_tags.clone();
+            if let Some(scope) = scope_metrics.scope {
+                if !scope.name.is_empty() {
+                    scope_tags.insert("scope.name".into(), scope.name);
+                }
+                if !scope.version.is_empty() {
+                    scope_tags.insert("scope.version".into(), scope.version);
+                }
+            }
+
+            for metric in scope_metrics.metrics {
+                let name = metric.name;
+                let mut push = |kind, value, attributes, time_unix_nano| {
+                    let mut tags = scope_tags.clone();
+                    for (key, value) in convert_attributes(attributes) {
+                        tags.insert(key, value.to_string_lossy());
+                    }
+                    let tags = if tags.is_empty() { None } else { Some(tags) };
+                    let metric = Metric::new(name.clone(), kind, value)
+                        .with_tags(tags)
+                        .with_timestamp(timestamp(time_unix_nano));
+                    events.push(Event::Metric(metric));
+                };
+
+                match metric.data {
+                    Some(Data::Gauge(gauge)) => {
+                        for point in gauge.data_points {
+                            if let Some(value) = number_value(&point) {
+                                let value = MetricValue::Gauge { value };
+                                push(
+                                    MetricKind::Absolute,
+                                    value,
+                                    point.attributes,
+                                    point.time_unix_nano,
+                                );
+                            }
+                        }
+                    }
+                    Some(Data::Sum(sum)) => {
+                        let kind = if sum.aggregation_temporality
+                            == AggregationTemporality::Delta as i32
+                        {
+                            MetricKind::Incremental
+                        } else {
+                            MetricKind::Absolute
+                        };
+                        for point in sum.data_points {
+                            if let Some(value) = number_value(&point) {
+                                // Sums that can go down are only meaningful as gauges.
+                                let value = if sum.is_monotonic {
+                                    MetricValue::Counter { value }
+                                } else {
+                                    MetricValue::Gauge { value }
+                                };
+                                push(kind, value, point.attributes, point.time_unix_nano);
+                            }
+                        }
+                    }
+                    Some(Data::Histogram(histogram)) => {
+                        let kind = if histogram.aggregation_temporality
+                            == AggregationTemporality::Delta as i32
+                        {
+                            MetricKind::Incremental
+                        } else {
+                            MetricKind::Absolute
+                        };
+                        for point in histogram.data_points {
+                            // There is one more bucket than bounds, the last one being
+                            // unbounded, which is implied by the total count.
+                            let buckets = point
+                                .explicit_bounds
+                                .iter()
+                                .zip(point.bucket_counts.iter())
+                                .map(|(&upper_limit, &count)| Bucket {
+                                    upper_limit,
+                                    count: count as u32,
+                                })
+                                .collect();
+                            let value = MetricValue::AggregatedHistogram {
+                                buckets,
+                                count: point.count as u32,
+                                sum: point.sum,
+                            };
+                            push(kind, value, point.attributes, point.time_unix_nano);
+                        }
+                    }
+                    Some(Data::Summary(summary)) => {
+                        for point in summary.data_points {
+                            let quantiles = point
+                                .quantile_values
+                                .iter()
+                                .map(|quantile| Quantile {
+                                    upper_limit: quantile.quantile,
+                                    value: quantile.value,
+                                })
+                                .collect();
+                            let value = MetricValue::AggregatedSummary {
+                                quantiles,
+                                count: point.count as u32,
+                                sum: point.sum,
+                            };
+                            push(
+                                MetricKind::Absolute,
+                                value,
+                                point.attributes,
+                                point.time_unix_nano,
+                            );
+                        }
+                    }
+                    None => (),
+                }
+            }
+        }
+    }
+    events
+}
+
+fn number_value(point: &NumberDataPoint) -> Option<f64> {
+    match point.value {
+        Some(number_data_point::Value::AsDouble(value)) => Some(value),
+        Some(number_data_point::Value::AsInt(value)) => Some(value as f64),
+        None => None,
+    }
+}
+
+fn convert_attributes(attributes: Vec<KeyValue>) -> BTreeMap<String, Value> {
+    attributes
+        .into_iter()
+        .map(|attribute| (attribute.key, convert_value(attribute.value)))
+        .collect()
+}
+
+fn convert_value(value: Option<AnyValue>) -> Value {
+    match value.and_then(|value| value.value) {
+        Some(any_value::Value::StringValue(value)) => Value::from(value),
+        Some(any_value::Value::BoolValue(value)) => Value::from(value),
+        Some(any_value::Value::IntValue(value)) => Value::from(value),
+        Some(any_value::Value::DoubleValue(value)) => Value::from(value),
+        Some(any_value::Value::BytesValue(value)) => Value::from(Bytes::from(value)),
+        Some(any_value::Value::ArrayValue(array)) => Value::Array(
+            array
+                .values
+                .into_iter()
+                .map(|value| convert_value(Some(value)))
+                .collect(),
+        ),
+        Some(any_value::Value::KvlistValue(list)) => Value::from(convert_attributes(list.values)),
+        None => Value::Null,
+    }
+}
+
+/// Zero stands for an unset time.
+fn timestamp(unix_nano: u64) -> Option<DateTime<Utc>> {
+    if unix_nano == 0 {
+        None
+    } else {
+        Some(Utc.timestamp_nanos(unix_nano as i64))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::opentelemetry::proto::{
+        common::v1::KeyValueList,
+        logs::v1::{ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            self as metrics, summary_data_point::ValueAtQuantile, HistogramDataPoint,
+            ResourceMetrics, ScopeMetrics, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+    };
+
+    fn string(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        })
+    }
+
+    fn attribute(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: string(value),
+        }
+    }
+
+    fn scope() -> Option<InstrumentationScope> {
+        Some(InstrumentationScope {
+            name: "checkout".into(),
+            version: "1.2.0".into(),
+            ..Default::default()
+        })
+    }
+
+    fn resource() -> Option<Resource> {
+        Some(Resource {
+            attributes: vec![attribute("service.name", "shop")],
+            ..Default::default()
+        })
+    }
+
+    fn metrics_request(metrics: Vec<metrics::Metric>) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: resource(),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: scope(),
+                    metrics,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn number_point(value: f64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![attribute("host", "a")],
+            time_unix_nano: 1_600_000_000_000_000_000,
+            value: Some(number_data_point::Value::AsDouble(value)),
+            ..Default::default()
+        }
+    }
+
+    fn expected_tags() -> Option<MetricTags> {
+        Some(
+            vec![
+                ("host".to_owned(), "a".to_owned()),
+                ("resource.service.name".to_owned(), "shop".to_owned()),
+                ("scope.name".to_owned(), "checkout".to_owned()),
+                ("scope.version".to_owned(), "1.2.0".to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn converts_logs() {
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: resource(),
+                scope_logs: vec![ScopeLogs {
+                    scope: scope(),
+                    log_records: vec![LogRecord {
+                        time_unix_nano: 1_600_000_000_000_000_000,
+                        severity_number: 9,
+                        severity_text: "INFO".into(),
+                        body: string("order placed"),
+                        attributes: vec![KeyValue {
+                            key: "order".into(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::KvlistValue(KeyValueList {
+                                    values: vec![attribute("id", "42")],
+                                })),
+                            }),
+                        }],
+                        trace_id: vec![0x0a; 16],
+                        span_id: vec![0xff; 8],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let events = convert_logs(request, &MetadataKeys::default());
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "order placed".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1_600_000_000, 0).into()
+        );
+        assert_eq!(log["attributes.order.id"], "42".into());
+        assert_eq!(log["resources.service\\.name"], "shop".into());
+        assert_eq!(log["scope.name"], "checkout".into());
+        assert_eq!(log["scope.version"], "1.2.0".into());
+        assert_eq!(log["severity_text"], "INFO".into());
+        assert_eq!(log["severity_number"], 9.into());
+        assert_eq!(log["trace_id"], "0a".repeat(16).into());
+        assert_eq!(log["span_id"], "ff".repeat(8).into());
+        assert_eq!(log[log_schema().source_type_key()], "opentelemetry".into());
+    }
+
+    #[test]
+    fn converts_gauges_and_sums() {
+        let request = metrics_request(vec![
+            metrics::Metric {
+                name: "temperature".into(),
+                data: Some(Data::Gauge(metrics::Gauge {
+                    data_points: vec![number_point(21.5)],
+                })),
+                ..Default::default()
+            },
+            metrics::Metric {
+                name: "requests".into(),
+                data: Some(Data::Sum(metrics::Sum {
+                    data_points: vec![number_point(3.0)],
+                    aggregation_temporality: AggregationTemporality::Delta as i32,
+                    is_monotonic: true,
+                })),
+                ..Default::default()
+            },
+            metrics::Metric {
+                name: "connections".into(),
+                data: Some(Data::Sum(metrics::Sum {
+                    data_points: vec![number_point(7.0)],
+                    aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                    is_monotonic: false,
+                })),
+                ..Default::default()
+            },
+        ]);
+
+        let timestamp = Some(Utc.timestamp(1_600_000_000, 0));
+        let metrics = convert_metrics(request)
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            metrics,
+            vec![
+                Metric::new(
+                    "temperature",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 21.5 }
+                )
+                .with_tags(expected_tags())
+                .with_timestamp(timestamp),
+                Metric::new(
+                    "requests",
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 3.0 }
+                )
+                .with_tags(expected_tags())
+                .with_timestamp(timestamp),
+                Metric::new(
+                    "connections",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 7.0 }
+                )
+                .with_tags(expected_tags())
+                .with_timestamp(timestamp),
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_histograms_and_summaries() {
+        let request = metrics_request(vec![
+            metrics::Metric {
+                name: "latency".into(),
+                data: Some(Data::Histogram(metrics::Histogram {
+                    data_points: vec![HistogramDataPoint {
+                        attributes: vec![attribute("host", "a")],
+                        count: 6,
+                        sum: 4.5,
+                        bucket_counts: vec![1, 2, 3],
+                        explicit_bounds: vec![0.5, 1.0],
+                        ..Default::default()
+                    }],
+                    aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                })),
+                ..Default::default()
+            },
+            metrics::Metric {
+                name: "size".into(),
+                data: Some(Data::Summary(metrics::Summary {
+                    data_points: vec![SummaryDataPoint {
+                        attributes: vec![attribute("host", "a")],
+                        count: 2,
+                        sum: 30.0,
+                        quantile_values: vec![ValueAtQuantile {
+                            quantile: 0.5,
+                            value: 10.0,
+                        }],
+                        ..Default::default()
+                    }],
+                })),
+                ..Default::default()
+            },
+        ]);
+
+        let metrics = convert_metrics(request)
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            metrics,
+            vec![
+                Metric::new(
+                    "latency",
+                    MetricKind::Absolute,
+                    MetricValue::AggregatedHistogram {
+                        buckets: vec![
+                            Bucket {
+                                upper_limit: 0.5,
+                                count: 1
+                            },
+                            Bucket {
+                                upper_limit: 1.0,
+                                count: 2
+                            },
+                        ],
+                        count: 6,
+                        sum: 4.5,
+                    }
+                )
+                .with_tags(expected_tags()),
+                Metric::new(
+                    "size",
+                    MetricKind::Absolute,
+                    MetricValue::AggregatedSummary {
+                        quantiles: vec![Quantile {
+                            upper_limit: 0.5,
+                            value: 10.0
+                        }],
+                        count: 2,
+                        sum: 30.0,
+                    }
+                )
+                .with_tags(expected_tags()),
+            ]
+        );
+    }
+}
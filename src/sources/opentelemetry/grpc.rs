@@ -0,0 +1,109 @@
+use super::convert::{convert_logs, convert_metrics};
+use crate::{
+    config::MetadataKeys,
+    event::Event,
+    internal_events::OpentelemetryEventsReceived,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        metrics::v1::{
+            metrics_service_server::{MetricsService, MetricsServiceServer},
+            ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        },
+    },
+    shutdown::ShutdownSignal,
+    sources::Source,
+    tls::MaybeTlsSettings,
+    Pipeline,
+};
+use futures::{stream, FutureExt, SinkExt, StreamExt};
+use prost::Message;
+use std::net::SocketAddr;
+use tonic::{transport::Server, Request, Response, Status};
+
+#[derive(Debug, Clone)]
+struct Service {
+    pipeline: Pipeline,
+    metadata_keys: MetadataKeys,
+}
+
+impl Service {
+    /// The response is the acknowledgement of the events, so it's only sent once they are
+    /// accepted by the pipeline.
+    async fn send(&self, events: Vec<Event>, byte_size: usize) -> Result<(), Status> {
+        emit!(OpentelemetryEventsReceived {
+            count: events.len(),
+            byte_size,
+        });
+
+        self.pipeline
+            .clone()
+            .send_all(&mut stream::iter(events).map(Ok))
+            .await
+            .map_err(|error| {
+                error!(message = "Failed to forward events, downstream is closed.", %error);
+                Status::unavailable("Downstream is closed.")
+            })
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let byte_size = request.get_ref().encoded_len();
+        let events = convert_logs(request.into_inner(), &self.metadata_keys);
+        self.send(events, byte_size)
+            .await
+            .map(|()| Response::new(ExportLogsServiceResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let byte_size = request.get_ref().encoded_len();
+        self.send(convert_metrics(request.into_inner()), byte_size)
+            .await
+            .map(|()| Response::new(ExportMetricsServiceResponse {}))
+    }
+}
+
+pub(super) fn run(
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    metadata_keys: MetadataKeys,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> Source {
+    let service = Service {
+        pipeline: out,
+        metadata_keys,
+    };
+
+    Box::pin(async move {
+        let listener = tls.bind(&address).await.map_err(|error| {
+            error!(message = "Failed to bind to listener socket.", %error);
+        })?;
+        info!(message = "Listening.", address = %address, protocol = "grpc");
+
+        Server::builder()
+            .add_service(LogsServiceServer::new(service.clone()))
+            .add_service(MetricsServiceServer::new(service))
+            .serve_with_incoming_shutdown(listener.accept_stream(), shutdown.clone().map(|_| ()))
+            .await
+            .map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            })?;
+        // We need to drop the last copy of ShutdownSignalToken only after server has shut down.
+        drop(shutdown);
+        Ok(())
+    })
+}
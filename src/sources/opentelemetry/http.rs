@@ -0,0 +1,63 @@
+use super::convert::{convert_logs, convert_metrics};
+use crate::{
+    config::MetadataKeys,
+    event::Event,
+    proto::opentelemetry::proto::collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+    },
+    sources::util::{ErrorMessage, HttpSource},
+};
+use bytes::Bytes;
+use prost::Message;
+use std::collections::HashMap;
+use warp::http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
+
+pub(super) const PATH: &str = "/v1";
+const LOGS_PATH: &str = "/v1/logs";
+const METRICS_PATH: &str = "/v1/metrics";
+
+/// OTLP/HTTP, which carries the same requests as OTLP/gRPC as the body of `POST`s to a path
+/// per signal.
+#[derive(Clone)]
+pub(super) struct OpentelemetryHttpSource {
+    pub(super) metadata_keys: MetadataKeys,
+}
+
+impl HttpSource for OpentelemetryHttpSource {
+    fn build_event(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        // The JSON encoding of OTLP isn't supported.
+        match header_map.get(CONTENT_TYPE).map(|value| value.as_bytes()) {
+            None | Some(b"application/x-protobuf") => (),
+            Some(_) => {
+                return Err(ErrorMessage::new(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    "Only `application/x-protobuf` bodies are supported".to_owned(),
+                ))
+            }
+        }
+
+        let events = match path {
+            LOGS_PATH => ExportLogsServiceRequest::decode(body)
+                .map(|request| convert_logs(request, &self.metadata_keys)),
+            METRICS_PATH => ExportMetricsServiceRequest::decode(body).map(convert_metrics),
+            _ => {
+                return Err(ErrorMessage::new(
+                    StatusCode::NOT_FOUND,
+                    "Not found".to_owned(),
+                ))
+            }
+        };
+        events.map_err(|error| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid OTLP request: {}", error),
+            )
+        })
+    }
+}
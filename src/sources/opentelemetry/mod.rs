@@ -0,0 +1,239 @@
+mod convert;
+mod grpc;
+mod http;
+
+use self::http::OpentelemetryHttpSource;
+use crate::{
+    config::{DataType, GlobalOptions, Resource, SourceConfig, SourceDescription},
+    shutdown::ShutdownSignal,
+    sources::util::HttpSource,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use futures::{future, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    #[serde(default = "default_grpc")]
+    grpc: ListenerConfig,
+    #[serde(default = "default_http")]
+    http: ListenerConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ListenerConfig {
+    address: SocketAddr,
+    tls: Option<TlsConfig>,
+}
+
+fn default_grpc() -> ListenerConfig {
+    ListenerConfig {
+        address: "0.0.0.0:4317".parse().unwrap(),
+        tls: None,
+    }
+}
+
+fn default_http() -> ListenerConfig {
+    ListenerConfig {
+        address: "0.0.0.0:4318".parse().unwrap(),
+        tls: None,
+    }
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
+
+impl_generate_config_from_default!(OpentelemetryConfig);
+
+impl Default for OpentelemetryConfig {
+    fn default() -> Self {
+        Self {
+            grpc: default_grpc(),
+            http: default_http(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpentelemetryConfig {
+    async fn build(
+        &self,
+        _: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let grpc_tls = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
+        let grpc = grpc::run(
+            self.grpc.address,
+            grpc_tls,
+            globals.metadata_keys.clone(),
+            shutdown.clone(),
+            out.clone(),
+        );
+        let http_source = OpentelemetryHttpSource {
+            metadata_keys: globals.metadata_keys.clone(),
+        };
+        let http = http_source.run(
+            self.http.address,
+            http::PATH,
+            false,
+            &self.http.tls,
+            &None,
+            out,
+            shutdown,
+        )?;
+
+        Ok(Box::pin(future::try_join(grpc, http).map_ok(|_| ())))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            Resource::tcp(self.grpc.address),
+            Resource::tcp(self.http.address),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::Event,
+        proto::opentelemetry::proto::{
+            collector::logs::v1::ExportLogsServiceRequest,
+            common::v1::{any_value, AnyValue},
+            logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        },
+        test_util::{collect_n, next_addr, trace_init, wait_for_tcp},
+    };
+    use prost::Message;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    async fn source() -> (mpsc::Receiver<Event>, SocketAddr) {
+        let (sender, recv) = Pipeline::new_test();
+        let config = OpentelemetryConfig {
+            grpc: ListenerConfig {
+                address: next_addr(),
+                tls: None,
+            },
+            http: ListenerConfig {
+                address: next_addr(),
+                tls: None,
+            },
+        };
+        let address = config.http.address;
+        tokio::spawn(async move {
+            config
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    sender,
+                )
+                .await
+                .unwrap()
+                .await
+                .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn send(address: SocketAddr, path: &str, content_type: &str, body: Vec<u8>) -> u16 {
+        reqwest::Client::new()
+            .post(&format!("http://{}{}", address, path))
+            .header("Content-Type", content_type)
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    fn logs_request(message: &str) -> Vec<u8> {
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: vec![LogRecord {
+                        body: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue(message.into())),
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let mut body = Vec::new();
+        request.encode(&mut body).unwrap();
+        body
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_http() {
+        trace_init();
+        let (rx, address) = source().await;
+
+        let status = send(
+            address,
+            "/v1/logs",
+            "application/x-protobuf",
+            logs_request("hello"),
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log[log_schema().source_type_key()], "opentelemetry".into());
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_requests_over_http() {
+        trace_init();
+        let (_rx, address) = source().await;
+
+        let status = send(address, "/v1/logs", "application/json", b"{}".to_vec()).await;
+        assert_eq!(status, 415);
+        let status = send(
+            address,
+            "/v1/traces",
+            "application/x-protobuf",
+            logs_request("hello"),
+        )
+        .await;
+        assert_eq!(status, 404);
+        let status = send(
+            address,
+            "/v1/logs",
+            "application/x-protobuf",
+            b"not protobuf".to_vec(),
+        )
+        .await;
+        assert_eq!(status, 400);
+    }
+}
@@ -0,0 +1,283 @@
+//! Rewrites or drops scraped metrics with rules in the style of Prometheus'
+//! `metric_relabel_configs`.
+//! https://prometheus.io/docs/prometheus/latest/configuration/configuration/#metric_relabel_configs
+
+use crate::event::Metric;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+/// The pseudo label holding the name of the metric.
+const NAME_LABEL: &str = "__name__";
+
+#[derive(Debug, Snafu)]
+pub enum RelabelError {
+    #[snafu(display("invalid relabel regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("relabel action `replace` requires `target_label`"))]
+    MissingTargetLabel,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    Replace,
+    Keep,
+    Drop,
+    LabelDrop,
+    LabelKeep,
+}
+
+impl Default for RelabelAction {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    #[serde(default)]
+    source_labels: Vec<String>,
+    #[serde(default = "default_separator")]
+    separator: String,
+    #[serde(default = "default_regex")]
+    regex: String,
+    target_label: Option<String>,
+    #[serde(default = "default_replacement")]
+    replacement: String,
+    #[serde(default)]
+    action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".into()
+}
+
+fn default_regex() -> String {
+    "(.*)".into()
+}
+
+fn default_replacement() -> String {
+    "$1".into()
+}
+
+impl RelabelConfig {
+    pub fn build(&self) -> Result<RelabelRule, RelabelError> {
+        if self.action == RelabelAction::Replace && self.target_label.is_none() {
+            return Err(RelabelError::MissingTargetLabel);
+        }
+        // As in Prometheus, the regex has to match the whole value.
+        let regex = Regex::new(&format!("^(?:{})$", self.regex)).context(InvalidRegex {
+            regex: self.regex.clone(),
+        })?;
+
+        Ok(RelabelRule {
+            source_labels: self.source_labels.clone(),
+            separator: self.separator.clone(),
+            regex,
+            target_label: self.target_label.clone().unwrap_or_default(),
+            replacement: self.replacement.clone(),
+            action: self.action,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RelabelRule {
+    source_labels: Vec<String>,
+    separator: String,
+    regex: Regex,
+    target_label: String,
+    replacement: String,
+    action: RelabelAction,
+}
+
+impl RelabelRule {
+    /// Applies the rule to the metric, returning `None` if it's dropped.
+    pub fn apply(&self, mut metric: Metric) -> Option<Metric> {
+        match self.action {
+            RelabelAction::Replace => {
+                let value = self.source_value(&metric);
+                if let Some(captures) = self.regex.captures(&value) {
+                    let mut target = String::new();
+                    captures.expand(&self.target_label, &mut target);
+                    let mut replacement = String::new();
+                    captures.expand(&self.replacement, &mut replacement);
+
+                    if target == NAME_LABEL {
+                        // A metric can't be left without a name.
+                        if !replacement.is_empty() {
+                            metric.series.name.name = replacement;
+                        }
+                    } else if replacement.is_empty() {
+                        metric.delete_tag(&target);
+                    } else {
+                        metric.set_tag_value(target, replacement);
+                    }
+                }
+                Some(metric)
+            }
+            RelabelAction::Keep => {
+                let value = self.source_value(&metric);
+                self.regex.is_match(&value).then(|| metric)
+            }
+            RelabelAction::Drop => {
+                let value = self.source_value(&metric);
+                (!self.regex.is_match(&value)).then(|| metric)
+            }
+            RelabelAction::LabelDrop => {
+                if let Some(tags) = metric.tags_mut() {
+                    *tags = std::mem::take(tags)
+                        .into_iter()
+                        .filter(|(name, _)| !self.regex.is_match(name))
+                        .collect();
+                }
+                Some(metric)
+            }
+            RelabelAction::LabelKeep => {
+                if let Some(tags) = metric.tags_mut() {
+                    *tags = std::mem::take(tags)
+                        .into_iter()
+                        .filter(|(name, _)| self.regex.is_match(name))
+                        .collect();
+                }
+                Some(metric)
+            }
+        }
+    }
+
+    /// The values of the source labels joined by the separator, with missing labels empty.
+    fn source_value(&self, metric: &Metric) -> String {
+        self.source_labels
+            .iter()
+            .map(|label| {
+                if label == NAME_LABEL {
+                    metric.name().to_owned()
+                } else {
+                    metric.tag_value(label).unwrap_or_default()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{MetricKind, MetricValue};
+    use shared::btreemap;
+
+    fn rule(config: &str) -> RelabelRule {
+        toml::from_str::<RelabelConfig>(config)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn metric() -> Metric {
+        Metric::new(
+            "http_requests_total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(btreemap! {
+            "code" => "200",
+            "method" => "get",
+        }))
+    }
+
+    #[test]
+    fn replaces_labels() {
+        let rule = rule(
+            r#"
+            source_labels = ["method", "code"]
+            regex = "(.*);2.."
+            target_label = "success"
+            replacement = "${1}_ok""#,
+        );
+        let metric = rule.apply(metric()).unwrap();
+        assert_eq!(metric.tag_value("success"), Some("get_ok".into()));
+
+        // The regex has to match the whole value.
+        let rule = self::rule(
+            r#"
+            source_labels = ["code"]
+            regex = "2"
+            target_label = "success"
+            replacement = "yes""#,
+        );
+        let metric = rule.apply(metric()).unwrap();
+        assert_eq!(metric.tag_value("success"), None);
+    }
+
+    #[test]
+    fn renames_metrics() {
+        let rule = rule(
+            r#"
+            source_labels = ["__name__"]
+            regex = "http_(.*)"
+            target_label = "__name__""#,
+        );
+        assert_eq!(rule.apply(metric()).unwrap().name(), "requests_total");
+    }
+
+    #[test]
+    fn removes_labels_replaced_with_nothing() {
+        let rule = rule(
+            r#"
+            target_label = "method"
+            replacement = """#,
+        );
+        assert_eq!(rule.apply(metric()).unwrap().tag_value("method"), None);
+    }
+
+    #[test]
+    fn keeps_and_drops_metrics() {
+        let keep = rule(
+            r#"
+            action = "keep"
+            source_labels = ["code"]
+            regex = "2..""#,
+        );
+        assert!(keep.apply(metric()).is_some());
+
+        let drop = rule(
+            r#"
+            action = "drop"
+            source_labels = ["__name__"]
+            regex = "http_.*""#,
+        );
+        assert!(drop.apply(metric()).is_none());
+    }
+
+    #[test]
+    fn keeps_and_drops_labels() {
+        let drop = rule(
+            r#"
+            action = "labeldrop"
+            regex = "meth.*""#,
+        );
+        assert_eq!(
+            drop.apply(metric()).unwrap().tags(),
+            Some(&btreemap! { "code" => "200" })
+        );
+
+        let keep = rule(
+            r#"
+            action = "labelkeep"
+            regex = "meth.*""#,
+        );
+        assert_eq!(
+            keep.apply(metric()).unwrap().tags(),
+            Some(&btreemap! { "method" => "get" })
+        );
+    }
+
+    #[test]
+    fn requires_target_label_to_replace() {
+        let config = toml::from_str::<RelabelConfig>(r#"source_labels = ["code"]"#).unwrap();
+        assert!(config.build().is_err());
+    }
+}
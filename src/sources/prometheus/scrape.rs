@@ -1,6 +1,10 @@
-use super::parser;
+use super::{
+    parser,
+    relabel::{RelabelConfig, RelabelRule},
+};
 use crate::{
     config::{self, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription},
+    event::{Event, Metric},
     http::Auth,
     http::HttpClient,
     internal_events::{
@@ -18,6 +22,7 @@ use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
     future::ready,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -35,6 +40,17 @@ struct PrometheusScrapeConfig {
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
 
+    /// Tag holding the `host:port` of the endpoint each metric was scraped from.
+    instance_tag: Option<String>,
+    /// Tag holding the URL of the endpoint each metric was scraped from.
+    endpoint_tag: Option<String>,
+    /// Keeps scraped labels that conflict with the tags above, instead of renaming them
+    /// with an `exported_` prefix.
+    #[serde(default)]
+    honor_labels: bool,
+    #[serde(default)]
+    metric_relabel_configs: Vec<RelabelConfig>,
+
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
@@ -57,6 +73,10 @@ impl GenerateConfig for PrometheusScrapeConfig {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
             scrape_interval_secs: default_scrape_interval_secs(),
+            instance_tag: None,
+            endpoint_tag: None,
+            honor_labels: false,
+            metric_relabel_configs: Vec::new(),
             tls: None,
             auth: None,
         })
@@ -80,10 +100,21 @@ impl SourceConfig for PrometheusScrapeConfig {
             .map(|s| s.parse::<http::Uri>().context(sources::UriParseError))
             .collect::<Result<Vec<http::Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
+        let labeling = Labeling {
+            instance_tag: self.instance_tag.clone(),
+            endpoint_tag: self.endpoint_tag.clone(),
+            honor_labels: self.honor_labels,
+            relabel: self
+                .metric_relabel_configs
+                .iter()
+                .map(RelabelConfig::build)
+                .collect::<Result<_, _>>()?,
+        };
         Ok(prometheus(
             urls,
             tls,
             self.auth.clone(),
+            labeling,
             self.scrape_interval_secs,
             shutdown,
             out,
@@ -110,6 +141,13 @@ struct PrometheusCompatConfig {
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
 
+    instance_tag: Option<String>,
+    endpoint_tag: Option<String>,
+    #[serde(default)]
+    honor_labels: bool,
+    #[serde(default)]
+    metric_relabel_configs: Vec<RelabelConfig>,
+
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
@@ -130,6 +168,10 @@ impl SourceConfig for PrometheusCompatConfig {
         PrometheusScrapeConfig {
             endpoints: self.endpoints.clone(),
             scrape_interval_secs: self.scrape_interval_secs,
+            instance_tag: self.instance_tag.clone(),
+            endpoint_tag: self.endpoint_tag.clone(),
+            honor_labels: self.honor_labels,
+            metric_relabel_configs: self.metric_relabel_configs.clone(),
             tls: self.tls.clone(),
             auth: self.auth.clone(),
         }
@@ -146,15 +188,57 @@ impl SourceConfig for PrometheusCompatConfig {
     }
 }
 
+/// How scraped metrics are tagged and relabelled, in the order Prometheus applies them to
+/// its targets' metrics.
+#[derive(Debug)]
+struct Labeling {
+    instance_tag: Option<String>,
+    endpoint_tag: Option<String>,
+    honor_labels: bool,
+    relabel: Vec<RelabelRule>,
+}
+
+impl Labeling {
+    fn apply(&self, mut metric: Metric, url: &http::Uri) -> Option<Metric> {
+        if let Some(tag) = &self.instance_tag {
+            let port = url.port_u16().unwrap_or_else(|| match url.scheme_str() {
+                Some("https") => 443,
+                _ => 80,
+            });
+            let instance = format!("{}:{}", url.host().unwrap_or_default(), port);
+            self.add_tag(&mut metric, tag, instance);
+        }
+        if let Some(tag) = &self.endpoint_tag {
+            self.add_tag(&mut metric, tag, url.to_string());
+        }
+
+        self.relabel
+            .iter()
+            .try_fold(metric, |metric, rule| rule.apply(metric))
+    }
+
+    fn add_tag(&self, metric: &mut Metric, tag: &str, value: String) {
+        if let Some(scraped) = metric.tag_value(tag) {
+            if self.honor_labels {
+                return;
+            }
+            metric.set_tag_value(format!("exported_{}", tag), scraped);
+        }
+        metric.set_tag_value(tag.to_owned(), value);
+    }
+}
+
 fn prometheus(
     urls: Vec<http::Uri>,
     tls: TlsSettings,
     auth: Option<Auth>,
+    labeling: Labeling,
     interval: u64,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> sources::Source {
     let out = out.sink_map_err(|error| error!(message = "Error sending metric.", %error));
+    let labeling = Arc::new(labeling);
 
     Box::pin(tokio::time::interval(Duration::from_secs(interval))
         .take_until(shutdown)
@@ -162,6 +246,7 @@ fn prometheus(
         .flatten()
         .map(move |url| {
             let client = HttpClient::new(tls.clone()).expect("Building HTTP client failed");
+            let labeling = Arc::clone(&labeling);
 
             let mut request = Request::get(&url)
                 .body(Body::empty())
@@ -197,6 +282,14 @@ fn prometheus(
                                         byte_size,
                                         count: metrics.len(),
                                     });
+                                    let metrics = metrics
+                                        .into_iter()
+                                        .filter_map(|event| {
+                                            labeling
+                                                .apply(event.into_metric(), &url)
+                                                .map(Event::Metric)
+                                        })
+                                        .collect::<Vec<_>>();
                                     Some(stream::iter(metrics).map(Ok))
                                 }
                                 Err(error) => {
@@ -251,6 +344,7 @@ mod test {
     use super::*;
     use crate::{
         config,
+        event::{MetricKind, MetricValue},
         sinks::prometheus::exporter::PrometheusExporterConfig,
         test_util::{next_addr, start_topology},
         Error,
@@ -267,6 +361,68 @@ mod test {
         crate::test_util::test_generate_config::<PrometheusScrapeConfig>();
     }
 
+    fn labeling(honor_labels: bool) -> Labeling {
+        Labeling {
+            instance_tag: Some("instance".into()),
+            endpoint_tag: Some("endpoint".into()),
+            honor_labels,
+            relabel: vec![toml::from_str::<RelabelConfig>(
+                r#"
+                action = "drop"
+                source_labels = ["code"]
+                regex = "5..""#,
+            )
+            .unwrap()
+            .build()
+            .unwrap()],
+        }
+    }
+
+    fn scraped(code: &str) -> Metric {
+        Metric::new(
+            "requests_total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(
+            vec![
+                ("code".to_owned(), code.to_owned()),
+                ("instance".to_owned(), "scraped".to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+    }
+
+    #[test]
+    fn renames_conflicting_labels() {
+        let url = "http://localhost/metrics".parse().unwrap();
+        let metric = labeling(false).apply(scraped("200"), &url).unwrap();
+        assert_eq!(metric.tag_value("instance"), Some("localhost:80".into()));
+        assert_eq!(
+            metric.tag_value("exported_instance"),
+            Some("scraped".into())
+        );
+        assert_eq!(
+            metric.tag_value("endpoint"),
+            Some("http://localhost/metrics".into())
+        );
+    }
+
+    #[test]
+    fn honors_labels() {
+        let url = "https://localhost/metrics".parse().unwrap();
+        let metric = labeling(true).apply(scraped("200"), &url).unwrap();
+        assert_eq!(metric.tag_value("instance"), Some("scraped".into()));
+        assert_eq!(metric.tag_value("exported_instance"), None);
+    }
+
+    #[test]
+    fn relabels_metrics() {
+        let url = "http://localhost:9090/metrics".parse().unwrap();
+        assert!(labeling(false).apply(scraped("503"), &url).is_none());
+    }
+
     #[tokio::test]
     async fn test_prometheus_routing() {
         let in_addr = next_addr();
@@ -319,6 +475,10 @@ mod test {
             PrometheusScrapeConfig {
                 endpoints: vec![format!("http://{}", in_addr)],
                 scrape_interval_secs: 1,
+                instance_tag: None,
+                endpoint_tag: None,
+                honor_labels: false,
+                metric_relabel_configs: Vec::new(),
                 tls: None,
                 auth: None,
             },
@@ -399,6 +559,10 @@ mod integration_tests {
         let config = PrometheusScrapeConfig {
             endpoints: vec!["http://localhost:9090/metrics".into()],
             scrape_interval_secs: 1,
+            instance_tag: Some("instance".to_string()),
+            endpoint_tag: None,
+            honor_labels: false,
+            metric_relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -438,6 +602,7 @@ mod integration_tests {
         assert!(matches!(build.data.value, MetricValue::Gauge { .. }));
         assert!(build.tags().unwrap().contains_key("branch"));
         assert!(build.tags().unwrap().contains_key("version"));
+        assert_eq!(build.tag_value("instance"), Some("localhost:9090".into()));
 
         let queries = find_metric("prometheus_engine_queries");
         assert!(matches!(queries.data.kind, MetricKind::Absolute));
@@ -5,6 +5,7 @@ mod unix;
 
 use super::util::TcpSource;
 use crate::{
+    codecs::{self, DecodingConfig, FramingConfig},
     config::{
         log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
         SourceDescription,
@@ -66,6 +67,16 @@ inventory::submit! {
     SourceDescription::new::<SocketConfig>("socket")
 }
 
+/// Frames are separated by newlines, unless the `framing` option is set.
+fn build_decoder(
+    framing: Option<FramingConfig>,
+    decoding: DecodingConfig,
+    max_length: usize,
+) -> codecs::Decoder {
+    let framing = framing.unwrap_or(FramingConfig::NewlineDelimited);
+    codecs::Decoder::new(framing.build(max_length), decoding)
+}
+
 impl GenerateConfig for SocketConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -82,14 +93,16 @@ impl SourceConfig for SocketConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
+        let metadata_keys = globals.metadata_keys.clone();
         match self.mode.clone() {
             Mode::Tcp(config) => {
                 let tcp = tcp::RawTcpSource {
                     config: config.clone(),
+                    metadata_keys,
                 };
                 let tls = MaybeTlsSettings::from_config(&config.tls(), true)?;
                 tcp.run(
@@ -107,12 +120,17 @@ impl SourceConfig for SocketConfig {
                     .host_key()
                     .clone()
                     .unwrap_or_else(|| log_schema().host_key().to_string());
+                let decoder =
+                    build_decoder(config.framing(), config.decoding(), config.max_length());
                 Ok(udp::udp(
                     config.address(),
                     config.max_length(),
                     host_key,
+                    metadata_keys,
                     #[cfg(unix)]
                     config.receive_buffer_bytes(),
+                    config.decompression(),
+                    decoder,
                     shutdown,
                     out,
                 ))
@@ -122,26 +140,33 @@ impl SourceConfig for SocketConfig {
                 let host_key = config
                     .host_key
                     .unwrap_or_else(|| log_schema().host_key().to_string());
-                Ok(unix::unix_datagram(
+                let decoder = build_decoder(config.framing, config.decoding, config.max_length);
+                unix::unix_datagram(
                     config.path,
+                    config.socket_file_mode,
                     config.max_length,
                     host_key,
+                    metadata_keys,
+                    decoder,
                     shutdown,
                     out,
-                ))
+                )
             }
             #[cfg(unix)]
             Mode::UnixStream(config) => {
                 let host_key = config
                     .host_key
                     .unwrap_or_else(|| log_schema().host_key().to_string());
-                Ok(unix::unix_stream(
+                let decoder = build_decoder(config.framing, config.decoding, config.max_length);
+                unix::unix_stream(
                     config.path,
-                    config.max_length,
+                    config.socket_file_mode,
                     host_key,
+                    metadata_keys,
+                    decoder,
                     shutdown,
                     out,
-                ))
+                )
             }
         }
     }
@@ -170,6 +195,7 @@ impl SourceConfig for SocketConfig {
 mod test {
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     use crate::{
+        codecs::{DecodingConfig, FramingConfig},
         config::{log_schema, GlobalOptions, SinkContext, SourceConfig},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
@@ -303,6 +329,39 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn tcp_decodes_octet_counted_json() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_framing(Some(FramingConfig::OctetCounting));
+        config.set_decoding(DecodingConfig::Json);
+
+        let server = SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        let frame = r#"{"message": "first\nsecond", "status": 200}"#;
+        wait_for_tcp(addr).await;
+        send_lines(addr, vec![format!("{} {}", frame.len(), frame)].into_iter())
+            .await
+            .unwrap();
+
+        let event = rx.next().await.unwrap();
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "first\nsecond".into());
+        assert_eq!(log["status"], 200.into());
+        assert_eq!(log[log_schema().source_type_key()], "socket".into());
+    }
+
     #[tokio::test]
     async fn tcp_with_tls() {
         let (tx, mut rx) = Pipeline::new_test();
@@ -847,6 +906,40 @@ mod test {
         unix_multiple_packets(false).await
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_datagram_sets_socket_file_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (tx, _rx) = Pipeline::new_test();
+        let path = tempfile::tempdir().unwrap().into_path().join("unix_test");
+        let mut config = UnixConfig::new(path.clone());
+        config.socket_file_mode = Some(0o660);
+        let server = SocketConfig {
+            mode: Mode::UnixDatagram(config),
+        }
+        .build(
+            "default",
+            &GlobalOptions::default(),
+            ShutdownSignal::noop(),
+            tx,
+        )
+        .await
+        .unwrap();
+        tokio::spawn(server);
+
+        while std::os::unix::net::UnixDatagram::unbound()
+            .unwrap()
+            .connect(&path)
+            .is_err()
+        {
+            yield_now().await;
+        }
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o660);
+    }
+
     #[cfg(unix)]
     #[test]
     fn parses_unix_datagram_config() {
@@ -886,6 +979,26 @@ mod test {
         unix_multiple_packets(true).await
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_stream_rejects_invalid_socket_file_mode() {
+        let (tx, _rx) = Pipeline::new_test();
+        let path = tempfile::tempdir().unwrap().into_path().join("unix_test");
+        let mut config = UnixConfig::new(path);
+        config.socket_file_mode = Some(0o10660);
+        let server = SocketConfig {
+            mode: Mode::UnixStream(config),
+        }
+        .build(
+            "default",
+            &GlobalOptions::default(),
+            ShutdownSignal::noop(),
+            tx,
+        )
+        .await;
+        assert!(server.is_err());
+    }
+
     #[cfg(unix)]
     #[test]
     fn parses_new_unix_stream_config() {
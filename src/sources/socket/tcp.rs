@@ -1,12 +1,13 @@
 use crate::{
+    codecs::{self, DecodingConfig, FramingConfig},
+    config::MetadataKeys,
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
-    sources::util::{SocketListenAddr, TcpSource},
+    sources::util::{DecompressingDecoder, Decompression, SocketListenAddr, TcpSource},
     tcp::TcpKeepaliveConfig,
     tls::TlsConfig,
 };
 use bytes::Bytes;
-use codec::BytesDelimitedCodec;
 use getset::{CopyGetters, Getters, Setters};
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +29,14 @@ pub struct TcpConfig {
     tls: Option<TlsConfig>,
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    decompression: Decompression,
+    #[getset(get_copy = "pub", set = "pub")]
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    decoding: DecodingConfig,
 }
 
 fn default_max_length() -> usize {
@@ -56,6 +65,9 @@ impl TcpConfig {
             host_key,
             tls,
             receive_buffer_bytes,
+            decompression: Decompression::default(),
+            framing: None,
+            decoding: DecodingConfig::default(),
         }
     }
 
@@ -68,6 +80,9 @@ impl TcpConfig {
             host_key: None,
             tls: None,
             receive_buffer_bytes: None,
+            decompression: Decompression::default(),
+            framing: None,
+            decoding: DecodingConfig::default(),
         }
     }
 }
@@ -75,21 +90,28 @@ impl TcpConfig {
 #[derive(Debug, Clone)]
 pub struct RawTcpSource {
     pub config: TcpConfig,
+    pub metadata_keys: MetadataKeys,
 }
 
 impl TcpSource for RawTcpSource {
     type Error = std::io::Error;
-    type Decoder = BytesDelimitedCodec;
+    type Decoder = DecompressingDecoder<codecs::Decoder>;
 
     fn decoder(&self) -> Self::Decoder {
-        BytesDelimitedCodec::new_with_max_length(b'\n', self.config.max_length)
+        DecompressingDecoder::new(
+            super::build_decoder(
+                self.config.framing,
+                self.config.decoding,
+                self.config.max_length,
+            ),
+            self.config.decompression,
+        )
     }
 
-    fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
-        let byte_size = frame.len();
-        let mut event = Event::from(frame);
-
-        event.as_mut_log().insert(
+    fn build_event(&self, (mut event, byte_size): (Event, usize), host: Bytes) -> Option<Event> {
+        let log = event.as_mut_log();
+        self.metadata_keys.source_type.insert(
+            log,
             crate::config::log_schema().source_type_key(),
             Bytes::from("socket"),
         );
@@ -97,7 +119,7 @@ impl TcpSource for RawTcpSource {
         let host_key = (self.config.host_key.clone())
             .unwrap_or_else(|| crate::config::log_schema().host_key().to_string());
 
-        event.as_mut_log().insert(host_key, host);
+        self.metadata_keys.host.insert(log, &host_key, host);
 
         emit!(SocketEventReceived {
             byte_size,
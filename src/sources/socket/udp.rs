@@ -1,14 +1,14 @@
 #[cfg(unix)]
 use crate::udp;
 use crate::{
-    event::Event,
-    internal_events::{SocketEventReceived, SocketMode, SocketReceiveError},
+    codecs::{self, DecodingConfig, FramingConfig},
+    config::MetadataKeys,
+    internal_events::{SocketDecompressError, SocketEventReceived, SocketMode, SocketReceiveError},
     shutdown::ShutdownSignal,
-    sources::Source,
+    sources::{util::Decompression, Source},
     Pipeline,
 };
-use bytes::{Bytes, BytesMut};
-use codec::BytesDelimitedCodec;
+use bytes::BytesMut;
 use futures::SinkExt;
 use getset::{CopyGetters, Getters};
 use serde::{Deserialize, Serialize};
@@ -16,7 +16,7 @@ use std::net::SocketAddr;
 use tokio::net::UdpSocket;
 use tokio_util::codec::Decoder;
 
-/// UDP processes messages per packet, where messages are separated by newline.
+/// UDP processes messages per packet, where messages are separated by newline by default.
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters)]
 #[serde(deny_unknown_fields)]
 pub struct UdpConfig {
@@ -30,6 +30,14 @@ pub struct UdpConfig {
     #[cfg(unix)]
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    #[get_copy = "pub"]
+    decompression: Decompression,
+    #[get_copy = "pub"]
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    #[get_copy = "pub"]
+    decoding: DecodingConfig,
 }
 
 fn default_max_length() -> usize {
@@ -44,6 +52,9 @@ impl UdpConfig {
             host_key: None,
             #[cfg(unix)]
             receive_buffer_bytes: None,
+            decompression: Decompression::default(),
+            framing: None,
+            decoding: DecodingConfig::default(),
         }
     }
 }
@@ -52,7 +63,10 @@ pub fn udp(
     address: SocketAddr,
     max_length: usize,
     host_key: String,
+    metadata_keys: MetadataKeys,
     #[cfg(unix)] receive_buffer_bytes: Option<usize>,
+    decompression: Decompression,
+    decoder: codecs::Decoder,
     mut shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
@@ -89,20 +103,25 @@ pub fn udp(
                         });
                     })?;
 
-                    let mut payload = buf.split_to(byte_size);
-
-                    // UDP processes messages per payload, where messages are separated by newline
-                    // and stretch to end of payload.
-                    let mut decoder = BytesDelimitedCodec::new(b'\n');
-                    while let Ok(Some(line)) = decoder.decode_eof(&mut payload) {
-                        let mut event = Event::from(line);
+                    let payload = buf.split_to(byte_size).freeze();
+                    let mut payload = match decompression.decompress(payload) {
+                        Ok(payload) => BytesMut::from(&payload[..]),
+                        Err(error) => {
+                            emit!(SocketDecompressError {
+                                error,
+                                mode: SocketMode::Udp
+                            });
+                            continue;
+                        }
+                    };
 
-                        event
-                            .as_mut_log()
-                            .insert(crate::config::log_schema().source_type_key(), Bytes::from("socket"));
-                        event
-                            .as_mut_log()
-                            .insert(host_key.clone(), address.to_string());
+                    // UDP processes messages per payload, where frames stretch to end of payload.
+                    let mut decoder = decoder.clone();
+                    while let Ok(Some((mut event, byte_size))) = decoder.decode_eof(&mut payload) {
+                        let log = event.as_mut_log();
+                        let source_type_key = crate::config::log_schema().source_type_key();
+                        metadata_keys.source_type.insert(log, source_type_key, "socket");
+                        metadata_keys.host.insert(log, &host_key, address.to_string());
 
                         emit!(SocketEventReceived { byte_size,mode:SocketMode::Udp });
 
@@ -1,4 +1,6 @@
 use crate::{
+    codecs::{self, DecodingConfig, FramingConfig},
+    config::MetadataKeys,
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
     shutdown::ShutdownSignal,
@@ -11,7 +13,6 @@ use crate::{
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use tokio_util::codec::LinesCodec;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -20,6 +21,11 @@ pub struct UnixConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    /// Permissions of the socket file, set once it's bound.
+    pub socket_file_mode: Option<u32>,
+    pub framing: Option<FramingConfig>,
+    #[serde(default)]
+    pub decoding: DecodingConfig,
 }
 
 fn default_max_length() -> usize {
@@ -32,23 +38,31 @@ impl UnixConfig {
             path,
             max_length: default_max_length(),
             host_key: None,
+            socket_file_mode: None,
+            framing: None,
+            decoding: DecodingConfig::default(),
         }
     }
 }
 
 /**
 * Function to pass to build_unix_*_source, specific to the basic unix source.
-* Takes a single line of a received message and builds an Event object.
+* Takes a single decoded frame of a received message and completes its Event object.
 **/
-fn build_event(host_key: &str, received_from: Option<Bytes>, line: &str) -> Event {
-    let byte_size = line.len();
-    let mut event = Event::from(line);
-    event.as_mut_log().insert(
+fn build_event(
+    host_key: &str,
+    metadata_keys: &MetadataKeys,
+    received_from: Option<Bytes>,
+    (mut event, byte_size): (Event, usize),
+) -> Event {
+    let log = event.as_mut_log();
+    metadata_keys.source_type.insert(
+        log,
         crate::config::log_schema().source_type_key(),
         Bytes::from("socket"),
     );
     if let Some(host) = received_from {
-        event.as_mut_log().insert(host_key, host);
+        metadata_keys.host.insert(log, host_key, host);
     }
     emit!(SocketEventReceived {
         byte_size,
@@ -59,35 +73,46 @@ fn build_event(host_key: &str, received_from: Option<Bytes>, line: &str) -> Even
 
 pub(super) fn unix_datagram(
     path: PathBuf,
+    socket_file_mode: Option<u32>,
     max_length: usize,
     host_key: String,
+    metadata_keys: MetadataKeys,
+    decoder: codecs::Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
-) -> Source {
+) -> crate::Result<Source> {
     build_unix_datagram_source(
         path,
+        socket_file_mode,
         max_length,
         host_key,
-        LinesCodec::new_with_max_length(max_length),
+        decoder,
         shutdown,
         out,
-        |host_key, received_from, line| Some(build_event(host_key, received_from, line)),
+        move |host_key, received_from, frame| {
+            Some(build_event(host_key, &metadata_keys, received_from, frame))
+        },
     )
 }
 
 pub(super) fn unix_stream(
     path: PathBuf,
-    max_length: usize,
+    socket_file_mode: Option<u32>,
     host_key: String,
+    metadata_keys: MetadataKeys,
+    decoder: codecs::Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
-) -> Source {
+) -> crate::Result<Source> {
     build_unix_stream_source(
         path,
-        LinesCodec::new_with_max_length(max_length),
+        socket_file_mode,
+        decoder,
         host_key,
         shutdown,
         out,
-        |host_key, received_from, line| Some(build_event(host_key, received_from, line)),
+        move |host_key, received_from, frame| {
+            Some(build_event(host_key, &metadata_keys, received_from, frame))
+        },
     )
 }
@@ -1,6 +1,9 @@
 use crate::{
-    config::{log_schema, DataType, GlobalOptions, Resource, SourceConfig, SourceDescription},
-    event::{Event, LogEvent, Value},
+    config::{
+        log_schema, DataType, GlobalOptions, MetadataKeys, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatusReceiver, Event, EventStatus, LogEvent, Value},
     internal_events::{
         SplunkHECEventReceived, SplunkHECRequestBodyInvalid, SplunkHECRequestError,
         SplunkHECRequestReceived,
@@ -12,16 +15,19 @@ use crate::{
 use bytes::{buf::BufExt, Bytes};
 use chrono::{DateTime, TimeZone, Utc};
 use flate2::read::GzDecoder;
-use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt, TryStreamExt};
 use futures01::{Async, Stream};
 use http::StatusCode;
 use serde::{de, Deserialize, Serialize};
 use serde_json::{de::IoRead, json, Deserializer, Value as JsonValue};
 use snafu::Snafu;
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     future,
     io::Read,
     net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use warp::{filters::BoxedFilter, path, reject::Rejection, reply::Response, Filter, Reply};
@@ -42,6 +48,30 @@ pub struct SplunkConfig {
     /// Splunk HEC token
     token: Option<String>,
     tls: Option<TlsConfig>,
+    /// Respond only once the events of a request are accepted by the sinks
+    acknowledgements: bool,
+    /// Splunk HEC indexer acknowledgements, as used by forwarders with `useACK` enabled
+    indexer_acknowledgements: IndexerAcknowledgementsConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct IndexerAcknowledgementsConfig {
+    enabled: bool,
+    /// Requests are refused while a channel has this many ack ids that weren't queried yet
+    max_pending_acks_per_channel: usize,
+    /// Channels that aren't used for this long are forgotten, along with their ack ids
+    max_idle_secs: u64,
+}
+
+impl Default for IndexerAcknowledgementsConfig {
+    fn default() -> Self {
+        IndexerAcknowledgementsConfig {
+            enabled: false,
+            max_pending_acks_per_channel: 1_000_000,
+            max_idle_secs: 300,
+        }
+    }
 }
 
 inventory::submit! {
@@ -66,6 +96,8 @@ impl Default for SplunkConfig {
             address: default_socket_address(),
             token: None,
             tls: None,
+            acknowledgements: false,
+            indexer_acknowledgements: IndexerAcknowledgementsConfig::default(),
         }
     }
 }
@@ -80,14 +112,15 @@ impl SourceConfig for SplunkConfig {
     async fn build(
         &self,
         _: &str,
-        _: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
-        let source = SplunkSource::new(self);
+        let source = SplunkSource::new(self, globals.metadata_keys.clone());
 
         let event_service = source.event_service(out.clone());
         let raw_service = source.raw_service(out.clone());
+        let ack_service = source.ack_service();
         let health_service = source.health_service();
         let options = SplunkSource::options();
 
@@ -105,6 +138,8 @@ impl SourceConfig for SplunkConfig {
                 event_service
                     .or(raw_service)
                     .unify()
+                    .or(ack_service)
+                    .unify()
                     .or(health_service)
                     .unify()
                     .or(options)
@@ -144,19 +179,34 @@ impl SourceConfig for SplunkConfig {
 /// Shared data for responding to requests.
 struct SplunkSource {
     credentials: Option<Bytes>,
+    acknowledgements: bool,
+    acknowledger: Option<Arc<IndexerAcknowledger>>,
+    metadata_keys: MetadataKeys,
 }
 
 impl SplunkSource {
-    fn new(config: &SplunkConfig) -> Self {
+    fn new(config: &SplunkConfig, metadata_keys: MetadataKeys) -> Self {
         SplunkSource {
             credentials: config
                 .token
                 .as_ref()
                 .map(|token| format!("Splunk {}", token).into()),
+            acknowledgements: config.acknowledgements,
+            acknowledger: if config.indexer_acknowledgements.enabled {
+                Some(Arc::new(IndexerAcknowledger::new(
+                    &config.indexer_acknowledgements,
+                )))
+            } else {
+                None
+            },
+            metadata_keys,
         }
     }
 
     fn event_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
+        let acknowledgements = self.acknowledgements;
+        let acknowledger = self.acknowledger.clone();
+        let metadata_keys = self.metadata_keys.clone();
         warp::post()
             .and(path!("event").or(path!("event" / "1.0")))
             .and(self.authorization())
@@ -171,7 +221,16 @@ impl SplunkSource {
                       host: Option<String>,
                       gzip: bool,
                       body: Bytes| {
-                    process_service_request(out.clone(), channel, host, gzip, body)
+                    process_service_request(
+                        out.clone(),
+                        acknowledgements,
+                        acknowledger.clone(),
+                        metadata_keys.clone(),
+                        channel,
+                        host,
+                        gzip,
+                        body,
+                    )
                 },
             )
             .map(finish_ok)
@@ -179,6 +238,9 @@ impl SplunkSource {
     }
 
     fn raw_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
+        let acknowledgements = self.acknowledgements;
+        let acknowledger = self.acknowledger.clone();
+        let metadata_keys = self.metadata_keys.clone();
         warp::post()
             .and(path!("raw" / "1.0").or(path!("raw")))
             .and(self.authorization())
@@ -199,15 +261,21 @@ impl SplunkSource {
             .and_then(
                 move |_, _, channel: String, host: Option<String>, gzip: bool, body: Bytes| {
                     let out = out.clone();
+                    let acknowledger = acknowledger.clone();
+                    let metadata_keys = metadata_keys.clone();
                     async move {
+                        let pending = reserve_ack(acknowledger, Some(&channel))?;
+                        let (batch, receiver) =
+                            batch_notifier(acknowledgements || pending.is_some());
                         // Construct event parser
-                        let event = future::ready(raw_event(body, gzip, channel, host));
-                        futures::stream::once(event)
+                        let event = raw_event(body, gzip, channel, host, &metadata_keys)
+                            .map(|event| with_batch_notifier(event, batch));
+                        futures::stream::once(future::ready(event))
                             .forward(
                                 out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown)),
                             )
-                            .map_ok(|_| ())
-                            .await
+                            .await?;
+                        finish_delivery(pending, receiver).await
                     }
                 },
             )
@@ -215,6 +283,28 @@ impl SplunkSource {
             .boxed()
     }
 
+    fn ack_service(&self) -> BoxedFilter<(Response,)> {
+        let acknowledger = self.acknowledger.clone();
+        warp::post()
+            .and(path!("ack"))
+            .and(self.authorization())
+            .and(warp::header::optional::<String>("x-splunk-request-channel"))
+            .and(warp::body::bytes())
+            .and_then(move |_, channel: Option<String>, body: Bytes| {
+                let acknowledger = acknowledger.clone();
+                async move {
+                    let acknowledger = acknowledger.ok_or(ApiError::AckDisabled)?;
+                    let channel = channel.ok_or(ApiError::MissingChannel)?;
+                    let request = serde_json::from_slice::<AckRequest>(&body)
+                        .map_err(|_| ApiError::BadRequest)?;
+
+                    let acks = acknowledger.query(&channel, &request.acks);
+                    Ok::<_, Rejection>(response_json(StatusCode::OK, json!({ "acks": acks })))
+                }
+            })
+            .boxed()
+    }
+
     fn health_service(&self) -> BoxedFilter<(Response,)> {
         let credentials = self.credentials.clone();
         let authorize =
@@ -244,7 +334,8 @@ impl SplunkSource {
                 path!("event")
                     .or(path!("event" / "1.0"))
                     .or(path!("raw" / "1.0"))
-                    .or(path!("raw")),
+                    .or(path!("raw"))
+                    .or(path!("ack")),
             )
             .map(|_| warp::reply::with_header(warp::reply(), "Allow", "POST").into_response());
 
@@ -291,13 +382,17 @@ impl SplunkSource {
 
 async fn process_service_request(
     out: Pipeline,
+    acknowledgements: bool,
+    acknowledger: Option<Arc<IndexerAcknowledger>>,
+    metadata_keys: MetadataKeys,
     channel: Option<String>,
     host: Option<String>,
     gzip: bool,
     body: Bytes,
-) -> Result<(), Rejection> {
+) -> Result<Option<u64>, Rejection> {
     use futures::compat::Stream01CompatExt;
 
+    let pending = reserve_ack(acknowledger, channel.as_deref())?;
     let mut out = out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown));
 
     let reader: Box<dyn Read + Send> = if gzip {
@@ -306,7 +401,10 @@ async fn process_service_request(
         Box::new(body.reader())
     };
 
-    let stream = EventStream::new(reader, channel, host).compat();
+    let (batch, receiver) = batch_notifier(acknowledgements || pending.is_some());
+    let stream = EventStream::new(reader, channel, host, metadata_keys)
+        .compat()
+        .map_ok(move |event| with_batch_notifier(event, batch.clone()));
 
     let res = stream.forward(&mut out).await;
 
@@ -314,7 +412,183 @@ async fn process_service_request(
         .map_err(|_| Rejection::from(ApiError::ServerShutdown))
         .await?;
 
-    res.map(|_| ())
+    res?;
+    finish_delivery(pending, receiver).await
+}
+
+/// Reserves an ack id on the channel of a request, if indexer acknowledgements are enabled.
+fn reserve_ack(
+    acknowledger: Option<Arc<IndexerAcknowledger>>,
+    channel: Option<&str>,
+) -> Result<Option<PendingAck>, ApiError> {
+    match acknowledger {
+        None => Ok(None),
+        Some(acknowledger) => {
+            let channel = channel.ok_or(ApiError::MissingChannel)?;
+            IndexerAcknowledger::reserve(acknowledger, channel).map(Some)
+        }
+    }
+}
+
+/// With indexer acknowledgements the request is answered right away with its ack id, and the
+/// delivery of its events is reported through the ack endpoint. Otherwise the request waits
+/// for the delivery, if acknowledgements are enabled.
+async fn finish_delivery(
+    pending: Option<PendingAck>,
+    receiver: Option<BatchStatusReceiver>,
+) -> Result<Option<u64>, Rejection> {
+    match (pending, receiver) {
+        (Some(pending), Some(receiver)) => Ok(Some(pending.resolve_on(receiver))),
+        (_, receiver) => wait_for_delivery(receiver).await.map(|()| None),
+    }
+}
+
+fn batch_notifier(
+    acknowledgements: bool,
+) -> (Option<Arc<BatchNotifier>>, Option<BatchStatusReceiver>) {
+    if acknowledgements {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        (Some(batch), Some(receiver))
+    } else {
+        (None, None)
+    }
+}
+
+fn with_batch_notifier(mut event: Event, batch: Option<Arc<BatchNotifier>>) -> Event {
+    if let Some(batch) = batch {
+        event.add_batch_notifier(batch);
+    }
+    event
+}
+
+/// Waits for the events of a request to be accepted by the sinks, once all of them have
+/// been sent.
+async fn wait_for_delivery(receiver: Option<BatchStatusReceiver>) -> Result<(), Rejection> {
+    match receiver {
+        None => Ok(()),
+        Some(receiver) => match receiver.await {
+            EventStatus::Delivered => Ok(()),
+            EventStatus::Errored | EventStatus::Failed => Err(ApiError::DeliveryFailed.into()),
+        },
+    }
+}
+
+#[derive(Deserialize)]
+struct AckRequest {
+    acks: Vec<u64>,
+}
+
+/// Tracks the ack ids handed out to each channel, for indexer acknowledgements.
+struct IndexerAcknowledger {
+    max_pending_acks_per_channel: usize,
+    max_idle: Duration,
+    channels: Mutex<HashMap<String, AckChannel>>,
+}
+
+struct AckChannel {
+    next_ack_id: u64,
+    /// Ack ids whose events aren't delivered yet.
+    pending: HashSet<u64>,
+    /// Ack ids whose events are delivered, until they're queried.
+    delivered: HashSet<u64>,
+    last_used: Instant,
+}
+
+impl IndexerAcknowledger {
+    fn new(config: &IndexerAcknowledgementsConfig) -> Self {
+        IndexerAcknowledger {
+            max_pending_acks_per_channel: config.max_pending_acks_per_channel,
+            max_idle: Duration::from_secs(config.max_idle_secs),
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn reserve(this: Arc<Self>, channel: &str) -> Result<PendingAck, ApiError> {
+        let now = Instant::now();
+        let mut channels = this.channels.lock().unwrap();
+        if !channels.contains_key(channel) {
+            let max_idle = this.max_idle;
+            channels.retain(|_, state| now.duration_since(state.last_used) < max_idle);
+            channels.insert(
+                channel.to_owned(),
+                AckChannel {
+                    next_ack_id: 0,
+                    pending: HashSet::new(),
+                    delivered: HashSet::new(),
+                    last_used: now,
+                },
+            );
+        }
+
+        let state = channels.get_mut(channel).unwrap();
+        state.last_used = now;
+        if state.pending.len() + state.delivered.len() >= this.max_pending_acks_per_channel {
+            return Err(ApiError::ServerBusy);
+        }
+        let ack_id = state.next_ack_id;
+        state.next_ack_id += 1;
+        state.pending.insert(ack_id);
+        drop(channels);
+
+        Ok(PendingAck {
+            acknowledger: this,
+            channel: channel.to_owned(),
+            ack_id,
+            delivered: false,
+        })
+    }
+
+    fn resolve(&self, channel: &str, ack_id: u64, delivered: bool) {
+        // The channel may have been forgotten in the meantime.
+        if let Some(state) = self.channels.lock().unwrap().get_mut(channel) {
+            if state.pending.remove(&ack_id) && delivered {
+                state.delivered.insert(ack_id);
+            }
+        }
+    }
+
+    /// Reports which of the ack ids are delivered. Those are forgotten afterwards, as clients
+    /// don't query them again.
+    fn query(&self, channel: &str, ack_ids: &[u64]) -> BTreeMap<u64, bool> {
+        let mut channels = self.channels.lock().unwrap();
+        match channels.get_mut(channel) {
+            Some(state) => {
+                state.last_used = Instant::now();
+                ack_ids
+                    .iter()
+                    .map(|&ack_id| (ack_id, state.delivered.remove(&ack_id)))
+                    .collect()
+            }
+            None => ack_ids.iter().map(|&ack_id| (ack_id, false)).collect(),
+        }
+    }
+}
+
+/// An ack id whose events are on their way. It's resolved once dropped, and counts as
+/// undelivered unless the sinks accepted its events.
+struct PendingAck {
+    acknowledger: Arc<IndexerAcknowledger>,
+    channel: String,
+    ack_id: u64,
+    delivered: bool,
+}
+
+impl PendingAck {
+    /// Resolves the ack id once the events are delivered, on the side.
+    fn resolve_on(mut self, receiver: BatchStatusReceiver) -> u64 {
+        let ack_id = self.ack_id;
+        tokio::spawn(async move {
+            self.delivered = receiver.await == EventStatus::Delivered;
+        });
+        ack_id
+    }
+}
+
+impl Drop for PendingAck {
+    fn drop(&mut self) {
+        self.acknowledger
+            .resolve(&self.channel, self.ack_id, self.delivered);
+    }
 }
 
 /// Constructs one ore more events from json-s coming from reader.
@@ -330,10 +604,16 @@ struct EventStream<R: Read> {
     time: Time,
     /// Remaining extracted default values
     extractors: [DefaultExtractor; 4],
+    metadata_keys: MetadataKeys,
 }
 
 impl<R: Read> EventStream<R> {
-    fn new(data: R, channel: Option<String>, host: Option<String>) -> Self {
+    fn new(
+        data: R,
+        channel: Option<String>,
+        host: Option<String>,
+        metadata_keys: MetadataKeys,
+    ) -> Self {
         EventStream {
             data,
             events: 0,
@@ -345,6 +625,7 @@ impl<R: Read> EventStream<R> {
                 DefaultExtractor::new("source", &SOURCE),
                 DefaultExtractor::new("sourcetype", &SOURCETYPE),
             ],
+            metadata_keys,
         }
     }
 
@@ -390,7 +671,11 @@ impl<R: Read> Stream for EventStream<R> {
         let log = event.as_mut_log();
 
         // Add source type
-        log.insert(log_schema().source_type_key(), Bytes::from("splunk_hec"));
+        self.metadata_keys.source_type.insert(
+            log,
+            log_schema().source_type_key(),
+            Bytes::from("splunk_hec"),
+        );
 
         // Process event field
         match json.get_mut("event") {
@@ -574,6 +859,7 @@ fn raw_event(
     gzip: bool,
     channel: String,
     host: Option<String>,
+    metadata_keys: &MetadataKeys,
 ) -> Result<Event, Rejection> {
     // Process gzip
     let message: Value = if gzip {
@@ -602,16 +888,20 @@ fn raw_event(
 
     // Add host
     if let Some(host) = host {
-        log.insert(log_schema().host_key(), host);
+        metadata_keys
+            .host
+            .insert(log, log_schema().host_key(), host);
     }
 
     // Add timestamp
     log.insert(log_schema().timestamp_key(), Utc::now());
 
     // Add source type
-    event
-        .as_mut_log()
-        .try_insert(log_schema().source_type_key(), Bytes::from("splunk_hec"));
+    metadata_keys.source_type.try_insert(
+        log,
+        log_schema().source_type_key(),
+        Bytes::from("splunk_hec"),
+    );
 
     emit!(SplunkHECEventReceived);
 
@@ -630,6 +920,9 @@ pub(crate) enum ApiError {
     EmptyEventField { event: usize },
     MissingEventField { event: usize },
     BadRequest,
+    DeliveryFailed,
+    AckDisabled,
+    ServerBusy,
 }
 
 impl From<ApiError> for Rejection {
@@ -665,11 +958,21 @@ mod splunk_response {
             json_to_bytes(json!({"text":"unsupported content encoding"}));
         pub static ref NO_CHANNEL: Bytes =
             json_to_bytes(json!({"text":"Data channel is missing","code":10}));
+        pub static ref ACK_DISABLED: Bytes =
+            json_to_bytes(json!({"text":"ACK is disabled","code":14}));
+        pub static ref SERVER_BUSY: Bytes =
+            json_to_bytes(json!({"text":"Server is busy","code":9}));
     }
 }
 
-fn finish_ok(_: ()) -> Response {
-    response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref())
+fn finish_ok(ack_id: Option<u64>) -> Response {
+    match ack_id {
+        Some(ack_id) => response_json(
+            StatusCode::OK,
+            json!({"text":"Success","code":0,"ackId":ack_id}),
+        ),
+        None => response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref()),
+    }
 }
 
 async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
@@ -707,6 +1010,18 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 event_error("Event field is required", 12, event)
             }
             ApiError::BadRequest => empty_response(StatusCode::BAD_REQUEST),
+            ApiError::DeliveryFailed => response_json(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                splunk_response::SERVER_ERROR.as_ref(),
+            ),
+            ApiError::AckDisabled => response_json(
+                StatusCode::BAD_REQUEST,
+                splunk_response::ACK_DISABLED.as_ref(),
+            ),
+            ApiError::ServerBusy => response_json(
+                StatusCode::SERVICE_UNAVAILABLE,
+                splunk_response::SERVER_BUSY.as_ref(),
+            ),
         },))
     } else {
         Err(rejection)
@@ -748,10 +1063,10 @@ fn event_error(text: &str, code: u16, event: usize) -> Response {
 #[cfg(feature = "sinks-splunk_hec")]
 #[cfg(test)]
 mod tests {
-    use super::{parse_timestamp, SplunkConfig};
+    use super::{parse_timestamp, IndexerAcknowledgementsConfig, SplunkConfig};
     use crate::{
         config::{log_schema, GlobalOptions, SinkConfig, SinkContext, SourceConfig},
-        event::Event,
+        event::{Event, EventStatus},
         shutdown::ShutdownSignal,
         sinks::{
             splunk_hec::{Encoding, HecSinkConfig},
@@ -763,6 +1078,7 @@ mod tests {
     };
     use chrono::{TimeZone, Utc};
     use futures::{stream, StreamExt};
+    use serde_json::{json, Value as JsonValue};
     use std::{future::ready, net::SocketAddr};
     use tokio::sync::mpsc;
 
@@ -775,28 +1091,36 @@ mod tests {
     const TOKEN: &str = "token";
 
     async fn source() -> (mpsc::Receiver<Event>, SocketAddr) {
-        source_with(Some(TOKEN.to_owned())).await
+        source_with(Some(TOKEN.to_owned()), false).await
+    }
+
+    async fn source_with(
+        token: Option<String>,
+        acknowledgements: bool,
+    ) -> (mpsc::Receiver<Event>, SocketAddr) {
+        source_with_config(SplunkConfig {
+            token,
+            acknowledgements,
+            ..SplunkConfig::default()
+        })
+        .await
     }
 
-    async fn source_with(token: Option<String>) -> (mpsc::Receiver<Event>, SocketAddr) {
+    async fn source_with_config(config: SplunkConfig) -> (mpsc::Receiver<Event>, SocketAddr) {
         let (sender, recv) = Pipeline::new_test();
         let address = next_addr();
         tokio::spawn(async move {
-            SplunkConfig {
-                address,
-                token,
-                tls: None,
-            }
-            .build(
-                "default",
-                &GlobalOptions::default(),
-                ShutdownSignal::noop(),
-                sender,
-            )
-            .await
-            .unwrap()
-            .await
-            .unwrap()
+            SplunkConfig { address, ..config }
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    sender,
+                )
+                .await
+                .unwrap()
+                .await
+                .unwrap()
         });
         wait_for_tcp(address).await;
         (recv, address)
@@ -859,6 +1183,19 @@ mod tests {
         send_with(address, api, message, TOKEN).await
     }
 
+    async fn post_json(address: SocketAddr, api: &str, message: &str) -> (u16, JsonValue) {
+        let response = reqwest::Client::new()
+            .post(&format!("http://{}/{}", address, api))
+            .header("Authorization", format!("Splunk {}", TOKEN))
+            .header("x-splunk-request-channel", "guid")
+            .body(message.to_owned())
+            .send()
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+
     async fn send_with(address: SocketAddr, api: &str, message: &str, token: &str) -> u16 {
         reqwest::Client::new()
             .post(&format!("http://{}/{}", address, api))
@@ -1047,7 +1384,7 @@ mod tests {
         trace_init();
 
         let message = "no_authorization";
-        let (source, address) = source_with(None).await;
+        let (source, address) = source_with(None, false).await;
         let (sink, health) = sink(address, Encoding::Text, Compression::gzip_default()).await;
         assert!(health.await.is_ok());
 
@@ -1056,6 +1393,78 @@ mod tests {
         assert_eq!(event.as_log()[log_schema().message_key()], message.into());
     }
 
+    #[tokio::test]
+    async fn acknowledgements() {
+        trace_init();
+
+        let (mut source, address) = source_with(Some(TOKEN.to_owned()), true).await;
+
+        let delivered = tokio::spawn(post(
+            address,
+            "services/collector/event",
+            r#"{"event":"first"}"#,
+        ));
+        let event = source.recv().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "first".into());
+        drop(event);
+        assert_eq!(200, delivered.await.unwrap());
+
+        let failed = tokio::spawn(post(address, "services/collector/raw", "second"));
+        let mut event = source.recv().await.unwrap();
+        event.take_finalizers().update_status(EventStatus::Failed);
+        assert_eq!(500, failed.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn indexer_acknowledgements() {
+        trace_init();
+
+        let (mut source, address) = source_with_config(SplunkConfig {
+            token: Some(TOKEN.to_owned()),
+            indexer_acknowledgements: IndexerAcknowledgementsConfig {
+                enabled: true,
+                ..IndexerAcknowledgementsConfig::default()
+            },
+            ..SplunkConfig::default()
+        })
+        .await;
+
+        // Requests are answered before their events are delivered.
+        let (status, body) =
+            post_json(address, "services/collector/event", r#"{"event":"first"}"#).await;
+        assert_eq!(200, status);
+        assert_eq!(body["ackId"], json!(0));
+        let (_, body) = post_json(address, "services/collector/raw", "second").await;
+        assert_eq!(body["ackId"], json!(1));
+
+        let first = source.recv().await.unwrap();
+        let mut second = source.recv().await.unwrap();
+        let (_, body) = post_json(address, "services/collector/ack", r#"{"acks":[0,1]}"#).await;
+        assert_eq!(body, json!({"acks": {"0": false, "1": false}}));
+
+        drop(first);
+        second.take_finalizers().update_status(EventStatus::Failed);
+        drop(second);
+        tokio::time::delay_for(std::time::Duration::from_millis(100)).await;
+
+        let (_, body) = post_json(address, "services/collector/ack", r#"{"acks":[0,1]}"#).await;
+        assert_eq!(body, json!({"acks": {"0": true, "1": false}}));
+        // Delivered ack ids are only reported once.
+        let (_, body) = post_json(address, "services/collector/ack", r#"{"acks":[0]}"#).await;
+        assert_eq!(body, json!({"acks": {"0": false}}));
+    }
+
+    #[tokio::test]
+    async fn indexer_acknowledgements_disabled() {
+        trace_init();
+
+        let (_source, address) = source().await;
+
+        let (status, body) = post_json(address, "services/collector/ack", r#"{"acks":[0]}"#).await;
+        assert_eq!(400, status);
+        assert_eq!(body["code"], json!(14));
+    }
+
     #[tokio::test]
     async fn partial() {
         trace_init();
@@ -117,7 +117,7 @@ impl SourceConfig for StatsdConfig {
                 )
             }
             #[cfg(unix)]
-            StatsdConfig::Unix(config) => Ok(statsd_unix(config.clone(), shutdown, out)),
+            StatsdConfig::Unix(config) => statsd_unix(config.clone(), shutdown, out),
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+use crate::event::metric::{Metric, MetricKind, MetricValue, Sample, StatisticKind};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::{
@@ -53,9 +53,15 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
         None
     };
 
+    // DogStatsD packs several values of the same metric into one line, separated by `:`.
+    let values = parts[0].split(':').collect::<Vec<_>>();
+
     let metric = match metric_type {
         "c" => {
-            let val: f64 = parts[0].parse()?;
+            let val = values
+                .iter()
+                .map(|value| value.parse::<f64>())
+                .sum::<Result<f64, _>>()?;
             Metric::new(
                 name,
                 MetricKind::Incremental,
@@ -66,44 +72,63 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
             .with_tags(tags)
         }
         unit @ "h" | unit @ "ms" | unit @ "d" => {
-            let val: f64 = parts[0].parse()?;
+            let samples = values
+                .iter()
+                .map(|value| -> Result<Sample, ParseError> {
+                    let val: f64 = value.parse()?;
+                    Ok(Sample {
+                        value: convert_to_base_units(unit, val),
+                        rate: sample_rate as u32,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
             Metric::new(
-                name, MetricKind::Incremental, MetricValue::Distribution {
-                    samples: crate::samples![convert_to_base_units(unit, val) => sample_rate as u32],
+                name,
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples,
                     statistic: convert_to_statistic(unit),
                 },
-            ).with_tags(tags)
+            )
+            .with_tags(tags)
         }
         "g" => {
-            let value = if parts[0]
-                .chars()
-                .next()
-                .map(|c| c.is_ascii_digit())
-                .ok_or(ParseError::Malformed("empty first body component"))?
-            {
-                parts[0].parse()?
+            // Values are applied in order, so an absolute value discards the changes before it.
+            let mut absolute = false;
+            let mut value = 0.0;
+            for part in &values {
+                let val: f64 = if part
+                    .chars()
+                    .next()
+                    .map(|c| c.is_ascii_digit())
+                    .ok_or(ParseError::Malformed("empty first body component"))?
+                {
+                    part.parse()?
+                } else {
+                    part[1..].parse()?
+                };
+
+                match parse_direction(part)? {
+                    None => {
+                        absolute = true;
+                        value = val;
+                    }
+                    Some(sign) => value += val * sign,
+                }
+            }
+
+            let kind = if absolute {
+                MetricKind::Absolute
             } else {
-                parts[0][1..].parse()?
+                MetricKind::Incremental
             };
-
-            match parse_direction(parts[0])? {
-                None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
-                    .with_tags(tags),
-                Some(sign) => Metric::new(
-                    name,
-                    MetricKind::Incremental,
-                    MetricValue::Gauge {
-                        value: value * sign,
-                    },
-                )
-                .with_tags(tags),
-            }
+            Metric::new(name, kind, MetricValue::Gauge { value }).with_tags(tags)
         }
         "s" => Metric::new(
             name,
             MetricKind::Incremental,
             MetricValue::Set {
-                values: vec![parts[0].into()].into_iter().collect(),
+                values: values.iter().map(|value| value.to_string()).collect(),
             },
         )
         .with_tags(tags),
@@ -257,6 +282,67 @@ mod test {
         );
     }
 
+    #[test]
+    fn multi_value_counter() {
+        assert_eq!(
+            parse("foo:1:2:3|c|@0.5"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 12.0 },
+            )),
+        );
+    }
+
+    #[test]
+    fn multi_value_distribution() {
+        assert_eq!(
+            parse("glork:320:100|ms|@0.1"),
+            Ok(Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: crate::samples![0.320 => 10, 0.100 => 10],
+                    statistic: StatisticKind::Histogram
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn multi_value_gauge() {
+        assert_eq!(
+            parse("gaugor:+4:-1|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Incremental,
+                MetricValue::Gauge { value: 3.0 },
+            )),
+        );
+        assert_eq!(
+            parse("gaugor:+4:10:-1|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 9.0 },
+            )),
+        );
+    }
+
+    #[test]
+    fn multi_value_set() {
+        assert_eq!(
+            parse("uniques:765:766|s"),
+            Ok(Metric::new(
+                "uniques",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["765".into(), "766".into()].into_iter().collect()
+                },
+            )),
+        );
+    }
+
     #[test]
     fn sampled_counter() {
         assert_eq!(
@@ -12,13 +12,18 @@ pub struct UnixConfig {
     pub path: PathBuf,
 }
 
-fn build_event(_: &str, _: Option<Bytes>, line: &str) -> Option<Event> {
-    super::parse_event(line)
+fn build_event(_: &str, _: Option<Bytes>, line: String) -> Option<Event> {
+    super::parse_event(&line)
 }
 
-pub fn statsd_unix(config: UnixConfig, shutdown: ShutdownSignal, out: Pipeline) -> Source {
+pub fn statsd_unix(
+    config: UnixConfig,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> crate::Result<Source> {
     build_unix_stream_source(
         config.path,
+        None,
         LinesCodec::new(),
         String::new(),
         shutdown,
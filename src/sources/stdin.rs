@@ -1,15 +1,20 @@
 use crate::{
-    config::{log_schema, DataType, GlobalOptions, Resource, SourceConfig, SourceDescription},
+    codecs::{self, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GlobalOptions, MetadataKeys, Resource, SourceConfig,
+        SourceDescription,
+    },
     event::Event,
     internal_events::{StdinEventReceived, StdinReadFailed},
     shutdown::ShutdownSignal,
     Pipeline,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use futures::{executor, FutureExt, SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use std::{io, thread};
 use tokio::sync::mpsc::channel;
+use tokio_util::codec::Decoder;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
@@ -17,6 +22,8 @@ pub struct StdinConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    pub framing: Option<FramingConfig>,
+    pub decoding: DecodingConfig,
 }
 
 impl Default for StdinConfig {
@@ -24,6 +31,8 @@ impl Default for StdinConfig {
         StdinConfig {
             max_length: default_max_length(),
             host_key: None,
+            framing: None,
+            decoding: DecodingConfig::default(),
         }
     }
 }
@@ -44,11 +53,17 @@ impl SourceConfig for StdinConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
-        stdin_source(io::BufReader::new(io::stdin()), self.clone(), shutdown, out)
+        stdin_source(
+            io::BufReader::new(io::stdin()),
+            self.clone(),
+            globals.metadata_keys.clone(),
+            shutdown,
+            out,
+        )
     }
 
     fn output_type(&self) -> DataType {
@@ -67,6 +82,7 @@ impl SourceConfig for StdinConfig {
 pub fn stdin_source<R>(
     stdin: R,
     config: StdinConfig,
+    metadata_keys: MetadataKeys,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> crate::Result<super::Source>
@@ -77,19 +93,22 @@ where
         .host_key
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
-
-    let (mut sender, receiver) = channel(1024);
+    // Lines of text may end with `\r\n`.
+    let decoder = codecs::Decoder::new(
+        config
+            .framing
+            .unwrap_or(FramingConfig::NewlineDelimited)
+            .build(config.max_length)
+            .strip_carriage_returns(),
+        config.decoding,
+    );
+
+    let (sender, receiver) = channel(1024);
 
     // Start the background thread
     thread::spawn(move || {
         info!("Capturing STDIN.");
-
-        for line in stdin.lines() {
-            if executor::block_on(sender.send(line)).is_err() {
-                // receiver has closed so we should shutdown
-                return;
-            }
-        }
+        read_frames(stdin, decoder, sender);
     });
 
     Ok(Box::pin(async move {
@@ -99,11 +118,9 @@ where
         let res = receiver
             .take_until(shutdown)
             .map_err(|error| emit!(StdinReadFailed { error }))
-            .map_ok(move |line| {
-                emit!(StdinEventReceived {
-                    byte_size: line.len()
-                });
-                create_event(Bytes::from(line), &host_key, &hostname)
+            .map_ok(move |(event, byte_size)| {
+                emit!(StdinEventReceived { byte_size });
+                create_event(event, &host_key, &hostname, &metadata_keys)
             })
             .forward(&mut out)
             .inspect(|_| info!("Finished sending."))
@@ -115,16 +132,68 @@ where
     }))
 }
 
-fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event {
-    let mut event = Event::from(line);
+/// Decodes the events read from `stdin`, until it ends or the receiver of `sender` is dropped.
+fn read_frames<R: io::BufRead>(
+    mut stdin: R,
+    mut decoder: codecs::Decoder,
+    mut sender: tokio::sync::mpsc::Sender<io::Result<(Event, usize)>>,
+) {
+    let mut buffer = BytesMut::new();
+    loop {
+        let read = match stdin.fill_buf() {
+            Ok(chunk) => {
+                buffer.extend_from_slice(chunk);
+                chunk.len()
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => {
+                let _ = executor::block_on(sender.send(Err(error)));
+                return;
+            }
+        };
+        stdin.consume(read);
+        let eof = read == 0;
+
+        loop {
+            let frame = if eof {
+                decoder.decode_eof(&mut buffer)
+            } else {
+                decoder.decode(&mut buffer)
+            };
+            let frame = match frame {
+                Ok(Some(frame)) => Ok(frame),
+                Ok(None) => break,
+                Err(error) => Err(error),
+            };
+            // Framing errors end the input, as the following frames can't be found.
+            let failed = frame.is_err();
+            if executor::block_on(sender.send(frame)).is_err() || failed {
+                // receiver has closed so we should shutdown
+                return;
+            }
+        }
+
+        if eof {
+            return;
+        }
+    }
+}
+
+fn create_event(
+    mut event: Event,
+    host_key: &str,
+    hostname: &Option<String>,
+    metadata_keys: &MetadataKeys,
+) -> Event {
+    let log = event.as_mut_log();
 
     // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("stdin"));
+    metadata_keys
+        .source_type
+        .insert(log, log_schema().source_type_key(), Bytes::from("stdin"));
 
     if let Some(hostname) = &hostname {
-        event.as_mut_log().insert(host_key, hostname.clone());
+        metadata_keys.host.insert(log, host_key, hostname.clone());
     }
 
     event
@@ -144,11 +213,11 @@ mod tests {
 
     #[test]
     fn stdin_create_event() {
-        let line = Bytes::from("hello world");
+        let event = Event::from("hello world");
         let host_key = "host".to_string();
         let hostname = Some("Some.Machine".to_string());
 
-        let event = create_event(line, &host_key, &hostname);
+        let event = create_event(event, &host_key, &hostname, &MetadataKeys::default());
         let log = event.into_log();
 
         assert_eq!(log["host"], "Some.Machine".into());
@@ -162,12 +231,18 @@ mod tests {
 
         let (tx, mut rx) = Pipeline::new_test();
         let config = StdinConfig::default();
-        let buf = Cursor::new("hello world\nhello world again");
-
-        stdin_source(buf, config, ShutdownSignal::noop(), tx)
-            .unwrap()
-            .await
-            .unwrap();
+        let buf = Cursor::new("hello world\r\nhello world again");
+
+        stdin_source(
+            buf,
+            config,
+            MetadataKeys::default(),
+            ShutdownSignal::noop(),
+            tx,
+        )
+        .unwrap()
+        .await
+        .unwrap();
 
         let event = rx.try_recv();
 
@@ -188,4 +263,33 @@ mod tests {
         assert!(event.is_err());
         assert_eq!(Err(mpsc::error::TryRecvError::Closed), event);
     }
+
+    #[tokio::test]
+    async fn stdin_decodes_json() {
+        trace_init();
+
+        let (tx, mut rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            decoding: DecodingConfig::Json,
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("{\"message\": \"hello\", \"status\": 200}\nnot json\n");
+
+        stdin_source(
+            buf,
+            config,
+            MetadataKeys::default(),
+            ShutdownSignal::noop(),
+            tx,
+        )
+        .unwrap()
+        .await
+        .unwrap();
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "hello".into());
+        assert_eq!(event.as_log()["status"], 200.into());
+
+        assert_eq!(Err(mpsc::error::TryRecvError::Closed), rx.try_recv());
+    }
 }
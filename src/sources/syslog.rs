@@ -1,35 +1,39 @@
 use super::util::{SocketListenAddr, TcpSource};
 #[cfg(unix)]
-use crate::sources::util::build_unix_stream_source;
+use crate::sources::util::{build_unix_datagram_source, build_unix_stream_source};
 #[cfg(unix)]
 use crate::udp;
 use crate::{
+    codecs::{insert_fields_from_syslog, resolve_year, OctetCountingDecoder},
     config::{
-        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        log_schema, DataType, GenerateConfig, GlobalOptions, MetadataKeys, Resource, SourceConfig,
         SourceDescription,
     },
-    event::{Event, Value},
+    event::Event,
     internal_events::{SyslogEventReceived, SyslogUdpReadError, SyslogUdpUtf8Error},
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsConfig},
     Pipeline,
 };
-use bytes::{Buf, Bytes, BytesMut};
-use chrono::{Datelike, Utc};
+use bytes::Bytes;
+#[cfg(unix)]
+use bytes::BytesMut;
+use chrono::Utc;
 use derive_is_enum_variant::is_enum_variant;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::io;
 use std::net::SocketAddr;
 #[cfg(unix)]
-use std::path::PathBuf;
-use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
-use tokio::net::UdpSocket;
-use tokio_util::{
-    codec::{BytesCodec, Decoder, LinesCodec, LinesCodecError},
-    udp::UdpFramed,
+use std::{
+    fs,
+    path::{Path, PathBuf},
 };
+use tokio::net::UdpSocket;
+#[cfg(unix)]
+use tokio_util::codec::{Decoder, LinesCodecError};
+use tokio_util::{codec::BytesCodec, udp::UdpFramed};
 
 #[derive(Deserialize, Serialize, Debug)]
 // TODO: add back when serde-rs/serde#1358 is addressed
@@ -59,12 +63,31 @@ pub enum Mode {
     },
     #[cfg(unix)]
     Unix { path: PathBuf },
+    #[cfg(unix)]
+    UnixDatagram {
+        #[serde(default = "default_unix_datagram_path")]
+        path: PathBuf,
+        #[serde(default = "default_socket_file_mode")]
+        socket_file_mode: u32,
+    },
 }
 
 pub fn default_max_length() -> usize {
     bytesize::kib(100u64) as usize
 }
 
+/// The socket applications log to through the libc `syslog` API.
+#[cfg(unix)]
+fn default_unix_datagram_path() -> PathBuf {
+    PathBuf::from("/dev/log")
+}
+
+/// Any local user must be able to log, as with a syslog daemon.
+#[cfg(unix)]
+const fn default_socket_file_mode() -> u32 {
+    0o666
+}
+
 impl SyslogConfig {
     pub fn from_mode(mode: Mode) -> Self {
         Self {
@@ -101,7 +124,7 @@ impl SourceConfig for SyslogConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
@@ -109,6 +132,7 @@ impl SourceConfig for SyslogConfig {
             .host_key
             .clone()
             .unwrap_or_else(|| log_schema().host_key().to_string());
+        let metadata_keys = globals.metadata_keys.clone();
 
         match self.mode.clone() {
             Mode::Tcp {
@@ -120,6 +144,7 @@ impl SourceConfig for SyslogConfig {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
+                    metadata_keys,
                 };
                 let shutdown_secs = 30;
                 let tls = MaybeTlsSettings::from_config(&tls, true)?;
@@ -141,21 +166,50 @@ impl SourceConfig for SyslogConfig {
                 address,
                 self.max_length,
                 host_key,
+                metadata_keys,
                 receive_buffer_bytes,
                 shutdown,
                 out,
             )),
             #[cfg(not(unix))]
-            Mode::Udp { address } => Ok(udp(address, self.max_length, host_key, shutdown, out)),
+            Mode::Udp { address } => Ok(udp(
+                address,
+                self.max_length,
+                host_key,
+                metadata_keys,
+                shutdown,
+                out,
+            )),
             #[cfg(unix)]
-            Mode::Unix { path } => Ok(build_unix_stream_source(
+            Mode::Unix { path } => build_unix_stream_source(
                 path,
-                SyslogDecoder::new(self.max_length),
+                None,
+                OctetCountingDecoder::new(self.max_length),
                 host_key,
                 shutdown,
                 out,
-                |host_key, default_host, line| Some(event_from_str(host_key, default_host, line)),
-            )),
+                move |host_key, default_host, frame| {
+                    Some(event_from_str(
+                        host_key,
+                        &metadata_keys,
+                        default_host,
+                        &String::from_utf8_lossy(&frame),
+                    ))
+                },
+            ),
+            #[cfg(unix)]
+            Mode::UnixDatagram {
+                path,
+                socket_file_mode,
+            } => unix_datagram(
+                path,
+                socket_file_mode,
+                self.max_length,
+                host_key,
+                metadata_keys,
+                shutdown,
+                out,
+            ),
         }
     }
 
@@ -173,6 +227,8 @@ impl SourceConfig for SyslogConfig {
             Mode::Udp { address, .. } => vec![Resource::udp(address)],
             #[cfg(unix)]
             Mode::Unix { .. } => vec![],
+            #[cfg(unix)]
+            Mode::UnixDatagram { .. } => vec![],
         }
     }
 }
@@ -181,120 +237,24 @@ impl SourceConfig for SyslogConfig {
 struct SyslogTcpSource {
     max_length: usize,
     host_key: String,
+    metadata_keys: MetadataKeys,
 }
 
 impl TcpSource for SyslogTcpSource {
-    type Error = LinesCodecError;
-    type Decoder = SyslogDecoder;
+    type Error = io::Error;
+    type Decoder = OctetCountingDecoder;
 
     fn decoder(&self) -> Self::Decoder {
-        SyslogDecoder::new(self.max_length)
-    }
-
-    fn build_event(&self, frame: String, host: Bytes) -> Option<Event> {
-        Some(event_from_str(&self.host_key, Some(host), &frame))
-    }
-}
-
-/// Decodes according to `Octet Counting` in https://tools.ietf.org/html/rfc6587
-#[derive(Clone, Debug)]
-struct SyslogDecoder {
-    other: LinesCodec,
-}
-
-impl SyslogDecoder {
-    fn new(max_length: usize) -> Self {
-        Self {
-            other: LinesCodec::new_with_max_length(max_length),
-        }
-    }
-
-    fn octet_decode(&self, src: &mut BytesMut) -> Result<Option<String>, LinesCodecError> {
-        // Encoding scheme:
-        //
-        // len ' ' data
-        // |    |  | len number of bytes that contain syslog message
-        // |    |
-        // |    | Separating whitespace
-        // |
-        // | ASCII decimal number of unknown length
-
-        if let Some(i) = src.iter().position(|&b| b == b' ') {
-            let len: usize = std::str::from_utf8(&src[..i])
-                .map_err(|_| ())
-                .and_then(|num| num.parse().map_err(|_| ()))
-                .map_err(|_| {
-                    LinesCodecError::Io(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        "Unable to decode message len as number",
-                    ))
-                })?;
-
-            let from = i + 1;
-            let to = from + len;
-
-            if let Some(msg) = src.get(from..to) {
-                let s = std::str::from_utf8(msg)
-                    .map_err(|_| {
-                        LinesCodecError::Io(io::Error::new(
-                            io::ErrorKind::InvalidData,
-                            "Unable to decode message as UTF8",
-                        ))
-                    })?
-                    .to_string();
-                src.advance(to);
-                Ok(Some(s))
-            } else {
-                Ok(None)
-            }
-        } else if src.len() < self.other.max_length() {
-            Ok(None)
-        } else {
-            // This is certainly malformed, and there is no recovering from this.
-            Err(LinesCodecError::Io(io::Error::new(
-                io::ErrorKind::Other,
-                "Frame length limit exceeded",
-            )))
-        }
+        OctetCountingDecoder::new(self.max_length)
     }
 
-    /// None if this is not octet counting encoded
-    fn checked_decode(
-        &self,
-        src: &mut BytesMut,
-    ) -> Option<Result<Option<String>, LinesCodecError>> {
-        if let Some(&first_byte) = src.get(0) {
-            if (49..=57).contains(&first_byte) {
-                // First character is non zero number so we can assume that
-                // octet count framing is used.
-                trace!("Octet counting encoded event detected.");
-                return Some(self.octet_decode(src));
-            }
-        }
-        None
-    }
-}
-
-impl Decoder for SyslogDecoder {
-    type Item = String;
-    type Error = LinesCodecError;
-
-    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(ret) = self.checked_decode(src) {
-            ret
-        } else {
-            // Octet counting isn't used so fallback to newline codec.
-            self.other.decode(src)
-        }
-    }
-
-    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(ret) = self.checked_decode(buf) {
-            ret
-        } else {
-            // Octet counting isn't used so fallback to newline codec.
-            self.other.decode_eof(buf)
-        }
+    fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
+        Some(event_from_str(
+            &self.host_key,
+            &self.metadata_keys,
+            Some(host),
+            &String::from_utf8_lossy(&frame),
+        ))
     }
 }
 
@@ -302,6 +262,7 @@ pub fn udp(
     addr: SocketAddr,
     _max_length: usize,
     host_key: String,
+    metadata_keys: MetadataKeys,
     #[cfg(unix)] receive_buffer_bytes: Option<usize>,
     shutdown: ShutdownSignal,
     out: Pipeline,
@@ -328,6 +289,7 @@ pub fn udp(
             .take_until(shutdown)
             .filter_map(|frame| {
                 let host_key = host_key.clone();
+                let metadata_keys = metadata_keys.clone();
                 async move {
                     match frame {
                         Ok((bytes, received_from)) => {
@@ -336,7 +298,14 @@ pub fn udp(
                             std::str::from_utf8(&bytes)
                                 .map_err(|error| emit!(SyslogUdpUtf8Error { error }))
                                 .ok()
-                                .map(|s| Ok(event_from_str(&host_key, Some(received_from), s)))
+                                .map(|s| {
+                                    Ok(event_from_str(
+                                        &host_key,
+                                        &metadata_keys,
+                                        Some(received_from),
+                                        s,
+                                    ))
+                                })
                         }
                         Err(error) => {
                             emit!(SyslogUdpReadError { error });
@@ -353,15 +322,93 @@ pub fn udp(
     })
 }
 
-/// Function used to resolve the year for syslog messages that don't include the year.
-/// If the current month is January, and the syslog message is for December, it will take the previous year.
-/// Otherwise, take the current year.
-fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
-    let now = Utc::now();
-    if now.month() == 1 && month == 12 {
-        now.year() - 1
-    } else {
-        now.year()
+/// Takes each datagram whole, as messages sent through the libc `syslog` API
+/// aren't framed and may span multiple lines.
+#[cfg(unix)]
+#[derive(Clone, Debug)]
+struct SyslogDatagramDecoder;
+
+#[cfg(unix)]
+impl Decoder for SyslogDatagramDecoder {
+    type Item = String;
+    type Error = LinesCodecError;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        let frame = buf.split();
+        // Some libc implementations terminate messages with a null byte.
+        let message = String::from_utf8_lossy(&frame);
+        Ok(Some(message.trim_end_matches(&['\n', '\0'][..]).to_owned()))
+    }
+}
+
+/// Listens on a Unix datagram socket, `/dev/log` by default, so that Vector
+/// can take the place of the local syslog daemon.
+#[cfg(unix)]
+fn unix_datagram(
+    path: PathBuf,
+    socket_file_mode: u32,
+    max_length: usize,
+    host_key: String,
+    metadata_keys: MetadataKeys,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> crate::Result<super::Source> {
+    let source = build_unix_datagram_source(
+        path.clone(),
+        Some(socket_file_mode),
+        max_length,
+        host_key,
+        SyslogDatagramDecoder,
+        shutdown,
+        out,
+        move |host_key, default_host, line| {
+            Some(event_from_str(
+                host_key,
+                &metadata_keys,
+                default_host,
+                &line,
+            ))
+        },
+    )?;
+
+    Ok(Box::pin(async move {
+        remove_stale_socket(&path).map_err(|error| {
+            error!(message = "Unable to take over socket.", path = ?path, %error);
+        })?;
+        source.await
+    }))
+}
+
+/// Removes a socket file left behind by a previous run, or by a syslog daemon
+/// that has since stopped. Sockets that are still being listened on are kept,
+/// so that Vector doesn't silently steal them from another daemon.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    use std::os::unix::{fs::FileTypeExt, net::UnixDatagram};
+
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            match UnixDatagram::unbound()?.connect(path) {
+                Err(error) if error.kind() == io::ErrorKind::ConnectionRefused => {
+                    fs::remove_file(path)
+                }
+                Err(error) => Err(error),
+                Ok(()) => Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    "socket is in use by another process",
+                )),
+            }
+        }
+        // Other files are refused when building the source, and binding
+        // reports any that appeared since.
+        _ => Ok(()),
     }
 }
 
@@ -372,15 +419,22 @@ fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
 // TODO: many more cases to handle:
 // octet framing (i.e. num bytes as ascii string prefix) with and without delimiters
 // null byte delimiter in place of newline
-fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Event {
+fn event_from_str(
+    host_key: &str,
+    metadata_keys: &MetadataKeys,
+    default_host: Option<Bytes>,
+    line: &str,
+) -> Event {
     let line = line.trim();
     let parsed = syslog_loose::parse_message_with_year(line, resolve_year);
     let mut event = Event::from(&parsed.msg[..]);
 
     // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("syslog"));
+    metadata_keys.source_type.insert(
+        event.as_mut_log(),
+        log_schema().source_type_key(),
+        Bytes::from("syslog"),
+    );
 
     if let Some(default_host) = default_host.clone() {
         event.as_mut_log().insert("source_ip", default_host);
@@ -388,7 +442,9 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Ev
 
     let parsed_hostname = parsed.hostname.map(|x| Bytes::from(x.to_owned()));
     if let Some(parsed_host) = parsed_hostname.or(default_host) {
-        event.as_mut_log().insert(host_key, parsed_host);
+        metadata_keys
+            .host
+            .insert(event.as_mut_log(), host_key, parsed_host);
     }
 
     let timestamp = parsed
@@ -413,43 +469,6 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Ev
     event
 }
 
-fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
-    let log = event.as_mut_log();
-
-    if let Some(host) = parsed.hostname {
-        log.insert("hostname", host.to_string());
-    }
-    if let Some(severity) = parsed.severity {
-        log.insert("severity", severity.as_str().to_owned());
-    }
-    if let Some(facility) = parsed.facility {
-        log.insert("facility", facility.as_str().to_owned());
-    }
-    if let Protocol::RFC5424(version) = parsed.protocol {
-        log.insert("version", version as i64);
-    }
-    if let Some(app_name) = parsed.appname {
-        log.insert("appname", app_name.to_owned());
-    }
-    if let Some(msg_id) = parsed.msgid {
-        log.insert("msgid", msg_id.to_owned());
-    }
-    if let Some(procid) = parsed.procid {
-        let value: Value = match procid {
-            ProcId::PID(pid) => pid.into(),
-            ProcId::Name(name) => name.to_string().into(),
-        };
-        log.insert("procid", value);
-    }
-
-    for element in parsed.structured_data.into_iter() {
-        for (name, value) in element.params.into_iter() {
-            let key = format!("{}.{}", element.id, name);
-            log.insert(key, value.to_string());
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::{event_from_str, Mode, SyslogConfig};
@@ -584,6 +603,100 @@ mod test {
         assert!(config.mode.is_unix());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn config_unix_datagram() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "unix_datagram"
+          "#,
+        )
+        .unwrap();
+
+        match config.mode {
+            Mode::UnixDatagram {
+                path,
+                socket_file_mode,
+            } => {
+                assert_eq!(path, std::path::PathBuf::from("/dev/log"));
+                assert_eq!(socket_file_mode, 0o666);
+            }
+            _ => panic!("expected Mode::UnixDatagram"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_datagram_rejects_invalid_socket_file_mode() {
+        use crate::{config::SourceConfig, shutdown::ShutdownSignal, Pipeline};
+
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "unix_datagram"
+            path = "/tmp/vector_syslog_invalid_mode"
+            socket_file_mode = 0o10666
+          "#,
+        )
+        .unwrap();
+
+        let (tx, _rx) = Pipeline::new_test();
+        let result = config
+            .build("default", &Default::default(), ShutdownSignal::noop(), tx)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_datagram_rejects_path_that_isnt_a_socket() {
+        use crate::{config::SourceConfig, shutdown::ShutdownSignal, Pipeline};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log");
+        std::fs::write(&path, "").unwrap();
+        let config = SyslogConfig::from_mode(Mode::UnixDatagram {
+            path,
+            socket_file_mode: 0o666,
+        });
+
+        let (tx, _rx) = Pipeline::new_test();
+        let result = config
+            .build("default", &Default::default(), ShutdownSignal::noop(), tx)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_datagram_decodes_whole_message() {
+        use super::SyslogDatagramDecoder;
+        use tokio_util::codec::Decoder;
+
+        let mut buf = bytes::BytesMut::from(&b"<13>Feb 13 20:07:26 app[1]: first\nsecond\n\0"[..]);
+        let message = SyslogDatagramDecoder.decode_eof(&mut buf).unwrap();
+        assert_eq!(
+            message.as_deref(),
+            Some("<13>Feb 13 20:07:26 app[1]: first\nsecond")
+        );
+        assert_eq!(SyslogDatagramDecoder.decode_eof(&mut buf).unwrap(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn removes_only_stale_sockets() {
+        use super::remove_stale_socket;
+        use std::os::unix::net::UnixDatagram;
+
+        let path = tempfile::tempdir().unwrap().into_path().join("log");
+        let socket = UnixDatagram::bind(&path).unwrap();
+        assert!(remove_stale_socket(&path).is_err());
+        assert!(path.exists());
+
+        drop(socket);
+        remove_stale_socket(&path).unwrap();
+        assert!(!path.exists());
+    }
+
     #[test]
     fn syslog_ng_network_syslog_protocol() {
         // this should also match rsyslog omfwd with template=RSYSLOG_SyslogProtocol23Format
@@ -620,7 +733,10 @@ mod test {
             expected.insert("procid", 8449);
         }
 
-        assert_eq!(event_from_str(&"host".to_string(), None, &raw), expected);
+        assert_eq!(
+            event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &raw),
+            expected
+        );
     }
 
     #[test]
@@ -648,7 +764,7 @@ mod test {
             expected.insert("procid", 8449);
         }
 
-        let event = event_from_str(&"host".to_string(), None, &raw);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &raw);
         assert_eq!(event, expected);
 
         let raw = format!(
@@ -656,7 +772,7 @@ mod test {
             r#"[incorrect x=]"#, msg
         );
 
-        let event = event_from_str(&"host".to_string(), None, &raw);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &raw);
         assert_eq!(event, expected);
     }
 
@@ -675,7 +791,7 @@ mod test {
             r#"[empty]"#
         );
 
-        let event = event_from_str(&"host".to_string(), None, &msg);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &msg);
         assert!(there_is_map_called_empty(event));
 
         let msg = format!(
@@ -683,7 +799,7 @@ mod test {
             r#"[non_empty x="1"][empty]"#
         );
 
-        let event = event_from_str(&"host".to_string(), None, &msg);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &msg);
         assert!(there_is_map_called_empty(event));
 
         let msg = format!(
@@ -691,7 +807,7 @@ mod test {
             r#"[empty][non_empty x="1"]"#
         );
 
-        let event = event_from_str(&"host".to_string(), None, &msg);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &msg);
         assert!(there_is_map_called_empty(event));
 
         let msg = format!(
@@ -699,7 +815,7 @@ mod test {
             r#"[empty not_really="testing the test"]"#
         );
 
-        let event = event_from_str(&"host".to_string(), None, &msg);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &msg);
         assert!(!there_is_map_called_empty(event));
     }
 
@@ -712,8 +828,8 @@ mod test {
         let cleaned = r#"<13>1 2019-02-13T19:48:34+00:00 74794bfb6795 root 8449 - [meta sequenceId="1"] i am foobar"#;
 
         assert_eq!(
-            event_from_str(&"host".to_string(), None, raw),
-            event_from_str(&"host".to_string(), None, cleaned)
+            event_from_str(&"host".to_string(), &MetadataKeys::default(), None, raw),
+            event_from_str(&"host".to_string(), &MetadataKeys::default(), None, cleaned)
         );
     }
 
@@ -721,7 +837,7 @@ mod test {
     fn syslog_ng_default_network() {
         let msg = "i am foobar";
         let raw = format!(r#"<13>Feb 13 20:07:26 74794bfb6795 root[8539]: {}"#, msg);
-        let event = event_from_str(&"host".to_string(), None, &raw);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &raw);
 
         let mut expected = Event::from(msg);
         {
@@ -751,7 +867,7 @@ mod test {
             r#"<190>Feb 13 21:31:56 74794bfb6795 liblogging-stdlog:  [origin software="rsyslogd" swVersion="8.24.0" x-pid="8979" x-info="http://www.rsyslog.com"] {}"#,
             msg
         );
-        let event = event_from_str(&"host".to_string(), None, &raw);
+        let event = event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &raw);
 
         let mut expected = Event::from(msg);
         {
@@ -806,6 +922,9 @@ mod test {
             expected.insert("origin.x-info", "http://www.rsyslog.com");
         }
 
-        assert_eq!(event_from_str(&"host".to_string(), None, &raw), expected);
+        assert_eq!(
+            event_from_str(&"host".to_string(), &MetadataKeys::default(), None, &raw),
+            expected
+        );
     }
 }
@@ -0,0 +1,231 @@
+//! Decompression of payloads that senders compress before sending them to a
+//! source, either whole datagrams or the stream of a connection.
+
+use bytes::{Bytes, BytesMut};
+use flate2::{read::MultiGzDecoder, write::GzDecoder};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, Read, Write},
+    sync::{Arc, Mutex},
+};
+use tokio_util::codec::Decoder;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// How received payloads are compressed.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Decompression {
+    /// Payloads aren't compressed.
+    #[derivative(Default)]
+    None,
+    Gzip,
+    Zstd,
+    /// Payloads are decompressed if they start with the magic bytes of gzip or
+    /// zstd, and are passed through otherwise.
+    Auto,
+}
+
+impl Decompression {
+    /// The compression of a payload starting with `prefix`.
+    fn detect(self, prefix: &[u8]) -> Self {
+        match self {
+            Self::Auto if prefix.starts_with(GZIP_MAGIC) => Self::Gzip,
+            Self::Auto if prefix.starts_with(ZSTD_MAGIC) => Self::Zstd,
+            Self::Auto => Self::None,
+            decompression => decompression,
+        }
+    }
+
+    /// Decompresses a whole payload, such as a datagram.
+    pub fn decompress(self, payload: Bytes) -> io::Result<Bytes> {
+        match self.detect(&payload) {
+            Self::Gzip => {
+                let mut decompressed = Vec::new();
+                MultiGzDecoder::new(&payload[..]).read_to_end(&mut decompressed)?;
+                Ok(decompressed.into())
+            }
+            Self::Zstd => Ok(zstd::decode_all(&payload[..])?.into()),
+            Self::None | Self::Auto => Ok(payload),
+        }
+    }
+}
+
+/// Where a streaming decompressor writes its output.
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<BytesMut>>);
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .expect("Decompression mutex is poisoned")
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Decompresses the stream of a connection before passing it on to `inner`
+/// to be split into frames.
+pub struct DecompressingDecoder<D> {
+    inner: D,
+    decompression: Decompression,
+    /// Set once the compression of the stream is known.
+    decompressor: Option<Option<Box<dyn Write + Send>>>,
+    output: Output,
+    buffer: BytesMut,
+}
+
+impl<D> DecompressingDecoder<D> {
+    pub fn new(inner: D, decompression: Decompression) -> Self {
+        Self {
+            inner,
+            decompression,
+            decompressor: None,
+            output: Output::default(),
+            buffer: BytesMut::new(),
+        }
+    }
+
+    fn start(&mut self, prefix: &[u8]) -> io::Result<()> {
+        let decompressor: Option<Box<dyn Write + Send>> = match self.decompression.detect(prefix) {
+            Decompression::Gzip => Some(Box::new(GzDecoder::new(self.output.clone()))),
+            Decompression::Zstd => Some(Box::new(zstd::stream::write::Decoder::new(
+                self.output.clone(),
+            )?)),
+            Decompression::None | Decompression::Auto => None,
+        };
+        self.decompressor = Some(decompressor);
+        Ok(())
+    }
+
+    /// Feeds `src` to the decompressor, and moves its output to the buffer.
+    fn decompress(
+        decompressor: &mut dyn Write,
+        output: &Output,
+        src: &mut BytesMut,
+        buffer: &mut BytesMut,
+    ) -> io::Result<()> {
+        decompressor.write_all(&src[..])?;
+        decompressor.flush()?;
+        src.clear();
+
+        let mut output = output.0.lock().expect("Decompression mutex is poisoned");
+        buffer.extend_from_slice(&output);
+        output.clear();
+        Ok(())
+    }
+}
+
+impl<D> Decoder for DecompressingDecoder<D>
+where
+    D: Decoder,
+    D::Error: From<io::Error>,
+{
+    type Item = D::Item;
+    type Error = D::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if self.decompressor.is_none() {
+            // Wait for enough of the stream to tell how it's compressed.
+            if self.decompression == Decompression::Auto && src.len() < ZSTD_MAGIC.len() {
+                return Ok(None);
+            }
+            self.start(src)?;
+        }
+
+        match self.decompressor.as_mut().and_then(Option::as_mut) {
+            Some(decompressor) => {
+                Self::decompress(decompressor.as_mut(), &self.output, src, &mut self.buffer)?;
+                self.inner.decode(&mut self.buffer)
+            }
+            None => self.inner.decode(src),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if self.decompressor.is_none() {
+            self.start(src)?;
+        }
+
+        match self.decompressor.as_mut().and_then(Option::as_mut) {
+            Some(decompressor) => {
+                Self::decompress(decompressor.as_mut(), &self.output, src, &mut self.buffer)?;
+                self.inner.decode_eof(&mut self.buffer)
+            }
+            None => self.inner.decode_eof(src),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::BytesDelimitedCodec;
+    use flate2::{write::GzEncoder, Compression};
+
+    const LINES: &[u8] = b"first\nsecond\nthird";
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn decode_all(decompression: Decompression, data: &[u8]) -> Vec<Bytes> {
+        let mut decoder = DecompressingDecoder::new(BytesDelimitedCodec::new(b'\n'), decompression);
+        let mut frames = Vec::new();
+
+        // Feed the stream in small chunks, as it would arrive over a connection.
+        let mut src = BytesMut::new();
+        for chunk in data.chunks(3) {
+            src.extend_from_slice(chunk);
+            while let Some(frame) = decoder.decode(&mut src).unwrap() {
+                frames.push(frame);
+            }
+        }
+        while let Some(frame) = decoder.decode_eof(&mut src).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn decompresses_datagrams() {
+        let payload = Bytes::from(gzip(LINES));
+        assert_eq!(
+            Decompression::Gzip.decompress(payload.clone()).unwrap(),
+            LINES
+        );
+        assert_eq!(Decompression::Auto.decompress(payload).unwrap(), LINES);
+
+        let payload = Bytes::from(zstd::encode_all(LINES, 3).unwrap());
+        assert_eq!(Decompression::Auto.decompress(payload).unwrap(), LINES);
+
+        let payload = Bytes::from_static(LINES);
+        assert_eq!(Decompression::Auto.decompress(payload).unwrap(), LINES);
+    }
+
+    #[test]
+    fn decompresses_streams() {
+        let expected = vec!["first", "second", "third"];
+
+        assert_eq!(decode_all(Decompression::Gzip, &gzip(LINES)), expected);
+        assert_eq!(decode_all(Decompression::Auto, &gzip(LINES)), expected);
+        let zstd = zstd::encode_all(LINES, 3).unwrap();
+        assert_eq!(decode_all(Decompression::Zstd, &zstd), expected);
+        assert_eq!(decode_all(Decompression::Auto, LINES), expected);
+    }
+
+    #[test]
+    fn rejects_corrupt_payloads() {
+        let payload = Bytes::from_static(b"\x1f\x8bnot gzip");
+        assert!(Decompression::Auto.decompress(payload).is_err());
+    }
+}
@@ -145,6 +145,9 @@ pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMe
                         .map_err(|error| handle_decode_error(encoding, error))?;
                     decoded.into()
                 }
+                "zstd" => zstd::stream::decode_all(body.reader())
+                    .map_err(|error| handle_decode_error(encoding, error))?
+                    .into(),
                 "snappy" => SnappyDecoder::new()
                     .decompress_vec(&body)
                     .map_err(|error| handle_decode_error(encoding, error))?
@@ -183,6 +186,19 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// Verifies a request before its body is decoded, for services that sign
+    /// the payloads they deliver.
+    fn verify(&self, _header_map: &HeaderMap, _body: &Bytes) -> Result<(), ErrorMessage> {
+        Ok(())
+    }
+
+    /// The response to a `GET` request of `path`, for services that challenge
+    /// an endpoint before delivering to it. Such requests are not found unless
+    /// this returns a response.
+    fn challenge(&self, _path: &str) -> Option<String> {
+        None
+    }
+
     fn run(
         self,
         address: SocketAddr,
@@ -196,6 +212,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         let tls = MaybeTlsSettings::from_config(tls, true)?;
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
         let path = path.to_owned();
+        let challenger = self.clone();
         Ok(Box::pin(async move {
             let span = crate::trace::current_span();
             let mut filter: BoxedFilter<()> = warp::post().boxed();
@@ -234,6 +251,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                         let mut out = out.clone();
                         let events = auth
                             .is_valid(&auth_header)
+                            .and_then(|()| self.verify(&headers, &body))
                             .and_then(|()| decode(&encoding_header, body))
                             .and_then(|body| {
                                 let body_len=body.len();
@@ -273,19 +291,28 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 );
 
             let ping = warp::get().and(warp::path("ping")).map(|| "pong");
-            let routes = svc.or(ping).recover(|r: Rejection| async move {
-                if let Some(e_msg) = r.find::<ErrorMessage>() {
-                    let json = warp::reply::json(e_msg);
-                    Ok(warp::reply::with_status(
-                        json,
-                        StatusCode::from_u16(e_msg.code)
-                            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
-                    ))
-                } else {
-                    //other internal error - will return 500 internal server error
-                    Err(r)
-                }
-            });
+            let challenge = warp::get()
+                .and(warp::path::full())
+                .and_then(move |path: FullPath| {
+                    let response = challenger.challenge(path.as_str());
+                    async move { response.ok_or_else(warp::reject::not_found) }
+                });
+            let routes = svc
+                .or(ping)
+                .or(challenge)
+                .recover(|r: Rejection| async move {
+                    if let Some(e_msg) = r.find::<ErrorMessage>() {
+                        let json = warp::reply::json(e_msg);
+                        Ok(warp::reply::with_status(
+                            json,
+                            StatusCode::from_u16(e_msg.code)
+                                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                        ))
+                    } else {
+                        //other internal error - will return 500 internal server error
+                        Err(r)
+                    }
+                });
 
             info!(message = "Building HTTP server.", address = %address);
 
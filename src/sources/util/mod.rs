@@ -1,10 +1,22 @@
+#[cfg(feature = "sources-socket")]
+mod decompression;
 mod encoding_config;
 #[cfg(feature = "sources-utils-http")]
 mod http;
 pub mod multiline_config;
+#[cfg(any(
+    feature = "sources-amqp",
+    feature = "sources-docker_logs",
+    feature = "sources-journald",
+    feature = "sources-kafka",
+    all(feature = "sources-utils-tls", feature = "listenfd")
+))]
+mod reconnect;
+#[cfg(feature = "sources-utils-schema-registry")]
+mod schema_registry;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
 mod tcp;
-#[cfg(all(unix, feature = "sources-socket"))]
+#[cfg(all(unix, any(feature = "sources-socket", feature = "sources-syslog")))]
 mod unix_datagram;
 #[cfg(all(unix, feature = "sources-utils-unix"))]
 mod unix_stream;
@@ -15,11 +27,47 @@ pub(crate) use self::http::add_query_parameters;
 pub(crate) use self::http::decode;
 #[cfg(feature = "sources-utils-http")]
 pub(crate) use self::http::{ErrorMessage, HttpSource, HttpSourceAuthConfig};
+#[cfg(feature = "sources-socket")]
+pub use decompression::{DecompressingDecoder, Decompression};
 pub use encoding_config::EncodingConfig;
 pub use multiline_config::MultilineConfig;
+#[cfg(any(
+    feature = "sources-docker_logs",
+    feature = "sources-journald",
+    feature = "sources-kafka",
+    all(feature = "sources-utils-tls", feature = "listenfd")
+))]
+pub use reconnect::{Backoff, ReconnectPolicy};
+#[cfg(feature = "sources-utils-schema-registry")]
+pub use schema_registry::{OnValidationFailure, SchemaValidationConfig, SchemaValidator};
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
 pub use tcp::{SocketListenAddr, TcpSource};
-#[cfg(all(unix, feature = "sources-socket",))]
+#[cfg(all(unix, any(feature = "sources-socket", feature = "sources-syslog")))]
 pub use unix_datagram::build_unix_datagram_source;
 #[cfg(all(unix, feature = "sources-utils-unix",))]
 pub use unix_stream::build_unix_stream_source;
+
+/// Checks that `mode` only holds permission bits, so that a mistyped mode fails
+/// the config rather than the source once it's running.
+#[cfg(all(unix, feature = "sources-utils-unix"))]
+fn validate_socket_file_mode(mode: u32) -> crate::Result<()> {
+    if mode & !0o777 != 0 {
+        return Err(format!("Invalid socket file mode {:o}, expected at most 777.", mode).into());
+    }
+    Ok(())
+}
+
+/// Checks that nothing but a socket exists at `path`, so that a datagram source
+/// told to listen on a regular file, or on a symlink such as `/dev/log` on some
+/// systems, fails the config rather than the source once it's running.
+#[cfg(all(unix, any(feature = "sources-socket", feature = "sources-syslog")))]
+fn validate_socket_path(path: &std::path::Path) -> crate::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.file_type().is_socket() => {
+            Err(format!("Path {:?} already exists and isn't a socket.", path).into())
+        }
+        _ => Ok(()),
+    }
+}
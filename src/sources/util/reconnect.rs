@@ -0,0 +1,131 @@
+//! How sources wait before reconnecting, or restarting whatever they read
+//! from, after a failure. Delays grow exponentially with consecutive failures,
+//! so that nothing is retried in a hot loop, and are jittered, so that
+//! instances failing together don't retry in lockstep.
+
+use crate::internal_events::SourceReconnecting;
+use rand::Rng;
+use std::time::Duration;
+use tokio::time::delay_for;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    /// Share of each delay that's randomized.
+    jitter: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self.max_delay = self.max_delay.max(initial_delay);
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay.max(self.initial_delay);
+        self
+    }
+
+    pub fn backoff(&self) -> Backoff {
+        Backoff {
+            policy: *self,
+            attempts: 0,
+        }
+    }
+}
+
+/// The state of a policy across consecutive failures.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    policy: ReconnectPolicy,
+    attempts: u32,
+}
+
+impl Backoff {
+    /// Returns how long to wait before the next attempt, and reports it.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.delay_for_attempt(rand::thread_rng().gen());
+        self.attempts = self.attempts.saturating_add(1);
+
+        emit!(SourceReconnecting {
+            attempt: self.attempts,
+            delay
+        });
+        delay
+    }
+
+    /// Waits before the next attempt.
+    pub async fn wait(&mut self) {
+        delay_for(self.next_delay()).await;
+    }
+
+    /// Starts over after a successful attempt.
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+    }
+
+    /// `random` is in `[0, 1)`, and takes off up to the jitter's share of the
+    /// delay.
+    fn delay_for_attempt(&self, random: f64) -> Duration {
+        let ReconnectPolicy {
+            initial_delay,
+            max_delay,
+            jitter,
+        } = self.policy;
+
+        let delay = initial_delay.as_secs_f64() * 2f64.powi(self.attempts.min(32) as i32);
+        let delay = delay.min(max_delay.as_secs_f64());
+        Duration::from_secs_f64(delay * (1.0 - jitter * random))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delays_grow_to_max() {
+        let mut backoff = ReconnectPolicy::default()
+            .initial_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .backoff();
+
+        let delays = (0..5)
+            .map(|_| {
+                let delay = backoff.delay_for_attempt(0.0);
+                backoff.attempts += 1;
+                delay.as_secs()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+
+        backoff.reset();
+        assert_eq!(backoff.delay_for_attempt(0.0), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_shortens_delays() {
+        let mut backoff = ReconnectPolicy::default()
+            .initial_delay(Duration::from_secs(4))
+            .backoff();
+
+        assert_eq!(backoff.delay_for_attempt(0.5), Duration::from_secs(3));
+        for _ in 0..100 {
+            let delay = backoff.next_delay();
+            backoff.reset();
+            assert!(Duration::from_secs(2) <= delay && delay <= Duration::from_secs(4));
+        }
+    }
+}
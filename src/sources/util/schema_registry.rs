@@ -0,0 +1,418 @@
+//! Validation of incoming events against an Avro schema fetched from a schema
+//! registry, either a Confluent Schema Registry or a plain HTTP endpoint
+//! serving the schema itself. Events are either decoded from JSON and then
+//! validated, or decoded from Avro with the schema itself.
+
+use crate::{
+    event::{Event, LogEvent, Value},
+    http::HttpClient,
+    internal_events::{SchemaValidationFailed, SchemaValidationFetched},
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+use avro_rs::{types::Value as AvroValue, Reader, Schema};
+use chrono::{TimeZone, Utc};
+use http::{Request, StatusCode, Uri};
+use hyper::{body::to_bytes as body_to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum SchemaRegistryError {
+    #[snafu(display("Invalid schema registry URL: {}", source))]
+    InvalidUrl { source: http::uri::InvalidUri },
+    #[snafu(display("Unexpected status fetching schema: {}", status))]
+    UnexpectedStatus { status: StatusCode },
+    #[snafu(display("Invalid schema registry response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("Unsupported schema type {:?}, only AVRO is supported", schema_type))]
+    UnsupportedSchemaType { schema_type: String },
+    #[snafu(display("Invalid Avro schema: {}", source))]
+    InvalidSchema { source: avro_rs::Error },
+}
+
+/// The magic bytes an Avro object container file starts with.
+const AVRO_MAGIC: &[u8] = b"Obj\x01";
+
+/// The magic byte of the Confluent wire format, followed by the 4 bytes of the schema id.
+const CONFLUENT_MAGIC: u8 = 0;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SchemaRegistryConfig {
+    /// A Confluent Schema Registry, serving the schemas of `subject`.
+    Confluent {
+        url: String,
+        subject: String,
+        #[serde(default = "default_version")]
+        version: String,
+    },
+    /// A plain HTTP endpoint serving the schema itself.
+    Http { url: String },
+}
+
+fn default_version() -> String {
+    "latest".to_owned()
+}
+
+/// What to do with events that don't conform to the schema.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum OnValidationFailure {
+    /// Keep the event, with the validation error in `error_field`.
+    #[derivative(Default)]
+    Tag,
+    /// Drop the event.
+    Drop,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaValidationConfig {
+    registry: SchemaRegistryConfig,
+    #[serde(default)]
+    on_failure: OnValidationFailure,
+    #[serde(default = "default_error_field")]
+    error_field: String,
+    tls: Option<TlsConfig>,
+}
+
+fn default_error_field() -> String {
+    "schema_error".to_owned()
+}
+
+impl SchemaValidationConfig {
+    /// Fetches the schema from the registry. The schema is fetched once per build, so a
+    /// new version of the schema is picked up by reloading the config.
+    pub async fn build(&self) -> crate::Result<SchemaValidator> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let client = HttpClient::new(tls)?;
+
+        let (url, body, schema_id) = match &self.registry {
+            SchemaRegistryConfig::Confluent {
+                url,
+                subject,
+                version,
+            } => {
+                let url = format!(
+                    "{}/subjects/{}/versions/{}",
+                    url.trim_end_matches('/'),
+                    subject,
+                    version
+                );
+                let body = fetch(&client, &url).await?;
+                let (schema, id) = confluent_schema(&body)?;
+                (url, schema, id)
+            }
+            SchemaRegistryConfig::Http { url } => {
+                let body = fetch(&client, url).await?;
+                (
+                    url.clone(),
+                    String::from_utf8_lossy(&body).into_owned(),
+                    None,
+                )
+            }
+        };
+
+        let schema = Schema::parse_str(&body).context(InvalidSchema)?;
+        emit!(SchemaValidationFetched { url: &url });
+
+        let mut validator = SchemaValidator::new(schema, self.on_failure, self.error_field.clone());
+        validator.schema_id = schema_id;
+        Ok(validator)
+    }
+}
+
+async fn fetch(client: &HttpClient, url: &str) -> crate::Result<bytes::Bytes> {
+    let uri = url.parse::<Uri>().context(InvalidUrl)?;
+    let request = Request::get(uri)
+        .header(
+            "Accept",
+            "application/vnd.schemaregistry.v1+json, application/json",
+        )
+        .body(Body::empty())?;
+
+    let response = client.send(request).await?;
+    match response.status() {
+        StatusCode::OK => Ok(body_to_bytes(response.into_body()).await?),
+        status => Err(SchemaRegistryError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfluentSchema {
+    schema: String,
+    schema_type: Option<String>,
+    id: Option<u32>,
+}
+
+/// The schema and schema id of a Confluent Schema Registry subject version response.
+fn confluent_schema(body: &[u8]) -> crate::Result<(String, Option<u32>)> {
+    let response: ConfluentSchema = serde_json::from_slice(body).context(InvalidResponse)?;
+    match response.schema_type {
+        Some(schema_type) if schema_type != "AVRO" => {
+            Err(SchemaRegistryError::UnsupportedSchemaType { schema_type }.into())
+        }
+        _ => Ok((response.schema, response.id)),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaValidator {
+    schema: Schema,
+    /// The id of the schema in a Confluent Schema Registry, which Avro payloads in the
+    /// Confluent wire format are expected to be written with.
+    schema_id: Option<u32>,
+    on_failure: OnValidationFailure,
+    error_field: String,
+}
+
+impl SchemaValidator {
+    pub fn new(schema: Schema, on_failure: OnValidationFailure, error_field: String) -> Self {
+        Self {
+            schema,
+            schema_id: None,
+            on_failure,
+            error_field,
+        }
+    }
+
+    /// Decodes the events of an Avro payload, which is either an object container file,
+    /// whose records are resolved against the schema, or a single datum written with the
+    /// schema, in the Confluent wire format if the schema comes from a Confluent Schema
+    /// Registry. Payloads that can't be decoded have no events to keep, so they're an
+    /// error whatever `on_failure` is.
+    pub fn decode_avro(&self, body: &[u8]) -> Result<Vec<Event>, String> {
+        let result = self.decode_avro_values(body).and_then(|values| {
+            values
+                .into_iter()
+                .map(|value| match avro_to_value(value) {
+                    Value::Map(fields) => Ok(Event::from(fields)),
+                    _ => Err("Schema is not a record".to_owned()),
+                })
+                .collect()
+        });
+
+        if let Err(error) = &result {
+            emit!(SchemaValidationFailed {
+                error,
+                dropped: true,
+            });
+        }
+        result
+    }
+
+    fn decode_avro_values(&self, body: &[u8]) -> Result<Vec<AvroValue>, String> {
+        if body.starts_with(AVRO_MAGIC) {
+            let reader =
+                Reader::with_schema(&self.schema, body).map_err(|error| error.to_string())?;
+            return reader
+                .collect::<Result<_, _>>()
+                .map_err(|error| error.to_string());
+        }
+
+        let mut datum = match self.schema_id {
+            Some(schema_id) => match body {
+                [CONFLUENT_MAGIC, a, b, c, d, datum @ ..] => {
+                    let id = u32::from_be_bytes([*a, *b, *c, *d]);
+                    if id != schema_id {
+                        return Err(format!(
+                            "Payload was written with schema id {}, expected {}",
+                            id, schema_id
+                        ));
+                    }
+                    datum
+                }
+                _ => return Err("Payload is not in the Confluent wire format".to_owned()),
+            },
+            None => body,
+        };
+        avro_rs::from_avro_datum(&self.schema, &mut datum, None)
+            .map(|value| vec![value])
+            .map_err(|error| error.to_string())
+    }
+
+    /// Validates the fields of log events against the schema. Fields that aren't part of
+    /// the schema are ignored. Returns `None` if the event doesn't conform, and such events
+    /// are to be dropped.
+    pub fn validate(&self, mut event: Event) -> Option<Event> {
+        let result = match &event {
+            Event::Log(log) => self.validate_log(log),
+            Event::Metric(_) => Ok(()),
+        };
+        let error = match result {
+            Ok(()) => return Some(event),
+            Err(error) => error,
+        };
+
+        emit!(SchemaValidationFailed {
+            error: &error,
+            dropped: self.on_failure == OnValidationFailure::Drop,
+        });
+        match self.on_failure {
+            OnValidationFailure::Tag => {
+                event.as_mut_log().insert(self.error_field.as_str(), error);
+                Some(event)
+            }
+            OnValidationFailure::Drop => None,
+        }
+    }
+
+    fn validate_log(&self, log: &LogEvent) -> Result<(), String> {
+        let json = serde_json::to_value(log).map_err(|error| error.to_string())?;
+        AvroValue::from(json)
+            .resolve(&self.schema)
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+}
+
+/// Converts a decoded Avro value, with timestamps becoming timestamps and the other
+/// logical types their underlying representation.
+fn avro_to_value(value: AvroValue) -> Value {
+    match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(boolean) => Value::Boolean(boolean),
+        AvroValue::Int(int) | AvroValue::Date(int) | AvroValue::TimeMillis(int) => {
+            Value::Integer(int.into())
+        }
+        AvroValue::Long(long) | AvroValue::TimeMicros(long) => Value::Integer(long),
+        AvroValue::Float(float) => Value::Float(float.into()),
+        AvroValue::Double(double) => Value::Float(double),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => Value::Bytes(bytes.into()),
+        AvroValue::String(string) | AvroValue::Enum(_, string) => Value::from(string),
+        AvroValue::Uuid(uuid) => Value::from(uuid.to_string()),
+        AvroValue::Union(value) => avro_to_value(*value),
+        AvroValue::Array(values) => Value::Array(values.into_iter().map(avro_to_value).collect()),
+        AvroValue::Map(fields) => Value::Map(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, avro_to_value(value)))
+                .collect(),
+        ),
+        AvroValue::Record(fields) => Value::Map(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, avro_to_value(value)))
+                .collect(),
+        ),
+        AvroValue::TimestampMillis(millis) => Value::Timestamp(Utc.timestamp_millis(millis)),
+        AvroValue::TimestampMicros(micros) => Value::Timestamp(Utc.timestamp(
+            micros.div_euclid(1_000_000),
+            (micros.rem_euclid(1_000_000) * 1_000) as u32,
+        )),
+        other => Value::from(format!("{:?}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "request",
+        "fields": [
+            {"name": "method", "type": "string"},
+            {"name": "status", "type": "long"}
+        ]
+    }"#;
+
+    fn validator(on_failure: OnValidationFailure) -> SchemaValidator {
+        SchemaValidator::new(
+            Schema::parse_str(SCHEMA).unwrap(),
+            on_failure,
+            default_error_field(),
+        )
+    }
+
+    fn event(status: serde_json::Value) -> Event {
+        let mut event = Event::new_empty_log();
+        event.as_mut_log().insert("method", "GET");
+        event.as_mut_log().insert("status", status);
+        event.as_mut_log().insert("unknown", "ignored");
+        event
+    }
+
+    #[test]
+    fn passes_conforming_events() {
+        let event = event(serde_json::json!(200));
+        let validated = validator(OnValidationFailure::Drop).validate(event.clone());
+        assert_eq!(validated, Some(event));
+    }
+
+    #[test]
+    fn tags_nonconforming_events() {
+        let validated = validator(OnValidationFailure::Tag)
+            .validate(event(serde_json::json!("ok")))
+            .unwrap();
+        assert!(validated.as_log().contains("schema_error"));
+    }
+
+    #[test]
+    fn drops_nonconforming_events() {
+        let validated =
+            validator(OnValidationFailure::Drop).validate(event(serde_json::json!("ok")));
+        assert_eq!(validated, None);
+    }
+
+    #[test]
+    fn rejects_unsupported_confluent_schema_types() {
+        let body = serde_json::json!({"schema": "{}", "schemaType": "PROTOBUF"}).to_string();
+        assert!(confluent_schema(body.as_bytes()).is_err());
+
+        let body = serde_json::json!({ "schema": SCHEMA, "id": 7 }).to_string();
+        assert_eq!(
+            confluent_schema(body.as_bytes()).unwrap(),
+            (SCHEMA.to_owned(), Some(7))
+        );
+    }
+
+    fn record(status: i64) -> AvroValue {
+        AvroValue::Record(vec![
+            ("method".to_owned(), AvroValue::String("GET".to_owned())),
+            ("status".to_owned(), AvroValue::Long(status)),
+        ])
+    }
+
+    fn assert_decoded(events: Vec<Event>, statuses: &[i64]) {
+        assert_eq!(events.len(), statuses.len());
+        for (event, status) in events.iter().zip(statuses) {
+            assert_eq!(event.as_log()["method"], Value::from("GET"));
+            assert_eq!(event.as_log()["status"], Value::Integer(*status));
+        }
+    }
+
+    #[test]
+    fn decodes_avro_datums() {
+        let validator = validator(OnValidationFailure::Tag);
+        let datum = avro_rs::to_avro_datum(&validator.schema, record(200)).unwrap();
+        assert_decoded(validator.decode_avro(&datum).unwrap(), &[200]);
+    }
+
+    #[test]
+    fn decodes_avro_container_files() {
+        let validator = validator(OnValidationFailure::Tag);
+        let mut writer = avro_rs::Writer::new(&validator.schema, Vec::new());
+        writer.append(record(200)).unwrap();
+        writer.append(record(404)).unwrap();
+        let file = writer.into_inner().unwrap();
+        assert_decoded(validator.decode_avro(&file).unwrap(), &[200, 404]);
+    }
+
+    #[test]
+    fn decodes_avro_confluent_wire_format() {
+        let mut validator = validator(OnValidationFailure::Tag);
+        validator.schema_id = Some(7);
+        let datum = avro_rs::to_avro_datum(&validator.schema, record(200)).unwrap();
+
+        let mut payload = vec![CONFLUENT_MAGIC, 0, 0, 0, 7];
+        payload.extend_from_slice(&datum);
+        assert_decoded(validator.decode_avro(&payload).unwrap(), &[200]);
+
+        payload[4] = 8;
+        assert!(validator.decode_avro(&payload).is_err());
+        assert!(validator.decode_avro(&datum).is_err());
+    }
+}
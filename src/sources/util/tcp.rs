@@ -2,6 +2,7 @@ use crate::{
     config::Resource,
     internal_events::{ConnectionOpen, OpenGauge, TcpSocketConnectionError},
     shutdown::ShutdownSignal,
+    sources::util::ReconnectPolicy,
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
     Event, Pipeline,
@@ -10,7 +11,16 @@ use bytes::Bytes;
 use futures::{future::BoxFuture, stream, FutureExt, Sink, SinkExt, StreamExt, TryFutureExt};
 use listenfd::ListenFd;
 use serde::{de, Deserialize, Deserializer, Serialize};
-use std::{fmt, future::ready, io, mem::drop, net::SocketAddr, task::Poll, time::Duration};
+use std::{
+    fmt,
+    future::ready,
+    io,
+    mem::drop,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    task::Poll,
+    time::Duration,
+};
 use tokio::{
     net::{TcpListener, TcpStream},
     time::delay_for,
@@ -115,6 +125,9 @@ where
             .shared();
 
             let connection_gauge = OpenGauge::new();
+            // Accepting fails repeatedly while out of file descriptors, for
+            // example, so the listener is given time to recover.
+            let backoff = Arc::new(Mutex::new(ReconnectPolicy::default().backoff()));
 
             listener
                 .accept_stream()
@@ -125,15 +138,21 @@ where
                     let source = self.clone();
                     let out = out.clone();
                     let connection_gauge = connection_gauge.clone();
+                    let backoff = Arc::clone(&backoff);
 
                     async move {
                         let socket = match connection {
-                            Ok(socket) => socket,
+                            Ok(socket) => {
+                                backoff.lock().unwrap().reset();
+                                socket
+                            }
                             Err(error) => {
                                 error!(
                                     message = "Failed to accept socket.",
                                     %error
                                 );
+                                let delay = backoff.lock().unwrap().next_delay();
+                                delay_for(delay).await;
                                 return;
                             }
                         };
@@ -3,12 +3,15 @@ use crate::{
     event::Event,
     internal_events::{SocketMode, SocketReceiveError},
     shutdown::ShutdownSignal,
-    sources::Source,
+    sources::{
+        util::{validate_socket_file_mode, validate_socket_path},
+        Source,
+    },
     Pipeline,
 };
 use bytes::{Bytes, BytesMut};
 use futures::SinkExt;
-use std::path::PathBuf;
+use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf};
 use tokio::net::UnixDatagram;
 use tokio_util::codec::Decoder;
 use tracing::field;
@@ -16,25 +19,51 @@ use tracing::field;
 /// Returns a Source object corresponding to a Unix domain datagram
 /// socket.  Passing in different functions for build_event can allow
 /// for different source-specific logic (such as decoding syslog
-/// messages in the syslog source).
+/// messages in the syslog source). If `socket_file_mode` is set, the
+/// permissions of the socket file are set to it once it's bound, and a
+/// mode that isn't a valid set of permissions is a build error, as is a
+/// file other than a socket at `listen_path`.
 pub fn build_unix_datagram_source<D>(
     listen_path: PathBuf,
+    socket_file_mode: Option<u32>,
     max_length: usize,
     host_key: String,
     mut decoder: D,
     mut shutdown: ShutdownSignal,
     out: Pipeline,
-    build_event: impl Fn(&str, Option<Bytes>, &str) -> Option<Event> + Clone + Send + Sync + 'static,
-) -> Source
+    build_event: impl Fn(&str, Option<Bytes>, D::Item) -> Option<Event> + Clone + Send + Sync + 'static,
+) -> crate::Result<Source>
 where
-    D: Decoder<Item = String> + Clone + Send + 'static,
+    D: Decoder + Clone + Send + 'static,
+    D::Item: Send,
     D::Error: From<std::io::Error> + std::fmt::Debug + std::fmt::Display + Send,
 {
+    if let Some(mode) = socket_file_mode {
+        validate_socket_file_mode(mode)?;
+    }
+    validate_socket_path(&listen_path)?;
+
     let mut out = out.sink_map_err(|error| error!(message = "Error sending line.", %error));
 
-    Box::pin(async move {
-        let mut socket =
-            UnixDatagram::bind(&listen_path).expect("Failed to bind to datagram socket");
+    Ok(Box::pin(async move {
+        let mut socket = UnixDatagram::bind(&listen_path).map_err(|error| {
+            error!(
+                message = "Failed to bind to datagram socket.",
+                path = ?listen_path,
+                %error
+            )
+        })?;
+        if let Some(mode) = socket_file_mode {
+            fs::set_permissions(&listen_path, fs::Permissions::from_mode(mode)).map_err(
+                |error| {
+                    error!(
+                        message = "Failed to set permissions of datagram socket.",
+                        path = ?listen_path,
+                        %error
+                    )
+                },
+            )?;
+        }
         info!(message = "Listening.", path = ?listen_path, r#type = "unix_datagram");
 
         let mut buf = BytesMut::with_capacity(max_length);
@@ -57,8 +86,8 @@ where
                     let received_from: Option<Bytes> =
                         path.map(|p| p.to_string_lossy().into_owned().into());
 
-                    while let Ok(Some(line)) = decoder.decode_eof(&mut payload) {
-                        if let Some(event) = build_event(&host_key, received_from.clone(), &line) {
+                    while let Ok(Some(frame)) = decoder.decode_eof(&mut payload) {
+                        if let Some(event) = build_event(&host_key, received_from.clone(), frame) {
                             out.send(event).await?;
                         }
                     }
@@ -66,5 +95,5 @@ where
                 _ = &mut shutdown => return Ok(()),
             }
         }
-    })
+    }))
 }
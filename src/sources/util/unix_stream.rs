@@ -4,12 +4,12 @@ use crate::{
     event::Event,
     internal_events::{ConnectionOpen, OpenGauge, UnixSocketError},
     shutdown::ShutdownSignal,
-    sources::Source,
+    sources::{util::validate_socket_file_mode, Source},
     Pipeline,
 };
 use bytes::Bytes;
 use futures::{FutureExt, SinkExt, StreamExt};
-use std::{future::ready, path::PathBuf};
+use std::{fs, future::ready, os::unix::fs::PermissionsExt, path::PathBuf};
 use tokio::net::{UnixListener, UnixStream};
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing::field;
@@ -18,24 +18,43 @@ use tracing_futures::Instrument;
 /// Returns a Source object corresponding to a Unix domain stream
 /// socket.  Passing in different functions for build_event can allow
 /// for different source-specific logic (such as decoding syslog
-/// messages in the syslog source).
+/// messages in the syslog source). If `socket_file_mode` is set, the
+/// permissions of the socket file are set to it once it's bound, and a
+/// mode that isn't a valid set of permissions is a build error.
 pub fn build_unix_stream_source<D>(
     listen_path: PathBuf,
+    socket_file_mode: Option<u32>,
     decoder: D,
     host_key: String,
     shutdown: ShutdownSignal,
     out: Pipeline,
-    build_event: impl Fn(&str, Option<Bytes>, &str) -> Option<Event> + Clone + Send + Sync + 'static,
-) -> Source
+    build_event: impl Fn(&str, Option<Bytes>, D::Item) -> Option<Event> + Clone + Send + Sync + 'static,
+) -> crate::Result<Source>
 where
-    D: Decoder<Item = String> + Clone + Send + 'static,
+    D: Decoder + Clone + Send + 'static,
+    D::Item: Send,
     D::Error: From<std::io::Error> + std::fmt::Debug + std::fmt::Display,
 {
+    if let Some(mode) = socket_file_mode {
+        validate_socket_file_mode(mode)?;
+    }
+
     let out = out.sink_map_err(|error| error!(message = "Error sending line.", %error));
 
-    Box::pin(async move {
+    Ok(Box::pin(async move {
         let mut listener =
             UnixListener::bind(&listen_path).expect("Failed to bind to listener socket");
+        if let Some(mode) = socket_file_mode {
+            fs::set_permissions(&listen_path, fs::Permissions::from_mode(mode)).map_err(
+                |error| {
+                    error!(
+                        message = "Failed to set permissions of listener socket.",
+                        path = ?listen_path,
+                        %error
+                    )
+                },
+            )?;
+        }
         info!(message = "Listening.", path = ?listen_path, r#type = "unix");
 
         let connection_open = OpenGauge::new();
@@ -69,9 +88,9 @@ where
                 path.map(|p| p.to_string_lossy().into_owned().into());
 
             let stream = socket.allow_read_until(shutdown.clone().map(|_| ()));
-            let mut stream = FramedRead::new(stream, decoder.clone()).filter_map(move |line| {
-                ready(match line {
-                    Ok(line) => build_event(&host_key, received_from.clone(), &line).map(Ok),
+            let mut stream = FramedRead::new(stream, decoder.clone()).filter_map(move |frame| {
+                ready(match frame {
+                    Ok(frame) => build_event(&host_key, received_from.clone(), frame).map(Ok),
                     Err(error) => {
                         emit!(UnixSocketError {
                             error,
@@ -98,5 +117,5 @@ where
         }
 
         Ok(())
-    })
+    }))
 }
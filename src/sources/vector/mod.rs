@@ -3,6 +3,7 @@ use crate::{
     config::{DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig, SourceDescription},
     event::proto,
     internal_events::{VectorEventReceived, VectorProtoDecodeError},
+    proto::VectorVersion,
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsConfig},
@@ -14,9 +15,14 @@ use prost::Message;
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::LengthDelimitedCodec;
 
+mod v2;
+
 #[derive(Deserialize, Serialize, Debug, Clone, Setters)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
+    #[serde(default)]
+    #[set = "pub"]
+    version: VectorVersion,
     address: SocketListenAddr,
     keepalive: Option<TcpKeepaliveConfig>,
     #[serde(default = "default_shutdown_timeout_secs")]
@@ -33,6 +39,7 @@ fn default_shutdown_timeout_secs() -> u64 {
 impl VectorConfig {
     pub fn from_address(address: SocketListenAddr) -> Self {
         Self {
+            version: VectorVersion::V1,
             address,
             keepalive: None,
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
@@ -65,8 +72,17 @@ impl SourceConfig for VectorConfig {
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
-        let vector = VectorSource;
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        if self.version == VectorVersion::V2 {
+            return match self.address {
+                SocketListenAddr::SocketAddr(address) => Ok(v2::run(address, tls, shutdown, out)),
+                SocketListenAddr::SystemdFd(_) => {
+                    Err("Version 2 doesn't support systemd sockets.".into())
+                }
+            };
+        }
+
+        let vector = VectorSource;
         vector.run(
             self.address,
             self.keepalive,
@@ -128,6 +144,7 @@ mod test {
             metric::{MetricKind, MetricValue},
             Metric,
         },
+        proto::VectorVersion,
         sinks::vector::VectorSinkConfig,
         test_util::{collect_ready, next_addr, trace_init, wait_for_tcp},
         tls::{TlsConfig, TlsOptions},
@@ -237,6 +254,54 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn it_works_with_vector_sink_v2() {
+        let addr = next_addr();
+        stream_test(
+            addr,
+            {
+                let mut config = VectorConfig::from_address(addr.into());
+                config.set_version(VectorVersion::V2);
+                config
+            },
+            {
+                let mut config =
+                    VectorSinkConfig::from_address(format!("localhost:{}", addr.port()));
+                config.set_version(VectorVersion::V2);
+                config
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn it_works_with_vector_sink_v2_tls() {
+        let addr = next_addr();
+        stream_test(
+            addr,
+            {
+                let mut config = VectorConfig::from_address(addr.into());
+                config.set_version(VectorVersion::V2);
+                config.set_tls(Some(TlsConfig::test_config()));
+                config
+            },
+            {
+                let mut config =
+                    VectorSinkConfig::from_address(format!("localhost:{}", addr.port()));
+                config.set_version(VectorVersion::V2);
+                config.set_tls(Some(TlsConfig {
+                    enabled: Some(true),
+                    options: TlsOptions {
+                        verify_certificate: Some(false),
+                        ..Default::default()
+                    },
+                }));
+                config
+            },
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn it_closes_stream_on_garbage_data() {
         trace_init();
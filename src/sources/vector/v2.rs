@@ -0,0 +1,76 @@
+use crate::{
+    event::Event, internal_events::VectorEventReceived, proto::vector as proto,
+    shutdown::ShutdownSignal, sources::Source, tls::MaybeTlsSettings, Pipeline,
+};
+use futures::{stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use prost::Message;
+use std::net::SocketAddr;
+use tonic::{transport::Server, Request, Response, Status};
+
+#[derive(Debug, Clone)]
+struct Service {
+    pipeline: Pipeline,
+}
+
+#[tonic::async_trait]
+impl proto::vector_server::Vector for Service {
+    async fn push_events(
+        &self,
+        request: Request<proto::PushEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let events = request.into_inner().events.into_iter().map(|event| {
+            emit!(VectorEventReceived {
+                byte_size: event.encoded_len()
+            });
+            Event::from(event)
+        });
+
+        // The response is the acknowledgement of the events, so it's only sent once they are
+        // accepted by the pipeline.
+        self.pipeline
+            .clone()
+            .send_all(&mut stream::iter(events).map(Ok))
+            .map_ok(|_| Response::new(proto::PushEventsResponse {}))
+            .map_err(|error| {
+                error!(message = "Failed to forward events, downstream is closed.", %error);
+                Status::unavailable("Downstream is closed.")
+            })
+            .await
+    }
+
+    async fn health_check(
+        &self,
+        _request: Request<proto::HealthCheckRequest>,
+    ) -> Result<Response<proto::HealthCheckResponse>, Status> {
+        Ok(Response::new(proto::HealthCheckResponse {
+            status: proto::ServingStatus::Serving.into(),
+        }))
+    }
+}
+
+pub(super) fn run(
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> Source {
+    let service = proto::vector_server::VectorServer::new(Service { pipeline: out });
+
+    Box::pin(async move {
+        let listener = tls.bind(&address).await.map_err(|error| {
+            error!(message = "Failed to bind to listener socket.", %error);
+        })?;
+        info!(message = "Listening.", address = %address);
+
+        Server::builder()
+            .add_service(service)
+            .serve_with_incoming_shutdown(listener.accept_stream(), shutdown.clone().map(|_| ()))
+            .await
+            .map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            })?;
+        // We need to drop the last copy of ShutdownSignalToken only after server has shut down.
+        drop(shutdown);
+        Ok(())
+    })
+}
@@ -43,6 +43,15 @@ pub enum TemplateRenderingError {
     MissingKeys { missing_keys: Vec<String> },
 }
 
+/// Templates are equal when they're parsed from the same source.
+impl PartialEq for Template {
+    fn eq(&self, other: &Self) -> bool {
+        self.src == other.src
+    }
+}
+
+impl Eq for Template {}
+
 impl TryFrom<&str> for Template {
     type Error = TemplateParseError;
 
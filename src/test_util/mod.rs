@@ -268,6 +268,18 @@ pub fn lines_from_gzip_file<P: AsRef<Path>>(path: P) -> Vec<String> {
     output.lines().map(|s| s.to_owned()).collect()
 }
 
+#[cfg(feature = "zstd")]
+pub fn lines_from_zstd_file<P: AsRef<Path>>(path: P) -> Vec<String> {
+    trace!(message = "Reading zstd file.", path = %path.as_ref().display());
+    let file = File::open(path).unwrap();
+    let output = zstd::decode_all(file).unwrap();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|s| s.to_owned())
+        .collect()
+}
+
 pub fn runtime() -> runtime::Runtime {
     runtime::Builder::new()
         .threaded_scheduler()
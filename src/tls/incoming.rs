@@ -140,6 +140,13 @@ impl<S> MaybeTlsIncomingStream<S> {
     }
 }
 
+#[cfg(any(feature = "sources-opentelemetry", feature = "sources-vector"))]
+impl tonic::transport::server::Connected for MaybeTlsIncomingStream<TcpStream> {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        Some(self.peer_addr())
+    }
+}
+
 impl MaybeTlsIncomingStream<TcpStream> {
     pub(super) fn new(
         stream: TcpStream,
@@ -45,6 +45,8 @@ pub enum TlsError {
     TlsIdentityError { source: ErrorStack },
     #[snafu(display("Could not export identity to DER: {}", source))]
     DerExportError { source: ErrorStack },
+    #[snafu(display("Could not export certificate to PEM: {}", source))]
+    PemExportError { source: ErrorStack },
     #[snafu(display("Identity certificate is missing a key"))]
     MissingKey,
     #[snafu(display("Certificate file contains no certificates"))]
@@ -1,7 +1,8 @@
 use super::{
     AddCertToStore, AddExtraChainCert, CaStackPush, DerExportError, FileOpenFailed, FileReadFailed,
-    MaybeTls, NewCaStack, NewStoreBuilder, ParsePkcs12, Pkcs12Error, PrivateKeyParseError, Result,
-    SetCertificate, SetPrivateKey, SetVerifyCert, TlsError, TlsIdentityError, X509ParseError,
+    MaybeTls, NewCaStack, NewStoreBuilder, ParsePkcs12, PemExportError, Pkcs12Error,
+    PrivateKeyParseError, Result, SetCertificate, SetPrivateKey, SetVerifyCert, TlsError,
+    TlsIdentityError, X509ParseError,
 };
 use openssl::{
     pkcs12::{ParsedPkcs12, Pkcs12},
@@ -182,6 +183,24 @@ impl TlsSettings {
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
     }
+
+    /// The identity as a DER encoded PKCS#12 archive, along with its
+    /// password, for clients that don't use openssl directly.
+    pub fn identity_pkcs12(&self) -> Option<(&[u8], &str)> {
+        self.identity
+            .as_ref()
+            .map(|identity| (identity.0.as_slice(), identity.1.as_str()))
+    }
+
+    /// The certificate authorities, concatenated in PEM format.
+    pub fn authorities_pem(&self) -> Result<String> {
+        let mut pem = String::new();
+        for authority in &self.authorities {
+            let authority = authority.to_pem().context(PemExportError)?;
+            pem.push_str(&String::from_utf8_lossy(&authority));
+        }
+        Ok(pem)
+    }
 }
 
 impl TlsOptions {
@@ -5,7 +5,9 @@ use super::{
 use crate::config;
 use indoc::indoc;
 use url::Url;
-use vector_api_client::{connect_subscription_client, gql::HealthQueryExt, Client};
+use vector_api_client::{
+    connect_subscription_client_with_options, gql::HealthQueryExt, Client, ConnectOptions,
+};
 
 /// CLI command func for displaying Vector components, and communicating with a local/remote
 /// Vector API server via HTTP/WebSockets
@@ -26,7 +28,12 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
     });
 
     // Create a new API client for connecting to the local/remote Vector instance
-    let client = Client::new(url.clone());
+    let mut client = Client::new(url.clone());
+    let mut connect_options = ConnectOptions::default();
+    if let Some(token) = &opts.token {
+        client = client.with_token(token);
+        connect_options = connect_options.with_token(token);
+    }
 
     // Check that the GraphQL server is reachable
     match client.health_query().await {
@@ -69,13 +76,16 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
         })
         .expect("Couldn't build WebSocket URL. Please report.");
 
-    let subscription_client = match connect_subscription_client(ws_url).await {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Couldn't connect to Vector API via WebSockets: {:?}", e);
-            return exitcode::UNAVAILABLE;
-        }
-    };
+    // Dropped connections are reconnected, so the dashboard survives a restart of the
+    // Vector instance
+    let subscription_client =
+        match connect_subscription_client_with_options(ws_url, connect_options).await {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Couldn't connect to Vector API via WebSockets: {:?}", e);
+                return exitcode::UNAVAILABLE;
+            }
+        };
 
     // Subscribe to updated metrics
     metrics::subscribe(subscription_client, tx.clone(), opts.interval as i64);
@@ -20,6 +20,10 @@ pub struct Opts {
     #[structopt(short, long)]
     url: Option<Url>,
 
+    /// Token sent as a bearer token to authenticate with the Vector GraphQL API server
+    #[structopt(long, env = "VECTOR_API_TOKEN")]
+    token: Option<String>,
+
     /// Humanize metrics, using numeric suffixes - e.g. 1,100 = 1.10 k, 1,000,000 = 1.00 M
     #[structopt(short, long)]
     human_metrics: bool,
@@ -1,14 +1,17 @@
 use super::{
     fanout::{self, Fanout},
+    health,
     task::{Task, TaskOutput},
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
     buffers,
-    config::{DataType, SinkContext},
+    config::{DataType, GlobalOptions, SinkConfig, SinkContext},
+    enrichment_tables::{EnrichmentTables, StagedTables},
     event::Event,
     internal_events::{EventIn, EventOut, EventProcessed, EventZeroIn},
     shutdown::SourceShutdownCoordinator,
+    sinks::util::request_limits,
     stream::VecStreamExt,
     transforms::Transform,
     Pipeline,
@@ -20,7 +23,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
-use tokio::time::{timeout, Duration};
+use tokio::time::{interval_at, timeout, Duration, Instant};
 
 pub struct Pieces {
     pub inputs: HashMap<String, (buffers::BufferInputCloner, Vec<String>)>,
@@ -30,13 +33,27 @@ pub struct Pieces {
     pub healthchecks: HashMap<String, Task>,
     pub shutdown_coordinator: SourceShutdownCoordinator,
     pub detach_triggers: HashMap<String, Trigger>,
+    /// Committed once the pieces are started.
+    pub enrichment_tables: StagedTables,
 }
 
 /// Builds only the new pieces, and doesn't check their topology.
 pub async fn build_pieces(
+    config: &super::Config,
+    diff: &ConfigDiff,
+    buffers: HashMap<String, BuiltBuffer>,
+) -> Result<Pieces, Vec<String>> {
+    rebuild_pieces(config, diff, buffers, &EnrichmentTables::default()).await
+}
+
+/// Like `build_pieces`, but stages the enrichment tables of a running
+/// topology, so that the contents of its tables are replaced once the pieces
+/// are started.
+pub(super) async fn rebuild_pieces(
     config: &super::Config,
     diff: &ConfigDiff,
     mut buffers: HashMap<String, BuiltBuffer>,
+    enrichment_tables: &EnrichmentTables,
 ) -> Result<Pieces, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut outputs = HashMap::new();
@@ -48,6 +65,9 @@ pub async fn build_pieces(
 
     let mut errors = vec![];
 
+    // Transforms look their enrichment tables up when they're built, so tables are loaded first.
+    let enrichment_tables = enrichment_tables.stage(&config.enrichment_tables)?;
+
     // Build sources
     for (name, source) in config
         .sources
@@ -57,12 +77,19 @@ pub async fn build_pieces(
         let (tx, rx) = tokio::sync::mpsc::channel(1000);
         let pipeline = Pipeline::from_sender(tx, vec![]);
 
-        let typetag = source.source_type();
+        let typetag = source.inner.source_type();
+        let log_schema = source.log_schema.clone();
+        // Sources add their metadata keys following the policies of the source.
+        let globals = GlobalOptions {
+            metadata_keys: source.metadata_keys.or(&config.global.metadata_keys),
+            ..config.global.clone()
+        };
 
         let (shutdown_signal, force_shutdown_tripwire) = shutdown_coordinator.register_source(name);
 
         let server = match source
-            .build(&name, &config.global, shutdown_signal, pipeline)
+            .inner
+            .build(&name, &globals, shutdown_signal, pipeline)
             .await
         {
             Err(error) => {
@@ -73,7 +100,10 @@ pub async fn build_pieces(
         };
 
         let (output, control) = Fanout::new();
-        let pump = rx.map(Ok).forward(output).map_ok(|_| TaskOutput::Source);
+        let pump = rx
+            .map(move |event| Ok(log_schema.apply(event)))
+            .forward(output)
+            .map_ok(|_| TaskOutput::Source);
         let pump = Task::new(name, typetag, pump);
 
         // The force_shutdown_tripwire is a Future that when it resolves means that this source
@@ -109,7 +139,10 @@ pub async fn build_pieces(
         let typetag = transform.inner.transform_type();
 
         let input_type = transform.inner.input_type();
-        let transform = match transform.inner.build(&config.global).await {
+        let transform = match enrichment_tables
+            .building(transform.inner.build(&config.global))
+            .await
+        {
             Err(error) => {
                 errors.push(format!("Transform \"{}\": {}", name, error));
                 continue;
@@ -118,8 +151,9 @@ pub async fn build_pieces(
         };
 
         let (input_tx, input_rx) = futures::channel::mpsc::channel(100);
-        let input_tx = buffers::BufferInputCloner::Memory(input_tx, buffers::WhenFull::Block);
-        let input_rx = crate::utilization::wrap(input_rx);
+        let input_tx =
+            buffers::BufferInputCloner::Memory(input_tx, buffers::WhenFull::Block, None, true);
+        let input_rx = crate::utilization::wrap(name.clone(), input_rx);
 
         let (output, control) = Fanout::new();
 
@@ -177,7 +211,9 @@ pub async fn build_pieces(
         let (tx, rx, acker) = if let Some(buffer) = buffers.remove(name) {
             buffer
         } else {
-            let buffer = sink.buffer.build(&config.global.data_dir, &name);
+            let buffer = sink
+                .buffer
+                .build(&config.global.data_dir, &name, sink.acknowledgements);
             match buffer {
                 Err(error) => {
                     errors.push(format!("Sink \"{}\": {}", name, error));
@@ -188,12 +224,21 @@ pub async fn build_pieces(
         };
 
         let cx = SinkContext {
+            name: name.clone(),
             acker: acker.clone(),
             healthcheck,
             globals: config.global.clone(),
         };
 
-        let (sink, healthcheck) = match sink.inner.build(cx).await {
+        let recheck = if enable_healthcheck && config.healthchecks.interval_secs > 0 {
+            let period = Duration::from_secs(config.healthchecks.interval_secs);
+            let sink = dyn_clone::clone_box(&*sink.inner);
+            recheck_healthcheck(name.clone(), sink, cx.clone(), period).boxed()
+        } else {
+            future::pending().boxed()
+        };
+
+        let (sink, healthcheck) = match request_limits::building(name, sink.inner.build(cx)).await {
             Err(error) => {
                 errors.push(format!("Sink \"{}\": {}", name, error));
                 continue;
@@ -203,6 +248,7 @@ pub async fn build_pieces(
 
         let (trigger, tripwire) = Tripwire::new();
 
+        let component_name = name.clone();
         let sink = async move {
             // Why is this Arc<Mutex<Option<_>>> needed you ask.
             // In case when this function build_pieces errors
@@ -216,16 +262,19 @@ pub async fn build_pieces(
                 .take()
                 .expect("Task started but input has been taken.");
 
-            let mut rx = Box::pin(crate::utilization::wrap(rx));
+            let mut rx = Box::pin(crate::utilization::wrap(component_name, rx));
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|event| ready(filter_event_type(event, input_type)))
-                    .inspect(|_| emit!(EventIn))
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+            let result = tokio::select! {
+                result = sink.run(
+                    rx.by_ref()
+                        .filter(|event| ready(filter_event_type(event, input_type)))
+                        .inspect(|_| emit!(EventIn))
+                        .take_until_if(tripwire),
+                ) => result,
+                _ = recheck => unreachable!("Healthcheck re-runs never finish."),
+            };
+
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx, acker)
             })
@@ -240,9 +289,11 @@ pub async fn build_pieces(
                     .map(|result| match result {
                         Ok(Ok(_)) => {
                             info!("Healthcheck: Passed.");
+                            health::record_healthcheck(&component_name, Ok(()));
                             Ok(TaskOutput::Healthcheck)
                         }
                         Ok(Err(error)) => {
+                            health::record_healthcheck(&component_name, Err(error.to_string()));
                             error!(
                                 msg = "Healthcheck: Failed Reason.",
                                 %error,
@@ -253,6 +304,10 @@ pub async fn build_pieces(
                             Err(())
                         }
                         Err(_) => {
+                            health::record_healthcheck(
+                                &component_name,
+                                Err("Healthcheck timed out.".to_string()),
+                            );
                             error!(
                                 msg = "Healthcheck: timeout.",
                                 component_kind = "sink",
@@ -265,6 +320,7 @@ pub async fn build_pieces(
                     .await
             } else {
                 info!("Healthcheck: Disabled.");
+                health::record_disabled(&component_name);
                 Ok(TaskOutput::Healthcheck)
             }
         };
@@ -285,6 +341,7 @@ pub async fn build_pieces(
             healthchecks,
             shutdown_coordinator,
             detach_triggers,
+            enrichment_tables,
         };
 
         Ok(pieces)
@@ -293,6 +350,33 @@ pub async fn build_pieces(
     }
 }
 
+/// Sink healthchecks can only be run once, so this rebuilds the sink every
+/// `period` to get a fresh healthcheck, and records its result.
+async fn recheck_healthcheck(
+    name: String,
+    sink: Box<dyn SinkConfig>,
+    cx: SinkContext,
+    period: Duration,
+) {
+    let mut interval = interval_at(Instant::now() + period, period);
+    loop {
+        interval.tick().await;
+
+        let result = match sink.build(cx.clone()).await {
+            Ok((_, healthcheck)) => match timeout(Duration::from_secs(10), healthcheck).await {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(error)) => Err(error.to_string()),
+                Err(_) => Err("Healthcheck timed out.".to_string()),
+            },
+            Err(error) => Err(error.to_string()),
+        };
+        if let Err(error) = &result {
+            warn!(message = "Healthcheck: Failed Reason.", %error, component_name = %name);
+        }
+        health::record_healthcheck(&name, result);
+    }
+}
+
 fn filter_event_type(event: &Event, data_type: DataType) -> bool {
     match data_type {
         DataType::Any => true,
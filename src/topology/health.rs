@@ -0,0 +1,129 @@
+//! Tracks the most recent health of running components.
+//!
+//! Sinks record the result of each of their healthchecks here, and transforms and
+//! sinks record their utilization, which is used to determine whether they're
+//! currently backpressured. The API reads from this registry to report health.
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use std::{collections::HashMap, sync::RwLock};
+
+/// Utilization at, or above, which a component is considered backpressured, as
+/// it's then hardly ever waiting for input.
+const BACKPRESSURE_UTILIZATION: f64 = 0.95;
+
+const INVARIANT: &str = "Couldn't acquire lock on component health. Please report this.";
+
+lazy_static! {
+    static ref HEALTH: RwLock<HashMap<String, ComponentHealth>> = RwLock::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// No healthcheck has completed yet, or the component doesn't have one.
+    Unknown,
+    Healthy,
+    Unhealthy,
+    /// Healthchecks are disabled for the component.
+    Disabled,
+}
+
+impl Default for HealthStatus {
+    fn default() -> Self {
+        HealthStatus::Unknown
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    pub last_error: Option<String>,
+    pub last_checked: Option<DateTime<Utc>>,
+    pub utilization: Option<f64>,
+}
+
+impl ComponentHealth {
+    pub fn is_backpressured(&self) -> bool {
+        self.utilization
+            .map_or(false, |utilization| utilization >= BACKPRESSURE_UTILIZATION)
+    }
+}
+
+fn update(name: &str, f: impl FnOnce(&mut ComponentHealth)) {
+    let mut health = HEALTH.write().expect(INVARIANT);
+    f(health.entry(name.to_owned()).or_default());
+}
+
+/// Records the outcome of a healthcheck of the component `name`.
+pub fn record_healthcheck(name: &str, result: Result<(), String>) {
+    update(name, |health| {
+        match result {
+            Ok(()) => health.status = HealthStatus::Healthy,
+            Err(error) => {
+                health.status = HealthStatus::Unhealthy;
+                health.last_error = Some(error);
+            }
+        }
+        health.last_checked = Some(Utc::now());
+    })
+}
+
+/// Records that healthchecks are disabled for the component `name`.
+pub fn record_disabled(name: &str) {
+    update(name, |health| health.status = HealthStatus::Disabled)
+}
+
+/// Records the latest utilization average of the component `name`.
+pub fn record_utilization(name: &str, utilization: f64) {
+    update(name, |health| health.utilization = Some(utilization))
+}
+
+/// Forgets a component that is no longer running.
+pub fn remove(name: &str) {
+    HEALTH.write().expect(INVARIANT).remove(name);
+}
+
+/// Returns the health of the component `name`, which is `Unknown` for components
+/// that haven't reported anything yet.
+pub fn get(name: &str) -> ComponentHealth {
+    HEALTH
+        .read()
+        .expect(INVARIANT)
+        .get(name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn healthcheck_keeps_last_error() {
+        let name = "health_test_keeps_last_error";
+
+        record_healthcheck(name, Err("connection refused".into()));
+        record_healthcheck(name, Ok(()));
+
+        let health = get(name);
+        assert_eq!(health.status, HealthStatus::Healthy);
+        assert_eq!(health.last_error.as_deref(), Some("connection refused"));
+        assert!(health.last_checked.is_some());
+
+        remove(name);
+        assert_eq!(get(name).status, HealthStatus::Unknown);
+    }
+
+    #[test]
+    fn backpressure_from_utilization() {
+        let name = "health_test_backpressure";
+
+        assert!(!get(name).is_backpressured());
+        record_utilization(name, 0.5);
+        assert!(!get(name).is_backpressured());
+        record_utilization(name, 0.99);
+        assert!(get(name).is_backpressured());
+
+        remove(name);
+    }
+}
@@ -8,11 +8,13 @@
 
 pub mod builder;
 mod fanout;
+pub mod health;
 mod task;
 
 use crate::{
     buffers,
     config::{Config, ConfigDiff, HealthcheckOptions, Resource},
+    enrichment_tables::EnrichmentTables,
     event::Event,
     shutdown::SourceShutdownCoordinator,
     topology::{
@@ -51,6 +53,7 @@ pub struct RunningTopology {
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<String, DisabledTrigger>,
     config: Config,
+    enrichment_tables: EnrichmentTables,
     abort_tx: mpsc::UnboundedSender<()>,
 }
 
@@ -65,6 +68,7 @@ pub async fn start_validated(
         inputs: HashMap::new(),
         outputs: HashMap::new(),
         config,
+        enrichment_tables: pieces.enrichment_tables.registry().clone(),
         shutdown_coordinator: SourceShutdownCoordinator::default(),
         detach_triggers: HashMap::new(),
         source_tasks: HashMap::new(),
@@ -78,6 +82,7 @@ pub async fn start_validated(
     {
         return None;
     }
+    pieces.enrichment_tables.commit();
     running_topology.connect_diff(&diff, &mut pieces).await;
     running_topology.spawn_diff(&diff, pieces);
 
@@ -89,7 +94,11 @@ pub async fn build_or_log_errors(
     diff: &ConfigDiff,
     buffers: HashMap<String, BuiltBuffer>,
 ) -> Option<Pieces> {
-    match builder::build_pieces(config, diff, buffers).await {
+    log_build_errors(builder::build_pieces(config, diff, buffers).await)
+}
+
+fn log_build_errors(pieces: Result<Pieces, Vec<String>>) -> Option<Pieces> {
+    match pieces {
         Err(errors) => {
             for error in errors {
                 error!(message = "Configuration error.", %error);
@@ -229,12 +238,15 @@ impl RunningTopology {
         }
 
         // Now let's actually build the new pieces.
-        if let Some(mut new_pieces) = build_or_log_errors(&new_config, &diff, buffers.clone()).await
-        {
+        let new_pieces =
+            builder::rebuild_pieces(&new_config, &diff, buffers.clone(), &self.enrichment_tables)
+                .await;
+        if let Some(mut new_pieces) = log_build_errors(new_pieces) {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
                 .await
             {
+                new_pieces.enrichment_tables.commit();
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
                 self.config = new_config;
@@ -245,8 +257,12 @@ impl RunningTopology {
 
         // We need to rebuild the removed.
         info!("Rebuilding old configuration.");
+        // The tables staged for the old configuration aren't committed, so
+        // the running components keep the contents they had before the reload.
         let diff = diff.flip();
-        if let Some(mut new_pieces) = build_or_log_errors(&self.config, &diff, buffers).await {
+        let new_pieces =
+            builder::rebuild_pieces(&self.config, &diff, buffers, &self.enrichment_tables).await;
+        if let Some(mut new_pieces) = log_build_errors(new_pieces) {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
                 .await
@@ -362,6 +378,7 @@ impl RunningTopology {
 
             self.remove_inputs(&name);
             self.remove_outputs(&name);
+            health::remove(&name);
         }
 
         // Sinks
@@ -378,7 +395,7 @@ impl RunningTopology {
         let add_source = diff
             .sources
             .changed_and_added()
-            .map(|name| (name, new_config.sources[name].resources()));
+            .map(|name| (name, new_config.sources[name].inner.resources()));
         let add_sink = diff
             .sinks
             .changed_and_added()
@@ -405,7 +422,10 @@ impl RunningTopology {
             .sinks
             .to_change
             .iter()
-            .filter(|&name| self.config.sinks[name].buffer == new_config.sinks[name].buffer)
+            .filter(|&name| {
+                let (old, new) = (&self.config.sinks[name], &new_config.sinks[name]);
+                old.buffer == new.buffer && old.acknowledgements == new.acknowledgements
+            })
             .cloned()
             .collect::<HashSet<_>>();
 
@@ -438,6 +458,7 @@ impl RunningTopology {
 
         // Cleanup removed
         for name in &diff.sinks.to_remove {
+            health::remove(&name);
             let previous = self.tasks.remove(name).unwrap();
             if wait_for_sinks.contains(name) {
                 debug!(message = "Waiting for sink to shutdown.", %name);
@@ -546,6 +567,7 @@ impl RunningTopology {
             component_name = %task.name(),
             component_type = %task.typetag(),
         );
+        let task = track_allocations(task, "sink", name);
         let task = handle_errors(task, self.abort_tx.clone()).instrument(span);
         let spawned = tokio::spawn(task);
         if let Some(previous) = self.tasks.insert(name.to_string(), spawned) {
@@ -561,6 +583,7 @@ impl RunningTopology {
             component_name = %task.name(),
             component_type = %task.typetag(),
         );
+        let task = track_allocations(task, "transform", name);
         let task = handle_errors(task, self.abort_tx.clone()).instrument(span);
         let spawned = tokio::spawn(task);
         if let Some(previous) = self.tasks.insert(name.to_string(), spawned) {
@@ -576,6 +599,7 @@ impl RunningTopology {
             component_name = %task.name(),
             component_type = %task.typetag(),
         );
+        let task = track_allocations(task, "source", name);
         let task = handle_errors(task, self.abort_tx.clone()).instrument(span.clone());
         let spawned = tokio::spawn(task);
         if let Some(previous) = self.tasks.insert(name.to_string(), spawned) {
@@ -586,6 +610,7 @@ impl RunningTopology {
             .takeover_source(name, &mut new_pieces.shutdown_coordinator);
 
         let source_task = new_pieces.source_tasks.remove(name).unwrap();
+        let source_task = track_allocations(source_task, "source", name);
         let source_task = handle_errors(source_task, self.abort_tx.clone()).instrument(span);
         self.source_tasks
             .insert(name.to_string(), tokio::spawn(source_task));
@@ -726,6 +751,25 @@ impl RunningTopology {
     }
 }
 
+/// Attributes the allocations made while polling a component's task to that
+/// component, if allocation tracing is enabled.
+fn track_allocations<F: Future>(
+    task: F,
+    component_kind: &'static str,
+    component_name: &str,
+) -> impl Future<Output = F::Output> {
+    #[cfg(feature = "allocation-tracing")]
+    {
+        let group = crate::allocations::register(component_kind, component_name);
+        crate::allocations::Tracked::new(task, group)
+    }
+    #[cfg(not(feature = "allocation-tracing"))]
+    {
+        let _ = (component_kind, component_name);
+        task
+    }
+}
+
 async fn handle_errors(
     task: impl Future<Output = Result<TaskOutput, ()>>,
     abort_tx: mpsc::UnboundedSender<()>,
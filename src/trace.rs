@@ -45,7 +45,8 @@ pub fn init(color: bool, json: bool, levels: &str) {
     let metrics_layer_enabled = metrics_layer_enabled();
 
     let subscriber = tracing_subscriber::registry::Registry::default()
-        .with(tracing_subscriber::filter::EnvFilter::from(levels));
+        .with(tracing_subscriber::filter::EnvFilter::from(levels))
+        .with(crate::profiling::ProfilingLayer);
 
     // dev note: we attempted to refactor to reduce duplication but it was starting to seem like
     // the refactored code would be introducing more complexity than it was worth to remove this
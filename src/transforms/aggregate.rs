@@ -0,0 +1,299 @@
+use crate::{
+    config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
+    event::{
+        metric::{MetricData, MetricSeries},
+        Event, Metric,
+    },
+    internal_events::{AggregateEventRecorded, AggregateFlushed},
+    transforms::{TaskTransform, Transform},
+};
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, mem, pin::Pin, time::Duration};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AggregateConfig {
+    /// The window over which metrics are aggregated before being flushed.
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_interval_ms() -> u64 {
+    10 * 1000
+}
+
+impl Default for AggregateConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: default_interval_ms(),
+        }
+    }
+}
+
+inventory::submit! {
+    TransformDescription::new::<AggregateConfig>("aggregate")
+}
+
+impl_generate_config_from_default!(AggregateConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aggregate")]
+impl TransformConfig for AggregateConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        Ok(Transform::task(Aggregate::new(self)))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "aggregate"
+    }
+}
+
+#[derive(Debug)]
+pub struct Aggregate {
+    interval: Duration,
+    map: HashMap<MetricSeries, MetricData>,
+}
+
+impl Aggregate {
+    pub fn new(config: &AggregateConfig) -> Self {
+        Self {
+            interval: Duration::from_millis(config.interval_ms),
+            map: HashMap::new(),
+        }
+    }
+
+    /// Merges an incremental metric into the one recorded for its series,
+    /// and replaces the recorded one with an absolute metric, or with one
+    /// whose type differs.
+    fn record(&mut self, event: Event) {
+        let Metric { series, data } = event.into_metric();
+
+        match self.map.get_mut(&series) {
+            Some(existing)
+                if data.kind.is_incremental()
+                    && existing.kind == data.kind
+                    && mem::discriminant(&existing.value) == mem::discriminant(&data.value) =>
+            {
+                existing.add(&data);
+                existing.timestamp = data.timestamp.or(existing.timestamp);
+            }
+            _ => {
+                self.map.insert(series, data);
+            }
+        }
+
+        emit!(AggregateEventRecorded);
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let count = self.map.len();
+        output.extend(
+            self.map
+                .drain()
+                .map(|(series, data)| Event::Metric(Metric { series, data })),
+        );
+
+        emit!(AggregateFlushed { count });
+    }
+}
+
+impl TaskTransform for Aggregate {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(me.interval);
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.next() => {
+                      me.flush_into(&mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.record(event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{Bucket, MetricKind, MetricValue};
+    use shared::btreemap;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AggregateConfig>();
+    }
+
+    fn make_metric(name: &str, kind: MetricKind, value: MetricValue) -> Event {
+        Event::Metric(
+            Metric::new(name, kind, value).with_tags(Some(btreemap! { "host" => "local" })),
+        )
+    }
+
+    fn flush(agg: &mut Aggregate) -> Vec<Metric> {
+        let mut output = Vec::new();
+        agg.flush_into(&mut output);
+        let mut metrics = output
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        metrics.sort_by(|a, b| a.name().cmp(b.name()));
+        metrics
+    }
+
+    #[test]
+    fn merges_incremental_metrics() {
+        let mut agg = Aggregate::new(&AggregateConfig::default());
+
+        for value in &[1.0, 2.0, 3.0] {
+            agg.record(make_metric(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: *value },
+            ));
+        }
+        for count in &[1, 4] {
+            agg.record(make_metric(
+                "latency",
+                MetricKind::Incremental,
+                MetricValue::AggregatedHistogram {
+                    buckets: vec![
+                        Bucket {
+                            upper_limit: 1.0,
+                            count: *count,
+                        },
+                        Bucket {
+                            upper_limit: 2.0,
+                            count: 0,
+                        },
+                    ],
+                    count: *count,
+                    sum: 0.5 * *count as f64,
+                },
+            ));
+        }
+
+        let metrics = flush(&mut agg);
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(
+            metrics[0].data.value,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 5,
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 0,
+                    },
+                ],
+                count: 5,
+                sum: 2.5,
+            }
+        );
+        assert_eq!(metrics[1].data.value, MetricValue::Counter { value: 6.0 });
+
+        // Each flush starts a new window.
+        assert!(flush(&mut agg).is_empty());
+    }
+
+    #[test]
+    fn keeps_last_absolute_value() {
+        let mut agg = Aggregate::new(&AggregateConfig::default());
+
+        for value in &[5.0, 3.0] {
+            agg.record(make_metric(
+                "memory",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: *value },
+            ));
+        }
+        // A metric of another type replaces the recorded one.
+        agg.record(make_metric(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        agg.record(make_metric(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Gauge { value: 2.0 },
+        ));
+
+        let metrics = flush(&mut agg);
+        assert_eq!(metrics[0].data.value, MetricValue::Gauge { value: 3.0 });
+        assert_eq!(metrics[0].data.kind, MetricKind::Absolute);
+        assert_eq!(metrics[1].data.value, MetricValue::Gauge { value: 2.0 });
+    }
+
+    #[tokio::test]
+    async fn flushes_at_end_of_input() {
+        let agg = toml::from_str::<AggregateConfig>("interval_ms = 60000")
+            .unwrap()
+            .build(&GlobalOptions::default())
+            .await
+            .unwrap()
+            .into_task();
+
+        let inputs = vec![
+            make_metric(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ),
+            make_metric(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 2.0 },
+            ),
+        ];
+        let output = agg
+            .transform(Box::pin(stream::iter(inputs)))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            output[0].as_metric().data.value,
+            MetricValue::Counter { value: 3.0 }
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    event::{metric::MetricValue, Event},
+    transforms::{FunctionTransform, Transform},
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ConvertUnitsConfig {
+    pub rules: Vec<RuleConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+    /// Glob pattern matched against metric names.
+    pub metric: String,
+    pub from: Option<Unit>,
+    pub to: Option<Unit>,
+    pub scale: Option<f64>,
+    pub rename: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Unit {
+    Bits,
+    Bytes,
+    Kilobytes,
+    Megabytes,
+    Gigabytes,
+    Kibibytes,
+    Mebibytes,
+    Gibibytes,
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Dimension {
+    Information,
+    Time,
+}
+
+impl Unit {
+    /// The dimension of the unit, and its size in the base unit of that dimension.
+    fn size(self) -> (Dimension, f64) {
+        match self {
+            Self::Bits => (Dimension::Information, 1.0 / 8.0),
+            Self::Bytes => (Dimension::Information, 1.0),
+            Self::Kilobytes => (Dimension::Information, 1e3),
+            Self::Megabytes => (Dimension::Information, 1e6),
+            Self::Gigabytes => (Dimension::Information, 1e9),
+            Self::Kibibytes => (Dimension::Information, 1024.0),
+            Self::Mebibytes => (Dimension::Information, 1024.0 * 1024.0),
+            Self::Gibibytes => (Dimension::Information, 1024.0 * 1024.0 * 1024.0),
+            Self::Nanoseconds => (Dimension::Time, 1e-9),
+            Self::Microseconds => (Dimension::Time, 1e-6),
+            Self::Milliseconds => (Dimension::Time, 1e-3),
+            Self::Seconds => (Dimension::Time, 1.0),
+            Self::Minutes => (Dimension::Time, 60.0),
+            Self::Hours => (Dimension::Time, 3600.0),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid metric pattern {:?}: {}", pattern, source))]
+    InvalidPattern {
+        pattern: String,
+        source: glob::PatternError,
+    },
+    #[snafu(display("Can't convert {:?} to {:?}", from, to))]
+    IncompatibleUnits { from: Unit, to: Unit },
+    #[snafu(display(
+        "Rule for {:?} must have either both `from` and `to`, or `scale`",
+        pattern
+    ))]
+    MissingConversion { pattern: String },
+}
+
+impl RuleConfig {
+    fn build(&self) -> crate::Result<Rule> {
+        let pattern =
+            glob::Pattern::new(&self.metric).map_err(|source| BuildError::InvalidPattern {
+                pattern: self.metric.clone(),
+                source,
+            })?;
+
+        let factor = match (self.from, self.to, self.scale) {
+            (Some(from), Some(to), None) => {
+                let (from_dimension, from_size) = from.size();
+                let (to_dimension, to_size) = to.size();
+                if from_dimension != to_dimension {
+                    return Err(BuildError::IncompatibleUnits { from, to }.into());
+                }
+                from_size / to_size
+            }
+            (None, None, Some(scale)) => scale,
+            _ => {
+                return Err(BuildError::MissingConversion {
+                    pattern: self.metric.clone(),
+                }
+                .into())
+            }
+        };
+
+        Ok(Rule {
+            pattern,
+            factor,
+            rename: self.rename.clone(),
+        })
+    }
+}
+
+inventory::submit! {
+    TransformDescription::new::<ConvertUnitsConfig>("convert_units")
+}
+
+impl GenerateConfig for ConvertUnitsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            rules: vec![RuleConfig {
+                metric: "*_milliseconds".to_owned(),
+                from: Some(Unit::Milliseconds),
+                to: Some(Unit::Seconds),
+                scale: None,
+                rename: None,
+            }],
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "convert_units")]
+impl TransformConfig for ConvertUnitsConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        let rules = self
+            .rules
+            .iter()
+            .map(RuleConfig::build)
+            .collect::<crate::Result<_>>()?;
+        Ok(Transform::function(ConvertUnits::new(rules)))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Metric
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "convert_units"
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    pattern: glob::Pattern,
+    factor: f64,
+    rename: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConvertUnits {
+    rules: Vec<Rule>,
+}
+
+impl ConvertUnits {
+    fn new(rules: Vec<Rule>) -> Self {
+        Self { rules }
+    }
+}
+
+impl FunctionTransform for ConvertUnits {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        let metric = event.as_mut_metric();
+
+        // Only the first rule matching the name of the metric applies.
+        if let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| rule.pattern.matches(metric.name()))
+        {
+            scale(&mut metric.data.value, rule.factor);
+            if let Some(name) = &rule.rename {
+                metric.series.name.name = name.clone();
+            }
+        }
+
+        output.push(event)
+    }
+}
+
+/// Scales the values of a metric by `factor`. The counts of values are left alone, and sets
+/// have no values to scale.
+fn scale(value: &mut MetricValue, factor: f64) {
+    match value {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => *value *= factor,
+        MetricValue::Set { .. } => {}
+        MetricValue::Distribution { samples, .. } => {
+            for sample in samples {
+                sample.value *= factor;
+            }
+        }
+        MetricValue::AggregatedHistogram { buckets, sum, .. } => {
+            for bucket in buckets {
+                bucket.upper_limit *= factor;
+            }
+            *sum *= factor;
+        }
+        MetricValue::AggregatedSummary { quantiles, sum, .. } => {
+            for quantile in quantiles {
+                quantile.value *= factor;
+            }
+            *sum *= factor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{Metric, MetricKind, StatisticKind};
+
+    fn rule(metric: &str, from: Unit, to: Unit) -> RuleConfig {
+        RuleConfig {
+            metric: metric.to_owned(),
+            from: Some(from),
+            to: Some(to),
+            scale: None,
+            rename: None,
+        }
+    }
+
+    fn transform(rules: Vec<RuleConfig>) -> ConvertUnits {
+        ConvertUnits::new(rules.iter().map(|rule| rule.build().unwrap()).collect())
+    }
+
+    fn metric(name: &str, value: MetricValue) -> Event {
+        Event::Metric(Metric::new(name, MetricKind::Absolute, value))
+    }
+
+    fn requests_total() -> Event {
+        metric("requests_total", MetricValue::Counter { value: 1500.0 })
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ConvertUnitsConfig>();
+    }
+
+    #[test]
+    fn converts_matching_metrics() {
+        let config = rule("*_milliseconds", Unit::Milliseconds, Unit::Seconds);
+        let mut transform = transform(vec![config]);
+
+        let event = metric("latency_milliseconds", MetricValue::Gauge { value: 1500.0 });
+        let metric = transform.transform_one(event).unwrap().into_metric();
+        assert_eq!(metric.data.value, MetricValue::Gauge { value: 1.5 });
+
+        let metric = transform
+            .transform_one(requests_total())
+            .unwrap()
+            .into_metric();
+        assert_eq!(metric.data.value, MetricValue::Counter { value: 1500.0 });
+    }
+
+    #[test]
+    fn converts_distributions_and_renames() {
+        let mut config = rule("memory", Unit::Bytes, Unit::Kibibytes);
+        config.rename = Some("memory_kib".to_owned());
+        let mut transform = transform(vec![config]);
+
+        let event = metric(
+            "memory",
+            MetricValue::Distribution {
+                samples: crate::samples![2048.0 => 1, 512.0 => 2],
+                statistic: StatisticKind::Histogram,
+            },
+        );
+        let metric = transform.transform_one(event).unwrap().into_metric();
+        assert_eq!(metric.name(), "memory_kib");
+        assert_eq!(
+            metric.data.value,
+            MetricValue::Distribution {
+                samples: crate::samples![2.0 => 1, 0.5 => 2],
+                statistic: StatisticKind::Histogram,
+            }
+        );
+    }
+
+    #[test]
+    fn scales_counters() {
+        let mut transform = transform(vec![RuleConfig {
+            metric: "requests_total".to_owned(),
+            from: None,
+            to: None,
+            scale: Some(100.0),
+            rename: None,
+        }]);
+
+        let metric = transform
+            .transform_one(requests_total())
+            .unwrap()
+            .into_metric();
+        assert_eq!(metric.data.value, MetricValue::Counter { value: 150_000.0 });
+    }
+
+    #[test]
+    fn rejects_incompatible_units() {
+        assert!(rule("*", Unit::Bytes, Unit::Seconds).build().is_err());
+    }
+}
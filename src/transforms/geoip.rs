@@ -1,12 +1,26 @@
 use crate::{
     config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
     event::Event,
-    internal_events::{GeoipFieldDoesNotExist, GeoipIpAddressParseError},
+    internal_events::{
+        GeoipDatabaseReloadFailed, GeoipDatabaseReloaded, GeoipFieldDoesNotExist,
+        GeoipIpAddressParseError,
+    },
     transforms::{FunctionTransform, Transform},
     Result,
 };
+use futures::FutureExt;
+use maxminddb::{MaxMindDBError, Reader};
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{
+    fs,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::task::JoinHandle;
+
+/// How often the database file is checked for changes.
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -17,24 +31,37 @@ pub struct GeoipConfig {
     pub target: String,
 }
 
+/// The database opened again, along with the modification time of its file, if the file
+/// has changed.
+type Reloaded = Option<(
+    Option<SystemTime>,
+    std::result::Result<Reader<Vec<u8>>, MaxMindDBError>,
+)>;
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct Geoip {
     #[derivative(Debug = "ignore")]
-    pub dbreader: maxminddb::Reader<Vec<u8>>,
+    pub dbreader: Arc<Reader<Vec<u8>>>,
     pub database: String,
     pub source: String,
     pub target: String,
+    modified: Option<SystemTime>,
+    last_checked: Instant,
+    #[derivative(Debug = "ignore")]
+    reloading: Option<JoinHandle<Reloaded>>,
 }
 
 impl Clone for Geoip {
     fn clone(&self) -> Self {
         Self {
-            dbreader: maxminddb::Reader::open_readfile(self.database.clone())
-                .expect("Panicked while cloning GeoIP lookup database. Did you move the GeoIP database on disk during runtime?"),
+            dbreader: Arc::clone(&self.dbreader),
             database: self.database.clone(),
             source: self.source.clone(),
-            target: self.target.clone()
+            target: self.target.clone(),
+            modified: self.modified,
+            last_checked: self.last_checked,
+            reloading: None,
         }
     }
 }
@@ -91,19 +118,69 @@ const ISP_DATABASE_TYPE: &str = "GeoIP2-ISP";
 impl Geoip {
     pub fn new(database: String, source: String, target: String) -> crate::Result<Self> {
         Ok(Geoip {
-            dbreader: maxminddb::Reader::open_readfile(database.clone())?,
+            modified: modified_time(&database),
+            dbreader: Arc::new(Reader::open_readfile(database.clone())?),
             database,
             source,
             target,
+            last_checked: Instant::now(),
+            reloading: None,
         })
     }
 
+    /// Opens the database again in the background when its file has changed since it
+    /// was last opened, and swaps it in once opened, keeping the current one until then,
+    /// or if the new file can't be read.
+    fn reload_if_changed(&mut self) {
+        if let Some(reloading) = &mut self.reloading {
+            let reloaded = match reloading.now_or_never() {
+                Some(reloaded) => reloaded,
+                None => return,
+            };
+            self.reloading = None;
+            match reloaded {
+                Ok(Some((modified, Ok(dbreader)))) => {
+                    self.dbreader = Arc::new(dbreader);
+                    self.modified = modified;
+                    emit!(GeoipDatabaseReloaded {
+                        path: &self.database
+                    });
+                }
+                Ok(Some((_, Err(error)))) => emit!(GeoipDatabaseReloadFailed {
+                    path: &self.database,
+                    error,
+                }),
+                Ok(None) | Err(_) => {}
+            }
+        }
+
+        if self.last_checked.elapsed() < RELOAD_CHECK_INTERVAL {
+            return;
+        }
+        self.last_checked = Instant::now();
+
+        let database = self.database.clone();
+        let opened = self.modified;
+        self.reloading = Some(tokio::task::spawn_blocking(move || {
+            let modified = modified_time(&database);
+            if modified == opened {
+                None
+            } else {
+                Some((modified, Reader::open_readfile(&database)))
+            }
+        }));
+    }
+
     fn has_isp_db(&self) -> bool {
         self.dbreader.metadata.database_type == ASN_DATABASE_TYPE
             || self.dbreader.metadata.database_type == ISP_DATABASE_TYPE
     }
 }
 
+fn modified_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
 #[derive(Default, Serialize)]
 struct ISP<'a> {
     autonomous_system_number: i64,
@@ -125,6 +202,8 @@ struct City<'a> {
 
 impl FunctionTransform for Geoip {
     fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        self.reload_if_changed();
+
         let mut isp: ISP = Default::default();
         let mut city: City = Default::default();
         let target_field = self.target.clone();
@@ -249,6 +328,39 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn geoip_reloads_changed_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("GeoIP.mmdb");
+        std::fs::copy("tests/data/GeoIP2-City-Test.mmdb", &database).unwrap();
+
+        let mut augment = Geoip::new(
+            database.to_str().unwrap().to_string(),
+            "remote_addr".into(),
+            "geo".to_string(),
+        )
+        .unwrap();
+        assert!(!augment.has_isp_db());
+
+        std::fs::copy("tests/data/GeoIP2-ISP-Test.mmdb", &database).unwrap();
+        // The file is only checked once the interval has passed.
+        augment.modified = None;
+        augment.transform_one(Event::from("")).unwrap();
+        assert!(!augment.has_isp_db());
+
+        augment.last_checked = Instant::now() - RELOAD_CHECK_INTERVAL;
+        augment.transform_one(Event::from("")).unwrap();
+        // The database is opened in the background, and swapped in by a later event.
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !augment.has_isp_db() {
+                tokio::time::delay_for(Duration::from_millis(10)).await;
+                augment.transform_one(Event::from("")).unwrap();
+            }
+        })
+        .await
+        .expect("database should be reloaded");
+    }
+
     #[test]
     fn geoip_city_lookup_partial_results() {
         let mut parser = JsonParser::from(JsonParserConfig::default());
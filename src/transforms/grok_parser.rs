@@ -2,11 +2,15 @@ use crate::{
     config::{log_schema, DataType, GlobalOptions, TransformConfig, TransformDescription},
     event::{Event, PathComponent, PathIter, Value},
     internal_events::{GrokParserConversionFailed, GrokParserFailedMatch, GrokParserMissingField},
-    transforms::{FunctionTransform, Transform},
+    transforms::{
+        util::rejected::{self, Parser, ParserOutput},
+        Transform,
+    },
     types::{parse_conversion_map, Conversion},
 };
 use bytes::Bytes;
 use grok::Pattern;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
 use snafu::{ResultExt, Snafu};
@@ -29,6 +33,12 @@ pub struct GrokParserConfig {
     pub drop_field: bool,
     pub types: HashMap<String, String>,
     pub timezone: Option<TimeZone>,
+    /// The field of events sent to the `rejected` output holding the reason they failed to
+    /// parse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_field: Option<String>,
+    #[serde(skip)]
+    pub output: ParserOutput,
 }
 
 inventory::submit! {
@@ -51,18 +61,30 @@ impl TransformConfig for GrokParserConfig {
         let timezone = self.timezone.unwrap_or(globals.timezone);
         let types = parse_conversion_map(&self.types, timezone)?;
 
-        Ok(grok
-            .compile(&self.pattern, true)
-            .map(|p| GrokParser {
-                pattern: self.pattern.clone(),
-                pattern_built: p,
-                field: field.clone(),
-                drop_field: self.drop_field,
-                types,
-                paths: HashMap::new(),
-            })
-            .map(Transform::function)
-            .context(InvalidGrok)?)
+        rejected::build(self.output, self.error_field.as_deref(), false, || {
+            Ok(grok
+                .compile(&self.pattern, true)
+                .map(|p| GrokParser {
+                    pattern: self.pattern.clone(),
+                    pattern_built: p,
+                    field,
+                    drop_field: self.drop_field,
+                    types,
+                    paths: HashMap::new(),
+                })
+                .context(InvalidGrok)?)
+        })
+    }
+
+    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+        Ok(Some(rejected::expand(self, |config, output| Self {
+            output,
+            ..config
+        })))
+    }
+
+    fn expanded_input(&self) -> Option<&str> {
+        rejected::expanded_input(self.output)
     }
 
     fn input_type(&self) -> DataType {
@@ -104,45 +126,51 @@ impl Clone for GrokParser {
     }
 }
 
-impl FunctionTransform for GrokParser {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        let mut event = event.into_log();
-        let value = event.get(&self.field).map(|s| s.to_string_lossy());
-
-        if let Some(value) = value {
-            if let Some(matches) = self.pattern_built.match_against(&value) {
-                let drop_field = self.drop_field && matches.get(&self.field).is_none();
-                for (name, value) in matches.iter() {
-                    let conv = self.types.get(name).unwrap_or(&Conversion::Bytes);
-                    match conv.convert::<Value>(Bytes::copy_from_slice(value.as_bytes())) {
-                        Ok(value) => {
-                            if let Some(path) = self.paths.get(name) {
-                                event.insert_path(path.to_vec(), value);
-                            } else {
-                                let path = PathIter::new(name).collect::<Vec<_>>();
-                                self.paths.insert(name.to_string(), path.clone());
-                                event.insert_path(path, value);
-                            }
-                        }
-                        Err(error) => emit!(GrokParserConversionFailed { name, error }),
-                    }
-                }
+impl Parser for GrokParser {
+    fn parse(&mut self, event: &mut Event) -> Result<(), String> {
+        let log = event.as_mut_log();
+        let value = match log.get(&self.field) {
+            Some(value) => value.to_string_lossy(),
+            None => {
+                emit!(GrokParserMissingField {
+                    field: self.field.as_ref()
+                });
+                return Err(format!("Field {:?} does not exist.", self.field));
+            }
+        };
 
-                if drop_field {
-                    event.remove(&self.field);
-                }
-            } else {
+        let matches = match self.pattern_built.match_against(&value) {
+            Some(matches) => matches,
+            None => {
                 emit!(GrokParserFailedMatch {
                     value: value.as_ref()
                 });
+                return Err("Grok pattern failed to match.".into());
+            }
+        };
+
+        let drop_field = self.drop_field && matches.get(&self.field).is_none();
+        for (name, value) in matches.iter() {
+            let conv = self.types.get(name).unwrap_or(&Conversion::Bytes);
+            match conv.convert::<Value>(Bytes::copy_from_slice(value.as_bytes())) {
+                Ok(value) => {
+                    if let Some(path) = self.paths.get(name) {
+                        log.insert_path(path.to_vec(), value);
+                    } else {
+                        let path = PathIter::new(name).collect::<Vec<_>>();
+                        self.paths.insert(name.to_string(), path.clone());
+                        log.insert_path(path, value);
+                    }
+                }
+                Err(error) => emit!(GrokParserConversionFailed { name, error }),
             }
-        } else {
-            emit!(GrokParserMissingField {
-                field: self.field.as_ref()
-            });
         }
 
-        output.push(Event::Log(event));
+        if drop_field {
+            log.remove(&self.field);
+        }
+
+        Ok(())
     }
 }
 
@@ -153,6 +181,10 @@ mod tests {
         config::{log_schema, GlobalOptions, TransformConfig},
         event,
         event::LogEvent,
+        transforms::{
+            util::rejected::{ParserOutput, RoutedOutput, DEFAULT_ERROR_FIELD},
+            FunctionTransform,
+        },
         Event,
     };
     use pretty_assertions::assert_eq;
@@ -170,20 +202,51 @@ mod tests {
         drop_field: bool,
         types: &[(&str, &str)],
     ) -> LogEvent {
+        transform_log(
+            event,
+            pattern,
+            field,
+            drop_field,
+            types,
+            ParserOutput::Parsed,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn reject_log(event: &str, pattern: &str, field: Option<&str>) -> LogEvent {
+        let parsed = transform_log(event, pattern, field, false, &[], ParserOutput::Parsed).await;
+        assert!(parsed.is_none());
+
+        transform_log(event, pattern, field, false, &[], ParserOutput::Rejected)
+            .await
+            .unwrap()
+    }
+
+    async fn transform_log(
+        event: &str,
+        pattern: &str,
+        field: Option<&str>,
+        drop_field: bool,
+        types: &[(&str, &str)],
+        output: ParserOutput,
+    ) -> Option<LogEvent> {
         let event = Event::from(event);
-        let mut parser = GrokParserConfig {
+        let config = |output| GrokParserConfig {
             pattern: pattern.into(),
             field: field.map(|s| s.into()),
             drop_field,
             types: types.iter().map(|&(k, v)| (k.into(), v.into())).collect(),
-            timezone: Default::default(),
-        }
-        .build(&GlobalOptions::default())
-        .await
-        .unwrap();
-        let parser = parser.as_function();
+            output,
+            ..Default::default()
+        };
+        let globals = GlobalOptions::default();
+        let mut parser = RoutedOutput::from_transforms(
+            config(ParserOutput::Parse).build(&globals).await.unwrap(),
+            config(output).build(&globals).await.unwrap(),
+        );
 
-        parser.transform_one(event).unwrap().into_log()
+        parser.transform_one(event).map(|event| event.into_log())
     }
 
     #[tokio::test]
@@ -213,17 +276,19 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn grok_parser_does_nothing_on_no_match() {
-        let event = parse_log(
+    async fn grok_parser_rejects_on_no_match() {
+        let event = reject_log(
             r#"Help I'm stuck in an HTTP server"#,
             "%{HTTPD_COMMONLOG}",
             None,
-            true,
-            &[],
         )
         .await;
 
-        assert_eq!(2, event.keys().count());
+        assert_eq!(3, event.keys().count());
+        assert_eq!(
+            event::Value::from("Grok pattern failed to match."),
+            event[DEFAULT_ERROR_FIELD]
+        );
         assert_eq!(
             event::Value::from("Help I'm stuck in an HTTP server"),
             event[log_schema().message_key()]
@@ -261,17 +326,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn grok_parser_does_nothing_on_missing_field() {
-        let event = parse_log(
-            "i am the only field",
-            "^(?<foo>.*)",
-            Some("bar"),
-            false,
-            &[],
-        )
-        .await;
+    async fn grok_parser_rejects_on_missing_field() {
+        let event = reject_log("i am the only field", "^(?<foo>.*)", Some("bar")).await;
 
-        assert_eq!(2, event.keys().count());
+        assert_eq!(3, event.keys().count());
         assert_eq!(
             event::Value::from("i am the only field"),
             event[log_schema().message_key()]
@@ -2,8 +2,12 @@ use crate::{
     config::{log_schema, DataType, GlobalOptions, TransformConfig, TransformDescription},
     event::Event,
     internal_events::{JsonParserFailedParse, JsonParserTargetExists},
-    transforms::{FunctionTransform, Transform},
+    transforms::{
+        util::rejected::{self, Parser, ParserOutput},
+        FunctionTransform, Transform,
+    },
 };
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -12,11 +16,20 @@ use serde_json::Value;
 #[derivative(Default)]
 pub struct JsonParserConfig {
     pub field: Option<String>,
-    pub drop_invalid: bool,
+    /// Whether events that fail to parse are dropped rather than passed on, when the
+    /// `rejected` output isn't used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_invalid: Option<bool>,
     #[derivative(Default(value = "true"))]
     pub drop_field: bool,
     pub target_field: Option<String>,
     pub overwrite_target: Option<bool>,
+    /// The field of events sent to the `rejected` output holding the reason they failed to
+    /// parse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_field: Option<String>,
+    #[serde(skip)]
+    pub output: ParserOutput,
 }
 
 inventory::submit! {
@@ -29,7 +42,23 @@ impl_generate_config_from_default!(JsonParserConfig);
 #[typetag::serde(name = "json_parser")]
 impl TransformConfig for JsonParserConfig {
     async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::function(JsonParser::from(self.clone())))
+        rejected::build(
+            self.output,
+            self.error_field.as_deref(),
+            self.drop_invalid.unwrap_or(false),
+            || Ok(JsonParser::from(self.clone())),
+        )
+    }
+
+    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+        Ok(Some(rejected::expand(self, |config, output| Self {
+            output,
+            ..config
+        })))
+    }
+
+    fn expanded_input(&self) -> Option<&str> {
+        rejected::expanded_input(self.output)
     }
 
     fn input_type(&self) -> DataType {
@@ -62,7 +91,7 @@ impl From<JsonParserConfig> for JsonParser {
 
         JsonParser {
             field,
-            drop_invalid: config.drop_invalid,
+            drop_invalid: config.drop_invalid.unwrap_or(false),
             drop_field: config.drop_field,
             target_field: config.target_field,
             overwrite_target: config.overwrite_target.unwrap_or(false),
@@ -70,72 +99,85 @@ impl From<JsonParserConfig> for JsonParser {
     }
 }
 
-impl FunctionTransform for JsonParser {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+impl Parser for JsonParser {
+    fn parse(&mut self, event: &mut Event) -> Result<(), String> {
         let log = event.as_mut_log();
-        let value = log.get(&self.field);
-
-        let parsed = value
-            .and_then(|value| {
-                let to_parse = value.as_bytes();
-                serde_json::from_slice::<Value>(to_parse.as_ref())
-                    .map_err(|error| {
-                        emit!(JsonParserFailedParse {
-                            field: &self.field,
-                            value: value.to_string_lossy().as_str(),
-                            error,
-                            drop_invalid: self.drop_invalid,
-                        })
-                    })
-                    .ok()
-            })
-            .and_then(|value| {
-                if let Value::Object(object) = value {
-                    Some(object)
-                } else {
-                    None
+        let value = log
+            .get(&self.field)
+            .ok_or_else(|| format!("Field {:?} does not exist.", self.field))?;
+
+        let object = match serde_json::from_slice::<Value>(value.as_bytes().as_ref()) {
+            Ok(Value::Object(object)) => object,
+            Ok(_) => return Err("Event parsed as JSON, but not as an object.".into()),
+            Err(error) => {
+                let message = format!("Event failed to parse as JSON: {}", error);
+                emit!(JsonParserFailedParse {
+                    field: &self.field,
+                    value: value.to_string_lossy().as_str(),
+                    error,
+                });
+                return Err(message);
+            }
+        };
+
+        match self.target_field {
+            Some(ref target_field) => {
+                if log.contains(&target_field) && !self.overwrite_target {
+                    emit!(JsonParserTargetExists { target_field });
+                    return Err(format!("Target field {:?} already exists.", target_field));
                 }
-            });
-
-        if let Some(object) = parsed {
-            match self.target_field {
-                Some(ref target_field) => {
-                    let contains_target = log.contains(&target_field);
-
-                    if contains_target && !self.overwrite_target {
-                        emit!(JsonParserTargetExists { target_field })
-                    } else {
-                        if self.drop_field {
-                            log.remove(&self.field);
-                        }
-
-                        log.insert(&target_field, Value::Object(object));
-                    }
+
+                if self.drop_field {
+                    log.remove(&self.field);
                 }
-                None => {
-                    if self.drop_field {
-                        log.remove(&self.field);
-                    }
-
-                    for (key, value) in object {
-                        log.insert_flat(key, value);
-                    }
+
+                log.insert(&target_field, Value::Object(object));
+            }
+            None => {
+                if self.drop_field {
+                    log.remove(&self.field);
+                }
+
+                for (key, value) in object {
+                    log.insert_flat(key, value);
                 }
             }
-        } else if self.drop_invalid {
-            return;
         }
 
-        output.push(event);
+        Ok(())
+    }
+}
+
+/// Parses events as a parser that isn't expanded, passing on or dropping those it fails to
+/// parse.
+impl FunctionTransform for JsonParser {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        if self.parse(&mut event).is_ok() || !self.drop_invalid {
+            output.push(event);
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{config::log_schema, event::Event};
+    use crate::{
+        config::log_schema,
+        event::Event,
+        transforms::util::rejected::{ParserTransform, RoutedOutput, DEFAULT_ERROR_FIELD},
+    };
     use serde_json::json;
 
+    fn parsed_output(config: JsonParserConfig) -> RoutedOutput {
+        let stage = ParserTransform::marking(JsonParser::from(config));
+        RoutedOutput::new(stage, ParserOutput::Parsed)
+    }
+
+    fn rejected_output(config: JsonParserConfig) -> RoutedOutput {
+        let stage = ParserTransform::marking(JsonParser::from(config));
+        RoutedOutput::new(stage, ParserOutput::Rejected)
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<JsonParserConfig>();
@@ -331,7 +373,7 @@ mod test {
 
         // Raw
         let mut parser = JsonParser::from(JsonParserConfig {
-            drop_invalid: true,
+            drop_invalid: Some(true),
             ..Default::default()
         });
 
@@ -347,7 +389,7 @@ mod test {
         // Field
         let mut parser = JsonParser::from(JsonParserConfig {
             field: Some("data".into()),
-            drop_invalid: true,
+            drop_invalid: Some(true),
             ..Default::default()
         });
 
@@ -368,6 +410,90 @@ mod test {
         assert!(parser.transform_one(event).is_none());
     }
 
+    #[test]
+    fn json_parser_rejects_invalid_json() {
+        let invalid = r#"{"greeting": "hello","#;
+
+        // Raw
+        let mut parser = rejected_output(JsonParserConfig {
+            drop_field: false,
+            ..Default::default()
+        });
+
+        let event = Event::from(invalid);
+
+        let mut rejected_event = parser.transform_one(event.clone()).unwrap();
+        assert!(rejected_event.as_log().get(DEFAULT_ERROR_FIELD).is_some());
+        rejected_event.as_mut_log().remove(DEFAULT_ERROR_FIELD);
+        assert_eq!(event, rejected_event);
+
+        // Field
+        let mut parser = rejected_output(JsonParserConfig {
+            field: Some("data".into()),
+            drop_field: false,
+            ..Default::default()
+        });
+
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("data", invalid);
+
+        let event = parser.transform_one(event).unwrap();
+
+        assert_eq!(event.as_log()["data"], invalid.into());
+        assert!(event.as_log().get("greeting").is_none());
+    }
+
+    #[test]
+    fn json_parser_sends_invalid_to_rejected() {
+        let valid = r#"{"greeting": "hello", "name": "bob"}"#;
+        let invalid = r#"{"greeting": "hello","#;
+        let not_object = r#""hello""#;
+
+        // Raw
+        let mut parser = parsed_output(JsonParserConfig::default());
+        let mut rejects = rejected_output(JsonParserConfig::default());
+
+        let event = Event::from(valid);
+        assert!(parser.transform_one(event.clone()).is_some());
+        assert!(rejects.transform_one(event).is_none());
+
+        let event = Event::from(invalid);
+        assert!(parser.transform_one(event.clone()).is_none());
+        assert!(rejects.transform_one(event).is_some());
+
+        let event = Event::from(not_object);
+        assert!(parser.transform_one(event.clone()).is_none());
+        assert!(rejects.transform_one(event).is_some());
+
+        // Field
+        let config = JsonParserConfig {
+            field: Some("data".into()),
+            ..Default::default()
+        };
+        let mut parser = parsed_output(config.clone());
+        let mut rejects = rejected_output(config);
+
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("data", valid);
+        assert!(parser.transform_one(event.clone()).is_some());
+        assert!(rejects.transform_one(event).is_none());
+
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("data", invalid);
+        assert!(parser.transform_one(event.clone()).is_none());
+        assert!(rejects.transform_one(event).is_some());
+
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("data", not_object);
+        assert!(parser.transform_one(event.clone()).is_none());
+        assert!(rejects.transform_one(event).is_some());
+
+        // Missing field
+        let event = Event::from("message");
+        assert!(parser.transform_one(event.clone()).is_none());
+        assert!(rejects.transform_one(event).is_some());
+    }
+
     #[test]
     fn json_parser_chained() {
         let mut parser1 = JsonParser::from(JsonParserConfig {
@@ -515,4 +641,37 @@ mod test {
         assert_eq!(event["message.greeting"], "hello".into());
         assert_eq!(event["message.name"], "bob".into());
     }
+
+    #[test]
+    fn expands_to_parsed_and_rejected_outputs() {
+        let mut config = JsonParserConfig::default();
+        let expanded = config.expand().unwrap().unwrap();
+        assert_eq!(
+            expanded.keys().collect::<Vec<_>>(),
+            vec!["_parse", "", rejected::REJECTED_OUTPUT]
+        );
+        assert_eq!(expanded["_parse"].expanded_input(), None);
+        assert_eq!(expanded[""].expanded_input(), Some("_parse"));
+    }
+
+    #[tokio::test]
+    async fn rejected_error_field_is_configurable() {
+        let config = |output| JsonParserConfig {
+            error_field: Some("parse_error".into()),
+            output,
+            ..Default::default()
+        };
+        let globals = GlobalOptions::default();
+        let mut parser = RoutedOutput::from_transforms(
+            config(ParserOutput::Parse).build(&globals).await.unwrap(),
+            config(ParserOutput::Rejected)
+                .build(&globals)
+                .await
+                .unwrap(),
+        );
+
+        let event = parser.transform_one(Event::from("not json")).unwrap();
+        assert!(event.as_log().get("parse_error").is_some());
+        assert!(event.as_log().get(DEFAULT_ERROR_FIELD).is_none());
+    }
 }
@@ -2,9 +2,13 @@ use crate::{
     config::{log_schema, DataType, GlobalOptions, TransformConfig, TransformDescription},
     event::{Event, Value},
     internal_events::{KeyValueFieldDoesNotExist, KeyValueParseFailed, KeyValueTargetExists},
-    transforms::{FunctionTransform, Transform},
+    transforms::{
+        util::rejected::{self, Parser, ParserOutput},
+        Transform,
+    },
     types::{parse_conversion_map, Conversion},
 };
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
 use std::collections::HashMap;
@@ -26,6 +30,12 @@ pub struct KeyValueConfig {
     pub trim_value: Option<String>,
     pub types: HashMap<String, String>,
     pub timezone: Option<TimeZone>,
+    /// The field of events sent to the `rejected` output holding the reason they failed to
+    /// parse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_field: Option<String>,
+    #[serde(skip)]
+    pub output: ParserOutput,
 }
 
 inventory::submit! {
@@ -64,17 +74,30 @@ impl TransformConfig for KeyValueConfig {
             field_split = "=".to_string();
         }
 
-        Ok(Transform::function(KeyValue {
-            conversions,
-            drop_field,
-            field,
-            field_split,
-            overwrite_target,
-            separator,
-            target_field,
-            trim_key,
-            trim_value,
-        }))
+        rejected::build(self.output, self.error_field.as_deref(), false, || {
+            Ok(KeyValue {
+                conversions,
+                drop_field,
+                field,
+                field_split,
+                overwrite_target,
+                separator,
+                target_field,
+                trim_key,
+                trim_value,
+            })
+        })
+    }
+
+    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+        Ok(Some(rejected::expand(self, |config, output| Self {
+            output,
+            ..config
+        })))
+    }
+
+    fn expanded_input(&self) -> Option<&str> {
+        rejected::expanded_input(self.output)
     }
 
     fn input_type(&self) -> DataType {
@@ -129,56 +152,56 @@ impl KeyValue {
     }
 }
 
-impl FunctionTransform for KeyValue {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+impl Parser for KeyValue {
+    fn parse(&mut self, event: &mut Event) -> Result<(), String> {
         let log = event.as_mut_log();
-        let value = log.get(&self.field).map(|s| s.to_string_lossy());
-
-        if let Some(value) = &value {
-            let pairs = value
-                .split(&self.separator)
-                .filter_map(|pair| self.parse_pair(pair));
-
-            if let Some(target_field) = &self.target_field {
-                if log.contains(target_field) {
-                    if self.overwrite_target {
-                        log.remove(target_field);
-                    } else {
-                        emit!(KeyValueTargetExists { target_field });
-                        return output.push(event);
-                    }
-                }
+        let value = match log.get(&self.field) {
+            Some(value) => value.to_string_lossy(),
+            None => {
+                emit!(KeyValueFieldDoesNotExist {
+                    field: self.field.to_string()
+                });
+                return Err(format!("Field {:?} does not exist.", self.field));
             }
+        };
 
-            for (mut key, val) in pairs {
-                if let Some(target_field) = self.target_field.to_owned() {
-                    key = format!("{}.{}", target_field, key);
+        if let Some(target_field) = &self.target_field {
+            if log.contains(target_field) {
+                if self.overwrite_target {
+                    log.remove(target_field);
+                } else {
+                    emit!(KeyValueTargetExists { target_field });
+                    return Err(format!("Target field {:?} already exists.", target_field));
                 }
+            }
+        }
 
-                if let Some(conv) = self.conversions.get(&key) {
-                    match conv.convert::<Value>(val.into()) {
-                        Ok(value) => {
-                            log.insert(key, value);
-                        }
-                        Err(error) => {
-                            emit!(KeyValueParseFailed { key, error });
-                        }
+        let pairs = value
+            .split(&self.separator)
+            .filter_map(|pair| self.parse_pair(pair));
+
+        for (mut key, val) in pairs {
+            if let Some(target_field) = self.target_field.to_owned() {
+                key = format!("{}.{}", target_field, key);
+            }
+
+            if let Some(conv) = self.conversions.get(&key) {
+                match conv.convert::<Value>(val.into()) {
+                    Ok(value) => {
+                        log.insert(key, value);
                     }
-                } else {
-                    log.insert(key, val);
+                    Err(error) => emit!(KeyValueParseFailed { key, error }),
                 }
+            } else {
+                log.insert(key, val);
             }
+        }
 
-            if self.drop_field {
-                log.remove(&self.field);
-            }
-        } else {
-            emit!(KeyValueFieldDoesNotExist {
-                field: self.field.to_string()
-            });
-        };
+        if self.drop_field {
+            log.remove(&self.field);
+        }
 
-        output.push(event)
+        Ok(())
     }
 }
 
@@ -188,6 +211,10 @@ mod tests {
     use crate::{
         config::{GlobalOptions, TransformConfig},
         event::{LogEvent, Value},
+        transforms::{
+            util::rejected::{ParserOutput, RoutedOutput, DEFAULT_ERROR_FIELD},
+            FunctionTransform,
+        },
         Event,
     };
 
@@ -214,6 +241,8 @@ mod tests {
             trim_key,
             trim_value,
             timezone: Default::default(),
+            error_field: None,
+            output: ParserOutput::Unrouted,
         }
         .build(&GlobalOptions::default())
         .await
@@ -389,4 +418,29 @@ mod tests {
         assert_eq!(log["bop"], Value::Bytes("beep".into()));
         assert_eq!(log["score"], Value::Integer(78));
     }
+
+    #[tokio::test]
+    async fn it_rejects_missing_field() {
+        let config = |output| KeyValueConfig {
+            field: Some("data".into()),
+            output,
+            ..Default::default()
+        };
+        let globals = GlobalOptions::default();
+        let mut parser = RoutedOutput::from_transforms(
+            config(ParserOutput::Parse).build(&globals).await.unwrap(),
+            config(ParserOutput::Rejected)
+                .build(&globals)
+                .await
+                .unwrap(),
+        );
+
+        let log = parser
+            .transform_one(Event::from("foo=bar"))
+            .unwrap()
+            .into_log();
+        assert_eq!(log["message"], Value::Bytes("foo=bar".into()));
+        assert!(log.get("foo").is_none());
+        assert!(log.get(DEFAULT_ERROR_FIELD).is_some());
+    }
 }
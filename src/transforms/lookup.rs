@@ -0,0 +1,231 @@
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    enrichment_tables::{self, Table, TableHandle},
+    event::Event,
+    internal_events::{LookupFailed, LookupRowNotFound},
+    transforms::{FunctionTransform, Transform},
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("no enrichment table named {:?}", table))]
+    UnknownTable { table: String },
+    #[snafu(display("enrichment table {:?} has no column named {:?}", table, column))]
+    UnknownColumn { table: String, column: String },
+    #[snafu(display("`match_fields` must name at least one column"))]
+    NoMatchFields,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LookupConfig {
+    /// The name of the enrichment table to look events up in.
+    pub table: String,
+    /// Maps columns of the table to the fields of events whose values they must equal.
+    pub match_fields: IndexMap<String, String>,
+    /// The field the columns of the matching row are inserted under, instead
+    /// of at the root of the event.
+    pub target: Option<String>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<LookupConfig>("lookup")
+}
+
+impl GenerateConfig for LookupConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            table = "teams"
+            match_fields.user_id = "user_id""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "lookup")]
+impl TransformConfig for LookupConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        let (table, contents) =
+            enrichment_tables::get(&self.table).ok_or_else(|| BuildError::UnknownTable {
+                table: self.table.clone(),
+            })?;
+        Lookup::new(self, table, &contents).map(Transform::function)
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "lookup"
+    }
+
+    fn enrichment_tables(&self) -> Vec<&str> {
+        vec![&self.table]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Lookup {
+    name: String,
+    table: TableHandle,
+    match_fields: IndexMap<String, String>,
+    target: Option<String>,
+}
+
+impl Lookup {
+    /// Columns are checked against `contents`, the contents `table` has once
+    /// the topology being built is started.
+    fn new(config: &LookupConfig, table: TableHandle, contents: &Table) -> crate::Result<Self> {
+        if config.match_fields.is_empty() {
+            return Err(BuildError::NoMatchFields.into());
+        }
+        if let Some(column) = config
+            .match_fields
+            .keys()
+            .find(|column| !contents.columns().contains(*column))
+        {
+            return Err(BuildError::UnknownColumn {
+                table: config.table.clone(),
+                column: column.clone(),
+            }
+            .into());
+        }
+
+        Ok(Self {
+            name: config.table.clone(),
+            table,
+            match_fields: config.match_fields.clone(),
+            target: config.target.clone(),
+        })
+    }
+}
+
+impl FunctionTransform for Lookup {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        let log = event.as_mut_log();
+
+        let values = self
+            .match_fields
+            .iter()
+            .map(|(column, field)| {
+                log.get(field)
+                    .map(|value| (column.as_str(), value.to_string_lossy()))
+                    .ok_or(field)
+            })
+            .collect::<Result<Vec<_>, _>>();
+
+        match values {
+            Ok(values) => {
+                let conditions = values
+                    .iter()
+                    .map(|(column, value)| (*column, value.as_str()))
+                    .collect::<Vec<_>>();
+                // The table is fetched for every event, so that a reloaded table is used.
+                match self.table.get().find(&conditions) {
+                    Ok(Some(row)) => {
+                        for (column, value) in row {
+                            let key = match &self.target {
+                                Some(target) => format!("{}.{}", target, column),
+                                None => column,
+                            };
+                            log.insert(key, value);
+                        }
+                    }
+                    Ok(None) => emit!(LookupRowNotFound { table: &self.name }),
+                    Err(error) => emit!(LookupFailed {
+                        table: &self.name,
+                        error,
+                    }),
+                }
+            }
+            Err(field) => emit!(LookupFailed {
+                table: &self.name,
+                error: format!("field {:?} is missing", field),
+            }),
+        }
+
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LookupConfig>();
+    }
+
+    fn lookup(config: &str) -> crate::Result<Lookup> {
+        let table = Table::new(
+            vec!["user_id".into(), "region".into(), "team".into()],
+            vec![
+                vec!["1".into(), "eu".into(), "platform".into()],
+                vec!["2".into(), "us".into(), "billing".into()],
+            ],
+        );
+        let config = toml::from_str::<LookupConfig>(config).unwrap();
+        let table = TableHandle::new(table);
+        let contents = table.get();
+        Lookup::new(&config, table, &contents)
+    }
+
+    fn user_event(user_id: &str) -> Event {
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("user.id", user_id);
+        event
+    }
+
+    #[test]
+    fn inserts_matching_rows() {
+        let mut lookup = lookup(
+            r#"
+            table = "teams"
+            match_fields.user_id = "user.id"
+            target = "user""#,
+        )
+        .unwrap();
+
+        let event = lookup.transform_one(user_event("2")).unwrap();
+        assert_eq!(event.as_log()["user.team"], "billing".into());
+        assert_eq!(event.as_log()["user.region"], "us".into());
+
+        // Events without a matching row pass through unchanged.
+        let event = lookup.transform_one(user_event("3")).unwrap();
+        assert!(event.as_log().get("user.team").is_none());
+    }
+
+    #[test]
+    fn inserts_at_root_without_target() {
+        let mut lookup = lookup(
+            r#"
+            table = "teams"
+            match_fields.user_id = "user.id""#,
+        )
+        .unwrap();
+
+        let event = lookup.transform_one(user_event("1")).unwrap();
+        assert_eq!(event.as_log()["team"], "platform".into());
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        assert!(lookup(
+            r#"
+            table = "teams"
+            match_fields.user = "user.id""#,
+        )
+        .is_err());
+    }
+}
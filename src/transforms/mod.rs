@@ -9,6 +9,8 @@ pub mod util;
 pub mod add_fields;
 #[cfg(feature = "transforms-add_tags")]
 pub mod add_tags;
+#[cfg(feature = "transforms-aggregate")]
+pub mod aggregate;
 #[cfg(feature = "transforms-ansi_stripper")]
 pub mod ansi_stripper;
 #[cfg(feature = "transforms-aws_cloudwatch_logs_subscription_parser")]
@@ -19,6 +21,8 @@ pub mod aws_ec2_metadata;
 pub mod coercer;
 #[cfg(feature = "transforms-concat")]
 pub mod concat;
+#[cfg(feature = "transforms-convert_units")]
+pub mod convert_units;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
 #[cfg(feature = "transforms-field_filter")]
@@ -37,6 +41,8 @@ pub mod key_value_parser;
 pub mod log_to_metric;
 #[cfg(feature = "transforms-logfmt_parser")]
 pub mod logfmt_parser;
+#[cfg(feature = "transforms-lookup")]
+pub mod lookup;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
 #[cfg(feature = "transforms-merge")]
@@ -63,8 +69,12 @@ pub mod sample;
 pub mod split;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-time_enricher")]
+pub mod time_enricher;
 #[cfg(feature = "transforms-tokenizer")]
 pub mod tokenizer;
+#[cfg(feature = "transforms-traffic_split")]
+pub mod traffic_split;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "snake_case")]
 pub enum MergeStrategy {
     Discard,
+    Retain,
     Sum,
     Max,
     Min,
@@ -41,6 +42,31 @@ impl ReduceValueMerger for DiscardMerger {
 
 //------------------------------------------------------------------------------
 
+#[derive(Debug, Clone)]
+struct RetainMerger {
+    v: Value,
+}
+
+impl RetainMerger {
+    fn new(v: Value) -> Self {
+        Self { v }
+    }
+}
+
+impl ReduceValueMerger for RetainMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        self.v = v;
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        v.insert(k, self.v);
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+
 #[derive(Debug, Clone)]
 struct ConcatMerger {
     v: BytesMut,
@@ -419,6 +445,7 @@ pub fn get_value_merger(v: Value, m: &MergeStrategy) -> Result<Box<dyn ReduceVal
         },
         MergeStrategy::Array => Ok(Box::new(ArrayMerger::new(v))),
         MergeStrategy::Discard => Ok(Box::new(DiscardMerger::new(v))),
+        MergeStrategy::Retain => Ok(Box::new(RetainMerger::new(v))),
     }
 }
 
@@ -431,6 +458,7 @@ mod test {
     #[test]
     fn initial_values() {
         assert!(get_value_merger("foo".into(), &MergeStrategy::Discard).is_ok());
+        assert!(get_value_merger("foo".into(), &MergeStrategy::Retain).is_ok());
         assert!(get_value_merger("foo".into(), &MergeStrategy::Sum).is_err());
         assert!(get_value_merger("foo".into(), &MergeStrategy::Max).is_err());
         assert!(get_value_merger("foo".into(), &MergeStrategy::Min).is_err());
@@ -438,6 +466,7 @@ mod test {
         assert!(get_value_merger("foo".into(), &MergeStrategy::Concat).is_ok());
 
         assert!(get_value_merger(42.into(), &MergeStrategy::Discard).is_ok());
+        assert!(get_value_merger(42.into(), &MergeStrategy::Retain).is_ok());
         assert!(get_value_merger(42.into(), &MergeStrategy::Sum).is_ok());
         assert!(get_value_merger(42.into(), &MergeStrategy::Min).is_ok());
         assert!(get_value_merger(42.into(), &MergeStrategy::Max).is_ok());
@@ -500,6 +529,10 @@ mod test {
             merge("foo".into(), "bar".into(), &MergeStrategy::Discard),
             Ok("foo".into())
         );
+        assert_eq!(
+            merge("foo".into(), "bar".into(), &MergeStrategy::Retain),
+            Ok("bar".into())
+        );
         assert_eq!(
             merge("foo".into(), "bar".into(), &MergeStrategy::Array),
             Ok(json!(["foo", "bar"]).into())
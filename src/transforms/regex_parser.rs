@@ -5,10 +5,14 @@ use crate::{
         RegexParserConversionFailed, RegexParserFailedMatch, RegexParserMissingField,
         RegexParserTargetExists,
     },
-    transforms::{FunctionTransform, Transform},
+    transforms::{
+        util::rejected::{self, Parser, ParserOutput},
+        Transform,
+    },
     types::{parse_check_conversion_map, Conversion},
 };
 use bytes::Bytes;
+use indexmap::IndexMap;
 use regex::bytes::{CaptureLocations, Regex, RegexSet};
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
@@ -28,13 +32,22 @@ pub struct RegexParserConfig {
     pub field: Option<String>,
     #[derivative(Default(value = "true"))]
     pub drop_field: bool,
-    pub drop_failed: bool,
+    /// Whether events that fail to parse are dropped rather than passed on, when the
+    /// `rejected` output isn't used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drop_failed: Option<bool>,
     pub target_field: Option<String>,
     #[derivative(Default(value = "true"))]
     pub overwrite_target: bool,
     pub types: HashMap<String, String>,
     #[serde(default)]
     pub timezone: Option<TimeZone>,
+    /// The field of events sent to the `rejected` output holding the reason they failed to
+    /// parse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_field: Option<String>,
+    #[serde(skip)]
+    pub output: ParserOutput,
 }
 
 inventory::submit! {
@@ -50,6 +63,17 @@ impl TransformConfig for RegexParserConfig {
         RegexParser::build(&self, globals.timezone)
     }
 
+    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+        Ok(Some(rejected::expand(self, |config, output| Self {
+            output,
+            ..config
+        })))
+    }
+
+    fn expanded_input(&self) -> Option<&str> {
+        rejected::expanded_input(self.output)
+    }
+
     fn input_type(&self) -> DataType {
         DataType::Log
     }
@@ -69,7 +93,6 @@ pub struct RegexParser {
     patterns: Vec<CompiledRegex>, // indexes correspend to RegexSet
     field: String,
     drop_field: bool,
-    drop_failed: bool,
     target_field: Option<String>,
     overwrite_target: bool,
 }
@@ -190,16 +213,22 @@ impl RegexParser {
         let types =
             parse_check_conversion_map(&config.types, names, config.timezone.unwrap_or(timezone))?;
 
-        Ok(Transform::function(RegexParser::new(
-            regexset,
-            patterns,
-            field,
-            config.drop_field,
-            config.drop_failed,
-            config.target_field.clone(),
-            config.overwrite_target,
-            types,
-        )))
+        rejected::build(
+            config.output,
+            config.error_field.as_deref(),
+            config.drop_failed.unwrap_or(false),
+            || {
+                Ok(RegexParser::new(
+                    regexset,
+                    patterns,
+                    field,
+                    config.drop_field,
+                    config.target_field.clone(),
+                    config.overwrite_target,
+                    types,
+                ))
+            },
+        )
     }
 
     pub fn new(
@@ -207,7 +236,6 @@ impl RegexParser {
         patterns: Vec<Regex>,
         field: String,
         mut drop_field: bool,
-        drop_failed: bool,
         target_field: Option<String>,
         overwrite_target: bool,
         types: HashMap<String, Conversion>,
@@ -232,73 +260,66 @@ impl RegexParser {
             patterns,
             field,
             drop_field,
-            drop_failed,
             target_field,
             overwrite_target,
         }
     }
 }
 
-impl FunctionTransform for RegexParser {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+impl Parser for RegexParser {
+    fn parse(&mut self, event: &mut Event) -> Result<(), String> {
         let log = event.as_mut_log();
-        let value = log.get(&self.field).map(|s| s.as_bytes());
-
-        if let Some(value) = &value {
-            let regex_id = self.regexset.matches(&value).into_iter().next();
-            let id = match regex_id {
-                Some(id) => id,
-                None => {
-                    emit!(RegexParserFailedMatch { value });
-                    if self.drop_failed {
-                        return;
-                    } else {
-                        output.push(event);
-                        return;
-                    };
-                }
-            };
-
-            let target_field = self.target_field.as_ref();
-
-            let pattern = self
-                .patterns
-                .get_mut(id)
-                .expect("Mismatch between capture patterns and regexset");
-
-            if let Some(captures) = pattern.captures(&value) {
-                // Handle optional overwriting of the target field
-                if let Some(target_field) = target_field {
-                    if log.contains(target_field) {
-                        if self.overwrite_target {
-                            log.remove(target_field);
-                        } else {
-                            emit!(RegexParserTargetExists { target_field });
-                            output.push(event);
-                            return;
-                        }
-                    }
-                }
+        let value = match log.get(&self.field) {
+            Some(value) => value.as_bytes(),
+            None => {
+                emit!(RegexParserMissingField { field: &self.field });
+                return Err(format!("Field {:?} does not exist.", self.field));
+            }
+        };
 
-                log.extend(captures.map(|(name, value)| {
-                    let name = target_field
-                        .map(|target| format!("{}.{}", target, name))
-                        .unwrap_or_else(|| name.clone());
-                    (name, value)
-                }));
-                if self.drop_field {
-                    log.remove(&self.field);
+        let regex_id = self.regexset.matches(&value).into_iter().next();
+        let id = match regex_id {
+            Some(id) => id,
+            None => {
+                emit!(RegexParserFailedMatch { value: &value });
+                return Err("Regex pattern failed to match.".into());
+            }
+        };
+
+        let target_field = self.target_field.as_ref();
+
+        let pattern = self
+            .patterns
+            .get_mut(id)
+            .expect("Mismatch between capture patterns and regexset");
+
+        let captures = pattern
+            .captures(&value)
+            .ok_or_else(|| "Regex pattern failed to match.".to_owned())?;
+
+        // Handle optional overwriting of the target field
+        if let Some(target_field) = target_field {
+            if log.contains(target_field) {
+                if self.overwrite_target {
+                    log.remove(target_field);
+                } else {
+                    emit!(RegexParserTargetExists { target_field });
+                    return Err(format!("Target field {:?} already exists.", target_field));
                 }
-                output.push(event);
-                return;
             }
-        } else {
-            emit!(RegexParserMissingField { field: &self.field });
         }
 
-        if !self.drop_failed {
-            output.push(event);
+        log.extend(captures.map(|(name, value)| {
+            let name = target_field
+                .map(|target| format!("{}.{}", target, name))
+                .unwrap_or_else(|| name.clone());
+            (name, value)
+        }));
+        if self.drop_field {
+            log.remove(&self.field);
         }
+
+        Ok(())
     }
 }
 
@@ -308,6 +329,10 @@ mod tests {
     use crate::{
         config::{GlobalOptions, TransformConfig},
         event::{LogEvent, Value},
+        transforms::{
+            util::rejected::{ParserOutput, RoutedOutput, DEFAULT_ERROR_FIELD},
+            FunctionTransform,
+        },
         Event,
     };
 
@@ -317,19 +342,43 @@ mod tests {
     }
 
     async fn do_transform(event: &str, patterns: &str, config: &str) -> Option<LogEvent> {
+        transform_output(event, patterns, config, ParserOutput::Parsed).await
+    }
+
+    async fn do_reject(event: &str, patterns: &str, config: &str) -> Option<LogEvent> {
+        transform_output(event, patterns, config, ParserOutput::Rejected).await
+    }
+
+    async fn do_unrouted(event: &str, patterns: &str, config: &str) -> Option<LogEvent> {
+        transform_output(event, patterns, config, ParserOutput::Unrouted).await
+    }
+
+    async fn transform_output(
+        event: &str,
+        patterns: &str,
+        config: &str,
+        output: ParserOutput,
+    ) -> Option<LogEvent> {
         let event = Event::from(event);
-        let mut parser = toml::from_str::<RegexParserConfig>(&format!(
+        let mut config = toml::from_str::<RegexParserConfig>(&format!(
             r#"
                 patterns = {}
                 {}
             "#,
             patterns, config
         ))
-        .unwrap()
-        .build(&GlobalOptions::default())
-        .await
         .unwrap();
-        let parser = parser.as_function();
+        let globals = GlobalOptions::default();
+        let mut parser: Box<dyn FunctionTransform> = match output {
+            ParserOutput::Unrouted => config.build(&globals).await.unwrap().into_function(),
+            output => {
+                config.output = ParserOutput::Parse;
+                let stage = config.build(&globals).await.unwrap();
+                config.output = output;
+                let filter = config.build(&globals).await.unwrap();
+                Box::new(RoutedOutput::from_transforms(stage, filter))
+            }
+        };
 
         parser.transform_one(event).map(|event| event.into_log())
     }
@@ -350,17 +399,45 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn doesnt_do_anything_if_no_match() {
+    async fn rejects_event_if_no_match() {
         let log = do_transform(
             "asdf1234",
             r#"['status=(?P<status>\d+)']"#,
             "drop_field = false",
         )
+        .await;
+        assert!(log.is_none());
+
+        let log = do_reject(
+            "asdf1234",
+            r#"['status=(?P<status>\d+)']"#,
+            "drop_field = false",
+        )
         .await
         .unwrap();
 
         assert_eq!(log.get("status"), None);
         assert!(log.get("message").is_some());
+        assert_eq!(
+            log[DEFAULT_ERROR_FIELD],
+            "Regex pattern failed to match.".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_event_if_field_missing() {
+        let log = do_reject("asdf1234", r#"['asdf']"#, r#"field = "other""#)
+            .await
+            .unwrap();
+
+        assert!(log.get("message").is_some());
+        assert!(log.get(DEFAULT_ERROR_FIELD).is_some());
+    }
+
+    #[tokio::test]
+    async fn doesnt_reject_event_if_match() {
+        let log = do_reject("asdf1234", r#"['asdf']"#, "").await;
+        assert!(log.is_none());
     }
 
     #[tokio::test]
@@ -394,7 +471,7 @@ mod tests {
 
     #[tokio::test]
     async fn does_not_drop_field_if_no_match() {
-        let log = do_transform(
+        let log = do_reject(
             "asdf1234",
             r#"['status=(?P<message>\S+)']"#,
             r#"field = "message""#,
@@ -434,7 +511,7 @@ mod tests {
     #[tokio::test]
     async fn preserves_target_field() {
         let message = "status=1234 time=5678";
-        let log = do_transform(
+        let log = do_reject(
             message,
             r#"['status=(?P<status>\d+) time=(?P<time>\d+)']"#,
             r#"
@@ -477,16 +554,24 @@ mod tests {
 
     #[tokio::test]
     async fn does_not_drop_event_if_match() {
-        let log = do_transform("asdf1234", r#"['asdf']"#, "drop_failed = true").await;
+        let log = do_unrouted("asdf1234", r#"['asdf']"#, "drop_failed = true").await;
         assert!(log.is_some());
     }
 
     #[tokio::test]
     async fn does_drop_event_if_no_match() {
-        let log = do_transform("asdf1234", r#"['something']"#, "drop_failed = true").await;
+        let log = do_unrouted("asdf1234", r#"['something']"#, "drop_failed = true").await;
         assert!(log.is_none());
     }
 
+    #[tokio::test]
+    async fn passes_on_event_if_no_match_and_not_expanded() {
+        let log = do_unrouted("asdf1234", r#"['something']"#, "")
+            .await
+            .unwrap();
+        assert_eq!(log["message"], "asdf1234".into());
+    }
+
     #[tokio::test]
     async fn handles_valid_optional_capture() {
         let log = do_transform("1234", r#"['(?P<status>\d+)?']"#, "")
@@ -3,7 +3,7 @@ use crate::{
     config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
     event::Event,
     internal_events::RouteEventDiscarded,
-    transforms::{FunctionTransform, Transform},
+    transforms::{util::UNMATCHED_OUTPUT, FunctionTransform, Transform},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -62,6 +62,67 @@ impl FunctionTransform for Lane {
 
 //------------------------------------------------------------------------------
 
+/// The output of a route receiving the events that none of its lanes match.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct UnmatchedConfig {
+    conditions: Vec<AnyCondition>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "route_unmatched")]
+impl TransformConfig for UnmatchedConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|condition| condition.build())
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Transform::function(Unmatched::new(conditions)))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "route_unmatched"
+    }
+}
+
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct Unmatched {
+    #[derivative(Debug = "ignore")]
+    conditions: Vec<Box<dyn Condition>>,
+}
+
+impl Unmatched {
+    pub fn new(conditions: Vec<Box<dyn Condition>>) -> Self {
+        Self { conditions }
+    }
+}
+
+impl FunctionTransform for Unmatched {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        if self
+            .conditions
+            .iter()
+            .any(|condition| condition.check(&event))
+        {
+            emit!(RouteEventDiscarded);
+        } else {
+            output.push(event);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct RouteConfig {
@@ -95,17 +156,27 @@ impl TransformConfig for RouteConfig {
     }
 
     fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+        if self.route.is_empty() {
+            return Err("must specify at least one lane".into());
+        }
+        if self.route.contains_key(UNMATCHED_OUTPUT) {
+            return Err(format!("the `{}` lane name is reserved", UNMATCHED_OUTPUT).into());
+        }
+
+        // The unmatched output is dropped by the config compiler when nothing consumes it.
         let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
+        map.insert(
+            UNMATCHED_OUTPUT.into(),
+            Box::new(UnmatchedConfig {
+                conditions: self.route.values().cloned().collect(),
+            }),
+        );
 
         while let Some((k, v)) = self.route.pop() {
             map.insert(k.clone(), Box::new(LaneConfig { condition: v }));
         }
 
-        if !map.is_empty() {
-            Ok(Some(map))
-        } else {
-            Err("must specify at least one lane".into())
-        }
+        Ok(Some(map))
     }
 
     fn input_type(&self) -> DataType {
@@ -153,7 +224,7 @@ impl TransformConfig for RouteCompatConfig {
 
 #[cfg(test)]
 mod test {
-    use super::RouteConfig;
+    use super::*;
 
     #[test]
     fn generate_config() {
@@ -170,4 +241,63 @@ mod test {
         )
         .unwrap();
     }
+
+    #[test]
+    fn expands_to_unmatched_output() {
+        let mut config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "check_fields"
+            route.first."message.eq" = "foo"
+        "#,
+        )
+        .unwrap();
+
+        let outputs = config.expand().unwrap().unwrap();
+        assert_eq!(
+            outputs.keys().collect::<Vec<_>>(),
+            vec![UNMATCHED_OUTPUT, "first"]
+        );
+    }
+
+    #[test]
+    fn rejects_reserved_lane_name() {
+        let mut config = toml::from_str::<RouteConfig>(
+            r#"
+            route._unmatched.type = "check_fields"
+            route._unmatched."message.eq" = "foo"
+        "#,
+        )
+        .unwrap();
+
+        assert!(config.expand().is_err());
+    }
+
+    #[tokio::test]
+    async fn unmatched_passes_events_matching_no_lane() {
+        let config = toml::from_str::<UnmatchedConfig>(
+            r#"
+            [[conditions]]
+            type = "check_fields"
+            "message.eq" = "foo"
+
+            [[conditions]]
+            type = "check_fields"
+            "message.eq" = "bar"
+        "#,
+        )
+        .unwrap();
+        let mut unmatched = config
+            .build(&GlobalOptions::default())
+            .await
+            .unwrap()
+            .into_function();
+
+        let mut output = Vec::new();
+        for message in &["foo", "bar", "baz"] {
+            unmatched.transform(&mut output, Event::from(*message));
+        }
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["message"], "baz".into());
+    }
 }
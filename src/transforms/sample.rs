@@ -1,18 +1,62 @@
 use crate::{
-    conditions::{CheckFieldsConfig, Condition, ConditionConfig},
+    conditions::{AnyCondition, CheckFieldsConfig, Condition, ConditionConfig},
     config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
     event::Event,
     internal_events::SampleEventDiscarded,
     transforms::{FunctionTransform, Transform},
 };
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("exactly one of `rate` and `ratio` must be set"))]
+    RateOrRatio,
+    #[snafu(display("`rate` must be greater than zero"))]
+    ZeroRate,
+    #[snafu(display("`ratio` must be greater than zero and at most one, got {}", ratio))]
+    InvalidRatio { ratio: f64 },
+}
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct SampleConfig {
-    pub rate: u64,
+    /// Forwards 1 out of every `rate` events.
+    pub rate: Option<u64>,
+    /// Forwards this fraction of the events, between `0` and `1`.
+    pub ratio: Option<f64>,
     pub key_field: Option<String>,
-    pub exclude: Option<CheckFieldsConfig>,
+    pub exclude: Option<SampleExclude>,
+}
+
+/// Takes any condition, and still accepts the `check_fields` predicates `exclude` took
+/// before, which have no `type`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum SampleExclude {
+    Condition(AnyCondition),
+    CheckFields(CheckFieldsConfig),
+}
+
+impl SampleExclude {
+    fn build(&self) -> crate::Result<Box<dyn Condition>> {
+        match self {
+            SampleExclude::Condition(condition) => condition.build(),
+            SampleExclude::CheckFields(condition) => condition.build(),
+        }
+    }
+}
+
+impl SampleConfig {
+    fn sample_rate(&self) -> crate::Result<SampleRate> {
+        match (self.rate, self.ratio) {
+            (Some(0), None) => Err(BuildError::ZeroRate.into()),
+            (Some(rate), None) => Ok(SampleRate::OneIn(rate)),
+            (None, Some(ratio)) if ratio > 0.0 && ratio <= 1.0 => Ok(SampleRate::Ratio(ratio)),
+            (None, Some(ratio)) => Err(BuildError::InvalidRatio { ratio }.into()),
+            _ => Err(BuildError::RateOrRatio.into()),
+        }
+    }
 }
 
 inventory::submit! {
@@ -26,7 +70,8 @@ inventory::submit! {
 impl GenerateConfig for SampleConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            rate: 10,
+            rate: Some(10),
+            ratio: None,
             key_field: None,
             exclude: None,
         })
@@ -38,8 +83,8 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            self.rate,
+        Ok(Transform::function(Sample::with_sample_rate(
+            self.sample_rate()?,
             self.key_field.clone(),
             self.exclude
                 .as_ref()
@@ -85,9 +130,43 @@ impl TransformConfig for SampleCompatConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SampleRate {
+    OneIn(u64),
+    Ratio(f64),
+}
+
+impl SampleRate {
+    /// Whether to keep the event hashing to `hash`, which is uniformly
+    /// distributed over the `u64` range.
+    fn keeps_hash(self, hash: u64) -> bool {
+        match self {
+            Self::OneIn(rate) => hash % rate == 0,
+            Self::Ratio(ratio) => (hash as f64) <= ratio * u64::MAX as f64,
+        }
+    }
+
+    /// Whether to keep the `count`th event, spreading the kept events evenly.
+    fn keeps_count(self, count: u64) -> bool {
+        match self {
+            Self::OneIn(rate) => count % rate == 0,
+            Self::Ratio(ratio) => {
+                (count as f64 * ratio).ceil() < ((count + 1) as f64 * ratio).ceil()
+            }
+        }
+    }
+
+    fn to_field(self) -> String {
+        match self {
+            Self::OneIn(rate) => rate.to_string(),
+            Self::Ratio(ratio) => ratio.to_string(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Sample {
-    rate: u64,
+    rate: SampleRate,
     key_field: Option<String>,
     exclude: Option<Box<dyn Condition>>,
     count: u64,
@@ -95,6 +174,14 @@ pub struct Sample {
 
 impl Sample {
     pub fn new(rate: u64, key_field: Option<String>, exclude: Option<Box<dyn Condition>>) -> Self {
+        Self::with_sample_rate(SampleRate::OneIn(rate), key_field, exclude)
+    }
+
+    pub fn with_sample_rate(
+        rate: SampleRate,
+        key_field: Option<String>,
+        exclude: Option<Box<dyn Condition>>,
+    ) -> Self {
         Self {
             rate,
             key_field,
@@ -119,18 +206,20 @@ impl FunctionTransform for Sample {
             .and_then(|key_field| event.as_log().get(key_field))
             .map(|v| v.to_string_lossy());
 
-        let num = if let Some(value) = value {
-            seahash::hash(value.as_bytes())
-        } else {
-            self.count
+        let keep = match value {
+            Some(value) => self.rate.keeps_hash(seahash::hash(value.as_bytes())),
+            None => self.rate.keeps_count(self.count),
         };
 
-        self.count = (self.count + 1) % self.rate;
+        self.count = match self.rate {
+            SampleRate::OneIn(rate) => (self.count + 1) % rate,
+            SampleRate::Ratio(_) => self.count.wrapping_add(1),
+        };
 
-        if num % self.rate == 0 {
+        if keep {
             event
                 .as_mut_log()
-                .insert("sample_rate", self.rate.to_string());
+                .insert("sample_rate", self.rate.to_field());
             output.push(event);
         } else {
             emit!(SampleEventDiscarded);
@@ -200,6 +289,106 @@ mod tests {
         assert_relative_eq!(ideal, actual, epsilon = ideal * 0.5);
     }
 
+    #[test]
+    fn samples_at_the_configured_ratio() {
+        for key_field in &[None, Some(log_schema().message_key().into())] {
+            let num_events = 10000;
+
+            let events = random_events(num_events);
+            let mut sampler =
+                Sample::with_sample_rate(SampleRate::Ratio(0.3), key_field.clone(), None);
+            let passing = events
+                .into_iter()
+                .filter_map(|event| sampler.transform_one(event))
+                .collect::<Vec<_>>();
+            let actual = passing.len() as f64 / num_events as f64;
+            assert_relative_eq!(0.3, actual, epsilon = 0.3 * 0.5);
+            assert_eq!(passing[0].as_log()["sample_rate"], "0.3".into());
+        }
+    }
+
+    #[test]
+    fn ratio_spreads_counted_events_evenly() {
+        let mut sampler = Sample::with_sample_rate(SampleRate::Ratio(0.5), None, None);
+        let passed = (0..10)
+            .map(|_| sampler.transform_one(Event::from("message")).is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            passed,
+            vec![true, false, true, false, true, false, true, false, true, false]
+        );
+    }
+
+    #[test]
+    fn requires_either_rate_or_ratio() {
+        let sample_rate = |config: &str| {
+            toml::from_str::<SampleConfig>(config)
+                .unwrap()
+                .sample_rate()
+        };
+
+        assert_eq!(sample_rate("rate = 4").unwrap(), SampleRate::OneIn(4));
+        assert_eq!(
+            sample_rate("ratio = 0.25").unwrap(),
+            SampleRate::Ratio(0.25)
+        );
+        assert!(sample_rate("").is_err());
+        assert!(sample_rate("rate = 4\nratio = 0.25").is_err());
+        assert!(sample_rate("rate = 0").is_err());
+        assert!(sample_rate("ratio = 1.5").is_err());
+    }
+
+    #[test]
+    fn excludes_events_matching_remap_condition() {
+        let config = toml::from_str::<SampleConfig>(
+            r#"
+            rate = 1000000
+            exclude = '.status == "error"'"#,
+        )
+        .unwrap();
+        let mut sampler = Sample::with_sample_rate(
+            config.sample_rate().unwrap(),
+            None,
+            config.exclude.map(|exclude| exclude.build().unwrap()),
+        );
+
+        // The first event is counted in, so start with another one.
+        sampler.transform_one(Event::from("message"));
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("status", "error");
+        assert!(sampler.transform_one(event).is_some());
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("status", "ok");
+        assert!(sampler.transform_one(event).is_none());
+    }
+
+    #[test]
+    fn excludes_events_matching_legacy_check_fields() {
+        let config = toml::from_str::<SampleConfig>(
+            r#"
+            rate = 1000000
+            exclude."status.eq" = "error""#,
+        )
+        .unwrap();
+        assert!(matches!(
+            config.exclude,
+            Some(SampleExclude::CheckFields(_))
+        ));
+        let mut sampler = Sample::with_sample_rate(
+            config.sample_rate().unwrap(),
+            None,
+            config.exclude.map(|exclude| exclude.build().unwrap()),
+        );
+
+        sampler.transform_one(Event::from("message"));
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("status", "error");
+        assert!(sampler.transform_one(event).is_some());
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("status", "ok");
+        assert!(sampler.transform_one(event).is_none());
+    }
+
     #[test]
     fn hash_consistently_samples_the_same_events() {
         let events = random_events(1000);
@@ -0,0 +1,325 @@
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription,
+    },
+    event::{Event, Value},
+    internal_events::{TimeEnricherFieldMissing, TimeEnricherInvalidTimestamp},
+    transforms::{FunctionTransform, Transform},
+};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use shared::TimeZone;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct TimeEnricherConfig {
+    pub field: Option<String>,
+    pub target: Option<String>,
+    pub timezone: Option<TimeZone>,
+    pub business_hours: Option<BusinessHoursConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BusinessHoursConfig {
+    #[serde(default = "default_start_hour")]
+    pub start_hour: u32,
+    #[serde(default = "default_end_hour")]
+    pub end_hour: u32,
+    #[serde(default = "default_days")]
+    pub days: Vec<Weekday>,
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+}
+
+fn default_start_hour() -> u32 {
+    9
+}
+
+fn default_end_hour() -> u32 {
+    17
+}
+
+fn default_days() -> Vec<Weekday> {
+    vec![
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+    ]
+}
+
+impl Default for BusinessHoursConfig {
+    fn default() -> Self {
+        Self {
+            start_hour: default_start_hour(),
+            end_hour: default_end_hour(),
+            days: default_days(),
+            holidays: Vec::new(),
+        }
+    }
+}
+
+inventory::submit! {
+    TransformDescription::new::<TimeEnricherConfig>("time_enricher")
+}
+
+impl GenerateConfig for TimeEnricherConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            field: None,
+            target: Some("time".to_string()),
+            timezone: None,
+            business_hours: Some(BusinessHoursConfig::default()),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "time_enricher")]
+impl TransformConfig for TimeEnricherConfig {
+    async fn build(&self, globals: &GlobalOptions) -> crate::Result<Transform> {
+        if let Some(business_hours) = &self.business_hours {
+            if business_hours.start_hour > 24 || business_hours.end_hour > 24 {
+                return Err("`business_hours` hours must be between 0 and 24".into());
+            }
+            if business_hours.start_hour >= business_hours.end_hour {
+                return Err("`business_hours.start_hour` must be before `end_hour`".into());
+            }
+        }
+
+        Ok(Transform::function(TimeEnricher::new(
+            self.field
+                .clone()
+                .unwrap_or_else(|| log_schema().timestamp_key().to_string()),
+            self.target.clone().unwrap_or_else(|| "time".to_string()),
+            self.timezone.unwrap_or(globals.timezone),
+            self.business_hours.clone(),
+        )))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "time_enricher"
+    }
+}
+
+/// Calendar fields derived from a timestamp, already shifted into the
+/// configured time zone.
+#[derive(Debug)]
+struct DateParts {
+    date: NaiveDate,
+    weekday: Weekday,
+    hour: u32,
+    minute: u32,
+}
+
+impl DateParts {
+    fn new<TZ: chrono::TimeZone>(ts: DateTime<TZ>) -> Self {
+        Self {
+            date: ts.naive_local().date(),
+            weekday: ts.weekday(),
+            hour: ts.hour(),
+            minute: ts.minute(),
+        }
+    }
+
+    fn from_utc(ts: DateTime<Utc>, timezone: TimeZone) -> Self {
+        match timezone {
+            TimeZone::Local => Self::new(ts.with_timezone(&Local)),
+            TimeZone::Named(tz) => Self::new(ts.with_timezone(&tz)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TimeEnricher {
+    field: String,
+    target: String,
+    timezone: TimeZone,
+    business_hours: Option<BusinessHoursConfig>,
+}
+
+impl TimeEnricher {
+    pub fn new(
+        field: String,
+        target: String,
+        timezone: TimeZone,
+        business_hours: Option<BusinessHoursConfig>,
+    ) -> Self {
+        Self {
+            field,
+            target,
+            timezone,
+            business_hours,
+        }
+    }
+
+    fn is_business_hours(&self, business_hours: &BusinessHoursConfig, parts: &DateParts) -> bool {
+        business_hours.days.contains(&parts.weekday)
+            && !business_hours.holidays.contains(&parts.date)
+            && parts.hour >= business_hours.start_hour
+            && parts.hour < business_hours.end_hour
+    }
+}
+
+impl FunctionTransform for TimeEnricher {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        let log = event.as_mut_log();
+
+        let timestamp = match log.get(&self.field) {
+            Some(Value::Timestamp(ts)) => *ts,
+            Some(_) => {
+                emit!(TimeEnricherInvalidTimestamp { field: &self.field });
+                output.push(event);
+                return;
+            }
+            None => {
+                emit!(TimeEnricherFieldMissing { field: &self.field });
+                output.push(event);
+                return;
+            }
+        };
+
+        let parts = DateParts::from_utc(timestamp, self.timezone);
+        let prefix = &self.target;
+
+        log.insert(format!("{}.year", prefix), parts.date.year());
+        log.insert(format!("{}.month", prefix), parts.date.month() as i64);
+        log.insert(format!("{}.day", prefix), parts.date.day() as i64);
+        log.insert(
+            format!("{}.day_of_year", prefix),
+            parts.date.ordinal() as i64,
+        );
+        log.insert(
+            format!("{}.week", prefix),
+            parts.date.iso_week().week() as i64,
+        );
+        log.insert(format!("{}.hour", prefix), parts.hour as i64);
+        log.insert(format!("{}.minute", prefix), parts.minute as i64);
+        log.insert(format!("{}.weekday", prefix), weekday_name(parts.weekday));
+
+        if let Some(business_hours) = &self.business_hours {
+            let is_business_hours = self.is_business_hours(business_hours, &parts);
+            log.insert(format!("{}.is_business_hours", prefix), is_business_hours);
+        }
+
+        output.push(event);
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+    use chrono::TimeZone as _;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TimeEnricherConfig>();
+    }
+
+    fn enrich(
+        timestamp: Value,
+        timezone: TimeZone,
+        business_hours: Option<BusinessHoursConfig>,
+    ) -> LogEvent {
+        let mut event = Event::from("message");
+        event
+            .as_mut_log()
+            .insert(log_schema().timestamp_key(), timestamp);
+
+        let mut transform = TimeEnricher::new(
+            log_schema().timestamp_key().to_string(),
+            "time".into(),
+            timezone,
+            business_hours,
+        );
+
+        transform.transform_one(event).unwrap().into_log()
+    }
+
+    fn named(tz: &str) -> TimeZone {
+        TimeZone::parse(tz).unwrap()
+    }
+
+    #[test]
+    fn time_enricher_date_parts() {
+        // Friday, 2021-01-01 23:30:00 UTC
+        let ts = Utc.ymd(2021, 1, 1).and_hms(23, 30, 0);
+        let log = enrich(ts.into(), named("UTC"), None);
+
+        assert_eq!(log["time.year"], 2021.into());
+        assert_eq!(log["time.month"], 1.into());
+        assert_eq!(log["time.day"], 1.into());
+        assert_eq!(log["time.day_of_year"], 1.into());
+        assert_eq!(log["time.week"], 53.into());
+        assert_eq!(log["time.hour"], 23.into());
+        assert_eq!(log["time.minute"], 30.into());
+        assert_eq!(log["time.weekday"], "friday".into());
+        assert!(log.get("time.is_business_hours").is_none());
+    }
+
+    #[test]
+    fn time_enricher_applies_timezone() {
+        // 2021-01-01 23:30:00 UTC is already Saturday in Tokyo.
+        let ts = Utc.ymd(2021, 1, 1).and_hms(23, 30, 0);
+        let log = enrich(ts.into(), named("Asia/Tokyo"), None);
+
+        assert_eq!(log["time.day"], 2.into());
+        assert_eq!(log["time.hour"], 8.into());
+        assert_eq!(log["time.weekday"], "saturday".into());
+    }
+
+    #[test]
+    fn time_enricher_business_hours() {
+        let business_hours = BusinessHoursConfig {
+            holidays: vec![NaiveDate::from_ymd(2021, 1, 1)],
+            ..Default::default()
+        };
+
+        let cases = vec![
+            // Monday morning, inside business hours.
+            (Utc.ymd(2021, 1, 4).and_hms(9, 0, 0), true),
+            // Monday evening, `end_hour` is exclusive.
+            (Utc.ymd(2021, 1, 4).and_hms(17, 0, 0), false),
+            // Saturday.
+            (Utc.ymd(2021, 1, 2).and_hms(12, 0, 0), false),
+            // Friday, but a holiday.
+            (Utc.ymd(2021, 1, 1).and_hms(12, 0, 0), false),
+        ];
+
+        for (ts, expected) in cases {
+            let log = enrich(ts.into(), named("UTC"), Some(business_hours.clone()));
+            assert_eq!(log["time.is_business_hours"], expected.into(), "{}", ts);
+        }
+    }
+
+    #[test]
+    fn time_enricher_skips_non_timestamp() {
+        let log = enrich("not a timestamp".into(), named("UTC"), None);
+
+        assert!(log.get("time").is_none());
+    }
+}
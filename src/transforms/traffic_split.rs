@@ -0,0 +1,333 @@
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    event::Event,
+    internal_events::TrafficSplitEventDiscarded,
+    transforms::{FunctionTransform, Transform},
+};
+use indexmap::IndexMap;
+use seahash::SeaHasher;
+use serde::{Deserialize, Serialize};
+use std::hash::Hasher;
+
+/// Percentages are resolved to this many buckets, so up to four decimal places
+/// are honoured.
+const BUCKETS: u64 = 1_000_000;
+
+//------------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SplitOutputConfig {
+    key_field: Option<String>,
+    /// The buckets in `[start, end)` are sent to this output.
+    start: u64,
+    end: u64,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "traffic_split_output")]
+impl TransformConfig for SplitOutputConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        Ok(Transform::function(SplitOutput::new(
+            self.key_field.clone(),
+            self.start,
+            self.end,
+        )))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "traffic_split_output"
+    }
+}
+
+/// Keeps the events that fall into its share of the buckets.
+///
+/// The bucket of an event only depends on the event itself, so all the outputs
+/// of a split agree on it and exactly one of them keeps the event, whatever
+/// events each of them saw before.
+#[derive(Clone, Debug)]
+pub struct SplitOutput {
+    key_field: Option<String>,
+    start: u64,
+    end: u64,
+}
+
+impl SplitOutput {
+    pub fn new(key_field: Option<String>, start: u64, end: u64) -> Self {
+        Self {
+            key_field,
+            start,
+            end,
+        }
+    }
+
+    fn bucket(&self, event: &Event) -> u64 {
+        let log = event.as_log();
+        let value = self
+            .key_field
+            .as_ref()
+            .and_then(|key_field| log.get(key_field))
+            .map(|v| v.to_string_lossy());
+
+        let hash = if let Some(value) = value {
+            seahash::hash(value.as_bytes())
+        } else {
+            // Without a key, the whole event is hashed, which spreads distinct
+            // events across outputs.
+            let mut hasher = SeaHasher::new();
+            for (key, value) in log.as_map() {
+                hasher.write(key.as_bytes());
+                hasher.write(&value.as_bytes());
+            }
+            hasher.finish()
+        };
+
+        hash % BUCKETS
+    }
+}
+
+impl FunctionTransform for SplitOutput {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        let bucket = self.bucket(&event);
+        if self.start <= bucket && bucket < self.end {
+            output.push(event);
+        } else {
+            emit!(TrafficSplitEventDiscarded);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TrafficSplitConfig {
+    key_field: Option<String>,
+    outputs: IndexMap<String, f64>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<TrafficSplitConfig>("traffic_split")
+}
+
+impl GenerateConfig for TrafficSplitConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            key_field: None,
+            outputs: vec![("stable".to_owned(), 95.0), ("canary".to_owned(), 5.0)]
+                .into_iter()
+                .collect(),
+        })
+        .unwrap()
+    }
+}
+
+impl TrafficSplitConfig {
+    /// Resolves the percentage of each output to its range of buckets.
+    fn ranges(&self) -> crate::Result<Vec<(String, u64, u64)>> {
+        if self.outputs.is_empty() {
+            return Err("must specify at least one output".into());
+        }
+
+        let mut total = 0.0;
+        for (name, percentage) in &self.outputs {
+            if !(0.0..=100.0).contains(percentage) {
+                return Err(format!(
+                    "percentage of output {:?} must be between 0 and 100, got {}",
+                    name, percentage
+                )
+                .into());
+            }
+            total += percentage;
+        }
+        if (total - 100.0).abs() > 1e-6 {
+            return Err(format!("output percentages must add up to 100, got {}", total).into());
+        }
+
+        let mut cumulative = 0.0;
+        let mut start = 0;
+        let ranges = self
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(i, (name, percentage))| {
+                cumulative += percentage;
+                // The last output takes any buckets left over from rounding.
+                let end = if i == self.outputs.len() - 1 {
+                    BUCKETS
+                } else {
+                    ((cumulative / 100.0) * BUCKETS as f64).round() as u64
+                };
+                let range = (name.clone(), start, end);
+                start = end;
+                range
+            })
+            .collect();
+
+        Ok(ranges)
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "traffic_split")]
+impl TransformConfig for TrafficSplitConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        Err("this transform must be expanded".into())
+    }
+
+    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
+        let map = self
+            .ranges()?
+            .into_iter()
+            .map(|(name, start, end)| {
+                let output: Box<dyn TransformConfig> = Box::new(SplitOutputConfig {
+                    key_field: self.key_field.clone(),
+                    start,
+                    end,
+                });
+                (name, output)
+            })
+            .collect();
+
+        Ok(Some(map))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "traffic_split"
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::log_schema, test_util::random_lines};
+
+    fn config(key_field: Option<&str>, outputs: &[(&str, f64)]) -> TrafficSplitConfig {
+        TrafficSplitConfig {
+            key_field: key_field.map(Into::into),
+            outputs: outputs
+                .iter()
+                .map(|(name, percentage)| (name.to_string(), *percentage))
+                .collect(),
+        }
+    }
+
+    fn outputs(config: &TrafficSplitConfig) -> Vec<(String, SplitOutput)> {
+        config
+            .ranges()
+            .unwrap()
+            .into_iter()
+            .map(|(name, start, end)| {
+                let output = SplitOutput::new(config.key_field.clone(), start, end);
+                (name, output)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TrafficSplitConfig>();
+    }
+
+    #[test]
+    fn rejects_percentages_not_adding_up() {
+        assert!(config(None, &[("a", 50.0), ("b", 40.0)]).ranges().is_err());
+        assert!(config(None, &[("a", 150.0), ("b", -50.0)])
+            .ranges()
+            .is_err());
+        assert!(config(None, &[]).ranges().is_err());
+    }
+
+    #[test]
+    fn each_event_goes_to_exactly_one_output() {
+        let config = config(None, &[("stable", 95.0), ("canary", 5.0)]);
+        let mut outputs = outputs(&config);
+
+        let num_events = 10000;
+        let mut counts = vec![0; outputs.len()];
+        for line in random_lines(10).take(num_events) {
+            let event = Event::from(line);
+            let passed = outputs
+                .iter_mut()
+                .map(|(_, output)| output.transform_one(event.clone()).is_some())
+                .collect::<Vec<_>>();
+            assert_eq!(passed.iter().filter(|passed| **passed).count(), 1);
+
+            for (count, passed) in counts.iter_mut().zip(passed) {
+                *count += passed as usize;
+            }
+        }
+
+        let canary = counts[1] as f64 / num_events as f64;
+        assert!((0.03..0.07).contains(&canary), "canary got {}", canary);
+    }
+
+    #[test]
+    fn outputs_agree_whatever_events_they_saw_before() {
+        let config = config(None, &[("a", 50.0), ("b", 50.0)]);
+        let mut outputs = outputs(&config);
+
+        // An output may see events the others don't, as when it's reloaded.
+        for line in random_lines(10).take(7) {
+            outputs[0].1.transform_one(Event::from(line));
+        }
+
+        for line in random_lines(10).take(100) {
+            let event = Event::from(line);
+            let passed = outputs
+                .iter_mut()
+                .map(|(_, output)| output.transform_one(event.clone()).is_some())
+                .filter(|passed| *passed)
+                .count();
+            assert_eq!(passed, 1);
+        }
+    }
+
+    #[test]
+    fn same_key_goes_to_same_output() {
+        let key_field = log_schema().message_key();
+        let config = config(Some(key_field), &[("a", 50.0), ("b", 50.0)]);
+
+        for line in random_lines(10).take(100) {
+            let event = Event::from(line);
+            let mut outputs = outputs(&config);
+            let first = outputs
+                .iter_mut()
+                .position(|(_, output)| output.transform_one(event.clone()).is_some());
+            // Earlier events don't change where a keyed event goes.
+            let again = outputs
+                .iter_mut()
+                .position(|(_, output)| output.transform_one(event.clone()).is_some());
+            assert!(first.is_some());
+            assert_eq!(first, again);
+        }
+    }
+
+    #[test]
+    fn expands_to_named_outputs() {
+        let mut config = config(None, &[("stable", 90.0), ("canary", 10.0)]);
+        let expanded = config.expand().unwrap().unwrap();
+        assert_eq!(
+            expanded.keys().collect::<Vec<_>>(),
+            vec!["stable", "canary"]
+        );
+    }
+}
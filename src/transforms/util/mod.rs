@@ -1,2 +1,6 @@
+pub mod rejected;
 #[cfg(any(feature = "transforms-lua"))]
 pub mod runtime_transform;
+
+/// Name of the output of events that match none of the conditions of a route.
+pub const UNMATCHED_OUTPUT: &str = "_unmatched";
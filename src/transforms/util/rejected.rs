@@ -0,0 +1,290 @@
+//! Parsers can send the events they fail to parse to a separate `<name>.rejected`
+//! output, rather than each deciding whether to drop or pass them on, so that
+//! failures can be handled explicitly in the topology.
+//!
+//! A parser whose rejected output is used expands into three transforms: a stage
+//! that parses every event once and marks those it failed to parse in their metadata,
+//! the parser's own name, which keeps the parsed events, and its `rejected` output,
+//! which keeps the original events it failed to parse, with the reason in a
+//! configurable field.
+//! A parser whose rejected output isn't used isn't expanded, and passes on the
+//! events it fails to parse, or drops them.
+
+use crate::{
+    config::TransformConfig,
+    event::Event,
+    transforms::{FunctionTransform, Transform},
+};
+use indexmap::IndexMap;
+
+/// Name of the output of events that failed to parse.
+pub const REJECTED_OUTPUT: &str = "rejected";
+
+/// Name of the stage parsing the events of an expanded parser.
+const PARSE_STAGE: &str = "_parse";
+
+/// Default field of a rejected event holding the reason it was rejected.
+pub const DEFAULT_ERROR_FIELD: &str = "error";
+
+/// The part a parser config plays once expanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserOutput {
+    /// The parser isn't expanded, and passes on or drops the events it fails to parse.
+    Unrouted,
+    /// Parses the events for the outputs, marking those that failed to parse.
+    Parse,
+    Parsed,
+    Rejected,
+}
+
+impl Default for ParserOutput {
+    fn default() -> Self {
+        ParserOutput::Unrouted
+    }
+}
+
+pub trait Parser: Send + Sync + Clone {
+    /// Parses the event in place. On failure, the event must be left as it was.
+    fn parse(&mut self, event: &mut Event) -> Result<(), String>;
+}
+
+/// Parses events, and either marks those it fails to parse for the outputs, or passes them
+/// on or drops them when the parser isn't expanded.
+#[derive(Debug, Clone)]
+pub struct ParserTransform<P> {
+    parser: P,
+    mark_failed: bool,
+    drop_failed: bool,
+}
+
+impl<P: Parser + 'static> ParserTransform<P> {
+    /// A parser that isn't expanded.
+    pub fn new(parser: P, drop_failed: bool) -> Self {
+        Self {
+            parser,
+            mark_failed: false,
+            drop_failed,
+        }
+    }
+
+    /// The parse stage of an expanded parser.
+    pub fn marking(parser: P) -> Self {
+        Self {
+            parser,
+            mark_failed: true,
+            drop_failed: false,
+        }
+    }
+}
+
+impl<P: Parser> FunctionTransform for ParserTransform<P> {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        match self.parser.parse(&mut event) {
+            Ok(()) => output.push(event),
+            Err(error) if self.mark_failed => {
+                event.as_mut_log().metadata_mut().set_rejection(error);
+                output.push(event);
+            }
+            Err(_) if self.drop_failed => {}
+            Err(_) => output.push(event),
+        }
+    }
+}
+
+/// Keeps the events of one of the outputs of an expanded parser.
+#[derive(Debug, Clone)]
+pub struct OutputFilter {
+    output: ParserOutput,
+    error_field: String,
+}
+
+impl OutputFilter {
+    pub fn new(output: ParserOutput, error_field: Option<&str>) -> Self {
+        Self {
+            output,
+            error_field: error_field.unwrap_or(DEFAULT_ERROR_FIELD).to_owned(),
+        }
+    }
+}
+
+impl FunctionTransform for OutputFilter {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        let rejection = event.as_mut_log().metadata_mut().take_rejection();
+        match (rejection, self.output) {
+            (None, ParserOutput::Parsed) => output.push(event),
+            (Some(error), ParserOutput::Rejected) => {
+                event.as_mut_log().insert(self.error_field.as_str(), error);
+                output.push(event);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Builds the transform of a parser config for its part, only building the parser itself
+/// when the part parses events.
+pub fn build<P: Parser + 'static>(
+    output: ParserOutput,
+    error_field: Option<&str>,
+    drop_failed: bool,
+    parser: impl FnOnce() -> crate::Result<P>,
+) -> crate::Result<Transform> {
+    Ok(match output {
+        ParserOutput::Unrouted => Transform::function(ParserTransform::new(parser()?, drop_failed)),
+        ParserOutput::Parse => Transform::function(ParserTransform::marking(parser()?)),
+        ParserOutput::Parsed | ParserOutput::Rejected => {
+            Transform::function(OutputFilter::new(output, error_field))
+        }
+    })
+}
+
+/// The sibling whose output the outputs of an expanded parser take as their input.
+pub fn expanded_input(output: ParserOutput) -> Option<&'static str> {
+    match output {
+        ParserOutput::Parsed | ParserOutput::Rejected => Some(PARSE_STAGE),
+        ParserOutput::Unrouted | ParserOutput::Parse => None,
+    }
+}
+
+/// Expands a parser config into its parse stage, its parsed output, which keeps the
+/// parser's name, and its rejected output.
+pub fn expand<T>(
+    config: &T,
+    with_output: impl Fn(T, ParserOutput) -> T,
+) -> IndexMap<String, Box<dyn TransformConfig>>
+where
+    T: TransformConfig + Clone + 'static,
+{
+    let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
+    map.insert(
+        PARSE_STAGE.to_owned(),
+        Box::new(with_output(config.clone(), ParserOutput::Parse)),
+    );
+    map.insert(
+        String::new(),
+        Box::new(with_output(config.clone(), ParserOutput::Parsed)),
+    );
+    map.insert(
+        REJECTED_OUTPUT.to_owned(),
+        Box::new(with_output(config.clone(), ParserOutput::Rejected)),
+    );
+    map
+}
+
+/// Runs an event through the parse stage and one of the outputs of an expanded parser.
+#[cfg(test)]
+#[derive(Clone)]
+pub struct RoutedOutput {
+    stage: Box<dyn FunctionTransform>,
+    filter: Box<dyn FunctionTransform>,
+}
+
+#[cfg(test)]
+impl RoutedOutput {
+    pub fn new(stage: impl FunctionTransform + 'static, output: ParserOutput) -> Self {
+        Self::from_transforms(
+            Transform::function(stage),
+            Transform::function(OutputFilter::new(output, None)),
+        )
+    }
+
+    /// Combines the transforms built from the configs of a parse stage and an output.
+    pub fn from_transforms(stage: Transform, filter: Transform) -> Self {
+        Self {
+            stage: stage.into_function(),
+            filter: filter.into_function(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl FunctionTransform for RoutedOutput {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        let mut staged = Vec::new();
+        self.stage.transform(&mut staged, event);
+        for event in staged {
+            self.filter.transform(output, event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{EventMetadata, Value};
+
+    #[derive(Clone)]
+    struct Uppercase;
+
+    impl Parser for Uppercase {
+        fn parse(&mut self, event: &mut Event) -> Result<(), String> {
+            let log = event.as_mut_log();
+            let message = log
+                .get("message")
+                .map(|value| value.to_string_lossy())
+                .ok_or_else(|| "missing message".to_owned())?;
+            if message.starts_with('!') {
+                return Err("unparseable".to_owned());
+            }
+            log.insert("message", message.to_uppercase());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn outputs_split_parsed_and_rejected() {
+        let stage = ParserTransform::marking(Uppercase);
+        let mut parsed = RoutedOutput::new(stage.clone(), ParserOutput::Parsed);
+        let mut rejected = RoutedOutput::new(stage, ParserOutput::Rejected);
+
+        let event = Event::from("hello");
+        let result = parsed.transform_one(event.clone()).unwrap();
+        assert_eq!(result.as_log()["message"], Value::from("HELLO"));
+        assert!(rejected.transform_one(event).is_none());
+
+        let event = Event::from("!hello");
+        assert!(parsed.transform_one(event.clone()).is_none());
+        let result = rejected.transform_one(event).unwrap();
+        assert_eq!(result.as_log()["message"], Value::from("!hello"));
+        assert_eq!(
+            result.as_log()[DEFAULT_ERROR_FIELD],
+            Value::from("unparseable")
+        );
+        assert_eq!(*result.as_log().metadata(), EventMetadata::default());
+    }
+
+    #[test]
+    fn events_are_only_rejected_by_the_parser() {
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("_rejection", "none");
+
+        let stage = ParserTransform::marking(Uppercase);
+        let mut parsed = RoutedOutput::new(stage.clone(), ParserOutput::Parsed);
+        let mut rejected = RoutedOutput::new(stage, ParserOutput::Rejected);
+        let result = parsed.transform_one(event.clone()).unwrap();
+        assert_eq!(result.as_log()["_rejection"], Value::from("none"));
+        assert!(rejected.transform_one(event).is_none());
+    }
+
+    #[test]
+    fn parsed_events_with_an_error_field_are_not_rejected() {
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert(DEFAULT_ERROR_FIELD, "none");
+
+        let mut parsed =
+            RoutedOutput::new(ParserTransform::marking(Uppercase), ParserOutput::Parsed);
+        let result = parsed.transform_one(event).unwrap();
+        assert_eq!(result.as_log()[DEFAULT_ERROR_FIELD], Value::from("none"));
+    }
+
+    #[test]
+    fn unrouted_parsers_pass_on_or_drop_failed_events() {
+        let event = Event::from("!hello");
+
+        let mut passing = ParserTransform::new(Uppercase, false);
+        assert_eq!(passing.transform_one(event.clone()), Some(event.clone()));
+
+        let mut dropping = ParserTransform::new(Uppercase, true);
+        assert!(dropping.transform_one(event).is_none());
+    }
+}
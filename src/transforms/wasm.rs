@@ -40,7 +40,7 @@ impl Into<WasmModuleConfig> for WasmConfig {
             self.module,
             self.artifact_cache,
             self.options,
-            defaults::HEAP_MEMORY_SIZE,
+            self.heap_memory_size,
         )
     }
 }
@@ -123,12 +123,26 @@ mod tests {
     use futures::{stream, StreamExt};
     use serde_json::Value;
     use std::{collections::HashMap, fs, io::Read, path::Path};
+    use vector_wasm::WasmModuleConfig;
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<WasmConfig>();
     }
 
+    #[test]
+    fn passes_heap_memory_size() {
+        let config = toml::from_str::<WasmConfig>(
+            r#"
+            module = "module.wasm"
+            artifact_cache = "cache"
+            heap_memory_size = 1048576"#,
+        )
+        .unwrap();
+        let config: WasmModuleConfig = config.into();
+        assert_eq!(config.max_heap_memory_size, 1048576);
+    }
+
     fn parse_config(s: &str) -> crate::Result<Box<Wasm>> {
         Wasm::new(toml::from_str(s).unwrap()).map(Box::new)
     }
@@ -1,4 +1,4 @@
-use crate::{stats, Event};
+use crate::{stats, topology::health, Event};
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use std::time::{Duration, Instant};
@@ -9,8 +9,8 @@ use std::time::{Duration, Instant};
 /// component is idle and the rest of the time it is doing useful work. This is more true for sinks
 /// than transforms, which can be blocked by downstream components, but with knowledge of the
 /// config the data is still useful.
-pub fn wrap(inner: impl Stream<Item = Event>) -> impl Stream<Item = Event> {
-    let mut timer = Timer::new();
+pub fn wrap(name: String, inner: impl Stream<Item = Event>) -> impl Stream<Item = Event> {
+    let mut timer = Timer::new(name);
     let mut interval = tokio::time::interval(Duration::from_secs(5));
     stream! {
         tokio::pin!(inner);
@@ -36,6 +36,7 @@ pub fn wrap(inner: impl Stream<Item = Event>) -> impl Stream<Item = Event> {
 }
 
 struct Timer {
+    name: String,
     overall_start: Instant,
     span_start: Instant,
     waiting: bool,
@@ -51,8 +52,9 @@ struct Timer {
 /// Reporting periods are expected to be of uniform length and used to aggregate span data into
 /// time-weighted averages.
 impl Timer {
-    fn new() -> Self {
+    fn new(name: String) -> Self {
         Self {
+            name,
             overall_start: Instant::now(),
             span_start: Instant::now(),
             waiting: false,
@@ -87,7 +89,11 @@ impl Timer {
         let utilization = 1.0 - wait_ratio;
 
         self.ewma.update(utilization);
-        debug!(utilization = %self.ewma.average().unwrap_or(f64::NAN));
+        let average = self.ewma.average();
+        debug!(utilization = %average.unwrap_or(f64::NAN));
+        if let Some(average) = average {
+            health::record_utilization(&self.name, average);
+        }
 
         // Reset overall statistics for the next reporting period.
         self.overall_start = self.span_start;
@@ -1,11 +1,11 @@
 use crate::{
-    config::{self, Config, ConfigDiff},
+    config::{self, Config, ConfigDiff, Protocol, Resource},
     topology::{self, builder::Pieces},
 };
 use colored::*;
 use exitcode::ExitCode;
 use std::collections::HashMap;
-use std::{fmt, fs::remove_dir_all, path::PathBuf};
+use std::{fmt, fs::remove_dir_all, io, path::PathBuf};
 use structopt::StructOpt;
 
 const TEMPORARY_DIRECTORY: &str = "validate_tmp";
@@ -13,7 +13,8 @@ const TEMPORARY_DIRECTORY: &str = "validate_tmp";
 #[derive(StructOpt, Debug)]
 #[structopt(rename_all = "kebab-case")]
 pub struct Opts {
-    /// Disables environment checks. That includes component checks and health checks.
+    /// Disables environment checks. That includes component checks, checks that
+    /// the addresses components listen on are free, and health checks.
     #[structopt(long)]
     no_environment: bool,
 
@@ -34,6 +35,11 @@ pub struct Opts {
     #[structopt(name = "config-yaml", long)]
     paths_yaml: Vec<PathBuf>,
 
+    /// Directories of Vector config files to validate. Format is detected from
+    /// the file name.
+    #[structopt(name = "config-dir", short = "C", long)]
+    config_dirs: Vec<PathBuf>,
+
     /// Any number of Vector config files to validate.
     /// Format is detected from the file name.
     /// If none are specified the default config path `/etc/vector/vector.toml`
@@ -48,6 +54,7 @@ impl Opts {
             (&self.paths_toml, Some(config::Format::TOML)),
             (&self.paths_json, Some(config::Format::JSON)),
             (&self.paths_yaml, Some(config::Format::YAML)),
+            (&self.config_dirs, None),
         ])
     }
 }
@@ -138,7 +145,58 @@ async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter)
         return false;
     };
 
-    validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await
+    let addresses_free = validate_addresses(opts, config, fmt);
+
+    validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await && addresses_free
+}
+
+/// Checks that the addresses components listen on can be bound. Addresses
+/// already in use are only warned about, as they may be used by the Vector
+/// instance the config is validated for.
+fn validate_addresses(opts: &Opts, config: &Config, fmt: &mut Formatter) -> bool {
+    let resources = config
+        .sources
+        .iter()
+        .map(|(name, source)| (name, source.inner.resources()))
+        .chain(
+            config
+                .sinks
+                .iter()
+                .map(|(name, sink)| (name, sink.inner.resources())),
+        );
+
+    let mut validated = true;
+    for (name, resources) in resources {
+        for resource in resources {
+            if let Resource::Port(address, protocol) = resource {
+                let (bound, protocol) = match protocol {
+                    Protocol::Tcp => (std::net::TcpListener::bind(address).map(drop), "TCP"),
+                    Protocol::Udp => (std::net::UdpSocket::bind(address).map(drop), "UDP"),
+                };
+                match bound {
+                    Ok(()) => fmt.success(format!(
+                        "Address {} {} of `{}` is free",
+                        protocol, address, name
+                    )),
+                    Err(error) if error.kind() == io::ErrorKind::AddrInUse => {
+                        validated &= !opts.deny_warnings;
+                        fmt.warning(format!(
+                            "Address {} {} of `{}` is already in use",
+                            protocol, address, name
+                        ));
+                    }
+                    Err(error) => {
+                        validated = false;
+                        fmt.error(format!(
+                            "Address {} {} of `{}` can't be bound: {}",
+                            protocol, address, name, error
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    validated
 }
 
 async fn validate_components(
@@ -166,7 +224,7 @@ async fn validate_healthchecks(
     pieces: &mut Pieces,
     fmt: &mut Formatter,
 ) -> bool {
-    if config.healthchecks.enabled {
+    if !config.healthchecks.enabled {
         fmt.warning("Health checks are disabled");
         return !opts.deny_warnings;
     }
@@ -212,6 +212,44 @@ async fn nonexistant_input() {
     );
 }
 
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-lookup",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn unknown_enrichment_table() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [transforms.lookup]
+        type = "lookup"
+        inputs = ["in"]
+        table = "teams"
+        match_fields.user_id = "user.id"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["lookup"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#,
+        Some(Format::TOML),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec!["Transform \"lookup\" looks events up in unknown enrichment table \"teams\""]
+    );
+}
+
 #[cfg(all(
     feature = "sources-socket",
     feature = "transforms-sample",
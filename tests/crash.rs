@@ -20,7 +20,7 @@ use vector::{
     },
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct PanicSink;
 
 #[async_trait]
@@ -106,7 +106,7 @@ async fn test_sink_panic() {
     assert_eq!(input_lines, output_lines);
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct ErrorSink;
 
 #[async_trait]
@@ -282,7 +282,7 @@ impl TransformConfig for MockTransformConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MockSinkConfig<T>
 where
     T: Sink<Event> + Unpin + std::fmt::Debug + Clone + Send + Sync + 'static,
@@ -68,7 +68,8 @@ async fn sample() {
         "sample",
         &["in"],
         transforms::sample::SampleConfig {
-            rate: 10,
+            rate: Some(10),
+            ratio: None,
             key_field: Some(config::log_schema().message_key().into()),
             exclude: None,
         },